@@ -3,14 +3,49 @@ use std::collections::VecDeque;
 #[cfg(test)]
 mod tests;
 
+/// Redo entries which were replaced by a new change in a branching [UndoHistory]
+#[derive(Debug)]
+pub struct UndoBranch<T> {
+    fork_index: usize,
+    entries: Vec<T>,
+}
+
+impl<T> UndoBranch<T> {
+    /// Returns the index of the last history entry which is shared with the current history
+    pub fn fork_index(&self) -> usize {
+        self.fork_index
+    }
+
+    /// Returns the count of entries after the fork index
+    #[expect(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the newest entry of the branch
+    pub fn last(&self) -> &T {
+        self.entries.last().unwrap()
+    }
+}
+
 #[derive(Debug)]
 pub struct UndoHistory<T> {
     history: VecDeque<T>,
     current_index: usize,
+
+    branches: Vec<UndoBranch<T>>,
+    max_branch_count: usize,
 }
 
 impl<T> UndoHistory<T> {
     pub fn new(capacity: usize, initial: T) -> Self {
+        Self::new_branching(capacity, 0, initial)
+    }
+
+    /// Creates an undo history which keeps up to `max_branch_count` redo branches instead of discarding them on a new change
+    ///
+    /// Branches can be restored with [jump_to_branch](Self::jump_to_branch), the oldest branch is removed if there are too many branches
+    pub fn new_branching(capacity: usize, max_branch_count: usize, initial: T) -> Self {
         if capacity == 0 {
             panic!("Capacity must be > 0");
         }
@@ -21,6 +56,9 @@ impl<T> UndoHistory<T> {
         Self {
             history,
             current_index: 0,
+
+            branches: Vec::with_capacity(max_branch_count),
+            max_branch_count,
         }
     }
 
@@ -44,16 +82,22 @@ impl<T> UndoHistory<T> {
         self.history.get(self.current_index)
     }
 
-    pub fn commit_change(&mut self, value: T) {
-        self.history.truncate(self.current_index + 1);
-
-        if self.history.len() == self.history.capacity() {
-            self.history.pop_front();
-        }else {
-            self.current_index += 1;
+    /// Moves the current index to the provided index without changing the history
+    ///
+    /// None is returned if the index is out of bounds or already the current index
+    pub fn jump_to(&mut self, index: usize) -> Option<&T> {
+        if index == self.current_index || index >= self.history.len() {
+            return None;
         }
 
-        self.history.push_back(value);
+        self.current_index = index;
+
+        self.history.get(self.current_index)
+    }
+
+    /// Returns all stored branches (Oldest branch first)
+    pub fn branches(&self) -> &[UndoBranch<T>] {
+        &self.branches
     }
 
     pub fn current(&self) -> &T {
@@ -78,11 +122,89 @@ impl<T> UndoHistory<T> {
         self.history.swap_remove_back(0);
         self.history.truncate(1);
         self.current_index = 0;
+
+        self.branches.clear();
     }
 
     pub fn clear_with_new_initial(&mut self, initial_value: T) {
         self.history.clear();
         self.history.push_back(initial_value);
         self.current_index = 0;
+
+        self.branches.clear();
+    }
+
+    fn push_branch(&mut self, branch: UndoBranch<T>) {
+        if self.branches.len() == self.max_branch_count {
+            self.branches.remove(0);
+        }
+
+        self.branches.push(branch);
+    }
+}
+
+impl<T: Clone> UndoHistory<T> {
+    pub fn commit_change(&mut self, value: T) {
+        if self.max_branch_count > 0 && self.current_index + 1 < self.history.len() {
+            self.fork_branch(self.current_index);
+        }else {
+            self.history.truncate(self.current_index + 1);
+        }
+
+        if self.history.len() == self.history.capacity() {
+            self.history.pop_front();
+
+            //Branches which were forked from the removed entry can no longer be restored
+            self.branches.retain(|branch| branch.fork_index > 0);
+            for branch in self.branches.iter_mut() {
+                branch.fork_index -= 1;
+            }
+        }else {
+            self.current_index += 1;
+        }
+
+        self.history.push_back(value);
+    }
+
+    /// Replaces all entries after the fork index of the branch with the entries of the branch and moves the current index to
+    /// the newest entry of the branch
+    ///
+    /// The replaced entries are stored as a new branch. None is returned if the branch index is out of bounds
+    pub fn jump_to_branch(&mut self, branch_index: usize) -> Option<&T> {
+        if branch_index >= self.branches.len() {
+            return None;
+        }
+
+        let branch = self.branches.remove(branch_index);
+
+        if branch.fork_index + 1 < self.history.len() {
+            self.fork_branch(branch.fork_index);
+        }
+
+        self.history.extend(branch.entries);
+        self.current_index = self.history.len() - 1;
+
+        self.history.get(self.current_index)
+    }
+
+    /// Moves all entries after the fork index to a new branch
+    fn fork_branch(&mut self, fork_index: usize) {
+        let entries = self.history.drain(fork_index + 1..).collect::<Vec<_>>();
+
+        //Branches which were forked from the moved entries are now forked from the fork index
+        for branch in self.branches.iter_mut() {
+            if branch.fork_index > fork_index {
+                let mut branch_entries = entries[..branch.fork_index - fork_index].to_vec();
+                branch_entries.append(&mut branch.entries);
+
+                branch.fork_index = fork_index;
+                branch.entries = branch_entries;
+            }
+        }
+
+        self.push_branch(UndoBranch {
+            fork_index,
+            entries,
+        });
     }
 }
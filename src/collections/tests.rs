@@ -495,3 +495,78 @@ fn clear_with_new_initial() {
     assert_eq!(undo_history.history.capacity(), 5);
     assert_eq!(undo_history.current_index, 0);
 }
+
+#[test]
+fn jump_to() {
+    let mut undo_history = UndoHistory::new(5, 1);
+    undo_history.commit_change(2);
+    undo_history.commit_change(3);
+    undo_history.commit_change(4);
+
+    assert_eq!(undo_history.jump_to(0), Some(&1));
+    assert_eq!(undo_history.current(), &1);
+    assert_eq!(undo_history.history.len(), 4);
+    assert_eq!(undo_history.current_index, 0);
+
+    assert_eq!(undo_history.jump_to(0), None);
+    assert_eq!(undo_history.jump_to(4), None);
+    assert_eq!(undo_history.current_index, 0);
+
+    assert_eq!(undo_history.jump_to(2), Some(&3));
+    assert_eq!(undo_history.current(), &3);
+    assert_eq!(undo_history.history.len(), 4);
+    assert_eq!(undo_history.current_index, 2);
+
+    assert_eq!(undo_history.redo(), Some(&4));
+    assert_eq!(undo_history.redo(), None);
+}
+
+#[test]
+fn branching_history() {
+    let mut undo_history = UndoHistory::new_branching(10, 2, 1);
+    undo_history.commit_change(2);
+    undo_history.commit_change(3);
+    undo_history.commit_change(4);
+    assert!(undo_history.branches().is_empty());
+
+    undo_history.undo();
+    undo_history.undo();
+    undo_history.commit_change(5);
+    assert_eq!(undo_history.current(), &5);
+    assert_eq!(undo_history.history, [1, 2, 5]);
+    assert_eq!(undo_history.branches().len(), 1);
+    assert_eq!(undo_history.branches()[0].fork_index(), 1);
+    assert_eq!(undo_history.branches()[0].len(), 2);
+    assert_eq!(undo_history.branches()[0].last(), &4);
+
+    //Branch forked from an entry of the replaced entries
+    undo_history.undo();
+    undo_history.undo();
+    undo_history.commit_change(6);
+    assert_eq!(undo_history.history, [1, 6]);
+    assert_eq!(undo_history.branches().len(), 2);
+    assert_eq!(undo_history.branches()[0].fork_index(), 0);
+    assert_eq!(undo_history.branches()[0].entries, [2, 3, 4]);
+    assert_eq!(undo_history.branches()[1].fork_index(), 0);
+    assert_eq!(undo_history.branches()[1].entries, [2, 5]);
+
+    assert_eq!(undo_history.jump_to_branch(0), Some(&4));
+    assert_eq!(undo_history.history, [1, 2, 3, 4]);
+    assert_eq!(undo_history.current_index, 3);
+    assert_eq!(undo_history.branches().len(), 2);
+    assert_eq!(undo_history.branches()[0].entries, [2, 5]);
+    assert_eq!(undo_history.branches()[1].entries, [6]);
+
+    assert_eq!(undo_history.jump_to_branch(2), None);
+
+    //Oldest branch is removed
+    undo_history.undo();
+    undo_history.commit_change(7);
+    assert_eq!(undo_history.branches().len(), 2);
+    assert_eq!(undo_history.branches()[0].entries, [6]);
+    assert_eq!(undo_history.branches()[1].fork_index(), 2);
+    assert_eq!(undo_history.branches()[1].entries, [4]);
+
+    undo_history.clear();
+    assert!(undo_history.branches().is_empty());
+}
@@ -1,45 +1,134 @@
-use std::collections::HashMap;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use std::collections::{HashMap, VecDeque};
 use std::error::Error;
 use std::ffi::OsString;
 use std::fmt::{Debug, Display, Formatter};
+#[cfg(any(feature = "cli", feature = "gui"))]
 use std::mem;
+#[cfg(any(feature = "cli", feature = "gui"))]
 use std::fs::File;
-use std::io::{Read, Write};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use std::io::Read;
+#[cfg(any(feature = "cli", feature = "gui"))]
 use std::path::Path;
 use std::str::FromStr;
-use crate::game::audio::{AudioHandler, BackgroundMusic, BackgroundMusicId, SoundEffect};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use std::time::{Duration, Instant};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::achievements::{TotalStat, TotalStats, TotalStatsPlugin};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::archive::{LevelPackArchive, ARCHIVE_FILE_EXTENSION};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::audio::{AudioDeviceMonitor, AudioHandler, BackgroundMusic, BackgroundMusicId, PackSoundEffectId, PackSoundEffects, SoundEffect};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::console_extension::{ConsoleExtension, MouseButton, MouseInput};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::diagnostics::ErrorLog;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::events::{GameEventListener, LevelCompletedEvent, LevelStartedEvent};
+#[cfg(any(feature = "cli", feature = "gui"))]
 use crate::game::help_page::HelpPage;
-use crate::game::level::{Level, LevelPack, LevelSoundEffect};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::input::KeyInputQueue;
+#[cfg(feature = "cli")]
+use crate::game::input::BracketedPasteParser;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::level::{Level, LevelAttempt, LevelPack, LevelSoundEffect, PackStory, UnsupportedGameVersionError};
+use crate::game::logging::LogLevel;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::logging::{log_debug, log_error, log_info, log_warn};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::patch::{BuiltInLevelPack, LevelPackSource};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::save_writer::SaveWriter;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::settings_file::{SettingsFile, SettingsOption, SettingsValue, SETTINGS_FILE_VERSION};
+#[cfg(any(feature = "cli", feature = "gui"))]
 use crate::game::screen::*;
-use crate::game::screen::dialog::{DialogType, RenderedDialog, Dialog};
-use crate::io::{Console, Key};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::screen::dialog::{DialogQueue, DialogSelection, DialogType, Dialog};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::screen::hit_regions::HitRegions;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::snapshot::GameStateSnapshot;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::solver::{BackgroundValidator, DifficultyEstimateCache, LevelDifficulty, MinPushCountCache, Solvability};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::startup_flags::StartupFlags;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::plugins::{Plugin, PluginId, PluginRegistry};
+#[cfg(feature = "telemetry")]
+use crate::game::telemetry::TelemetryEventListener;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::recommend::{PlayerStats, PlayerStatsPlugin};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::io::{Color, Console, Key};
 
 #[cfg(feature = "gui")]
-use bevy::prelude::*;
+use crate::game::credits::CreditsEventListener;
 #[cfg(feature = "steam")]
 use bevy_steamworks::*;
+#[cfg(feature = "steam")]
+use crate::game::steam::achievement::AchievementEventListener;
+#[cfg(feature = "steam")]
+use crate::game::steam::rich_presence::{RichPresence, RichPresenceEventListener};
 
 pub mod level;
+pub mod achievements;
+pub mod archive;
+#[cfg(any(feature = "cli", feature = "gui"))]
 pub(crate) mod screen;
+#[cfg(any(feature = "cli", feature = "gui"))]
 mod help_page;
 pub mod audio;
+pub mod checksum;
 pub mod console_extension;
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub mod credits;
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub mod diagnostics;
+pub mod editor_recovery;
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub mod events;
+pub mod input;
+pub mod logging;
+pub mod patch;
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub mod plugins;
+pub mod profile;
+pub mod recommend;
+pub mod save_folder;
+pub mod save_writer;
+pub mod settings_file;
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub(crate) mod snapshot;
+pub mod solver;
+pub mod startup_flags;
+pub mod telemetry;
+pub mod trash;
+pub mod workshop;
 
 #[cfg(feature = "steam")]
 pub mod steam;
 
+#[cfg(any(feature = "cli", feature = "gui"))]
 pub struct EditorState {
     level_packs: Vec<LevelPack>,
     selected_level_pack_index: usize,
     selected_level_index: usize,
+
+    difficulty_estimate_cache: DifficultyEstimateCache,
 }
 
+#[cfg(any(feature = "cli", feature = "gui"))]
 impl EditorState {
     pub fn new(level_packs: Vec<LevelPack>) -> Self {
         Self {
             level_packs,
             selected_level_pack_index: Default::default(),
             selected_level_index: Default::default(),
+
+            difficulty_estimate_cache: DifficultyEstimateCache::new(),
         }
     }
 
@@ -82,6 +171,20 @@ impl EditorState {
                 and_then(|level_pack| level_pack.levels_mut().get_mut(self.selected_level_index)).
                 map(|level_with_stats| level_with_stats.level_mut())
     }
+
+    /// Returns the estimated difficulty of the level of the current level pack, it is estimated in the background on first use
+    pub fn level_difficulty(&self, level_index: usize) -> Option<LevelDifficulty> {
+        self.get_current_level_pack().
+                and_then(|level_pack| level_pack.levels().get(level_index)).
+                and_then(|level| self.difficulty_estimate_cache.difficulty(level.level()))
+    }
+
+    /// Returns the suggested difficulty of the current level pack (Levels which are not estimated yet are ignored)
+    pub fn level_pack_difficulty(&self) -> Option<LevelDifficulty> {
+        let level_count = self.get_current_level_pack()?.level_count();
+
+        solver::estimate_level_pack_difficulty((0..level_count).filter_map(|level_index| self.level_difficulty(level_index)))
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
@@ -155,6 +258,7 @@ impl AnimationSpeed {
         }
     }
 
+    #[cfg(any(feature = "cli", feature = "gui"))]
     #[must_use]
     fn next_setting(self) -> Self {
         match self {
@@ -187,594 +291,2268 @@ impl FromStr for AnimationSpeed {
     }
 }
 
-pub struct GameSettings {
-    color_scheme_index: usize,
-    tile_mode: TileMode,
-
-    background_music: bool,
-
-    animation_speed: AnimationSpeed,
+/// The action which is triggered by the right or the middle mouse button
+#[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum MouseButtonAction {
+    #[default]
+    Disabled,
+    /// The same as pressing ESC
+    Back,
+    /// Opens or closes the level preview in the level selection
+    TogglePreview,
 }
 
-impl GameSettings {
-    pub fn new() -> GameSettings {
-        Self {
-            color_scheme_index: 0,
-            tile_mode: TileMode::default(),
-
-            background_music: true,
-
-            animation_speed: AnimationSpeed::default(),
+impl MouseButtonAction {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            MouseButtonAction::Disabled => "Disabled",
+            MouseButtonAction::Back => "Back",
+            MouseButtonAction::TogglePreview => "Preview",
         }
     }
 
-    pub fn read_from_file() -> Result<Self, Box<dyn Error>> {
-        let mut settings_save_file = Game::get_or_create_save_game_folder()?;
-        settings_save_file.push("settings.data");
-
-        let mut settings = GameSettings::new();
-
-        if std::fs::exists(&settings_save_file)? {
-            let settings_data = std::fs::read_to_string(&settings_save_file)?;
-            for line in settings_data.split("\n").
-                    filter(|line| !line.trim().is_empty()) {
-                let mut tokens = line.splitn(2, " = ");
-
-                let key = tokens.next();
-                let value = tokens.next();
-
-                if let Some(key) = key && let Some(value) = value {
-                    match key {
-                        "color_scheme_index" => {
-                            let Ok(value) = usize::from_str(value) else {
-                                #[cfg(feature = "gui")]
-                                {
-                                    warn!("\"settings.data\" contains invalid value for option \"{key}\": \"{value}\": Using default");
-                                }
-
-                                //TODO warning in cli version
-
-                                continue;
-                            };
-
-                            #[cfg(feature = "gui")]
-                            {
-                                settings.color_scheme_index = value % crate::io::bevy_abstraction::COLOR_SCHEMES.len();
-                            }
-
-                            #[cfg(feature = "cli")]
-                            {
-                                //Not used in CLI build, but keep value as is for saving (CLI and GUI builds might both be played)
-                                settings.color_scheme_index = value;
-                            }
-                        },
-
-                        "tile_mode" => {
-                            let Ok(value) = TileMode::from_str(value) else {
-                                #[cfg(feature = "gui")]
-                                {
-                                    warn!("\"settings.data\" contains invalid value for option \"{key}\": \"{value}\": Using default");
-                                }
-
-                                //TODO warning in cli version
-
-                                continue;
-                            };
-
-                            settings.tile_mode = value;
-                        },
-
-                        "background_music" => {
-                            let Ok(value) = bool::from_str(value) else {
-                                #[cfg(feature = "gui")]
-                                {
-                                    warn!("\"settings.data\" contains invalid value for option \"{key}\": \"{value}\": Using default");
-                                }
-
-                                //TODO warning in cli version
-
-                                continue;
-                            };
-
-                            settings.background_music = value;
-                        },
-
-                        "animation_speed" => {
-                            let Ok(value) = AnimationSpeed::from_str(value) else {
-                                #[cfg(feature = "gui")]
-                                {
-                                    warn!("\"settings.data\" contains invalid value for option \"{key}\": \"{value}\": Using default");
-                                }
-
-                                //TODO warning in cli version
-
-                                continue;
-                            };
-
-                            settings.animation_speed = value;
-                        },
-
-                        _ => {
-                            #[cfg(feature = "gui")]
-                            {
-                                warn!("\"settings.data\" contains invalid settings option: \"{key}\" with value \"{value}\": Ignoring");
-                            }
-
-                            //TODO warning in cli version
-                        }
-                    }
-                }else {
-                    #[cfg(feature = "gui")]
-                    {
-                        warn!("\"settings.data\" contains invalid data: \"{line}\": Ignoring");
-                    }
-
-                    //TODO warning in cli version
-                }
-            }
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    #[must_use]
+    fn next_setting(self) -> Self {
+        match self {
+            MouseButtonAction::Disabled => MouseButtonAction::Back,
+            MouseButtonAction::Back => MouseButtonAction::TogglePreview,
+            MouseButtonAction::TogglePreview => MouseButtonAction::Disabled,
         }
+    }
+}
 
-        Ok(settings)
+impl Display for MouseButtonAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
     }
+}
 
-    pub fn save_to_file(&self) -> Result<(), Box<dyn Error>> {
-        let mut settings_save_file = Game::get_or_create_save_game_folder()?;
-        settings_save_file.push("settings.data");
-        let mut file = File::create(settings_save_file)?;
+impl FromStr for MouseButtonAction {
+    type Err = GameError;
 
-        writeln!(file, "color_scheme_index = {}", self.color_scheme_index)?;
-        writeln!(file, "tile_mode = {}", self.tile_mode)?;
-        writeln!(file, "background_music = {}", self.background_music)?;
-        writeln!(file, "animation_speed = {:?}", self.animation_speed)?;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Disabled" => Ok(MouseButtonAction::Disabled),
+            "Back" => Ok(MouseButtonAction::Back),
+            "TogglePreview" => Ok(MouseButtonAction::TogglePreview),
 
-        Ok(())
+            _ => Err(GameError::new("Invalid mouse button action \"{s}\"")),
+        }
     }
+}
 
-    pub fn color_scheme_index(&self) -> usize {
-        self.color_scheme_index
-    }
+/// The action which is triggered by the mouse wheel
+#[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum MouseWheelAction {
+    #[default]
+    Scroll,
+    InvertedScroll,
+    Disabled,
+}
 
-    pub fn tile_mode(&self) -> TileMode {
-        self.tile_mode
+impl MouseWheelAction {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            MouseWheelAction::Scroll => "Scroll",
+            MouseWheelAction::InvertedScroll => "Inverted",
+            MouseWheelAction::Disabled => "Disabled",
+        }
     }
 
-    pub fn background_music(&self) -> bool {
-        self.background_music
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    #[must_use]
+    fn next_setting(self) -> Self {
+        match self {
+            MouseWheelAction::Scroll => MouseWheelAction::InvertedScroll,
+            MouseWheelAction::InvertedScroll => MouseWheelAction::Disabled,
+            MouseWheelAction::Disabled => MouseWheelAction::Scroll,
+        }
     }
+}
 
-    pub fn animation_speed(&self) -> AnimationSpeed {
-        self.animation_speed
+impl Display for MouseWheelAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
     }
 }
 
-impl Default for GameSettings {
-    fn default() -> Self {
-        GameSettings::new()
+impl FromStr for MouseWheelAction {
+    type Err = GameError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Scroll" => Ok(MouseWheelAction::Scroll),
+            "InvertedScroll" => Ok(MouseWheelAction::InvertedScroll),
+            "Disabled" => Ok(MouseWheelAction::Disabled),
+
+            _ => Err(GameError::new("Invalid mouse wheel action \"{s}\"")),
+        }
     }
 }
 
-pub struct GameState {
-    current_screen_id: ScreenId,
-    should_call_on_set_screen: bool,
+/// Decides which arrow keys are used if different arrow keys are pressed at nearly the same time (In the same update)
+#[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ArrowKeyPriority {
+    /// Every arrow key is used in the order in which the keys were pressed
+    #[default]
+    InOrder,
+    MostRecent,
+    First,
+}
 
-    is_help: bool,
-    dialog: Option<RenderedDialog>,
+impl ArrowKeyPriority {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ArrowKeyPriority::InOrder => "All keys in order",
+            ArrowKeyPriority::MostRecent => "Most recent key wins",
+            ArrowKeyPriority::First => "First key wins",
+        }
+    }
 
-    current_level_pack_index: usize,
-    level_packs: Vec<LevelPack>,
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    #[must_use]
+    fn next_setting(self) -> Self {
+        match self {
+            ArrowKeyPriority::InOrder => ArrowKeyPriority::MostRecent,
+            ArrowKeyPriority::MostRecent => ArrowKeyPriority::First,
+            ArrowKeyPriority::First => ArrowKeyPriority::InOrder,
+        }
+    }
+}
 
-    current_level_index: usize,
-    allow_skip_level: bool,
+impl Display for ArrowKeyPriority {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
 
-    is_player_background: bool,
-    player_background_tmp: i32,
+impl FromStr for ArrowKeyPriority {
+    type Err = GameError;
 
-    pending_animation_play_count: f32,
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "InOrder" => Ok(ArrowKeyPriority::InOrder),
+            "MostRecent" => Ok(ArrowKeyPriority::MostRecent),
+            "First" => Ok(ArrowKeyPriority::First),
 
-    found_secret_main_level_pack: bool,
+            _ => Err(GameError::new("Invalid arrow key priority \"{s}\"")),
+        }
+    }
+}
 
-    should_exit: bool,
+/// Key layout of the in-game actions (Movement and confirm keys are the same in all presets)
+#[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ControlPreset {
+    #[default]
+    Default,
+    /// All actions are on the left side of the keyboard: WASD movement, q/e undo/redo, SPACEBAR confirm
+    OneHanded,
+}
 
-    editor_state: EditorState,
-    settings: GameSettings,
+impl ControlPreset {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ControlPreset::Default => "Default",
+            ControlPreset::OneHanded => "One-handed",
+        }
+    }
 
-    audio_handler: Option<AudioHandler>,
-    current_background_music_id: Option<BackgroundMusicId>,
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    #[must_use]
+    fn next_setting(self) -> Self {
+        match self {
+            ControlPreset::Default => ControlPreset::OneHanded,
+            ControlPreset::OneHanded => ControlPreset::Default,
+        }
+    }
+}
 
-    #[cfg(feature = "steam")]
-    steam_client: Client,
-    #[cfg(feature = "steam")]
-    pub show_workshop_upload_popup: bool,
+impl Display for ControlPreset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
+    }
 }
 
-impl GameState {
-    fn new(
-        level_packs: Vec<LevelPack>, editor_level_packs: Vec<LevelPack>,
+impl FromStr for ControlPreset {
+    type Err = GameError;
 
-        settings: GameSettings,
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Default" => Ok(ControlPreset::Default),
+            "OneHanded" => Ok(ControlPreset::OneHanded),
 
-        #[cfg(feature = "steam")]
-        steam_client: Client,
-    ) -> Self {
-        Self {
-            current_screen_id: ScreenId::StartMenu,
-            should_call_on_set_screen: Default::default(),
+            _ => Err(GameError::new("Invalid control preset \"{s}\"")),
+        }
+    }
+}
 
-            is_help: Default::default(),
-            dialog: Default::default(),
+/// Hides personal identifiers and clutter for recordings and streams
+#[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum StreamerMode {
+    #[default]
+    Off,
+    /// The level pack number, the profile name, and the save folder path are hidden
+    On,
+    /// Also hides the timer (Best times are still recorded)
+    OnHideTime,
+}
 
-            current_level_pack_index: Default::default(),
-            level_packs,
+impl StreamerMode {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            StreamerMode::Off => "Off",
+            StreamerMode::On => "On",
+            StreamerMode::OnHideTime => "On (Hide time)",
+        }
+    }
 
-            current_level_index: Default::default(),
-            allow_skip_level: false,
+    pub fn is_enabled(self) -> bool {
+        self != StreamerMode::Off
+    }
 
-            is_player_background: Default::default(),
-            player_background_tmp: Default::default(),
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    #[must_use]
+    fn next_setting(self) -> Self {
+        match self {
+            StreamerMode::Off => StreamerMode::On,
+            StreamerMode::On => StreamerMode::OnHideTime,
+            StreamerMode::OnHideTime => StreamerMode::Off,
+        }
+    }
+}
 
-            pending_animation_play_count: 0.0,
+impl Display for StreamerMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
 
-            found_secret_main_level_pack: Default::default(),
+impl FromStr for StreamerMode {
+    type Err = GameError;
 
-            should_exit: Default::default(),
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Off" => Ok(StreamerMode::Off),
+            "On" => Ok(StreamerMode::On),
+            "OnHideTime" => Ok(StreamerMode::OnHideTime),
 
-            settings,
-            editor_state: EditorState::new(editor_level_packs),
+            _ => Err(GameError::new("Invalid streamer mode \"{s}\"")),
+        }
+    }
+}
 
-            audio_handler: AudioHandler::new().ok(),
-            current_background_music_id: None,
+/// Which entries are written to the log file
+#[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum LogVerbosity {
+    Errors,
+    #[default]
+    Warnings,
+    Info,
+    Debug,
+}
 
-            #[cfg(feature = "steam")]
-            steam_client,
-            #[cfg(feature = "steam")]
-            show_workshop_upload_popup: false,
+impl LogVerbosity {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            LogVerbosity::Errors => "Errors",
+            LogVerbosity::Warnings => "Warnings",
+            LogVerbosity::Info => "Info",
+            LogVerbosity::Debug => "Debug",
         }
     }
 
-    pub fn set_screen(&mut self, screen_id: ScreenId) {
-        self.current_screen_id = screen_id;
-        self.should_call_on_set_screen = true;
+    pub fn max_log_level(self) -> LogLevel {
+        match self {
+            LogVerbosity::Errors => LogLevel::Error,
+            LogVerbosity::Warnings => LogLevel::Warn,
+            LogVerbosity::Info => LogLevel::Info,
+            LogVerbosity::Debug => LogLevel::Debug,
+        }
     }
 
-    pub fn level_packs(&self) -> &[LevelPack] {
-        &self.level_packs
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    #[must_use]
+    fn next_setting(self) -> Self {
+        match self {
+            LogVerbosity::Errors => LogVerbosity::Warnings,
+            LogVerbosity::Warnings => LogVerbosity::Info,
+            LogVerbosity::Info => LogVerbosity::Debug,
+            LogVerbosity::Debug => LogVerbosity::Errors,
+        }
     }
+}
 
-    pub fn get_level_pack_count(&self) -> usize {
-        self.level_packs.len()
+impl Display for LogVerbosity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
     }
+}
 
-    pub fn get_level_pack_index(&self) -> usize {
-        self.current_level_pack_index
-    }
+impl FromStr for LogVerbosity {
+    type Err = GameError;
 
-    pub fn set_level_pack_index(&mut self, level_pack_index: usize) {
-        self.current_level_pack_index = level_pack_index;
-    }
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Errors" => Ok(LogVerbosity::Errors),
+            "Warnings" => Ok(LogVerbosity::Warnings),
+            "Info" => Ok(LogVerbosity::Info),
+            "Debug" => Ok(LogVerbosity::Debug),
 
-    pub fn get_current_level_pack(&self) -> Option<&LevelPack> {
-        self.level_packs.get(self.current_level_pack_index)
+            _ => Err(GameError::new("Invalid log verbosity \"{s}\"")),
+        }
     }
+}
 
-    pub fn get_current_level_pack_mut(&mut self) -> Option<&mut LevelPack> {
-        self.level_packs.get_mut(self.current_level_pack_index)
-    }
+/// The consent of the player to upload anonymous level statistics to level pack authors
+#[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum TelemetryConsent {
+    /// The consent dialog was not shown yet, no statistics are recorded
+    #[default]
+    NotAsked,
+    Allowed,
+    Denied,
+}
 
-    pub fn get_level_index(&self) -> usize {
-        self.current_level_index
+impl TelemetryConsent {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            TelemetryConsent::NotAsked | TelemetryConsent::Denied => "Disabled",
+            TelemetryConsent::Allowed => "Enabled",
+        }
     }
 
-    pub fn set_level_index(&mut self, level_index: usize) {
-        self.current_level_index = level_index;
+    pub fn is_allowed(self) -> bool {
+        self == TelemetryConsent::Allowed
     }
 
-    pub fn is_player_background(&self) -> bool {
-        self.is_player_background
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    #[must_use]
+    fn next_setting(self) -> Self {
+        match self {
+            TelemetryConsent::NotAsked | TelemetryConsent::Denied => TelemetryConsent::Allowed,
+            TelemetryConsent::Allowed => TelemetryConsent::Denied,
+        }
     }
+}
 
-    pub fn open_help_page(&mut self) {
-        self.play_sound_effect(audio::BOOK_OPEN_EFFECT);
-
-        self.is_help = true;
+impl Display for TelemetryConsent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
     }
+}
 
-    pub fn close_help_page(&mut self) {
-        self.play_sound_effect(audio::UI_SELECT_EFFECT);
+impl FromStr for TelemetryConsent {
+    type Err = GameError;
 
-        self.is_help = false;
-    }
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "NotAsked" => Ok(TelemetryConsent::NotAsked),
+            "Allowed" => Ok(TelemetryConsent::Allowed),
+            "Denied" => Ok(TelemetryConsent::Denied),
 
-    pub fn is_dialog_opened(&self) -> bool {
-        self.dialog.is_some()
+            _ => Err(GameError::new(format!("Invalid telemetry consent \"{}\"", s))),
+        }
     }
+}
 
-    pub fn open_dialog(&mut self, dialog: Dialog) {
-        let dialog_type = dialog.dialog_type();
+/// The language which is used for level pack names and level hints if the level pack contains translations
+#[derive(Default, Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+    Italian,
+    Portuguese,
+    Dutch,
+    Polish,
+}
 
-        self.dialog = Some(dialog.render(Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT));
+impl Language {
+    pub const VALUES: [Language; 8] = [
+        Language::English,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Italian,
+        Language::Portuguese,
+        Language::Dutch,
+        Language::Polish,
+    ];
 
-        match dialog_type {
-            DialogType::Information => {
-                self.play_sound_effect_ui_dialog_open();
-            },
-            DialogType::Error => {
-                self.play_sound_effect_ui_error();
-            },
-            DialogType::SecretFound => {
-                self.play_sound_effect(audio::SECRET_FOUND_EFFECT);
-            },
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "German",
+            Language::French => "French",
+            Language::Spanish => "Spanish",
+            Language::Italian => "Italian",
+            Language::Portuguese => "Portuguese",
+            Language::Dutch => "Dutch",
+            Language::Polish => "Polish",
         }
     }
 
-    pub fn close_dialog(&mut self) {
-        self.dialog = None;
+    /// ISO 639-1 language code which is used in level pack files
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::Dutch => "nl",
+            Language::Polish => "pl",
+        }
     }
 
-    pub fn exit(&mut self) {
-        self.should_exit = true;
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::VALUES.into_iter().find(|language| language.code() == code)
     }
 
-    fn on_found_secret_for_level_pack(&mut self, level_pack_index: usize, save_immediately: bool) -> Result<(), Box<dyn Error>> {
-        if level_pack_index == 1 && !self.found_secret_main_level_pack {
-            self.found_secret_main_level_pack = true;
+    #[must_use]
+    pub fn next_setting(self) -> Self {
+        let index = Self::VALUES.iter().position(|language| *language == self).unwrap();
 
-            let secret_level_pack = LevelPack::read_from_save_game(
-                "secret", "built-in:secret", Game::MAP_SECRET, false,
+        Self::VALUES[(index + 1) % Self::VALUES.len()]
+    }
+}
 
-                #[cfg(feature = "steam")]
-                None,
-            )?;
+impl Display for Language {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
 
-            if save_immediately {
-                //Save immediately in order to keep secret level pack after game restart if not yet played
-                secret_level_pack.save_save_game(false)?;
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub struct GameSettings {
+    color_scheme_index: usize,
+    tile_mode: TileMode,
+    double_width_tiles: bool,
+    large_tiles: bool,
+    wall_variation: bool,
+
+    background_music: bool,
+    menu_background_music_id: BackgroundMusicId,
+    //None for the default device of the system
+    audio_device: Option<String>,
+
+    animation_speed: AnimationSpeed,
+
+    language: Language,
+
+    show_session_stats_on_exit: bool,
+
+    relaxed_mode: bool,
+
+    mouse_right_button_action: MouseButtonAction,
+    mouse_middle_button_action: MouseButtonAction,
+    mouse_wheel_action: MouseWheelAction,
+
+    arrow_key_priority: ArrowKeyPriority,
+    control_preset: ControlPreset,
+
+    streamer_mode: StreamerMode,
+    //Count of empty columns on the right side of the in-game screen for overlays in streamer mode
+    overlay_margin: usize,
+
+    telemetry_consent: TelemetryConsent,
+
+    log_verbosity: LogVerbosity,
+
+    //True if the credits were already rolled after all built-in level packs were completed (Only used by the GUI version)
+    credits_rolled: bool,
+
+    //Options of other versions of the game which are written back unchanged
+    unknown_options: Vec<(String, SettingsValue)>,
+}
+
+/// Schema of the settings file, new settings must be added here to be saved
+#[cfg(any(feature = "cli", feature = "gui"))]
+const SETTINGS_OPTIONS: &[SettingsOption<GameSettings>] = &[
+    SettingsOption {
+        key: "color_scheme_index",
+        read: |settings, value| {
+            let Ok(value) = usize::from_str(value) else {
+                return false;
+            };
+
+            #[cfg(feature = "gui")]
+            {
+                settings.color_scheme_index = value % crate::io::bevy_abstraction::COLOR_SCHEMES.len();
             }
 
-            self.level_packs.insert(4, secret_level_pack);
+            #[cfg(feature = "cli")]
+            {
+                //Not used in CLI build, but keep value as is for saving (CLI and GUI builds might both be played)
+                settings.color_scheme_index = value;
+            }
+
+            true
+        },
+        write: |settings| Some(SettingsValue::Int(settings.color_scheme_index)),
+    },
+    SettingsOption {
+        key: "tile_mode",
+        read: |settings, value| TileMode::from_str(value).map(|value| settings.tile_mode = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(settings.tile_mode.to_string())),
+    },
+    SettingsOption {
+        key: "double_width_tiles",
+        read: |settings, value| bool::from_str(value).map(|value| settings.double_width_tiles = value).is_ok(),
+        write: |settings| Some(SettingsValue::Bool(settings.double_width_tiles)),
+    },
+    SettingsOption {
+        key: "large_tiles",
+        read: |settings, value| bool::from_str(value).map(|value| settings.large_tiles = value).is_ok(),
+        write: |settings| Some(SettingsValue::Bool(settings.large_tiles)),
+    },
+    SettingsOption {
+        key: "wall_variation",
+        read: |settings, value| bool::from_str(value).map(|value| settings.wall_variation = value).is_ok(),
+        write: |settings| Some(SettingsValue::Bool(settings.wall_variation)),
+    },
+    SettingsOption {
+        key: "background_music",
+        read: |settings, value| bool::from_str(value).map(|value| settings.background_music = value).is_ok(),
+        write: |settings| Some(SettingsValue::Bool(settings.background_music)),
+    },
+    SettingsOption {
+        key: "menu_background_music",
+        read: |settings, value| usize::from_str(value).ok().
+                and_then(|value| audio::BACKGROUND_MUSIC_TRACKS.check_id(value)).
+                map(|value| settings.menu_background_music_id = value).
+                is_some(),
+        write: |settings| Some(SettingsValue::Int(settings.menu_background_music_id.id())),
+    },
+    SettingsOption {
+        key: "audio_device",
+        read: |settings, value| {
+            //Devices which are not available are kept (The default device is used until the device is available)
+            settings.audio_device = Some(value.to_string());
+
+            true
+        },
+        write: |settings| settings.audio_device.clone().map(SettingsValue::String),
+    },
+    SettingsOption {
+        key: "animation_speed",
+        read: |settings, value| AnimationSpeed::from_str(value).map(|value| settings.animation_speed = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.animation_speed))),
+    },
+    SettingsOption {
+        key: "language",
+        read: |settings, value| Language::from_code(value).map(|value| settings.language = value).is_some(),
+        write: |settings| Some(SettingsValue::String(settings.language.code().to_string())),
+    },
+    SettingsOption {
+        key: "show_session_stats_on_exit",
+        read: |settings, value| bool::from_str(value).map(|value| settings.show_session_stats_on_exit = value).is_ok(),
+        write: |settings| Some(SettingsValue::Bool(settings.show_session_stats_on_exit)),
+    },
+    SettingsOption {
+        key: "relaxed_mode",
+        read: |settings, value| bool::from_str(value).map(|value| settings.relaxed_mode = value).is_ok(),
+        write: |settings| Some(SettingsValue::Bool(settings.relaxed_mode)),
+    },
+    SettingsOption {
+        key: "mouse_right_button_action",
+        read: |settings, value| MouseButtonAction::from_str(value).map(|value| settings.mouse_right_button_action = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.mouse_right_button_action))),
+    },
+    SettingsOption {
+        key: "mouse_middle_button_action",
+        read: |settings, value| MouseButtonAction::from_str(value).map(|value| settings.mouse_middle_button_action = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.mouse_middle_button_action))),
+    },
+    SettingsOption {
+        key: "mouse_wheel_action",
+        read: |settings, value| MouseWheelAction::from_str(value).map(|value| settings.mouse_wheel_action = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.mouse_wheel_action))),
+    },
+    SettingsOption {
+        key: "arrow_key_priority",
+        read: |settings, value| ArrowKeyPriority::from_str(value).map(|value| settings.arrow_key_priority = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.arrow_key_priority))),
+    },
+    SettingsOption {
+        key: "control_preset",
+        read: |settings, value| ControlPreset::from_str(value).map(|value| settings.control_preset = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.control_preset))),
+    },
+    SettingsOption {
+        key: "streamer_mode",
+        read: |settings, value| StreamerMode::from_str(value).map(|value| settings.streamer_mode = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.streamer_mode))),
+    },
+    SettingsOption {
+        key: "overlay_margin",
+        read: |settings, value| usize::from_str(value).ok().
+                filter(|value| GameSettings::OVERLAY_MARGINS.contains(value)).
+                map(|value| settings.overlay_margin = value).
+                is_some(),
+        write: |settings| Some(SettingsValue::Int(settings.overlay_margin)),
+    },
+    SettingsOption {
+        key: "telemetry_consent",
+        read: |settings, value| TelemetryConsent::from_str(value).map(|value| settings.telemetry_consent = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.telemetry_consent))),
+    },
+    SettingsOption {
+        key: "log_verbosity",
+        read: |settings, value| LogVerbosity::from_str(value).map(|value| settings.log_verbosity = value).is_ok(),
+        write: |settings| Some(SettingsValue::String(format!("{:?}", settings.log_verbosity))),
+    },
+    SettingsOption {
+        key: "credits_rolled",
+        read: |settings, value| bool::from_str(value).map(|value| settings.credits_rolled = value).is_ok(),
+        write: |settings| Some(SettingsValue::Bool(settings.credits_rolled)),
+    },
+];
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl GameSettings {
+    pub const OVERLAY_MARGINS: [usize; 3] = [0, 4, 8];
+
+    const FILE_NAME: &'static str = "settings.data";
+
+    pub fn new() -> GameSettings {
+        Self {
+            color_scheme_index: 0,
+            tile_mode: TileMode::default(),
+            double_width_tiles: false,
+            large_tiles: false,
+            wall_variation: true,
+
+            background_music: true,
+            menu_background_music_id: audio::BACKGROUND_MUSIC_FIELDS_OF_ICE.id(),
+            audio_device: None,
+
+            animation_speed: AnimationSpeed::default(),
+
+            language: Language::default(),
+
+            show_session_stats_on_exit: true,
+
+            relaxed_mode: false,
+
+            mouse_right_button_action: MouseButtonAction::Back,
+            mouse_middle_button_action: MouseButtonAction::TogglePreview,
+            mouse_wheel_action: MouseWheelAction::default(),
+
+            arrow_key_priority: ArrowKeyPriority::default(),
+            control_preset: ControlPreset::default(),
+
+            streamer_mode: StreamerMode::default(),
+            overlay_margin: 0,
+
+            telemetry_consent: TelemetryConsent::default(),
+
+            log_verbosity: LogVerbosity::default(),
+
+            credits_rolled: false,
+
+            unknown_options: Vec::new(),
+        }
+    }
+
+    pub fn read_from_file() -> Result<Self, Box<dyn Error>> {
+        let mut settings_save_file = Game::get_or_create_save_game_folder()?;
+        settings_save_file.push(Self::FILE_NAME);
+
+        let mut settings = GameSettings::new();
+
+        if std::fs::exists(&settings_save_file)? {
+            let settings_data = std::fs::read_to_string(&settings_save_file)?;
+            let settings_file = SettingsFile::parse(Self::FILE_NAME, &settings_data);
+            settings.unknown_options = settings_file.apply(Self::FILE_NAME, &mut settings, SETTINGS_OPTIONS);
+
+            //Files without version are rewritten in the current format
+            if settings_file.version() < SETTINGS_FILE_VERSION {
+                log_info!("Migrating \"{}\" from version {} to version {}", Self::FILE_NAME, settings_file.version(), SETTINGS_FILE_VERSION);
+
+                settings.save_to_file()?;
+            }
         }
 
+        credits::migrate_legacy_credits_rolled_file(&mut settings)?;
+
+        Ok(settings)
+    }
+
+    pub fn save_to_file(&self) -> Result<(), Box<dyn Error>> {
+        let mut settings_save_file = Game::get_or_create_save_game_folder()?;
+        settings_save_file.push(Self::FILE_NAME);
+
+        let data = SettingsFile::from_schema(self, SETTINGS_OPTIONS, &self.unknown_options).to_str();
+        settings_file::write_file_atomically(Path::new(&settings_save_file), &data)?;
+
         Ok(())
     }
 
-    pub fn on_found_secret(&mut self) -> Result<(), Box<dyn Error>> {
-        self.on_found_secret_for_level_pack(self.current_level_pack_index, true)
+    pub fn color_scheme_index(&self) -> usize {
+        self.color_scheme_index
     }
 
-    pub fn play_sound_effect_ui_dialog_open(&mut self) {
-        self.play_sound_effect(audio::UI_DIALOG_OPEN_EFFECT);
+    pub fn tile_mode(&self) -> TileMode {
+        self.tile_mode
     }
 
-    pub fn play_sound_effect_ui_select(&mut self) {
-        self.play_sound_effect(audio::UI_SELECT_EFFECT);
+    pub fn double_width_tiles(&self) -> bool {
+        self.double_width_tiles
     }
 
-    pub fn play_sound_effect_ui_error(&mut self) {
-        self.play_sound_effect(audio::UI_ERROR_EFFECT);
+    pub fn large_tiles(&self) -> bool {
+        self.large_tiles
     }
 
-    pub fn play_sound_effect(&mut self, sound_effect: &'static SoundEffect) {
-        if let Some(audio_handler) = &mut self.audio_handler {
-            let _ = audio_handler.play_sound_effect(sound_effect);
-        }
+    pub fn wall_variation(&self) -> bool {
+        self.wall_variation
     }
 
-    pub fn play_level_sound_effect(&mut self, sound_effect: LevelSoundEffect) {
-        if let Some(audio_handler) = &mut self.audio_handler {
-            let _ = audio_handler.play_sound_effect(match sound_effect {
-                LevelSoundEffect::BoxFall => audio::BOX_FALL_EFFECT,
-                LevelSoundEffect::KeyFall => audio::KEY_FALL_EFFECT,
-                LevelSoundEffect::DoorUnlocked => audio::DOOR_OPEN_EFFECT,
-                LevelSoundEffect::FloorBroken => audio::FLOOR_BROKEN_EFFECT,
-            });
-        }
+    pub fn background_music(&self) -> bool {
+        self.background_music
     }
 
-    pub fn current_background_music_id(&self) -> Option<BackgroundMusicId> {
-        self.current_background_music_id
+    pub fn menu_background_music_id(&self) -> BackgroundMusicId {
+        self.menu_background_music_id
     }
 
-    pub fn stop_background_music(&mut self) {
-        self.current_background_music_id = None;
+    /// Returns the name of the selected audio output device or None if the default device of the system is used
+    pub fn audio_device(&self) -> Option<&str> {
+        self.audio_device.as_deref()
+    }
 
-        self.stop_background_music_internal();
+    pub fn animation_speed(&self) -> AnimationSpeed {
+        self.animation_speed
     }
 
-    fn stop_background_music_internal(&mut self) {
-        if let Some(audio_handler) = &self.audio_handler {
-            audio_handler.stop_background_music();
-        }
+    pub fn show_session_stats_on_exit(&self) -> bool {
+        self.show_session_stats_on_exit
     }
 
-    pub fn set_background_music_loop(&mut self, background_music: &BackgroundMusic) {
-        if self.current_background_music_id.is_some_and(|id| background_music.id() == id) {
-            return;
-        }
+    /// In relaxed mode the timer and the move counter are hidden and no time stats are recorded
+    pub fn relaxed_mode(&self) -> bool {
+        self.relaxed_mode
+    }
 
-        self.current_background_music_id = Some(background_music.id());
+    pub fn mouse_right_button_action(&self) -> MouseButtonAction {
+        self.mouse_right_button_action
+    }
 
-        if !self.settings.background_music {
-            return;
-        }
+    pub fn mouse_middle_button_action(&self) -> MouseButtonAction {
+        self.mouse_middle_button_action
+    }
 
-        if let Some(audio_handler) = &self.audio_handler {
-            let _ = audio_handler.set_background_music_loop(
-                background_music.intro_audio_data(),
-                background_music.main_loop_audio_data(),
-            );
+    pub fn mouse_wheel_action(&self) -> MouseWheelAction {
+        self.mouse_wheel_action
+    }
+
+    pub fn arrow_key_priority(&self) -> ArrowKeyPriority {
+        self.arrow_key_priority
+    }
+
+    pub fn control_preset(&self) -> ControlPreset {
+        self.control_preset
+    }
+
+    pub fn streamer_mode(&self) -> StreamerMode {
+        self.streamer_mode
+    }
+
+    /// Returns the count of empty columns for overlays, the margin is only used in streamer mode
+    pub fn overlay_margin(&self) -> usize {
+        if self.streamer_mode.is_enabled() {
+            self.overlay_margin
+        }else {
+            0
         }
     }
 
-    pub fn settings(&self) -> &GameSettings {
-        &self.settings
+    #[must_use]
+    fn next_overlay_margin(&self) -> usize {
+        let index = Self::OVERLAY_MARGINS.iter().position(|&margin| margin == self.overlay_margin).unwrap_or_default();
+
+        Self::OVERLAY_MARGINS[(index + 1) % Self::OVERLAY_MARGINS.len()]
     }
 
-    pub fn editor_state(&self) -> &EditorState {
-        &self.editor_state
+    /// Anonymous level statistics are only recorded and uploaded if the player allowed it
+    pub fn telemetry_consent(&self) -> TelemetryConsent {
+        self.telemetry_consent
     }
 
-    pub fn set_and_save_color_scheme_index(&mut self, color_scheme_index: usize) -> Result<(), Box<dyn Error>> {
-        self.settings.color_scheme_index = color_scheme_index;
-        self.settings.save_to_file()?;
+    pub fn log_verbosity(&self) -> LogVerbosity {
+        self.log_verbosity
+    }
 
-        Ok(())
+    pub fn credits_rolled(&self) -> bool {
+        self.credits_rolled
     }
+}
 
-    pub fn set_and_save_tile_mode(&mut self, tile_mode: TileMode) -> Result<(), Box<dyn Error>> {
-        self.settings.tile_mode = tile_mode;
-        self.settings.save_to_file()?;
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl Default for GameSettings {
+    fn default() -> Self {
+        GameSettings::new()
+    }
+}
 
-        Ok(())
+/// Statistics of the current session (Since the game was started)
+#[cfg(any(feature = "cli", feature = "gui"))]
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    start_time: Instant,
+
+    levels_completed: u32,
+    moves: u32,
+    undos: u32,
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            start_time: Instant::now(),
+
+            levels_completed: 0,
+            moves: 0,
+            undos: 0,
+        }
     }
 
-    pub fn set_and_save_background_music_enabled(&mut self, background_music: bool) -> Result<(), Box<dyn Error>> {
-        self.settings.background_music = background_music;
+    pub fn on_level_completed(&mut self) {
+        self.levels_completed = self.levels_completed.saturating_add(1);
+    }
 
-        if background_music {
-            if let Some(current_background_music_id) = self.current_background_music_id {
-                //Force restart current background music
-                self.stop_background_music();
-                self.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(current_background_music_id));
-            }
-        }else {
-            self.stop_background_music_internal();
+    pub fn on_move(&mut self) {
+        self.moves = self.moves.saturating_add(1);
+    }
+
+    pub fn on_undo(&mut self) {
+        self.undos = self.undos.saturating_add(1);
+    }
+
+    /// Returns a summary of the session or None if no level was played
+    pub fn summary(&self) -> Option<String> {
+        if self.moves == 0 && self.undos == 0 {
+            return None;
         }
 
-        self.settings.save_to_file()?;
+        let minutes = self.start_time.elapsed().as_secs() / 60;
 
-        Ok(())
+        Some(format!(
+            "You played {}, completed {}, made {}, undid {}.",
+            Self::count_to_string(minutes, "minute"),
+            Self::count_to_string(self.levels_completed as u64, "level"),
+            Self::count_to_string(self.moves as u64, "move"),
+            Self::count_to_string(self.undos as u64, "time"),
+        ))
     }
 
-    pub fn set_and_save_animation_speed(&mut self, animation_speed: AnimationSpeed) -> Result<(), Box<dyn Error>> {
-        self.settings.animation_speed = animation_speed;
+    fn count_to_string(count: u64, unit: &str) -> String {
+        if count == 1 {
+            format!("{count} {unit}")
+        }else {
+            format!("{count} {unit}s")
+        }
+    }
+}
 
-        self.settings.save_to_file()?;
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl Default for SessionStats {
+    fn default() -> Self {
+        SessionStats::new()
+    }
+}
 
-        Ok(())
+/// A non-blocking message which is shown for a short time in the bottom right corner
+#[derive(Debug, Clone)]
+#[cfg(any(feature = "cli", feature = "gui"))]
+struct Toast {
+    message: Box<str>,
+
+    //Set as soon as the toast is shown (Queued toasts are shown one after another)
+    shown_since: Option<Instant>,
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl Toast {
+    const DURATION: Duration = Duration::from_secs(2);
+    const MAX_QUEUED_COUNT: usize = 5;
+
+    fn new(message: impl Into<Box<str>>) -> Self {
+        Self {
+            message: message.into(),
+            shown_since: None,
+        }
     }
+
+    fn draw(&self, console: &Console) {
+        let text = format!(" {} ", self.message);
+        let text = &text[..text.len().min(Game::CONSOLE_MIN_WIDTH)];
+
+        console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - text.len(), Game::CONSOLE_MIN_HEIGHT - 1);
+        console.set_color(Color::Black, Color::LightYellow);
+        console.draw_text(text);
+        console.reset_color();
+    }
+}
+
+/// The console area in which the GUI shows the thumbnail level of the highlighted level pack as image
+#[cfg(feature = "gui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelPackThumbnailArea {
+    pub level_pack_index: usize,
+
+    pub column: usize,
+    pub row: usize,
+    pub width: usize,
+    pub height: usize,
 }
 
-pub struct Game<'a> {
-    console: &'a Console<'a>,
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub struct GameState {
+    current_screen_id: ScreenId,
+    should_call_on_set_screen: bool,
+
+    is_help: bool,
+    dialogs: DialogQueue,
+    toasts: VecDeque<Toast>,
+    clipboard_text: Option<String>,
+    //Messages of the last error dialogs (Shown in the diagnostics)
+    error_log: ErrorLog,
+    #[cfg(feature = "gui")]
+    level_pack_thumbnail_area: Option<LevelPackThumbnailArea>,
+    hit_regions: HitRegions,
+
+    current_level_pack_index: usize,
+    level_packs: Vec<LevelPack>,
+    //Level pack ID -> Source of built-in level packs
+    level_pack_sources: HashMap<String, LevelPackSource>,
+    background_validator: BackgroundValidator,
+    min_push_count_cache: MinPushCountCache,
+    save_writer: SaveWriter,
+
+    current_level_index: usize,
+    allow_skip_level: bool,
+    should_start_speedrun: bool,
+    #[cfg(feature = "gui")]
+    should_roll_credits: bool,
+    pending_snapshot_level_attempt: Option<LevelAttempt>,
+    //Screen which is shown if the settings are closed (The start menu if None)
+    settings_back_screen_id: Option<ScreenId>,
+    //Seed and level of the remix which is started instead of the current level
+    pending_remix: Option<(u64, Level)>,
+    //Intro or outro which is shown by the pack story screen
+    pending_pack_story: Option<PackStory>,
+    //Level pack index and level indices of the shuffled sort mode of the level selection
+    shuffled_level_order: Option<(usize, Vec<usize>)>,
+
+    is_player_background: bool,
+    player_background_tmp: i32,
+
+    //The HUD of the in-game screen is hidden for the current session (e.g. for recordings)
+    is_hud_hidden: bool,
+
+    pending_animation_play_count: f32,
+
+    found_secret_main_level_pack: bool,
+
+    should_exit: bool,
+
+    #[cfg(not(feature = "steam"))]
+    pending_profile: Option<Option<String>>,
+
+    editor_state: EditorState,
+    settings: GameSettings,
+    session_stats: SessionStats,
+    plugins: PluginRegistry,
+
+    startup_flags: StartupFlags,
+
+    audio_handler: Option<AudioHandler>,
+    audio_device_monitor: AudioDeviceMonitor,
+    current_background_music_id: Option<BackgroundMusicId>,
+    //Level pack path -> Sound effects of the level pack (Loaded when the first sound effect of the level pack is played)
+    pack_sound_effects: HashMap<String, PackSoundEffects>,
+
+    #[cfg(feature = "steam")]
+    steam_client: Client,
+    #[cfg(feature = "steam")]
+    pub show_workshop_upload_popup: bool,
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl GameState {
+    fn new(
+        level_packs: Vec<LevelPack>, editor_level_packs: Vec<LevelPack>,
+
+        settings: GameSettings, player_stats: PlayerStats, total_stats: TotalStats,
+
+        #[cfg(feature = "steam")]
+        steam_client: Client,
+    ) -> Self {
+        let startup_flags = startup_flags::startup_flags();
+        let audio_handler = if startup_flags.no_audio() {
+            None
+        }else {
+            AudioHandler::new(settings.audio_device()).ok()
+        };
+
+        let mut game_state = Self {
+            current_screen_id: ScreenId::StartMenu,
+            should_call_on_set_screen: Default::default(),
+
+            is_help: Default::default(),
+            dialogs: Default::default(),
+            toasts: VecDeque::new(),
+            clipboard_text: None,
+            error_log: ErrorLog::default(),
+            #[cfg(feature = "gui")]
+            level_pack_thumbnail_area: None,
+            hit_regions: HitRegions::new(),
+
+            current_level_pack_index: Default::default(),
+            level_packs,
+            level_pack_sources: HashMap::new(),
+            background_validator: BackgroundValidator::new(),
+            min_push_count_cache: MinPushCountCache::new(),
+            save_writer: SaveWriter::new(),
+
+            current_level_index: Default::default(),
+            allow_skip_level: false,
+            should_start_speedrun: false,
+            #[cfg(feature = "gui")]
+            should_roll_credits: false,
+            pending_snapshot_level_attempt: None,
+            settings_back_screen_id: None,
+            pending_remix: None,
+            pending_pack_story: None,
+            shuffled_level_order: None,
+
+            is_player_background: Default::default(),
+            player_background_tmp: Default::default(),
+
+            is_hud_hidden: false,
+
+            pending_animation_play_count: 0.0,
+
+            found_secret_main_level_pack: Default::default(),
+
+            should_exit: Default::default(),
+
+            #[cfg(not(feature = "steam"))]
+            pending_profile: None,
+
+            settings,
+            editor_state: EditorState::new(editor_level_packs),
+            session_stats: SessionStats::new(),
+
+            plugins: PluginRegistry::new(),
+
+            startup_flags,
+
+            audio_handler,
+            audio_device_monitor: AudioDeviceMonitor::new(),
+            current_background_music_id: None,
+            pack_sound_effects: HashMap::new(),
+
+            #[cfg(feature = "steam")]
+            steam_client,
+            #[cfg(feature = "steam")]
+            show_workshop_upload_popup: false,
+        };
+
+        #[cfg(feature = "telemetry")]
+        {
+            let is_telemetry_allowed = game_state.settings.telemetry_consent.is_allowed();
+            game_state.register_plugin(PluginId::Telemetry, is_telemetry_allowed, TelemetryEventListener::default());
+        }
+        game_state.register_plugin(PluginId::PlayerStats, true, PlayerStatsPlugin::new(player_stats));
+        game_state.register_plugin(PluginId::TotalStats, true, TotalStatsPlugin::new(total_stats));
+
+        #[cfg(feature = "gui")]
+        game_state.register_plugin(PluginId::Credits, true, CreditsEventListener::default());
+
+        #[cfg(feature = "steam")]
+        {
+            game_state.register_plugin(PluginId::RichPresence, true, RichPresenceEventListener);
+            game_state.register_plugin(PluginId::SteamAchievements, true, AchievementEventListener);
+        }
+
+        game_state
+    }
+
+    pub fn set_screen(&mut self, screen_id: ScreenId) {
+        self.current_screen_id = screen_id.clone();
+        self.should_call_on_set_screen = true;
+
+        #[cfg(feature = "gui")]
+        {
+            self.level_pack_thumbnail_area = None;
+        }
+
+        self.dispatch_event(|listener, game_state| listener.on_screen_changed(game_state, &screen_id));
+    }
+
+    /// Registers an optional subsystem which is notified about game events
+    pub fn register_plugin(&mut self, id: PluginId, is_enabled: bool, listener: impl Plugin) {
+        self.plugins.register(id, is_enabled, listener);
+    }
+
+    pub fn is_plugin_enabled(&self, id: PluginId) -> bool {
+        self.plugins.is_enabled(id)
+    }
+
+    /// Enables or disables a registered plugin at runtime, disabled plugins are not notified about events
+    pub fn set_plugin_enabled(&mut self, id: PluginId, is_enabled: bool) {
+        self.plugins.set_enabled(id, is_enabled);
+    }
+
+    /// Notifies all plugins about the started (Or restarted) current level
+    pub fn on_level_started(&mut self, is_restart: bool) {
+        let event = LevelStartedEvent {
+            level_pack_index: self.current_level_pack_index,
+            level_index: self.current_level_index,
+            is_restart,
+        };
+
+        self.dispatch_event(|listener, game_state| listener.on_level_started(game_state, &event));
+    }
+
+    /// Notifies all event listeners about a completed level (And about the completed level pack if all levels are completed)
+    pub fn on_level_completed(&mut self, event: LevelCompletedEvent) {
+        self.dispatch_event(|listener, game_state| listener.on_level_completed(game_state, &event));
+
+        let is_pack_completed = event.is_progress_saved && self.level_packs.get(event.level_pack_index).
+                is_some_and(|level_pack| level_pack.min_level_not_completed() >= level_pack.level_count());
+        if is_pack_completed {
+            self.dispatch_event(|listener, game_state| listener.on_pack_completed(game_state, event.level_pack_index));
+        }
+    }
+
+    fn dispatch_event(&mut self, mut dispatch: impl FnMut(&mut dyn GameEventListener, &mut GameState)) {
+        //Plugins are taken out of the game state, because they can modify the game state
+        let mut plugins = mem::take(&mut self.plugins);
+        plugins.for_each_enabled(|listener| dispatch(listener, self));
+
+        self.restore_plugins(plugins);
+    }
+
+    /// Calls the function if the plugin is enabled (Plugins are not available while events are dispatched)
+    fn with_plugin<T: Plugin>(&mut self, id: PluginId, func: impl FnOnce(&mut T, &mut GameState)) {
+        let mut plugins = mem::take(&mut self.plugins);
+        if let Some(plugin) = plugins.enabled_plugin_mut(id) {
+            func(plugin, self);
+        }
+
+        self.restore_plugins(plugins);
+    }
+
+    fn restore_plugins(&mut self, mut plugins: PluginRegistry) {
+        //Keep plugins which were registered by other plugins
+        plugins.append(mem::take(&mut self.plugins));
+        self.plugins = plugins;
+    }
+
+    #[cfg(feature = "steam")]
+    fn update_rich_presence(&self) {
+        let rich_presence = match self.current_screen_id {
+            ScreenId::InGame => self.get_current_level_pack().map(|level_pack| RichPresence::Playing {
+                level_pack_name: level_pack.name(),
+                level: self.current_level_index + 1,
+            }),
+
+            ScreenId::SelectLevelPackEditor => Some(RichPresence::BrowsingLevelPackEditor),
+
+            ScreenId::LevelPackEditor | ScreenId::LevelEditor | ScreenId::SelectLevelPackBackgroundMusic => {
+                self.editor_state.get_current_level_pack().map(|level_pack| RichPresence::EditingLevelPack {
+                    level_pack_name: level_pack.name(),
+                })
+            },
+
+            _ => None,
+        }.unwrap_or(RichPresence::InMenu);
+
+        rich_presence.update(&self.steam_client);
+    }
+
+    #[cfg(not(feature = "steam"))]
+    fn update_rich_presence(&self) {}
+
+    /// Switches to the profile (None is the default profile) after the current update
+    #[cfg(not(feature = "steam"))]
+    pub fn switch_profile(&mut self, profile: Option<String>) {
+        self.pending_profile = Some(profile);
+    }
+
+    pub fn level_packs(&self) -> &[LevelPack] {
+        &self.level_packs
+    }
+
+    /// Returns true if all levels of all built-in level packs (Except the secret level pack) are completed
+    pub fn are_built_in_level_packs_completed(&self) -> bool {
+        Game::BUILT_IN_LEVEL_PACKS.iter().all(|built_in_level_pack| self.level_packs.iter().
+                find(|level_pack| level_pack.id() == built_in_level_pack.id).
+                is_some_and(|level_pack| level_pack.min_level_not_completed() >= level_pack.level_count()))
+    }
+
+    /// Opens the credits screen which scrolls automatically and returns to the level pack selection afterwards
+    #[cfg(feature = "gui")]
+    pub fn roll_credits(&mut self) {
+        self.should_roll_credits = true;
+
+        self.set_screen(ScreenId::Credits);
+    }
+
+    /// Opens the text screen with the intro or outro of the current level pack
+    ///
+    /// The current level is started after the intro and the level selection is opened after the outro
+    pub fn show_pack_story(&mut self, story: PackStory) {
+        self.pending_pack_story = Some(story);
+
+        self.set_screen(ScreenId::PackStory);
+    }
+
+    /// Returns the source of a built-in level pack (Built-in data or a patch) or None for all other level packs
+    pub fn level_pack_source(&self, level_pack_id: &str) -> Option<&LevelPackSource> {
+        self.level_pack_sources.get(level_pack_id)
+    }
+
+    /// Returns the result of the background validation of the level of the current level pack
+    ///
+    /// Only imported level packs are validated, None is returned for all other level packs or if the validation is not finished
+    pub fn level_solvability(&self, level_index: usize) -> Option<Solvability> {
+        self.get_current_level_pack().
+                and_then(|level_pack| self.background_validator.solvability(level_pack.id(), level_index))
+    }
+
+    /// Returns the minimum push count of the level of the current level pack, it is computed in the background on first use
+    pub fn level_min_push_count(&self, level_index: usize) -> Option<u32> {
+        self.get_current_level_pack().
+                and_then(|level_pack| level_pack.levels().get(level_index)).
+                and_then(|level| self.min_push_count_cache.min_push_count(level.level()))
+    }
+
+    /// Returns the Steam Workshop IDs of the level packs which are required by the current level pack but are not installed
+    #[cfg(feature = "steam")]
+    pub fn missing_required_workshop_ids(&self) -> Vec<u64> {
+        let Some(level_pack) = self.get_current_level_pack() else {
+            return Vec::new();
+        };
+
+        level_pack.required_workshop_ids().iter().
+                copied().
+                filter(|&workshop_id| !self.level_packs.iter().any(|level_pack| level_pack.steam_level_pack_data().
+                        is_some_and(|steam_level_pack_data| steam_level_pack_data.workshop_id().0 == workshop_id))).
+                collect()
+    }
+
+    pub fn get_level_pack_count(&self) -> usize {
+        self.level_packs.len()
+    }
+
+    pub fn get_level_pack_index(&self) -> usize {
+        self.current_level_pack_index
+    }
+
+    pub fn set_level_pack_index(&mut self, level_pack_index: usize) {
+        self.current_level_pack_index = level_pack_index;
+    }
+
+    pub fn get_current_level_pack(&self) -> Option<&LevelPack> {
+        self.level_packs.get(self.current_level_pack_index)
+    }
+
+    pub fn get_current_level_pack_mut(&mut self) -> Option<&mut LevelPack> {
+        self.level_packs.get_mut(self.current_level_pack_index)
+    }
+
+    pub fn get_level_index(&self) -> usize {
+        self.current_level_index
+    }
+
+    pub fn set_level_index(&mut self, level_index: usize) {
+        self.current_level_index = level_index;
+
+        self.update_rich_presence();
+    }
+
+    /// Returns None if the level suggestions are disabled
+    pub fn player_stats(&self) -> Option<&PlayerStats> {
+        self.plugins.enabled_plugin::<PlayerStatsPlugin>(PluginId::PlayerStats).
+                map(PlayerStatsPlugin::player_stats)
+    }
+
+    /// Returns None if the local achievements are disabled
+    pub fn total_stats(&self) -> Option<&TotalStats> {
+        self.plugins.enabled_plugin::<TotalStatsPlugin>(PluginId::TotalStats).
+                map(TotalStatsPlugin::total_stats)
+    }
+
+    /// Increments the total stat, a toast is shown for every local achievement which is unlocked by this
+    pub fn record_total_stat(&mut self, stat: TotalStat) {
+        self.with_plugin(PluginId::TotalStats, |plugin: &mut TotalStatsPlugin, game_state| plugin.record(game_state, stat));
+    }
+
+    pub fn is_player_background(&self) -> bool {
+        self.is_player_background
+    }
+
+    /// Returns true if only the level is drawn on the in-game screen
+    pub fn is_hud_hidden(&self) -> bool {
+        self.is_hud_hidden
+    }
+
+    pub fn open_help_page(&mut self) {
+        self.play_sound_effect(audio::BOOK_OPEN_EFFECT);
+
+        self.is_help = true;
+    }
+
+    pub fn close_help_page(&mut self) {
+        self.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+        self.is_help = false;
+    }
+
+    pub fn is_dialog_opened(&self) -> bool {
+        self.dialogs.is_open()
+    }
+
+    /// Opens the dialog, if another dialog is open the dialog is opened after all earlier dialogs were closed
+    pub fn open_dialog(&mut self, dialog: Dialog) {
+        let dialog_type = dialog.dialog_type();
+        if dialog_type == DialogType::Error {
+            log_error!("{}", dialog.message());
+            self.error_log.push(dialog.message());
+        }
+
+        if self.dialogs.open(dialog, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT) {
+            self.play_sound_effect_dialog_open(dialog_type);
+        }
+    }
+
+    /// Closes the open dialog and opens the next queued dialog
+    pub fn close_dialog(&mut self) {
+        self.dialogs.close();
+        self.open_next_queued_dialog();
+    }
+
+    /// Closes the open dialog and discards all queued dialogs
+    pub fn close_all_dialogs(&mut self) {
+        self.dialogs.clear();
+    }
+
+    fn open_next_queued_dialog(&mut self) {
+        if let Some(dialog_type) = self.dialogs.open_next(Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT) {
+            self.play_sound_effect_dialog_open(dialog_type);
+        }
+    }
+
+    fn play_sound_effect_dialog_open(&mut self, dialog_type: DialogType) {
+        match dialog_type {
+            DialogType::Information => {
+                self.play_sound_effect_ui_dialog_open();
+            },
+            DialogType::Error => {
+                self.play_sound_effect_ui_error();
+            },
+            DialogType::SecretFound => {
+                self.play_sound_effect(audio::SECRET_FOUND_EFFECT);
+            },
+        }
+    }
+
+    /// Shows a non-blocking message for a short time (e.g. "Progress saved"), toasts are dropped if too many are queued
+    pub fn show_toast(&mut self, message: impl Into<Box<str>>) {
+        if self.toasts.len() >= Toast::MAX_QUEUED_COUNT {
+            return;
+        }
+
+        self.toasts.push_back(Toast::new(message));
+    }
+
+    /// The text is written to the system clipboard by the UI after the current update
+    pub fn copy_to_clipboard(&mut self, text: impl Into<String>) {
+        self.clipboard_text = Some(text.into());
+    }
+
+    /// Clickable regions of the current screen, regions are registered while the screen is drawn
+    pub fn hit_regions(&self) -> &HitRegions {
+        &self.hit_regions
+    }
+
+    /// The GUI shows the thumbnail level of the level pack in the area until it is unset or the screen is changed
+    #[cfg(feature = "gui")]
+    pub fn set_level_pack_thumbnail_area(&mut self, level_pack_thumbnail_area: Option<LevelPackThumbnailArea>) {
+        self.level_pack_thumbnail_area = level_pack_thumbnail_area;
+    }
+
+    /// Returns true if another toast is shown afterward
+    fn update_toasts(&mut self) -> bool {
+        let Some(toast) = self.toasts.front_mut() else {
+            return false;
+        };
+
+        let is_new_toast = toast.shown_since.is_none();
+        if toast.shown_since.get_or_insert_with(Instant::now).elapsed() >= Toast::DURATION {
+            self.toasts.pop_front();
+
+            return true;
+        }
+
+        is_new_toast
+    }
+
+    pub fn exit(&mut self) {
+        self.should_exit = true;
+    }
+
+    fn on_found_secret_for_level_pack(&mut self, level_pack_index: usize, save_immediately: bool) -> Result<(), Box<dyn Error>> {
+        if level_pack_index == 1 && !self.found_secret_main_level_pack {
+            self.found_secret_main_level_pack = true;
+
+            let built_in_level_pack = Game::BUILT_IN_LEVEL_PACK_SECRET;
+            let (level_pack_data, source, warning) = patch::select_level_pack_data(&built_in_level_pack);
+            if let Some(message) = warning {
+                log_warn!("{message}");
+            }
+
+            let secret_level_pack = LevelPack::read_from_save_game(
+                built_in_level_pack.id, built_in_level_pack.path(), level_pack_data, false,
+
+                #[cfg(feature = "steam")]
+                None,
+            )?;
+            self.level_pack_sources.insert(built_in_level_pack.id.to_string(), source);
+
+            if save_immediately {
+                //Save immediately in order to keep secret level pack after game restart if not yet played
+                secret_level_pack.save_save_game_in_background(&self.save_writer)?;
+            }
+
+            self.level_packs.insert(4, secret_level_pack);
+        }
+
+        Ok(())
+    }
+
+    pub fn on_found_secret(&mut self) -> Result<(), Box<dyn Error>> {
+        self.on_found_secret_for_level_pack(self.current_level_pack_index, true)
+    }
+
+    pub fn play_sound_effect_ui_dialog_open(&mut self) {
+        self.play_sound_effect(audio::UI_DIALOG_OPEN_EFFECT);
+    }
+
+    pub fn play_sound_effect_ui_select(&mut self) {
+        self.play_sound_effect(audio::UI_SELECT_EFFECT);
+    }
+
+    pub fn play_sound_effect_ui_error(&mut self) {
+        self.play_sound_effect(audio::UI_ERROR_EFFECT);
+    }
+
+    pub fn play_sound_effect(&mut self, sound_effect: &'static SoundEffect) {
+        if let Some(audio_handler) = &mut self.audio_handler {
+            let _ = audio_handler.play_sound_effect(sound_effect);
+        }
+    }
+
+    /// Plays the sound effect of the current level pack or the built-in sound effect if the level pack does not contain it
+    pub fn play_pack_sound_effect(&mut self, id: PackSoundEffectId) {
+        let level_pack_path = self.get_current_level_pack().map(|level_pack| level_pack.path().to_string());
+
+        self.play_sound_effect_of_level_pack(id, level_pack_path);
+    }
+
+    /// Plays the sound effect of the selected editor level pack in order to test it during validation
+    pub fn play_editor_pack_sound_effect(&mut self, id: PackSoundEffectId) {
+        let level_pack_path = self.editor_state.get_current_level_pack().map(|level_pack| level_pack.path().to_string());
+
+        self.play_sound_effect_of_level_pack(id, level_pack_path);
+    }
+
+    fn play_sound_effect_of_level_pack(&mut self, id: PackSoundEffectId, level_pack_path: Option<String>) {
+        if self.audio_handler.is_none() {
+            return;
+        }
+
+        let sound_effect = match level_pack_path {
+            Some(level_pack_path) => {
+                if !self.pack_sound_effects.contains_key(&level_pack_path) {
+                    let folder = format!("{}{}", level_pack_path, audio::PACK_SOUND_EFFECTS_FOLDER_POSTFIX);
+                    let (pack_sound_effects, warnings) = audio::load_pack_sound_effects(Path::new(&folder));
+
+                    for message in warnings {
+                        log_warn!("{message}");
+                    }
+
+                    self.pack_sound_effects.insert(level_pack_path.clone(), pack_sound_effects);
+                }
+
+                self.pack_sound_effects[&level_pack_path].get(id)
+            },
+
+            None => id.built_in_sound_effect(),
+        };
+
+        self.play_sound_effect(sound_effect);
+    }
+
+    pub fn play_level_sound_effect(&mut self, sound_effect: LevelSoundEffect) {
+        if let Some(audio_handler) = &mut self.audio_handler {
+            let _ = audio_handler.play_sound_effect(match sound_effect {
+                LevelSoundEffect::BoxSink => audio::BOX_SINK_EFFECT,
+                LevelSoundEffect::KeyFall => audio::KEY_FALL_EFFECT,
+                LevelSoundEffect::DoorUnlocked => audio::DOOR_OPEN_EFFECT,
+                LevelSoundEffect::FloorBroken => audio::FLOOR_BROKEN_EFFECT,
+            });
+        }
+    }
+
+    /// Returns the startup flags which override the settings for the current session
+    pub fn startup_flags(&self) -> StartupFlags {
+        self.startup_flags
+    }
+
+    /// Returns false if the background music is disabled in the settings or muted by a startup flag
+    pub fn is_background_music_enabled(&self) -> bool {
+        self.settings.background_music && !self.startup_flags.mute_music()
+    }
+
+    pub fn current_background_music_id(&self) -> Option<BackgroundMusicId> {
+        self.current_background_music_id
+    }
+
+    pub fn stop_background_music(&mut self) {
+        self.current_background_music_id = None;
+
+        self.stop_background_music_internal();
+    }
+
+    fn stop_background_music_internal(&mut self) {
+        if let Some(audio_handler) = &self.audio_handler {
+            audio_handler.stop_background_music();
+        }
+    }
+
+    pub fn set_background_music_loop(&mut self, background_music: &BackgroundMusic) {
+        if self.current_background_music_id.is_some_and(|id| background_music.id() == id) {
+            return;
+        }
+
+        self.current_background_music_id = Some(background_music.id());
+
+        if !self.is_background_music_enabled() {
+            return;
+        }
+
+        if let Some(audio_handler) = &self.audio_handler {
+            let _ = audio_handler.set_background_music_loop(
+                background_music.intro_audio_data(),
+                background_music.main_loop_audio_data(),
+            );
+        }
+    }
+
+    /// Plays the background music which was selected for menus in the jukebox
+    pub fn set_menu_background_music_loop(&mut self) {
+        self.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(self.settings.menu_background_music_id));
+    }
+
+    /// Returns the name of the audio output device which is used or None if no device is available
+    pub fn audio_device_name(&self) -> Option<&str> {
+        self.audio_handler.as_ref().and_then(|audio_handler| audio_handler.device_name())
+    }
+
+    /// Returns true if the selected audio output device is not available and the default device is used instead
+    pub fn is_audio_device_fallback(&self) -> bool {
+        self.audio_handler.as_ref().is_some_and(|audio_handler| audio_handler.is_fallback())
+    }
+
+    /// Opens the selected audio output device again, the background music is restarted
+    fn reconnect_audio_device(&mut self) {
+        if self.startup_flags.no_audio() {
+            return;
+        }
+
+        //The old output stream is closed before the new output stream is opened
+        self.audio_handler = None;
+        self.audio_handler = AudioHandler::new(self.settings.audio_device()).ok();
+
+        if let Some(background_music_id) = self.current_background_music_id.take() {
+            self.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id));
+        }
+    }
+
+    /// Reconnects if the audio output device was disconnected or if a preferred device is available again
+    fn update_audio_device(&mut self) {
+        if self.startup_flags.no_audio() {
+            return;
+        }
+
+        let Some(audio_devices) = self.audio_device_monitor.poll() else {
+            return;
+        };
+
+        let should_reconnect = match &self.audio_handler {
+            Some(audio_handler) => audio_handler.should_reconnect(self.settings.audio_device(), &audio_devices),
+
+            //No device was available until now (e.g. a headset was plugged in after the game was started)
+            None => !audio_devices.names().is_empty(),
+        };
+        if !should_reconnect {
+            return;
+        }
+
+        let was_fallback = self.is_audio_device_fallback();
+        self.reconnect_audio_device();
+
+        if self.is_audio_device_fallback() && !was_fallback {
+            self.show_toast("Audio device disconnected: Using default device");
+        }
+    }
+
+    pub fn pause_background_music(&mut self) {
+        if let Some(audio_handler) = &self.audio_handler {
+            audio_handler.pause_background_music();
+        }
+    }
+
+    pub fn resume_background_music(&mut self) {
+        if let Some(audio_handler) = &self.audio_handler {
+            audio_handler.resume_background_music();
+        }
+    }
+
+    pub fn is_background_music_paused(&self) -> bool {
+        self.audio_handler.as_ref().is_some_and(|audio_handler| audio_handler.is_background_music_paused())
+    }
+
+    pub fn background_music_position(&self) -> Option<Duration> {
+        if self.current_background_music_id.is_none() || !self.is_background_music_enabled() {
+            return None;
+        }
+
+        self.audio_handler.as_ref().map(|audio_handler| audio_handler.background_music_position())
+    }
+
+    /// Seeks the current background music by the provided offset (Seeking is not supported for all tracks)
+    pub fn seek_background_music(&mut self, offset_secs: i64) {
+        let Some(position) = self.background_music_position() else {
+            return;
+        };
+
+        let position = if offset_secs < 0 {
+            position.saturating_sub(Duration::from_secs(offset_secs.unsigned_abs()))
+        }else {
+            position + Duration::from_secs(offset_secs as u64)
+        };
+
+        if let Some(audio_handler) = &self.audio_handler {
+            let _ = audio_handler.seek_background_music(position);
+        }
+    }
+
+    pub fn settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    pub fn editor_state(&self) -> &EditorState {
+        &self.editor_state
+    }
+
+    pub fn editor_state_mut(&mut self) -> &mut EditorState {
+        &mut self.editor_state
+    }
+
+    pub fn set_and_save_color_scheme_index(&mut self, color_scheme_index: usize) -> Result<(), Box<dyn Error>> {
+        self.settings.color_scheme_index = color_scheme_index;
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_tile_mode(&mut self, tile_mode: TileMode) -> Result<(), Box<dyn Error>> {
+        self.settings.tile_mode = tile_mode;
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_double_width_tiles(&mut self, double_width_tiles: bool) -> Result<(), Box<dyn Error>> {
+        self.settings.double_width_tiles = double_width_tiles;
+        console_extension::set_double_width_tiles(double_width_tiles);
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_large_tiles(&mut self, large_tiles: bool) -> Result<(), Box<dyn Error>> {
+        self.settings.large_tiles = large_tiles;
+        console_extension::set_large_tiles(large_tiles);
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_wall_variation(&mut self, wall_variation: bool) -> Result<(), Box<dyn Error>> {
+        self.settings.wall_variation = wall_variation;
+        console_extension::set_wall_variation(wall_variation);
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_background_music_enabled(&mut self, background_music: bool) -> Result<(), Box<dyn Error>> {
+        self.settings.background_music = background_music;
+
+        if self.is_background_music_enabled() {
+            if let Some(current_background_music_id) = self.current_background_music_id {
+                //Force restart current background music
+                self.stop_background_music();
+                self.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(current_background_music_id));
+            }
+        }else {
+            self.stop_background_music_internal();
+        }
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_menu_background_music_id(&mut self, menu_background_music_id: BackgroundMusicId) -> Result<(), Box<dyn Error>> {
+        self.settings.menu_background_music_id = menu_background_music_id;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_animation_speed(&mut self, animation_speed: AnimationSpeed) -> Result<(), Box<dyn Error>> {
+        self.settings.animation_speed = animation_speed;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_language(&mut self, language: Language) -> Result<(), Box<dyn Error>> {
+        self.settings.language = language;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_show_session_stats_on_exit(&mut self, show_session_stats_on_exit: bool) -> Result<(), Box<dyn Error>> {
+        self.settings.show_session_stats_on_exit = show_session_stats_on_exit;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_audio_device(&mut self, audio_device: Option<String>) -> Result<(), Box<dyn Error>> {
+        self.settings.audio_device = audio_device;
+        self.reconnect_audio_device();
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_relaxed_mode(&mut self, relaxed_mode: bool) -> Result<(), Box<dyn Error>> {
+        self.settings.relaxed_mode = relaxed_mode;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_mouse_right_button_action(&mut self, mouse_right_button_action: MouseButtonAction) -> Result<(), Box<dyn Error>> {
+        self.settings.mouse_right_button_action = mouse_right_button_action;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_mouse_middle_button_action(&mut self, mouse_middle_button_action: MouseButtonAction) -> Result<(), Box<dyn Error>> {
+        self.settings.mouse_middle_button_action = mouse_middle_button_action;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_mouse_wheel_action(&mut self, mouse_wheel_action: MouseWheelAction) -> Result<(), Box<dyn Error>> {
+        self.settings.mouse_wheel_action = mouse_wheel_action;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_arrow_key_priority(&mut self, arrow_key_priority: ArrowKeyPriority) -> Result<(), Box<dyn Error>> {
+        self.settings.arrow_key_priority = arrow_key_priority;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_control_preset(&mut self, control_preset: ControlPreset) -> Result<(), Box<dyn Error>> {
+        self.settings.control_preset = control_preset;
+        console_extension::set_control_preset(control_preset);
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_streamer_mode(&mut self, streamer_mode: StreamerMode) -> Result<(), Box<dyn Error>> {
+        self.settings.streamer_mode = streamer_mode;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_overlay_margin(&mut self, overlay_margin: usize) -> Result<(), Box<dyn Error>> {
+        self.settings.overlay_margin = overlay_margin;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_telemetry_consent(&mut self, telemetry_consent: TelemetryConsent) -> Result<(), Box<dyn Error>> {
+        self.settings.telemetry_consent = telemetry_consent;
+
+        //Statistics which were not uploaded are discarded if the telemetry plugin is disabled
+        self.set_plugin_enabled(PluginId::Telemetry, telemetry_consent.is_allowed());
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_log_verbosity(&mut self, log_verbosity: LogVerbosity) -> Result<(), Box<dyn Error>> {
+        self.settings.log_verbosity = log_verbosity;
+        logging::set_max_level(log_verbosity.max_log_level());
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+
+    pub fn set_and_save_credits_rolled(&mut self, credits_rolled: bool) -> Result<(), Box<dyn Error>> {
+        self.settings.credits_rolled = credits_rolled;
+
+        self.settings.save_to_file()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub struct Game<'a> {
+    console: &'a Console<'a>,
+
+    screens: HashMap<ScreenId, Box<dyn Screen>>,
+    help_page: HelpPage,
+
+    game_state: GameState,
+
+    //Level pack paths of the command line arguments, they are loaded again after profile switches
+    level_pack_args: Vec<String>,
+
+    key_input_queue: KeyInputQueue,
+    #[cfg(feature = "cli")]
+    bracketed_paste_parser: BracketedPasteParser,
+
+    has_reloaded_settings: bool,
+    //Set if the console content could have changed since the last draw
+    is_redraw_required: bool,
+}
+
+/// Without feature `cli` or `gui` only the constants and the save game folders of the game are available
+#[cfg(not(any(feature = "cli", feature = "gui")))]
+pub struct Game<'a> {
+    _console: std::marker::PhantomData<&'a ()>,
+}
+
+impl Game<'_> {
+    pub const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    pub const CONSOLE_MIN_WIDTH: usize = 74;
+    pub const CONSOLE_MIN_HEIGHT: usize = 23;
+
+    pub const LEVEL_MAX_WIDTH: usize = Self::CONSOLE_MIN_WIDTH;
+    pub const LEVEL_MAX_HEIGHT: usize = Self::CONSOLE_MIN_HEIGHT - 1;
+
+    const SAVE_GAME_FOLDER: &'static str = "SokoTerm";
+
+    /// Returns the save game folder of the active profile
+    pub fn get_or_create_save_game_folder() -> Result<OsString, Box<dyn Error>> {
+        let mut directory = Self::get_or_create_base_save_game_folder()?;
+
+        if let Some(profile) = profile::active_profile() {
+            directory.push(profile::PROFILES_FOLDER);
+            directory.push("/");
+            directory.push(profile);
+            std::fs::create_dir_all(&directory)?;
+
+            directory.push("/");
+        }
+
+        #[cfg(feature = "steam")]
+        {
+            let mut directory = directory.clone();
+            directory.push("SteamWorkshop");
+            std::fs::create_dir_all(&directory)?;
+        }
+
+        Ok(directory)
+    }
+
+    /// Returns the save game folder which contains the data shared between all profiles
+    ///
+    /// This is also the save game folder of the default profile
+    pub fn get_or_create_base_save_game_folder() -> Result<OsString, Box<dyn Error>> {
+        #[cfg(all(feature = "headless", not(feature = "steam")))]
+        if let Some(directory) = crate::io::headless::base_save_game_folder_override() {
+            std::fs::create_dir_all(&directory)?;
+
+            return Ok(directory);
+        }
+
+        let directory = save_folder::base_save_game_folder()?;
+        std::fs::create_dir_all(&directory)?;
+
+        Ok(directory)
+    }
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl <'a> Game<'a> {
+    pub const MAX_PASTED_TEXT_LEN: usize = 256;
+    pub const MAX_PASTED_LINE_COUNT: usize = 64;
+
+    const PLAYER_BACKGROUND_DELAY: i32 = 12;
+
+    const MAP_TUTORIAL: &'static str = include_str!("../resources/tutorial.lvl");
+    const MAP_MAIN: &'static str = include_str!("../resources/main.lvl");
+    const MAP_SPECIAL: &'static str = include_str!("../resources/special.lvl");
+    const MAP_DEMON: &'static str = include_str!("../resources/demon.lvl");
+
+    const MAP_SECRET: &'static str = include_str!("../resources/secret.lvl");
+
+    const BUILT_IN_LEVEL_PACKS: [BuiltInLevelPack; 4] = [
+        BuiltInLevelPack { id: "tutorial", data: Self::MAP_TUTORIAL, revision: 1 },
+        BuiltInLevelPack { id: "main", data: Self::MAP_MAIN, revision: 1 },
+        BuiltInLevelPack { id: "special", data: Self::MAP_SPECIAL, revision: 1 },
+        BuiltInLevelPack { id: "demon", data: Self::MAP_DEMON, revision: 1 },
+    ];
+
+    const BUILT_IN_LEVEL_PACK_SECRET: BuiltInLevelPack = BuiltInLevelPack { id: "secret", data: Self::MAP_SECRET, revision: 1 };
+
+    pub fn new(
+        console: &'a Console,
+
+        #[cfg(feature = "steam")]
+        steam_client: Client,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new_with_args(
+            console, std::env::args().skip(1).collect(),
+
+            #[cfg(feature = "steam")]
+            steam_client,
+        )
+    }
+
+    /// Creates the game with the provided command line arguments (Level pack paths and snapshot flag) instead of the
+    /// arguments of the process
+    pub(crate) fn new_with_args(
+        console: &'a Console,
+        args: Vec<String>,
+
+        #[cfg(feature = "steam")]
+        steam_client: Client,
+    ) -> Result<Self, Box<dyn Error>> {
+        //Startup flags are read before the game is created
+        let (_, args) = startup_flags::split_startup_flag_args(args);
+        let (snapshot_path, level_pack_args) = snapshot::split_snapshot_arg(args)?;
+
+        let (width, height) = console.get_console_size();
+        if width < Self::CONSOLE_MIN_WIDTH || height < Self::CONSOLE_MIN_HEIGHT {
+            return Err(Box::new(GameError::new(format!(
+                "Console is to small (Min: {} x {})!",
+                Self::CONSOLE_MIN_WIDTH,
+                Self::CONSOLE_MIN_HEIGHT
+            ))));
+        }
+
+        #[cfg(not(feature = "steam"))]
+        profile::activate_last_profile()?;
+
+        let screens = HashMap::from_iter([
+            (ScreenId::StartMenu, Box::new(ScreenStartMenu::new()) as Box<dyn Screen>),
+            (ScreenId::About, Box::new(ScreenAbout::new()) as Box<dyn Screen>),
+            (ScreenId::Settings, Box::new(ScreenSettings::new()) as Box<dyn Screen>),
+            (ScreenId::Jukebox, Box::new(ScreenJukebox::new()) as Box<dyn Screen>),
+            (ScreenId::Achievements, Box::new(ScreenAchievements::new()) as Box<dyn Screen>),
+            (ScreenId::Diagnostics, Box::new(ScreenDiagnostics::new()) as Box<dyn Screen>),
+
+            (ScreenId::SelectLevelPack, Box::new(ScreenSelectLevelPack::new()) as Box<dyn Screen>),
+            (ScreenId::SelectLevel, Box::new(ScreenSelectLevel::new()) as Box<dyn Screen>),
+            (ScreenId::WorkshopBrowser, Box::new(ScreenWorkshopBrowser::new()) as Box<dyn Screen>),
+            #[cfg(feature = "steam")]
+            (ScreenId::WorkshopSubscriptions, Box::new(ScreenWorkshopSubscriptions::new()) as Box<dyn Screen>),
+
+            (ScreenId::InGame, Box::new(ScreenInGame::new()) as Box<dyn Screen>),
+            (ScreenId::PackStory, Box::new(ScreenPackStory::new()) as Box<dyn Screen>),
+
+            (ScreenId::SelectLevelPackEditor, Box::new(ScreenSelectLevelPackEditor::new()) as Box<dyn Screen>),
+            (ScreenId::SelectLevelPackBackgroundMusic, Box::new(ScreenSelectLevelPackBackgroundMusic::new()) as Box<dyn Screen>),
+            (ScreenId::RecentlyDeleted, Box::new(ScreenRecentlyDeleted::new()) as Box<dyn Screen>),
+            (ScreenId::LevelPackEditor, Box::new(ScreenLevelPackEditor::new()) as Box<dyn Screen>),
+            (ScreenId::LevelEditor, Box::new(ScreenLevelEditor::new()) as Box<dyn Screen>),
+            #[cfg(feature = "gui")]
+            (ScreenId::Credits, Box::new(ScreenCredits::new()) as Box<dyn Screen>),
+            #[cfg(not(feature = "steam"))]
+            (ScreenId::SelectProfile, Box::new(ScreenSelectProfile::new()) as Box<dyn Screen>),
+        ]);
+
+        #[cfg_attr(feature = "steam", expect(unused_mut))]
+        let mut game_state = Self::load_game_state(
+            &level_pack_args,
+
+            #[cfg(feature = "steam")]
+            steam_client,
+        )?;
+
+        //Allow selecting another profile on startup if there are any
+        #[cfg(not(feature = "steam"))]
+        if !profile::profile_names()?.is_empty() {
+            game_state.set_screen(ScreenId::SelectProfile);
+        }
+
+        let mut game = Self {
+            console,
+
+            screens,
+            help_page: HelpPage::new(Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT),
+
+            game_state,
+
+            level_pack_args,
+
+            key_input_queue: KeyInputQueue::new(),
+            #[cfg(feature = "cli")]
+            bracketed_paste_parser: BracketedPasteParser::new(),
+
+            has_reloaded_settings: false,
+            is_redraw_required: true,
+        };
+
+        if let Some(snapshot_path) = snapshot_path {
+            let snapshot = GameStateSnapshot::read_from_file(&snapshot_path)?;
+            game.restore_snapshot(&snapshot)?;
+        }
+
+        Ok(game)
+    }
+
+    /// Creates a snapshot of the current screen, the selected level packs and levels, and the progress of the current level
+    pub(crate) fn create_snapshot(&self) -> GameStateSnapshot {
+        let mut snapshot = GameStateSnapshot::new(self.game_state.current_screen_id.clone());
+
+        if let Some(level_pack) = self.game_state.get_current_level_pack() {
+            snapshot = snapshot.with_level(level_pack.id(), self.game_state.current_level_index);
+        }
+
+        if let Some(level_pack) = self.game_state.editor_state.get_current_level_pack() {
+            snapshot = snapshot.with_editor_level(level_pack.id(), self.game_state.editor_state.selected_level_index);
+        }
+
+        let level_attempt = self.screens.get(&self.game_state.current_screen_id).
+                and_then(|screen| screen.level_attempt_snapshot());
+
+        snapshot.with_level_attempt(level_attempt)
+    }
+
+    /// Restores a snapshot which was created with [Game::create_snapshot]
+    ///
+    /// An error is returned if a level pack or level of the snapshot does not exist
+    pub(crate) fn restore_snapshot(&mut self, snapshot: &GameStateSnapshot) -> Result<(), Box<dyn Error>> {
+        let level_index = if let Some(level_pack_id) = snapshot.level_pack_id() {
+            let Some(level_pack_index) = self.game_state.level_packs.iter().
+                    position(|level_pack| level_pack.id() == level_pack_id) else {
+                return Err(Box::new(GameError::new(format!("Level pack \"{}\" of snapshot does not exist!", level_pack_id))));
+            };
+
+            if snapshot.level_index() >= self.game_state.level_packs[level_pack_index].level_count() {
+                return Err(Box::new(GameError::new(format!(
+                    "Level {} of level pack \"{}\" of snapshot does not exist!",
+                    snapshot.level_index() + 1, level_pack_id,
+                ))));
+            }
 
-    screens: HashMap<ScreenId, Box<dyn Screen>>,
-    help_page: HelpPage,
+            Some((level_pack_index, snapshot.level_index()))
+        }else {
+            None
+        };
 
-    game_state: GameState,
-}
+        let editor_level_index = if let Some(level_pack_id) = snapshot.editor_level_pack_id() {
+            let Some(level_pack_index) = self.game_state.editor_state.level_packs.iter().
+                    position(|level_pack| level_pack.id() == level_pack_id) else {
+                return Err(Box::new(GameError::new(format!("Editor level pack \"{}\" of snapshot does not exist!", level_pack_id))));
+            };
 
-impl <'a> Game<'a> {
-    pub const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+            //The level index is the level count in the level pack editor if the cursor is on the "Create level" entry
+            let max_level_index = self.game_state.editor_state.level_packs[level_pack_index].level_count();
+            let max_level_index = if *snapshot.screen_id() == ScreenId::LevelEditor {
+                max_level_index.saturating_sub(1)
+            }else {
+                max_level_index
+            };
 
-    pub const CONSOLE_MIN_WIDTH: usize = 74;
-    pub const CONSOLE_MIN_HEIGHT: usize = 23;
+            if snapshot.editor_level_index() > max_level_index {
+                return Err(Box::new(GameError::new(format!(
+                    "Level {} of editor level pack \"{}\" of snapshot does not exist!",
+                    snapshot.editor_level_index() + 1, level_pack_id,
+                ))));
+            }
 
-    pub const LEVEL_MAX_WIDTH: usize = Self::CONSOLE_MIN_WIDTH;
-    pub const LEVEL_MAX_HEIGHT: usize = Self::CONSOLE_MIN_HEIGHT - 1;
+            Some((level_pack_index, snapshot.editor_level_index()))
+        }else {
+            None
+        };
 
-    const PLAYER_BACKGROUND_DELAY: i32 = 12;
+        let is_level_required = matches!(snapshot.screen_id(), ScreenId::SelectLevel | ScreenId::InGame);
+        let is_editor_level_required = matches!(
+            snapshot.screen_id(),
+            ScreenId::SelectLevelPackBackgroundMusic | ScreenId::LevelPackEditor | ScreenId::LevelEditor,
+        );
+        if (is_level_required && level_index.is_none()) || (is_editor_level_required && editor_level_index.is_none()) {
+            return Err(Box::new(GameError::new(format!(
+                "The level pack is missing in the snapshot for screen \"{}\"!",
+                snapshot.screen_id().id(),
+            ))));
+        }
 
-    const SAVE_GAME_FOLDER: &'static str = "SokoTerm";
+        if let Some((level_pack_index, level_index)) = level_index {
+            self.game_state.set_level_pack_index(level_pack_index);
+            self.game_state.set_level_index(level_index);
+        }
 
-    const MAP_TUTORIAL: &'static str = include_str!("../resources/tutorial.lvl");
-    const MAP_MAIN: &'static str = include_str!("../resources/main.lvl");
-    const MAP_SPECIAL: &'static str = include_str!("../resources/special.lvl");
-    const MAP_DEMON: &'static str = include_str!("../resources/demon.lvl");
+        if let Some((level_pack_index, level_index)) = editor_level_index {
+            self.game_state.editor_state.set_level_pack_index(level_pack_index);
+            self.game_state.editor_state.set_level_index(level_index);
+        }
 
-    const MAP_SECRET: &'static str = include_str!("../resources/secret.lvl");
+        self.game_state.is_help = false;
+        self.game_state.close_all_dialogs();
 
-    pub fn get_or_create_save_game_folder() -> Result<OsString, Box<dyn Error>> {
-        let mut directory = if cfg!(windows) {
-            std::env::var_os("USERPROFILE").
-                    ok_or(GameError::new("%USERPROFILE% is not set!"))?
+        self.game_state.should_start_speedrun = false;
+        self.game_state.pending_snapshot_level_attempt = if *snapshot.screen_id() == ScreenId::InGame {
+            snapshot.level_attempt().cloned()
         }else {
-            std::env::var_os("HOME").
-                    ok_or(GameError::new("$HOME not set!"))?
+            None
         };
 
-        directory.push("/.jddev0/");
-        directory.push(Self::SAVE_GAME_FOLDER);
-        std::fs::create_dir_all(&directory)?;
+        self.game_state.set_screen(snapshot.screen_id().clone());
 
-        #[cfg(feature = "steam")]
-        {
-            let mut directory = directory.clone();
-            directory.push("/SteamWorkshop");
-            std::fs::create_dir_all(&directory)?;
-        }
+        Ok(())
+    }
 
-        directory.push("/");
-        Ok(directory)
+    /// Saves a snapshot of the current game state to the save game folder
+    fn save_snapshot(&mut self) {
+        let snapshot = self.create_snapshot();
+
+        match snapshot::snapshot_file_path().and_then(|path| snapshot.save_to_file(path)) {
+            Ok(_) => self.game_state.show_toast("Snapshot saved"),
+
+            Err(err) => {
+                self.game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save snapshot: {}", err)));
+            },
+        }
     }
 
-    pub fn new(
-        console: &'a Console,
+    /// Loads all level packs, editor level packs, and settings of the active profile
+    fn load_game_state(
+        level_pack_args: &[String],
 
         #[cfg(feature = "steam")]
         steam_client: Client,
-    ) -> Result<Self, Box<dyn Error>> {
-        let (width, height) = console.get_console_size();
-        if width < Self::CONSOLE_MIN_WIDTH || height < Self::CONSOLE_MIN_HEIGHT {
-            return Err(Box::new(GameError::new(format!(
-                "Console is to small (Min: {} x {})!",
-                Self::CONSOLE_MIN_WIDTH,
-                Self::CONSOLE_MIN_HEIGHT
-            ))));
-        }
-
+    ) -> Result<GameState, Box<dyn Error>> {
         let mut warning_message = String::new();
 
-        let screens = HashMap::from_iter([
-            (ScreenId::StartMenu, Box::new(ScreenStartMenu::new()) as Box<dyn Screen>),
-            (ScreenId::About, Box::new(ScreenAbout::new()) as Box<dyn Screen>),
-            (ScreenId::Settings, Box::new(ScreenSettings::new()) as Box<dyn Screen>),
-
-            (ScreenId::SelectLevelPack, Box::new(ScreenSelectLevelPack::new()) as Box<dyn Screen>),
-            (ScreenId::SelectLevel, Box::new(ScreenSelectLevel::new()) as Box<dyn Screen>),
+        //Custom tracks are shared between all profiles and are only loaded once (Before level packs and settings reference them)
+        if !audio::BACKGROUND_MUSIC_TRACKS.are_custom_tracks_set() {
+            let mut custom_music_folder = Game::get_or_create_base_save_game_folder()?;
+            custom_music_folder.push(audio::CUSTOM_MUSIC_FOLDER);
+            custom_music_folder.push("/");
+            std::fs::create_dir_all(&custom_music_folder)?;
 
-            (ScreenId::InGame, Box::new(ScreenInGame::new()) as Box<dyn Screen>),
+            let readme_file = Path::new(&custom_music_folder).join(audio::CUSTOM_MUSIC_README_FILE_NAME);
+            if !readme_file.exists() && let Err(err) = std::fs::write(&readme_file, audio::CUSTOM_MUSIC_README) {
+                log_warn!("Cannot write the README of the custom music folder: {err}");
+            }
 
-            (ScreenId::SelectLevelPackEditor, Box::new(ScreenSelectLevelPackEditor::new()) as Box<dyn Screen>),
-            (ScreenId::SelectLevelPackBackgroundMusic, Box::new(ScreenSelectLevelPackBackgroundMusic::new()) as Box<dyn Screen>),
-            (ScreenId::LevelPackEditor, Box::new(ScreenLevelPackEditor::new()) as Box<dyn Screen>),
-            (ScreenId::LevelEditor, Box::new(ScreenLevelEditor::new()) as Box<dyn Screen>),
-        ]);
+            let warnings = audio::BACKGROUND_MUSIC_TRACKS.load_custom_tracks(Path::new(&custom_music_folder))?;
 
-        let mut level_packs = Vec::with_capacity(LevelPack::MAX_LEVEL_PACK_COUNT);
-        level_packs.append(&mut vec![
-            LevelPack::read_from_save_game(
-                "tutorial", "built-in:tutorial", Self::MAP_TUTORIAL, false,
+            for message in warnings {
+                log_warn!("{message}");
 
-                #[cfg(feature = "steam")]
-                None,
-            )?,
-            LevelPack::read_from_save_game(
-                "main", "built-in:main", Self::MAP_MAIN, false,
+                warning_message += "\n";
+                warning_message += &message;
+            }
+        }
 
-                #[cfg(feature = "steam")]
-                None,
-            )?,
-            LevelPack::read_from_save_game(
-                "special", "built-in:special", Self::MAP_SPECIAL, false,
+        let mut level_packs = Vec::with_capacity(LevelPack::MAX_LEVEL_PACK_COUNT);
+        let mut level_pack_sources = HashMap::new();
+        for built_in_level_pack in Self::BUILT_IN_LEVEL_PACKS {
+            //Built-in level packs can be replaced by newer patches from the save game folder
+            let (level_pack_data, source, warning) = patch::select_level_pack_data(&built_in_level_pack);
+            if let Some(message) = warning {
+                log_warn!("{message}");
+
+                warning_message += "\n";
+                warning_message += &message;
+            }
 
-                #[cfg(feature = "steam")]
-                None,
-            )?,
-            LevelPack::read_from_save_game(
-                "demon", "built-in:demon", Self::MAP_DEMON, false,
+            level_packs.push(LevelPack::read_from_save_game(
+                built_in_level_pack.id, built_in_level_pack.path(), level_pack_data, false,
 
                 #[cfg(feature = "steam")]
                 None,
-            )?,
-        ]);
+            )?);
+            level_pack_sources.insert(built_in_level_pack.id.to_string(), source);
+        }
 
-        for arg in std::env::args().
-                skip(1) {
-            if !arg.ends_with(".lvl") {
+        for arg in level_pack_args {
+            let file_extension = if arg.ends_with(".lvl") {
+                ".lvl"
+            }else if arg.ends_with(ARCHIVE_FILE_EXTENSION) {
+                ARCHIVE_FILE_EXTENSION
+            }else {
                 return Err(Box::new(GameError::new(format!(
-                    "Invalid level pack \"{}\": The file extension of level pack must be \".lvl\"",
-                    arg
+                    "Invalid level pack \"{}\": The file extension of level pack must be \".lvl\" or \"{}\"",
+                    arg, ARCHIVE_FILE_EXTENSION,
                 ))));
-            }
+            };
 
             let level_pack_path = Path::new(&arg);
 
@@ -802,15 +2580,34 @@ impl <'a> Game<'a> {
                 )))),
             };
 
-            let mut level_pack_data = String::new();
-            if let Err(err) = level_pack_file.read_to_string(&mut level_pack_data) {
+            let mut level_pack_data = Vec::new();
+            if let Err(err) = level_pack_file.read_to_end(&mut level_pack_data) {
                 return Err(Box::new(GameError::new(format!(
                     "Error while loading level pack \"{}\": {}",
                     arg, err
                 ))));
             };
 
-            let level_pack_id = &level_pack_file_name[..level_pack_file_name.len() - 4];
+            //Archives are detected by their content, because they could have been renamed
+            let level_pack_data = if LevelPackArchive::is_archive(&level_pack_data) {
+                match LevelPackArchive::decode(&level_pack_data) {
+                    Ok(archive) => archive.level_pack_data().to_string(),
+                    Err(err) => return Err(Box::new(GameError::new(format!(
+                        "Error while loading level pack archive \"{}\": {}",
+                        arg, err
+                    )))),
+                }
+            }else {
+                match String::from_utf8(level_pack_data) {
+                    Ok(level_pack_data) => level_pack_data,
+                    Err(err) => return Err(Box::new(GameError::new(format!(
+                        "Error while loading level pack \"{}\": {}",
+                        arg, err
+                    )))),
+                }
+            };
+
+            let level_pack_id = &level_pack_file_name[..level_pack_file_name.len() - file_extension.len()];
             if level_pack_id.len() > LevelPack::MAX_LEVEL_PACK_NAME_LEN {
                 return Err(Box::new(GameError::new(format!(
                     "Error while loading level pack \"{}\": Level pack ID is too long (Max: {})",
@@ -829,12 +2626,27 @@ impl <'a> Game<'a> {
                 }
             }
 
-            level_packs.push(LevelPack::read_from_save_game(
-                level_pack_id, &arg, level_pack_data, false,
+            let level_pack = LevelPack::read_from_save_game(
+                level_pack_id, arg, level_pack_data, false,
 
                 #[cfg(feature = "steam")]
                 None,
-            )?);
+            );
+            match level_pack {
+                Ok(level_pack) => level_packs.push(level_pack),
+
+                //Level packs of newer versions are skipped instead of preventing the game from starting
+                Err(err) if err.is::<UnsupportedGameVersionError>() => {
+                    let message = format!("Level pack \"{arg}\" cannot be loaded:\n{err}");
+
+                    log_warn!("{message}");
+
+                    warning_message += "\n";
+                    warning_message += &message;
+                },
+
+                Err(err) => return Err(err),
+            }
         }
 
         if level_packs.len() > LevelPack::MAX_LEVEL_PACK_COUNT {
@@ -901,10 +2713,7 @@ impl <'a> Game<'a> {
                     Err(err) => {
                         let message = format!("Could not load editor level pack \"{file_name}\":\n{err}");
 
-                        #[cfg(feature = "gui")]
-                        {
-                            warn!(message);
-                        }
+                        log_warn!("{message}");
 
                         warning_message += "\n";
                         warning_message += &message;
@@ -955,36 +2764,97 @@ impl <'a> Game<'a> {
         editor_level_packs.sort_by_key(|level_pack| level_pack.id().to_string());
 
         let settings = GameSettings::read_from_file()?;
+        console_extension::set_double_width_tiles(settings.double_width_tiles);
+        console_extension::set_large_tiles(settings.large_tiles);
+        console_extension::set_wall_variation(settings.wall_variation);
+        console_extension::set_control_preset(settings.control_preset);
+        logging::set_max_level(settings.log_verbosity.max_log_level());
+
+        log_info!("Starting SokoTerm {}", Game::VERSION);
+        log_debug!("Loaded {} level packs and {} editor level packs", level_packs.len(), editor_level_packs.len());
+
+        let player_stats = PlayerStats::read_from_file()?;
+        let total_stats = TotalStats::read_from_file()?;
 
         let mut game_state = GameState::new(
             level_packs, editor_level_packs,
 
-            settings,
+            settings, player_stats, total_stats,
 
             #[cfg(feature = "steam")]
             steam_client,
         );
 
+        game_state.level_pack_sources = level_pack_sources;
+
+        //Level packs from the command line are checked for unsolvable levels
+        for level_pack in game_state.level_packs.iter().
+                filter(|level_pack| !level_pack.path().starts_with("built-in:")) {
+            game_state.background_validator.validate_level_pack(level_pack);
+        }
+
         let mut save_game_file = Game::get_or_create_save_game_folder()?;
         save_game_file.push("secret.lvl.sav");
         if std::fs::exists(&save_game_file).is_ok_and(|exists| exists) {
             game_state.on_found_secret_for_level_pack(1, false)?;
         }
 
-        game_state.set_background_music_loop(&audio::BACKGROUND_MUSIC_FIELDS_OF_ICE);
+        //Deleted editor level packs and levels are removed permanently after the retention period
+        if let Err(err) = trash::remove_expired_entries() {
+            let message = format!("Could not remove expired level packs and levels from the trash: {err}");
+
+            log_warn!("{message}");
+
+            warning_message += "\n";
+            warning_message += &message;
+        }
+
+        game_state.set_menu_background_music_loop();
 
         if !warning_message.is_empty() {
             game_state.open_dialog(Dialog::new_ok_error(format!("Warning!{warning_message}")));
         }
 
-        Ok(Self {
-            console,
+        Ok(game_state)
+    }
 
-            screens,
-            help_page: HelpPage::new(Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT),
+    /// Activates the profile and replaces the game state with the save games and settings of the profile
+    ///
+    /// The previous profile stays active if the profile cannot be loaded
+    #[cfg(not(feature = "steam"))]
+    fn switch_profile(&mut self, profile: Option<String>) {
+        //The save games of the new profile are read afterward
+        self.game_state.save_writer.flush();
 
-            game_state,
-        })
+        let previous_profile = profile::active_profile();
+        profile::set_active_profile(profile.clone());
+
+        let game_state = profile::save_last_profile(profile.as_deref()).
+                and_then(|_| Self::load_game_state(&self.level_pack_args));
+        match game_state {
+            Ok(game_state) => {
+                self.game_state = game_state;
+                self.game_state.set_screen(ScreenId::StartMenu);
+
+                self.has_reloaded_settings = true;
+            },
+
+            Err(err) => {
+                profile::set_active_profile(previous_profile);
+
+                self.game_state.open_dialog(Dialog::new_ok_error(format!(
+                    "Cannot load profile \"{}\": {}",
+                    profile::display_name(profile.as_deref()), err,
+                )));
+            },
+        }
+    }
+
+    /// Returns true once after the settings were replaced by the settings of another profile
+    ///
+    /// UI specific settings (e.g. the color scheme of the GUI version) must be applied again
+    pub fn take_reloaded_settings(&mut self) -> bool {
+        mem::replace(&mut self.has_reloaded_settings, false)
     }
 
     #[cfg(feature = "steam")]
@@ -993,7 +2863,7 @@ impl <'a> Game<'a> {
 
         item: QueryResult,
     ) -> Result<(), Box<dyn Error>> {
-        info!("Loading steam workshop level pack (ID: {}, Name: \"{}\")", item.published_file_id.0, item.title);
+        log_info!("Loading steam workshop level pack (ID: {}, Name: \"{}\")", item.published_file_id.0, item.title);
 
         let ascii_level_title = item.title.replace(|c: char| !c.is_ascii(), "?");
 
@@ -1056,10 +2926,19 @@ impl <'a> Game<'a> {
         let mut level_pack = match level_pack {
             Ok(level_pack) => level_pack,
 
-            Err(_) => {
+            Err(err) if err.is::<UnsupportedGameVersionError>() => {
+                return Err(Box::new(GameError::new(format!(
+                    "Steam workshop level pack (ID: {}, Name: \"{}\") cannot be loaded:\n{}",
+                    item.published_file_id.0, ascii_level_title,
+                    err,
+                ))));
+            },
+
+            Err(err) => {
                 return Err(Box::new(GameError::new(format!(
-                    "Error while loading level pack from steam workshop (ID: {}, Name: \"{}\")",
+                    "Error while loading level pack from steam workshop (ID: {}, Name: \"{}\"):\n{}",
                     item.published_file_id.0, ascii_level_title,
+                    err,
                 ))));
             },
         };
@@ -1080,6 +2959,7 @@ impl <'a> Game<'a> {
             }
         }
 
+        self.game_state.background_validator.validate_level_pack(&level_pack);
         self.game_state.level_packs.push(level_pack);
 
         Ok(())
@@ -1088,14 +2968,46 @@ impl <'a> Game<'a> {
     #[must_use]
     pub fn update(&mut self) -> bool {
         if self.game_state.should_exit {
+            //Pending save games must be written before the game exits
+            self.game_state.save_writer.flush();
+
             return true;
         }
 
-        if self.console.has_input() && let Some(key) = self.console.get_key() {
+        let save_failures = self.game_state.save_writer.take_failures();
+        if !save_failures.is_empty() {
+            self.game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", save_failures.join("\n"))));
+            self.is_redraw_required = true;
+        }
+
+        //All keys which were pressed since the last update are read at once for the arrow key priority
+        let mut pressed_keys = Vec::new();
+        while self.console.has_input() && let Some(key) = self.console.get_key() {
+            pressed_keys.push(key);
+        }
+
+        #[cfg(feature = "cli")]
+        let pressed_keys = {
+            let (pressed_keys, pasted_texts) = self.bracketed_paste_parser.parse(&pressed_keys);
+            for pasted_text in pasted_texts {
+                self.on_text_pasted(&pasted_text);
+                self.is_redraw_required = true;
+            }
+
+            pressed_keys
+        };
+
+        self.key_input_queue.push_keys(pressed_keys, self.game_state.settings.arrow_key_priority);
+
+        if let Some(key) = self.key_input_queue.pop() {
             self.update_key(key);
+            self.is_redraw_required = true;
         }
 
-        self.update_mouse();
+        if let Some(mouse_input) = self.console.get_mouse_input() {
+            self.update_mouse(mouse_input);
+            self.is_redraw_required = true;
+        }
 
         if !self.game_state.is_help {
             let screen = self.screens.get_mut(&self.game_state.current_screen_id);
@@ -1104,6 +3016,7 @@ impl <'a> Game<'a> {
                 //Otherwise "on_set_screen" would not be called for the new screen
                 while mem::replace(&mut self.game_state.should_call_on_set_screen, false) {
                     screen.on_set_screen(&mut self.game_state);
+                    self.is_redraw_required = true;
 
                     if self.game_state.should_call_on_set_screen {
                         //Change local current screen if screen was set in "on_set_screen"
@@ -1120,25 +3033,56 @@ impl <'a> Game<'a> {
 
                     screen.animate(&mut self.game_state);
                 }
+
+                if screen.is_animated(&self.game_state) {
+                    self.is_redraw_required = true;
+                }
             }else {
                 self.game_state.pending_animation_play_count = 0.0;
             }
         }
 
+        #[cfg(not(feature = "steam"))]
+        if let Some(profile) = self.game_state.pending_profile.take() {
+            self.switch_profile(profile);
+            self.is_redraw_required = true;
+        }
+
+        self.game_state.update_audio_device();
+
+        if self.game_state.update_toasts() {
+            self.is_redraw_required = true;
+        }
+
         //Player background
         self.game_state.player_background_tmp += 1;
         if self.game_state.player_background_tmp >= Self::PLAYER_BACKGROUND_DELAY + self.game_state.is_player_background as i32 {
             //If isPlayerBackground: wait an additional update (25 updates per second, every half
             //second: switch background/foreground colors [12 updates, 13 updates])
             self.game_state.player_background_tmp = 0;
-            self.game_state.is_player_background = !self.game_state.is_player_background;
+
+            //The player does not flash with reduced motion
+            if !self.game_state.startup_flags.reduced_motion() {
+                self.game_state.is_player_background = !self.game_state.is_player_background;
+            }
+
+            //Also redraws static screens periodically (e.g. for results of background tasks)
+            self.is_redraw_required = true;
         }
 
         false
     }
 
     fn update_key(&mut self, key: Key) {
-        if key == Key::F7 {
+        if key == Key::F6 {
+            self.game_state.play_sound_effect_ui_select();
+
+            if let Err(err) = self.game_state.set_and_save_language(self.game_state.settings.language.next_setting()) {
+                self.game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
+
+            return;
+        }else if key == Key::F7 {
             self.game_state.play_sound_effect_ui_select();
 
             if let Err(err) = self.game_state.set_and_save_animation_speed(self.game_state.settings.animation_speed.next_setting()) {
@@ -1147,12 +3091,31 @@ impl <'a> Game<'a> {
 
             return;
         }else if key == Key::F8 {
+            if self.game_state.startup_flags.mute_music() {
+                self.game_state.open_dialog(Dialog::new_ok_error("Background music is muted by a startup flag!"));
+
+                return;
+            }
+
             self.game_state.play_sound_effect_ui_select();
 
             if let Err(err) = self.game_state.set_and_save_background_music_enabled(!self.game_state.settings.background_music) {
                 self.game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
             }
 
+            return;
+        }else if key == Key::F4 {
+            self.game_state.play_sound_effect_ui_select();
+
+            self.game_state.is_hud_hidden = !self.game_state.is_hud_hidden;
+            if self.game_state.is_hud_hidden {
+                self.game_state.show_toast("HUD hidden (Show with F4)");
+            }
+
+            return;
+        }else if key == Key::F12 {
+            self.save_snapshot();
+
             return;
         }
 
@@ -1171,16 +3134,9 @@ impl <'a> Game<'a> {
             return;
         }
 
-        if let Some(dialog) = self.game_state.dialog.as_ref() {
+        if let Some(dialog) = self.game_state.dialogs.current_mut() {
             if let Some(dialog_selection) = dialog.on_key_pressed(key) {
-                self.game_state.close_dialog();
-
-                let screen = self.screens.get_mut(&self.game_state.current_screen_id);
-                if let Some(screen) = screen {
-                    screen.on_dialog_selection(&mut self.game_state, dialog_selection);
-
-                    self.game_state.play_sound_effect_ui_select();
-                }
+                self.on_dialog_selection(dialog_selection);
             }
 
             return;
@@ -1197,27 +3153,139 @@ impl <'a> Game<'a> {
         }
     }
 
-    fn update_mouse(&mut self) {
-        let Some((column, row)) = self.console.get_mouse_pos_clicked() else {
+    /// Forwards pasted text to the current screen
+    ///
+    /// All characters which are not printable ASCII characters are removed from each line
+    pub fn on_text_pasted(&mut self, text: &str) {
+        if self.game_state.is_help || self.game_state.is_dialog_opened() {
             return;
-        };
+        }
 
-        if self.game_state.is_help {
-            self.help_page.on_mouse_pressed(Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT, &mut self.game_state, column, row);
+        let text = text.lines().
+                take(Self::MAX_PASTED_LINE_COUNT).
+                map(|line| line.chars().
+                        filter(|c| c.is_ascii_graphic() || *c == ' ').
+                        take(Self::MAX_PASTED_TEXT_LEN).
+                        collect::<String>()).
+                collect::<Vec<_>>().
+                join("\n");
 
+        if text.trim().is_empty() {
             return;
         }
 
-        if let Some(dialog) = self.game_state.dialog.as_ref() {
-            if let Some(dialog_selection) = dialog.on_mouse_pressed(column, row) {
-                self.game_state.close_dialog();
+        if let Some(screen) = self.screens.get_mut(&self.game_state.current_screen_id) {
+            screen.on_text_pasted(&mut self.game_state, &text);
+        }
+    }
 
-                let screen = self.screens.get_mut(&self.game_state.current_screen_id);
-                if let Some(screen) = screen {
-                    screen.on_dialog_selection(&mut self.game_state, dialog_selection);
+    /// Called if the game should exit without a selection in the game (e.g. the window was closed)
+    ///
+    /// The game exits with the next update, unless the current screen asks the player to save unsaved changes first
+    pub fn on_exit_requested(&mut self) {
+        let can_exit = self.screens.get_mut(&self.game_state.current_screen_id).
+                is_none_or(|screen| screen.on_exit_requested(&mut self.game_state));
+        if can_exit {
+            self.game_state.exit();
+
+            return;
+        }
+
+        if self.game_state.is_help {
+            self.game_state.close_help_page();
+        }
+        self.is_redraw_required = true;
+    }
+
+    /// Returns true once after the console content could have changed, the CLI version only draws if a redraw is required
+    pub fn take_redraw_required(&mut self) -> bool {
+        mem::replace(&mut self.is_redraw_required, false)
+    }
+
+    /// Returns the text which should be written to the system clipboard
+    pub fn take_clipboard_text(&mut self) -> Option<String> {
+        self.game_state.clipboard_text.take()
+    }
+
+    /// Returns the area in which the thumbnail level of a level pack should be shown (None if it is covered by the help page or a dialog)
+    #[cfg(feature = "gui")]
+    pub fn level_pack_thumbnail_area(&self) -> Option<LevelPackThumbnailArea> {
+        if self.game_state.is_help || self.game_state.is_dialog_opened() {
+            return None;
+        }
+
+        self.game_state.level_pack_thumbnail_area
+    }
+
+    fn update_mouse(&mut self, mouse_input: MouseInput) {
+        match mouse_input {
+            MouseInput::Pressed { button: MouseButton::Left, column, row } => {
+                self.update_mouse_left_pressed(column, row);
+            },
+
+            MouseInput::Pressed { button, .. } => {
+                let action = if button == MouseButton::Right {
+                    self.game_state.settings.mouse_right_button_action
+                }else {
+                    self.game_state.settings.mouse_middle_button_action
+                };
+
+                match action {
+                    MouseButtonAction::Disabled => {},
+
+                    MouseButtonAction::Back => {
+                        self.update_key(Key::ESC);
+                    },
+
+                    MouseButtonAction::TogglePreview => {
+                        if self.game_state.is_help || self.game_state.is_dialog_opened() {
+                            return;
+                        }
+
+                        if let Some(screen) = self.screens.get_mut(&self.game_state.current_screen_id) {
+                            screen.on_toggle_preview(&mut self.game_state);
+                        }
+                    },
+                }
+            },
 
-                    self.game_state.play_sound_effect_ui_select();
+            MouseInput::Scrolled { delta, column, row } => {
+                let delta = match self.game_state.settings.mouse_wheel_action {
+                    MouseWheelAction::Scroll => delta,
+                    MouseWheelAction::InvertedScroll => -delta,
+                    MouseWheelAction::Disabled => return,
+                };
+
+                if self.game_state.is_help {
+                    //Every scrolled line switches the page
+                    for key in screen::utils::scroll_keys(delta) {
+                        self.help_page.on_key_pressed(&mut self.game_state, key);
+                    }
+
+                    return;
+                }
+
+                if self.game_state.is_dialog_opened() {
+                    return;
+                }
+
+                if let Some(screen) = self.screens.get_mut(&self.game_state.current_screen_id) {
+                    screen.on_mouse_scrolled(&mut self.game_state, column, row, delta);
                 }
+            },
+        }
+    }
+
+    fn update_mouse_left_pressed(&mut self, column: usize, row: usize) {
+        if self.game_state.is_help {
+            self.help_page.on_mouse_pressed(Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT, &mut self.game_state, column, row);
+
+            return;
+        }
+
+        if let Some(dialog) = self.game_state.dialogs.current() {
+            if let Some(dialog_selection) = dialog.on_mouse_pressed(column, row) {
+                self.on_dialog_selection(dialog_selection);
             }
 
             return;
@@ -1225,12 +3293,31 @@ impl <'a> Game<'a> {
 
         let screen = self.screens.get_mut(&self.game_state.current_screen_id);
         if let Some(screen) = screen {
-            screen.on_mouse_pressed(&mut self.game_state, column, row);
+            if let Some(click) = self.game_state.hit_regions.hit_test(column, row) {
+                screen.on_region_clicked(&mut self.game_state, click);
+            }else {
+                screen.on_mouse_pressed(&mut self.game_state, column, row);
+            }
+        }
+    }
+
+    /// The dialog is closed before the selection is processed: Dialogs which are opened by the screen are opened before queued dialogs
+    fn on_dialog_selection(&mut self, dialog_selection: DialogSelection) {
+        self.game_state.dialogs.close();
+
+        let screen = self.screens.get_mut(&self.game_state.current_screen_id);
+        if let Some(screen) = screen {
+            screen.on_dialog_selection(&mut self.game_state, dialog_selection);
+
+            self.game_state.play_sound_effect_ui_select();
         }
+
+        self.game_state.open_next_queued_dialog();
     }
 
     pub fn draw(&self) {
         self.console.repaint();
+        self.game_state.hit_regions.clear();
 
         if self.game_state.is_help {
             self.help_page.draw(self.console);
@@ -1243,7 +3330,11 @@ impl <'a> Game<'a> {
             screen.draw(&self.game_state, self.console);
         }
 
-        if let Some(dialog) = self.game_state.dialog.as_ref() {
+        if let Some(toast) = self.game_state.toasts.front() {
+            toast.draw(self.console);
+        }
+
+        if let Some(dialog) = self.game_state.dialogs.current() {
             dialog.draw(self.console);
         }
     }
@@ -0,0 +1,254 @@
+use std::error::Error;
+use std::fmt::Write as _;
+use crate::game::Game;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::GameState;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::events::{GameEventListener, LevelCompletedEvent};
+use crate::game::logging::log_warn;
+use crate::game::save_writer::SaveWriter;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::screen::ScreenId;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::screen::dialog::Dialog;
+
+#[cfg(test)]
+mod tests;
+
+const TOTAL_STATS_FILE_NAME: &str = "total_stats.data";
+
+/// Cumulative counters of all levels which were played with the current profile
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TotalStats {
+    moves: u64,
+    pushes: u64,
+    undos: u64,
+    levels_completed: u64,
+}
+
+impl TotalStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read_from_file() -> Result<Self, Box<dyn Error>> {
+        let mut total_stats_file = Game::get_or_create_save_game_folder()?;
+        total_stats_file.push(TOTAL_STATS_FILE_NAME);
+
+        if !std::fs::exists(&total_stats_file)? {
+            return Ok(Self::new());
+        }
+
+        Ok(Self::parse(&std::fs::read_to_string(&total_stats_file)?))
+    }
+
+    /// Parses the data of the total stats file, invalid lines are ignored
+    pub fn parse(data: &str) -> Self {
+        let mut total_stats = Self::new();
+
+        for line in data.split("\n").
+                filter(|line| !line.trim().is_empty()) {
+            let Some((key, value)) = line.split_once(" = ") else {
+                log_warn!("\"{TOTAL_STATS_FILE_NAME}\" contains invalid data: \"{line}\": Ignoring");
+
+                continue;
+            };
+
+            let is_valid = match TotalStat::from_id(key) {
+                Some(stat) => value.trim().parse().map(|value| *total_stats.value_mut(stat) = value).is_ok(),
+                None => false,
+            };
+
+            if !is_valid {
+                log_warn!("\"{TOTAL_STATS_FILE_NAME}\" contains invalid value for \"{key}\": \"{value}\": Ignoring");
+            }
+        }
+
+        total_stats
+    }
+
+    pub fn to_data(&self) -> String {
+        let mut data = String::new();
+
+        for stat in TotalStat::VALUES {
+            let _ = writeln!(data, "{} = {}", stat.id(), self.value(stat));
+        }
+
+        data
+    }
+
+    /// Queues the total stats to be written in the background (Write failures are reported by the save writer)
+    pub fn save_in_background(&self, save_writer: &SaveWriter) -> Result<(), Box<dyn Error>> {
+        let mut total_stats_file = Game::get_or_create_save_game_folder()?;
+        total_stats_file.push(TOTAL_STATS_FILE_NAME);
+
+        save_writer.write(total_stats_file, self.to_data());
+
+        Ok(())
+    }
+
+    pub fn value(&self, stat: TotalStat) -> u64 {
+        match stat {
+            TotalStat::Moves => self.moves,
+            TotalStat::Pushes => self.pushes,
+            TotalStat::Undos => self.undos,
+            TotalStat::LevelsCompleted => self.levels_completed,
+        }
+    }
+
+    fn value_mut(&mut self, stat: TotalStat) -> &mut u64 {
+        match stat {
+            TotalStat::Moves => &mut self.moves,
+            TotalStat::Pushes => &mut self.pushes,
+            TotalStat::Undos => &mut self.undos,
+            TotalStat::LevelsCompleted => &mut self.levels_completed,
+        }
+    }
+
+    /// Increments the counter of the provided stat
+    pub fn record(&mut self, stat: TotalStat) {
+        let value = self.value_mut(stat);
+        *value = value.saturating_add(1);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TotalStat {
+    Moves,
+    /// Moves in which a box was pushed
+    Pushes,
+    Undos,
+    LevelsCompleted,
+}
+
+impl TotalStat {
+    pub const VALUES: [TotalStat; 4] = [TotalStat::Moves, TotalStat::Pushes, TotalStat::Undos, TotalStat::LevelsCompleted];
+
+    /// Returns a stable identifier of this stat (Used in the total stats file)
+    pub fn id(self) -> &'static str {
+        match self {
+            TotalStat::Moves => "moves",
+            TotalStat::Pushes => "pushes",
+            TotalStat::Undos => "undos",
+            TotalStat::LevelsCompleted => "levels_completed",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::VALUES.into_iter().find(|stat| stat.id() == id)
+    }
+}
+
+/// An achievement which is unlocked if a total stat reaches the target value
+///
+/// Local achievements are stored with the total stats of the profile, the Steam build mirrors the stats to Steam
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalAchievement {
+    name: &'static str,
+    description: &'static str,
+    stat: TotalStat,
+    target: u64,
+}
+
+impl LocalAchievement {
+    const fn new(name: &'static str, description: &'static str, stat: TotalStat, target: u64) -> Self {
+        Self { name, description, stat, target }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    pub fn target(&self) -> u64 {
+        self.target
+    }
+
+    /// Returns the progress towards the target value (Capped at the target value)
+    pub fn progress(&self, total_stats: &TotalStats) -> u64 {
+        total_stats.value(self.stat).min(self.target)
+    }
+
+    pub fn is_unlocked(&self, total_stats: &TotalStats) -> bool {
+        total_stats.value(self.stat) >= self.target
+    }
+}
+
+pub const LOCAL_ACHIEVEMENTS: [LocalAchievement; 9] = [
+    LocalAchievement::new("First Steps", "Complete 10 levels", TotalStat::LevelsCompleted, 10),
+    LocalAchievement::new("Centurion", "Complete 100 levels", TotalStat::LevelsCompleted, 100),
+    LocalAchievement::new("Warehouse Veteran", "Complete 500 levels", TotalStat::LevelsCompleted, 500),
+    LocalAchievement::new("Box Mover", "Push boxes 1,000 times", TotalStat::Pushes, 1000),
+    LocalAchievement::new("Heavy Lifter", "Push boxes 10,000 times", TotalStat::Pushes, 10000),
+    LocalAchievement::new("Long Walk", "Make 10,000 moves", TotalStat::Moves, 10000),
+    LocalAchievement::new("Marathon", "Make 100,000 moves", TotalStat::Moves, 100000),
+    LocalAchievement::new("Second Thoughts", "Undo 100 times", TotalStat::Undos, 100),
+    LocalAchievement::new("Time Traveler", "Undo 1,000 times", TotalStat::Undos, 1000),
+];
+
+/// Owns the total stats: Records completed levels and saves the total stats after a level is left
+#[cfg(any(feature = "cli", feature = "gui"))]
+#[derive(Debug, Default)]
+pub struct TotalStatsPlugin {
+    total_stats: TotalStats,
+    is_changed: bool,
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl TotalStatsPlugin {
+    pub fn new(total_stats: TotalStats) -> Self {
+        Self {
+            total_stats,
+            is_changed: false,
+        }
+    }
+
+    pub fn total_stats(&self) -> &TotalStats {
+        &self.total_stats
+    }
+
+    /// Increments the total stat, a toast is shown for every local achievement which is unlocked by this
+    pub fn record(&mut self, game_state: &mut GameState, stat: TotalStat) {
+        let old_total_stats = self.total_stats;
+        self.total_stats.record(stat);
+        self.is_changed = true;
+
+        for achievement in LOCAL_ACHIEVEMENTS.iter().
+                filter(|achievement| achievement.is_unlocked(&self.total_stats) && !achievement.is_unlocked(&old_total_stats)) {
+            game_state.show_toast(format!("Achievement unlocked: {}", achievement.name()));
+        }
+    }
+
+    /// Writes the total stats if they were changed since they were saved last
+    fn save(&mut self, game_state: &mut GameState) {
+        if !self.is_changed {
+            return;
+        }
+        self.is_changed = false;
+
+        if let Err(err) = self.total_stats.save_in_background(&game_state.save_writer) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save total stats: {}", err)));
+        }
+
+        #[cfg(feature = "steam")]
+        crate::game::steam::achievement::store_total_stats(&game_state.steam_client, &self.total_stats);
+    }
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl GameEventListener for TotalStatsPlugin {
+    fn on_screen_changed(&mut self, game_state: &mut GameState, screen_id: &ScreenId) {
+        //Moves and undos are only saved after the level was left
+        if *screen_id != ScreenId::InGame {
+            self.save(game_state);
+        }
+    }
+
+    fn on_level_completed(&mut self, game_state: &mut GameState, _: &LevelCompletedEvent) {
+        self.record(game_state, TotalStat::LevelsCompleted);
+        self.save(game_state);
+    }
+}
@@ -0,0 +1,36 @@
+use crate::game::achievements::*;
+
+#[test]
+fn total_stats_data_round_trip() {
+    let mut total_stats = TotalStats::new();
+    for _ in 0..3 {
+        total_stats.record(TotalStat::Moves);
+    }
+    total_stats.record(TotalStat::Pushes);
+    total_stats.record(TotalStat::LevelsCompleted);
+
+    assert_eq!(total_stats.value(TotalStat::Moves), 3);
+    assert_eq!(total_stats.value(TotalStat::Undos), 0);
+    assert_eq!(TotalStats::parse(&total_stats.to_data()), total_stats);
+
+    let total_stats = TotalStats::parse("moves = 12\npushes = -1\nunknown = 5\ninvalid\nundos = 7\n");
+    assert_eq!(total_stats.value(TotalStat::Moves), 12);
+    assert_eq!(total_stats.value(TotalStat::Pushes), 0);
+    assert_eq!(total_stats.value(TotalStat::Undos), 7);
+}
+
+#[test]
+fn local_achievement_progress_is_capped_at_target() {
+    let achievement = LOCAL_ACHIEVEMENTS.iter().
+            find(|achievement| achievement.name() == "Second Thoughts").
+            unwrap();
+
+    let mut total_stats = TotalStats::parse("undos = 99\n");
+    assert_eq!(achievement.progress(&total_stats), 99);
+    assert!(!achievement.is_unlocked(&total_stats));
+
+    total_stats.record(TotalStat::Undos);
+    total_stats.record(TotalStat::Undos);
+    assert_eq!(achievement.progress(&total_stats), achievement.target());
+    assert!(achievement.is_unlocked(&total_stats));
+}
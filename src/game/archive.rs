@@ -0,0 +1,226 @@
+use std::error::Error;
+use std::io::Write;
+use flate2::{Compression, Decompress, FlushDecompress, Status};
+use flate2::write::ZlibEncoder;
+use crate::game::GameError;
+
+#[cfg(test)]
+mod tests;
+
+/// File extension of compressed level pack archives
+pub const ARCHIVE_FILE_EXTENSION: &str = ".lvlz";
+
+const ARCHIVE_MAGIC: &[u8; 8] = b"SOKOLVLZ";
+const ARCHIVE_FORMAT_VERSION: u8 = 1;
+
+/// Max size of all decompressed entries (Protects against archives which decompress to huge amounts of data)
+const MAX_DECOMPRESSED_SIZE: u64 = 16 * 1024 * 1024;
+const DECOMPRESS_BUFFER_SIZE: usize = 64 * 1024;
+
+const LEVEL_PACK_ENTRY_NAME: &str = "pack.lvl";
+const THUMBNAIL_ENTRY_NAME: &str = "thumbnail.txt";
+const ASSET_ENTRY_PREFIX: &str = "assets/";
+
+/// A level pack in the text format with additional files which is stored as a single compressed file
+///
+/// Format: `SOKOLVLZ`, format version (u8), zlib stream of entries (u16 name length, name, u32 data length, data; little endian)
+///
+/// Entries with unknown names are ignored, they could be added by newer game versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelPackArchive {
+    level_pack_data: String,
+    thumbnail: Option<String>,
+    //Asset file name -> Data
+    assets: Vec<(String, Vec<u8>)>,
+}
+
+impl LevelPackArchive {
+    pub fn new(level_pack_data: impl Into<String>) -> Self {
+        Self {
+            level_pack_data: level_pack_data.into(),
+            thumbnail: None,
+            assets: Vec::new(),
+        }
+    }
+
+    /// Checks if the data starts with the archive header (Level pack files in the text format never do)
+    pub fn is_archive(data: &[u8]) -> bool {
+        data.starts_with(ARCHIVE_MAGIC)
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let Some(data) = data.strip_prefix(ARCHIVE_MAGIC) else {
+            return Err(Box::new(GameError::new("Not a level pack archive")));
+        };
+
+        let Some((&format_version, compressed_data)) = data.split_first() else {
+            return Err(Box::new(GameError::new("The level pack archive is truncated")));
+        };
+        if format_version != ARCHIVE_FORMAT_VERSION {
+            return Err(Box::new(GameError::new(format!(
+                "Unsupported level pack archive version {} (Supported: {})",
+                format_version, ARCHIVE_FORMAT_VERSION,
+            ))));
+        }
+
+        let entries_data = Self::decompress(compressed_data)?;
+
+        let mut level_pack_data = None;
+        let mut thumbnail = None;
+        let mut assets = Vec::new();
+
+        let mut entries_data = entries_data.as_slice();
+        while !entries_data.is_empty() {
+            let name_len = u16::from_le_bytes(Self::take_bytes(&mut entries_data, 2)?.try_into().unwrap()) as usize;
+            let name = String::from_utf8(Self::take_bytes(&mut entries_data, name_len)?.to_vec()).
+                    map_err(|_| GameError::new("Invalid entry name in the level pack archive"))?;
+
+            let data_len = u32::from_le_bytes(Self::take_bytes(&mut entries_data, 4)?.try_into().unwrap()) as usize;
+            let data = Self::take_bytes(&mut entries_data, data_len)?.to_vec();
+
+            match name.as_str() {
+                LEVEL_PACK_ENTRY_NAME => {
+                    level_pack_data = Some(String::from_utf8(data).
+                            map_err(|_| GameError::new("The level pack in the archive is not valid UTF-8"))?);
+                },
+
+                THUMBNAIL_ENTRY_NAME => {
+                    thumbnail = Some(String::from_utf8(data).
+                            map_err(|_| GameError::new("The thumbnail in the archive is not valid UTF-8"))?);
+                },
+
+                name if let Some(asset_name) = name.strip_prefix(ASSET_ENTRY_PREFIX) => {
+                    if !is_valid_asset_name(asset_name) {
+                        return Err(Box::new(GameError::new(format!(
+                            "Invalid asset name \"{}\" in the level pack archive", asset_name,
+                        ))));
+                    }
+
+                    assets.push((asset_name.to_string(), data));
+                },
+
+                //Unknown entries of newer game versions are ignored
+                _ => {},
+            }
+        }
+
+        let Some(level_pack_data) = level_pack_data else {
+            return Err(Box::new(GameError::new(format!(
+                "The level pack archive does not contain \"{}\"", LEVEL_PACK_ENTRY_NAME,
+            ))));
+        };
+
+        Ok(Self {
+            level_pack_data,
+            thumbnail,
+            assets,
+        })
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+
+        Self::write_entry(&mut encoder, LEVEL_PACK_ENTRY_NAME, self.level_pack_data.as_bytes())?;
+
+        if let Some(thumbnail) = &self.thumbnail {
+            Self::write_entry(&mut encoder, THUMBNAIL_ENTRY_NAME, thumbnail.as_bytes())?;
+        }
+
+        for (name, data) in self.assets.iter() {
+            Self::write_entry(&mut encoder, &format!("{}{}", ASSET_ENTRY_PREFIX, name), data)?;
+        }
+
+        let mut archive = ARCHIVE_MAGIC.to_vec();
+        archive.push(ARCHIVE_FORMAT_VERSION);
+        archive.append(&mut encoder.finish()?);
+
+        Ok(archive)
+    }
+
+    pub fn level_pack_data(&self) -> &str {
+        &self.level_pack_data
+    }
+
+    /// Returns the thumbnail level in the text format (Allows previews without parsing the level pack)
+    pub fn thumbnail(&self) -> Option<&str> {
+        self.thumbnail.as_deref()
+    }
+
+    pub fn set_thumbnail(&mut self, thumbnail: Option<String>) {
+        self.thumbnail = thumbnail;
+    }
+
+    /// Returns the custom assets of the level pack (The files of the sound effects folder of the level pack)
+    pub fn assets(&self) -> &[(String, Vec<u8>)] {
+        &self.assets
+    }
+
+    /// Adds a custom asset, the name must be a file name without folders
+    pub fn add_asset(&mut self, name: impl Into<String>, data: Vec<u8>) -> Result<(), GameError> {
+        let name = name.into();
+        if !is_valid_asset_name(&name) {
+            return Err(GameError::new(format!("Invalid asset name \"{}\"", name)));
+        }
+
+        self.assets.push((name, data));
+
+        Ok(())
+    }
+
+    /// Decompresses the zlib stream, the stream must be complete (Including its checksum)
+    fn decompress(compressed_data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut decompress = Decompress::new(true);
+        let mut data = Vec::new();
+
+        loop {
+            let (total_in, total_out) = (decompress.total_in(), decompress.total_out());
+
+            data.reserve(DECOMPRESS_BUFFER_SIZE);
+            let status = decompress.decompress_vec(&compressed_data[total_in as usize..], &mut data, FlushDecompress::Finish)?;
+            if status == Status::StreamEnd {
+                return Ok(data);
+            }
+
+            if data.len() as u64 > MAX_DECOMPRESSED_SIZE {
+                return Err(Box::new(GameError::new(format!(
+                    "The level pack archive is too large (Max: {} bytes)", MAX_DECOMPRESSED_SIZE,
+                ))));
+            }
+
+            //No progress is possible if the end of the stream is missing
+            if decompress.total_in() == total_in && decompress.total_out() == total_out {
+                return Err(Box::new(GameError::new("The level pack archive is truncated")));
+            }
+        }
+    }
+
+    fn write_entry(writer: &mut impl Write, name: &str, data: &[u8]) -> Result<(), Box<dyn Error>> {
+        let name_len = u16::try_from(name.len()).
+                map_err(|_| GameError::new(format!("The archive entry name \"{}\" is too long", name)))?;
+        let data_len = u32::try_from(data.len()).
+                map_err(|_| GameError::new(format!("The archive entry \"{}\" is too large", name)))?;
+
+        writer.write_all(&name_len.to_le_bytes())?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&data_len.to_le_bytes())?;
+        writer.write_all(data)?;
+
+        Ok(())
+    }
+
+    fn take_bytes<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], GameError> {
+        if data.len() < len {
+            return Err(GameError::new("The level pack archive is truncated"));
+        }
+
+        let (bytes, remaining) = data.split_at(len);
+        *data = remaining;
+
+        Ok(bytes)
+    }
+}
+
+/// Asset names are file names without folders (Assets are written into the folder of the imported level pack)
+fn is_valid_asset_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', ':'])
+}
@@ -0,0 +1,71 @@
+use crate::game::archive::*;
+
+#[test]
+fn archive_round_trip() {
+    let archive = LevelPackArchive::new("Name: Test\nLevels: 1\n\nw: 4, h: 1\np@-x\n");
+
+    let data = archive.encode().unwrap();
+    assert!(LevelPackArchive::is_archive(&data));
+
+    assert_eq!(LevelPackArchive::decode(&data).unwrap(), archive);
+}
+
+#[test]
+fn archive_round_trip_with_thumbnail_and_assets() {
+    let mut archive = LevelPackArchive::new("Name: Test\nLevels: 1\n\nw: 4, h: 1\np@-x\n");
+    archive.set_thumbnail(Some("w: 4, h: 1\np@-x\n".to_string()));
+    archive.add_asset("step.ogg", vec![0, 1, 2, 255]).unwrap();
+    archive.add_asset("push.wav", Vec::new()).unwrap();
+
+    let data = archive.encode().unwrap();
+
+    let decoded = LevelPackArchive::decode(&data).unwrap();
+    assert_eq!(decoded.thumbnail(), Some("w: 4, h: 1\np@-x\n"));
+    assert_eq!(decoded.assets(), &[
+        ("step.ogg".to_string(), vec![0, 1, 2, 255]),
+        ("push.wav".to_string(), Vec::new()),
+    ]);
+    assert_eq!(decoded, archive);
+}
+
+#[test]
+fn archive_rejects_invalid_asset_names() {
+    let mut archive = LevelPackArchive::new("Levels: 0\n");
+    for name in ["", ".", "..", "../step.ogg", "sounds/step.ogg", "sounds\\step.ogg", "C:step.ogg"] {
+        assert!(archive.add_asset(name, Vec::new()).is_err(), "{name:?}");
+    }
+    assert!(archive.assets().is_empty());
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    LevelPackArchive::write_entry(&mut encoder, LEVEL_PACK_ENTRY_NAME, b"Levels: 0\n").unwrap();
+    LevelPackArchive::write_entry(&mut encoder, "assets/../step.ogg", &[0, 1, 2]).unwrap();
+
+    let mut data = ARCHIVE_MAGIC.to_vec();
+    data.push(ARCHIVE_FORMAT_VERSION);
+    data.append(&mut encoder.finish().unwrap());
+
+    assert!(LevelPackArchive::decode(&data).is_err());
+}
+
+#[test]
+fn archive_decode_rejects_invalid_data() {
+    assert!(!LevelPackArchive::is_archive(b"Name: Test\nLevels: 0\n"));
+    assert!(LevelPackArchive::decode(b"Name: Test\nLevels: 0\n").is_err());
+
+    let data = LevelPackArchive::new("Levels: 0\n").encode().unwrap();
+    assert!(LevelPackArchive::decode(&data[..data.len() - 4]).is_err());
+}
+
+#[test]
+fn archive_decode_ignores_unknown_entries() {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    LevelPackArchive::write_entry(&mut encoder, "future.bin", b"p@-x\n").unwrap();
+    LevelPackArchive::write_entry(&mut encoder, LEVEL_PACK_ENTRY_NAME, b"Levels: 0\n").unwrap();
+    LevelPackArchive::write_entry(&mut encoder, "music/track.ogg", &[0, 1, 2]).unwrap();
+
+    let mut data = ARCHIVE_MAGIC.to_vec();
+    data.push(ARCHIVE_FORMAT_VERSION);
+    data.append(&mut encoder.finish().unwrap());
+
+    assert_eq!(LevelPackArchive::decode(&data).unwrap(), LevelPackArchive::new("Levels: 0\n"));
+}
@@ -1,11 +1,21 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::io::Cursor;
 use std::num::NonZeroUsize;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use rand::prelude::IndexedRandom;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use crate::game::GameError;
+use crate::utils;
+
+#[cfg(test)]
+mod tests;
 
 pub const UI_SELECT_EFFECT: &SoundEffect = &SoundEffect::new(&[
     include_bytes!("../../assets/audio/ui_select.ogg"),
@@ -37,6 +47,10 @@ pub const NO_PATH_EFFECT: &SoundEffect = &SoundEffect::new(&[
 pub const LEVEL_COMPLETE_EFFECT: &SoundEffect = &SoundEffect::new(&[
     include_bytes!("../../assets/audio/level_complete.ogg"),
 ]);
+//Played faster than the recording to sound brighter than a normal level completion
+pub const NEW_RECORD_EFFECT: &SoundEffect = &SoundEffect::new(&[
+    include_bytes!("../../assets/audio/level_complete.ogg"),
+]).with_speed(1.25);
 pub const LEVEL_PACK_COMPLETE_EFFECT: &SoundEffect = &SoundEffect::new(&[
     include_bytes!("../../assets/audio/level_pack_complete.ogg"),
 ]);
@@ -49,9 +63,11 @@ pub const STEP_EFFECT: &SoundEffect = &SoundEffect::new(&[
     include_bytes!("../../assets/audio/step_3.ogg"),
 ]);
 
-pub const BOX_FALL_EFFECT: &SoundEffect = &SoundEffect::new(&[
+//The box fall recording is reused intentionally: Boxes still fall into holes, only the animation shows them sinking
+//It is played slower than the recording to sound deeper while the box sinks
+pub const BOX_SINK_EFFECT: &SoundEffect = &SoundEffect::new(&[
     include_bytes!("../../assets/audio/box_fall.ogg"),
-]);
+]).with_speed(0.8);
 pub const KEY_FALL_EFFECT: &SoundEffect = &SoundEffect::new(&[
     include_bytes!("../../assets/audio/key_fall.ogg"),
 ]);
@@ -62,7 +78,41 @@ pub const FLOOR_BROKEN_EFFECT: &SoundEffect = &SoundEffect::new(&[
     include_bytes!("../../assets/audio/floor_broken.ogg"),
 ]);
 
-pub const BACKGROUND_MUSIC_TRACKS: BackgroundMusicTracks<6> = BackgroundMusicTracks::new([
+/// Folder in the save game folder (Shared between all profiles) from which custom background music tracks are loaded
+pub const CUSTOM_MUSIC_FOLDER: &str = "music";
+
+/// IDs of custom background music tracks start at this value (All built-in tracks have lower IDs)
+pub const CUSTOM_TRACK_MIN_ID: usize = 1000;
+
+/// File in the custom music folder which explains how custom tracks are loaded (It is ignored by the track loader)
+pub const CUSTOM_MUSIC_README_FILE_NAME: &str = "README.txt";
+pub const CUSTOM_MUSIC_README: &str = "\
+Custom background music tracks of SokoTerm
+
+Audio files in this folder are added to the background music tracks when the game starts.
+Supported formats: .ogg, .oga, .mp3, .wav, .flac (Max 100 tracks, max 64 MiB per file)
+File names in the format \"Creator - Title.ogg\" are shown with the creator.
+
+The ID of a custom track is calculated from its file name (including the file extension):
+1000 + (64-bit FNV-1a hash of the file name % 1000000)
+Settings and editor level packs store this ID, renaming a file breaks all references to the track.
+";
+
+const CUSTOM_TRACK_FILE_EXTENSIONS: [&str; 5] = ["ogg", "oga", "mp3", "wav", "flac"];
+const MAX_CUSTOM_TRACK_FILE_SIZE: u64 = 64 * 1024 * 1024;
+const MAX_CUSTOM_TRACK_COUNT: usize = 100;
+//Longer names of custom tracks are cut off to fit into the track lists
+/// Postfix of the folder next to the level pack file which contains the sound effects of the level pack (e.g. "pack.lvl.sounds")
+pub const PACK_SOUND_EFFECTS_FOLDER_POSTFIX: &str = ".sounds";
+
+const MAX_PACK_SOUND_EFFECT_FILE_SIZE: u64 = 1024 * 1024;
+const MAX_PACK_SOUND_EFFECT_DURATION: Duration = Duration::from_secs(5);
+
+const MAX_CUSTOM_TRACK_NAME_LEN: usize = 35;
+const MAX_CUSTOM_TRACK_CREATOR_LEN: usize = 20;
+
+//Static, because the custom tracks are added at runtime
+pub static BACKGROUND_MUSIC_TRACKS: BackgroundMusicTracks<6> = BackgroundMusicTracks::new([
     &BACKGROUND_MUSIC_FIELDS_OF_ICE,
     &BACKGROUND_MUSIC_LEAP,
     &BACKGROUND_MUSIC_TRIANGULAR,
@@ -122,6 +172,7 @@ pub const BACKGROUND_MUSIC_CATCHY: BackgroundMusic = BackgroundMusic {
 #[derive(Debug)]
 pub struct SoundEffect {
     sound_effects: &'static [&'static [u8]],
+    speed: f32,
 }
 
 impl SoundEffect {
@@ -130,7 +181,12 @@ impl SoundEffect {
             panic!("At least one sound effect must be present!");
         }
 
-        Self { sound_effects }
+        Self { sound_effects, speed: 1.0 }
+    }
+
+    /// Sets the playback speed (Lower values also lower the pitch)
+    const fn with_speed(self, speed: f32) -> Self {
+        Self { speed, ..self }
     }
 
     pub fn sound_effects(&self) -> &'static [&'static [u8]] {
@@ -138,6 +194,52 @@ impl SoundEffect {
     }
 }
 
+/// Sound effects which can be replaced by audio files of a level pack
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum PackSoundEffectId {
+    Step,
+    Push,
+    LevelComplete,
+}
+
+impl PackSoundEffectId {
+    pub const VALUES: [PackSoundEffectId; 3] = [PackSoundEffectId::Step, PackSoundEffectId::Push, PackSoundEffectId::LevelComplete];
+
+    /// Returns the name of the audio file of the level pack without the file extension
+    pub fn file_stem(self) -> &'static str {
+        match self {
+            PackSoundEffectId::Step => "step",
+            PackSoundEffectId::Push => "push",
+            PackSoundEffectId::LevelComplete => "level_complete",
+        }
+    }
+
+    /// Returns the sound effect which is used if the level pack does not contain this sound effect
+    pub fn built_in_sound_effect(self) -> &'static SoundEffect {
+        match self {
+            PackSoundEffectId::Step | PackSoundEffectId::Push => STEP_EFFECT,
+            PackSoundEffectId::LevelComplete => LEVEL_COMPLETE_EFFECT,
+        }
+    }
+}
+
+/// Sound effects of a level pack which replace the built-in sound effects
+#[derive(Debug, Default)]
+pub struct PackSoundEffects {
+    sound_effects: HashMap<PackSoundEffectId, &'static SoundEffect>,
+}
+
+impl PackSoundEffects {
+    /// Returns the sound effect of the level pack or the built-in sound effect
+    pub fn get(&self, id: PackSoundEffectId) -> &'static SoundEffect {
+        self.sound_effects.get(&id).copied().unwrap_or_else(|| id.built_in_sound_effect())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sound_effects.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct BackgroundMusicId(NonZeroUsize);
 
@@ -149,6 +251,20 @@ impl BackgroundMusicId {
     pub fn id(self) -> usize {
         self.0.get()
     }
+
+    /// Creates the ID of a custom track from its file name
+    ///
+    /// The ID must not change in other game versions, because it is stored in the settings and in editor level packs
+    fn from_custom_track_file_name(file_name: &str) -> BackgroundMusicId {
+        let hash = utils::fnv1a_64(file_name.bytes());
+
+        BackgroundMusicId::new(CUSTOM_TRACK_MIN_ID + (hash % 1_000_000) as usize)
+    }
+
+    /// Returns true if this is the ID of a track from the custom music folder
+    pub fn is_custom(self) -> bool {
+        self.id() >= CUSTOM_TRACK_MIN_ID
+    }
 }
 
 #[derive(Debug)]
@@ -184,18 +300,46 @@ impl BackgroundMusic {
 
 #[derive(Debug)]
 pub struct BackgroundMusicTracks<const N: usize> {
-    tracks: [&'static BackgroundMusic; N],
+    built_in_tracks: [&'static BackgroundMusic; N],
+    //Built-in tracks followed by the custom tracks
+    all_tracks: OnceLock<Vec<&'static BackgroundMusic>>,
 }
 
 impl<const N: usize> BackgroundMusicTracks<N> {
-    const fn new(tracks: [&'static BackgroundMusic; N]) -> BackgroundMusicTracks<N> {
+    const fn new(built_in_tracks: [&'static BackgroundMusic; N]) -> BackgroundMusicTracks<N> {
         BackgroundMusicTracks {
-            tracks,
+            built_in_tracks,
+            all_tracks: OnceLock::new(),
+        }
+    }
+
+    /// Loads the custom tracks from the custom music folder and adds them after the built-in tracks
+    ///
+    /// Custom tracks can only be loaded once, because the loaded track data is referenced for the rest of the program run.
+    /// The warnings of skipped files are returned.
+    pub fn load_custom_tracks(&self, folder: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+        if self.are_custom_tracks_set() {
+            return Err(Box::new(GameError::new("Custom music tracks were already loaded")));
         }
+
+        let (custom_tracks, warnings) = read_custom_tracks(folder);
+
+        let mut all_tracks = self.built_in_tracks.to_vec();
+        all_tracks.extend(custom_tracks);
+
+        if self.all_tracks.set(all_tracks).is_err() {
+            return Err(Box::new(GameError::new("Custom music tracks were already loaded")));
+        }
+
+        Ok(warnings)
+    }
+
+    pub fn are_custom_tracks_set(&self) -> bool {
+        self.all_tracks.get().is_some()
     }
 
     pub fn check_id(&self, id: usize) -> Option<BackgroundMusicId> {
-        for track in &self.tracks {
+        for track in self.tracks() {
             if track.id.id() == id {
                 return Some(track.id);
             }
@@ -205,11 +349,349 @@ impl<const N: usize> BackgroundMusicTracks<N> {
     }
 
     pub fn get_track_by_id(&self, id: BackgroundMusicId) -> &BackgroundMusic {
-        self.tracks.iter().find(|background_music| background_music.id == id).unwrap()
+        self.tracks().iter().find(|background_music| background_music.id == id).unwrap()
+    }
+
+    /// Returns the position of the track in [tracks](Self::tracks)
+    pub fn track_index(&self, id: BackgroundMusicId) -> Option<usize> {
+        self.tracks().iter().position(|background_music| background_music.id == id)
+    }
+
+    pub fn tracks(&self) -> &[&'static BackgroundMusic] {
+        self.all_tracks.get().map_or(self.built_in_tracks.as_slice(), Vec::as_slice)
+    }
+}
+
+/// Reads all playable audio files from the custom music folder (A missing folder contains no tracks)
+///
+/// Files with other file extensions are ignored, unreadable files and files which cannot be decoded are skipped with a warning.
+/// File names in the format "Creator - Title.ogg" are shown with the creator.
+/// The track data is leaked, this must only be called by [BackgroundMusicTracks::load_custom_tracks].
+fn read_custom_tracks(folder: &Path) -> (Vec<&'static BackgroundMusic>, Vec<String>) {
+    let mut custom_tracks: Vec<&'static BackgroundMusic> = Vec::new();
+    let mut warnings = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return (custom_tracks, warnings);
+    };
+
+    let mut paths = entries.
+            filter_map(Result::ok).
+            map(|entry| entry.path()).
+            filter(|path| path.is_file() && path.extension().
+                    and_then(|extension| extension.to_str()).
+                    is_some_and(|extension| CUSTOM_TRACK_FILE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()))).
+            collect::<Vec<_>>();
+    paths.sort();
+
+    for path in paths {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+        if custom_tracks.len() == MAX_CUSTOM_TRACK_COUNT {
+            warnings.push(format!("Custom music track \"{}\" was skipped: Max track count ({}) reached", file_name, MAX_CUSTOM_TRACK_COUNT));
+
+            continue;
+        }
+
+        let audio_data = match std::fs::metadata(&path) {
+            Ok(metadata) if metadata.len() > MAX_CUSTOM_TRACK_FILE_SIZE => {
+                warnings.push(format!(
+                    "Custom music track \"{}\" was skipped: The file is too large (Max: {} MiB)",
+                    file_name, MAX_CUSTOM_TRACK_FILE_SIZE / 1024 / 1024,
+                ));
+
+                continue;
+            },
+
+            Ok(_) => std::fs::read(&path),
+            Err(err) => Err(err),
+        };
+        let audio_data = match audio_data {
+            Ok(audio_data) => audio_data,
+
+            Err(err) => {
+                warnings.push(format!("Custom music track \"{}\" was skipped: {}", file_name, err));
+
+                continue;
+            },
+        };
+
+        //Check if the audio format is supported before the track is added
+        if let Err(err) = Decoder::new(Cursor::new(audio_data.clone())) {
+            warnings.push(format!("Custom music track \"{}\" was skipped: Unsupported audio data ({})", file_name, err));
+
+            continue;
+        }
+
+        let id = BackgroundMusicId::from_custom_track_file_name(&file_name);
+        if let Some(track) = custom_tracks.iter().find(|track| track.id == id) {
+            warnings.push(format!(
+                "Custom music track \"{}\" was skipped: The ID is already used by \"{}\" (Rename the file)",
+                file_name, track.display_name,
+            ));
+
+            continue;
+        }
+
+        let file_stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let (creator, display_name) = custom_track_creator_and_name(&file_stem);
+        custom_tracks.push(Box::leak(Box::new(BackgroundMusic {
+            id,
+            display_name: display_name.to_string().leak(),
+            creator: creator.to_string().leak(),
+            intro_audio_data: None,
+            main_loop_audio_data: audio_data.leak(),
+        })));
+    }
+
+    (custom_tracks, warnings)
+}
+
+/// Loads the sound effects of a level pack from the sound effects folder of the level pack (A missing folder contains no sound effects)
+///
+/// Only files which are named like a sound effect (e.g. "step.ogg") are loaded, files which are too large, too long, or
+/// which cannot be decoded are skipped with a warning.
+pub fn load_pack_sound_effects(folder: &Path) -> (PackSoundEffects, Vec<String>) {
+    let mut pack_sound_effects = PackSoundEffects::default();
+    let mut warnings = Vec::new();
+
+    if !folder.is_dir() {
+        return (pack_sound_effects, warnings);
+    }
+
+    for id in PackSoundEffectId::VALUES {
+        let Some(path) = find_pack_sound_effect_file(folder, id) else {
+            continue;
+        };
+
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+        let audio_data = match std::fs::metadata(&path) {
+            Ok(metadata) if metadata.len() > MAX_PACK_SOUND_EFFECT_FILE_SIZE => {
+                warnings.push(format!(
+                    "Level pack sound effect \"{}\" was skipped: The file is too large (Max: {} KiB)",
+                    file_name, MAX_PACK_SOUND_EFFECT_FILE_SIZE / 1024,
+                ));
+
+                continue;
+            },
+
+            Ok(_) => std::fs::read(&path),
+            Err(err) => Err(err),
+        };
+        let audio_data = match audio_data {
+            Ok(audio_data) => audio_data,
+
+            Err(err) => {
+                warnings.push(format!("Level pack sound effect \"{}\" was skipped: {}", file_name, err));
+
+                continue;
+            },
+        };
+
+        match is_audio_longer_than(&audio_data, MAX_PACK_SOUND_EFFECT_DURATION) {
+            Ok(false) => {},
+
+            Ok(true) => {
+                warnings.push(format!(
+                    "Level pack sound effect \"{}\" was skipped: The sound effect is too long (Max: {} s)",
+                    file_name, MAX_PACK_SOUND_EFFECT_DURATION.as_secs(),
+                ));
+
+                continue;
+            },
+
+            Err(err) => {
+                warnings.push(format!("Level pack sound effect \"{}\" was skipped: Unsupported audio data ({})", file_name, err));
+
+                continue;
+            },
+        }
+
+        let audio_data: &'static [u8] = audio_data.leak();
+        let sound_effects: &'static [&'static [u8]] = Box::leak(Box::new([audio_data]));
+        pack_sound_effects.sound_effects.insert(id, Box::leak(Box::new(SoundEffect { sound_effects, speed: 1.0 })));
+    }
+
+    (pack_sound_effects, warnings)
+}
+
+/// Copies the sound effect files of a level pack into the target folder (e.g. for a Steam Workshop upload)
+///
+/// Other files of the sound effects folder are not copied. Nothing is copied if the level pack has no sound effects folder.
+#[cfg(feature = "steam")]
+pub fn copy_pack_sound_effects(folder: &Path, target_folder: &Path) -> Result<(), Box<dyn Error>> {
+    if !folder.is_dir() {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(target_folder)?;
+    for path in pack_sound_effect_files(folder)? {
+        std::fs::copy(&path, target_folder.join(path.file_name().unwrap_or_default()))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the sound effect files of a level pack which are not too large (Other files of the sound effects folder are ignored)
+pub fn pack_sound_effect_files(folder: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = Vec::new();
+    if !folder.is_dir() {
+        return Ok(files);
+    }
+
+    for id in PackSoundEffectId::VALUES {
+        if let Some(path) = find_pack_sound_effect_file(folder, id) &&
+                std::fs::metadata(&path)?.len() <= MAX_PACK_SOUND_EFFECT_FILE_SIZE {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Checks if the file name is the name of a level pack sound effect file (e.g. "step.ogg")
+pub fn is_pack_sound_effect_file_name(file_name: &str) -> bool {
+    let path = Path::new(file_name);
+
+    path.file_stem().and_then(|file_stem| file_stem.to_str()).
+            is_some_and(|file_stem| PackSoundEffectId::VALUES.iter().any(|id| id.file_stem() == file_stem)) &&
+            path.extension().and_then(|extension| extension.to_str()).
+                    is_some_and(|extension| CUSTOM_TRACK_FILE_EXTENSIONS.contains(&extension))
+}
+
+fn find_pack_sound_effect_file(folder: &Path, id: PackSoundEffectId) -> Option<PathBuf> {
+    CUSTOM_TRACK_FILE_EXTENSIONS.iter().
+            map(|extension| folder.join(format!("{}.{}", id.file_stem(), extension))).
+            find(|path| path.is_file())
+}
+
+/// Decodes the audio data until the max duration is exceeded (The duration is not stored in all audio formats)
+fn is_audio_longer_than(audio_data: &[u8], max_duration: Duration) -> Result<bool, Box<dyn Error>> {
+    let decoder = Decoder::new(Cursor::new(audio_data.to_vec()))?;
+    if let Some(duration) = decoder.total_duration() {
+        return Ok(duration > max_duration);
+    }
+
+    let max_sample_count = (max_duration.as_secs_f64() * decoder.sample_rate() as f64 * decoder.channels() as f64) as usize;
+
+    Ok(decoder.take(max_sample_count + 1).count() > max_sample_count)
+}
+
+/// Splits the file name of a custom track ("Creator - Title" or "Title") into the creator and the title
+fn custom_track_creator_and_name(file_stem: &str) -> (&str, &str) {
+    let (creator, name) = match file_stem.split_once(" - ") {
+        Some((creator, name)) if !creator.trim().is_empty() && !name.trim().is_empty() => (creator.trim(), name.trim()),
+        _ => ("Unknown", file_stem.trim()),
+    };
+
+    (truncate_chars(creator, MAX_CUSTOM_TRACK_CREATOR_LEN), truncate_chars(name, MAX_CUSTOM_TRACK_NAME_LEN))
+}
+
+fn truncate_chars(text: &str, max_len: usize) -> &str {
+    text.char_indices().nth(max_len).map_or(text, |(index, _)| &text[..index])
+}
+
+/// The names of the available audio output devices
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AudioDevices {
+    names: Vec<String>,
+    default_name: Option<String>,
+}
+
+impl AudioDevices {
+    pub fn new(names: Vec<String>, default_name: Option<String>) -> Self {
+        Self { names, default_name }
     }
 
-    pub fn tracks(&self) -> &[&BackgroundMusic] {
-        &self.tracks
+    /// Enumerates the output devices of the default audio host (This might block for a noticeable time)
+    pub fn enumerate() -> Self {
+        let host = rodio::cpal::default_host();
+
+        let names = host.output_devices().
+                map(|devices| devices.filter_map(|device| device.name().ok()).collect()).
+                unwrap_or_default();
+        let default_name = host.default_output_device().
+                and_then(|device| device.name().ok());
+
+        Self { names, default_name }
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn default_name(&self) -> Option<&str> {
+        self.default_name.as_deref()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|device_name| device_name == name)
+    }
+}
+
+/// Enumerates the audio output devices periodically in a background thread
+#[derive(Debug)]
+pub struct AudioDeviceMonitor {
+    last_check: Instant,
+    pending_check: Option<JoinHandle<AudioDevices>>,
+}
+
+impl AudioDeviceMonitor {
+    pub const CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+    pub fn new() -> Self {
+        Self {
+            last_check: Instant::now(),
+            pending_check: None,
+        }
+    }
+
+    /// Returns the available devices if a check was finished since the last call
+    pub fn poll(&mut self) -> Option<AudioDevices> {
+        if let Some(pending_check) = &self.pending_check {
+            if !pending_check.is_finished() {
+                return None;
+            }
+
+            self.last_check = Instant::now();
+
+            return self.pending_check.take().unwrap().join().ok();
+        }
+
+        if self.last_check.elapsed() >= Self::CHECK_INTERVAL {
+            self.pending_check = Some(std::thread::spawn(AudioDevices::enumerate));
+        }
+
+        None
+    }
+}
+
+impl Default for AudioDeviceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns true if the output stream should be recreated
+///
+/// This is the case if the current device was disconnected, if the selected device is available again after a fallback to the default device,
+/// or if the default device of the system was changed and no device is selected
+fn should_reconnect(
+    selected_device_name: Option<&str>, current_device_name: Option<&str>, is_fallback: bool, audio_devices: &AudioDevices,
+) -> bool {
+    //Disconnects can not be detected if the name of the device is unknown
+    let Some(current_device_name) = current_device_name else {
+        return false;
+    };
+
+    if !audio_devices.contains(current_device_name) {
+        return true;
+    }
+
+    match selected_device_name {
+        Some(selected_device_name) => is_fallback && audio_devices.contains(selected_device_name),
+        None => audio_devices.default_name().is_some_and(|default_name| default_name != current_device_name),
     }
 }
 
@@ -220,13 +702,30 @@ pub struct AudioHandler {
 
     background_music_sink: Sink,
 
+    device_name: Option<String>,
+    //True if the selected device was not available and the default device is used instead
+    is_fallback: bool,
+
     rand: ChaCha8Rng,
 }
 
 impl AudioHandler {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let output_stream = OutputStream::try_default();
-        let (_stream, stream_handle) = output_stream?;
+    /// Opens the output device with the provided name or the default device if no name is provided
+    ///
+    /// The default device is used as fallback if the selected device is not available
+    pub fn new(device_name: Option<&str>) -> Result<Self, Box<dyn Error>> {
+        let host = rodio::cpal::default_host();
+
+        let selected_device = device_name.and_then(|device_name| host.output_devices().ok()?.
+                find(|device| device.name().is_ok_and(|name| name == device_name)));
+        let selected_output_stream = selected_device.
+                and_then(|device| OutputStream::try_from_device(&device).ok().map(|output_stream| (device, output_stream)));
+
+        let is_fallback = device_name.is_some() && selected_output_stream.is_none();
+        let (device, (_stream, stream_handle)) = match selected_output_stream {
+            Some((device, output_stream)) => (Some(device), output_stream),
+            None => (host.default_output_device(), OutputStream::try_default()?),
+        };
 
         let background_music_sink = Sink::try_new(&stream_handle)?;
         let rand = ChaCha8Rng::from_os_rng();
@@ -238,15 +737,32 @@ impl AudioHandler {
 
             background_music_sink,
 
+            device_name: device.and_then(|device| device.name().ok()),
+            is_fallback,
+
             rand,
         })
     }
 
+    /// Returns the name of the output device which is used
+    pub fn device_name(&self) -> Option<&str> {
+        self.device_name.as_deref()
+    }
+
+    pub fn is_fallback(&self) -> bool {
+        self.is_fallback
+    }
+
+    pub fn should_reconnect(&self, selected_device_name: Option<&str>, audio_devices: &AudioDevices) -> bool {
+        should_reconnect(selected_device_name, self.device_name(), self.is_fallback, audio_devices)
+    }
+
     pub fn play_sound_effect(&mut self, sound_effect: &'static SoundEffect) -> Result<(), Box<dyn Error>> {
+        let sound_effect_speed = sound_effect.speed;
         let sound_effect = *sound_effect.sound_effects.choose(&mut self.rand).unwrap();
 
         let cursor = Cursor::new(sound_effect);
-        let source = Decoder::new(cursor)?.speed(self.rand.random_range(0.99..1.01));
+        let source = Decoder::new(cursor)?.speed(sound_effect_speed * self.rand.random_range(0.99..1.01));
 
         self.stream_handle.play_raw(source.convert_samples())?;
 
@@ -257,8 +773,32 @@ impl AudioHandler {
         self.background_music_sink.stop();
     }
 
+    pub fn pause_background_music(&self) {
+        self.background_music_sink.pause();
+    }
+
+    pub fn resume_background_music(&self) {
+        self.background_music_sink.play();
+    }
+
+    pub fn is_background_music_paused(&self) -> bool {
+        self.background_music_sink.is_paused()
+    }
+
+    /// Returns the playback position in the currently playing part (Intro or main loop) of the background music
+    pub fn background_music_position(&self) -> Duration {
+        self.background_music_sink.get_pos()
+    }
+
+    pub fn seek_background_music(&self, pos: Duration) -> Result<(), Box<dyn Error>> {
+        self.background_music_sink.try_seek(pos)?;
+
+        Ok(())
+    }
+
     pub fn set_background_music_loop(&self, intro: Option<&'static [u8]>, main_loop: &'static [u8]) -> Result<(), Box<dyn Error>> {
         self.stop_background_music();
+        self.resume_background_music();
 
         if let Some(intro) = intro {
             let cursor = Cursor::new(intro);
@@ -0,0 +1,100 @@
+use std::path::Path;
+use crate::game::audio::*;
+
+#[test]
+fn custom_track_creator_and_name_from_file_name() {
+    assert_eq!(custom_track_creator_and_name("Artist - My Song"), ("Artist", "My Song"));
+    assert_eq!(custom_track_creator_and_name("Artist - Song - Remix"), ("Artist", "Song - Remix"));
+    assert_eq!(custom_track_creator_and_name("My Song"), ("Unknown", "My Song"));
+    assert_eq!(custom_track_creator_and_name(" - My Song"), ("Unknown", "- My Song"));
+    assert_eq!(custom_track_creator_and_name(&"a".repeat(50)), ("Unknown", "a".repeat(35).as_str()));
+}
+
+#[test]
+fn custom_track_ids_are_stable_and_do_not_collide_with_built_in_tracks() {
+    let id = BackgroundMusicId::from_custom_track_file_name("Artist - My Song.ogg");
+
+    assert!(id.is_custom());
+    assert!(id.id() >= CUSTOM_TRACK_MIN_ID);
+    assert_eq!(id, BackgroundMusicId::from_custom_track_file_name("Artist - My Song.ogg"));
+    assert_ne!(id, BackgroundMusicId::from_custom_track_file_name("Artist - My Song.mp3"));
+
+    for track in BACKGROUND_MUSIC_TRACKS.tracks() {
+        assert!(!track.id().is_custom());
+    }
+}
+
+#[test]
+fn reconnect_if_device_was_disconnected_or_is_available_again() {
+    let audio_devices = AudioDevices::new(vec!["Speakers".to_string(), "Headset".to_string()], Some("Speakers".to_string()));
+
+    assert!(!should_reconnect(None, Some("Speakers"), false, &audio_devices));
+    assert!(!should_reconnect(Some("Headset"), Some("Headset"), false, &audio_devices));
+    assert!(!should_reconnect(None, None, false, &audio_devices));
+
+    //The default device of the system was changed
+    assert!(should_reconnect(None, Some("Headset"), false, &audio_devices));
+
+    //The selected device is available again
+    assert!(should_reconnect(Some("Headset"), Some("Speakers"), true, &audio_devices));
+    assert!(!should_reconnect(Some("USB Headset"), Some("Speakers"), true, &audio_devices));
+
+    //The current device was disconnected
+    assert!(should_reconnect(Some("USB Headset"), Some("USB Headset"), false, &audio_devices));
+}
+
+#[test]
+fn pack_sound_effects_fall_back_to_built_in_sound_effects() {
+    let (pack_sound_effects, warnings) = load_pack_sound_effects(Path::new("missing-folder.lvl.sounds"));
+    assert!(pack_sound_effects.is_empty());
+    assert!(warnings.is_empty());
+    assert_eq!(pack_sound_effects.get(PackSoundEffectId::Push).sound_effects(), STEP_EFFECT.sound_effects());
+
+    let folder = std::env::temp_dir().join(format!("sokoterm-pack-sound-effects-test-{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    std::fs::write(folder.join("step.ogg"), UI_SELECT_EFFECT.sound_effects()[0]).unwrap();
+    std::fs::write(folder.join("push.ogg"), "Not audio data").unwrap();
+    std::fs::write(folder.join("other.ogg"), UI_SELECT_EFFECT.sound_effects()[0]).unwrap();
+
+    let (pack_sound_effects, warnings) = load_pack_sound_effects(&folder);
+    assert_eq!(pack_sound_effects.get(PackSoundEffectId::Step).sound_effects(), UI_SELECT_EFFECT.sound_effects());
+    assert_eq!(pack_sound_effects.get(PackSoundEffectId::Push).sound_effects(), STEP_EFFECT.sound_effects());
+    assert_eq!(pack_sound_effects.get(PackSoundEffectId::LevelComplete).sound_effects(), LEVEL_COMPLETE_EFFECT.sound_effects());
+    assert_eq!(warnings.len(), 1);
+
+    let mut files = pack_sound_effect_files(&folder).unwrap();
+    files.sort();
+    assert_eq!(files, vec![folder.join("push.ogg"), folder.join("step.ogg")]);
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn pack_sound_effect_file_names() {
+    assert!(is_pack_sound_effect_file_name("step.ogg"));
+    assert!(is_pack_sound_effect_file_name("level_complete.flac"));
+
+    assert!(!is_pack_sound_effect_file_name("other.ogg"));
+    assert!(!is_pack_sound_effect_file_name("step.txt"));
+    assert!(!is_pack_sound_effect_file_name("step"));
+}
+
+#[test]
+fn custom_tracks_can_only_be_loaded_once() {
+    let tracks = BackgroundMusicTracks::new([&BACKGROUND_MUSIC_FIELDS_OF_ICE]);
+    assert!(!tracks.are_custom_tracks_set());
+
+    let folder = std::env::temp_dir().join(format!("sokoterm-custom-music-test-{}", std::process::id()));
+    assert!(tracks.load_custom_tracks(&folder).unwrap().is_empty());
+    assert!(tracks.are_custom_tracks_set());
+    assert_eq!(tracks.tracks().len(), 1);
+
+    assert!(tracks.load_custom_tracks(&folder).is_err());
+    assert_eq!(tracks.tracks().len(), 1);
+}
+
+#[test]
+fn custom_music_readme_is_not_loaded_as_track() {
+    assert!(!CUSTOM_TRACK_FILE_EXTENSIONS.iter().any(|extension| CUSTOM_MUSIC_README_FILE_NAME.ends_with(&format!(".{extension}"))));
+    assert!(CUSTOM_MUSIC_README.contains(&format!("{} + (64-bit FNV-1a hash of the file name % 1000000)", CUSTOM_TRACK_MIN_ID)));
+}
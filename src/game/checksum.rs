@@ -0,0 +1,180 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::sync::{Mutex, OnceLock};
+use crate::game::Game;
+use crate::game::logging::log_warn;
+use crate::utils;
+
+#[cfg(test)]
+mod tests;
+
+static INSTALL_KEY: OnceLock<ChecksumKey> = OnceLock::new();
+static SIGNED_SAVE_GAMES: Mutex<Option<BTreeSet<String>>> = Mutex::new(None);
+
+const SIGNED_SAVE_GAMES_FILE_NAME: &str = "checksum.signed";
+
+/// Returns the key of this installation, it is read from the base save game folder or created on first use
+///
+/// The key is shared between all profiles
+pub fn install_key() -> Result<&'static ChecksumKey, Box<dyn Error>> {
+    if let Some(key) = INSTALL_KEY.get() {
+        return Ok(key);
+    }
+
+    let key = ChecksumKey::read_or_create()?;
+
+    Ok(INSTALL_KEY.get_or_init(|| key))
+}
+
+/// Returns true if the save game file was written with checksums by this installation at least once
+///
+/// Save games without checksums are only accepted if they were written by older versions of the game,
+/// the list is stored next to the install key, because a marker in the save game could be removed together with the checksums.
+pub fn was_save_game_signed(save_game_file: &OsStr) -> Result<bool, Box<dyn Error>> {
+    let save_game_id = save_game_id(save_game_file)?;
+
+    let mut signed_save_games = SIGNED_SAVE_GAMES.lock().unwrap();
+    if signed_save_games.is_none() {
+        *signed_save_games = Some(read_signed_save_games()?);
+    }
+    let signed_save_games = signed_save_games.as_mut().unwrap();
+
+    Ok(signed_save_games.contains(&save_game_id))
+}
+
+/// Records that the save game file contains checksums, the list file is only written if the save game was not signed before
+pub fn mark_save_game_signed(save_game_file: &OsStr) -> Result<(), Box<dyn Error>> {
+    let save_game_id = save_game_id(save_game_file)?;
+
+    let mut signed_save_games = SIGNED_SAVE_GAMES.lock().unwrap();
+    if signed_save_games.is_none() {
+        *signed_save_games = Some(read_signed_save_games()?);
+    }
+    let signed_save_games = signed_save_games.as_mut().unwrap();
+
+    if !signed_save_games.insert(save_game_id) {
+        return Ok(());
+    }
+
+    let mut signed_save_games_file = Game::get_or_create_base_save_game_folder()?;
+    signed_save_games_file.push(SIGNED_SAVE_GAMES_FILE_NAME);
+
+    let mut signed_save_games_data = String::new();
+    for save_game_id in signed_save_games.iter() {
+        let _ = writeln!(signed_save_games_data, "{}", save_game_id);
+    }
+    std::fs::write(&signed_save_games_file, signed_save_games_data)?;
+
+    Ok(())
+}
+
+fn read_signed_save_games() -> Result<BTreeSet<String>, Box<dyn Error>> {
+    let mut signed_save_games_file = Game::get_or_create_base_save_game_folder()?;
+    signed_save_games_file.push(SIGNED_SAVE_GAMES_FILE_NAME);
+
+    if !std::fs::exists(&signed_save_games_file)? {
+        return Ok(BTreeSet::new());
+    }
+
+    Ok(std::fs::read_to_string(&signed_save_games_file)?.lines().
+            map(str::trim).
+            filter(|line| !line.is_empty()).
+            map(ToString::to_string).
+            collect())
+}
+
+/// The path of the save game file relative to the base save game folder (Contains the profile folder)
+fn save_game_id(save_game_file: &OsStr) -> Result<String, Box<dyn Error>> {
+    let base_save_game_folder = Game::get_or_create_base_save_game_folder()?;
+    let base_save_game_folder = base_save_game_folder.to_string_lossy();
+
+    let save_game_file = save_game_file.to_string_lossy();
+
+    Ok(save_game_file.strip_prefix(&*base_save_game_folder).
+            unwrap_or(&save_game_file).
+            to_string())
+}
+
+/// A random per-install secret which is used for HMAC-style checksums of save game files
+///
+/// The checksums are meant to detect obvious manual edits of save games, they are not cryptographically secure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumKey([u8; Self::KEY_LEN]);
+
+impl ChecksumKey {
+    const KEY_LEN: usize = 32;
+    const BLOCK_LEN: usize = 64;
+
+    const FILE_NAME: &'static str = "checksum.key";
+
+    pub fn new(key: [u8; Self::KEY_LEN]) -> Self {
+        Self(key)
+    }
+
+    /// Reads the key from the base save game folder, a new random key is created if the key file does not exist or is invalid
+    ///
+    /// All existing checksums become invalid if a new key is created
+    fn read_or_create() -> Result<Self, Box<dyn Error>> {
+        let mut key_file = Game::get_or_create_base_save_game_folder()?;
+        key_file.push(Self::FILE_NAME);
+
+        if std::fs::exists(&key_file)? {
+            let key_data = std::fs::read_to_string(&key_file)?;
+            if let Some(key) = Self::from_hex(key_data.trim()) {
+                return Ok(key);
+            }
+
+            log_warn!("\"{}\" is invalid: Creating new key", Self::FILE_NAME);
+        }
+
+        let key = Self(rand::random());
+        std::fs::write(&key_file, key.to_hex())?;
+
+        Ok(key)
+    }
+
+    fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != Self::KEY_LEN * 2 || !hex.is_ascii() {
+            return None;
+        }
+
+        let mut key = [0; Self::KEY_LEN];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+        }
+
+        Some(Self(key))
+    }
+
+    fn to_hex(&self) -> String {
+        let mut out = String::with_capacity(Self::KEY_LEN * 2);
+        for byte in self.0 {
+            let _ = write!(out, "{:02x}", byte);
+        }
+
+        out
+    }
+
+    /// Calculates the checksum of the provided data: H((K ^ opad) || H((K ^ ipad) || data))
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        let mut inner_key = [0x36; Self::BLOCK_LEN];
+        let mut outer_key = [0x5c; Self::BLOCK_LEN];
+        for (i, byte) in self.0.iter().enumerate() {
+            inner_key[i] ^= byte;
+            outer_key[i] ^= byte;
+        }
+
+        let inner_hash = hash(&inner_key, data);
+
+        hash(&outer_key, &inner_hash.to_le_bytes())
+    }
+}
+
+/// 64-bit FNV-1a hash of the concatenation of prefix and data
+fn hash(prefix: &[u8], data: &[u8]) -> u64 {
+    utils::fnv1a_64(prefix.iter().
+            chain(data).
+            copied())
+}
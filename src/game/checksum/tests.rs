@@ -0,0 +1,20 @@
+use crate::game::checksum::*;
+
+#[test]
+fn checksum_key_hex_round_trip() {
+    let key = ChecksumKey::new(std::array::from_fn(|i| i as u8 * 7));
+    assert_eq!(ChecksumKey::from_hex(&key.to_hex()), Some(key));
+
+    assert_eq!(ChecksumKey::from_hex(""), None);
+    assert_eq!(ChecksumKey::from_hex(&"x".repeat(64)), None);
+}
+
+#[test]
+fn checksum_depends_on_key_and_data() {
+    let key = ChecksumKey::new([1; 32]);
+    let other_key = ChecksumKey::new([2; 32]);
+
+    assert_eq!(key.checksum(b"main:0:1234:56"), key.checksum(b"main:0:1234:56"));
+    assert_ne!(key.checksum(b"main:0:1234:56"), key.checksum(b"main:0:1234:55"));
+    assert_ne!(key.checksum(b"main:0:1234:56"), other_key.checksum(b"main:0:1234:56"));
+}
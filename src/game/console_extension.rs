@@ -1,25 +1,178 @@
+use std::cell::RefCell;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use std::fmt;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use std::fmt::Write as _;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::game::{ControlPreset, Game};
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::input::InGameAction;
+#[cfg(any(feature = "cli", feature = "gui"))]
 use crate::game::level::Tile;
+#[cfg(any(feature = "cli", feature = "gui"))]
 use crate::io::{Color, Console};
 
+static DOUBLE_WIDTH_TILES: AtomicBool = AtomicBool::new(false);
+static LARGE_TILES: AtomicBool = AtomicBool::new(false);
+static WALL_VARIATION: AtomicBool = AtomicBool::new(false);
+static CONTROL_PRESET: RwLock<ControlPreset> = RwLock::new(ControlPreset::Default);
+
+thread_local! {
+    //Reused by "draw_fmt" to avoid allocating a new String for every formatted text which is drawn
+    static FORMAT_BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Enables drawing every level tile as two console columns (Only supported in the CLI version)
+pub fn set_double_width_tiles(double_width_tiles: bool) {
+    DOUBLE_WIDTH_TILES.store(double_width_tiles, Ordering::Relaxed);
+}
+
+/// Enables drawing every level tile as a block of 2x2 console cells for players with low vision (Only supported in the CLI version)
+pub fn set_large_tiles(large_tiles: bool) {
+    LARGE_TILES.store(large_tiles, Ordering::Relaxed);
+}
+
+/// Enables drawing wall tiles with slightly different graphics to make large wall areas look less uniform (Only supported in the GUI version)
+pub fn set_wall_variation(wall_variation: bool) {
+    WALL_VARIATION.store(wall_variation, Ordering::Relaxed);
+}
+
+pub fn is_wall_variation_enabled() -> bool {
+    cfg!(feature = "gui") && WALL_VARIATION.load(Ordering::Relaxed)
+}
+
+/// Sets the control preset whose keys are shown in key hints
+pub fn set_control_preset(control_preset: ControlPreset) {
+    *CONTROL_PRESET.write().unwrap() = control_preset;
+}
+
+pub fn control_preset() -> ControlPreset {
+    *CONTROL_PRESET.read().unwrap()
+}
+
+/// Returns the count of console columns and rows which are used to draw a single tile of a level with the provided size
+///
+/// Levels which are wider than half of the console are always drawn with single width tiles, large tiles are only used
+/// if the level fits below the first row of the console
+pub fn tile_size(level_width: usize, level_height: usize) -> (usize, usize) {
+    if !cfg!(feature = "cli") || level_width * 2 > Game::CONSOLE_MIN_WIDTH {
+        return (1, 1);
+    }
+
+    if LARGE_TILES.load(Ordering::Relaxed) && level_height * 2 < Game::CONSOLE_MIN_HEIGHT {
+        (2, 2)
+    }else if DOUBLE_WIDTH_TILES.load(Ordering::Relaxed) {
+        (2, 1)
+    }else {
+        (1, 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MouseInput {
+    Pressed {
+        button: MouseButton,
+        column: usize,
+        row: usize,
+    },
+
+    /// The delta is the count of scrolled lines (Positive values: Scrolled down)
+    Scrolled {
+        delta: i32,
+        column: usize,
+        row: usize,
+    },
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
 pub trait ConsoleExtension {
     fn draw_key_input_text(&self, input_text: &str);
 
-    fn draw_tile(&self, tile: Tile, is_player_background: bool, inverted: bool);
+    /// Draws the key which is bound to the action in the current control preset
+    fn draw_action_key_input_text(&self, action: InGameAction);
+
+    /// Draws formatted text at the current cursor position (e.g. `console.draw_fmt(format_args!("Moves: {:04}", moves))`)
+    ///
+    /// The text is formatted into a reused buffer instead of a new String
+    fn draw_fmt(&self, args: fmt::Arguments);
+
+    /// Draws the tile, the background color is used if the tile has no background color of its own
+    fn draw_tile(&self, tile: Tile, is_player_background: bool, inverted: bool, background: Color);
+
+    /// Draws the graphical variant of the tile, the variant is ignored if the tile has only one graphic
+    fn draw_tile_variant(&self, tile: Tile, variant: usize, is_player_background: bool, inverted: bool, background: Color);
+
+    /// Returns the next mouse input or None
+    fn get_mouse_input(&self) -> Option<MouseInput>;
 }
 
+#[cfg(any(feature = "cli", feature = "gui"))]
 impl<'a> ConsoleExtension for Console<'a> {
     fn draw_key_input_text(&self, input_text: &str) {
         self.set_color(Color::LightRed, Color::Default);
         self.draw_text(input_text);
     }
 
+    fn draw_action_key_input_text(&self, action: InGameAction) {
+        self.draw_key_input_text(action.key_name(control_preset()));
+    }
+
+    fn draw_fmt(&self, args: fmt::Arguments) {
+        if let Some(text) = args.as_str() {
+            self.draw_text(text);
+
+            return;
+        }
+
+        FORMAT_BUFFER.with_borrow_mut(|buffer| {
+            buffer.clear();
+            let _ = buffer.write_fmt(args);
+
+            self.draw_text(buffer.as_str());
+        });
+    }
+
+    #[cfg(feature = "cli")]
+    fn draw_tile(&self, tile: Tile, is_player_background: bool, inverted: bool, background: Color) {
+        tile.draw_raw(self, is_player_background, inverted, background);
+    }
+
+    #[cfg(feature = "gui")]
+    fn draw_tile(&self, tile: Tile, is_player_background: bool, inverted: bool, background: Color) {
+        self.draw_tile_internal(tile, 0, is_player_background, inverted, background);
+    }
+
+    //Tile variants are only drawn in the graphical tile mode
+    #[cfg(feature = "cli")]
+    fn draw_tile_variant(&self, tile: Tile, _variant: usize, is_player_background: bool, inverted: bool, background: Color) {
+        tile.draw_raw(self, is_player_background, inverted, background);
+    }
+
+    #[cfg(feature = "gui")]
+    fn draw_tile_variant(&self, tile: Tile, variant: usize, is_player_background: bool, inverted: bool, background: Color) {
+        self.draw_tile_internal(tile, variant, is_player_background, inverted, background);
+    }
+
+    //The console lib only reports left clicks
     #[cfg(feature = "cli")]
-    fn draw_tile(&self, tile: Tile, is_player_background: bool, inverted: bool) {
-        tile.draw_raw(self, is_player_background, inverted);
+    fn get_mouse_input(&self) -> Option<MouseInput> {
+        self.get_mouse_pos_clicked().map(|(column, row)| MouseInput::Pressed {
+            button: MouseButton::Left,
+            column,
+            row,
+        })
     }
 
     #[cfg(feature = "gui")]
-    fn draw_tile(&self, tile: Tile, is_player_background: bool, inverted: bool) {
-        self.draw_tile_internal(tile, is_player_background, inverted);
+    fn get_mouse_input(&self) -> Option<MouseInput> {
+        self.get_mouse_input_internal()
     }
 }
@@ -0,0 +1,228 @@
+use std::error::Error;
+use std::sync::LazyLock;
+use crate::game::{Game, GameSettings};
+
+#[cfg(feature = "gui")]
+use crate::game::GameState;
+#[cfg(feature = "gui")]
+use crate::game::events::GameEventListener;
+#[cfg(feature = "gui")]
+use crate::game::screen::ScreenId;
+#[cfg(feature = "gui")]
+use crate::game::screen::dialog::Dialog;
+
+#[cfg(test)]
+mod tests;
+
+const SOUND_EFFECTS_TEXT: &str = include_str!("../../resources/attribution/audio_sound_effects.txt");
+const BACKGROUND_MUSIC_TEXT: &str = include_str!("../../resources/attribution/audio_background_music.txt");
+
+#[cfg(all(feature = "gui", not(feature = "steam")))]
+const FONTS_TEXT: &str = include_str!("../../resources/attribution/fonts_gui.txt");
+#[cfg(feature = "steam")]
+const FONTS_TEXT: &str = include_str!("../../resources/attribution/fonts_steam.txt");
+
+#[cfg(feature = "cli")]
+const LIBRARIES_TEXT: &str = include_str!("../../resources/attribution/libraries_cli.txt");
+#[cfg(all(feature = "gui", not(feature = "steam")))]
+const LIBRARIES_TEXT: &str = include_str!("../../resources/attribution/libraries_gui.txt");
+#[cfg(feature = "steam")]
+const LIBRARIES_TEXT: &str = include_str!("../../resources/attribution/libraries_steam.txt");
+
+static SOUND_EFFECTS: LazyLock<Box<[CreditEntry]>> = LazyLock::new(|| parse_entries(SOUND_EFFECTS_TEXT, true));
+static BACKGROUND_MUSIC: LazyLock<Box<[CreditEntry]>> = LazyLock::new(|| parse_entries(BACKGROUND_MUSIC_TEXT, true));
+#[cfg(feature = "gui")]
+static FONTS: LazyLock<Box<[CreditEntry]>> = LazyLock::new(|| parse_entries(FONTS_TEXT, true));
+static LIBRARIES: LazyLock<Box<[CreditEntry]>> = LazyLock::new(|| parse_entries(LIBRARIES_TEXT, false));
+
+static CREDIT_LINES: LazyLock<Box<[CreditLine]>> = LazyLock::new(|| {
+    let mut lines = Vec::new();
+
+    for category in CreditCategory::VALUES {
+        let entries = category.entries();
+        if entries.is_empty() {
+            continue;
+        }
+
+        lines.extend([CreditLine::Empty, CreditLine::Empty]);
+        lines.push(CreditLine::Heading(category.heading()));
+        lines.push(CreditLine::Separator(category.heading().len()));
+
+        for (i, entry) in entries.iter().enumerate() {
+            if i > 0 {
+                lines.push(CreditLine::Empty);
+            }
+
+            lines.push(CreditLine::Name(entry.name));
+            if let Some(creator) = entry.creator {
+                lines.push(CreditLine::Creator(creator));
+            }
+            lines.push(CreditLine::License(entry.license));
+            lines.push(CreditLine::Link(entry.link));
+        }
+    }
+
+    lines.into_boxed_slice()
+});
+
+/// Older versions of the game stored the credits state in this file instead of the settings file
+const LEGACY_CREDITS_ROLLED_FILE_NAME: &str = "credits_rolled.data";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreditCategory {
+    SoundEffects,
+    BackgroundMusic,
+    /// Fonts are only used by the GUI version
+    Fonts,
+    Libraries,
+}
+
+impl CreditCategory {
+    pub const VALUES: [CreditCategory; 4] = [
+        CreditCategory::SoundEffects, CreditCategory::BackgroundMusic, CreditCategory::Fonts, CreditCategory::Libraries,
+    ];
+
+    pub fn heading(self) -> &'static str {
+        match self {
+            CreditCategory::SoundEffects => "This build of the game uses the following sound effects:",
+            CreditCategory::BackgroundMusic => "This build of the game uses the following background music tracks:",
+            CreditCategory::Fonts => "This build of the game uses the following text fonts:",
+            CreditCategory::Libraries => "This build of the game uses the following open-source libraries:",
+        }
+    }
+
+    /// Returns the entries of this category which are used by this build of the game
+    pub fn entries(self) -> &'static [CreditEntry] {
+        match self {
+            CreditCategory::SoundEffects => &SOUND_EFFECTS,
+            CreditCategory::BackgroundMusic => &BACKGROUND_MUSIC,
+
+            #[cfg(feature = "gui")]
+            CreditCategory::Fonts => &FONTS,
+            #[cfg(not(feature = "gui"))]
+            CreditCategory::Fonts => &[],
+
+            CreditCategory::Libraries => &LIBRARIES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CreditEntry {
+    name: &'static str,
+    /// None for libraries
+    creator: Option<&'static str>,
+    license: &'static str,
+    link: &'static str,
+}
+
+impl CreditEntry {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn creator(&self) -> Option<&'static str> {
+        self.creator
+    }
+
+    pub fn license(&self) -> &'static str {
+        self.license
+    }
+
+    pub fn link(&self) -> &'static str {
+        self.link
+    }
+}
+
+/// A line of the credits, the screens decide how each kind of line is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreditLine {
+    Empty,
+    Heading(&'static str),
+    /// Underline of the heading with the provided length
+    Separator(usize),
+    Name(&'static str),
+    Creator(&'static str),
+    License(&'static str),
+    Link(&'static str),
+}
+
+/// Returns the lines of all credit categories which are used by this build of the game
+///
+/// Each category starts with two empty lines
+pub fn credit_lines() -> &'static [CreditLine] {
+    &CREDIT_LINES
+}
+
+/// Parses the embedded attribution data (One entry per line: "Name;[Creator;]License;Link")
+fn parse_entries(text: &'static str, has_creator: bool) -> Box<[CreditEntry]> {
+    text.split("\n").
+            filter(|line| !line.trim().is_empty()).
+            map(|line| {
+                let tokens = line.split(";").
+                        map(|token| token.trim()).
+                        collect::<Box<[&str]>>();
+
+                match (has_creator, tokens.as_ref()) {
+                    (true, &[name, creator, license, link]) => CreditEntry { name, creator: Some(creator), license, link },
+                    (false, &[name, license, link]) => CreditEntry { name, creator: None, license, link },
+
+                    _ => panic!("Invalid attribution data: \"{line}\""),
+                }
+            }).collect()
+}
+
+/// Moves the credits state of the legacy file into the settings file
+pub fn migrate_legacy_credits_rolled_file(settings: &mut GameSettings) -> Result<(), Box<dyn Error>> {
+    let mut credits_rolled_file = Game::get_or_create_save_game_folder()?;
+    credits_rolled_file.push(LEGACY_CREDITS_ROLLED_FILE_NAME);
+
+    if !std::fs::exists(&credits_rolled_file)? {
+        return Ok(());
+    }
+
+    settings.credits_rolled = true;
+    settings.save_to_file()?;
+
+    //The file is only removed after the settings were saved
+    std::fs::remove_file(credits_rolled_file)?;
+
+    Ok(())
+}
+
+/// Rolls the credits once after the level in which the last built-in level pack was completed was left
+#[cfg(feature = "gui")]
+#[derive(Debug, Default)]
+pub struct CreditsEventListener {
+    is_pending: bool,
+}
+
+#[cfg(feature = "gui")]
+impl GameEventListener for CreditsEventListener {
+    fn on_screen_changed(&mut self, game_state: &mut GameState, screen_id: &ScreenId) {
+        if !self.is_pending || *screen_id == ScreenId::InGame {
+            return;
+        }
+        self.is_pending = false;
+
+        if let Err(err) = game_state.set_and_save_credits_rolled(true) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save credits state: {}", err)));
+        }
+
+        game_state.roll_credits();
+    }
+
+    fn on_pack_completed(&mut self, game_state: &mut GameState, level_pack_index: usize) {
+        if self.is_pending {
+            return;
+        }
+
+        let is_built_in_level_pack = game_state.level_packs().get(level_pack_index).
+                is_some_and(|level_pack| game_state.level_pack_source(level_pack.id()).is_some());
+        if !is_built_in_level_pack || !game_state.are_built_in_level_packs_completed() {
+            return;
+        }
+
+        self.is_pending = !game_state.settings().credits_rolled();
+    }
+}
@@ -0,0 +1,37 @@
+use crate::game::credits::*;
+
+#[test]
+fn parse_attribution_entries() {
+    let entries = parse_entries("Song;Artist;CC0-1.0;https://example.com/song\n\n", true);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name(), "Song");
+    assert_eq!(entries[0].creator(), Some("Artist"));
+    assert_eq!(entries[0].license(), "CC0-1.0");
+    assert_eq!(entries[0].link(), "https://example.com/song");
+
+    let entries = parse_entries("lib v1.0.0; MIT ;https://example.com/lib", false);
+    assert_eq!(entries[0].creator(), None);
+    assert_eq!(entries[0].license(), "MIT");
+}
+
+#[test]
+#[should_panic(expected = "Invalid attribution data")]
+fn parse_attribution_entries_invalid() {
+    parse_entries("lib v1.0.0;MIT;https://example.com/lib", true);
+}
+
+#[test]
+fn credit_lines_contain_all_entries() {
+    let lines = credit_lines();
+
+    for category in CreditCategory::VALUES {
+        let heading_count = lines.iter().filter(|line| **line == CreditLine::Heading(category.heading())).count();
+        assert_eq!(heading_count, usize::from(!category.entries().is_empty()));
+
+        for entry in category.entries() {
+            assert!(lines.contains(&CreditLine::Link(entry.link())));
+        }
+    }
+
+    assert_eq!(lines[..2], [CreditLine::Empty, CreditLine::Empty]);
+}
@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::ffi::OsString;
+use crate::game::{Game, GameState};
+use crate::game::logging;
+use crate::game::profile;
+use crate::game::save_folder;
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the file in the base save game folder to which the diagnostics are written (Can be attached to bug reports)
+pub const DIAGNOSTICS_FILE_NAME: &str = "diagnostics.txt";
+
+pub const MAX_ERROR_LOG_ENTRY_COUNT: usize = 10;
+
+/// The last error messages which were shown in error dialogs of the current session
+#[derive(Debug, Default)]
+pub struct ErrorLog {
+    entries: VecDeque<Box<str>>,
+}
+
+impl ErrorLog {
+    /// Adds a message, the oldest message is removed if the log is full
+    pub fn push(&mut self, message: impl Into<Box<str>>) {
+        if self.entries.len() == MAX_ERROR_LOG_ENTRY_COUNT {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(message.into());
+    }
+
+    /// Returns all messages (Oldest first)
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Returns the names of the cargo features which were enabled for this build of the game
+pub fn build_features() -> Vec<&'static str> {
+    [
+        ("cli", cfg!(feature = "cli")),
+        ("bot", cfg!(feature = "bot")),
+        ("gui", cfg!(feature = "gui")),
+        ("steam", cfg!(feature = "steam")),
+        ("headless", cfg!(feature = "headless")),
+    ].into_iter().
+            filter(|(_, is_enabled)| *is_enabled).
+            map(|(feature, _)| feature).
+            collect()
+}
+
+#[cfg(feature = "cli")]
+fn renderer_info() -> String {
+    let env_var = |name| std::env::var(name).unwrap_or_else(|_| "-".to_string());
+
+    format!("Terminal (TERM: {}, COLORTERM: {})", env_var("TERM"), env_var("COLORTERM"))
+}
+
+#[cfg(feature = "gui")]
+fn renderer_info() -> String {
+    "Window (Bevy)".to_string()
+}
+
+/// Collects the information about the game and the system which is needed for bug reports
+///
+/// The save folder path and the profile name are replaced if they should be hidden (e.g. in streamer mode)
+pub fn diagnostics_lines(game_state: &GameState, is_private_info_hidden: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    lines.push(format!("Version: {}", Game::VERSION));
+    lines.push(format!("Build features: {}", build_features().join(", ")));
+    lines.push(format!("OS: {} ({})", std::env::consts::OS, std::env::consts::ARCH));
+    lines.push(format!("Renderer: {}", renderer_info()));
+    lines.push(format!("Log file: {} ({})", logging::LOG_FILE_NAME, game_state.settings.log_verbosity.display_name()));
+
+    let startup_flags = game_state.startup_flags();
+    lines.push(format!(
+        "Startup flags: no audio: {}, mute music: {}, reduced motion: {}, portable: {}",
+        startup_flags.no_audio(), startup_flags.mute_music(), startup_flags.reduced_motion(), startup_flags.portable(),
+    ));
+
+    if is_private_info_hidden {
+        lines.push("Save folder: Hidden".to_string());
+        lines.push("Profile: Hidden".to_string());
+    }else {
+        lines.push(format!("Save folder: {}", match save_folder::base_save_game_folder() {
+            Ok(directory) => directory.to_string_lossy().into_owned(),
+            Err(err) => format!("<{}>", err),
+        }));
+        lines.push(format!("Profile: {}", profile::display_name(profile::active_profile().as_deref())));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("Level packs ({}):", game_state.level_packs().len()));
+    for level_pack in game_state.level_packs() {
+        let source = if let Some(level_pack_source) = game_state.level_pack_source(level_pack.id()) {
+            level_pack_source.to_string()
+        }else {
+            format!("Min game version {}", level_pack.min_game_version())
+        };
+
+        lines.push(format!(
+            "  {}: {}, {}/{} levels completed",
+            level_pack.id(), source, level_pack.min_level_not_completed().min(level_pack.level_count()), level_pack.level_count(),
+        ));
+
+        #[cfg(feature = "steam")]
+        if let Some(steam_level_pack_data) = level_pack.steam_level_pack_data() {
+            lines.push(format!("    Workshop ID: {}", steam_level_pack_data.workshop_id().0));
+        }
+    }
+
+    lines.push(String::new());
+    if game_state.error_log.is_empty() {
+        lines.push("Recent errors: None".to_string());
+    }else {
+        lines.push("Recent errors (Oldest first):".to_string());
+        for entry in game_state.error_log.entries() {
+            //Multi line messages are joined to keep one entry per line
+            lines.push(format!("  {}", entry.lines().collect::<Vec<_>>().join(" ")));
+        }
+    }
+
+    lines
+}
+
+/// Writes the diagnostics to the diagnostics file in the base save game folder and returns the path of the file
+pub fn write_diagnostics_file(lines: &[String]) -> Result<OsString, Box<dyn Error>> {
+    let mut diagnostics_file = save_folder::base_save_game_folder()?;
+    std::fs::create_dir_all(&diagnostics_file)?;
+    diagnostics_file.push(DIAGNOSTICS_FILE_NAME);
+
+    let mut text = format!("SokoTerm diagnostics\n\n{}", lines.join("\n"));
+    text.push('\n');
+
+    std::fs::write(&diagnostics_file, text)?;
+
+    Ok(diagnostics_file)
+}
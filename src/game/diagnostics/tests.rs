@@ -0,0 +1,22 @@
+use crate::game::diagnostics::*;
+
+#[test]
+fn error_log_keeps_last_entries() {
+    let mut error_log = ErrorLog::default();
+    assert!(error_log.is_empty());
+
+    for i in 0..MAX_ERROR_LOG_ENTRY_COUNT + 2 {
+        error_log.push(format!("Error {i}"));
+    }
+
+    let entries = error_log.entries().collect::<Vec<_>>();
+    assert_eq!(entries.len(), MAX_ERROR_LOG_ENTRY_COUNT);
+    assert_eq!(entries[0], "Error 2");
+    assert_eq!(entries[MAX_ERROR_LOG_ENTRY_COUNT - 1], format!("Error {}", MAX_ERROR_LOG_ENTRY_COUNT + 1));
+}
+
+#[test]
+fn build_features_contain_frontend() {
+    let build_features = build_features();
+    assert!(build_features.contains(&"cli") || build_features.contains(&"gui"));
+}
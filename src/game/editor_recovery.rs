@@ -0,0 +1,132 @@
+use std::error::Error;
+use std::fmt::Write as _;
+use std::str::FromStr;
+use crate::game::{Game, GameError};
+use crate::game::level::{Level, LevelTheme};
+
+#[cfg(test)]
+mod tests;
+
+/// Unsaved changes of the level editor are written to this file in the CLI version, because the terminal can be
+/// closed or interrupted (e.g. Ctrl+C) without any possibility to ask the player to save the changes
+const RECOVERY_FILE_NAME: &str = "editor_recovery.data";
+
+/// Unsaved changes of an editor level of a previous session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorRecovery {
+    level_pack_id: String,
+    level_index: usize,
+    level: Level,
+}
+
+impl EditorRecovery {
+    pub fn new(level_pack_id: impl Into<String>, level_index: usize, level: Level) -> Self {
+        Self {
+            level_pack_id: level_pack_id.into(),
+            level_index,
+            level,
+        }
+    }
+
+    pub fn level_pack_id(&self) -> &str {
+        &self.level_pack_id
+    }
+
+    pub fn level_index(&self) -> usize {
+        self.level_index
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    pub fn to_str(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "Level Pack: {}", self.level_pack_id);
+        let _ = writeln!(out, "Level: {}", self.level_index);
+        let _ = writeln!(out, "Wrap Around: {}", self.level.wrap_around());
+        let _ = writeln!(out, "Theme: {}", self.level.theme().id());
+        if let Some((x, y)) = self.level.start_pos() {
+            let _ = writeln!(out, "Start Position: {}, {}", x, y);
+        }
+
+        out += &self.level.to_str();
+
+        out
+    }
+}
+
+impl FromStr for EditorRecovery {
+    type Err = GameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GameError::new("The editor recovery file is invalid!");
+
+        let mut lines = s.lines().peekable();
+
+        let level_pack_id = lines.next().and_then(|line| line.strip_prefix("Level Pack: ")).ok_or_else(invalid)?;
+        let level_index = lines.next().
+                and_then(|line| line.strip_prefix("Level: ")).
+                and_then(|value| usize::from_str(value.trim()).ok()).
+                ok_or_else(invalid)?;
+        let wrap_around = lines.next().
+                and_then(|line| line.strip_prefix("Wrap Around: ")).
+                and_then(|value| bool::from_str(value.trim()).ok()).
+                ok_or_else(invalid)?;
+        let theme = lines.next().
+                and_then(|line| line.strip_prefix("Theme: ")).
+                and_then(|value| LevelTheme::from_id(value.trim())).
+                ok_or_else(invalid)?;
+
+        let mut start_pos = None;
+        if let Some(value) = lines.peek().copied().and_then(|line| line.strip_prefix("Start Position: ")) {
+            start_pos = Some(value.split_once(", ").and_then(|(x, y)| {
+                Some((usize::from_str(x.trim()).ok()?, usize::from_str(y.trim()).ok()?))
+            }).ok_or_else(invalid)?);
+
+            lines.next();
+        }
+
+        let mut level = Level::from_str(&lines.collect::<Vec<_>>().join("\n")).map_err(|_| invalid())?;
+        level.set_wrap_around(wrap_around);
+        level.set_theme(theme);
+        level.set_start_pos(start_pos);
+
+        Ok(Self::new(level_pack_id, level_index, level))
+    }
+}
+
+pub fn read_editor_recovery() -> Result<Option<EditorRecovery>, Box<dyn Error>> {
+    let mut recovery_file = Game::get_or_create_save_game_folder()?;
+    recovery_file.push(RECOVERY_FILE_NAME);
+
+    if !std::fs::exists(&recovery_file)? {
+        return Ok(None);
+    }
+
+    let recovery = std::fs::read_to_string(&recovery_file)?;
+
+    Ok(Some(EditorRecovery::from_str(&recovery)?))
+}
+
+pub fn write_editor_recovery(recovery: &EditorRecovery) -> Result<(), Box<dyn Error>> {
+    let mut recovery_file = Game::get_or_create_save_game_folder()?;
+    recovery_file.push(RECOVERY_FILE_NAME);
+
+    std::fs::write(recovery_file, recovery.to_str())?;
+
+    Ok(())
+}
+
+/// Removes the recovery file after the changes were saved or discarded
+pub fn remove_editor_recovery() -> Result<(), Box<dyn Error>> {
+    let mut recovery_file = Game::get_or_create_save_game_folder()?;
+    recovery_file.push(RECOVERY_FILE_NAME);
+
+    if std::fs::exists(&recovery_file)? {
+        std::fs::remove_file(recovery_file)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use crate::game::editor_recovery::*;
+
+#[test]
+fn editor_recovery_round_trip() {
+    let mut level = Level::from_str("w: 5, h: 3\n#####\n#-@x#\n#####").unwrap();
+    level.set_wrap_around(false);
+    level.set_theme(LevelTheme::Night);
+    level.set_start_pos(Some((1, 1)));
+
+    let recovery = EditorRecovery::new("my_pack", 3, level);
+    assert_eq!(EditorRecovery::from_str(&recovery.to_str()).unwrap(), recovery);
+
+    let recovery = EditorRecovery::new("my_pack", 0, Level::new(3, 3));
+    assert_eq!(EditorRecovery::from_str(&recovery.to_str()).unwrap(), recovery);
+
+    assert!(EditorRecovery::from_str("Level Pack: my_pack\nLevel: x").is_err());
+}
@@ -0,0 +1,45 @@
+use crate::game::GameState;
+use crate::game::screen::ScreenId;
+
+/// A level which was completed by the player
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelCompletedEvent {
+    pub level_pack_index: usize,
+    pub level_index: usize,
+    pub moves: u32,
+    /// None in relaxed mode
+    pub time: Option<u64>,
+    /// False if the best scores and the progress were not updated (Locked levels and remixes)
+    pub is_progress_saved: bool,
+    /// The seed if a remix of the level was completed
+    pub remix_seed: Option<u64>,
+}
+
+/// A level which was started by the player (Remixes are not included)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelStartedEvent {
+    pub level_pack_index: usize,
+    pub level_index: usize,
+    /// True if the level was restarted after the player moved
+    pub is_restart: bool,
+}
+
+/// Integrations (e.g. telemetry or achievements) implement this trait instead of being called by the screens directly
+///
+/// Listeners are registered as plugins of the game state (See [PluginRegistry](crate::game::plugins::PluginRegistry)),
+/// they are called after the event occurred in the order in which they were registered
+#[allow(unused_variables)]
+pub trait GameEventListener {
+    /// Called after the current screen was changed (The new screen was not updated yet)
+    fn on_screen_changed(&mut self, game_state: &mut GameState, screen_id: &ScreenId) {}
+
+    fn on_level_started(&mut self, game_state: &mut GameState, event: &LevelStartedEvent) {}
+
+    fn on_level_completed(&mut self, game_state: &mut GameState, event: &LevelCompletedEvent) {}
+
+    /// Called after a level was completed if all levels of the level pack are completed
+    fn on_pack_completed(&mut self, game_state: &mut GameState, level_pack_index: usize) {}
+
+    /// Called if the plugin of this listener was disabled at runtime (e.g. to discard collected data)
+    fn on_disabled(&mut self) {}
+}
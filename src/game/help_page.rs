@@ -1,6 +1,7 @@
 use std::collections::HashMap;
-use crate::game::{audio, GameState};
-use crate::game::console_extension::ConsoleExtension;
+use crate::game::{audio, diagnostics, logging, trash, ControlPreset, GameState};
+use crate::game::console_extension::{self, ConsoleExtension};
+use crate::game::input::InGameAction;
 use crate::game::level::Tile;
 use crate::io::{Color, Console, Key};
 
@@ -51,7 +52,7 @@ impl SectionLabel {
         let page_len = page.chars().count();
 
         console.set_color(self.layer.get_heading_color(), Color::Default);
-        console.draw_text(format!("{}{}{}", heading, ".".repeat(width - heading_len - page_len), page));
+        console.draw_fmt(format_args!("{}{}{}", heading, ".".repeat(width - heading_len - page_len), page));
     }
 
     pub fn draw_reference(&self, console: &Console) {
@@ -222,6 +223,14 @@ impl HelpPage {
                     console.draw_key_input_text("F1");
                     console.reset_color();
                     console.draw_text(": Open/close help menu");
+                }, |console| {
+                    console.draw_key_input_text("F4");
+                    console.reset_color();
+                    console.draw_text(": Show/Hide the in-game HUD (e.g. for recordings)");
+                }, |console| {
+                    console.draw_key_input_text("F6");
+                    console.reset_color();
+                    console.draw_text(": Change language of level pack names and hints");
                 }, |console| {
                     console.draw_key_input_text("F7");
                     console.reset_color();
@@ -242,6 +251,10 @@ impl HelpPage {
                     console.draw_key_input_text("F11");
                     console.reset_color();
                     console.draw_text(": Toggle Fullscreen");
+                }, |console| {
+                    console.draw_key_input_text("F12");
+                    console.reset_color();
+                    console.draw_text(": Save a game state snapshot (Restore with \"--snapshot <file>\")");
                 }, empty_line, |console| {
                     console.draw_key_input_text("UP");
                     console.reset_color();
@@ -270,10 +283,55 @@ impl HelpPage {
                     console.draw_key_input_text("WASD");
                     console.reset_color();
                     console.draw_text(": Move level (pack) selection cursor");
+                }, |console| {
+                    console.draw_key_input_text("HOME");
+                    console.reset_color();
+                    console.draw_text("/");
+                    console.draw_key_input_text("END");
+                    console.reset_color();
+                    console.draw_text("/");
+                    console.draw_key_input_text("PAGE UP");
+                    console.reset_color();
+                    console.draw_text("/");
+                    console.draw_key_input_text("PAGE DOWN");
+                    console.reset_color();
+                    console.draw_text(": Jump in lists (GUI version only)");
                 }, |console| {
                     console.draw_key_input_text("p");
                     console.reset_color();
                     console.draw_text(": Level preview");
+                }, |console| {
+                    console.draw_key_input_text("ENTER");
+                    console.reset_color();
+                    console.draw_text(": Play level (Locked levels can be played from the level preview)");
+                }, |console| {
+                    console.draw_key_input_text("l");
+                    console.reset_color();
+                    console.draw_text(": Show/Hide tile legend (Level preview)");
+                }, |console| {
+                    console.draw_key_input_text("m");
+                    console.reset_color();
+                    console.draw_text(": Play a remix with other box positions (Level preview)");
+                }, |console| {
+                    console.draw_key_input_text("o");
+                    console.reset_color();
+                    console.draw_text(": Change the level sort order (Level selection)");
+                }, |console| {
+                    console.draw_key_input_text("r");
+                    console.reset_color();
+                    console.draw_text(": Start a speedrun of all levels (Completed level packs)");
+                }, |console| {
+                    console.draw_key_input_text("g");
+                    console.reset_color();
+                    console.draw_text(": Select the suggested next level pack or level");
+                }, |console| {
+                    console.draw_key_input_text("i");
+                    console.reset_color();
+                    console.draw_text(": Show level pack info (Level pack selection)");
+                }, |console| {
+                    console.draw_key_input_text("b");
+                    console.reset_color();
+                    console.draw_text(": Restore the best score before the last record (Level selection)");
                 }],
             ),
             Section::new(
@@ -285,24 +343,138 @@ impl HelpPage {
                     console.reset_color();
                     console.draw_text(": Move player");
                 }, |console| {
-                    console.draw_key_input_text("r");
+                    console.draw_action_key_input_text(InGameAction::Reset);
                     console.reset_color();
                     console.draw_text(": Reset level");
                 }, |console| {
-                    console.draw_key_input_text("z");
+                    console.draw_action_key_input_text(InGameAction::Undo);
+                    if console_extension::control_preset() == ControlPreset::Default {
+                        console.reset_color();
+                        console.draw_text("/");
+                        console.draw_key_input_text("u");
+                    }
                     console.reset_color();
-                    console.draw_text("/");
-                    console.draw_key_input_text("u");
+                    console.draw_text(": Undo (Hold to undo faster)");
+                }, |console| {
+                    console.draw_action_key_input_text(InGameAction::Redo);
                     console.reset_color();
-                    console.draw_text(": Undo");
+                    console.draw_text(": Redo (Hold to redo faster)");
                 }, |console| {
-                    console.draw_key_input_text("y");
+                    console.draw_action_key_input_text(InGameAction::UndoJump);
                     console.reset_color();
-                    console.draw_text(": Redo");
+                    console.draw_text("/");
+                    console.draw_action_key_input_text(InGameAction::RedoJump);
+                    console.reset_color();
+                    console.draw_text(": Undo/Redo 10 moves");
                 }, |console| {
-                    console.draw_key_input_text("q");
+                    console.draw_action_key_input_text(InGameAction::ToggleFloor);
                     console.reset_color();
                     console.draw_text(": Show/Hide floor tiles");
+                }, |console| {
+                    console.draw_action_key_input_text(InGameAction::ToggleTileLegend);
+                    console.reset_color();
+                    console.draw_text(": Show/Hide tile legend");
+                }, |console| {
+                    console.draw_action_key_input_text(InGameAction::Hint);
+                    console.reset_color();
+                    console.draw_text(": Hint (Press again for more help, each hint adds moves)");
+                }, |console| {
+                    console.draw_key_input_text("ESC");
+                    console.reset_color();
+                    console.draw_text(": Pause menu (The timer is paused while the menu is open)");
+                }, empty_line, |console| {
+                    console.reset_color();
+                    console.draw_text("The keys of the selected control preset are shown (Change with ");
+                    console.draw_key_input_text("c");
+                    console.reset_color();
+                    console.draw_text(" in");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("the settings).");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Jukebox"), &[|console| {
+                    console.draw_key_input_text("j");
+                    console.reset_color();
+                    console.draw_text(": Open the jukebox (Start menu)");
+                }, |console| {
+                    console.draw_key_input_text("ENTER");
+                    console.reset_color();
+                    console.draw_text("/");
+                    console.draw_key_input_text("SPACEBAR");
+                    console.reset_color();
+                    console.draw_text(": Play/Pause the selected track");
+                }, |console| {
+                    console.draw_key_input_text("LEFT");
+                    console.reset_color();
+                    console.draw_text("/");
+                    console.draw_key_input_text("RIGHT");
+                    console.reset_color();
+                    console.draw_text(": Seek backward/forward");
+                }, |console| {
+                    console.draw_key_input_text("m");
+                    console.reset_color();
+                    console.draw_text(": Use the selected track as menu background music");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_fmt(format_args!(
+                        "Custom tracks: Audio files in the \"{}\" save game folder",
+                        audio::CUSTOM_MUSIC_FOLDER,
+                    ));
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Achievements"), &[|console| {
+                    console.draw_key_input_text("c");
+                    console.reset_color();
+                    console.draw_text(": Open the achievements (Start menu)");
+                }, empty_line, |console| {
+                    console.reset_color();
+                    console.draw_text("Achievements are unlocked by the total moves, pushes, undos, and");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("completed levels of the current profile.");
+                }],
+            ),
+            #[cfg(feature = "gui")]
+            Section::new(
+                section_label_builder.new_sub_sub_section("Credits"), &[|console| {
+                    console.draw_key_input_text("r");
+                    console.reset_color();
+                    console.draw_text(": Roll the credits (About screen)");
+                }, |console| {
+                    console.draw_key_input_text("SPACEBAR");
+                    console.reset_color();
+                    console.draw_text(": Pause/Resume scrolling");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("Click a link to copy it to the clipboard");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Diagnostics"), &[|console| {
+                    console.draw_key_input_text("d");
+                    console.reset_color();
+                    console.draw_text(": Open the diagnostics for bug reports (About screen)");
+                }, |console| {
+                    console.draw_key_input_text("s");
+                    console.reset_color();
+                    console.draw_fmt(format_args!(
+                        ": Save the diagnostics to \"{}\" in the save game folder",
+                        diagnostics::DIAGNOSTICS_FILE_NAME,
+                    ));
+                }, |console| {
+                    console.draw_key_input_text("c");
+                    console.reset_color();
+                    console.draw_text(": Copy the diagnostics to the clipboard");
+                }, |console| {
+                    console.draw_key_input_text("g");
+                    console.reset_color();
+                    console.draw_fmt(format_args!(
+                        ": Change which entries are written to \"{}\" (Settings screen)",
+                        logging::LOG_FILE_NAME,
+                    ));
                 }],
             ),
 
@@ -337,6 +509,29 @@ impl HelpPage {
                     console.draw_key_input_text("key input label");
                     console.reset_color();
                     console.draw_text(" text");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("[");
+                    console.set_color(Color::Default, Color::Yellow);
+                    console.draw_text("R");
+                    console.reset_color();
+                    console.draw_text("] Anywhere: Back (The same as ");
+                    console.draw_key_input_text("ESC");
+                    console.reset_color();
+                    console.draw_text(", GUI version only)");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("[");
+                    console.set_color(Color::Default, Color::Yellow);
+                    console.draw_text("M");
+                    console.reset_color();
+                    console.draw_text("] Level selection: Show/Hide level preview (GUI version only)");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("Mouse wheel: Scroll lists, the about page and the help pages");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("(Mouse button and wheel actions can be changed in the settings)");
                 }],
             ),
             Section::new(
@@ -520,6 +715,14 @@ impl HelpPage {
                     console.draw_key_input_text("e");
                     console.reset_color();
                     console.draw_text(": Export the selected level pack to the current directory");
+                }, |console| {
+                    console.draw_key_input_text("z");
+                    console.reset_color();
+                    console.draw_text(": Export the selected level pack as compressed archive (\".lvlz\")");
+                }, |console| {
+                    console.draw_key_input_text("i");
+                    console.reset_color();
+                    console.draw_text(": Import a level pack archive from the current directory");
                 }, #[cfg(feature = "steam")] |console| {
                     console.draw_key_input_text("u");
                     console.reset_color();
@@ -528,6 +731,10 @@ impl HelpPage {
                     console.draw_key_input_text("DELETE");
                     console.reset_color();
                     console.draw_text(": Delete the selected level pack");
+                }, |console| {
+                    console.draw_key_input_text("t");
+                    console.reset_color();
+                    console.draw_fmt(format_args!(": Restore deleted level packs and levels (Kept for {} days)", trash::RETENTION_DAYS));
                 }, |console| {
                     console.draw_key_input_text("ESC");
                     console.reset_color();
@@ -546,6 +753,20 @@ impl HelpPage {
                     console.draw_key_input_text("t");
                     console.reset_color();
                     console.draw_text(": Set or unset level as level pack thumbnail");
+                }, |console| {
+                    console.draw_key_input_text("e");
+                    console.reset_color();
+                    console.draw_text(": Edit the par values (Target moves and time) of the selected level");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("   (Validation results are used as par values for exported levels)");
+                }, |console| {
+                    console.draw_key_input_text("n");
+                    console.reset_color();
+                    console.draw_text(": Edit the translated level pack names (");
+                    console.draw_key_input_text("TAB");
+                    console.reset_color();
+                    console.draw_text(": Save and change language)");
                 }, |console| {
                     console.draw_key_input_text("c");
                     console.reset_color();
@@ -565,10 +786,120 @@ impl HelpPage {
                     console.draw_key_input_text("DELETE");
                     console.reset_color();
                     console.draw_text(": Delete the selected level");
+                }, |console| {
+                    console.draw_key_input_text("r");
+                    console.reset_color();
+                    console.draw_text(": Reload the level pack from disk (Discards unsaved editor changes)");
+                }, |console| {
+                    console.draw_key_input_text("m");
+                    console.reset_color();
+                    console.draw_text(": Enter or exit the multi-select mode");
+                }, |console| {
+                    console.draw_key_input_text("l");
+                    console.reset_color();
+                    console.draw_text(": Lock or unlock the validation stats of the level pack");
+                }, |console| {
+                    console.draw_key_input_text("w");
+                    console.reset_color();
+                    console.draw_text(": Add or remove a required level pack (Steam Workshop ID)");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("   (");
+                    console.draw_key_input_text("SPACEBAR");
+                    console.reset_color();
+                    console.draw_text(": Mark, ");
+                    console.draw_key_input_text("ENTER");
+                    console.reset_color();
+                    console.draw_text(": Move marked, ");
+                    console.draw_key_input_text("DELETE");
+                    console.reset_color();
+                    console.draw_text(": Delete marked)");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("   (");
+                    console.draw_key_input_text("z");
+                    console.reset_color();
+                    console.draw_text("/");
+                    console.draw_key_input_text("u");
+                    console.reset_color();
+                    console.draw_text(": Undo last move or delete of marked levels)");
+                }, |console| {
+                    console.draw_key_input_text("ESC");
+                    console.reset_color();
+                    console.draw_text(": Cancel level creation, par value or name editing or multi-select");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Level pack editor / Changelog"), &[|console| {
+                    console.reset_color();
+                    console.draw_text("Changes to the level pack are recorded in a changelog which is used");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("as the change note of the next Steam Workshop upload.");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("The changelog is cleared after a successful upload.");
+                }, empty_line, |console| {
+                    console.draw_key_input_text("h");
+                    console.reset_color();
+                    console.draw_text(": Edit the change summary shown above the changelog entries");
+                }, |console| {
+                    console.draw_key_input_text("ENTER");
+                    console.reset_color();
+                    console.draw_text(": Save the change summary");
+                }, |console| {
+                    console.draw_key_input_text("ESC");
+                    console.reset_color();
+                    console.draw_text(": Cancel change summary editing");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Level pack editor / Intro and outro"), &[|console| {
+                    console.reset_color();
+                    console.draw_text("The intro is shown before level 1 is played for the first time and the");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("outro is shown after the last level was completed.");
+                }, empty_line, |console| {
+                    console.draw_key_input_text("o");
+                    console.reset_color();
+                    console.draw_text(": Edit the intro and outro texts");
+                }, |console| {
+                    console.draw_key_input_text("ENTER");
+                    console.reset_color();
+                    console.draw_text(": New line, ");
+                    console.draw_key_input_text("TAB");
+                    console.reset_color();
+                    console.draw_text(": Switch between intro and outro");
+                }, |console| {
+                    console.draw_key_input_text("ESC");
+                    console.reset_color();
+                    console.draw_text(": Save and close the text editor");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Level pack editor / Problem report"), &[|console| {
+                    console.draw_key_input_text("i");
+                    console.reset_color();
+                    console.draw_text(": Check all levels of the level pack for problems");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("   (Missing players, box and goal counts, unreachable goals,");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("    unused keys or locked doors and oversized levels)");
+                }, empty_line, |console| {
+                    console.draw_key_input_text("Arrow keys");
+                    console.reset_color();
+                    console.draw_text(": Select a problem");
+                }, |console| {
+                    console.draw_key_input_text("ENTER");
+                    console.reset_color();
+                    console.draw_text(": Go to the level of the selected problem");
                 }, |console| {
                     console.draw_key_input_text("ESC");
                     console.reset_color();
-                    console.draw_text(": Cancel the creation of a new level");
+                    console.draw_text(": Close the problem report");
                 }],
             ),
             Section::new(
@@ -598,6 +929,23 @@ impl HelpPage {
                     console.draw_key_input_text("q");
                     console.reset_color();
                     console.draw_text(": Show/Hide floor tiles");
+                }, |console| {
+                    console.draw_key_input_text("g");
+                    console.reset_color();
+                    console.draw_text(": Show/Hide the ghost of the last validation run");
+                }, |console| {
+                    console.draw_key_input_text("ESC");
+                    console.reset_color();
+                    console.draw_text(": Pause menu (Resume, restart, help, or exit the level editor)");
+                }, empty_line, |console| {
+                    console.reset_color();
+                    console.draw_text("Sound effects: \"step\", \"push\", and \"level_complete\" audio files in");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_fmt(format_args!(
+                        "the \"<Level pack file>{}\" folder (Max: 1 MiB, 5 s)",
+                        audio::PACK_SOUND_EFFECTS_FOLDER_POSTFIX,
+                    ));
                 }],
             ),
             Section::new(
@@ -626,16 +974,13 @@ impl HelpPage {
                     console.reset_color();
                     console.draw_text(": Copy the current row or column in the cursor direction");
                 }, |console| {
-                    console.draw_key_input_text("z");
-                    console.reset_color();
-                    console.draw_text("/");
-                    console.draw_key_input_text("u");
+                    console.draw_key_input_text("f");
                     console.reset_color();
-                    console.draw_text(": Undo");
+                    console.draw_text(": Fill the area at the cursor with the last placed tile");
                 }, |console| {
-                    console.draw_key_input_text("y");
+                    console.draw_key_input_text("g");
                     console.reset_color();
-                    console.draw_text(": Redo");
+                    console.draw_text(": Surround the play area with walls");
                 }, |console| {
                     console.draw_key_input_text("r");
                     console.reset_color();
@@ -644,6 +989,38 @@ impl HelpPage {
                     console.draw_key_input_text("q");
                     console.reset_color();
                     console.draw_text(": Show/Hide floor tiles");
+                }, |console| {
+                    console.draw_key_input_text("j");
+                    console.reset_color();
+                    console.draw_text(": Show/Hide changes since the last save (");
+                    console.set_color(Color::Black, Color::Green);
+                    console.draw_text("Added");
+                    console.reset_color();
+                    console.draw_text(", ");
+                    console.set_color(Color::Black, Color::Red);
+                    console.draw_text("Removed");
+                    console.reset_color();
+                    console.draw_text(", ");
+                    console.set_color(Color::Black, Color::Yellow);
+                    console.draw_text("Changed");
+                    console.reset_color();
+                    console.draw_text(")");
+                }, |console| {
+                    console.draw_key_input_text("e");
+                    console.reset_color();
+                    console.draw_text(": Enable/Disable wrap around at the level edges");
+                }, |console| {
+                    console.draw_key_input_text("l");
+                    console.reset_color();
+                    console.draw_text(": Select the next theme (Background color) of the level");
+                }, |console| {
+                    console.draw_key_input_text("t");
+                    console.reset_color();
+                    console.draw_text(": Place/Remove a TODO marker at the cursor (Not exported)");
+                }, |console| {
+                    console.draw_key_input_text("n");
+                    console.reset_color();
+                    console.draw_text(": View/Edit the level notes (Not exported)");
                 }, empty_line, |console| {
                     console.reset_color();
                     console.draw_text("[");
@@ -653,6 +1030,106 @@ impl HelpPage {
                     console.draw_text("] Press on any tile to set the cursor position");
                 }],
             ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Level editor (Editing mode - Tile counts)"), &[|console| {
+                    console.draw_key_input_text("TAB");
+                    console.reset_color();
+                    console.draw_text(": Show the cursor position or the tile counts in the header");
+                }, empty_line, |console| {
+                    console.reset_color();
+                    console.draw_text("P: Players, B: Boxes, G: Goals, K: Keys, D: Locked doors");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("Counts are ");
+                    console.set_color(Color::Red, Color::Default);
+                    console.draw_text("red");
+                    console.reset_color();
+                    console.draw_text(" if the level cannot be won with these tiles");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("(e.g. fewer boxes than goals or more boxes than goals and holes)");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Level editor (Editing mode - Go to level)"), &[|console| {
+                    console.draw_key_input_text("0");
+                    console.reset_color();
+                    console.draw_text(" - ");
+                    console.draw_key_input_text("9");
+                    console.reset_color();
+                    console.draw_text(": Enter the number of another level of the level pack");
+                }, |console| {
+                    console.draw_key_input_text("ENTER");
+                    console.reset_color();
+                    console.draw_text(": Edit the entered level (Unsaved changes can be saved first)");
+                }, |console| {
+                    console.draw_key_input_text("ESC");
+                    console.reset_color();
+                    console.draw_text(": Cancel");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Level editor (Editing mode - Undo history)"), &[|console| {
+                    console.draw_key_input_text("z");
+                    console.reset_color();
+                    console.draw_text("/");
+                    console.draw_key_input_text("u");
+                    console.reset_color();
+                    console.draw_text(": Undo");
+                }, |console| {
+                    console.draw_key_input_text("y");
+                    console.reset_color();
+                    console.draw_text(": Redo");
+                }, |console| {
+                    console.draw_key_input_text("h");
+                    console.reset_color();
+                    console.draw_text(": Show undo branches (Edits which were replaced after an undo)");
+                }, empty_line, |console| {
+                    console.reset_color();
+                    console.draw_text("Restoring an undo branch keeps the replaced edits as a new branch.");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Level editor (Editing mode - Hint preview)"), &[|console| {
+                    console.draw_key_input_text("?");
+                    console.reset_color();
+                    console.draw_text(": Preview the level hints (Press again to preview the completed level)");
+                }, |console| {
+                    console.draw_key_input_text("ESC");
+                    console.reset_color();
+                    console.draw_text(": Close the hint preview");
+                }, empty_line, |console| {
+                    console.reset_color();
+                    console.draw_text("Hints are shown like during gameplay. Hints which are clipped, cover");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("tiles, or overlap other hints are listed when the preview is opened.");
+                }],
+            ),
+            Section::new(
+                section_label_builder.new_sub_sub_section("Level editor (Editing mode - Clipboard)"), &[|console| {
+                    console.draw_key_input_text("m");
+                    console.reset_color();
+                    console.draw_text(": Start/Clear a selection at the cursor");
+                }, |console| {
+                    console.draw_key_input_text("k");
+                    console.reset_color();
+                    console.draw_text(": Copy the selection or the whole level to the clipboard");
+                }, #[cfg(feature = "gui")] |console| {
+                    console.draw_key_input_text("CTRL V");
+                    console.reset_color();
+                    console.draw_text(": Paste at the cursor (Larger levels replace the level)");
+                }, empty_line, |console| {
+                    console.reset_color();
+                    console.draw_text("Levels are copied in the XSB format which is used by most Sokoban");
+                }, |console| {
+                    console.reset_color();
+                    console.draw_text("programs (Levels with other tiles use the level pack format).");
+                }, #[cfg(feature = "cli")] |console| {
+                    console.reset_color();
+                    console.draw_text("Copying requires a terminal with OSC 52 support.");
+                }],
+            ),
             Section::new(
                 section_label_builder.new_sub_sub_section("Level editor (Editing mode - Tiles)"), &[|console| {
                     console.draw_key_input_text("SPACEBAR");
@@ -690,6 +1167,10 @@ impl HelpPage {
                     console.draw_key_input_text("&");
                     console.reset_color();
                     console.draw_text(": Insert a player on ice tile");
+                }, |console| {
+                    console.draw_key_input_text("$");
+                    console.reset_color();
+                    console.draw_text(": Place/Remove the start marker (The player starts on the tile)");
                 }, |console| {
                     console.draw_key_input_text("*");
                     console.reset_color();
@@ -761,6 +1242,12 @@ impl HelpPage {
                 }, |console| {
                     console.reset_color();
                     console.draw_text("2) \"Path to level pack 1\" \"Path to level pack 2\" ...");
+                }, #[cfg(feature = "cli")] |console| {
+                    console.reset_color();
+                    console.draw_text("3) --render-thumbnail \"Path to level pack\" LEVEL \"Path to PNG file\"");
+                }, #[cfg(feature = "bot")] |console| {
+                    console.reset_color();
+                    console.draw_text("4) --bot \"Path to level pack\" (JSON protocol on stdin/stdout)");
                 }],
             ),
         ].into_boxed_slice()
@@ -840,11 +1327,11 @@ impl HelpPage {
         console.reset_color();
         console.draw_text("Page: ");
         console.set_color(Color::Cyan, Color::Default);
-        console.draw_text(format!("{}", self.page + 1));
+        console.draw_fmt(format_args!("{}", self.page + 1));
         console.reset_color();
         console.draw_text(" of ");
         console.set_color(Color::Cyan, Color::Default);
-        console.draw_text(format!("{}", self.page_count));
+        console.draw_fmt(format_args!("{}", self.page_count));
     }
 
     pub fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
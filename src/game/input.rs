@@ -0,0 +1,267 @@
+#[cfg(any(feature = "cli", feature = "gui"))]
+use std::collections::VecDeque;
+use crate::game::ControlPreset;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::ArrowKeyPriority;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::io::Key;
+
+#[cfg(all(test, any(feature = "cli", feature = "gui")))]
+mod tests;
+
+/// Queue of pressed keys, only one key is processed per update
+///
+/// The same rules are applied in the CLI and the GUI version, because both backends provide their keys to this queue
+#[cfg(any(feature = "cli", feature = "gui"))]
+#[derive(Default)]
+pub struct KeyInputQueue {
+    keys: VecDeque<Key>,
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl KeyInputQueue {
+    pub fn new() -> Self {
+        Self {
+            keys: VecDeque::new(),
+        }
+    }
+
+    /// Adds all keys which were pressed since the last update
+    ///
+    /// If different arrow keys were pressed directly after each other, only the arrow keys which are chosen by the
+    /// arrow key priority are added (Repeated presses of the same arrow key are always kept)
+    pub fn push_keys(&mut self, keys: impl IntoIterator<Item = Key>, arrow_key_priority: ArrowKeyPriority) {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+
+        let mut i = 0;
+        while i < keys.len() {
+            let arrow_key_count = keys[i..].iter().take_while(|&&key| is_arrow_key(key)).count();
+            if arrow_key_count == 0 {
+                self.keys.push_back(keys[i]);
+                i += 1;
+
+                continue;
+            }
+
+            let arrow_keys = &keys[i..i + arrow_key_count];
+            let is_simultaneous = arrow_keys.iter().any(|&key| key != arrow_keys[0]);
+
+            match arrow_key_priority {
+                ArrowKeyPriority::MostRecent if is_simultaneous => self.keys.push_back(arrow_keys[arrow_key_count - 1]),
+                ArrowKeyPriority::First if is_simultaneous => self.keys.push_back(arrow_keys[0]),
+
+                _ => self.keys.extend(arrow_keys),
+            }
+
+            i += arrow_key_count;
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<Key> {
+        self.keys.pop_front()
+    }
+}
+
+/// Start and end sequences of pasted text in terminals with enabled bracketed paste mode
+#[cfg(any(feature = "cli", feature = "gui"))]
+const BRACKETED_PASTE_START: [Key; 6] = [Key::ESC, Key::LEFT_BRACKET, Key::DIGIT_2, Key::DIGIT_0, Key::DIGIT_0, Key::TILDE];
+#[cfg(any(feature = "cli", feature = "gui"))]
+const BRACKETED_PASTE_END: [Key; 6] = [Key::ESC, Key::LEFT_BRACKET, Key::DIGIT_2, Key::DIGIT_0, Key::DIGIT_1, Key::TILDE];
+
+/// Separates text which was pasted in the terminal ("ESC[200~TEXT ESC[201~") from the pressed keys
+///
+/// The pasted text can be split over multiple updates if the terminal sends large texts in multiple chunks
+#[cfg(any(feature = "cli", feature = "gui"))]
+#[derive(Default)]
+pub struct BracketedPasteParser {
+    pasted_text: Option<String>,
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl BracketedPasteParser {
+    pub fn new() -> Self {
+        Self {
+            pasted_text: None,
+        }
+    }
+
+    /// Returns the keys which are not part of pasted text and all texts which were completely pasted
+    pub fn parse(&mut self, keys: &[Key]) -> (Vec<Key>, Vec<String>) {
+        let mut pressed_keys = Vec::with_capacity(keys.len());
+        let mut pasted_texts = Vec::new();
+
+        let mut i = 0;
+        while i < keys.len() {
+            if self.pasted_text.is_some() && keys[i..].starts_with(&BRACKETED_PASTE_END) {
+                pasted_texts.extend(self.pasted_text.take());
+                i += BRACKETED_PASTE_END.len();
+            }else if let Some(pasted_text) = &mut self.pasted_text {
+                if keys[i] == Key::ENTER {
+                    pasted_text.push('\n');
+                }else if let Some(c) = keys[i].to_ascii() {
+                    pasted_text.push(c as char);
+                }
+                i += 1;
+            }else if keys[i..].starts_with(&BRACKETED_PASTE_START) {
+                self.pasted_text = Some(String::new());
+                i += BRACKETED_PASTE_START.len();
+            }else {
+                pressed_keys.push(keys[i]);
+                i += 1;
+            }
+        }
+
+        (pressed_keys, pasted_texts)
+    }
+}
+
+/// Actions of the in-game screen whose keys depend on the control preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InGameAction {
+    Undo,
+    Redo,
+    /// Undo multiple moves at once
+    UndoJump,
+    /// Redo multiple moves at once
+    RedoJump,
+    Reset,
+    ToggleFloor,
+    ToggleTileLegend,
+    Hint,
+}
+
+impl InGameAction {
+    pub const VALUES: [InGameAction; 8] = [
+        InGameAction::Undo, InGameAction::Redo, InGameAction::UndoJump, InGameAction::RedoJump,
+        InGameAction::Reset, InGameAction::ToggleFloor, InGameAction::ToggleTileLegend, InGameAction::Hint,
+    ];
+
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn key(self, control_preset: ControlPreset) -> Key {
+        match control_preset {
+            ControlPreset::Default => match self {
+                InGameAction::Undo => Key::Z,
+                InGameAction::Redo => Key::Y,
+                InGameAction::UndoJump => Key::COMMA,
+                InGameAction::RedoJump => Key::DOT,
+                InGameAction::Reset => Key::R,
+                InGameAction::ToggleFloor => Key::Q,
+                InGameAction::ToggleTileLegend => Key::L,
+                InGameAction::Hint => Key::H,
+            },
+
+            ControlPreset::OneHanded => match self {
+                InGameAction::Undo => Key::Q,
+                InGameAction::Redo => Key::E,
+                InGameAction::UndoJump => Key::Z,
+                InGameAction::RedoJump => Key::X,
+                InGameAction::Reset => Key::R,
+                InGameAction::ToggleFloor => Key::F,
+                InGameAction::ToggleTileLegend => Key::G,
+                InGameAction::Hint => Key::T,
+            },
+        }
+    }
+
+    /// Parses the name of the action which is used in level hints (e.g. "Undo" for "{a:Undo}")
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Undo" => InGameAction::Undo,
+            "Redo" => InGameAction::Redo,
+            "UndoJump" => InGameAction::UndoJump,
+            "RedoJump" => InGameAction::RedoJump,
+            "Reset" => InGameAction::Reset,
+            "ToggleFloor" => InGameAction::ToggleFloor,
+            "ToggleTileLegend" => InGameAction::ToggleTileLegend,
+            "Hint" => InGameAction::Hint,
+
+            _ => return None,
+        })
+    }
+
+    /// Returns the name of the key which is shown in key hints (Must match [Self::key], the name is also available
+    /// in builds without console, because level hints contain actions)
+    pub fn key_name(self, control_preset: ControlPreset) -> &'static str {
+        match control_preset {
+            ControlPreset::Default => match self {
+                InGameAction::Undo => "z",
+                InGameAction::Redo => "y",
+                InGameAction::UndoJump => ",",
+                InGameAction::RedoJump => ".",
+                InGameAction::Reset => "r",
+                InGameAction::ToggleFloor => "q",
+                InGameAction::ToggleTileLegend => "l",
+                InGameAction::Hint => "h",
+            },
+
+            ControlPreset::OneHanded => match self {
+                InGameAction::Undo => "q",
+                InGameAction::Redo => "e",
+                InGameAction::UndoJump => "z",
+                InGameAction::RedoJump => "x",
+                InGameAction::Reset => "r",
+                InGameAction::ToggleFloor => "f",
+                InGameAction::ToggleTileLegend => "g",
+                InGameAction::Hint => "t",
+            },
+        }
+    }
+}
+
+/// Actions of the level selection and level editor screens whose keys do not depend on the control preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    LevelPreview,
+    SkipLevel,
+    /// Set or unset the level pack thumbnail in the level pack editor
+    LevelPackThumbnail,
+}
+
+impl MenuAction {
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn key(self) -> Key {
+        match self {
+            MenuAction::LevelPreview => Key::P,
+            MenuAction::SkipLevel => Key::N,
+            MenuAction::LevelPackThumbnail => Key::T,
+        }
+    }
+
+    /// Returns the name of the key which is shown in key hints
+    pub fn key_name(self) -> &'static str {
+        match self {
+            MenuAction::LevelPreview => "p",
+            MenuAction::SkipLevel => "n",
+            MenuAction::LevelPackThumbnail => "t",
+        }
+    }
+}
+
+/// Translates a key of the control preset to the key of the same action in the default preset
+///
+/// Keys of the default preset which are not used by the control preset are ignored (None), all other keys are not changed
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub fn to_default_preset_key(key: Key, control_preset: ControlPreset) -> Option<Key> {
+    if control_preset == ControlPreset::Default {
+        return Some(key);
+    }
+
+    if let Some(action) = InGameAction::VALUES.into_iter().find(|action| action.key(control_preset) == key) {
+        return Some(action.key(ControlPreset::Default));
+    }
+
+    //"u" is an alternative undo key of the default preset
+    let is_default_preset_key = key == Key::U ||
+            InGameAction::VALUES.into_iter().any(|action| action.key(ControlPreset::Default) == key);
+    if is_default_preset_key {
+        None
+    }else {
+        Some(key)
+    }
+}
+
+/// WASD keys are not included, because they are also used for text input
+#[cfg(any(feature = "cli", feature = "gui"))]
+fn is_arrow_key(key: Key) -> bool {
+    matches!(key, Key::LEFT | Key::UP | Key::RIGHT | Key::DOWN)
+}
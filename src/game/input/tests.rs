@@ -0,0 +1,87 @@
+use crate::game::input::*;
+
+fn pushed_keys(keys: impl IntoIterator<Item = Key>, arrow_key_priority: ArrowKeyPriority) -> Vec<Key> {
+    let mut key_input_queue = KeyInputQueue::new();
+    key_input_queue.push_keys(keys, arrow_key_priority);
+
+    std::iter::from_fn(|| key_input_queue.pop()).collect()
+}
+
+#[test]
+fn arrow_key_priority_of_simultaneous_arrow_keys() {
+    let keys = [Key::UP, Key::RIGHT, Key::R, Key::LEFT, Key::LEFT];
+
+    assert!(pushed_keys(keys, ArrowKeyPriority::InOrder) == keys);
+    assert!(pushed_keys(keys, ArrowKeyPriority::MostRecent) == [Key::RIGHT, Key::R, Key::LEFT, Key::LEFT]);
+    assert!(pushed_keys(keys, ArrowKeyPriority::First) == [Key::UP, Key::R, Key::LEFT, Key::LEFT]);
+
+    //Text input is never changed
+    let keys = [Key::W, Key::A, Key::S, Key::D];
+    assert!(pushed_keys(keys, ArrowKeyPriority::MostRecent) == keys);
+}
+
+#[test]
+fn one_handed_control_preset_keys() {
+    assert!(to_default_preset_key(Key::Q, ControlPreset::OneHanded) == Some(Key::Z));
+    assert!(to_default_preset_key(Key::E, ControlPreset::OneHanded) == Some(Key::Y));
+    assert!(to_default_preset_key(Key::F, ControlPreset::OneHanded) == Some(Key::Q));
+
+    //Keys of the default preset are not mixed with the one-handed preset
+    assert!(to_default_preset_key(Key::H, ControlPreset::OneHanded).is_none());
+    assert!(to_default_preset_key(Key::U, ControlPreset::OneHanded).is_none());
+
+    //Movement keys are not changed
+    for key in [Key::W, Key::A, Key::S, Key::D, Key::SPACE] {
+        assert!(to_default_preset_key(key, ControlPreset::OneHanded) == Some(key));
+    }
+
+    for action in InGameAction::VALUES {
+        assert!(to_default_preset_key(action.key(ControlPreset::Default), ControlPreset::Default) == Some(action.key(ControlPreset::Default)));
+        assert_ne!(action.key_name(ControlPreset::OneHanded), "?");
+    }
+}
+
+#[test]
+fn in_game_action_key_names_match_keys() {
+    let key_names = [
+        (Key::COMMA, ","), (Key::DOT, "."), (Key::E, "e"), (Key::F, "f"), (Key::G, "g"), (Key::H, "h"), (Key::L, "l"),
+        (Key::Q, "q"), (Key::R, "r"), (Key::T, "t"), (Key::X, "x"), (Key::Y, "y"), (Key::Z, "z"),
+    ];
+
+    for control_preset in [ControlPreset::Default, ControlPreset::OneHanded] {
+        for action in InGameAction::VALUES {
+            let key = action.key(control_preset);
+            let key_name = action.key_name(control_preset);
+
+            assert!(key_names.iter().any(|&(expected_key, expected_key_name)| expected_key == key && expected_key_name == key_name), "{action:?}");
+        }
+    }
+}
+
+#[test]
+fn in_game_action_names() {
+    for action in InGameAction::VALUES {
+        assert_eq!(InGameAction::from_name(&format!("{action:?}")), Some(action));
+    }
+
+    assert_eq!(InGameAction::from_name("undo"), None);
+}
+
+#[test]
+fn bracketed_paste_text_is_separated_from_keys() {
+    let mut parser = BracketedPasteParser::new();
+
+    let mut keys = vec![Key::W];
+    keys.extend(BRACKETED_PASTE_START);
+    keys.extend([Key::A, Key::DIGIT_1, Key::ENTER, Key::B]);
+    let (pressed_keys, pasted_texts) = parser.parse(&keys);
+    assert!(pressed_keys == [Key::W]);
+    assert!(pasted_texts.is_empty());
+
+    //The end sequence is received in the next update
+    let mut keys = BRACKETED_PASTE_END.to_vec();
+    keys.push(Key::ESC);
+    let (pressed_keys, pasted_texts) = parser.parse(&keys);
+    assert!(pressed_keys == [Key::ESC]);
+    assert_eq!(pasted_texts, ["a1\nb"]);
+}
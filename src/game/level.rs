@@ -1,20 +1,55 @@
-use crate::game::{audio, Game, GameError};
+use crate::game::{audio, Game, GameError, Language};
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet};
 use std::error::Error;
 use std::ffi::OsString;
 use std::fmt::{Debug, Display, Formatter, Write as _};
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 use std::str::FromStr;
+use std::time::SystemTime;
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
 use crate::collections::UndoHistory;
+use crate::game::archive::LevelPackArchive;
 use crate::game::audio::BackgroundMusicId;
+use crate::game::checksum;
+use crate::game::logging::log_warn;
+use crate::game::save_writer::SaveWriter;
+use crate::game::console_extension;
+use crate::game::telemetry;
+use crate::game::trash;
+#[cfg(any(feature = "cli", feature = "gui"))]
 use crate::game::console_extension::ConsoleExtension;
+use crate::game::input::InGameAction;
+use crate::utils;
+#[cfg(any(feature = "cli", feature = "gui"))]
 use crate::io::{Color, Console};
 
 #[cfg(feature = "steam")]
 use bevy_steamworks::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Tile {
+#[cfg(test)]
+mod tests;
+
+/// Declares the tiles and [Tile::VALUES] from the same list, new tiles can not be missing in [Tile::VALUES]
+macro_rules! tiles {
+    { $( $variant:ident ),* $(,)? } => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Tile {
+            $( $variant, )*
+        }
+
+        impl Tile {
+            /// All tiles in declaration order
+            pub const VALUES: [Tile; [$( Tile::$variant ),*].len()] = [$( Tile::$variant ),*];
+        }
+    };
+}
+
+tiles! {
     Empty,
     FragileFloor,
     Ice,
@@ -51,6 +86,72 @@ pub enum Tile {
 }
 
 impl Tile {
+    /// Returns the name which is shown in the tile legend or None if the tile should not be listed
+    pub fn legend_name(self) -> Option<&'static str> {
+        match self {
+            Tile::Empty => Some("Empty"),
+            Tile::FragileFloor => Some("Fragile floor"),
+            Tile::Ice => Some("Ice"),
+
+            Tile::OneWayLeft => Some("One-way door (left)"),
+            Tile::OneWayUp => Some("One-way door (up)"),
+            Tile::OneWayRight => Some("One-way door (right)"),
+            Tile::OneWayDown => Some("One-way door (down)"),
+
+            Tile::Wall => Some("Wall"),
+
+            Tile::Player => Some("Player"),
+            Tile::PlayerOnFragileFloor => Some("Player on fragile floor"),
+            Tile::PlayerOnIce => Some("Player on ice"),
+
+            Tile::Key => Some("Key"),
+            Tile::KeyInGoal => Some("Key in goal"),
+            Tile::KeyOnFragileFloor => Some("Key on fragile floor"),
+            Tile::KeyOnIce => Some("Key on ice"),
+            Tile::LockedDoor => Some("Locked door"),
+
+            Tile::Box => Some("Box"),
+            Tile::BoxInGoal => Some("Box in goal"),
+            Tile::BoxOnFragileFloor => Some("Box on fragile floor"),
+            Tile::BoxOnIce => Some("Box on ice"),
+            Tile::Goal => Some("Goal"),
+
+            Tile::Hole => Some("Hole"),
+            Tile::BoxInHole => Some("Filled hole"),
+
+            Tile::DecorationBlank => None,
+
+            Tile::Secret => None,
+        }
+    }
+
+    /// Returns the first game version which supports this tile (Used for the minimum game version of level packs)
+    pub fn min_game_version(self) -> GameVersion {
+        match self {
+            Tile::Empty | Tile::FragileFloor | Tile::Ice |
+            Tile::OneWayLeft | Tile::OneWayUp | Tile::OneWayRight | Tile::OneWayDown |
+            Tile::Wall |
+            Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce |
+            Tile::Key | Tile::KeyInGoal | Tile::KeyOnFragileFloor | Tile::KeyOnIce | Tile::LockedDoor |
+            Tile::Box | Tile::BoxInGoal | Tile::BoxOnFragileFloor | Tile::BoxOnIce | Tile::Goal |
+            Tile::Hole | Tile::BoxInHole |
+            Tile::DecorationBlank |
+            Tile::Secret => GameVersion::V1_0,
+        }
+    }
+
+    /// Returns the player tile for a player which starts on this tile or None if the player cannot start on this tile
+    pub fn player_start_tile(self) -> Option<Self> {
+        match self {
+            Tile::Empty | Tile::Goal | Tile::BoxInHole |
+            Tile::OneWayLeft | Tile::OneWayUp | Tile::OneWayRight | Tile::OneWayDown => Some(Tile::Player),
+            Tile::FragileFloor => Some(Tile::PlayerOnFragileFloor),
+            Tile::Ice => Some(Tile::PlayerOnIce),
+
+            _ => None,
+        }
+    }
+
     pub fn floor_tile(self) -> Self {
         match self {
             Tile::Empty => Tile::Empty,
@@ -126,7 +227,7 @@ impl Tile {
 
             b's' | b'S' => Ok(Tile::Secret),
 
-            _ => Err(LevelLoadingError::new("Invalid tile")),
+            _ => Err(LevelLoadingError::new("Tile is invalid!")),
         }
     }
 
@@ -169,93 +270,60 @@ impl Tile {
         }
     }
 
+    #[cfg(any(feature = "cli", feature = "gui"))]
     pub fn draw(self, console: &Console, is_player_background: bool, inverted: bool) {
-        console.draw_tile(self, is_player_background, inverted);
+        console.draw_tile(self, is_player_background, inverted, Color::Default);
+    }
+
+    /// Draws the tile with the provided background color if the tile has no background color of its own
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn draw_with_background(self, console: &Console, is_player_background: bool, inverted: bool, background: Color) {
+        console.draw_tile(self, is_player_background, inverted, background);
+    }
+
+    /// Draws the graphical variant of the tile (e.g. of a wall) with the provided background color if the tile has no background color of its own
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn draw_variant_with_background(self, console: &Console, variant: usize, is_player_background: bool, inverted: bool, background: Color) {
+        console.draw_tile_variant(self, variant, is_player_background, inverted, background);
     }
 
-    pub fn draw_raw(self, console: &Console, is_player_background: bool, inverted: bool) {
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn draw_raw(self, console: &Console, is_player_background: bool, inverted: bool, background: Color) {
+        let (text, fg, bg) = self.text_appearance(is_player_background);
+        let bg = if bg == Color::Default { background } else { bg };
+
+        console.set_color_invertible(fg, bg, inverted);
+        console.draw_text(text);
+    }
+
+    /// Returns the text and the foreground and background color which are used to draw this tile in the console
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn text_appearance(self, is_player_background: bool) -> (&'static str, Color, Color) {
         match self {
-            Tile::Empty => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("-");
-            },
-            Tile::FragileFloor => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("~");
-            },
-            Tile::Ice => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("%");
-            },
-            Tile::OneWayLeft => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("<");
-            },
-            Tile::OneWayUp => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("^");
-            },
-            Tile::OneWayRight => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text(">");
-            },
-            Tile::OneWayDown => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("v");
-            },
-            Tile::Wall => {
-                console.set_color_invertible(Color::LightGreen, Color::Default, inverted);
-                console.draw_text("#");
-            },
-            Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce => {
-                if is_player_background {
-                    console.set_color_invertible(Color::Default, Color::Yellow, inverted);
-                }else {
-                    console.set_color_invertible(Color::Yellow, Color::Default, inverted);
-                }
-                console.draw_text("P");
-            },
-            Tile::Key | Tile::KeyOnFragileFloor | Tile::KeyOnIce => {
-                console.set_color_invertible(Color::LightCyan, Color::Default, inverted);
-                console.draw_text("*");
-            },
-            Tile::KeyInGoal => {
-                console.set_color_invertible(Color::LightPink, Color::Default, inverted);
-                console.draw_text("*");
-            },
-            Tile::LockedDoor => {
-                console.set_color_invertible(Color::LightRed, Color::Default, inverted);
-                console.draw_text("=");
-            },
-            Tile::Box | Tile::BoxOnFragileFloor | Tile::BoxOnIce => {
-                console.set_color_invertible(Color::LightCyan, Color::Default, inverted);
-                console.draw_text("@");
-            },
-            Tile::BoxInGoal => {
-                console.set_color_invertible(Color::LightPink, Color::Default, inverted);
-                console.draw_text("@");
-            },
-            Tile::Goal => {
-                console.set_color_invertible(Color::LightRed, Color::Default, inverted);
-                console.draw_text("x");
-            },
-            Tile::Hole => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("O");
-            },
-            Tile::BoxInHole => {
-                console.set_color_invertible(Color::Default, Color::LightBlue, inverted);
-                console.draw_text("@");
-            },
-            Tile::DecorationBlank => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text(" ");
-            },
-            Tile::Secret => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("+");
+            Tile::Empty => ("-", Color::LightBlue, Color::Default),
+            Tile::FragileFloor => ("~", Color::LightBlue, Color::Default),
+            Tile::Ice => ("%", Color::LightBlue, Color::Default),
+            Tile::OneWayLeft => ("<", Color::LightBlue, Color::Default),
+            Tile::OneWayUp => ("^", Color::LightBlue, Color::Default),
+            Tile::OneWayRight => (">", Color::LightBlue, Color::Default),
+            Tile::OneWayDown => ("v", Color::LightBlue, Color::Default),
+            Tile::Wall => ("#", Color::LightGreen, Color::Default),
+            Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce => if is_player_background {
+                ("P", Color::Default, Color::Yellow)
+            }else {
+                ("P", Color::Yellow, Color::Default)
             },
-        };
+            Tile::Key | Tile::KeyOnFragileFloor | Tile::KeyOnIce => ("*", Color::LightCyan, Color::Default),
+            Tile::KeyInGoal => ("*", Color::LightPink, Color::Default),
+            Tile::LockedDoor => ("=", Color::LightRed, Color::Default),
+            Tile::Box | Tile::BoxOnFragileFloor | Tile::BoxOnIce => ("@", Color::LightCyan, Color::Default),
+            Tile::BoxInGoal => ("@", Color::LightPink, Color::Default),
+            Tile::Goal => ("x", Color::LightRed, Color::Default),
+            Tile::Hole => ("O", Color::LightBlue, Color::Default),
+            Tile::BoxInHole => ("@", Color::Default, Color::LightBlue),
+            Tile::DecorationBlank => (" ", Color::LightBlue, Color::Default),
+            Tile::Secret => ("+", Color::LightBlue, Color::Default),
+        }
     }
 }
 
@@ -268,6 +336,26 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub fn from_ascii(a: u8) -> Option<Self> {
+        match a {
+            b'l' => Some(Direction::Left),
+            b'u' => Some(Direction::Up),
+            b'r' => Some(Direction::Right),
+            b'd' => Some(Direction::Down),
+
+            _ => None,
+        }
+    }
+
+    pub fn to_ascii(self) -> u8 {
+        match self {
+            Direction::Left => b'l',
+            Direction::Up => b'u',
+            Direction::Right => b'r',
+            Direction::Down => b'd',
+        }
+    }
+
     pub fn update_x(self, x: usize, width: usize) -> usize {
         match self {
             Direction::Left => if x == 0 {
@@ -321,12 +409,21 @@ enum AnimationState {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LevelSoundEffect {
-    BoxFall,
+    BoxSink,
     KeyFall,
     DoorUnlocked,
     FloorBroken,
 }
 
+/// A change of a single tile caused by the last move which can be shown by screens (E.g. with an animation)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileEvent {
+    BoxSunk {
+        x: usize,
+        y: usize,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub enum MoveResult {
     Valid {
@@ -363,11 +460,156 @@ impl MoveResult {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyDoorCount {
+    keys: usize,
+    locked_doors: usize,
+
+    /// Keys which were used to unlock a door
+    collected_keys: usize,
+}
+
+impl KeyDoorCount {
+    pub fn from_level(level: &Level) -> Self {
+        let keys = level.tiles().iter().filter(|tile| matches!(tile, Tile::Key | Tile::KeyInGoal | Tile::KeyOnFragileFloor | Tile::KeyOnIce)).count();
+        let locked_doors = level.tiles().iter().filter(|tile| **tile == Tile::LockedDoor).count();
+
+        Self { keys, locked_doors, collected_keys: 0 }
+    }
+
+    fn on_key_lost(&mut self) {
+        self.keys = self.keys.saturating_sub(1);
+    }
+
+    fn on_door_unlocked(&mut self) {
+        self.keys = self.keys.saturating_sub(1);
+        self.locked_doors = self.locked_doors.saturating_sub(1);
+        self.collected_keys += 1;
+    }
+
+    /// Returns the count of keys which are still in the level
+    pub fn keys(&self) -> usize {
+        self.keys
+    }
+
+    pub fn collected_keys(&self) -> usize {
+        self.collected_keys
+    }
+
+    pub fn locked_doors(&self) -> usize {
+        self.locked_doors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys == 0 && self.locked_doors == 0
+    }
+}
+
+/// Visual theme of a level which is drawn as background of all tiles without their own background color
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LevelTheme {
+    #[default]
+    Default,
+    Night,
+    Forest,
+    Ocean,
+    Lava,
+    Candy,
+    Desert,
+    Stone,
+}
+
+impl LevelTheme {
+    pub const ALL: [LevelTheme; 8] = [
+        LevelTheme::Default, LevelTheme::Night, LevelTheme::Forest, LevelTheme::Ocean,
+        LevelTheme::Lava, LevelTheme::Candy, LevelTheme::Desert, LevelTheme::Stone,
+    ];
+
+    /// Returns the ID which is used in level pack files
+    pub fn id(self) -> &'static str {
+        match self {
+            LevelTheme::Default => "default",
+            LevelTheme::Night => "night",
+            LevelTheme::Forest => "forest",
+            LevelTheme::Ocean => "ocean",
+            LevelTheme::Lava => "lava",
+            LevelTheme::Candy => "candy",
+            LevelTheme::Desert => "desert",
+            LevelTheme::Stone => "stone",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|theme| theme.id() == id)
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            LevelTheme::Default => "Default",
+            LevelTheme::Night => "Night",
+            LevelTheme::Forest => "Forest",
+            LevelTheme::Ocean => "Ocean",
+            LevelTheme::Lava => "Lava",
+            LevelTheme::Candy => "Candy",
+            LevelTheme::Desert => "Desert",
+            LevelTheme::Stone => "Stone",
+        }
+    }
+
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn background_color(self) -> Color {
+        match self {
+            LevelTheme::Default => Color::Default,
+            LevelTheme::Night => Color::Blue,
+            LevelTheme::Forest => Color::Green,
+            LevelTheme::Ocean => Color::Cyan,
+            LevelTheme::Lava => Color::Red,
+            LevelTheme::Candy => Color::Pink,
+            LevelTheme::Desert => Color::Yellow,
+            LevelTheme::Stone => Color::LightBlack,
+        }
+    }
+
+    /// Returns the next theme (Used by the level editor to cycle through all themes)
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|theme| *theme == self).unwrap();
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// The difference of a single tile between two versions of a level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileChange {
+    /// An empty tile was replaced
+    Added,
+    /// A tile was replaced with an empty tile
+    Removed,
+    /// A tile was replaced with another non-empty tile
+    Changed,
+}
+
+impl TileChange {
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn background_color(self) -> Color {
+        match self {
+            TileChange::Added => Color::Green,
+            TileChange::Removed => Color::Red,
+            TileChange::Changed => Color::Yellow,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Level {
     width: usize,
     height: usize,
     tiles: Vec<Tile>,
+    //The player starts on the tile at the start marker instead of a player tile (e.g. on a goal)
+    start_pos: Option<(usize, usize)>,
+
+    wrap_around: bool,
+    theme: LevelTheme,
 }
 
 impl Level {
@@ -382,7 +624,7 @@ impl Level {
 
         let tiles = vec![Tile::Empty; width * height];
 
-        Level { width, height, tiles }
+        Level { width, height, tiles, start_pos: None, wrap_around: true, theme: LevelTheme::Default }
     }
 
     pub fn width(&self) -> usize {
@@ -409,30 +651,390 @@ impl Level {
         self.tiles[x + y * self.width] = tile;
     }
 
-    pub fn draw(&self, console: &Console, x_offset: usize, y_offset: usize, is_player_background: bool, cursor_pos: Option<(usize, usize)>) {
-        let mut tile_iter = self.tiles.iter();
+    /// Returns the position of the start marker (The player starts on the tile at this position)
+    pub fn start_pos(&self) -> Option<(usize, usize)> {
+        self.start_pos
+    }
+
+    pub fn set_start_pos(&mut self, start_pos: Option<(usize, usize)>) {
+        self.start_pos = start_pos;
+    }
+
+    /// Returns the count of player tiles including the start marker
+    pub fn player_count(&self) -> usize {
+        self.tiles.iter().
+                filter(|tile| matches!(tile, Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce)).
+                count() + self.start_pos.is_some() as usize
+    }
 
-        for i in 0..self.height {
-            console.set_cursor_pos(x_offset, i + y_offset);
+    /// Returns a copy of this level in which the player tile is placed at the start marker
+    ///
+    /// An error is returned if the start marker is not inside the level or if the player cannot start on the tile at the start marker
+    pub fn with_player_at_start(&self) -> Result<Level, LevelLoadingError> {
+        let mut level = self.clone();
 
-            for j in 0..self.width {
-                if let Some(tile) = tile_iter.next() {
-                    tile.draw(console, is_player_background, cursor_pos.is_some_and(|(x, y)| x == j && y == i));
+        let Some((x, y)) = level.start_pos.take() else {
+            return Ok(level);
+        };
+
+        if x >= level.width || y >= level.height {
+            return Err(LevelLoadingError::new("The start marker is outside of the level!"));
+        }
+
+        let Some(player_tile) = level.get_tile(x, y).unwrap().player_start_tile() else {
+            return Err(LevelLoadingError::new("The player cannot start on the tile at the start marker!"));
+        };
+        level.set_tile(x, y, player_tile);
+
+        Ok(level)
+    }
+
+    /// Returns true if movement at the level border wraps around to the opposite side of the level
+    pub fn wrap_around(&self) -> bool {
+        self.wrap_around
+    }
+
+    pub fn set_wrap_around(&mut self, wrap_around: bool) {
+        self.wrap_around = wrap_around;
+    }
+
+    pub fn theme(&self) -> LevelTheme {
+        self.theme
+    }
+
+    pub fn set_theme(&mut self, theme: LevelTheme) {
+        self.theme = theme;
+    }
+
+    /// Returns the positions of all tiles which differ from the provided previous version of this level
+    ///
+    /// Tiles outside the previous version (e.g. after the level was resized) are compared with empty tiles
+    pub fn tile_changes(&self, previous_level: &Level) -> Vec<(usize, usize, TileChange)> {
+        let mut changes = Vec::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let tile = self.tiles[x + y * self.width];
+                let previous_tile = if x < previous_level.width && y < previous_level.height {
+                    previous_level.tiles[x + y * previous_level.width]
+                }else {
+                    Tile::Empty
+                };
+
+                let change = match (previous_tile, tile) {
+                    (previous_tile, tile) if previous_tile == tile => continue,
+
+                    (Tile::Empty, _) => TileChange::Added,
+                    (_, Tile::Empty) => TileChange::Removed,
+                    _ => TileChange::Changed,
+                };
+
+                changes.push((x, y, change));
+            }
+        }
+
+        changes
+    }
+
+    /// Returns the position next to the provided position in the provided direction
+    ///
+    /// Returns None if the position is at the level border and the level does not wrap around
+    pub fn neighbor_pos(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
+        let is_at_border = match direction {
+            Direction::Left => x == 0,
+            Direction::Up => y == 0,
+            Direction::Right => x + 1 >= self.width,
+            Direction::Down => y + 1 >= self.height,
+        };
+
+        if is_at_border && !self.wrap_around {
+            return None;
+        }
+
+        Some(direction.update_xy(x, y, self.width, self.height))
+    }
+
+    /// Replaces all tiles of the contiguous region of identical tiles at the provided position with the provided tile
+    ///
+    /// Returns false if no tile was changed
+    pub fn flood_fill(&mut self, x: usize, y: usize, tile: Tile) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+
+        let region_tile = self.get_tile(x, y).unwrap();
+        if region_tile == tile {
+            return false;
+        }
+
+        self.set_tile(x, y, tile);
+
+        let mut positions = vec![(x, y)];
+        while let Some((x, y)) = positions.pop() {
+            let neighbors = [
+                (x > 0).then(|| (x - 1, y)),
+                (y > 0).then(|| (x, y - 1)),
+                (x + 1 < self.width).then_some((x + 1, y)),
+                (y + 1 < self.height).then_some((x, y + 1)),
+            ];
+
+            for (x, y) in neighbors.into_iter().flatten() {
+                if self.get_tile(x, y) == Some(region_tile) {
+                    self.set_tile(x, y, tile);
+
+                    positions.push((x, y));
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns a copy of this level in which the play area is surrounded by walls
+    ///
+    /// The play area is the area which is enclosed by all tiles other than empty, wall and decoration tiles.
+    /// The level is extended on each side where the play area touches the level border.
+    ///
+    /// Returns None if the level contains no play area
+    pub fn with_wall_outline(&self) -> Option<Level> {
+        let play_area = self.play_area()?;
+        let is_play_area = |x: usize, y: usize| play_area[x + y * self.width];
+
+        let pad_left = (0..self.height).any(|y| is_play_area(0, y)) as usize;
+        let pad_top = (0..self.width).any(|x| is_play_area(x, 0)) as usize;
+        let pad_right = (0..self.height).any(|y| is_play_area(self.width - 1, y)) as usize;
+        let pad_bottom = (0..self.width).any(|x| is_play_area(x, self.height - 1)) as usize;
+
+        let mut level = Level::new(self.width + pad_left + pad_right, self.height + pad_top + pad_bottom);
+        level.start_pos = self.start_pos.map(|(x, y)| (x + pad_left, y + pad_top));
+        level.wrap_around = self.wrap_around;
+        level.theme = self.theme;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                level.set_tile(x + pad_left, y + pad_top, self.get_tile(x, y).unwrap());
+            }
+        }
+
+        //Position in the new level -> Is part of the play area
+        let is_play_area = |x: usize, y: usize| {
+            x.checked_sub(pad_left).zip(y.checked_sub(pad_top)).
+                    is_some_and(|(x, y)| x < self.width && y < self.height && is_play_area(x, y))
+        };
+
+        for y in 0..level.height {
+            for x in 0..level.width {
+                if level.get_tile(x, y).unwrap() != Tile::Empty || is_play_area(x, y) {
+                    continue;
+                }
+
+                let is_border = (-1..=1).
+                        flat_map(|dy| (-1..=1).map(move |dx| (dx, dy))).
+                        filter_map(|(dx, dy)| x.checked_add_signed(dx).zip(y.checked_add_signed(dy))).
+                        any(|(x, y)| is_play_area(x, y));
+                if is_border {
+                    level.set_tile(x, y, Tile::Wall);
+                }
+            }
+        }
+
+        Some(level)
+    }
+
+    /// Returns a mask of all tiles which are part of the play area
+    ///
+    /// Empty tiles between play area tiles in the same row or column and diagonal gaps are filled until the play area does not change anymore.
+    /// The bounding box is used if the resulting play area is not connected.
+    fn play_area(&self) -> Option<Vec<bool>> {
+        let mut play_area = self.tiles.iter().
+                map(|tile| !matches!(tile, Tile::Empty | Tile::Wall | Tile::DecorationBlank)).
+                collect::<Vec<_>>();
+        if let Some((x, y)) = self.start_pos && x < self.width && y < self.height {
+            play_area[x + y * self.width] = true;
+        }
+
+        if !play_area.contains(&true) {
+            return None;
+        }
+
+        let index = |x: usize, y: usize| x + y * self.width;
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            let mut fill = |play_area: &mut [bool], indices: &[usize]| {
+                let Some(first) = indices.iter().position(|i| play_area[*i]) else {
+                    return;
+                };
+                let last = indices.iter().rposition(|i| play_area[*i]).unwrap();
+
+                for i in &indices[first..=last] {
+                    if !play_area[*i] {
+                        play_area[*i] = true;
+                        changed = true;
+                    }
+                }
+            };
+
+            for y in 0..self.height {
+                fill(&mut play_area, &(0..self.width).map(|x| index(x, y)).collect::<Vec<_>>());
+            }
+
+            for x in 0..self.width {
+                fill(&mut play_area, &(0..self.height).map(|y| index(x, y)).collect::<Vec<_>>());
+            }
+
+            for y in 0..self.height - 1 {
+                for x in 0..self.width - 1 {
+                    let square = [index(x, y), index(x + 1, y), index(x, y + 1), index(x + 1, y + 1)];
+
+                    let is_diagonal_gap = (play_area[square[0]] && play_area[square[3]]) ||
+                            (play_area[square[1]] && play_area[square[2]]);
+                    if is_diagonal_gap {
+                        fill(&mut play_area, &square);
+                    }
+                }
+            }
+        }
+
+        //Check if play area is connected
+        let play_area_size = play_area.iter().filter(|is_play_area| **is_play_area).count();
+
+        let start = play_area.iter().position(|is_play_area| *is_play_area).unwrap();
+        let mut visited = vec![false; play_area.len()];
+        visited[start] = true;
+
+        let mut visited_count = 1;
+        let mut positions = vec![(start % self.width, start / self.width)];
+        while let Some((x, y)) = positions.pop() {
+            let neighbors = [
+                (x > 0).then(|| (x - 1, y)),
+                (y > 0).then(|| (x, y - 1)),
+                (x + 1 < self.width).then_some((x + 1, y)),
+                (y + 1 < self.height).then_some((x, y + 1)),
+            ];
+
+            for (x, y) in neighbors.into_iter().flatten() {
+                if play_area[index(x, y)] && !visited[index(x, y)] {
+                    visited[index(x, y)] = true;
+                    visited_count += 1;
+
+                    positions.push((x, y));
+                }
+            }
+        }
+
+        if visited_count < play_area_size {
+            let (mut min_x, mut min_y, mut max_x, mut max_y) = (self.width, self.height, 0, 0);
+            for (i, _) in play_area.iter().enumerate().filter(|(_, is_play_area)| **is_play_area) {
+                min_x = min_x.min(i % self.width);
+                min_y = min_y.min(i / self.width);
+                max_x = max_x.max(i % self.width);
+                max_y = max_y.max(i / self.width);
+            }
+
+            for y in min_y..=max_y {
+                for x in min_x..=max_x {
+                    play_area[index(x, y)] = true;
                 }
             }
+        }
+
+        Some(play_area)
+    }
+
+    /// Returns the count of console columns which are used to draw a single tile of this level
+    pub fn tile_width(&self) -> usize {
+        console_extension::tile_size(self.width, self.height).0
+    }
+
+    /// Returns the count of console rows which are used to draw a single tile of this level
+    pub fn tile_height(&self) -> usize {
+        console_extension::tile_size(self.width, self.height).1
+    }
 
-            console.draw_text("\n");
+    /// Returns the count of console columns which are used to draw this level
+    pub fn draw_width(&self) -> usize {
+        self.width * self.tile_width()
+    }
+
+    /// Returns the count of console rows which are used to draw this level
+    pub fn draw_height(&self) -> usize {
+        self.height * self.tile_height()
+    }
+
+    /// Returns a hash of the positions of all walls of this level, it does not change while the level is played
+    pub fn wall_layout_hash(&self) -> u64 {
+        utils::fnv1a_64(self.tiles.iter().
+                map(|&tile| (tile == Tile::Wall) as u8).
+                chain((self.width as u32).to_le_bytes()))
+    }
+
+    /// Returns the graphical variant of the wall at the provided position (0 is the default wall graphic)
+    ///
+    /// The variant only depends on the wall layout of the level, screenshots of the same level always look the same
+    pub fn wall_variant(wall_layout_hash: u64, x: usize, y: usize) -> usize {
+        let hash = utils::fnv1a_64_extend(wall_layout_hash, (x as u32).to_le_bytes().into_iter().
+                chain((y as u32).to_le_bytes()));
+
+        //Half of all walls use the default wall graphic
+        match (hash >> 32) % 4 {
+            0 | 1 => 0,
+            variant => variant as usize - 1,
+        }
+    }
+
+    /// Returns the graphical variant of the tile at the provided position if wall variation is enabled
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    fn tile_variant(tile: Tile, wall_layout_hash: Option<u64>, x: usize, y: usize) -> usize {
+        match wall_layout_hash {
+            Some(wall_layout_hash) if tile == Tile::Wall => Self::wall_variant(wall_layout_hash, x, y),
+
+            _ => 0,
+        }
+    }
+
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn draw(&self, console: &Console, x_offset: usize, y_offset: usize, is_player_background: bool, cursor_pos: Option<(usize, usize)>) {
+        let (tile_width, tile_height) = console_extension::tile_size(self.width, self.height);
+        let wall_layout_hash = console_extension::is_wall_variation_enabled().then(|| self.wall_layout_hash());
+
+        for (i, row) in self.tiles.chunks(self.width).enumerate() {
+            for tile_row in 0..tile_height {
+                console.set_cursor_pos(x_offset, i * tile_height + tile_row + y_offset);
+
+                for (j, &tile) in row.iter().enumerate() {
+                    //The player is drawn on the tile at the start marker
+                    let tile = if self.start_pos == Some((j, i)) {
+                        tile.player_start_tile().unwrap_or(tile)
+                    }else {
+                        tile
+                    };
+
+                    let variant = Self::tile_variant(tile, wall_layout_hash, j, i);
+                    for _ in 0..tile_width {
+                        tile.draw_variant_with_background(
+                            console, variant, is_player_background, cursor_pos.is_some_and(|(x, y)| x == j && y == i),
+                            self.theme.background_color(),
+                        );
+                    }
+                }
+
+                console.draw_text("\n");
+            }
         }
     }
 
+    #[cfg(any(feature = "cli", feature = "gui"))]
     pub fn draw_floor(&self, console: &Console, x_offset: usize, y_offset: usize, is_player_background: bool, original_level: &Level, cursor_pos: Option<(usize, usize)>) {
-        let mut tile_iter = self.tiles.iter().copied();
+        let (tile_width, tile_height) = console_extension::tile_size(self.width, self.height);
+        let wall_layout_hash = console_extension::is_wall_variation_enabled().then(|| self.wall_layout_hash());
 
-        for i in 0..self.height {
-            console.set_cursor_pos(x_offset, i + y_offset);
+        for (i, row) in self.tiles.chunks(self.width).enumerate() {
+            for tile_row in 0..tile_height {
+                console.set_cursor_pos(x_offset, i * tile_height + tile_row + y_offset);
 
-            for j in 0..self.width {
-                if let Some(tile) = tile_iter.next() {
+                for (j, &tile) in row.iter().enumerate() {
                     let tile = match tile.floor_tile() {
                         Tile::Player => match original_level.get_tile(j, i) {
                             Some(Tile::KeyOnIce | Tile::BoxOnIce | Tile::Ice | Tile::PlayerOnIce) => Tile::Ice,
@@ -461,11 +1063,17 @@ impl Level {
                         tile => tile,
                     };
 
-                    tile.draw(console, is_player_background, cursor_pos.is_some_and(|(x, y)| x == j && y == i));
+                    let variant = Self::tile_variant(tile, wall_layout_hash, j, i);
+                    for _ in 0..tile_width {
+                        tile.draw_variant_with_background(
+                            console, variant, is_player_background, cursor_pos.is_some_and(|(x, y)| x == j && y == i),
+                            self.theme.background_color(),
+                        );
+                    }
                 }
-            }
 
-            console.draw_text("\n");
+                console.draw_text("\n");
+            }
         }
     }
 
@@ -480,59 +1088,251 @@ impl Level {
 
         out
     }
-}
 
-impl FromStr for Level {
-    type Err = LevelLoadingError;
+    /// Returns a copy of the rectangular region of this level (Wrap around and the theme are copied from this level)
+    ///
+    /// Panics if the region is empty or not inside the level
+    pub fn region(&self, x: usize, y: usize, width: usize, height: usize) -> Level {
+        if x + width > self.width || y + height > self.height {
+            panic!("Region must be inside the level!");
+        }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s.lines().collect::<Vec<_>>();
+        let mut level = Level::new(width, height);
+        level.start_pos = self.start_pos.
+                filter(|&(start_x, start_y)| (x..x + width).contains(&start_x) && (y..y + height).contains(&start_y)).
+                map(|(start_x, start_y)| (start_x - x, start_y - y));
+        level.wrap_around = self.wrap_around;
+        level.theme = self.theme;
+        for j in 0..height {
+            for i in 0..width {
+                level.set_tile(i, j, self.get_tile(x + i, y + j).unwrap());
+            }
+        }
+
+        level
+    }
+
+    /// Converts the level to the XSB format which is used by most Sokoban programs
+    ///
+    /// Returns None if the level contains tiles which do not exist in the XSB format
+    pub fn to_xsb(&self) -> Option<String> {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+
+        for (y, row) in self.tiles.chunks(self.width).enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                out.push(match tile {
+                    Tile::Empty if self.start_pos == Some((x, y)) => '@',
+                    Tile::Goal if self.start_pos == Some((x, y)) => '+',
+
+                    Tile::Empty => ' ',
+                    Tile::Wall => '#',
+                    Tile::Player => '@',
+                    Tile::Box => '$',
+                    Tile::BoxInGoal => '*',
+                    Tile::Goal => '.',
+
+                    _ => return None,
+                });
+            }
+
+            //Trailing floor tiles are not written (They are added again during parsing)
+            out.truncate(out.trim_end_matches(' ').len());
+            out += "\n";
+        }
+
+        Some(out)
+    }
+
+    /// Parses the first level of the text in the XSB format (Run-length encoded rows are supported)
+    ///
+    /// Lines which are not part of a level (e.g. titles or comments) before the level are ignored
+    /// and short rows are filled with empty tiles. Wrap around is disabled for parsed levels.
+    pub fn from_xsb(s: &str) -> Result<Level, LevelLoadingError> {
+        let mut rows = Vec::new();
+        for line in s.lines() {
+            let decoded_rows = Self::decode_xsb_line(line.trim_end());
+            match decoded_rows {
+                Some(decoded_rows) => rows.extend(decoded_rows),
+
+                None if rows.is_empty() => {},
+
+                //Only the first level is parsed
+                None => break,
+            }
+        }
+
+        if rows.is_empty() {
+            return Err(LevelLoadingError::new("No level found (Expected XSB level rows)!"));
+        }
+
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+        if width == 0 {
+            return Err(LevelLoadingError::new("Level size must not be zero!"));
+        }
+
+        let mut level = Level::new(width, rows.len());
+        level.wrap_around = false;
+        for (y, row) in rows.iter().enumerate() {
+            for (x, tile) in row.bytes().enumerate() {
+                let tile = match tile {
+                    b' ' | b'-' | b'_' => Tile::Empty,
+                    b'#' => Tile::Wall,
+                    b'@' | b'p' => Tile::Player,
+                    b'$' | b'b' => Tile::Box,
+                    b'*' | b'B' => Tile::BoxInGoal,
+                    b'.' => Tile::Goal,
+
+                    //Only "+" and "P" are left
+                    _ => {
+                        return Err(LevelLoadingError::new("Player on goal tiles are not supported!").
+                                with_token(tile as char));
+                    },
+                };
+
+                level.set_tile(x, y, tile);
+            }
+        }
+
+        Ok(level)
+    }
+
+    /// Returns the rows of the line or None if the line is not part of an XSB level
+    fn decode_xsb_line(line: &str) -> Option<Vec<String>> {
+        if line.is_empty() {
+            return None;
+        }
+
+        let mut rows = vec![String::new()];
+        let mut count: Option<usize> = None;
+        for c in line.chars() {
+            match c {
+                '0'..='9' => {
+                    let digit = c as usize - '0' as usize;
+                    let new_count = count.unwrap_or_default() * 10 + digit;
+
+                    //Counts are limited to prevent huge allocations
+                    if new_count > Game::LEVEL_MAX_WIDTH {
+                        return None;
+                    }
+
+                    count = Some(new_count);
+                },
+
+                '|' => {
+                    if count.is_some() {
+                        return None;
+                    }
+
+                    rows.push(String::new());
+                },
+
+                ' ' | '-' | '_' | '#' | '@' | 'p' | '+' | 'P' | '$' | 'b' | '*' | 'B' | '.' => {
+                    let row = rows.last_mut().unwrap();
+                    for _ in 0..count.take().unwrap_or(1) {
+                        row.push(c);
+                    }
+                },
+
+                _ => return None,
+            }
+        }
+
+        //A count without a tile is not valid
+        if count.is_some() {
+            return None;
+        }
+
+        Some(rows)
+    }
+}
+
+impl FromStr for Level {
+    type Err = LevelLoadingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().collect::<Vec<_>>();
         if lines.is_empty() {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+            return Err(LevelLoadingError::new("Level is empty!"));
         }
 
         let line = lines.first().unwrap().trim();
         if !line.starts_with("w: ") || !line.contains(", h: ") {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+            return Err(LevelLoadingError::new("Level size is invalid (Expected \"w: WIDTH, h: HEIGHT\")!").
+                    with_line(1).
+                    with_token(line));
         }
 
         let index = line.to_string().find(", h: ").unwrap();
 
         let (width, height) = (&line[3..index], &line[index + 5..]);
-        let height = if let Ok(height) = usize::from_str(height) {
-            height
-        }else {
-            return Err(LevelLoadingError::new("Level is invalid!"));
-        };
         let width = if let Ok(width) = usize::from_str(width) {
             width
         }else {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+            return Err(LevelLoadingError::new("Level width is invalid!").
+                    with_line(1).
+                    with_column(4).
+                    with_token(width));
+        };
+        let height = if let Ok(height) = usize::from_str(height) {
+            height
+        }else {
+            return Err(LevelLoadingError::new("Level height is invalid!").
+                    with_line(1).
+                    with_column(index + 6).
+                    with_token(height));
         };
 
         if width == 0 || height == 0 {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+            return Err(LevelLoadingError::new("Level size must not be zero!").
+                    with_line(1).
+                    with_token(line));
+        }
+
+        let row_count = lines.len() - 1;
+        if row_count != height {
+            return Err(LevelLoadingError::new(format!(
+                "Level row count is invalid (Expected: {height}, Found: {row_count})!"
+            )).with_line(row_count.min(height.saturating_add(1)) + 1));
         }
 
-        let mut tiles = Vec::with_capacity(width * height);
+        //The capacity is bounded by the input length to prevent huge allocations for invalid level sizes
+        let mut tiles = Vec::with_capacity(width.saturating_mul(height).min(s.len()));
 
-        for line in lines.into_iter().
+        for (i, line) in lines.into_iter().
                 skip(1).
-                map(|line| line.trim()) {
-            if line.len() != width {
-                return Err(LevelLoadingError::new("Level is invalid!"));
-            }
+                enumerate() {
+            let line_number = i + 2;
+            let leading_whitespace_len = line.len() - line.trim_start().len();
+            let row = line.trim();
+
+            for (j, tile) in row.bytes().enumerate() {
+                let column = leading_whitespace_len + j + 1;
+
+                if j >= width {
+                    return Err(LevelLoadingError::new(format!(
+                        "Level row is too long (Expected: {width}, Found: {})!", row.len()
+                    )).with_line(line_number).with_column(column));
+                }
+
+                let tile = Tile::from_ascii(tile).map_err(|err| {
+                    //All previous bytes are ASCII characters, therefore "j" is always a char boundary
+                    let token = row[j..].chars().next().unwrap_or_default();
 
-            for tile in line.bytes() {
-                tiles.push(Tile::from_ascii(tile)?);
+                    err.with_line(line_number).
+                            with_column(column).
+                            with_token(token)
+                })?;
+                tiles.push(tile);
             }
-        }
 
-        if tiles.len() != width * height {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+            if row.len() < width {
+                return Err(LevelLoadingError::new(format!(
+                    "Level row is too short (Expected: {width}, Found: {})!", row.len()
+                )).with_line(line_number).with_column(leading_whitespace_len + row.len() + 1));
+            }
         }
 
-        Ok(Self { width, height, tiles })
+        Ok(Self { width, height, tiles, start_pos: None, wrap_around: true, theme: LevelTheme::Default })
     }
 }
 
@@ -540,24 +1340,33 @@ impl FromStr for Level {
 pub struct PlayingLevel {
     original_level: Level,
     animation_state: Option<AnimationState>,
-    playing_level: UndoHistory<(Level, (usize, usize))>,
+    original_key_door_count: KeyDoorCount,
+    playing_level: UndoHistory<(Level, (usize, usize), KeyDoorCount)>,
+
+    moves: Vec<Direction>,
+    move_count: usize,
+
+    tile_events: Vec<TileEvent>,
 }
 
 impl PlayingLevel {
     pub fn new(level: &Level, history_size: usize) -> Result<Self, LevelLoadingError> {
-        let player_tile_count = level.tiles().iter().filter(|tile| matches!(tile, Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce)).count();
+        let player_tile_count = level.player_count();
         if player_tile_count == 0 {
             return Err(LevelLoadingError::new("Level does not contain a player tile!"));
         }else if player_tile_count > 1 {
             return Err(LevelLoadingError::new("Level contains too many player tiles!"));
         }
 
+        //The original level keeps the tile below the start marker for the tile which is restored after the player moved
+        let start_level = level.with_player_at_start()?;
+
         let mut player_pos = None;
 
         'outer:
-        for i in 0..level.width() {
-            for j in 0..level.height() {
-                if let Some(tile) = level.get_tile(i, j) && matches!(tile, Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce) {
+        for i in 0..start_level.width() {
+            for j in 0..start_level.height() {
+                if let Some(tile) = start_level.get_tile(i, j) && matches!(tile, Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce) {
                     player_pos = Some((i, j));
 
                     break 'outer;
@@ -565,23 +1374,57 @@ impl PlayingLevel {
             }
         }
 
+        let key_door_count = KeyDoorCount::from_level(level);
+
         Ok(PlayingLevel {
             original_level: level.clone(),
             animation_state: None,
-            playing_level: UndoHistory::new(history_size, (level.clone(), player_pos.unwrap())),
+            original_key_door_count: key_door_count,
+            playing_level: UndoHistory::new(history_size, (start_level, player_pos.unwrap(), key_door_count)),
+
+            moves: Vec::new(),
+            move_count: 0,
+
+            tile_events: Vec::new(),
         })
     }
 
+    /// Creates a playing level which continues at the provided state of the original level
+    ///
+    /// The state must have been reached by playing the original level (The original level is used for the tiles below the player)
+    pub(crate) fn from_state(original_level: &Level, level: Level, player_pos: (usize, usize), history_size: usize) -> Self {
+        let key_door_count = KeyDoorCount::from_level(&level);
+
+        PlayingLevel {
+            original_level: original_level.clone(),
+            animation_state: None,
+            original_key_door_count: KeyDoorCount::from_level(original_level),
+            playing_level: UndoHistory::new(history_size, (level, player_pos, key_door_count)),
+
+            moves: Vec::new(),
+            move_count: 0,
+
+            tile_events: Vec::new(),
+        }
+    }
+
     pub fn is_playing_animation(&self) -> bool {
         self.animation_state.is_some()
     }
 
+    /// Returns the tile events of the last move or animation step
+    pub fn tile_events(&self) -> &[TileEvent] {
+        &self.tile_events
+    }
+
     #[must_use]
     pub fn continue_animation(&mut self) -> MoveResult {
         let Some(animation_state) = self.animation_state.clone() else {
             return MoveResult::Invalid;
         };
 
+        self.tile_events.clear();
+
         let move_result = match animation_state {
             AnimationState::Player {
                 last_valid_move_result, direction,
@@ -608,9 +1451,9 @@ impl PlayingLevel {
                 x_from, y_from,
                 direction,
             } => {
-                let (mut level, player_pos) = self.playing_level.current().clone();
+                let (mut level, player_pos, mut key_door_count) = self.playing_level.current().clone();
 
-                let move_result = self.move_box_or_key(&mut level, x_from, y_from, direction);
+                let move_result = self.move_box_or_key(&mut level, &mut key_door_count, x_from, y_from, direction);
                 if move_result.is_invalid() {
                     //Animation finished
                     self.animation_state = None;
@@ -624,7 +1467,7 @@ impl PlayingLevel {
                     self.animation_state = None;
                 }
 
-                self.playing_level.commit_change((level, player_pos));
+                self.playing_level.commit_change((level, player_pos, key_door_count));
 
                 move_result
             },
@@ -639,12 +1482,13 @@ impl PlayingLevel {
         move_result
     }
 
-    pub fn cancel_animation_and_undo_move(&mut self) -> Option<&(Level, (usize, usize))> {
+    pub fn cancel_animation_and_undo_move(&mut self) -> Option<&(Level, (usize, usize), KeyDoorCount)> {
         if !self.is_playing_animation() {
             return None;
         }
 
         self.animation_state = None;
+        self.tile_events.clear();
 
         //Undo temporary change from last animation iteration
         self.playing_level.undo();
@@ -654,6 +1498,9 @@ impl PlayingLevel {
         self.playing_level.undo();
         self.playing_level.commit_change(current_playing_level);
 
+        self.move_count = self.move_count.saturating_sub(1);
+        self.moves.truncate(self.move_count);
+
         Some(self.playing_level.current())
     }
 
@@ -663,15 +1510,44 @@ impl PlayingLevel {
             return MoveResult::Invalid;
         }
 
-        self.move_player_internal(direction)
+        self.tile_events.clear();
+
+        let move_result = self.move_player_internal(direction);
+        if move_result.is_valid() || move_result.is_animation() {
+            self.moves.truncate(self.move_count);
+            self.moves.push(direction);
+            self.move_count += 1;
+        }
+
+        move_result
+    }
+
+    /// Replays the provided moves including all animations.
+    ///
+    /// Returns false if one of the moves was invalid, all moves up to the invalid move will be kept.
+    pub fn replay_moves(&mut self, moves: &[Direction]) -> bool {
+        for direction in moves.iter().copied() {
+            let move_result = self.move_player(direction);
+            if move_result.is_invalid() {
+                return false;
+            }
+
+            while self.is_playing_animation() {
+                let _ = self.continue_animation();
+            }
+        }
+
+        true
     }
 
     #[must_use]
     fn move_player_internal(&mut self, direction: Direction) -> MoveResult {
-        let (mut level, mut player_pos) = self.playing_level.current().clone();
+        let (mut level, mut player_pos, mut key_door_count) = self.playing_level.current().clone();
 
         let (x_from, y_from) = player_pos;
-        let (x_to, y_to) = direction.update_xy(x_from, y_from, level.width, level.height);
+        let Some((x_to, y_to)) = level.neighbor_pos(x_from, y_from, direction) else {
+            return MoveResult::Invalid;
+        };
 
         let one_way_door_tile = match direction {
             Direction::Left => Tile::OneWayLeft,
@@ -707,7 +1583,7 @@ impl PlayingLevel {
         let move_result = if matches!(tile, Tile::Empty | Tile::FragileFloor | Tile::Ice | Tile::Goal | Tile::Secret | Tile::BoxInHole) || tile == one_way_door_tile {
             MoveResult::Valid { has_won: false, secret_found: tile == Tile::Secret, sound_effect: was_floor_broken.then_some(LevelSoundEffect::FloorBroken) }
         }else if matches!(tile, Tile::Box | Tile::BoxInGoal | Tile::BoxOnFragileFloor | Tile::BoxOnIce | Tile::Key | Tile::KeyInGoal | Tile::KeyOnFragileFloor | Tile::KeyOnIce) {
-            let move_result = self.move_box_or_key(&mut level, x_to, y_to, direction);
+            let move_result = self.move_box_or_key(&mut level, &mut key_door_count, x_to, y_to, direction);
             match move_result {
                 MoveResult::Valid {
                     has_won, secret_found, sound_effect,
@@ -733,7 +1609,7 @@ impl PlayingLevel {
         }
 
         if move_result.is_valid() || move_result.is_animation() {
-            self.playing_level.commit_change((level, player_pos));
+            self.playing_level.commit_change((level, player_pos, key_door_count));
 
             //If ice tile: move forwards until no longer ice (Start animation)
             if tile == Tile::Ice {
@@ -750,12 +1626,14 @@ impl PlayingLevel {
     }
 
     #[must_use]
-    fn move_box_or_key(&mut self, level: &mut Level, x_from: usize, y_from: usize, direction: Direction) -> MoveResult {
+    fn move_box_or_key(&mut self, level: &mut Level, key_door_count: &mut KeyDoorCount, x_from: usize, y_from: usize, direction: Direction) -> MoveResult {
         if level.width != self.original_level.width || level.height != self.original_level.height {
             panic!("Original level must have the same width and height as the modified level!");
         }
 
-        let (x_to, y_to) = direction.update_xy(x_from, y_from, level.width, level.height);
+        let Some((x_to, y_to)) = level.neighbor_pos(x_from, y_from, direction) else {
+            return MoveResult::Invalid;
+        };
 
         let index_from = x_from + y_from * level.width;
         let index_to = x_to + y_to * level.width;
@@ -819,15 +1697,21 @@ impl PlayingLevel {
             }else if *tile_to == Tile::Hole {
                 if is_box {
                     tile_to_new_value = Tile::BoxInHole;
+
+                    self.tile_events.push(TileEvent::BoxSunk { x: x_to, y: y_to });
                 }else {
                     //Key will be destroyed, only boxes can fill holes
                     tile_to_new_value = Tile::Hole;
+
+                    key_door_count.on_key_lost();
                 }
             }else if is_box {
                 tile_to_new_value = Tile::Box;
             }else if *tile_to == Tile::LockedDoor {
                 //Open door and destroy key
                 tile_to_new_value = Tile::Empty;
+
+                key_door_count.on_door_unlocked();
             }else {
                 tile_to_new_value = Tile::Key;
             }
@@ -848,7 +1732,7 @@ impl PlayingLevel {
             level.tiles[index_to] = tile_to_new_value;
 
             let move_result = MoveResult::Valid { has_won, secret_found: false, sound_effect: match tile_to_new_value {
-                Tile::BoxInHole => Some(LevelSoundEffect::BoxFall),
+                Tile::BoxInHole => Some(LevelSoundEffect::BoxSink),
                 Tile::Hole => Some(LevelSoundEffect::KeyFall),
                 Tile::Empty => Some(LevelSoundEffect::DoorUnlocked),
 
@@ -876,526 +1760,2475 @@ impl PlayingLevel {
         &self.original_level
     }
 
-    pub fn current_playing_level(&self) -> &(Level, (usize, usize)) {
+    pub fn current_playing_level(&self) -> &(Level, (usize, usize), KeyDoorCount) {
         self.playing_level.current()
     }
 
+    /// Returns true if the player would push a box by moving in the provided direction
+    pub fn is_push(&self, direction: Direction) -> bool {
+        let (level, (x, y), _) = self.current_playing_level();
+
+        level.neighbor_pos(*x, *y, direction).
+                and_then(|(x, y)| level.get_tile(x, y)).
+                is_some_and(|tile| matches!(tile, Tile::Box | Tile::BoxInGoal | Tile::BoxOnFragileFloor | Tile::BoxOnIce))
+    }
+
+    pub fn original_key_door_count(&self) -> KeyDoorCount {
+        self.original_key_door_count
+    }
+
     pub fn current_move_index(&self) -> usize {
         self.playing_level.current_index()
     }
 
-    pub fn undo_move(&mut self) -> Option<&(Level, (usize, usize))> {
+    /// Returns all moves which lead from the original level to the current state
+    pub fn moves(&self) -> &[Direction] {
+        &self.moves[..self.move_count]
+    }
+
+    pub fn undo_move(&mut self) -> Option<&(Level, (usize, usize), KeyDoorCount)> {
         if self.is_playing_animation() {
             return None;
         }
 
-        self.playing_level.undo()
+        self.tile_events.clear();
+
+        let level = self.playing_level.undo();
+        if level.is_some() {
+            self.move_count -= 1;
+        }
+
+        level
+    }
+
+    /// Returns the move index of the last move which can be redone
+    pub fn max_move_index(&self) -> usize {
+        self.playing_level.len() - 1
+    }
+
+    /// Undoes up to `count` moves at once, the count of undone moves is returned
+    pub fn undo_moves(&mut self, count: usize) -> usize {
+        if self.is_playing_animation() {
+            return 0;
+        }
+
+        let current_index = self.playing_level.current_index();
+        let undo_count = count.min(current_index);
+        if self.playing_level.jump_to(current_index - undo_count).is_none() {
+            return 0;
+        }
+
+        self.tile_events.clear();
+        self.move_count -= undo_count;
+
+        undo_count
+    }
+
+    /// Redoes up to `count` moves at once, the count of redone moves is returned
+    pub fn redo_moves(&mut self, count: usize) -> usize {
+        if self.is_playing_animation() {
+            return 0;
+        }
+
+        let current_index = self.playing_level.current_index();
+        let redo_count = count.min(self.max_move_index() - current_index);
+        if self.playing_level.jump_to(current_index + redo_count).is_none() {
+            return 0;
+        }
+
+        self.tile_events.clear();
+        self.move_count += redo_count;
+
+        redo_count
     }
 
-    pub fn redo_move(&mut self) -> Option<&(Level, (usize, usize))> {
+    pub fn redo_move(&mut self) -> Option<&(Level, (usize, usize), KeyDoorCount)> {
         if self.is_playing_animation() {
             return None;
         }
 
-        self.playing_level.redo()
+        self.tile_events.clear();
+
+        let level = self.playing_level.redo();
+        if level.is_some() {
+            self.move_count += 1;
+        }
+
+        level
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct LevelWithStats {
-    level: Level,
-    best_time: Option<u64>,
-    best_moves: Option<u32>
+/// A game version consisting of the major and minor version (e.g. "1.2")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GameVersion {
+    major: u32,
+    minor: u32,
 }
 
-impl LevelWithStats {
-    pub fn new(level: Level, best_time: Option<u64>, best_moves: Option<u32>) -> Self {
-        Self { level, best_time, best_moves }
-    }
+impl GameVersion {
+    pub const V1_0: GameVersion = GameVersion::new(1, 0);
+    pub const V1_1: GameVersion = GameVersion::new(1, 1);
 
-    pub fn level(&self) -> &Level {
-        &self.level
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
     }
 
-    pub fn level_mut(&mut self) -> &mut Level {
-        &mut self.level
-    }
+    /// Returns the version of this build of the game (Patch and pre-release parts are ignored)
+    pub fn current() -> Self {
+        let mut tokens = Game::VERSION.split(['.', '-']);
 
-    pub fn best_time(&self) -> Option<u64> {
-        self.best_time
-    }
+        let major = tokens.next().and_then(|major| u32::from_str(major).ok()).unwrap_or(0);
+        let minor = tokens.next().and_then(|minor| u32::from_str(minor).ok()).unwrap_or(0);
 
-    pub fn best_moves(&self) -> Option<u32> {
-        self.best_moves
+        Self::new(major, minor)
     }
 
-    pub fn set_best_time(&mut self, best_time: Option<u64>) {
-        self.best_time = best_time;
+    pub fn major(&self) -> u32 {
+        self.major
     }
 
-    pub fn set_best_moves(&mut self, best_moves: Option<u32>) {
-        self.best_moves = best_moves;
+    pub fn minor(&self) -> u32 {
+        self.minor
     }
 }
 
-#[cfg(feature = "steam")]
-#[derive(Debug)]
-pub struct SteamLevelPackData {
-    workshop_id: PublishedFileId,
+impl Display for GameVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
 }
 
-#[cfg(feature = "steam")]
-impl SteamLevelPackData {
-    pub fn workshop_id(&self) -> PublishedFileId {
-        self.workshop_id
+impl FromStr for GameVersion {
+    type Err = LevelLoadingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((major, minor)) = s.trim().split_once(".") else {
+            return Err(LevelLoadingError::new(format!("The game version \"{s}\" is invalid!")));
+        };
+
+        let (Ok(major), Ok(minor)) = (u32::from_str(major), u32::from_str(minor)) else {
+            return Err(LevelLoadingError::new(format!("The game version \"{s}\" is invalid!")));
+        };
+
+        Ok(Self::new(major, minor))
     }
 }
 
-#[cfg(feature = "steam")]
-impl From<QueryResult> for SteamLevelPackData {
-    fn from(value: QueryResult) -> Self {
-        SteamLevelPackData {
-            workshop_id: value.published_file_id,
-        }
-    }
+/// Lines of the level pack format which were added after version 1.0 and the first game version which supports them
+///
+/// Older versions cannot parse level packs which contain these lines.
+const FORMAT_FEATURE_VERSIONS: [(&str, GameVersion); 24] = [
+    ("Name[", GameVersion::V1_1),
+    ("Author: ", GameVersion::V1_1),
+    ("Description: ", GameVersion::V1_1),
+    ("Description[", GameVersion::V1_1),
+    ("Intro: ", GameVersion::V1_1),
+    ("Outro: ", GameVersion::V1_1),
+    ("Telemetry Endpoint: ", GameVersion::V1_1),
+    ("Bonus Level: ", GameVersion::V1_1),
+    ("Requires: ", GameVersion::V1_1),
+    ("Changelog: ", GameVersion::V1_1),
+    ("Change Summary: ", GameVersion::V1_1),
+    ("Par Moves: ", GameVersion::V1_1),
+    ("Par Time: ", GameVersion::V1_1),
+    ("Author Moves: ", GameVersion::V1_1),
+    ("Author Time: ", GameVersion::V1_1),
+    ("Wrap Around: ", GameVersion::V1_1),
+    ("Theme: ", GameVersion::V1_1),
+    ("Start Position: ", GameVersion::V1_1),
+    ("Hint: ", GameVersion::V1_1),
+    ("Hint[", GameVersion::V1_1),
+    ("Note: ", GameVersion::V1_1),
+    ("Todo: ", GameVersion::V1_1),
+    ("Validation Moves: ", GameVersion::V1_1),
+    ("Validated By: ", GameVersion::V1_1),
+];
+
+/// Returns the minimum game version which is required to parse the lines of the level pack data
+fn format_min_game_version(level_pack_data: &str) -> GameVersion {
+    level_pack_data.lines().
+            filter_map(|line| FORMAT_FEATURE_VERSIONS.iter().find(|(prefix, _)| line.starts_with(prefix))).
+            map(|(_, version)| *version).
+            fold(GameVersion::V1_0, GameVersion::max)
 }
 
-#[derive(Debug)]
-pub struct LevelPack {
-    name: String,
-    id: String,
-    path: String,
+/// Best scores of a remix of a level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemixBestScore {
+    /// None if the remix was only completed in relaxed mode
+    pub best_time: Option<u64>,
+    pub best_moves: u32,
+}
 
-    thumbnail_level_index: Option<usize>,
-    background_music_id: Option<BackgroundMusicId>,
+/// The most recent unfinished attempt of a level which can be restored later
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelAttempt {
+    time: u64,
+    moves: Vec<Direction>,
+}
 
-    levels: Vec<LevelWithStats>,
+impl LevelAttempt {
+    pub const MAX_MOVE_COUNT: usize = 10000;
 
-    min_level_not_completed: usize,
+    pub fn new(time: u64, moves: impl Into<Vec<Direction>>) -> Self {
+        Self { time, moves: moves.into() }
+    }
 
-    level_pack_best_time_sum: Option<u64>,
-    level_pack_best_moves_sum: Option<u32>,
+    pub fn time(&self) -> u64 {
+        self.time
+    }
 
-    #[cfg(feature = "steam")]
-    steam_level_pack_data: Option<SteamLevelPackData>,
+    pub fn moves(&self) -> &[Direction] {
+        &self.moves
+    }
+
+    /// Moves are run-length encoded (e.g. "3r2u" -> "rrruu")
+    pub fn to_str(&self) -> String {
+        format!("{},{}", self.time, moves_to_str(&self.moves))
+    }
 }
 
-impl LevelPack {
-    pub const MAX_LEVEL_PACK_NAME_LEN: usize = 25;
+/// Run-length encodes the moves (e.g. "rrruu" -> "3r2u")
+pub fn moves_to_str(moves: &[Direction]) -> String {
+    let mut out = String::new();
 
-    pub const MAX_LEVEL_PACK_COUNT: usize = 190;
-    pub const MAX_LEVEL_COUNT_PER_PACK: usize = 190;
+    for run in moves.chunk_by(|a, b| a == b) {
+        if run.len() > 1 {
+            let _ = write!(out, "{}", run.len());
+        }
 
-    pub fn new(name: impl Into<String>, id: impl Into<String>, path: impl Into<String>) -> Self {
-        Self {
-            name: name.into(),
-            id: id.into(),
-            path: path.into(),
-            levels: vec![],
+        out.push(run[0].to_ascii() as char);
+    }
 
-            thumbnail_level_index: None,
-            background_music_id: None,
+    out
+}
 
-            min_level_not_completed: Default::default(),
-            level_pack_best_time_sum: Default::default(),
-            level_pack_best_moves_sum: Default::default(),
+/// Parses run-length encoded moves, at most [LevelAttempt::MAX_MOVE_COUNT] moves are allowed
+pub fn moves_from_str(moves_str: &str) -> Result<Vec<Direction>, LevelLoadingError> {
+    let mut moves = Vec::new();
+    let mut run_length = None;
+    for c in moves_str.bytes() {
+        if c.is_ascii_digit() {
+            run_length = Some(run_length.unwrap_or(0_usize).saturating_mul(10).saturating_add((c - b'0') as usize));
 
-            #[cfg(feature = "steam")]
-            steam_level_pack_data: None,
+            continue;
         }
-    }
 
-    pub fn read_from_save_game(
-        id: impl Into<String>, path: impl Into<String>, lvl_data: impl Into<String>, editor_level_pack: bool,
+        let Some(direction) = Direction::from_ascii(c) else {
+            return Err(LevelLoadingError::new("The moves are invalid!"));
+        };
 
-        #[cfg(feature = "steam")]
-        steam_level_pack_data: Option<SteamLevelPackData>,
-    ) -> Result<Self, Box<dyn Error>> {
-        let mut lvl_name = None;
-        let id = id.into();
-        let path = path.into();
+        let run_length = run_length.take().unwrap_or(1);
+        if run_length > LevelAttempt::MAX_MOVE_COUNT - moves.len() {
+            return Err(LevelLoadingError::new("There are too many moves!"));
+        }
 
-        let mut pack_thumbnail_level_index = None;
-        let mut pack_background_music_id = None;
+        moves.extend(std::iter::repeat_n(direction, run_length));
+    }
 
-        let lvl_data = lvl_data.into();
+    if run_length.is_some() {
+        return Err(LevelLoadingError::new("The moves are invalid!"));
+    }
 
-        let mut levels = Vec::with_capacity(Self::MAX_LEVEL_COUNT_PER_PACK);
-        {
-            let lines = lvl_data.lines().collect::<Vec<_>>();
-            if lines.is_empty() {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "The level pack file \"{path}\" is empty!"
-                ))));
-            }
+    Ok(moves)
+}
 
-            let mut lines = lines.into_iter();
+impl FromStr for LevelAttempt {
+    type Err = LevelLoadingError;
 
-            let mut line = lines.next().unwrap().trim();
-            if let Some(name) = line.strip_prefix("Name: ") {
-                let name = name.trim();
-                if name.len() > Self::MAX_LEVEL_PACK_NAME_LEN {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "The level pack name \"{name}\" is too long!"
-                    ))));
-                }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((time, moves_str)) = s.trim().split_once(",") else {
+            return Err(LevelLoadingError::new("Level attempt is invalid!"));
+        };
 
-                lvl_name = Some(name);
+        let Ok(time) = u64::from_str(time) else {
+            return Err(LevelLoadingError::new("Level attempt is invalid!"));
+        };
 
-                let next_line = lines.next();
-                let Some(next_line) = next_line else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
+        let Ok(moves) = moves_from_str(moves_str) else {
+            return Err(LevelLoadingError::new("Level attempt contains invalid moves!"));
+        };
+
+        Ok(Self { time, moves })
+    }
+}
+
+/// The condition which must be met for a level hint to be shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelHintTrigger {
+    Always,
+
+    /// The level was not completed yet
+    Playing,
+
+    /// The level was completed and the next level can be started
+    Completed,
+
+    /// The last level of the level pack was completed
+    PackCompleted,
+
+    /// The player was next to or on top of the tile at least once during the current attempt
+    TileEncountered(Tile),
+}
+
+impl Display for LevelHintTrigger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelHintTrigger::Always => f.write_str("always"),
+            LevelHintTrigger::Playing => f.write_str("playing"),
+            LevelHintTrigger::Completed => f.write_str("completed"),
+            LevelHintTrigger::PackCompleted => f.write_str("pack-completed"),
+            LevelHintTrigger::TileEncountered(tile) => write!(f, "tile:{}", tile.to_ascii() as char),
+        }
+    }
+}
+
+impl FromStr for LevelHintTrigger {
+    type Err = LevelLoadingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim() {
+            "always" => LevelHintTrigger::Always,
+            "playing" => LevelHintTrigger::Playing,
+            "completed" => LevelHintTrigger::Completed,
+            "pack-completed" => LevelHintTrigger::PackCompleted,
+
+            s => {
+                let tile = s.strip_prefix("tile:").
+                        filter(|tile| tile.len() == 1).
+                        and_then(|tile| Tile::from_ascii(tile.as_bytes()[0]).ok());
+
+                let Some(tile) = tile else {
+                    return Err(LevelLoadingError::new(format!("Level hint trigger \"{s}\" is invalid!")));
+                };
+
+                LevelHintTrigger::TileEncountered(tile)
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelHintSegment<'a> {
+    Text(&'a str),
+
+    /// "{t:X}": The tile with the ASCII representation X
+    Tile(Tile),
+
+    /// "{k:TEXT}": TEXT is drawn as key input
+    KeyInput(&'a str),
+
+    /// "{a:ACTION}": The key of the in-game action of the current control preset is drawn as key input (e.g. "{a:Undo}")
+    Action(InGameAction),
+
+    /// "{e:TEXT}": TEXT is drawn highlighted
+    Emphasis(&'a str),
+}
+
+impl LevelHintSegment<'_> {
+    pub fn width(&self) -> usize {
+        match self {
+            LevelHintSegment::Text(text) |
+            LevelHintSegment::KeyInput(text) |
+            LevelHintSegment::Emphasis(text) => text.chars().count(),
+
+            LevelHintSegment::Tile(_) => 1,
+
+            LevelHintSegment::Action(action) => action.key_name(console_extension::control_preset()).chars().count(),
+        }
+    }
+}
+
+/// A tutorial-style text which is shown horizontally centered in the provided row during gameplay
+///
+/// Format: "ROW, TRIGGER, TEXT" (e.g. "8, tile:^, One-way doors ({t:^}) can only be entered from the opened side")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelHint {
+    row: usize,
+    trigger: LevelHintTrigger,
+    text: Box<str>,
+}
+
+impl LevelHint {
+    pub fn new(row: usize, trigger: LevelHintTrigger, text: impl Into<Box<str>>) -> Self {
+        Self { row, trigger, text: text.into() }
+    }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    pub fn trigger(&self) -> LevelHintTrigger {
+        self.trigger
+    }
+
+    /// Splits the text into its segments, invalid markup is kept as text
+    pub fn segments(&self) -> Vec<LevelHintSegment<'_>> {
+        let mut segments = Vec::new();
+
+        let mut text = &*self.text;
+        while let Some(start_index) = text.find('{') {
+            let markup = text[start_index + 1..].split_once('}').and_then(|(markup, remaining_text)| {
+                let segment = match markup.split_once(':')? {
+                    ("t", tile) if tile.len() == 1 => LevelHintSegment::Tile(Tile::from_ascii(tile.as_bytes()[0]).ok()?),
+                    ("k", key) => LevelHintSegment::KeyInput(key),
+                    ("a", action) => LevelHintSegment::Action(InGameAction::from_name(action)?),
+                    ("e", text) => LevelHintSegment::Emphasis(text),
+
+                    _ => return None,
+                };
+
+                Some((segment, remaining_text))
+            });
+
+            if let Some((segment, remaining_text)) = markup {
+                if start_index > 0 {
+                    segments.push(LevelHintSegment::Text(&text[..start_index]));
+                }
+                segments.push(segment);
+
+                text = remaining_text;
+            }else {
+                segments.push(LevelHintSegment::Text(&text[..=start_index]));
+
+                text = &text[start_index + 1..];
+            }
+        }
+
+        if !text.is_empty() {
+            segments.push(LevelHintSegment::Text(text));
+        }
+
+        segments
+    }
+
+    /// Returns the number of columns which are required to draw this hint
+    pub fn width(&self) -> usize {
+        self.segments().iter().
+                map(LevelHintSegment::width).
+                sum()
+    }
+
+    /// Returns the column in which the horizontally centered hint starts
+    pub fn column(&self) -> usize {
+        Game::CONSOLE_MIN_WIDTH.saturating_sub(self.width()) / 2
+    }
+
+    /// Returns false if the triggers of the hints can never be met at the same time
+    pub fn can_be_shown_with(&self, other: &LevelHint) -> bool {
+        !matches!(
+            (self.trigger, other.trigger),
+            (LevelHintTrigger::Playing, LevelHintTrigger::Completed | LevelHintTrigger::PackCompleted) |
+            (LevelHintTrigger::Completed | LevelHintTrigger::PackCompleted, LevelHintTrigger::Playing) |
+            (LevelHintTrigger::Completed, LevelHintTrigger::PackCompleted) |
+            (LevelHintTrigger::PackCompleted, LevelHintTrigger::Completed)
+        )
+    }
+
+    /// Returns warnings for hints which are clipped, cover tiles of the level, or overlap other hints if drawn during gameplay
+    pub fn placement_warnings(hints: &[LevelHint], level: &Level) -> Vec<String> {
+        let level_x = Game::CONSOLE_MIN_WIDTH.saturating_sub(level.draw_width()) / 2;
+        let level_columns = level_x..level_x + level.draw_width();
+        let level_rows = 1..1 + level.draw_height();
+
+        let mut warnings = Vec::new();
+        for (i, hint) in hints.iter().enumerate() {
+            let columns = hint.column()..hint.column() + hint.width();
+
+            if hint.width() > Game::CONSOLE_MIN_WIDTH {
+                warnings.push(format!(
+                    "Hint {} (Row {}) is clipped: The text is wider than the console ({} > {})",
+                    i + 1, hint.row, hint.width(), Game::CONSOLE_MIN_WIDTH,
+                ));
+            }
+
+            if hint.row == 0 || hint.row >= Game::CONSOLE_MIN_HEIGHT {
+                warnings.push(format!("Hint {} (Row {}) is outside of the console", i + 1, hint.row));
+            }
+
+            if level_rows.contains(&hint.row) && columns.start < level_columns.end && level_columns.start < columns.end {
+                warnings.push(format!("Hint {} (Row {}) covers tiles of the level", i + 1, hint.row));
+            }
+
+            for (j, other_hint) in hints.iter().enumerate().skip(i + 1) {
+                let other_columns = other_hint.column()..other_hint.column() + other_hint.width();
+
+                if hint.row == other_hint.row && hint.can_be_shown_with(other_hint) &&
+                        columns.start < other_columns.end && other_columns.start < columns.end {
+                    warnings.push(format!("Hint {} (Row {}) overlaps hint {}", i + 1, hint.row, j + 1));
+                }
+            }
+        }
+
+        warnings
+    }
+}
+
+impl Display for LevelHint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {}, {}", self.row, self.trigger, self.text)
+    }
+}
+
+impl FromStr for LevelHint {
+    type Err = LevelLoadingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = s.trim().splitn(3, ", ").collect::<Vec<_>>();
+        let [row, trigger, text] = tokens[..] else {
+            return Err(LevelLoadingError::new("Level hint is invalid!"));
+        };
+
+        let Ok(row) = usize::from_str(row) else {
+            return Err(LevelLoadingError::new("Level hint row is invalid!"));
+        };
+
+        if row == 0 || row >= Game::CONSOLE_MIN_HEIGHT {
+            return Err(LevelLoadingError::new("Level hint row is out of bounds!"));
+        }
+
+        let hint = Self::new(row, LevelHintTrigger::from_str(trigger)?, text);
+        if hint.width() > Game::CONSOLE_MIN_WIDTH {
+            return Err(LevelLoadingError::new("Level hint text is too long!"));
+        }
+
+        Ok(hint)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LevelWithStats {
+    level: Level,
+    best_time: Option<u64>,
+    best_moves: Option<u32>,
+    //Best time and best moves before the last new record (The record can be undone, e.g. after a fluke run)
+    previous_best: Option<(Option<u64>, u32)>,
+
+    par_time: Option<u64>,
+    par_moves: Option<u32>,
+
+    author_time: Option<u64>,
+    author_moves: Option<u32>,
+
+    hints: Vec<LevelHint>,
+    localized_hints: BTreeMap<Language, Vec<LevelHint>>,
+
+    editor_notes: Vec<String>,
+    todo_markers: Vec<(usize, usize)>,
+    validation_moves: Option<Vec<Direction>>,
+    //True if the best moves were found by the solver instead of a validation run
+    solver_validated: bool,
+
+    stats_verified: bool,
+}
+
+impl LevelWithStats {
+    pub const MAX_EDITOR_NOTE_LINE_COUNT: usize = 15;
+    pub const MAX_EDITOR_NOTE_LINE_LEN: usize = 68;
+
+    pub fn new(level: Level, best_time: Option<u64>, best_moves: Option<u32>) -> Self {
+        Self {
+            level, best_time, best_moves,
+            previous_best: None,
+            par_time: None, par_moves: None,
+            author_time: None, author_moves: None,
+            hints: Vec::new(),
+            localized_hints: BTreeMap::new(),
+            editor_notes: Vec::new(),
+            todo_markers: Vec::new(),
+            validation_moves: None,
+            solver_validated: false,
+            stats_verified: true,
+        }
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    pub fn level_mut(&mut self) -> &mut Level {
+        &mut self.level
+    }
+
+    pub fn best_time(&self) -> Option<u64> {
+        self.best_time
+    }
+
+    pub fn best_moves(&self) -> Option<u32> {
+        self.best_moves
+    }
+
+    pub fn set_best_time(&mut self, best_time: Option<u64>) {
+        self.best_time = best_time;
+        self.stats_verified = true;
+    }
+
+    pub fn set_best_moves(&mut self, best_moves: Option<u32>) {
+        self.best_moves = best_moves;
+        self.stats_verified = true;
+    }
+
+    /// Returns the best time and the best moves before the last new record
+    pub fn previous_best(&self) -> Option<(Option<u64>, u32)> {
+        self.previous_best
+    }
+
+    pub fn set_previous_best(&mut self, previous_best: Option<(Option<u64>, u32)>) {
+        self.previous_best = previous_best;
+    }
+
+    /// Returns false if the checksum of the best values in the save game did not match (e.g. if the save game was edited manually)
+    ///
+    /// Unverified best values must not be uploaded to leaderboards
+    pub fn is_stats_verified(&self) -> bool {
+        self.stats_verified
+    }
+
+    /// Target time in milliseconds which was set by the level pack author
+    pub fn par_time(&self) -> Option<u64> {
+        self.par_time
+    }
+
+    /// Target moves which were set by the level pack author
+    pub fn par_moves(&self) -> Option<u32> {
+        self.par_moves
+    }
+
+    pub fn set_par_time(&mut self, par_time: Option<u64>) {
+        self.par_time = par_time;
+    }
+
+    pub fn set_par_moves(&mut self, par_moves: Option<u32>) {
+        self.par_moves = par_moves;
+    }
+
+    /// Time in milliseconds of the level pack author's validation run
+    pub fn author_time(&self) -> Option<u64> {
+        self.author_time
+    }
+
+    /// Moves of the level pack author's validation run
+    pub fn author_moves(&self) -> Option<u32> {
+        self.author_moves
+    }
+
+    pub fn set_author_time(&mut self, author_time: Option<u64>) {
+        self.author_time = author_time;
+    }
+
+    pub fn set_author_moves(&mut self, author_moves: Option<u32>) {
+        self.author_moves = author_moves;
+    }
+
+    /// Tutorial-style hints which were set by the level pack author
+    pub fn hints(&self) -> &[LevelHint] {
+        &self.hints
+    }
+
+    pub fn set_hints(&mut self, hints: impl Into<Vec<LevelHint>>) {
+        self.hints = hints.into();
+    }
+
+    /// Returns the translated hints for the provided language or the untranslated hints if there are no translated hints
+    pub fn localized_hints(&self, language: Language) -> &[LevelHint] {
+        self.localized_hints.get(&language).
+                unwrap_or(&self.hints)
+    }
+
+    pub fn set_localized_hints(&mut self, language: Language, hints: impl Into<Vec<LevelHint>>) {
+        let hints = hints.into();
+        if hints.is_empty() {
+            self.localized_hints.remove(&language);
+        }else {
+            self.localized_hints.insert(language, hints);
+        }
+    }
+
+    /// Freeform notes of the level pack author, they are only stored in editor level packs and are not exported
+    pub fn editor_notes(&self) -> &[String] {
+        &self.editor_notes
+    }
+
+    pub fn set_editor_notes(&mut self, editor_notes: impl Into<Vec<String>>) {
+        self.editor_notes = editor_notes.into();
+    }
+
+    /// Positions of tiles which are marked as unfinished in the level editor, they are not exported
+    pub fn todo_markers(&self) -> &[(usize, usize)] {
+        &self.todo_markers
+    }
+
+    pub fn set_todo_markers(&mut self, todo_markers: impl Into<Vec<(usize, usize)>>) {
+        self.todo_markers = todo_markers.into();
+    }
+
+    /// Moves of the last validation run in the level editor, they are only stored in editor level packs and are not exported
+    pub fn validation_moves(&self) -> Option<&[Direction]> {
+        self.validation_moves.as_deref()
+    }
+
+    pub fn set_validation_moves(&mut self, validation_moves: Option<Vec<Direction>>) {
+        self.validation_moves = validation_moves;
+    }
+
+    /// Returns true if the level was validated by the solver instead of a validation run in the level editor
+    pub fn is_solver_validated(&self) -> bool {
+        self.solver_validated
+    }
+
+    pub fn set_solver_validated(&mut self, solver_validated: bool) {
+        self.solver_validated = solver_validated;
+    }
+}
+
+#[cfg(feature = "steam")]
+#[derive(Debug)]
+pub struct SteamLevelPackData {
+    workshop_id: PublishedFileId,
+}
+
+#[cfg(feature = "steam")]
+impl SteamLevelPackData {
+    pub fn workshop_id(&self) -> PublishedFileId {
+        self.workshop_id
+    }
+}
+
+#[cfg(feature = "steam")]
+impl From<QueryResult> for SteamLevelPackData {
+    fn from(value: QueryResult) -> Self {
+        SteamLevelPackData {
+            workshop_id: value.published_file_id,
+        }
+    }
+}
+
+/// Sums of the best values of all levels of a level pack
+///
+/// The sums are updated incrementally if a single level is added, removed, or updated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct LevelPackStatsSum {
+    best_time_sum: u64,
+    best_moves_sum: u32,
+
+    //The sums are only available if all levels have best values
+    levels_without_best_time: usize,
+    levels_without_best_moves: usize,
+}
+
+impl LevelPackStatsSum {
+    fn from_levels(levels: &[LevelWithStats]) -> Self {
+        let mut stats_sum = Self::default();
+        for level in levels {
+            stats_sum.add_level(level);
+        }
+
+        stats_sum
+    }
+
+    //Wrapping operations are used to ensure that remove_level always reverts add_level
+    fn add_level(&mut self, level: &LevelWithStats) {
+        match level.best_time {
+            Some(best_time) => self.best_time_sum = self.best_time_sum.wrapping_add(best_time),
+            None => self.levels_without_best_time += 1,
+        }
+
+        match level.best_moves {
+            Some(best_moves) => self.best_moves_sum = self.best_moves_sum.wrapping_add(best_moves),
+            None => self.levels_without_best_moves += 1,
+        }
+    }
+
+    fn remove_level(&mut self, level: &LevelWithStats) {
+        match level.best_time {
+            Some(best_time) => self.best_time_sum = self.best_time_sum.wrapping_sub(best_time),
+            None => self.levels_without_best_time -= 1,
+        }
+
+        match level.best_moves {
+            Some(best_moves) => self.best_moves_sum = self.best_moves_sum.wrapping_sub(best_moves),
+            None => self.levels_without_best_moves -= 1,
+        }
+    }
+}
+
+/// Text screen of a level pack which is shown before the first level or after the last level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackStory {
+    Intro,
+    Outro,
+}
+
+impl PackStory {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            PackStory::Intro => "Intro",
+            PackStory::Outro => "Outro",
+        }
+    }
+}
+
+/// Condition which must be fulfilled to unlock the bonus level (The last level) of a level pack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BonusLevelCondition {
+    /// The secrets of all other levels which contain a secret tile must be found
+    CollectAllSecrets,
+}
+
+impl BonusLevelCondition {
+    pub const ALL: [BonusLevelCondition; 1] = [BonusLevelCondition::CollectAllSecrets];
+
+    /// Returns the ID which is used in level pack files
+    pub fn id(self) -> &'static str {
+        match self {
+            BonusLevelCondition::CollectAllSecrets => "collect-all-secrets",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|condition| condition.id() == id)
+    }
+}
+
+#[derive(Debug)]
+pub struct LevelPack {
+    name: String,
+    localized_names: BTreeMap<Language, String>,
+    id: String,
+    path: String,
+
+    author: Option<String>,
+    description: Vec<String>,
+    localized_descriptions: BTreeMap<Language, Vec<String>>,
+
+    //Text screens which are shown before the first level and after the last level
+    intro: Vec<String>,
+    outro: Vec<String>,
+
+    thumbnail_level_index: Option<usize>,
+    background_music_id: Option<BackgroundMusicId>,
+
+    //Level statistics are only uploaded if the player allowed telemetry
+    telemetry_endpoint: Option<String>,
+
+    bonus_level_condition: Option<BonusLevelCondition>,
+
+    //Steam Workshop IDs of level packs which must be installed to play this level pack (e.g. previous parts of a campaign)
+    required_workshop_ids: Vec<u64>,
+
+    levels: Vec<LevelWithStats>,
+
+    min_level_not_completed: usize,
+
+    //Indices of levels in which the secret was found (Older versions only tracked secrets of level packs with a bonus level)
+    found_secret_level_indices: BTreeSet<usize>,
+    //False for save games of older versions which did not track the secrets of this level pack (Undiscovered secrets are not shown)
+    are_all_secrets_tracked: bool,
+
+    stats_sum: LevelPackStatsSum,
+
+    stats_locked: bool,
+
+    //Changes since the last Steam Workshop upload (Only stored in editor level packs)
+    changelog: Vec<String>,
+    change_summary: String,
+
+    //Modification time of the editor level pack file after it was loaded or saved (Used to detect changes of other programs)
+    editor_file_modified_time: Cell<Option<SystemTime>>,
+
+    #[cfg(feature = "steam")]
+    steam_level_pack_data: Option<SteamLevelPackData>,
+}
+
+impl LevelPack {
+    pub const MAX_LEVEL_PACK_NAME_LEN: usize = 25;
+
+    pub const MAX_AUTHOR_LEN: usize = 25;
+    pub const MAX_DESCRIPTION_LINE_LEN: usize = 68;
+    pub const MAX_DESCRIPTION_LINE_COUNT: usize = 10;
+    pub const MAX_STORY_LINE_LEN: usize = 68;
+    pub const MAX_STORY_LINE_COUNT: usize = 50;
+
+    pub const MAX_CHANGELOG_ENTRY_COUNT: usize = 100;
+    pub const MAX_CHANGE_SUMMARY_LEN: usize = 68;
+
+    pub const MAX_REQUIRED_LEVEL_PACK_COUNT: usize = 10;
+
+    /// Used as the Steam Workshop change note if no changes were recorded
+    pub const INITIAL_RELEASE_CHANGE_NOTE: &'static str = "<Initial Release>";
+
+    pub const MAX_LEVEL_PACK_COUNT: usize = 190;
+    pub const MAX_LEVEL_COUNT_PER_PACK: usize = 190;
+
+    const SAVE_GAME_CHECKSUMS_PREFIX: &'static str = "sig:";
+    const SAVE_GAME_SECRETS_PREFIX: &'static str = "secrets:";
+    /// Entry of the secrets line if the secrets of all levels were tracked since the level pack was started
+    const SAVE_GAME_SECRETS_TRACKED_MARKER: &'static str = "tracked";
+    const SAVE_GAME_PREVIOUS_BESTS_PREFIX: &'static str = "prev:";
+
+    const STATS_LOCK_FILE_POSTFIX: &'static str = ".lvl.edit.lock.sav";
+
+    pub fn new(name: impl Into<String>, id: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            localized_names: BTreeMap::new(),
+            id: id.into(),
+            path: path.into(),
+            levels: vec![],
+
+            author: None,
+            description: Vec::new(),
+            localized_descriptions: BTreeMap::new(),
+
+            intro: Vec::new(),
+            outro: Vec::new(),
+
+            thumbnail_level_index: None,
+            background_music_id: None,
+
+            telemetry_endpoint: None,
+
+            bonus_level_condition: None,
+
+            required_workshop_ids: Vec::new(),
+
+            min_level_not_completed: Default::default(),
+            found_secret_level_indices: BTreeSet::new(),
+            are_all_secrets_tracked: true,
+            stats_sum: Default::default(),
+
+            stats_locked: false,
+
+            changelog: Vec::new(),
+            change_summary: String::new(),
+
+            editor_file_modified_time: Cell::new(None),
+
+            #[cfg(feature = "steam")]
+            steam_level_pack_data: None,
+        }
+    }
+
+    /// Returns the ID with the lowest suffix ("_2", "_3", ...) which is not used yet
+    ///
+    /// The ID is shortened if the suffix would exceed the max length. None is returned if no suffix is available.
+    pub fn unused_level_pack_id(level_pack_id: &str, is_used: impl Fn(&str) -> bool) -> Option<String> {
+        (2..=Self::MAX_LEVEL_PACK_COUNT + 1).
+                map(|i| {
+                    let suffix = format!("_{i}");
+                    let base_len = level_pack_id.len().min(Self::MAX_LEVEL_PACK_NAME_LEN - suffix.len());
+
+                    format!("{}{}", &level_pack_id[..base_len], suffix)
+                }).
+                find(|id| !is_used(id))
+    }
+
+    /// Parses the level pack data without reading the save game of the level pack
+    ///
+    /// Errors contain the line number in the level pack data at which parsing failed
+    pub fn from_level_pack_data(
+        id: impl Into<String>, path: impl Into<String>, lvl_data: &str, editor_level_pack: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut lvl_name = None;
+        let mut lvl_localized_names = BTreeMap::new();
+        let id = id.into();
+        let path = path.into();
+
+        let mut pack_author = None;
+        let mut pack_description = Vec::new();
+        let mut pack_localized_descriptions = BTreeMap::<_, Vec<_>>::new();
+        let mut pack_intro = Vec::new();
+        let mut pack_outro = Vec::new();
+
+        let mut pack_thumbnail_level_index = None;
+        let mut pack_background_music_id = None;
+        let mut pack_telemetry_endpoint = None;
+        let mut pack_bonus_level_condition = None;
+        let mut pack_required_workshop_ids = Vec::new();
+
+        let mut pack_changelog = Vec::new();
+        let mut pack_change_summary = String::new();
+
+        let mut levels = Vec::with_capacity(Self::MAX_LEVEL_COUNT_PER_PACK);
+        {
+            let mut lines = lvl_data.lines().
+                    enumerate().
+                    map(|(i, line)| (i + 1, line));
+
+            let Some((mut line_number, line)) = lines.next() else {
+                return Err(Box::new(LevelLoadingError::new(format!(
+                    "The level pack file \"{path}\" is empty!"
+                ))));
+            };
+
+            let mut line = line.trim();
+            if let Some(min_game_version) = line.strip_prefix("Min Game Version: ") {
+                let Ok(min_game_version) = GameVersion::from_str(min_game_version) else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The minimum game version is invalid in the level pack file \"{path}\"!"
+                    )).with_line(line_number).with_token(min_game_version)));
+                };
+
+                if min_game_version > GameVersion::current() {
+                    return Err(Box::new(UnsupportedGameVersionError::new(min_game_version)));
+                }
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            if let Some(name) = line.strip_prefix("Name: ") {
+                let name = name.trim();
+                if name.len() > Self::MAX_LEVEL_PACK_NAME_LEN {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack name is too long (Max: {} characters)!",
+                        Self::MAX_LEVEL_PACK_NAME_LEN
+                    )).with_line(line_number).with_token(name)));
+                }
+
+                lvl_name = Some(name);
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            while let Some((language_code, name)) = strip_localized_prefix(line, "Name") {
+                let Some(language) = Language::from_code(language_code) else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The language of the level pack name is invalid in the level pack file \"{path}\"!"
+                    )).with_line(line_number).with_column(6).with_token(language_code)));
+                };
+
+                let name = name.trim();
+                if name.len() > Self::MAX_LEVEL_PACK_NAME_LEN {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack name is too long (Max: {} characters)!",
+                        Self::MAX_LEVEL_PACK_NAME_LEN
+                    )).with_line(line_number).with_token(name)));
+                }
+
+                lvl_localized_names.insert(language, name.to_string());
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            if let Some(author) = line.strip_prefix("Author: ") {
+                let author = author.trim();
+                if author.len() > Self::MAX_AUTHOR_LEN {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack author is too long (Max: {} characters)!",
+                        Self::MAX_AUTHOR_LEN
+                    )).with_line(line_number).with_token(author)));
+                }
+
+                pack_author = Some(author.to_string());
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            //Translated description lines can be mixed with the untranslated description lines
+            loop {
+                let (description, description_line) = if let Some(description_line) = line.strip_prefix("Description:") {
+                    (&mut pack_description, description_line)
+                }else if let Some((language_code, description_line)) = strip_localized_prefix(line, "Description") {
+                    let Some(language) = Language::from_code(language_code) else {
+                        return Err(Box::new(LevelLoadingError::new(format!(
+                            "The language of the level pack description is invalid in the level pack file \"{path}\"!"
+                        )).with_line(line_number).with_column(13).with_token(language_code)));
+                    };
+
+                    (pack_localized_descriptions.entry(language).or_default(), description_line)
+                }else {
+                    break;
+                };
+
+                let description_line = description_line.trim();
+                if description_line.len() > Self::MAX_DESCRIPTION_LINE_LEN {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack description line is too long (Max: {} characters)!",
+                        Self::MAX_DESCRIPTION_LINE_LEN
+                    )).with_line(line_number).with_token(description_line)));
+                }
+
+                if description.len() == Self::MAX_DESCRIPTION_LINE_COUNT {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack description has too many lines (Max: {} lines)!",
+                        Self::MAX_DESCRIPTION_LINE_COUNT
+                    )).with_line(line_number)));
+                }
+
+                description.push(description_line.to_string());
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            while let Some(story_line) = line.strip_prefix("Intro:") {
+                let story_line = story_line.trim();
+                if story_line.len() > Self::MAX_STORY_LINE_LEN {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack intro line is too long (Max: {} characters)!",
+                        Self::MAX_STORY_LINE_LEN
+                    )).with_line(line_number).with_token(story_line)));
+                }
+
+                if pack_intro.len() == Self::MAX_STORY_LINE_COUNT {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack intro has too many lines (Max: {} lines)!",
+                        Self::MAX_STORY_LINE_COUNT
+                    )).with_line(line_number)));
+                }
+
+                pack_intro.push(story_line.to_string());
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            while let Some(story_line) = line.strip_prefix("Outro:") {
+                let story_line = story_line.trim();
+                if story_line.len() > Self::MAX_STORY_LINE_LEN {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack outro line is too long (Max: {} characters)!",
+                        Self::MAX_STORY_LINE_LEN
+                    )).with_line(line_number).with_token(story_line)));
+                }
+
+                if pack_outro.len() == Self::MAX_STORY_LINE_COUNT {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack outro has too many lines (Max: {} lines)!",
+                        Self::MAX_STORY_LINE_COUNT
+                    )).with_line(line_number)));
+                }
+
+                pack_outro.push(story_line.to_string());
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            if let Some(thumbnail_level) = line.strip_prefix("Thumbnail Level: ") {
+                let Ok(thumbnail_level_index) = usize::from_str(thumbnail_level.trim()) else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The thumbnail level index is invalid in the level pack file \"{path}\"!"
+                    )).with_line(line_number).with_column(18).with_token(thumbnail_level)));
+                };
+
+                pack_thumbnail_level_index = Some(thumbnail_level_index);
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            if let Some(background_music) = line.strip_prefix("Background Music: ") {
+                let Ok(background_music_id) = usize::from_str(background_music.trim()) else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The background music id is invalid in the level pack file \"{path}\"!"
+                    )).with_line(line_number).with_column(19).with_token(background_music)));
+                };
+
+                pack_background_music_id = audio::BACKGROUND_MUSIC_TRACKS.check_id(background_music_id);
+
+                //Custom tracks might have been removed from the custom music folder: The default background music is used
+                if pack_background_music_id.is_none() && background_music_id < audio::CUSTOM_TRACK_MIN_ID {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The background music \"{background_music_id}\" from level pack file \"{path}\" does not exist \
+                        (Make sure that you are playing the latest version of SokoTerm)!"
+                    )).with_line(line_number).with_column(19)));
+                }
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            if let Some(telemetry_endpoint) = line.strip_prefix("Telemetry Endpoint: ") {
+                let telemetry_endpoint = telemetry_endpoint.trim();
+                if !telemetry::is_valid_endpoint(telemetry_endpoint) {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The telemetry endpoint is invalid in the level pack file \"{path}\" \
+                        (Must be a HTTP or HTTPS URL with at most {} characters)!",
+                        telemetry::MAX_ENDPOINT_LEN,
+                    )).with_line(line_number).with_column(21).with_token(telemetry_endpoint)));
+                }
+
+                pack_telemetry_endpoint = Some(telemetry_endpoint.to_string());
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            if let Some(bonus_level) = line.strip_prefix("Bonus Level: ") {
+                let Some(bonus_level_condition) = BonusLevelCondition::from_id(bonus_level.trim()) else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The bonus level condition is invalid in the level pack file \"{path}\"!"
+                    )).with_line(line_number).with_column(14).with_token(bonus_level)));
+                };
+
+                pack_bonus_level_condition = Some(bonus_level_condition);
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            while let Some(required_workshop_id) = line.strip_prefix("Requires: ") {
+                let Ok(required_workshop_id) = u64::from_str(required_workshop_id.trim()) else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The required Steam Workshop ID is invalid in the level pack file \"{path}\"!"
+                    )).with_line(line_number).with_column(11).with_token(required_workshop_id)));
+                };
+
+                if pack_required_workshop_ids.len() >= Self::MAX_REQUIRED_LEVEL_PACK_COUNT {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "There are too many required level packs in the level pack file \"{path}\" (Max: {})!",
+                        Self::MAX_REQUIRED_LEVEL_PACK_COUNT,
+                    )).with_line(line_number)));
+                }
+
+                if !pack_required_workshop_ids.contains(&required_workshop_id) {
+                    pack_required_workshop_ids.push(required_workshop_id);
+                }
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            while editor_level_pack && (line.starts_with("Changelog: ") || line.starts_with("Change Summary:")) {
+                if let Some(entry) = line.strip_prefix("Changelog: ") {
+                    if pack_changelog.len() >= Self::MAX_CHANGELOG_ENTRY_COUNT {
+                        return Err(Box::new(LevelLoadingError::new(format!(
+                            "There are too many changelog entries in the level pack file \"{path}\" (Max: {})!",
+                            Self::MAX_CHANGELOG_ENTRY_COUNT,
+                        )).with_line(line_number)));
+                    }
+
+                    pack_changelog.push(entry.trim().to_string());
+                }else if let Some(change_summary) = line.strip_prefix("Change Summary:") {
+                    let change_summary = change_summary.trim();
+                    if change_summary.len() > Self::MAX_CHANGE_SUMMARY_LEN {
+                        return Err(Box::new(LevelLoadingError::new(format!(
+                            "The change summary is too long (Max: {} characters)!",
+                            Self::MAX_CHANGE_SUMMARY_LEN,
+                        )).with_line(line_number).with_token(change_summary)));
+                    }
+
+                    pack_change_summary = change_summary.to_string();
+                }
+
+                let Some((next_line_number, next_line)) = lines.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
                         "The level pack file \"{path}\" does not contain level count!"
+                    )).with_line(line_number + 1)));
+                };
+                line_number = next_line_number;
+                line = next_line.trim();
+            }
+
+            let Some(level_count) = line.strip_prefix("Levels: ") else {
+                return Err(Box::new(LevelLoadingError::new(format!(
+                    "The level count is missing in the level pack file \"{path}\"!"
+                )).with_line(line_number).with_token(line)));
+            };
+
+            let level_count = if let Ok(level_count) = usize::from_str(level_count) {
+                if level_count > Self::MAX_LEVEL_COUNT_PER_PACK {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "There are too many levels in the level pack file \"{path}\" (Count: {level_count}, Max: {})!",
+                        Self::MAX_LEVEL_COUNT_PER_PACK
+                    )).with_line(line_number).with_column(9)));
+                }else {
+                    level_count
+                }
+            }else {
+                return Err(Box::new(LevelLoadingError::new(format!(
+                    "The level count is invalid in the level pack file \"{path}\"!"
+                )).with_line(line_number).with_column(9).with_token(level_count)));
+            };
+
+            if let Some(index) = pack_thumbnail_level_index && level_count <= index {
+                return Err(Box::new(LevelLoadingError::new(format!(
+                    "The thumbnail level index {index} is out of bounds (Should be less then {level_count}) in the level pack file \"{path}\"!"
+                ))));
+            }
+
+            let mut line_iter = lines.
+                    filter(|(_, line)| !line.trim().is_empty());
+            for i in 0..level_count {
+                let Some((mut line_number, mut line)) = line_iter.next() else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "EOF was reached early in the level pack file \"{path}\" (Read: {i} levels, Expected: {level_count} levels)!"
                     ))));
                 };
-                line = next_line.trim();
+
+                let mut par_moves = None;
+                let mut par_time = None;
+                let mut author_moves = None;
+                let mut author_time = None;
+                let mut wrap_around = true;
+                let mut theme = LevelTheme::Default;
+                let mut hints = Vec::new();
+                let mut localized_hints = BTreeMap::<_, Vec<_>>::new();
+                let mut editor_notes = Vec::new();
+                let mut todo_markers = Vec::new();
+                let mut validation_moves = None;
+                let mut solver_validated = false;
+                let mut start_pos = None;
+                loop {
+                    if let Some(value) = line.trim().strip_prefix("Par Moves: ") {
+                        let Ok(value) = u32::from_str(value.trim()) else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The par moves of level {} are invalid in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        };
+
+                        par_moves = Some(value);
+                    }else if let Some(value) = line.trim().strip_prefix("Par Time: ") {
+                        let Ok(value) = u64::from_str(value.trim()) else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The par time of level {} is invalid in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        };
+
+                        par_time = Some(value);
+                    }else if let Some(value) = line.trim().strip_prefix("Author Moves: ") {
+                        let Ok(value) = u32::from_str(value.trim()) else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The author moves of level {} are invalid in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        };
+
+                        author_moves = Some(value);
+                    }else if let Some(value) = line.trim().strip_prefix("Author Time: ") {
+                        let Ok(value) = u64::from_str(value.trim()) else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The author time of level {} is invalid in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        };
+
+                        author_time = Some(value);
+                    }else if let Some(value) = line.trim().strip_prefix("Wrap Around: ") {
+                        let Ok(value) = bool::from_str(value.trim()) else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The wrap around flag of level {} is invalid in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        };
+
+                        wrap_around = value;
+                    }else if let Some(value) = line.trim().strip_prefix("Theme: ") {
+                        let Some(value) = LevelTheme::from_id(value.trim()) else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The theme of level {} is invalid in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        };
+
+                        theme = value;
+                    }else if let Some(value) = line.trim().strip_prefix("Start Position: ") {
+                        let value = value.split_once(", ").and_then(|(x, y)| {
+                            Some((usize::from_str(x.trim()).ok()?, usize::from_str(y.trim()).ok()?))
+                        });
+                        let Some(value) = value else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The start position of level {} is invalid (Expected \"X, Y\") in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(line.trim())));
+                        };
+
+                        start_pos = Some(value);
+                    }else if let Some(value) = line.trim().strip_prefix("Hint: ") {
+                        let value = match LevelHint::from_str(value) {
+                            Ok(value) => value,
+                            Err(err) => {
+                                return Err(Box::new(LevelLoadingError::new(format!(
+                                    "\"{}\" occurred during parsing of a hint of level {} in the level pack file \"{path}\"!",
+                                    err.message(), i + 1
+                                )).with_line(line_number).with_token(value)));
+                            },
+                        };
+
+                        hints.push(value);
+                    }else if let Some((language_code, value)) = strip_localized_prefix(line.trim(), "Hint") {
+                        let Some(language) = Language::from_code(language_code) else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The language of a hint of level {} is invalid in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(language_code)));
+                        };
+
+                        let value = match LevelHint::from_str(value) {
+                            Ok(value) => value,
+                            Err(err) => {
+                                return Err(Box::new(LevelLoadingError::new(format!(
+                                    "\"{}\" occurred during parsing of a hint of level {} in the level pack file \"{path}\"!",
+                                    err.message(), i + 1
+                                )).with_line(line_number).with_token(value)));
+                            },
+                        };
+
+                        localized_hints.entry(language).or_default().push(value);
+                    }else if editor_level_pack && let Some(value) = line.trim().strip_prefix("Note:") {
+                        let value = value.trim();
+                        if editor_notes.len() >= LevelWithStats::MAX_EDITOR_NOTE_LINE_COUNT ||
+                                value.len() > LevelWithStats::MAX_EDITOR_NOTE_LINE_LEN {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The notes of level {} are too long in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        }
+
+                        editor_notes.push(value.to_string());
+                    }else if editor_level_pack && let Some(value) = line.trim().strip_prefix("Todo: ") {
+                        let todo_marker = value.split_once(", ").and_then(|(x, y)| {
+                            Some((usize::from_str(x.trim()).ok()?, usize::from_str(y.trim()).ok()?))
+                        });
+                        let Some(todo_marker) = todo_marker else {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "A TODO marker of level {} is invalid (Expected \"X, Y\") in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        };
+
+                        todo_markers.push(todo_marker);
+                    }else if editor_level_pack && let Some(value) = line.trim().strip_prefix("Validation Moves: ") {
+                        let value = match moves_from_str(value.trim()) {
+                            Ok(value) => value,
+                            Err(err) => {
+                                return Err(Box::new(LevelLoadingError::new(format!(
+                                    "\"{}\" occurred during parsing of the validation moves of level {} in the level pack file \"{path}\"!",
+                                    err.message(), i + 1
+                                )).with_line(line_number).with_token(value)));
+                            },
+                        };
+
+                        validation_moves = Some(value);
+                    }else if editor_level_pack && let Some(value) = line.trim().strip_prefix("Validated By: ") {
+                        if value.trim() != "solver" {
+                            return Err(Box::new(LevelLoadingError::new(format!(
+                                "The validation source of level {} is invalid (Expected \"solver\") in the level pack file \"{path}\"!",
+                                i + 1
+                            )).with_line(line_number).with_token(value)));
+                        }
+
+                        solver_validated = true;
+                    }else {
+                        break;
+                    }
+
+                    let Some((next_line_number, next_line)) = line_iter.next() else {
+                        return Err(Box::new(LevelLoadingError::new(format!(
+                            "EOF was reached early during parsing of level {} in the level pack file \"{path}\"!",
+                            i + 1
+                        ))));
+                    };
+                    line_number = next_line_number;
+                    line = next_line;
+                }
+
+                if !line.starts_with("w: ") || !line.contains(", h: ") {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The size of level {} is invalid (Expected \"w: WIDTH, h: HEIGHT\") in the level pack file \"{path}\"!",
+                        i + 1
+                    )).with_line(line_number).with_token(line)));
+                }
+
+                let index = line.to_string().find(", h: ").unwrap() + 5;
+                let height = if let Ok(height) = usize::from_str(&line[index..]) {
+                    height
+                }else {
+                    return Err(Box::new(LevelLoadingError::new(format!(
+                        "The height of level {} is invalid in the level pack file \"{path}\"!",
+                        i + 1
+                    )).with_line(line_number).with_column(index + 1).with_token(&line[index..])));
+                };
+
+                //The line numbers are stored separately, because empty lines are skipped
+                let mut level_line_numbers = vec![line_number];
+                let mut level_str = vec![line];
+                for _ in 0..height {
+                    if let Some((line_number, line)) = line_iter.next() {
+                        level_line_numbers.push(line_number);
+                        level_str.push(line);
+                    }else {
+                        return Err(Box::new(LevelLoadingError::new(format!(
+                            "EOF was reached early during parsing of level {} in the level pack file \"{path}\"!",
+                            i + 1
+                        ))));
+                    }
+                }
+
+                let level = Level::from_str(&level_str.join("\n"));
+                let mut level = match level {
+                    Ok(level) => level,
+                    Err(err) => {
+                        let mut error = LevelLoadingError::new(format!(
+                            "\"{}\" occurred during parsing of level {} in the level pack file \"{path}\"!",
+                            err.message(), i + 1
+                        ));
+                        error.line = err.line.and_then(|line| level_line_numbers.get(line - 1).copied());
+                        error.column = err.column;
+                        error.token = err.token;
+
+                        return Err(Box::new(error));
+                    },
+                };
+                level.set_start_pos(start_pos);
+                level.set_wrap_around(wrap_around);
+                level.set_theme(theme);
+
+                //TODO markers of removed rows or columns are dropped
+                todo_markers.retain(|&(x, y)| x < level.width() && y < level.height());
+
+                if !editor_level_pack {
+                    if let Err(err) = level.with_player_at_start() {
+                        return Err(Box::new(GameError::new(format!(
+                            "Error while loading level pack \"{}\": Level {}: {}",
+                            id,
+                            i + 1,
+                            err.message(),
+                        ))));
+                    }
+
+                    let player_tile_count = level.player_count();
+                    if player_tile_count == 0 {
+                        return Err(Box::new(GameError::new(format!(
+                            "Error while loading level pack \"{}\": Level {} does not contain a player tile",
+                            id,
+                            i + 1,
+                        ))));
+                    }else if player_tile_count > 1 {
+                        return Err(Box::new(GameError::new(format!(
+                            "Error while loading level pack \"{}\": Level {} contains too many player tiles",
+                            id,
+                            i + 1,
+                        ))));
+                    }
+                }
+
+                let mut level = LevelWithStats::new(level, None, None);
+                level.set_par_moves(par_moves);
+                level.set_par_time(par_time);
+                level.set_author_moves(author_moves);
+                level.set_author_time(author_time);
+                level.set_hints(hints);
+                for (language, hints) in localized_hints {
+                    level.set_localized_hints(language, hints);
+                }
+                level.set_editor_notes(editor_notes);
+                level.set_todo_markers(todo_markers);
+                level.set_validation_moves(validation_moves);
+                level.set_solver_validated(solver_validated);
+
+                levels.push(level);
+            }
+
+            if let Some((line_number, line)) = line_iter.next() {
+                return Err(Box::new(LevelLoadingError::new(format!(
+                    "Additional data was found after last level was parsed in the level pack file \"{path}\"!"
+                )).with_line(line_number).with_token(line.trim())));
+            }
+        }
+
+        if !editor_level_pack && levels.is_empty() {
+            return Err(Box::new(GameError::new(format!(
+                "Error while loading level pack \"{}\": Level pack contains no levels",
+                id,
+            ))));
+        }
+
+        Ok(Self {
+            name: lvl_name.map(ToString::to_string).unwrap_or_else(|| id.clone()),
+            localized_names: lvl_localized_names,
+            id,
+            path,
+
+            author: pack_author,
+            description: pack_description,
+            localized_descriptions: pack_localized_descriptions,
+
+            intro: pack_intro,
+            outro: pack_outro,
+
+            thumbnail_level_index: pack_thumbnail_level_index,
+            background_music_id: pack_background_music_id,
+
+            telemetry_endpoint: pack_telemetry_endpoint,
+
+            bonus_level_condition: pack_bonus_level_condition,
+
+            required_workshop_ids: pack_required_workshop_ids,
+
+            stats_sum: LevelPackStatsSum::from_levels(&levels),
+            levels,
+
+            min_level_not_completed: Default::default(),
+            found_secret_level_indices: BTreeSet::new(),
+            are_all_secrets_tracked: true,
+
+            stats_locked: false,
+
+            changelog: pack_changelog,
+            change_summary: pack_change_summary,
+
+            editor_file_modified_time: Cell::new(None),
+
+            #[cfg(feature = "steam")]
+            steam_level_pack_data: None,
+        })
+    }
+
+    pub fn read_from_save_game(
+        id: impl Into<String>, path: impl Into<String>, lvl_data: impl Into<String>, editor_level_pack: bool,
+
+        #[cfg(feature = "steam")]
+        steam_level_pack_data: Option<SteamLevelPackData>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut level_pack = Self::from_level_pack_data(id, path, &lvl_data.into(), editor_level_pack)?;
+
+        #[cfg(feature = "steam")]
+        {
+            level_pack.steam_level_pack_data = steam_level_pack_data;
+        }
+
+        if editor_level_pack {
+            level_pack.editor_file_modified_time.set(level_pack.read_editor_file_modified_time());
+        }
+
+        let level_save_file_postfix = if editor_level_pack {
+            ".lvl.edit.sav"
+        }else {
+            ".lvl.sav"
+        };
+
+        let mut save_game_file = Game::get_or_create_save_game_folder()?;
+        {
+            #[cfg(not(feature = "steam"))]
+            {
+                save_game_file.push(&level_pack.id);
+                save_game_file.push(level_save_file_postfix);
+            }
+
+            #[cfg(feature = "steam")]
+            if let Some(steam_level_pack_data) = &level_pack.steam_level_pack_data {
+                save_game_file.push("SteamWorkshop/");
+                save_game_file.push(steam_level_pack_data.workshop_id.0.to_string());
+                save_game_file.push(level_save_file_postfix);
+            }else {
+                save_game_file.push(&level_pack.id);
+                save_game_file.push(level_save_file_postfix);
+            }
+        }
+
+        let mut min_level_not_completed= Default::default();
+        let mut found_secret_level_indices = BTreeSet::new();
+        let mut are_all_secrets_tracked = true;
+        let mut previous_bests = BTreeMap::new();
+        let mut level_stats = vec![(None, None, true); Self::MAX_LEVEL_COUNT_PER_PACK];
+        'read_save_game: {
+            if std::fs::exists(&save_game_file)? {
+                let save_game_data = std::fs::read_to_string(&save_game_file)?;
+
+                let lines = save_game_data.lines().collect::<Vec<_>>();
+                if lines.is_empty() {
+                    log_warn!("Could not load save game of level pack \"{}\": \"{}{}\" is empty", level_pack.id, level_pack.id, level_save_file_postfix);
+
+                    break 'read_save_game;
+                }
+
+                let line = lines.first().unwrap().trim();
+
+                if !editor_level_pack {
+                    min_level_not_completed = if let Ok(min_level_not_completed) = usize::from_str(line) {
+                        min_level_not_completed
+                    }else {
+                        log_warn!(
+                            "Could not load save game of level pack \"{}\": \"{}{}\" contains invalid data: \"{}\"",
+                            level_pack.id, level_pack.id, level_save_file_postfix, line,
+                        );
+
+                        break 'read_save_game;
+                    };
+                }
+
+                let checksum_key = checksum::install_key()?;
+                let was_signed = checksum::was_save_game_signed(&save_game_file)?;
+
+                (found_secret_level_indices, are_all_secrets_tracked) = level_pack.read_save_game_secrets(&lines);
+
+                //Invalid entries are ignored ("<level index>=ms<best time>,<best moves>")
+                if let Some(previous_bests_line) = lines.iter().
+                        find_map(|line| line.trim().strip_prefix(Self::SAVE_GAME_PREVIOUS_BESTS_PREFIX)) {
+                    previous_bests = previous_bests_line.split(";").
+                            filter_map(|entry| {
+                                let (level_index, previous_best) = entry.split_once("=")?;
+                                let (best_time, best_moves) = previous_best.strip_prefix("ms")?.split_once(",")?;
+
+                                let best_time = if best_time == "-1" {
+                                    None
+                                }else {
+                                    Some(u64::from_str(best_time).ok()?)
+                                };
+
+                                Some((usize::from_str(level_index).ok()?, (best_time, u32::from_str(best_moves).ok()?)))
+                            }).
+                            collect();
+                }
+
+                level_stats = level_pack.read_save_game_level_stats(&lines, editor_level_pack, checksum_key, was_signed);
+            }
+        }
+
+        for (i, (level, (best_time, best_moves, stats_verified))) in level_pack.levels.iter_mut().zip(level_stats).enumerate() {
+            level.set_best_time(best_time);
+            level.set_best_moves(best_moves);
+            level.stats_verified = stats_verified;
+
+            //Previous bests which are better than the current best are invalid
+            level.previous_best = previous_bests.remove(&i).filter(|&(previous_best_time, previous_best_moves)| {
+                best_moves.is_some_and(|best_moves| previous_best_moves >= best_moves) &&
+                        best_time.is_none_or(|best_time| previous_best_time.is_some_and(|previous_best_time| previous_best_time >= best_time))
+            });
+        }
+
+        level_pack.min_level_not_completed = min_level_not_completed;
+        level_pack.found_secret_level_indices = found_secret_level_indices;
+        level_pack.are_all_secrets_tracked = are_all_secrets_tracked;
+        level_pack.calculate_stats_sum();
+
+        if editor_level_pack {
+            level_pack.stats_locked = std::fs::exists(level_pack.get_save_game_file_path(Self::STATS_LOCK_FILE_POSTFIX)?)?;
+        }
+
+        Ok(level_pack)
+    }
+
+    /// This function is used for saving level pack editor state to the default save path, validation results are included
+    /// Saves the editor level pack, the file is not overwritten if it was changed by another program since it was loaded
+    pub fn save_editor_level_pack(&self) -> Result<(), Box<dyn Error>> {
+        if self.was_editor_level_pack_changed_on_disk() {
+            return Err(Box::new(GameError::new(format!(
+                "The level pack file \"{}.lvl.edit\" was changed by another program!\n\
+                Reload the level pack or keep the changes of the editor in the level pack editor.",
+                self.id,
+            ))));
+        }
+
+        self.force_save_editor_level_pack()
+    }
+
+    /// Saves the editor level pack even if the file was changed by another program
+    pub fn force_save_editor_level_pack(&self) -> Result<(), Box<dyn Error>> {
+        self.write_level_pack_to_path(&self.path, false)?;
+        self.editor_file_modified_time.set(self.read_editor_file_modified_time());
+
+        self.save_save_game(true)
+    }
+
+    /// Returns true if the file of this editor level pack was changed or removed by another program since it was loaded or saved
+    pub fn was_editor_level_pack_changed_on_disk(&self) -> bool {
+        let modified_time = self.editor_file_modified_time.get();
+
+        modified_time.is_some() && self.read_editor_file_modified_time() != modified_time
+    }
+
+    /// Loads the editor level pack again from its file (Changes which were not saved are discarded)
+    pub fn reload_editor_level_pack(&self) -> Result<Self, Box<dyn Error>> {
+        let level_pack_data = std::fs::read_to_string(&self.path)?;
+
+        Self::read_from_save_game(
+            self.id.clone(), self.path.clone(), level_pack_data, true,
+
+            #[cfg(feature = "steam")]
+            None,
+        )
+    }
+
+    fn read_editor_file_modified_time(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path).
+                and_then(|metadata| metadata.modified()).
+                ok()
+    }
+
+    /// This function is used for saving level pack editor state and exporting, validation results are not included
+    ///
+    /// The validation results are used as par values for levels without par values, editor notes and TODO markers are removed
+    pub fn export_editor_level_pack_to_path(&self, path: impl Into<OsString>) -> Result<(), Box<dyn Error>> {
+        self.write_level_pack_to_path(path, true)
+    }
+
+    /// Exports the level pack as a compressed archive (See [export_editor_level_pack_to_path](Self::export_editor_level_pack_to_path))
+    pub fn export_editor_level_pack_to_archive(&self, path: impl Into<OsString>) -> Result<(), Box<dyn Error>> {
+        let mut level_pack_data = Vec::new();
+        self.write_level_pack(&mut level_pack_data, true)?;
+
+        let mut archive = LevelPackArchive::new(String::from_utf8(level_pack_data)?);
+        archive.set_thumbnail(self.levels.get(self.thumbnail_level_index.unwrap_or(0)).
+                map(|level| level.level().to_str()));
+
+        let sound_effects_folder = format!("{}{}", self.path, audio::PACK_SOUND_EFFECTS_FOLDER_POSTFIX);
+        for path in audio::pack_sound_effect_files(Path::new(&sound_effects_folder))? {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            archive.add_asset(file_name, std::fs::read(&path)?)?;
+        }
+
+        std::fs::write(path.into(), archive.encode()?)?;
+
+        Ok(())
+    }
+
+    /// Writes the level pack data of this editor level pack to the path without changing the save game (Used for the trash)
+    pub fn write_editor_level_pack_data_to_path(&self, path: impl Into<OsString>) -> Result<(), Box<dyn Error>> {
+        self.write_level_pack_to_path(path, false)
+    }
+
+    fn write_level_pack_to_path(&self, path: impl Into<OsString>, is_export: bool) -> Result<(), Box<dyn Error>> {
+        let mut file = File::create(path.into())?;
+
+        self.write_level_pack(&mut file, is_export)
+    }
+
+    fn write_level_pack(&self, file: &mut impl Write, is_export: bool) -> Result<(), Box<dyn Error>> {
+        //The minimum game version depends on the lines which are written (e.g. par values are only written if set)
+        let mut level_pack_data = Vec::new();
+        self.write_level_pack_data(&mut level_pack_data, is_export)?;
+        let level_pack_data = String::from_utf8(level_pack_data)?;
+
+        let min_game_version = self.tiles_min_game_version().max(format_min_game_version(&level_pack_data));
+        writeln!(file, "Min Game Version: {}", min_game_version)?;
+        file.write_all(level_pack_data.as_bytes())?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes the level pack data without the minimum game version
+    fn write_level_pack_data(&self, file: &mut impl Write, is_export: bool) -> Result<(), Box<dyn Error>> {
+        writeln!(file, "Name: {}", self.name)?;
+
+        for (language, name) in self.localized_names.iter() {
+            writeln!(file, "Name[{}]: {}", language.code(), name)?;
+        }
+
+        if let Some(author) = &self.author {
+            writeln!(file, "Author: {}", author)?;
+        }
+
+        for description_line in self.description.iter() {
+            writeln!(file, "Description: {}", description_line)?;
+        }
+
+        for (language, description) in self.localized_descriptions.iter() {
+            for description_line in description.iter() {
+                writeln!(file, "Description[{}]: {}", language.code(), description_line)?;
             }
+        }
+
+        for intro_line in self.intro.iter() {
+            writeln!(file, "Intro: {}", intro_line)?;
+        }
+
+        for outro_line in self.outro.iter() {
+            writeln!(file, "Outro: {}", outro_line)?;
+        }
+
+        if let Some(thumbnail_level_index) = self.thumbnail_level_index && thumbnail_level_index < self.levels.len() {
+            writeln!(file, "Thumbnail Level: {}", thumbnail_level_index)?;
+        }
+
+        //Custom tracks are not available on other computers
+        if let Some(background_music_id) = self.background_music_id && !(is_export && background_music_id.is_custom()) {
+            writeln!(file, "Background Music: {}", background_music_id.id())?;
+        }
+
+        if let Some(telemetry_endpoint) = &self.telemetry_endpoint {
+            writeln!(file, "Telemetry Endpoint: {}", telemetry_endpoint)?;
+        }
+
+        if let Some(bonus_level_condition) = self.bonus_level_condition {
+            writeln!(file, "Bonus Level: {}", bonus_level_condition.id())?;
+        }
 
-            if let Some(thumbnail_level) = line.strip_prefix("Thumbnail Level: ") {
-                let Ok(thumbnail_level_index) = usize::from_str(thumbnail_level.trim()) else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "The thumbnail level index \"{line}\" is invalid in the level pack file \"{path}\"!"
-                    ))));
-                };
+        for required_workshop_id in self.required_workshop_ids.iter() {
+            writeln!(file, "Requires: {}", required_workshop_id)?;
+        }
 
-                pack_thumbnail_level_index = Some(thumbnail_level_index);
+        if !is_export {
+            for entry in self.changelog.iter() {
+                writeln!(file, "Changelog: {}", entry)?;
+            }
 
-                let next_line = lines.next();
-                let Some(next_line) = next_line else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "The level pack file \"{path}\" does not contain level count!"
-                    ))));
-                };
-                line = next_line.trim();
+            if !self.change_summary.is_empty() {
+                writeln!(file, "Change Summary: {}", self.change_summary)?;
             }
+        }
 
-            if let Some(background_music) = line.strip_prefix("Background Music: ") {
-                let Ok(background_music_id) = usize::from_str(background_music.trim()) else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "The background music id \"{line}\" is invalid in the level pack file \"{path}\"!"
-                    ))));
-                };
+        writeln!(file, "Levels: {}", self.levels.len())?;
 
-                pack_background_music_id = audio::BACKGROUND_MUSIC_TRACKS.check_id(background_music_id);
-                if pack_background_music_id.is_none() {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "The background music \"{background_music_id}\" from level pack file \"{path}\" does not exist \
-                        (Make sure that you are playing the latest version of SokoTerm)!"
-                    ))));
-                }
+        for level in self.levels.iter() {
+            writeln!(file)?;
 
-                let next_line = lines.next();
-                let Some(next_line) = next_line else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "The level pack file \"{path}\" does not contain level count!"
-                    ))));
-                };
-                line = next_line.trim();
-            }
+            let (par_moves, par_time) = if is_export {
+                (level.par_moves.or(level.best_moves), level.par_time.or(level.best_time))
+            }else {
+                (level.par_moves, level.par_time)
+            };
 
-            if !line.starts_with("Levels: ") {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "The level count is missing in the level pack file \"{path}\"!"
-                ))));
+            if let Some(par_moves) = par_moves {
+                writeln!(file, "Par Moves: {}", par_moves)?;
             }
 
-            let line = &line[8..];
+            if let Some(par_time) = par_time {
+                writeln!(file, "Par Time: {}", par_time)?;
+            }
 
-            let level_count = if let Ok(level_count) = usize::from_str(line) {
-                if level_count > Self::MAX_LEVEL_COUNT_PER_PACK {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "There are too many levels in the level pack file \"{path}\" (Count: {line}, Max: {})!",
-                        Self::MAX_LEVEL_COUNT_PER_PACK
-                    ))));
-                }else {
-                    level_count
-                }
+            //The validation results are exported as author values
+            let (author_moves, author_time) = if is_export {
+                (level.best_moves.or(level.author_moves), level.best_time.or(level.author_time))
             }else {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "The level count \"{line}\" is invalid in the level pack file \"{path}\"!"
-                ))));
+                (level.author_moves, level.author_time)
             };
 
-            if let Some(index) = pack_thumbnail_level_index && level_count <= index {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "The thumbnail level index {index} is out of bounds (Should be less then {level_count}) in the level pack file \"{path}\"!"
-                ))));
+            if let Some(author_moves) = author_moves {
+                writeln!(file, "Author Moves: {}", author_moves)?;
             }
 
-            let mut line_iter = lines.
-                    filter(|line| !line.trim().is_empty());
-            for i in 0..level_count {
-                let line = line_iter.next();
-                let Some(line) = line else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "EOF was reached early in the level pack file \"{path}\" (Read: {} levels, Expected: {level_count} levels)!",
-                        i + 1
-                    ))));
-                };
+            if let Some(author_time) = author_time {
+                writeln!(file, "Author Time: {}", author_time)?;
+            }
 
-                if !line.starts_with("w: ") || !line.contains(", h: ") {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "Level {} is invalid in the level pack file \"{path}\"!",
-                        i + 1
-                    ))));
-                }
+            if !level.level().wrap_around() {
+                writeln!(file, "Wrap Around: false")?;
+            }
 
-                let index = line.to_string().find(", h: ").unwrap() + 5;
-                let height = if let Ok(height) = usize::from_str(&line[index..]) {
-                    height
-                }else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "Level {} is invalid in the level pack file \"{path}\"!",
-                        i + 1
-                    ))));
-                };
+            if level.level().theme() != LevelTheme::Default {
+                writeln!(file, "Theme: {}", level.level().theme().id())?;
+            }
 
-                let mut level_str = Vec::with_capacity(1 + height);
-                level_str.push(line);
-                for _ in 0..height {
-                    if let Some(line) = line_iter.next() {
-                        level_str.push(line);
-                    }else {
-                        return Err(Box::new(LevelLoadingError::new(format!(
-                            "EOF was reached early during parsing of level {} is invalid in the level pack file \"{path}\"!",
-                            i + 1
-                        ))));
-                    }
+            if let Some((x, y)) = level.level().start_pos() {
+                writeln!(file, "Start Position: {}, {}", x, y)?;
+            }
+
+            for hint in level.hints.iter() {
+                writeln!(file, "Hint: {}", hint)?;
+            }
+
+            for (language, hints) in level.localized_hints.iter() {
+                for hint in hints {
+                    writeln!(file, "Hint[{}]: {}", language.code(), hint)?;
                 }
+            }
 
-                let level = Level::from_str(&level_str.join("\n"));
-                let level = match level {
-                    Ok(level) => level,
-                    Err(err) => {
-                        return Err(Box::new(LevelLoadingError::new(format!(
-                            "\"{}\" occurred during parsing of level {} is invalid in the level pack file \"{path}\"!",
-                            err, i + 1
-                        ))));
-                    },
-                };
+            if !is_export {
+                for note in level.editor_notes.iter() {
+                    writeln!(file, "Note: {}", note)?;
+                }
 
-                if !editor_level_pack {
-                    let player_tile_count = level.tiles().iter().filter(|tile| matches!(tile, Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce)).count();
-                    if player_tile_count == 0 {
-                        return Err(Box::new(GameError::new(format!(
-                            "Error while loading level pack \"{}\": Level {} does not contain a player tile",
-                            id,
-                            i + 1,
-                        ))));
-                    }else if player_tile_count > 1 {
-                        return Err(Box::new(GameError::new(format!(
-                            "Error while loading level pack \"{}\": Level {} contains too many player tiles",
-                            id,
-                            i + 1,
-                        ))));
-                    }
+                for (x, y) in level.todo_markers.iter() {
+                    writeln!(file, "Todo: {}, {}", x, y)?;
                 }
 
-                levels.push(level);
-            }
+                if let Some(validation_moves) = &level.validation_moves && !validation_moves.is_empty() {
+                    writeln!(file, "Validation Moves: {}", moves_to_str(validation_moves))?;
+                }
 
-            if line_iter.next().is_some() {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "Additional data was found after last level was parsed in the level pack file \"{path}\"!"
-                ))));
+                if level.solver_validated {
+                    writeln!(file, "Validated By: solver")?;
+                }
             }
+
+            write!(file, "{}", level.level().to_str())?;
         }
 
-        if !editor_level_pack && levels.is_empty() {
-            return Err(Box::new(GameError::new(format!(
-                "Error while loading level pack \"{}\": Level pack contains no levels",
-                id,
-            ))));
+        Ok(())
+    }
+
+    fn get_save_game_file_path(&self, level_save_file_postfix: &str) -> Result<OsString, Box<dyn Error>> {
+        let mut save_game_file = Game::get_or_create_save_game_folder()?;
+
+        #[cfg(not(feature = "steam"))]
+        {
+            save_game_file.push(&self.id);
+            save_game_file.push(level_save_file_postfix);
         }
 
-        let level_save_file_postfix = if editor_level_pack {
+        #[cfg(feature = "steam")]
+        if let Some(steam_level_pack_data) = &self.steam_level_pack_data {
+            save_game_file.push("SteamWorkshop/");
+            save_game_file.push(steam_level_pack_data.workshop_id.0.to_string());
+            save_game_file.push(level_save_file_postfix);
+        }else {
+            save_game_file.push(&self.id);
+            save_game_file.push(level_save_file_postfix);
+        }
+
+        Ok(save_game_file)
+    }
+
+    pub fn save_save_game(&self, editor_validation: bool) -> Result<(), Box<dyn Error>> {
+        let (save_game_file, save_game_data) = self.create_save_game_data(editor_validation)?;
+
+        let mut file = File::create(save_game_file)?;
+        file.write_all(save_game_data.as_bytes())?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    /// Queues the save game to be written in the background (Write failures are reported by the save writer)
+    pub fn save_save_game_in_background(&self, save_writer: &SaveWriter) -> Result<(), Box<dyn Error>> {
+        let (save_game_file, save_game_data) = self.create_save_game_data(false)?;
+
+        save_writer.write(save_game_file, save_game_data);
+
+        Ok(())
+    }
+
+    /// Returns the path and the content of the save game file
+    fn create_save_game_data(&self, editor_validation: bool) -> Result<(OsString, String), Box<dyn Error>> {
+        let level_save_file_postfix = if editor_validation {
             ".lvl.edit.sav"
         }else {
             ".lvl.sav"
         };
 
-        let mut save_game_file = Game::get_or_create_save_game_folder()?;
-        {
-            #[cfg(not(feature = "steam"))]
-            {
-                save_game_file.push(&id);
-                save_game_file.push(level_save_file_postfix);
-            }
+        let save_game_file = self.get_save_game_file_path(level_save_file_postfix)?;
 
-            #[cfg(feature = "steam")]
-            if let Some(steam_level_pack_data) = &steam_level_pack_data {
-                save_game_file.push("SteamWorkshop/");
-                save_game_file.push(steam_level_pack_data.workshop_id.0.to_string());
-                save_game_file.push(level_save_file_postfix);
+        let mut save_game_data = String::new();
+
+        let level_score_count = if editor_validation {
+            self.levels.len()
+        }else {
+            writeln!(save_game_data, "{}", self.min_level_not_completed)?;
+
+            self.min_level_not_completed
+        };
+
+        let checksum_key = checksum::install_key()?;
+        let mut checksums = Vec::with_capacity(level_score_count);
+        for (i, level) in self.levels.iter().
+                take(level_score_count).
+                enumerate() {
+            let line = format!(
+                "ms{},{}",
+                level.best_time.map_or(-1, |best_time| best_time as i64),
+                level.best_moves.map_or(-1, |best_moves| best_moves as i32)
+            );
+            writeln!(save_game_data, "{}", line)?;
+
+            //Unverified best values stay unverified
+            checksums.push(if level.stats_verified {
+                format!("{:016x}", self.save_game_line_checksum(checksum_key, i, &line))
             }else {
-                save_game_file.push(&id);
-                save_game_file.push(level_save_file_postfix);
-            }
+                "-".to_string()
+            });
+        }
+        //Older versions ignore this line because it is not a valid level stats line (The marker is ignored as an invalid level index)
+        if !editor_validation && (self.are_all_secrets_tracked || !self.found_secret_level_indices.is_empty()) {
+            writeln!(
+                save_game_data, "{}{}", Self::SAVE_GAME_SECRETS_PREFIX,
+                self.are_all_secrets_tracked.then(|| Self::SAVE_GAME_SECRETS_TRACKED_MARKER.to_string()).into_iter().
+                        chain(self.found_secret_level_indices.iter().map(ToString::to_string)).
+                        collect::<Vec<_>>().
+                        join(";"),
+            )?;
+        }
+        if !editor_validation && self.levels.iter().take(level_score_count).any(|level| level.previous_best.is_some()) {
+            writeln!(
+                save_game_data, "{}{}", Self::SAVE_GAME_PREVIOUS_BESTS_PREFIX,
+                self.levels.iter().
+                        take(level_score_count).
+                        enumerate().
+                        filter_map(|(i, level)| level.previous_best.map(|(best_time, best_moves)| format!(
+                            "{}=ms{},{}", i, best_time.map_or(-1, |best_time| best_time as i64), best_moves,
+                        ))).
+                        collect::<Vec<_>>().
+                        join(";"),
+            )?;
         }
+        writeln!(save_game_data, "{}{}", Self::SAVE_GAME_CHECKSUMS_PREFIX, checksums.join(";"))?;
+        checksum::mark_save_game_signed(&save_game_file)?;
 
-        let mut min_level_not_completed= Default::default();
-        let mut level_stats: Vec<(Option<u64>, Option<u32>)> = vec![Default::default(); Self::MAX_LEVEL_COUNT_PER_PACK];
-        'read_save_game: {
-            if std::fs::exists(&save_game_file)? {
-                let save_game_data = std::fs::read_to_string(&save_game_file)?;
+        Ok((save_game_file, save_game_data))
+    }
 
-                let lines = save_game_data.lines().collect::<Vec<_>>();
-                if lines.is_empty() {
-                    //TODO add warning message (could not load save file '&id + level_save_file_postfix')
+    /// Parses the indices of levels whose secret was found and if all secrets were tracked, invalid level indices are ignored
+    ///
+    /// Older versions only tracked the secrets of level packs with a bonus level, they do not write the tracking marker.
+    fn read_save_game_secrets(&self, lines: &[&str]) -> (BTreeSet<usize>, bool) {
+        let Some(secrets) = lines.iter().
+                find_map(|line| line.trim().strip_prefix(Self::SAVE_GAME_SECRETS_PREFIX)) else {
+            return (BTreeSet::new(), self.bonus_level_index().is_some());
+        };
 
-                    break 'read_save_game;
-                }
+        let found_secret_level_indices = secrets.split(";").
+                filter_map(|level_index| usize::from_str(level_index).ok()).
+                filter(|level_index| *level_index < self.levels.len()).
+                collect();
+        let are_all_secrets_tracked = self.bonus_level_index().is_some() ||
+                secrets.split(";").any(|entry| entry == Self::SAVE_GAME_SECRETS_TRACKED_MARKER);
 
-                let line = lines.first().unwrap().trim();
+        (found_secret_level_indices, are_all_secrets_tracked)
+    }
 
-                if !editor_level_pack {
-                    min_level_not_completed = if let Ok(min_level_not_completed) = usize::from_str(line) {
-                        min_level_not_completed
-                    }else {
-                        //TODO add warning message (could not load save file '&id + level_save_file_postfix')
+    /// Parses the best values of the level lines of a save game, best values without a valid checksum are marked as unverified
+    ///
+    /// Only save games of older versions are accepted without checksums: A save game which was signed before was edited if checksums are missing.
+    fn read_save_game_level_stats(
+        &self, lines: &[&str], editor_level_pack: bool, checksum_key: &checksum::ChecksumKey, was_signed: bool,
+    ) -> Vec<(Option<u64>, Option<u32>, bool)> {
+        //Save games of older versions do not contain checksums, their best values are accepted
+        let checksums = lines.iter().
+                find_map(|line| line.trim().strip_prefix(Self::SAVE_GAME_CHECKSUMS_PREFIX)).
+                map(|checksums| checksums.split(";").collect::<Vec<_>>()).
+                or(was_signed.then(Vec::new));
+
+        let mut level_stats = vec![(None, None, true); Self::MAX_LEVEL_COUNT_PER_PACK];
+        for (i, mut line) in lines.iter().
+                skip(if editor_level_pack { 0 } else { 1 }).
+                map(|line| line.trim()).
+                filter(|line| !line.starts_with(Self::SAVE_GAME_CHECKSUMS_PREFIX) &&
+                        !line.starts_with(Self::SAVE_GAME_SECRETS_PREFIX) &&
+                        !line.starts_with(Self::SAVE_GAME_PREVIOUS_BESTS_PREFIX)).
+                take(Self::MAX_LEVEL_COUNT_PER_PACK).
+                enumerate() {
+            let stats_verified = checksums.as_ref().is_none_or(|checksums| {
+                checksums.get(i).
+                        and_then(|checksum| u64::from_str_radix(checksum, 16).ok()).
+                        is_some_and(|checksum| checksum == self.save_game_line_checksum(checksum_key, i, line))
+            });
+
+            let is_new_format = line.starts_with("ms");
+            if is_new_format {
+                line = &line[2..];
+            }
 
-                        break 'read_save_game;
-                    };
+            let tokens = line.split(",").collect::<Vec<_>>();
+            if tokens.len() != 2 {
+                continue;
+            }
+
+            let best_time = u64::from_str(tokens[0]).ok().map(|best_time| {
+                if is_new_format {
+                    best_time
+                }else {
+                    best_time * 1000 + 999
                 }
+            });
+            let best_moves = u32::from_str(tokens[1]).ok();
 
-                for (i, mut line) in lines.iter().
-                        skip(if editor_level_pack { 0 } else { 1 }).
-                        take(Self::MAX_LEVEL_COUNT_PER_PACK).
-                        map(|line| line.trim()).
-                        enumerate() {
-                    let is_new_format = line.starts_with("ms");
-                    if is_new_format {
-                        line = &line[2..];
-                    }
+            level_stats[i] = (best_time, best_moves, stats_verified || (best_time.is_none() && best_moves.is_none()));
+        }
 
-                    let tokens = line.split(",").collect::<Vec<_>>();
-                    if tokens.len() != 2 {
-                        continue;
-                    }
+        level_stats
+    }
 
-                    let best_time = u64::from_str(tokens[0]).ok().map(|best_time| {
-                        if is_new_format {
-                            best_time
-                        }else {
-                            best_time * 1000 + 999
-                        }
-                    });
-                    let best_moves = u32::from_str(tokens[1]).ok();
+    /// The checksum contains the level pack id and the level index to prevent copying of lines between levels and level packs
+    fn save_game_line_checksum(&self, checksum_key: &checksum::ChecksumKey, index: usize, line: &str) -> u64 {
+        checksum_key.checksum(format!("{}:{}:{}", self.id, index, line).as_bytes())
+    }
+
+    fn read_level_attempts(&self) -> Result<BTreeMap<usize, LevelAttempt>, Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(".lvl.attempt.sav")?;
+
+        let mut level_attempts = BTreeMap::new();
+        if !std::fs::exists(&save_game_file)? {
+            return Ok(level_attempts);
+        }
+
+        let save_game_data = std::fs::read_to_string(&save_game_file)?;
+        for line in save_game_data.lines() {
+            let Some((index, level_attempt)) = line.trim().split_once(":") else {
+                log_warn!("Level attempts of level pack \"{}\" contain invalid line: \"{}\": Ignoring", self.id, line);
+
+                continue;
+            };
+
+            let Ok(index) = usize::from_str(index) else {
+                log_warn!("Level attempts of level pack \"{}\" contain invalid level index \"{}\": \"{}\": Ignoring", self.id, index, line);
+
+                continue;
+            };
+
+            let Ok(level_attempt) = LevelAttempt::from_str(level_attempt) else {
+                log_warn!("Level attempts of level pack \"{}\" contain invalid attempt for level {}: \"{}\": Ignoring", self.id, index + 1, line);
+
+                continue;
+            };
+
+            level_attempts.insert(index, level_attempt);
+        }
+
+        Ok(level_attempts)
+    }
+
+    pub fn read_level_attempt(&self, index: usize) -> Result<Option<LevelAttempt>, Box<dyn Error>> {
+        Ok(self.read_level_attempts()?.remove(&index))
+    }
+
+    /// Stores the level attempt for the provided level, the attempt will be removed if level_attempt is None
+    pub fn save_level_attempt(&self, index: usize, level_attempt: Option<LevelAttempt>) -> Result<(), Box<dyn Error>> {
+        let mut level_attempts = self.read_level_attempts()?;
+        match level_attempt {
+            Some(level_attempt) if level_attempt.moves().len() <= LevelAttempt::MAX_MOVE_COUNT => {
+                level_attempts.insert(index, level_attempt);
+            },
 
-                    level_stats[i] = (best_time, best_moves);
+            _ => {
+                if level_attempts.remove(&index).is_none() {
+                    return Ok(());
                 }
-            }
+            },
         }
 
-        let levels = levels.into_iter().
-                zip(level_stats).
-                map(|(level, (best_time, best_moves))| {
-                    LevelWithStats::new(level, best_time, best_moves)
-                }).collect::<Vec<_>>();
+        let save_game_file = self.get_save_game_file_path(".lvl.attempt.sav")?;
+        if level_attempts.is_empty() {
+            std::fs::remove_file(save_game_file)?;
 
-        let mut level_pack = Self {
-            name: lvl_name.map(ToString::to_string).unwrap_or_else(|| id.clone()),
-            id,
-            path,
+            return Ok(());
+        }
 
-            thumbnail_level_index: pack_thumbnail_level_index,
-            background_music_id: pack_background_music_id,
+        let mut file = File::create(save_game_file)?;
+        for (index, level_attempt) in level_attempts.iter() {
+            writeln!(file, "{}:{}", index, level_attempt.to_str())?;
+        }
+        file.flush()?;
+
+        Ok(())
+    }
+
+    /// Returns the best total time in milliseconds of all speedruns of this level pack
+    pub fn read_speedrun_best_time(&self) -> Result<Option<u64>, Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(".lvl.speedrun.sav")?;
+        if !std::fs::exists(&save_game_file)? {
+            return Ok(None);
+        }
+
+        let save_game_data = std::fs::read_to_string(&save_game_file)?;
+
+        //Invalid data is treated as no previous speedrun
+        Ok(u64::from_str(save_game_data.trim()).ok())
+    }
+
+    pub fn save_speedrun_best_time(&self, best_time: u64) -> Result<(), Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(".lvl.speedrun.sav")?;
+
+        let mut file = File::create(save_game_file)?;
+        writeln!(file, "{}", best_time)?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    fn read_remix_best_scores(&self) -> Result<BTreeMap<(usize, u64), RemixBestScore>, Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(".lvl.remix.sav")?;
+
+        let mut remix_best_scores = BTreeMap::new();
+        if !std::fs::exists(&save_game_file)? {
+            return Ok(remix_best_scores);
+        }
 
-            levels,
+        let save_game_data = std::fs::read_to_string(&save_game_file)?;
+        for line in save_game_data.lines() {
+            //Invalid lines are ignored (Same as an invalid speedrun time)
+            let mut tokens = line.trim().splitn(4, ":");
+            let (Some(index), Some(seed), Some(best_time), Some(best_moves)) = (tokens.next(), tokens.next(), tokens.next(), tokens.next()) else {
+                continue;
+            };
 
-            min_level_not_completed,
-            level_pack_best_time_sum: Default::default(),
-            level_pack_best_moves_sum: Default::default(),
+            let (Ok(index), Ok(seed), Ok(best_time), Ok(best_moves)) = (
+                usize::from_str(index), u64::from_str(seed), i64::from_str(best_time), u32::from_str(best_moves),
+            ) else {
+                continue;
+            };
 
-            #[cfg(feature = "steam")]
-            steam_level_pack_data,
-        };
-        level_pack.calculate_stats_sum();
+            remix_best_scores.insert((index, seed), RemixBestScore {
+                best_time: u64::try_from(best_time).ok(),
+                best_moves,
+            });
+        }
 
-        Ok(level_pack)
+        Ok(remix_best_scores)
     }
 
-    /// This function is used for saving level pack editor state to the default save path, validation results are included
-    pub fn save_editor_level_pack(&self) -> Result<(), Box<dyn Error>> {
-        self.export_editor_level_pack_to_path(&self.path)?;
-
-        self.save_save_game(true)
+    /// Returns the best scores of the remix of the provided level or None if the remix was not completed yet
+    pub fn read_remix_best_score(&self, index: usize, seed: u64) -> Result<Option<RemixBestScore>, Box<dyn Error>> {
+        Ok(self.read_remix_best_scores()?.remove(&(index, seed)))
     }
 
-    /// This function is used for saving level pack editor state and exporting, validation results are not included
-    pub fn export_editor_level_pack_to_path(&self, path: impl Into<OsString>) -> Result<(), Box<dyn Error>> {
-        let mut file = File::create(path.into())?;
+    /// Updates the best scores of the remix of the provided level (Each remix seed has its own best scores)
+    pub fn save_remix_best_score(&self, index: usize, seed: u64, time: Option<u64>, moves: u32) -> Result<(), Box<dyn Error>> {
+        let mut remix_best_scores = self.read_remix_best_scores()?;
+        remix_best_scores.entry((index, seed)).
+                and_modify(|best_score| {
+                    best_score.best_time = match (best_score.best_time, time) {
+                        (Some(best_time), Some(time)) => Some(best_time.min(time)),
+                        (best_time, time) => best_time.or(time),
+                    };
+                    best_score.best_moves = best_score.best_moves.min(moves);
+                }).
+                or_insert(RemixBestScore {
+                    best_time: time,
+                    best_moves: moves,
+                });
 
-        writeln!(file, "Name: {}", self.name)?;
+        let save_game_file = self.get_save_game_file_path(".lvl.remix.sav")?;
 
-        if let Some(thumbnail_level_index) = self.thumbnail_level_index && thumbnail_level_index < self.levels.len() {
-            writeln!(file, "Thumbnail Level: {}", thumbnail_level_index)?;
+        let mut file = File::create(save_game_file)?;
+        for ((index, seed), best_score) in remix_best_scores.iter() {
+            writeln!(file, "{}:{}:{}:{}", index, seed, best_score.best_time.map_or(-1, |best_time| best_time as i64), best_score.best_moves)?;
         }
+        file.flush()?;
 
-        if let Some(background_music_id) = self.background_music_id {
-            writeln!(file, "Background Music: {}", background_music_id.id())?;
+        Ok(())
+    }
+
+    /// Returns the time (Seconds since the unix epoch) at which a level of this level pack was last played
+    pub fn read_last_played(&self) -> Result<Option<u64>, Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(".lvl.played.sav")?;
+        if !std::fs::exists(&save_game_file)? {
+            return Ok(None);
         }
 
-        writeln!(file, "Levels: {}", self.levels.len())?;
+        let save_game_data = std::fs::read_to_string(&save_game_file)?;
 
-        for level in self.levels.iter().
-                map(|level| level.level()) {
-            write!(file, "\n{}", level.to_str())?;
-        }
+        //Invalid data is treated as never played
+        Ok(u64::from_str(save_game_data.trim()).ok())
+    }
+
+    /// Stores the current time as the last played time of this level pack
+    pub fn save_last_played(&self) -> Result<(), Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(".lvl.played.sav")?;
+
+        let mut file = File::create(save_game_file)?;
+        writeln!(file, "{}", trash::current_time())?;
         file.flush()?;
 
         Ok(())
     }
 
-    pub fn save_save_game(&self, editor_validation: bool) -> Result<(), Box<dyn Error>> {
-        let level_save_file_postfix = if editor_validation {
-            ".lvl.edit.sav"
-        }else {
-            ".lvl.sav"
-        };
+    /// Returns true if the player was already asked to rate this Steam Workshop level pack
+    #[cfg(feature = "steam")]
+    pub fn was_workshop_rating_prompt_shown(&self) -> Result<bool, Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(".lvl.rating.sav")?;
 
-        let mut save_game_file = Game::get_or_create_save_game_folder()?;
-        {
-            #[cfg(not(feature = "steam"))]
-            {
-                save_game_file.push(&self.id);
-                save_game_file.push(level_save_file_postfix);
-            }
+        Ok(std::fs::exists(save_game_file)?)
+    }
 
-            #[cfg(feature = "steam")]
-            if let Some(steam_level_pack_data) = &self.steam_level_pack_data {
-                save_game_file.push("SteamWorkshop/");
-                save_game_file.push(steam_level_pack_data.workshop_id.0.to_string());
-                save_game_file.push(level_save_file_postfix);
-            }else {
-                save_game_file.push(&self.id);
-                save_game_file.push(level_save_file_postfix);
-            }
-        }
+    #[cfg(feature = "steam")]
+    pub fn set_workshop_rating_prompt_shown(&self) -> Result<(), Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(".lvl.rating.sav")?;
 
         let mut file = File::create(save_game_file)?;
+        writeln!(file, "shown")?;
+        file.flush()?;
 
-        let level_score_count = if editor_validation {
-            self.levels.len()
-        }else {
-            writeln!(file, "{}", self.min_level_not_completed)?;
+        Ok(())
+    }
 
-            self.min_level_not_completed
-        };
+    /// Returns true if the author locked the validation stats of this editor level pack
+    ///
+    /// The level editor asks for confirmation before a validated level of a locked level pack can be edited
+    pub fn stats_locked(&self) -> bool {
+        self.stats_locked
+    }
 
-        for level in self.levels.iter().
-                take(level_score_count) {
-            writeln!(
-                file, "ms{},{}",
-                level.best_time.map_or(-1, |best_time| best_time as i64),
-                level.best_moves.map_or(-1, |best_moves| best_moves as i32)
-            )?;
+    pub fn set_stats_locked(&mut self, stats_locked: bool) -> Result<(), Box<dyn Error>> {
+        let save_game_file = self.get_save_game_file_path(Self::STATS_LOCK_FILE_POSTFIX)?;
+
+        if stats_locked {
+            let mut file = File::create(save_game_file)?;
+            writeln!(file, "locked")?;
+            file.flush()?;
+        }else if std::fs::exists(&save_game_file)? {
+            std::fs::remove_file(save_game_file)?;
         }
-        file.flush()?;
+
+        self.stats_locked = stats_locked;
 
         Ok(())
     }
 
+    /// Returns the minimum game version which is required to play this level pack based on the used tiles and format features
+    pub fn min_game_version(&self) -> GameVersion {
+        let mut level_pack_data = Vec::new();
+        //Writing to a Vec does not fail
+        let _ = self.write_level_pack_data(&mut level_pack_data, false);
+
+        self.tiles_min_game_version().max(format_min_game_version(&String::from_utf8_lossy(&level_pack_data)))
+    }
+
+    fn tiles_min_game_version(&self) -> GameVersion {
+        self.levels.iter().
+                flat_map(|level| level.level().tiles()).
+                map(|tile| tile.min_game_version()).
+                fold(GameVersion::V1_0, GameVersion::max)
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -1404,6 +4237,35 @@ impl LevelPack {
         self.name = name.into();
     }
 
+    /// Returns the translated name for the provided language or the untranslated name if there is no translated name
+    pub fn localized_name(&self, language: Language) -> &str {
+        self.localized_names.get(&language).
+                unwrap_or(&self.name)
+    }
+
+    pub fn set_localized_name(&mut self, language: Language, name: Option<String>) {
+        if let Some(name) = name {
+            self.localized_names.insert(language, name);
+        }else {
+            self.localized_names.remove(&language);
+        }
+    }
+
+    /// Stores the edited name as translated name, false is returned if the name was not changed
+    ///
+    /// Empty names and names which are equal to the untranslated name remove the translation.
+    pub fn update_localized_name(&mut self, language: Language, name: &str) -> bool {
+        let name = name.trim();
+        let name = (!name.is_empty() && name != self.name).then(|| name.to_string());
+        if self.localized_names.get(&language) == name.as_ref() {
+            return false;
+        }
+
+        self.set_localized_name(language, name);
+
+        true
+    }
+
     pub fn id(&self) -> &str {
         &self.id
     }
@@ -1412,6 +4274,44 @@ impl LevelPack {
         &self.path
     }
 
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Returns the lines of the description of this level pack (Empty if there is no description)
+    pub fn description(&self) -> &[String] {
+        &self.description
+    }
+
+    /// Returns the translated description for the provided language or the untranslated description if there is no translated description
+    pub fn localized_description(&self, language: Language) -> &[String] {
+        self.localized_descriptions.get(&language).
+                unwrap_or(&self.description)
+    }
+
+    pub fn set_localized_description(&mut self, language: Language, description: Option<Vec<String>>) {
+        if let Some(description) = description {
+            self.localized_descriptions.insert(language, description);
+        }else {
+            self.localized_descriptions.remove(&language);
+        }
+    }
+
+    /// Returns the lines of the intro or outro text (Empty if the level pack has no such text)
+    pub fn story(&self, story: PackStory) -> &[String] {
+        match story {
+            PackStory::Intro => &self.intro,
+            PackStory::Outro => &self.outro,
+        }
+    }
+
+    pub fn set_story(&mut self, story: PackStory, lines: Vec<String>) {
+        match story {
+            PackStory::Intro => self.intro = lines,
+            PackStory::Outro => self.outro = lines,
+        }
+    }
+
     pub fn thumbnail_level_index(&self) -> Option<usize> {
         self.thumbnail_level_index
     }
@@ -1428,6 +4328,186 @@ impl LevelPack {
         self.background_music_id = background_music_id;
     }
 
+    /// Returns the URL to which anonymous level statistics are uploaded if the player allowed telemetry
+    pub fn telemetry_endpoint(&self) -> Option<&str> {
+        self.telemetry_endpoint.as_deref()
+    }
+
+    pub fn bonus_level_condition(&self) -> Option<BonusLevelCondition> {
+        self.bonus_level_condition
+    }
+
+    /// Returns the Steam Workshop IDs of the level packs which must be installed to play this level pack
+    pub fn required_workshop_ids(&self) -> &[u64] {
+        &self.required_workshop_ids
+    }
+
+    /// Adds the required level pack or removes it if it is already required
+    ///
+    /// Returns true if the level pack was added, None is returned if the max count of required level packs is reached
+    pub fn toggle_required_workshop_id(&mut self, workshop_id: u64) -> Option<bool> {
+        if let Some(index) = self.required_workshop_ids.iter().position(|id| *id == workshop_id) {
+            self.required_workshop_ids.remove(index);
+
+            return Some(false);
+        }
+
+        if self.required_workshop_ids.len() >= Self::MAX_REQUIRED_LEVEL_PACK_COUNT {
+            return None;
+        }
+
+        self.required_workshop_ids.push(workshop_id);
+
+        Some(true)
+    }
+
+    /// Returns the index of the bonus level (The last level) or None if the level pack has no bonus level
+    ///
+    /// Level packs with less than 2 levels have no bonus level
+    pub fn bonus_level_index(&self) -> Option<usize> {
+        self.bonus_level_condition.
+                and_then(|_| self.levels.len().checked_sub(1)).
+                filter(|index| *index > 0)
+    }
+
+    /// Returns the indices of all levels except the bonus level which contain a secret tile
+    pub fn secret_level_indices(&self) -> impl Iterator<Item = usize> {
+        let bonus_level_index = self.bonus_level_index();
+
+        self.levels.iter().
+                enumerate().
+                filter(move |(i, level)| Some(*i) != bonus_level_index && level.level().tiles().contains(&Tile::Secret)).
+                map(|(i, _)| i)
+    }
+
+    pub fn is_secret_found(&self, level_index: usize) -> bool {
+        self.found_secret_level_indices.contains(&level_index)
+    }
+
+    /// Returns true if the level contains a secret tile and its secret was not found yet
+    ///
+    /// Always false if the secrets were not tracked by an older version of the game
+    pub fn has_undiscovered_secret(&self, level_index: usize) -> bool {
+        self.are_all_secrets_tracked && !self.is_secret_found(level_index) && Some(level_index) != self.bonus_level_index() &&
+                self.levels.get(level_index).is_some_and(|level| level.level().tiles().contains(&Tile::Secret))
+    }
+
+    /// Marks the secret of the level as found
+    ///
+    /// Returns true if the bonus level was unlocked by this secret
+    pub fn set_secret_found(&mut self, level_index: usize) -> bool {
+        let was_bonus_level_locked = self.is_bonus_level_locked();
+
+        self.found_secret_level_indices.insert(level_index);
+
+        was_bonus_level_locked && !self.is_bonus_level_locked()
+    }
+
+    /// Clears all found secrets, all secrets are tracked afterward (e.g. if the stats of the level pack are reset)
+    pub fn clear_found_secrets(&mut self) {
+        self.found_secret_level_indices.clear();
+        self.are_all_secrets_tracked = true;
+    }
+
+    /// Returns true if the level pack has a bonus level whose condition is not fulfilled yet
+    pub fn is_bonus_level_locked(&self) -> bool {
+        if self.bonus_level_index().is_none() {
+            return false;
+        }
+
+        match self.bonus_level_condition {
+            Some(BonusLevelCondition::CollectAllSecrets) => self.secret_level_indices().
+                    any(|level_index| !self.is_secret_found(level_index)),
+
+            None => false,
+        }
+    }
+
+    pub fn is_locked_bonus_level(&self, level_index: usize) -> bool {
+        self.is_bonus_level_locked() && self.bonus_level_index() == Some(level_index)
+    }
+
+    /// Returns the count of levels which can be played (The locked bonus level is excluded)
+    pub fn unlocked_level_count(&self) -> usize {
+        if self.is_bonus_level_locked() {
+            self.levels.len() - 1
+        }else {
+            self.levels.len()
+        }
+    }
+
+    /// Returns true if all levels except a locked bonus level were completed
+    pub fn are_unlocked_levels_completed(&self) -> bool {
+        self.min_level_not_completed >= self.unlocked_level_count()
+    }
+
+    /// Returns the indices of the unlocked levels in the random order of the seed (All levels if the level pack is completed)
+    ///
+    /// The same seed always results in the same order for the same progress
+    pub fn shuffled_level_indices(&self, seed: u64) -> Vec<usize> {
+        let mut level_indices = (0..self.levels.len()).
+                filter(|&level_index| level_index <= self.min_level_not_completed && !self.is_locked_bonus_level(level_index)).
+                collect::<Vec<_>>();
+
+        level_indices.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+
+        level_indices
+    }
+
+    /// Returns the changes since the last Steam Workshop upload
+    pub fn changelog(&self) -> &[String] {
+        &self.changelog
+    }
+
+    /// Adds an entry to the changelog, entries which are already in the changelog are not added again
+    ///
+    /// If the changelog is full, the oldest entry is removed
+    pub fn add_changelog_entry(&mut self, entry: impl Into<String>) {
+        let entry = entry.into();
+        if self.changelog.contains(&entry) {
+            return;
+        }
+
+        if self.changelog.len() >= Self::MAX_CHANGELOG_ENTRY_COUNT {
+            self.changelog.remove(0);
+        }
+
+        self.changelog.push(entry);
+    }
+
+    /// Removes all changelog entries and the change summary (Used after a Steam Workshop upload)
+    pub fn clear_changelog(&mut self) {
+        self.changelog.clear();
+        self.change_summary.clear();
+    }
+
+    pub fn change_summary(&self) -> &str {
+        &self.change_summary
+    }
+
+    pub fn set_change_summary(&mut self, change_summary: impl Into<String>) {
+        self.change_summary = change_summary.into();
+    }
+
+    /// Returns the change note for a Steam Workshop upload: The change summary followed by all changelog entries
+    pub fn workshop_change_note(&self) -> String {
+        let mut change_note = self.change_summary.clone();
+
+        for entry in self.changelog.iter() {
+            if !change_note.is_empty() {
+                change_note += "\n";
+            }
+
+            let _ = write!(change_note, "- {}", entry);
+        }
+
+        if change_note.is_empty() {
+            change_note += Self::INITIAL_RELEASE_CHANGE_NOTE;
+        }
+
+        change_note
+    }
+
     pub fn levels(&self) -> &[LevelWithStats] {
         &self.levels
     }
@@ -1440,12 +4520,16 @@ impl LevelPack {
         self.min_level_not_completed
     }
 
+    /// Returns the sum of the best times of all levels or None if a level has no best time
     pub fn level_pack_best_time_sum(&self) -> Option<u64> {
-        self.level_pack_best_time_sum
+        (!self.levels.is_empty() && self.stats_sum.levels_without_best_time == 0).
+                then_some(self.stats_sum.best_time_sum)
     }
 
+    /// Returns the sum of the best moves of all levels or None if a level has no best moves
     pub fn level_pack_best_moves_sum(&self) -> Option<u32> {
-        self.level_pack_best_moves_sum
+        (!self.levels.is_empty() && self.stats_sum.levels_without_best_moves == 0).
+                then_some(self.stats_sum.best_moves_sum)
     }
 
     pub fn set_min_level_not_completed(&mut self, min_level_not_completed: usize) {
@@ -1456,14 +4540,16 @@ impl LevelPack {
         self.levels.len()
     }
 
-    pub fn update_stats(&mut self, index: usize, best_time: u64, best_moves: u32) -> Option<()> {
+    /// Updates the best values of the level, the best time is not changed if best_time is None (e.g. in relaxed mode)
+    pub fn update_stats(&mut self, index: usize, best_time: Option<u64>, best_moves: u32) -> Option<()> {
         let level = self.levels.get_mut(index)?;
+        self.stats_sum.remove_level(level);
 
-        level.best_time = if level.best_time.is_none_or(|level_best_time| best_time < level_best_time) {
-            Some(best_time)
-        }else {
-            level.best_time
-        };
+        let old_best = (level.best_time, level.best_moves);
+
+        if let Some(best_time) = best_time && level.best_time.is_none_or(|level_best_time| best_time < level_best_time) {
+            level.best_time = Some(best_time);
+        }
 
         level.best_moves = if level.best_moves.is_none_or(|level_best_moves| best_moves < level_best_moves) {
             Some(best_moves)
@@ -1471,44 +4557,92 @@ impl LevelPack {
             level.best_moves
         };
 
-        self.calculate_stats_sum();
+        //Unverified best values are only verified again if both values were reached
+        if !level.stats_verified {
+            level.stats_verified = level.best_time == best_time && level.best_moves == Some(best_moves);
+        }
+
+        //The first completion is not a record which can be undone
+        if let (old_best_time, Some(old_best_moves)) = old_best && old_best != (level.best_time, level.best_moves) {
+            level.previous_best = Some((old_best_time, old_best_moves));
+        }
+
+        self.stats_sum.add_level(level);
+        self.debug_check_stats_sum();
 
         Some(())
     }
 
-    pub fn add_level(&mut self, level: Level) {
-        self.levels.push(LevelWithStats::new(level, None, None));
+    /// Replaces the best time and the best moves of the level with the values before the last new record
+    ///
+    /// Returns None if the level has no previous best
+    pub fn restore_previous_best(&mut self, index: usize) -> Option<()> {
+        let level = self.levels.get_mut(index)?;
+        let (previous_best_time, previous_best_moves) = level.previous_best.take()?;
 
-        self.calculate_stats_sum();
+        self.stats_sum.remove_level(level);
+        level.best_time = previous_best_time;
+        level.best_moves = Some(previous_best_moves);
+        self.stats_sum.add_level(level);
+        self.debug_check_stats_sum();
+
+        Some(())
     }
 
-    pub(super) fn calculate_stats_sum(&mut self) {
-        if self.levels.is_empty() {
-            self.level_pack_best_time_sum = None;
-            self.level_pack_best_moves_sum = None;
+    /// Sets the best moves of the level (Used for the validation in the level editor)
+    /// Returns the count of levels which were validated by the solver instead of a validation run
+    pub fn solver_validated_level_count(&self) -> usize {
+        self.levels.iter().
+                filter(|level| level.is_solver_validated()).
+                count()
+    }
 
-            return;
+    pub fn set_level_best_moves(&mut self, index: usize, best_moves: Option<u32>) -> Option<()> {
+        let level = self.levels.get_mut(index)?;
+
+        self.stats_sum.remove_level(level);
+        level.set_best_moves(best_moves);
+        if best_moves.is_none() {
+            level.set_solver_validated(false);
         }
+        self.stats_sum.add_level(level);
+        self.debug_check_stats_sum();
 
-        let stats_sum = self.levels.iter().
-                fold((Some(0), Some(0)), |mut sum, current| {
-                    sum.0 = if let Some(best_time) = current.best_time {
-                        sum.0.map(|sum| sum + best_time)
-                    }else {
-                        None
-                    };
+        Some(())
+    }
 
-                    sum.1 = if let Some(best_moves) = current.best_moves {
-                        sum.1.map(|sum| sum + best_moves)
-                    }else {
-                        None
-                    };
+    pub fn add_level(&mut self, level: Level) {
+        self.insert_level(self.levels.len(), LevelWithStats::new(level, None, None));
+    }
 
-                    sum
-                });
+    /// Panics if index > level count
+    pub fn insert_level(&mut self, index: usize, level: LevelWithStats) {
+        self.stats_sum.add_level(&level);
+        self.levels.insert(index, level);
+        self.debug_check_stats_sum();
+    }
+
+    /// Panics if index >= level count
+    pub fn remove_level(&mut self, index: usize) -> LevelWithStats {
+        let level = self.levels.remove(index);
+        self.stats_sum.remove_level(&level);
+        self.debug_check_stats_sum();
+
+        level
+    }
+
+    /// Recalculates the stats sum from all levels, this is required if levels were changed directly with [Self::levels_mut]
+    pub(super) fn calculate_stats_sum(&mut self) {
+        self.stats_sum = LevelPackStatsSum::from_levels(&self.levels);
+    }
 
-        self.level_pack_best_time_sum = stats_sum.0;
-        self.level_pack_best_moves_sum = stats_sum.1;
+    /// Cross-checks the incrementally updated stats sum against a full recalculation in debug builds
+    fn debug_check_stats_sum(&self) {
+        debug_assert_eq!(
+            self.stats_sum, LevelPackStatsSum::from_levels(&self.levels),
+            "The incrementally updated stats sum of the level pack \"{}\" is out of sync",
+            self.id,
+        );
     }
 
     #[cfg(feature = "steam")]
@@ -1517,21 +4651,137 @@ impl LevelPack {
     }
 }
 
+/// Splits lines in the format "KEY[LANGUAGE_CODE]: VALUE" into the language code and the value (The value can be empty)
+fn strip_localized_prefix<'a>(line: &'a str, key: &str) -> Option<(&'a str, &'a str)> {
+    line.strip_prefix(key)?.
+            strip_prefix('[')?.
+            split_once("]:").
+            map(|(language_code, value)| (language_code, value.trim_start()))
+}
+
+/// Entry point for fuzz testing of the level parsers
+///
+/// The arbitrary input is parsed as level, level pack, level hint, and level attempt. Errors are ignored, but parsing must never panic.
+#[doc(hidden)]
+pub fn fuzz_parse(data: &[u8]) {
+    let data = String::from_utf8_lossy(data);
+
+    let _ = Level::from_str(&data);
+    let _ = LevelPack::from_level_pack_data("fuzz", "fuzz", &data, false);
+    let _ = LevelPack::from_level_pack_data("fuzz", "fuzz", &data, true);
+    let _ = LevelHint::from_str(&data);
+    let _ = LevelAttempt::from_str(&data);
+}
+
+/// An error which occurred during parsing of a level or a level pack
+///
+/// The line and column are 1-based and are relative to the parsed input
 #[derive(Debug)]
 pub struct LevelLoadingError {
-    message: String
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+    token: Option<String>,
 }
 
 impl LevelLoadingError {
     fn new(message: impl Into<String>) -> Self {
-        Self { message: message.into() }
+        Self { message: message.into(), line: None, column: None, token: None }
+    }
+
+    fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+
+        self
+    }
+
+    fn with_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+
+        self
+    }
+
+    fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+
+        self
+    }
+
+    /// The reason of this error without position information
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    /// The offending token
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
     }
 }
 
 impl Display for LevelLoadingError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.message)
+        f.write_str(&self.message)?;
+
+        if self.line.is_none() && self.column.is_none() && self.token.is_none() {
+            return Ok(());
+        }
+
+        f.write_str(" (")?;
+        if let Some(line) = self.line {
+            write!(f, "Line {line}")?;
+        }
+
+        if let Some(column) = self.column {
+            if self.line.is_some() {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "Column {column}")?;
+        }
+
+        if let Some(token) = &self.token {
+            if self.line.is_some() || self.column.is_some() {
+                f.write_str(": ")?;
+            }
+
+            //Non-ASCII and control characters are escaped, because they cannot be drawn
+            write!(f, "\"{}\"", token.escape_default())?;
+        }
+
+        f.write_str(")")
     }
 }
 
 impl Error for LevelLoadingError {}
+
+/// Returned if a level pack requires a newer version of the game
+#[derive(Debug)]
+pub struct UnsupportedGameVersionError {
+    min_game_version: GameVersion,
+}
+
+impl UnsupportedGameVersionError {
+    fn new(min_game_version: GameVersion) -> Self {
+        Self { min_game_version }
+    }
+
+    pub fn min_game_version(&self) -> GameVersion {
+        self.min_game_version
+    }
+}
+
+impl Display for UnsupportedGameVersionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "This pack requires SokoTerm >= {} (Installed version: {})", self.min_game_version, Game::VERSION)
+    }
+}
+
+impl Error for UnsupportedGameVersionError {}
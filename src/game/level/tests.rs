@@ -0,0 +1,736 @@
+use std::str::FromStr;
+use crate::game::level::*;
+use crate::game::input::InGameAction;
+
+#[test]
+fn level_attempt_to_str() {
+    let level_attempt = LevelAttempt::new(12345, Vec::new());
+    assert_eq!(level_attempt.to_str(), "12345,");
+
+    let level_attempt = LevelAttempt::new(0, [Direction::Left, Direction::Up, Direction::Right, Direction::Down]);
+    assert_eq!(level_attempt.to_str(), "0,lurd");
+
+    let level_attempt = LevelAttempt::new(42, [
+        Direction::Right, Direction::Right, Direction::Right,
+        Direction::Up,
+        Direction::Left, Direction::Left,
+    ]);
+    assert_eq!(level_attempt.to_str(), "42,3ru2l");
+}
+
+#[test]
+fn level_attempt_from_str() {
+    assert_eq!(LevelAttempt::from_str("12345,").unwrap(), LevelAttempt::new(12345, Vec::new()));
+    assert_eq!(LevelAttempt::from_str("0,lurd").unwrap(), LevelAttempt::new(0, [Direction::Left, Direction::Up, Direction::Right, Direction::Down]));
+    assert_eq!(LevelAttempt::from_str("42,3ru2l").unwrap(), LevelAttempt::new(42, [
+        Direction::Right, Direction::Right, Direction::Right,
+        Direction::Up,
+        Direction::Left, Direction::Left,
+    ]));
+    assert_eq!(LevelAttempt::from_str("1,12d").unwrap().moves().len(), 12);
+}
+
+#[test]
+fn level_attempt_from_str_invalid() {
+    assert!(LevelAttempt::from_str("").is_err());
+    assert!(LevelAttempt::from_str("lurd").is_err());
+    assert!(LevelAttempt::from_str("x,lurd").is_err());
+    assert!(LevelAttempt::from_str("0,lurdx").is_err());
+    assert!(LevelAttempt::from_str("0,lurd3").is_err());
+    assert!(LevelAttempt::from_str("0,10001r").is_err());
+}
+
+#[test]
+fn level_attempt_round_trip() {
+    let level_attempt = LevelAttempt::new(98765, [
+        Direction::Down, Direction::Down, Direction::Left, Direction::Up, Direction::Up, Direction::Up,
+        Direction::Right, Direction::Down, Direction::Down, Direction::Down, Direction::Down, Direction::Down,
+        Direction::Down, Direction::Down, Direction::Down, Direction::Down, Direction::Down, Direction::Down,
+    ]);
+
+    assert_eq!(LevelAttempt::from_str(&level_attempt.to_str()).unwrap(), level_attempt);
+}
+
+#[test]
+fn game_version_from_str() {
+    assert_eq!(GameVersion::from_str("1.0").unwrap(), GameVersion::V1_0);
+    assert_eq!(GameVersion::from_str(" 2.13 ").unwrap(), GameVersion::new(2, 13));
+    assert_eq!(GameVersion::new(2, 13).to_string(), "2.13");
+
+    assert!(GameVersion::from_str("1").is_err());
+    assert!(GameVersion::from_str("1.x").is_err());
+    assert!(GameVersion::from_str("1.0.0").is_err());
+
+    assert!(GameVersion::new(1, 10) > GameVersion::new(1, 9));
+    assert!(GameVersion::new(2, 0) > GameVersion::new(1, 10));
+    assert!(GameVersion::current() >= GameVersion::V1_0);
+}
+
+#[test]
+fn level_pack_min_game_version() {
+    //Level packs written by this version must be playable in this version
+    assert!(GameVersion::current() >= GameVersion::V1_1);
+
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nw: 3, h: 1\np@x", false).unwrap();
+    assert_eq!(level_pack.min_game_version(), GameVersion::V1_0);
+
+    let mut level_pack_data = Vec::new();
+    level_pack.write_level_pack(&mut level_pack_data, false).unwrap();
+    assert_eq!(String::from_utf8(level_pack_data).unwrap(), "Min Game Version: 1.0\nName: test\nLevels: 1\n\nw: 3, h: 1\nP@x\n");
+
+    //Format features of newer versions
+    level_pack.set_localized_name(Language::German, Some("Test".to_string()));
+    assert_eq!(level_pack.min_game_version(), GameVersion::V1_1);
+
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nPar Moves: 3\nw: 3, h: 1\np@x", false).unwrap();
+    assert_eq!(level_pack.min_game_version(), GameVersion::V1_1);
+
+    let mut level_pack_data = Vec::new();
+    level_pack.write_level_pack(&mut level_pack_data, false).unwrap();
+    let level_pack_data = String::from_utf8(level_pack_data).unwrap();
+    assert!(level_pack_data.starts_with("Min Game Version: 1.1\nName: test\n"));
+    assert_eq!(LevelPack::from_level_pack_data("test", "test.lvl", &level_pack_data, false).unwrap().min_game_version(), GameVersion::V1_1);
+
+    let err = LevelPack::from_level_pack_data("test", "test.lvl", "Min Game Version: 99.0\nName: test\nLevels: 1\n\nw: 3, h: 1\np@x", false).unwrap_err();
+    let err = err.downcast_ref::<UnsupportedGameVersionError>().unwrap();
+    assert_eq!(err.min_game_version(), GameVersion::new(99, 0));
+
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", "Min Game Version: 1.x\nName: test\nLevels: 1\n\nw: 3, h: 1\np@x", false).is_err());
+}
+
+#[test]
+fn level_flood_fill() {
+    let mut level = Level::from_str("w: 5, h: 4\n#####\n#--##\n#-#-#\n#####").unwrap();
+
+    assert!(level.flood_fill(1, 1, Tile::Box));
+    assert_eq!(level.to_str(), "w: 5, h: 4\n#####\n#@@##\n#@#-#\n#####\n");
+
+    assert!(!level.flood_fill(1, 1, Tile::Box));
+    assert!(!level.flood_fill(5, 0, Tile::Box));
+
+    assert!(level.flood_fill(0, 0, Tile::Empty));
+    assert_eq!(level.to_str(), "w: 5, h: 4\n-----\n-@@--\n-@---\n-----\n");
+}
+
+#[test]
+fn level_with_wall_outline() {
+    let level = Level::from_str("w: 6, h: 4\n------\n-p@---\n---x--\n------").unwrap();
+    assert_eq!(level.with_wall_outline().unwrap().to_str(), "w: 6, h: 4\n#####-\n#P@-#-\n#--x#-\n#####-\n");
+
+    let level = Level::from_str("w: 3, h: 1\np@x").unwrap();
+    assert_eq!(level.with_wall_outline().unwrap().to_str(), "w: 5, h: 3\n#####\n#P@x#\n#####\n");
+
+    let level = Level::from_str("w: 3, h: 3\n---\n-#-\n---").unwrap();
+    assert!(level.with_wall_outline().is_none());
+}
+
+#[test]
+fn level_wall_variants_only_depend_on_walls() {
+    let level = Level::from_str("w: 8, h: 3\n########\n#p@--x-#\n########").unwrap();
+    let moved_level = Level::from_str("w: 8, h: 3\n########\n#--p@x-#\n########").unwrap();
+    assert_eq!(level.wall_layout_hash(), moved_level.wall_layout_hash());
+
+    let other_level = Level::from_str("w: 8, h: 3\n########\n#p@-#x-#\n########").unwrap();
+    assert_ne!(level.wall_layout_hash(), other_level.wall_layout_hash());
+
+    let variants = (0..8).map(|x| Level::wall_variant(level.wall_layout_hash(), x, 0)).collect::<Vec<_>>();
+    assert!(variants.iter().all(|&variant| variant < 3));
+    assert_eq!(variants, (0..8).map(|x| Level::wall_variant(moved_level.wall_layout_hash(), x, 0)).collect::<Vec<_>>());
+}
+
+#[test]
+fn level_neighbor_pos_wrap_around() {
+    let mut level = Level::from_str("w: 3, h: 2\n---\n---").unwrap();
+    assert_eq!(level.neighbor_pos(0, 0, Direction::Left), Some((2, 0)));
+    assert_eq!(level.neighbor_pos(1, 1, Direction::Down), Some((1, 0)));
+
+    level.set_wrap_around(false);
+    assert_eq!(level.neighbor_pos(0, 0, Direction::Left), None);
+    assert_eq!(level.neighbor_pos(1, 1, Direction::Down), None);
+    assert_eq!(level.neighbor_pos(1, 1, Direction::Up), Some((1, 0)));
+}
+
+#[test]
+fn playing_level_box_sunk_event() {
+    let level = Level::from_str("w: 5, h: 1\np@o--").unwrap();
+    let mut playing_level = PlayingLevel::new(&level, 10).unwrap();
+
+    assert!(playing_level.move_player(Direction::Right).is_valid());
+    assert_eq!(playing_level.tile_events(), &[TileEvent::BoxSunk { x: 2, y: 0 }]);
+
+    assert!(playing_level.move_player(Direction::Right).is_valid());
+    assert!(playing_level.tile_events().is_empty());
+}
+
+#[test]
+fn playing_level_key_door_count() {
+    let level = Level::from_str("w: 7, h: 1\np*=*o-x").unwrap();
+    let mut playing_level = PlayingLevel::new(&level, 10).unwrap();
+    assert_eq!(playing_level.original_key_door_count().keys(), 2);
+
+    //The key is collected by unlocking the door
+    assert!(playing_level.move_player(Direction::Right).is_valid());
+    let key_door_count = playing_level.current_playing_level().2;
+    assert_eq!(key_door_count.collected_keys(), 1);
+    assert_eq!(key_door_count.keys(), 1);
+    assert_eq!(key_door_count.locked_doors(), 0);
+
+    //A lost key is not collected
+    assert!(playing_level.move_player(Direction::Right).is_valid());
+    assert!(playing_level.move_player(Direction::Right).is_valid());
+    let key_door_count = playing_level.current_playing_level().2;
+    assert_eq!(key_door_count.collected_keys(), 1);
+    assert_eq!(key_door_count.keys(), 0);
+
+    playing_level.undo_move();
+    playing_level.undo_move();
+    playing_level.undo_move();
+    assert_eq!(playing_level.current_playing_level().2.collected_keys(), 0);
+}
+
+#[test]
+fn level_pack_update_stats_without_time() {
+    let mut level_pack = LevelPack::new("Test", "test", "test.lvl");
+    level_pack.add_level(Level::from_str("w: 3, h: 1\np-x").unwrap());
+
+    level_pack.update_stats(0, None, 20);
+    assert_eq!(level_pack.levels()[0].best_time(), None);
+    assert_eq!(level_pack.levels()[0].best_moves(), Some(20));
+    assert_eq!(level_pack.level_pack_best_time_sum(), None);
+
+    level_pack.update_stats(0, Some(5000), 25);
+    level_pack.update_stats(0, None, 15);
+    assert_eq!(level_pack.levels()[0].best_time(), Some(5000));
+    assert_eq!(level_pack.levels()[0].best_moves(), Some(15));
+}
+
+#[test]
+fn level_pack_incremental_stats_sum() {
+    let level = Level::from_str("w: 3, h: 1\np-x").unwrap();
+
+    let mut level_pack = LevelPack::new("Test", "test", "test.lvl");
+    assert_eq!(level_pack.level_pack_best_moves_sum(), None);
+
+    level_pack.add_level(level.clone());
+    level_pack.add_level(level.clone());
+    level_pack.update_stats(0, Some(1000), 10);
+    assert_eq!(level_pack.level_pack_best_moves_sum(), None);
+
+    level_pack.update_stats(1, Some(2000), 20);
+    assert_eq!(level_pack.level_pack_best_time_sum(), Some(3000));
+    assert_eq!(level_pack.level_pack_best_moves_sum(), Some(30));
+
+    let removed_level = level_pack.remove_level(0);
+    assert_eq!(level_pack.level_pack_best_time_sum(), Some(2000));
+    assert_eq!(level_pack.level_pack_best_moves_sum(), Some(20));
+
+    level_pack.insert_level(0, removed_level);
+    level_pack.set_level_best_moves(1, Some(5));
+    assert_eq!(level_pack.level_pack_best_moves_sum(), Some(15));
+
+    level_pack.add_level(level);
+    assert_eq!(level_pack.level_pack_best_time_sum(), None);
+    assert_eq!(level_pack.level_pack_best_moves_sum(), None);
+}
+
+#[test]
+fn level_pack_shuffled_level_indices() {
+    let level = Level::from_str("w: 3, h: 1\np-x").unwrap();
+
+    let mut level_pack = LevelPack::new("Test", "test", "test.lvl");
+    for _ in 0..8 {
+        level_pack.add_level(level.clone());
+    }
+
+    level_pack.set_min_level_not_completed(3);
+    let mut level_indices = level_pack.shuffled_level_indices(42);
+    assert_eq!(level_indices, level_pack.shuffled_level_indices(42));
+    level_indices.sort();
+    assert_eq!(level_indices, [0, 1, 2, 3]);
+
+    level_pack.set_min_level_not_completed(8);
+    let mut level_indices = level_pack.shuffled_level_indices(7);
+    level_indices.sort();
+    assert_eq!(level_indices, (0..8).collect::<Vec<_>>());
+}
+
+#[test]
+fn level_hint_from_str() {
+    let hint = LevelHint::from_str("8, tile:^, One-way doors ({t:^}) can be entered from {e:one} side, press {k:q}").unwrap();
+    assert_eq!(hint.row(), 8);
+    assert_eq!(hint.trigger(), LevelHintTrigger::TileEncountered(Tile::OneWayUp));
+    assert_eq!(hint.segments(), [
+        LevelHintSegment::Text("One-way doors ("),
+        LevelHintSegment::Tile(Tile::OneWayUp),
+        LevelHintSegment::Text(") can be entered from "),
+        LevelHintSegment::Emphasis("one"),
+        LevelHintSegment::Text(" side, press "),
+        LevelHintSegment::KeyInput("q"),
+    ]);
+    assert_eq!(hint.width(), 55);
+    assert_eq!(hint.to_string(), "8, tile:^, One-way doors ({t:^}) can be entered from {e:one} side, press {k:q}");
+
+    let hint = LevelHint::from_str("9, always, {a:Undo}: Undo, {a:Jump}").unwrap();
+    assert_eq!(hint.segments(), [
+        LevelHintSegment::Action(InGameAction::Undo),
+        LevelHintSegment::Text(": Undo, {"),
+        LevelHintSegment::Text("a:Jump}"),
+    ]);
+    assert_eq!(hint.width(), 17);
+
+    let hint = LevelHint::from_str("7, always, {x:?} {t:").unwrap();
+    assert_eq!(hint.segments(), [
+        LevelHintSegment::Text("{"),
+        LevelHintSegment::Text("x:?} {"),
+        LevelHintSegment::Text("t:"),
+    ]);
+
+    assert!(LevelHint::from_str("0, always, Text").is_err());
+    assert!(LevelHint::from_str("8, sometimes, Text").is_err());
+    assert!(LevelHint::from_str("8, always").is_err());
+    assert!(LevelHint::from_str(&format!("8, always, {}", "-".repeat(75))).is_err());
+}
+
+#[test]
+fn level_hint_placement_warnings() {
+    let level = Level::new(20, 5);
+    let hints = [
+        LevelHint::new(3, LevelHintTrigger::Always, "Covers the level"),
+        LevelHint::new(10, LevelHintTrigger::Always, "Press {k:r} to restart"),
+        LevelHint::new(10, LevelHintTrigger::Playing, "Overlapping"),
+        LevelHint::new(12, LevelHintTrigger::Playing, "Playing"),
+        LevelHint::new(12, LevelHintTrigger::Completed, "Completed"),
+        LevelHint::new(14, LevelHintTrigger::Always, "-".repeat(80)),
+    ];
+
+    assert_eq!(LevelHint::placement_warnings(&hints, &level), [
+        "Hint 1 (Row 3) covers tiles of the level",
+        "Hint 2 (Row 10) overlaps hint 3",
+        "Hint 6 (Row 14) is clipped: The text is wider than the console (80 > 74)",
+    ]);
+    assert!(LevelHint::placement_warnings(&hints[3..5], &level).is_empty());
+}
+
+#[test]
+fn localized_line_prefix() {
+    assert_eq!(strip_localized_prefix("Name[de]: Tutorial", "Name"), Some(("de", "Tutorial")));
+    assert_eq!(strip_localized_prefix("Hint[fr]: 8, always, Text", "Hint"), Some(("fr", "8, always, Text")));
+    assert_eq!(strip_localized_prefix("Name: Tutorial", "Name"), None);
+    assert_eq!(strip_localized_prefix("Name[de] Tutorial", "Name"), None);
+
+    assert_eq!(strip_localized_prefix("Description[de]:", "Description"), Some(("de", "")));
+
+    assert_eq!(Language::from_code("de"), Some(Language::German));
+    assert_eq!(Language::from_code("xx"), None);
+}
+
+#[test]
+fn level_pack_localized_description() {
+    let level_pack_data = "Name: Test\nDescription: First line\nDescription[de]: Erste Zeile\nDescription[de]:\n\
+            Description: Second line\nLevels: 1\n\nw: 3, h: 1\nP@x";
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", level_pack_data, false).unwrap();
+    assert_eq!(level_pack.description(), ["First line", "Second line"]);
+    assert_eq!(level_pack.localized_description(Language::German), ["Erste Zeile", ""]);
+    assert_eq!(level_pack.localized_description(Language::French), ["First line", "Second line"]);
+
+    let mut written_level_pack_data = Vec::new();
+    level_pack.write_level_pack(&mut written_level_pack_data, false).unwrap();
+    let written_level_pack_data = String::from_utf8(written_level_pack_data).unwrap();
+    assert!(written_level_pack_data.contains("Description: Second line\nDescription[de]: Erste Zeile\nDescription[de]: \n"));
+
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", &written_level_pack_data, false).unwrap();
+    assert_eq!(level_pack.localized_description(Language::German), ["Erste Zeile", ""]);
+
+    let err = LevelPack::from_level_pack_data("test", "test.lvl", "Description[xx]: Text\nLevels: 1\n\nw: 3, h: 1\nP@x", false).unwrap_err();
+    let err = err.downcast_ref::<LevelLoadingError>().unwrap();
+    assert_eq!((err.line(), err.column(), err.token()), (Some(1), Some(13), Some("xx")));
+
+    let level_pack_data = format!("{}Levels: 1\n\nw: 3, h: 1\nP@x", "Description[de]: Text\n".repeat(LevelPack::MAX_DESCRIPTION_LINE_COUNT + 1));
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", &level_pack_data, false).is_err());
+}
+
+#[test]
+fn level_pack_update_localized_name() {
+    let mut level_pack = LevelPack::new("Test", "test", "test.lvl");
+
+    //The pending name is stored (e.g. before the next language is edited in the level pack editor)
+    assert!(level_pack.update_localized_name(Language::German, " Prüfung "));
+    assert_eq!(level_pack.localized_name(Language::German), "Prüfung");
+    assert!(!level_pack.update_localized_name(Language::German, "Prüfung"));
+
+    //The untranslated name is used instead of an equal translation
+    assert!(!level_pack.update_localized_name(Language::French, "Test"));
+    assert!(level_pack.update_localized_name(Language::German, ""));
+    assert_eq!(level_pack.localized_name(Language::German), "Test");
+    assert!(!level_pack.update_localized_name(Language::German, ""));
+}
+
+#[test]
+fn level_from_str_error_position() {
+    let err = Level::from_str("w: 3, h: 2\n#p#\n#?#").unwrap_err();
+    assert_eq!((err.line(), err.column(), err.token()), (Some(3), Some(2), Some("?")));
+
+    let err = Level::from_str("w: 3, h: 2\n#p#\n##").unwrap_err();
+    assert_eq!((err.line(), err.column()), (Some(3), Some(3)));
+
+    let err = Level::from_str("w: 3, h: x\n#p#").unwrap_err();
+    assert_eq!((err.line(), err.column(), err.token()), (Some(1), Some(10), Some("x")));
+    assert_eq!(err.to_string(), "Level height is invalid! (Line 1, Column 10: \"x\")");
+}
+
+#[test]
+fn level_pack_error_line() {
+    let err = LevelPack::from_level_pack_data("test", "test", "Name: Test\nLevels: 1\n\nw: 3, h: 1\n\n#p?", false).unwrap_err();
+    let err = err.downcast_ref::<LevelLoadingError>().unwrap();
+    assert_eq!((err.line(), err.column(), err.token()), (Some(6), Some(3), Some("?")));
+
+    let err = LevelPack::from_level_pack_data("test", "test", "Name: Test\nLevels: x", false).unwrap_err();
+    let err = err.downcast_ref::<LevelLoadingError>().unwrap();
+    assert_eq!((err.line(), err.token()), (Some(2), Some("x")));
+}
+
+#[test]
+fn level_pack_author_and_description() {
+    let lvl_data = "Name: Test\nAuthor: Someone\nDescription: First line\nDescription:\nDescription: Third line\nLevels: 1\n\nw: 3, h: 1\n#p-";
+
+    let level_pack = LevelPack::from_level_pack_data("test", "test", lvl_data, false).unwrap();
+    assert_eq!(level_pack.author(), Some("Someone"));
+    assert_eq!(level_pack.description(), ["First line", "", "Third line"]);
+
+    let level_pack = LevelPack::from_level_pack_data("test", "test", "Name: Test\nLevels: 1\n\nw: 3, h: 1\n#p-", false).unwrap();
+    assert_eq!(level_pack.author(), None);
+    assert!(level_pack.description().is_empty());
+
+    let lvl_data = format!("Name: Test\nAuthor: {}\nLevels: 1\n\nw: 3, h: 1\n#p-", "a".repeat(LevelPack::MAX_AUTHOR_LEN + 1));
+    let err = LevelPack::from_level_pack_data("test", "test", &lvl_data, false).unwrap_err();
+    assert_eq!(err.downcast_ref::<LevelLoadingError>().unwrap().line(), Some(2));
+}
+
+#[test]
+fn editor_level_pack_notes_and_todo_markers() {
+    let lvl_data = "Name: Test\nLevels: 1\n\nNote: Design intent\nNote:\nTodo: 1, 0\nTodo: 5, 0\nw: 3, h: 1\n#p-";
+
+    let level_pack = LevelPack::from_level_pack_data("test", "test", lvl_data, true).unwrap();
+    let level = &level_pack.levels()[0];
+    assert_eq!(level.editor_notes(), ["Design intent", ""]);
+    assert_eq!(level.todo_markers(), [(1, 0)]);
+
+    //Notes and TODO markers are only allowed in editor level packs
+    assert!(LevelPack::from_level_pack_data("test", "test", lvl_data, false).is_err());
+}
+
+#[test]
+fn editor_level_pack_validation_moves() {
+    let lvl_data = "Name: Test\nLevels: 1\n\nValidation Moves: 2rl\nw: 4, h: 1\n#p--";
+
+    let level_pack = LevelPack::from_level_pack_data("test", "test", lvl_data, true).unwrap();
+    let validation_moves = level_pack.levels()[0].validation_moves().unwrap();
+    assert_eq!(validation_moves, [Direction::Right, Direction::Right, Direction::Left]);
+    assert_eq!(moves_to_str(validation_moves), "2rl");
+
+    //Validation moves are only allowed in editor level packs
+    assert!(LevelPack::from_level_pack_data("test", "test", lvl_data, false).is_err());
+
+    let lvl_data = "Name: Test\nLevels: 1\n\nValidation Moves: 2x\nw: 4, h: 1\n#p--";
+    assert!(LevelPack::from_level_pack_data("test", "test", lvl_data, true).is_err());
+}
+
+#[test]
+fn editor_level_pack_changelog() {
+    let lvl_data = "Name: Test\nChangelog: Level 1 added\nChange Summary: New level\nLevels: 1\n\nw: 3, h: 1\n#p-";
+
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test", lvl_data, true).unwrap();
+    assert_eq!(level_pack.changelog(), ["Level 1 added"]);
+    assert_eq!(level_pack.change_summary(), "New level");
+
+    level_pack.add_changelog_entry("Level 1 modified");
+    level_pack.add_changelog_entry("Level 1 modified");
+    assert_eq!(level_pack.workshop_change_note(), "New level\n- Level 1 added\n- Level 1 modified");
+
+    level_pack.clear_changelog();
+    assert_eq!(level_pack.workshop_change_note(), LevelPack::INITIAL_RELEASE_CHANGE_NOTE);
+
+    //The changelog is only allowed in editor level packs
+    assert!(LevelPack::from_level_pack_data("test", "test", lvl_data, false).is_err());
+}
+
+#[test]
+fn level_xsb_conversion() {
+    let level = Level::from_xsb("Title: Test\n; Comment\n#####\n#@$.#\n# * #\n#####\n\n#####").unwrap();
+    assert_eq!(level.to_str(), "w: 5, h: 4\n#####\n#P@x#\n#-+-#\n#####\n");
+    assert!(!level.wrap_around());
+    assert_eq!(level.to_xsb().unwrap(), "#####\n#@$.#\n# * #\n#####\n");
+
+    //Run-length encoding and short rows
+    let level = Level::from_xsb("3#|#@\n2#").unwrap();
+    assert_eq!(level.to_str(), "w: 3, h: 3\n###\n#P-\n##-\n");
+
+    assert!(Level::from_xsb("#+#").is_err());
+    assert!(Level::from_xsb("Title only").is_err());
+    assert_eq!(Level::from_str("w: 1, h: 1\n%").unwrap().to_xsb(), None);
+}
+
+#[test]
+fn fuzz_parse_malformed_input() {
+    fuzz_parse(b"");
+    fuzz_parse(b"w: 18446744073709551615, h: 18446744073709551615\n#");
+    fuzz_parse(b"w: 1, h: 18446744073709551615\n#");
+    fuzz_parse(b"Levels: 1\nw: 1, h: 18446744073709551615\np");
+    fuzz_parse(b"Levels: 1\nHint: 8, always, {t:\xff}\nw: 1, h: 1\n\xc3\xa4");
+    fuzz_parse(b"w: 2, h: 1\n\xc3\xa4");
+    fuzz_parse(b"0,99999999999999999999999l");
+    fuzz_parse(b"Min Game Version: 1.\nName[: x");
+}
+
+#[test]
+fn level_pack_level_theme() {
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 2\n\nTheme: night\nw: 3, h: 1\np@x\n\nw: 3, h: 1\np@x", false).unwrap();
+    assert_eq!(level_pack.levels()[0].level().theme(), LevelTheme::Night);
+    assert_eq!(level_pack.levels()[1].level().theme(), LevelTheme::Default);
+
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nTheme: unknown\nw: 3, h: 1\np@x", false).is_err());
+}
+
+#[test]
+fn level_pack_par_moves_and_time() {
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 2\n\nPar Moves: 12\nPar Time: 3456\nw: 3, h: 1\np@x\n\nw: 3, h: 1\np@x", false).unwrap();
+    assert_eq!(level_pack.levels()[0].par_moves(), Some(12));
+    assert_eq!(level_pack.levels()[0].par_time(), Some(3456));
+    assert_eq!(level_pack.levels()[1].par_moves(), None);
+    assert_eq!(level_pack.levels()[1].par_time(), None);
+
+    let mut level_pack_data = Vec::new();
+    level_pack.write_level_pack(&mut level_pack_data, false).unwrap();
+    let level_pack_data = String::from_utf8(level_pack_data).unwrap();
+    assert!(level_pack_data.contains("Par Moves: 12\nPar Time: 3456\n"));
+    assert_eq!(level_pack_data.matches("Par Moves: ").count(), 1);
+    assert_eq!(level_pack_data.matches("Par Time: ").count(), 1);
+
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", &level_pack_data, false).unwrap();
+    assert_eq!(level_pack.levels()[0].par_moves(), Some(12));
+    assert_eq!(level_pack.levels()[0].par_time(), Some(3456));
+    assert_eq!(level_pack.levels()[1].par_moves(), None);
+    assert_eq!(level_pack.levels()[1].par_time(), None);
+}
+
+#[test]
+fn level_pack_par_moves_and_time_invalid() {
+    let err = LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nPar Moves: x\nw: 3, h: 1\np@x", false).unwrap_err();
+    let err = err.downcast_ref::<LevelLoadingError>().unwrap();
+    assert_eq!((err.line(), err.token()), (Some(3), Some("x")));
+
+    let err = LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nPar Time: 1.5\nw: 3, h: 1\np@x", false).unwrap_err();
+    let err = err.downcast_ref::<LevelLoadingError>().unwrap();
+    assert_eq!((err.line(), err.token()), (Some(3), Some("1.5")));
+
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nPar Moves: -1\nw: 3, h: 1\np@x", false).is_err());
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nPar Time: -5\nw: 3, h: 1\np@x", false).is_err());
+}
+
+#[test]
+fn level_tile_changes() {
+    let saved_level = Level::from_str("w: 4, h: 2\n#-@x\n#p--").unwrap();
+    let level = Level::from_str("w: 5, h: 2\n--@#-\n#p-x-").unwrap();
+
+    assert_eq!(level.tile_changes(&saved_level), vec![
+        (0, 0, TileChange::Removed),
+        (3, 0, TileChange::Changed),
+        (3, 1, TileChange::Added),
+    ]);
+    assert!(saved_level.tile_changes(&saved_level).is_empty());
+}
+
+#[test]
+fn level_start_marker_on_goal() {
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nStart Position: 0, 0\nw: 5, h: 1\nx-@--", false).unwrap();
+    let level = level_pack.levels()[0].level();
+    assert_eq!(level.start_pos(), Some((0, 0)));
+    assert_eq!(level.player_count(), 1);
+    assert_eq!(level.to_xsb().unwrap(), "+ $\n");
+
+    let mut playing_level = PlayingLevel::new(level, 10).unwrap();
+    assert_eq!(playing_level.current_playing_level().0.get_tile(0, 0), Some(Tile::Player));
+
+    //The goal below the start marker is restored after the player moved
+    assert!(playing_level.move_player(Direction::Left).is_valid());
+    assert_eq!(playing_level.current_playing_level().0.get_tile(0, 0), Some(Tile::Goal));
+    assert!(playing_level.replay_moves(&[Direction::Left, Direction::Left]));
+    assert!(playing_level.move_player(Direction::Left).has_won());
+
+    let mut level_pack_data = Vec::new();
+    level_pack.write_level_pack(&mut level_pack_data, false).unwrap();
+    assert!(String::from_utf8(level_pack_data).unwrap().contains("Start Position: 0, 0\nw: 5, h: 1\n"));
+
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nStart Position: 2, 0\nw: 5, h: 1\nx-@--", false).is_err());
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", "Levels: 1\n\nStart Position: 0, 0\nw: 5, h: 1\nx-@-p", false).is_err());
+}
+
+#[test]
+fn bonus_level_is_unlocked_after_all_secrets_are_found() {
+    let level_pack_data = "Bonus Level: collect-all-secrets\nLevels: 3\n\nw: 4, h: 1\np@xs\n\nw: 3, h: 1\np@x\n\nw: 4, h: 1\np@xs\n";
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test.lvl", level_pack_data, false).unwrap();
+
+    assert_eq!(level_pack.bonus_level_condition(), Some(BonusLevelCondition::CollectAllSecrets));
+    assert_eq!(level_pack.bonus_level_index(), Some(2));
+    assert_eq!(level_pack.secret_level_indices().collect::<Vec<_>>(), [0]);
+    assert!(level_pack.is_locked_bonus_level(2));
+    assert_eq!(level_pack.unlocked_level_count(), 2);
+
+    assert!(level_pack.has_undiscovered_secret(0));
+    assert!(!level_pack.has_undiscovered_secret(1));
+    assert!(!level_pack.has_undiscovered_secret(2));
+
+    assert!(level_pack.set_secret_found(0));
+    assert!(!level_pack.is_bonus_level_locked());
+    assert!(!level_pack.has_undiscovered_secret(0));
+    assert_eq!(level_pack.unlocked_level_count(), 3);
+
+    let mut written_level_pack_data = Vec::new();
+    level_pack.write_level_pack(&mut written_level_pack_data, false).unwrap();
+    assert!(String::from_utf8(written_level_pack_data).unwrap().starts_with("Min Game Version: 1.1\nName: test\nBonus Level: collect-all-secrets\n"));
+
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", "Bonus Level: unknown\nLevels: 1\n\nw: 3, h: 1\np@x", false).is_err());
+
+    //Level packs with a single level have no bonus level
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test.lvl", "Bonus Level: collect-all-secrets\nLevels: 1\n\nw: 4, h: 1\np@xs", false).unwrap();
+    assert_eq!(level_pack.bonus_level_index(), None);
+
+    //Secrets are tracked for level packs without a bonus level
+    assert!(level_pack.has_undiscovered_secret(0));
+    assert!(!level_pack.set_secret_found(0));
+    assert!(!level_pack.has_undiscovered_secret(0));
+    assert!(!level_pack.is_bonus_level_locked());
+}
+
+#[test]
+fn level_pack_save_game_secrets_of_older_versions() {
+    let level_pack_data = "Levels: 2\n\nw: 4, h: 1\np@xs\n\nw: 4, h: 1\np@xs\n";
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test.lvl", level_pack_data, false).unwrap();
+
+    let (found_secret_level_indices, are_all_secrets_tracked) = level_pack.read_save_game_secrets(&["2", "ms-1,5", "secrets:tracked;1;7"]);
+    assert_eq!(found_secret_level_indices.into_iter().collect::<Vec<_>>(), [1]);
+    assert!(are_all_secrets_tracked);
+
+    //Older versions did not track secrets of level packs without a bonus level
+    let (found_secret_level_indices, are_all_secrets_tracked) = level_pack.read_save_game_secrets(&["2", "ms-1,5"]);
+    assert!(found_secret_level_indices.is_empty());
+    assert!(!are_all_secrets_tracked);
+
+    let (_, are_all_secrets_tracked) = level_pack.read_save_game_secrets(&["2", "ms-1,5", "secrets:1"]);
+    assert!(!are_all_secrets_tracked);
+
+    //Undiscovered secrets are shown after the stats were reset
+    level_pack.are_all_secrets_tracked = false;
+    assert!(!level_pack.has_undiscovered_secret(0));
+    level_pack.clear_found_secrets();
+    assert!(level_pack.has_undiscovered_secret(0));
+
+    //Older versions tracked secrets of level packs with a bonus level
+    let level_pack_data = format!("Bonus Level: collect-all-secrets\n{level_pack_data}");
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", &level_pack_data, false).unwrap();
+    let (_, are_all_secrets_tracked) = level_pack.read_save_game_secrets(&["2", "ms-1,5"]);
+    assert!(are_all_secrets_tracked);
+}
+
+#[test]
+fn level_pack_required_workshop_ids() {
+    let level_pack_data = "Requires: 3100000001\nRequires: 3100000002\nLevels: 1\n\nw: 3, h: 1\np@x\n";
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test.lvl", level_pack_data, false).unwrap();
+    assert_eq!(level_pack.required_workshop_ids(), [3100000001, 3100000002]);
+
+    assert_eq!(level_pack.toggle_required_workshop_id(3100000001), Some(false));
+    assert_eq!(level_pack.toggle_required_workshop_id(3100000003), Some(true));
+
+    let mut written_level_pack_data = Vec::new();
+    level_pack.write_level_pack(&mut written_level_pack_data, false).unwrap();
+    assert!(String::from_utf8(written_level_pack_data).unwrap().contains("Requires: 3100000002\nRequires: 3100000003\nLevels: 1\n"));
+
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", "Requires: abc\nLevels: 1\n\nw: 3, h: 1\np@x", false).is_err());
+}
+
+#[test]
+fn level_pack_intro_and_outro() {
+    let level_pack_data = "Description: Text\nIntro: Once upon a time\nIntro:\nOutro: The end\nLevels: 1\n\nw: 3, h: 1\np@x\n";
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test.lvl", level_pack_data, false).unwrap();
+    assert_eq!(level_pack.story(PackStory::Intro), ["Once upon a time", ""]);
+    assert_eq!(level_pack.story(PackStory::Outro), ["The end"]);
+
+    level_pack.set_story(PackStory::Intro, Vec::new());
+
+    let mut written_level_pack_data = Vec::new();
+    level_pack.write_level_pack(&mut written_level_pack_data, false).unwrap();
+    assert!(String::from_utf8(written_level_pack_data).unwrap().contains("Description: Text\nOutro: The end\nLevels: 1\n"));
+
+    let level_pack_data = format!("Intro: {}\nLevels: 1\n\nw: 3, h: 1\np@x", "a".repeat(LevelPack::MAX_STORY_LINE_LEN + 1));
+    assert!(LevelPack::from_level_pack_data("test", "test.lvl", &level_pack_data, false).is_err());
+}
+
+#[test]
+fn unused_level_pack_id_with_suffix() {
+    assert_eq!(LevelPack::unused_level_pack_id("pack", |id| id == "pack"), Some("pack_2".to_string()));
+    assert_eq!(LevelPack::unused_level_pack_id("pack", |id| ["pack_2", "pack_3"].contains(&id)), Some("pack_4".to_string()));
+
+    let long_id = "a".repeat(LevelPack::MAX_LEVEL_PACK_NAME_LEN);
+    assert_eq!(LevelPack::unused_level_pack_id(&long_id, |_| false), Some(format!("{}_2", &long_id[..LevelPack::MAX_LEVEL_PACK_NAME_LEN - 2])));
+
+    assert_eq!(LevelPack::unused_level_pack_id("pack", |_| true), None);
+}
+
+#[test]
+fn level_pack_restore_previous_best() {
+    let mut level_pack = LevelPack::new("Test", "test", "test.lvl");
+    level_pack.add_level(Level::from_str("w: 3, h: 1\np-x").unwrap());
+
+    //The first completion is not a record which can be undone
+    level_pack.update_stats(0, Some(5000), 20);
+    assert_eq!(level_pack.levels()[0].previous_best(), None);
+
+    level_pack.update_stats(0, Some(6000), 20);
+    assert_eq!(level_pack.levels()[0].previous_best(), None);
+
+    level_pack.update_stats(0, Some(3000), 20);
+    assert_eq!(level_pack.levels()[0].previous_best(), Some((Some(5000), 20)));
+
+    assert_eq!(level_pack.restore_previous_best(0), Some(()));
+    assert_eq!(level_pack.levels()[0].best_time(), Some(5000));
+    assert_eq!(level_pack.levels()[0].best_moves(), Some(20));
+    assert_eq!(level_pack.level_pack_best_time_sum(), Some(5000));
+    assert_eq!(level_pack.restore_previous_best(0), None);
+}
+
+#[test]
+fn level_pack_save_game_without_checksums() {
+    let mut level_pack = LevelPack::new("Test", "test", "test.lvl");
+    level_pack.add_level(Level::from_str("w: 3, h: 1\np-x").unwrap());
+    level_pack.add_level(Level::from_str("w: 3, h: 1\np-x").unwrap());
+
+    let checksum_key = checksum::ChecksumKey::new([3; 32]);
+    let signature = format!(
+        "sig:{:016x};{:016x}",
+        level_pack.save_game_line_checksum(&checksum_key, 0, "ms5000,20"),
+        level_pack.save_game_line_checksum(&checksum_key, 1, "ms6000,30"),
+    );
+
+    let signed_lines = ["2", "ms5000,20", "ms6000,30", signature.as_str()];
+    let level_stats = level_pack.read_save_game_level_stats(&signed_lines, false, &checksum_key, true);
+    assert_eq!(level_stats[0], (Some(5000), Some(20), true));
+    assert_eq!(level_stats[1], (Some(6000), Some(30), true));
+
+    //Save game of an older version
+    let unsigned_lines = ["2", "ms5000,20", "ms6000,30"];
+    let level_stats = level_pack.read_save_game_level_stats(&unsigned_lines, false, &checksum_key, false);
+    assert_eq!(level_stats[0], (Some(5000), Some(20), true));
+    assert_eq!(level_stats[1], (Some(6000), Some(30), true));
+
+    //The "sig:" line was removed from a signed save game
+    let level_stats = level_pack.read_save_game_level_stats(&unsigned_lines, false, &checksum_key, true);
+    assert_eq!(level_stats[0], (Some(5000), Some(20), false));
+    assert_eq!(level_stats[1], (Some(6000), Some(30), false));
+
+    //The "sig:" line was shortened
+    let short_signature = signature.split(";").next().unwrap();
+    let short_signature_lines = ["2", "ms5000,20", "ms6000,30", short_signature];
+    let level_stats = level_pack.read_save_game_level_stats(&short_signature_lines, false, &checksum_key, true);
+    assert_eq!(level_stats[0], (Some(5000), Some(20), true));
+    assert_eq!(level_stats[1], (Some(6000), Some(30), false));
+}
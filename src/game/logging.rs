@@ -0,0 +1,160 @@
+use std::fmt::{Arguments, Display, Formatter};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::game::save_folder;
+use crate::utils;
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the log file in the base save game folder
+pub const LOG_FILE_NAME: &str = "sokoterm.log";
+
+/// The log file is rotated if it is larger than this size (In bytes)
+pub const MAX_LOG_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Rotated log files are named "sokoterm.1.log" (Newest) to "sokoterm.3.log" (Oldest)
+pub const MAX_ROTATED_LOG_FILE_COUNT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => LogLevel::Error,
+            1 => LogLevel::Warn,
+            2 => LogLevel::Info,
+            _ => LogLevel::Debug,
+        }
+    }
+}
+
+impl Display for LogLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+        })
+    }
+}
+
+/// Entries with a less important level than this level are not written to the log file
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Warn as u8);
+
+/// Prevents interleaved entries and concurrent rotations if multiple threads are logging at once
+static LOG_FILE_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn max_level() -> LogLevel {
+    LogLevel::from_u8(MAX_LEVEL.load(Ordering::Relaxed))
+}
+
+pub fn set_max_level(level: LogLevel) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Writes an entry to the log file (And to the Bevy log in the GUI version)
+///
+/// Errors while writing the log file are ignored, because there is nowhere left to report them to.
+/// Use the `log_error!`, `log_warn!`, `log_info!`, and `log_debug!` macros instead of calling this function directly.
+pub fn log(level: LogLevel, args: Arguments) {
+    #[cfg(feature = "gui")]
+    match level {
+        LogLevel::Error => bevy::log::error!("{args}"),
+        LogLevel::Warn => bevy::log::warn!("{args}"),
+        LogLevel::Info => bevy::log::info!("{args}"),
+        LogLevel::Debug => bevy::log::debug!("{args}"),
+    }
+
+    if level > max_level() || cfg!(test) {
+        return;
+    }
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let _ = write_log_entry(&format!("{} UTC [{}] {}\n", utils::unix_time_to_date_time_string(timestamp), level, args));
+}
+
+fn write_log_entry(entry: &str) -> std::io::Result<()> {
+    let _lock = LOG_FILE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let folder = save_folder::base_save_game_folder().map_err(|err| std::io::Error::other(err.to_string()))?;
+    let folder = Path::new(&folder);
+    std::fs::create_dir_all(folder)?;
+
+    let log_file = folder.join(LOG_FILE_NAME);
+    if std::fs::metadata(&log_file).is_ok_and(|metadata| metadata.len() >= MAX_LOG_FILE_SIZE) {
+        rotate_log_files(folder)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(log_file)?;
+    file.write_all(entry.as_bytes())
+}
+
+fn rotated_log_file_name(index: usize) -> String {
+    format!("sokoterm.{index}.log")
+}
+
+/// Moves the current log file to "sokoterm.1.log", all older log files are moved by one and the oldest log file is removed
+fn rotate_log_files(folder: &Path) -> std::io::Result<()> {
+    let oldest_log_file = folder.join(rotated_log_file_name(MAX_ROTATED_LOG_FILE_COUNT));
+    if std::fs::exists(&oldest_log_file)? {
+        std::fs::remove_file(oldest_log_file)?;
+    }
+
+    for i in (1..MAX_ROTATED_LOG_FILE_COUNT).rev() {
+        let log_file = folder.join(rotated_log_file_name(i));
+        if std::fs::exists(&log_file)? {
+            std::fs::rename(log_file, folder.join(rotated_log_file_name(i + 1)))?;
+        }
+    }
+
+    let log_file = folder.join(LOG_FILE_NAME);
+    if std::fs::exists(&log_file)? {
+        std::fs::rename(log_file, folder.join(rotated_log_file_name(1)))?;
+    }
+
+    Ok(())
+}
+
+//Only used by the game itself (Not in builds without feature `cli` or `gui`)
+#[allow(unused_macros)]
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::game::logging::log($crate::game::logging::LogLevel::Error, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::game::logging::log($crate::game::logging::LogLevel::Warn, format_args!($($arg)*))
+    };
+}
+
+//Only used by the game itself (Not in builds without feature `cli` or `gui`)
+#[allow(unused_macros)]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::game::logging::log($crate::game::logging::LogLevel::Info, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        $crate::game::logging::log($crate::game::logging::LogLevel::Debug, format_args!($($arg)*))
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {log_error, log_warn, log_info, log_debug};
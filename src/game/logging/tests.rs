@@ -0,0 +1,24 @@
+use crate::game::logging::*;
+
+#[test]
+fn log_file_rotation() {
+    let folder = std::env::temp_dir().join(format!("sokoterm-logging-test-{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    std::fs::write(folder.join(LOG_FILE_NAME), "current").unwrap();
+    for i in 1..=MAX_ROTATED_LOG_FILE_COUNT {
+        std::fs::write(folder.join(rotated_log_file_name(i)), format!("old {i}")).unwrap();
+    }
+
+    rotate_log_files(&folder).unwrap();
+
+    assert!(!std::fs::exists(folder.join(LOG_FILE_NAME)).unwrap());
+    assert_eq!(std::fs::read_to_string(folder.join(rotated_log_file_name(1))).unwrap(), "current");
+    assert_eq!(std::fs::read_to_string(folder.join(rotated_log_file_name(2))).unwrap(), "old 1");
+    assert_eq!(
+        std::fs::read_to_string(folder.join(rotated_log_file_name(MAX_ROTATED_LOG_FILE_COUNT))).unwrap(),
+        format!("old {}", MAX_ROTATED_LOG_FILE_COUNT - 1),
+    );
+    assert!(!std::fs::exists(folder.join(rotated_log_file_name(MAX_ROTATED_LOG_FILE_COUNT + 1))).unwrap());
+
+    std::fs::remove_dir_all(folder).unwrap();
+}
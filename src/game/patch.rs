@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::game::{Game, GameError};
+use crate::game::level::LevelPack;
+use crate::game::solver::{self, LevelProblem};
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the folder in the base save game folder which contains patches of built-in level packs (Shared by all profiles)
+pub const PATCH_FOLDER_NAME: &str = "Patches";
+
+/// First line of a patch file, the level pack data follows in the next lines
+const PATCH_REVISION_PREFIX: &str = "Patch Revision: ";
+
+/// A level pack which is included in the game
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltInLevelPack {
+    pub id: &'static str,
+    pub data: &'static str,
+    /// Must be increased if the levels are changed, patches with a lower or equal revision are ignored afterward
+    pub revision: u32,
+}
+
+impl BuiltInLevelPack {
+    pub fn path(&self) -> String {
+        format!("built-in:{}", self.id)
+    }
+}
+
+/// The source from which a built-in level pack was loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelPackSource {
+    BuiltIn {
+        revision: u32,
+    },
+    Patch {
+        revision: u32,
+        path: String,
+    },
+}
+
+impl Display for LevelPackSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelPackSource::BuiltIn { revision } => write!(f, "Built-in (Rev. {})", revision),
+            LevelPackSource::Patch { revision, .. } => write!(f, "Patch (Rev. {})", revision),
+        }
+    }
+}
+
+/// Splits a patch file into the patch revision and the level pack data
+pub fn parse_patch_data(patch_data: &str) -> Result<(u32, &str), GameError> {
+    let (first_line, level_pack_data) = patch_data.split_once('\n').unwrap_or((patch_data, ""));
+
+    let Some(revision) = first_line.trim().strip_prefix(PATCH_REVISION_PREFIX) else {
+        return Err(GameError::new(format!("The patch must start with \"{}<revision>\"", PATCH_REVISION_PREFIX)));
+    };
+
+    let revision = u32::from_str(revision.trim()).map_err(|_| GameError::new(format!(
+        "Invalid patch revision \"{}\"", revision.trim(),
+    )))?;
+
+    Ok((revision, level_pack_data))
+}
+
+/// Checks if all levels of the patched level pack can be played
+pub fn validate_patch(level_pack: &LevelPack) -> Result<(), GameError> {
+    if level_pack.level_count() == 0 || level_pack.level_count() > LevelPack::MAX_LEVEL_COUNT_PER_PACK {
+        return Err(GameError::new(format!(
+            "Invalid level count ({}, max: {})",
+            level_pack.level_count(), LevelPack::MAX_LEVEL_COUNT_PER_PACK,
+        )));
+    }
+
+    //Problems such as unused keys are allowed, because they do not prevent the level from being completed
+    let blocking_problem = solver::find_level_pack_problems(level_pack).into_iter().
+            find(|(_, problem)| matches!(
+                problem,
+                LevelProblem::NoPlayer | LevelProblem::TooManyPlayers(_) | LevelProblem::InvalidStartMarker |
+                LevelProblem::TooFewBoxes { .. } | LevelProblem::TooLarge { .. },
+            ));
+    if let Some((level_index, problem)) = blocking_problem {
+        return Err(GameError::new(format!("Level {}: {}", level_index + 1, problem)));
+    }
+
+    Ok(())
+}
+
+/// Returns the path of the patch file of the built-in level pack
+pub fn patch_file_path(level_pack_id: &str) -> Result<OsString, Box<dyn Error>> {
+    let mut path = Game::get_or_create_base_save_game_folder()?;
+    path.push(PATCH_FOLDER_NAME);
+    path.push("/");
+    path.push(level_pack_id);
+    path.push(".lvl");
+
+    Ok(path)
+}
+
+/// Returns the data of the newest valid source of the built-in level pack
+///
+/// Patches are only used if their revision is newer than the built-in revision,
+/// invalid patches are ignored and returned as warning instead
+pub fn select_level_pack_data(built_in_level_pack: &BuiltInLevelPack) -> (String, LevelPackSource, Option<String>) {
+    let built_in = (
+        built_in_level_pack.data.to_string(),
+        LevelPackSource::BuiltIn { revision: built_in_level_pack.revision },
+        None,
+    );
+
+    let Ok(path) = patch_file_path(built_in_level_pack.id) else {
+        return built_in;
+    };
+
+    if !std::fs::exists(&path).is_ok_and(|exists| exists) {
+        return built_in;
+    }
+
+    let path_display = path.to_string_lossy().into_owned();
+    let patch = std::fs::read_to_string(&path).
+            map_err(|err| GameError::new(err.to_string())).
+            and_then(|patch_data| {
+                let (revision, level_pack_data) = parse_patch_data(&patch_data)?;
+                if revision <= built_in_level_pack.revision {
+                    return Ok(None);
+                }
+
+                let level_pack = LevelPack::from_level_pack_data(
+                    built_in_level_pack.id, built_in_level_pack.path(), level_pack_data, false,
+                ).map_err(|err| GameError::new(err.to_string()))?;
+                validate_patch(&level_pack)?;
+
+                Ok(Some((revision, level_pack_data.to_string())))
+            });
+
+    match patch {
+        Ok(Some((revision, level_pack_data))) => (
+            level_pack_data,
+            LevelPackSource::Patch { revision, path: path_display },
+            None,
+        ),
+
+        //The patch is already included in the built-in level pack
+        Ok(None) => built_in,
+
+        Err(err) => {
+            let (data, source, _) = built_in;
+
+            (data, source, Some(format!(
+                "Ignoring patch \"{}\" of built-in level pack \"{}\":\n{}",
+                path_display, built_in_level_pack.id, err,
+            )))
+        },
+    }
+}
@@ -0,0 +1,21 @@
+use crate::game::level::LevelPack;
+use crate::game::patch::*;
+
+#[test]
+fn parse_patch_data_revision() {
+    let (revision, level_pack_data) = parse_patch_data("Patch Revision: 3\nName: Main\nLevels: 0").unwrap();
+    assert_eq!(revision, 3);
+    assert_eq!(level_pack_data, "Name: Main\nLevels: 0");
+
+    assert!(parse_patch_data("Name: Main\nLevels: 0").is_err());
+    assert!(parse_patch_data("Patch Revision: x\nLevels: 0").is_err());
+}
+
+#[test]
+fn validate_patch_rejects_unplayable_levels() {
+    let level_pack = LevelPack::from_level_pack_data("main", "built-in:main", "Levels: 1\n\nw: 4, h: 1\np@-x", false).unwrap();
+    assert!(validate_patch(&level_pack).is_ok());
+
+    let level_pack = LevelPack::from_level_pack_data("main", "built-in:main", "Levels: 1\n\nw: 4, h: 1\np--x", false).unwrap();
+    assert!(validate_patch(&level_pack).is_err());
+}
@@ -0,0 +1,124 @@
+use std::any::Any;
+use crate::game::events::GameEventListener;
+
+#[cfg(test)]
+mod tests;
+
+/// Optional subsystems of the game which register their event hooks at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluginId {
+    Telemetry,
+    PlayerStats,
+    TotalStats,
+    Credits,
+    RichPresence,
+    SteamAchievements,
+}
+
+impl PluginId {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            PluginId::Telemetry => "Anonymous level stats",
+            PluginId::PlayerStats => "Level suggestions",
+            PluginId::TotalStats => "Local achievements",
+            PluginId::Credits => "Credits",
+            PluginId::RichPresence => "Steam rich presence",
+            PluginId::SteamAchievements => "Steam achievements",
+        }
+    }
+}
+
+/// Event listener which can be accessed by its type (Plugins own the state of their subsystem)
+pub trait Plugin: GameEventListener + Any {}
+
+impl<T: GameEventListener + Any> Plugin for T {}
+
+struct RegisteredPlugin {
+    id: PluginId,
+    is_enabled: bool,
+    listener: Box<dyn Plugin>,
+}
+
+/// Registry of the plugins of the game state (No dynamic loading, plugins are registered depending on features and settings)
+///
+/// Events are only dispatched to enabled plugins in the order in which the plugins were registered.
+/// Toasts are not a plugin, they are the notifications of the game state which are shown by plugins (e.g. unlocked local achievements).
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Vec<RegisteredPlugin>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the plugin, a plugin with the same ID is replaced
+    pub fn register(&mut self, id: PluginId, is_enabled: bool, listener: impl Plugin) {
+        self.plugins.retain(|plugin| plugin.id != id);
+
+        self.plugins.push(RegisteredPlugin {
+            id,
+            is_enabled,
+            listener: Box::new(listener),
+        });
+    }
+
+    pub fn is_registered(&self, id: PluginId) -> bool {
+        self.plugins.iter().any(|plugin| plugin.id == id)
+    }
+
+    pub fn is_enabled(&self, id: PluginId) -> bool {
+        self.plugins.iter().any(|plugin| plugin.id == id && plugin.is_enabled)
+    }
+
+    /// Enables or disables the plugin at runtime, false is returned if the plugin is not registered
+    pub fn set_enabled(&mut self, id: PluginId, is_enabled: bool) -> bool {
+        let Some(plugin) = self.plugins.iter_mut().find(|plugin| plugin.id == id) else {
+            return false;
+        };
+
+        if plugin.is_enabled && !is_enabled {
+            plugin.listener.on_disabled();
+        }
+        plugin.is_enabled = is_enabled;
+
+        true
+    }
+
+    /// Returns the plugin if it is enabled and has the type `T`
+    pub fn enabled_plugin<T: Plugin>(&self, id: PluginId) -> Option<&T> {
+        self.plugins.iter().
+                find(|plugin| plugin.id == id && plugin.is_enabled).
+                and_then(|plugin| (plugin.listener.as_ref() as &dyn Any).downcast_ref())
+    }
+
+    /// Returns the plugin if it is enabled and has the type `T`
+    pub fn enabled_plugin_mut<T: Plugin>(&mut self, id: PluginId) -> Option<&mut T> {
+        self.plugins.iter_mut().
+                find(|plugin| plugin.id == id && plugin.is_enabled).
+                and_then(|plugin| (plugin.listener.as_mut() as &mut dyn Any).downcast_mut())
+    }
+
+    /// Returns the IDs of all registered plugins in the order of registration
+    pub fn ids(&self) -> impl Iterator<Item = PluginId> {
+        self.plugins.iter().map(|plugin| plugin.id)
+    }
+
+    /// Calls the function for all enabled plugins
+    pub fn for_each_enabled(&mut self, mut func: impl FnMut(&mut dyn GameEventListener)) {
+        for plugin in self.plugins.iter_mut().
+                filter(|plugin| plugin.is_enabled) {
+            func(plugin.listener.as_mut());
+        }
+    }
+
+    /// Adds all plugins of the other registry which are not registered in this registry
+    pub fn append(&mut self, other: PluginRegistry) {
+        for plugin in other.plugins {
+            if !self.is_registered(plugin.id) {
+                self.plugins.push(plugin);
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use crate::game::plugins::*;
+
+struct DisableCounter(Rc<Cell<usize>>);
+
+impl GameEventListener for DisableCounter {
+    fn on_disabled(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn plugin_registry_enable_and_disable() {
+    let disable_count = Rc::new(Cell::new(0));
+
+    let mut plugin_registry = PluginRegistry::new();
+    plugin_registry.register(PluginId::Telemetry, true, DisableCounter(Rc::clone(&disable_count)));
+    plugin_registry.register(PluginId::Credits, false, DisableCounter(Rc::clone(&disable_count)));
+    assert!(plugin_registry.is_enabled(PluginId::Telemetry));
+    assert!(!plugin_registry.is_enabled(PluginId::Credits));
+    assert!(!plugin_registry.is_registered(PluginId::RichPresence));
+
+    let mut enabled_count = 0;
+    plugin_registry.for_each_enabled(|_| enabled_count += 1);
+    assert_eq!(enabled_count, 1);
+
+    assert!(plugin_registry.set_enabled(PluginId::Telemetry, false));
+    assert!(plugin_registry.set_enabled(PluginId::Telemetry, false));
+    assert!(!plugin_registry.set_enabled(PluginId::RichPresence, true));
+    assert_eq!(disable_count.get(), 1);
+
+    //Plugins are replaced if they are registered again
+    plugin_registry.register(PluginId::Credits, true, DisableCounter(Rc::clone(&disable_count)));
+    assert_eq!(plugin_registry.ids().collect::<Vec<_>>(), [PluginId::Telemetry, PluginId::Credits]);
+    assert!(plugin_registry.is_enabled(PluginId::Credits));
+}
+
+#[test]
+fn plugin_registry_enabled_plugin() {
+    let disable_count = Rc::new(Cell::new(0));
+
+    let mut plugin_registry = PluginRegistry::new();
+    plugin_registry.register(PluginId::Telemetry, true, DisableCounter(Rc::clone(&disable_count)));
+    assert!(plugin_registry.enabled_plugin::<DisableCounter>(PluginId::Telemetry).is_some());
+    assert!(plugin_registry.enabled_plugin::<DisableCounter>(PluginId::Credits).is_none());
+
+    //Plugins of a different type are not returned
+    struct OtherPlugin;
+    impl GameEventListener for OtherPlugin {}
+    assert!(plugin_registry.enabled_plugin::<OtherPlugin>(PluginId::Telemetry).is_none());
+
+    plugin_registry.enabled_plugin_mut::<DisableCounter>(PluginId::Telemetry).unwrap().0.set(5);
+    assert_eq!(disable_count.get(), 5);
+
+    //Disabled plugins are not returned
+    plugin_registry.set_enabled(PluginId::Telemetry, false);
+    assert!(plugin_registry.enabled_plugin::<DisableCounter>(PluginId::Telemetry).is_none());
+}
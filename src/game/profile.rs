@@ -0,0 +1,208 @@
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+use crate::game::{Game, GameError};
+use crate::game::settings_file::{self, SettingsFile, SettingsOption, SettingsValue};
+
+#[cfg(test)]
+mod tests;
+
+/// The name of the active profile, None is the default profile which uses the save game folder directly
+static ACTIVE_PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+pub const DEFAULT_PROFILE_DISPLAY_NAME: &str = "Default";
+
+pub const MIN_PROFILE_NAME_LEN: usize = 3;
+pub const MAX_PROFILE_NAME_LEN: usize = 16;
+
+/// Max count of non-default profiles
+pub const MAX_PROFILE_COUNT: usize = 12;
+
+/// Name of the folder in the base save game folder which contains a sub folder for every non-default profile
+pub const PROFILES_FOLDER: &str = "profiles";
+
+const PROFILE_DATA_FILE_NAME: &str = "profile.data";
+
+/// Returns the name of the active profile or None if the default profile is active
+pub fn active_profile() -> Option<String> {
+    ACTIVE_PROFILE.read().unwrap().clone()
+}
+
+/// Sets the active profile, all save game paths which are created afterward point into the folder of the new profile
+pub fn set_active_profile(profile: Option<String>) {
+    *ACTIVE_PROFILE.write().unwrap() = profile;
+}
+
+pub fn display_name(profile: Option<&str>) -> &str {
+    profile.unwrap_or(DEFAULT_PROFILE_DISPLAY_NAME)
+}
+
+pub fn is_valid_profile_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Checks if the name can be used for a new profile (The name of the default profile is reserved)
+pub fn is_valid_profile_name(name: &str) -> bool {
+    (MIN_PROFILE_NAME_LEN..=MAX_PROFILE_NAME_LEN).contains(&name.len()) &&
+            name.chars().all(is_valid_profile_name_char) &&
+            !name.eq_ignore_ascii_case(DEFAULT_PROFILE_DISPLAY_NAME)
+}
+
+/// Returns the sorted names of all non-default profiles
+pub fn profile_names() -> Result<Vec<String>, Box<dyn Error>> {
+    let mut profiles_folder = Game::get_or_create_base_save_game_folder()?;
+    profiles_folder.push(PROFILES_FOLDER);
+
+    if !std::fs::exists(&profiles_folder)? {
+        return Ok(Vec::new());
+    }
+
+    let mut profile_names = std::fs::read_dir(profiles_folder)?.
+            filter_map(|entry| entry.ok()).
+            filter(|entry| entry.path().is_dir()).
+            filter_map(|entry| entry.file_name().into_string().ok()).
+            filter(|name| name.chars().all(is_valid_profile_name_char)).
+            collect::<Vec<_>>();
+    profile_names.sort();
+
+    Ok(profile_names)
+}
+
+pub fn create_profile(name: &str) -> Result<(), Box<dyn Error>> {
+    if !is_valid_profile_name(name) {
+        return Err(Box::new(GameError::new(format!("The profile name \"{}\" is invalid!", name))));
+    }
+
+    let profile_names = profile_names()?;
+    if profile_names.iter().any(|profile_name| profile_name.eq_ignore_ascii_case(name)) {
+        return Err(Box::new(GameError::new(format!("The profile \"{}\" already exists!", name))));
+    }
+
+    if profile_names.len() >= MAX_PROFILE_COUNT {
+        return Err(Box::new(GameError::new(format!("Cannot create new profiles (Max profile count ({}) reached)", MAX_PROFILE_COUNT))));
+    }
+
+    let mut profile_folder = Game::get_or_create_base_save_game_folder()?;
+    profile_folder.push(PROFILES_FOLDER);
+    profile_folder.push("/");
+    profile_folder.push(name);
+    std::fs::create_dir_all(&profile_folder)?;
+
+    Ok(())
+}
+
+/// Data which is shared between all profiles, it is stored in the base save game folder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileData {
+    last_profile: Option<String>,
+    steam_account_id: Option<u64>,
+
+    /// Options of other versions of the game which are kept for saving
+    unknown_options: Vec<(String, SettingsValue)>,
+}
+
+/// Schema of the profile data file
+const PROFILE_DATA_OPTIONS: &[SettingsOption<ProfileData>] = &[
+    SettingsOption {
+        key: "last_profile",
+        read: |data, value| {
+            if value.is_empty() || !value.chars().all(is_valid_profile_name_char) {
+                return false;
+            }
+
+            data.last_profile = Some(value.to_string());
+
+            true
+        },
+        write: |data| data.last_profile.clone().map(SettingsValue::String),
+    },
+    SettingsOption {
+        key: "steam_account_id",
+        read: |data, value| u64::from_str(value).map(|value| data.steam_account_id = Some(value)).is_ok(),
+        write: |data| data.steam_account_id.and_then(|value| usize::try_from(value).ok()).map(SettingsValue::Int),
+    },
+];
+
+impl ProfileData {
+    pub fn read_from_file() -> Result<Self, Box<dyn Error>> {
+        let mut profile_data_file = Game::get_or_create_base_save_game_folder()?;
+        profile_data_file.push(PROFILE_DATA_FILE_NAME);
+
+        if !std::fs::exists(&profile_data_file)? {
+            return Ok(Self::default());
+        }
+
+        Ok(Self::from_data(&std::fs::read_to_string(&profile_data_file)?))
+    }
+
+    fn from_data(profile_data: &str) -> Self {
+        let mut data = Self::default();
+
+        let settings_file = SettingsFile::parse(PROFILE_DATA_FILE_NAME, profile_data);
+        data.unknown_options = settings_file.apply(PROFILE_DATA_FILE_NAME, &mut data, PROFILE_DATA_OPTIONS);
+
+        data
+    }
+
+    pub fn save_to_file(&self) -> Result<(), Box<dyn Error>> {
+        let mut profile_data_file = Game::get_or_create_base_save_game_folder()?;
+        profile_data_file.push(PROFILE_DATA_FILE_NAME);
+
+        settings_file::write_file_atomically(Path::new(&profile_data_file), &self.to_data())?;
+
+        Ok(())
+    }
+
+    fn to_data(&self) -> String {
+        SettingsFile::from_schema(self, PROFILE_DATA_OPTIONS, &self.unknown_options).to_str()
+    }
+
+    pub fn last_profile(&self) -> Option<&str> {
+        self.last_profile.as_deref()
+    }
+
+    pub fn set_last_profile(&mut self, last_profile: Option<String>) {
+        self.last_profile = last_profile;
+    }
+
+    /// Returns the profile of the Steam account
+    ///
+    /// The first Steam account which plays on this computer keeps the default profile (Save games of older versions),
+    /// all other accounts get their own profile
+    pub fn steam_account_profile(&mut self, steam_account_id: u64) -> Option<String> {
+        let first_steam_account_id = *self.steam_account_id.get_or_insert(steam_account_id);
+
+        (first_steam_account_id != steam_account_id).then(|| format!("steam_{steam_account_id}"))
+    }
+}
+
+/// Activates the profile which was used last
+#[cfg(not(feature = "steam"))]
+pub fn activate_last_profile() -> Result<(), Box<dyn Error>> {
+    let profile_data = ProfileData::read_from_file()?;
+
+    set_active_profile(profile_data.last_profile().map(str::to_string));
+
+    Ok(())
+}
+
+/// Stores the profile which will be activated on the next start
+#[cfg(not(feature = "steam"))]
+pub fn save_last_profile(profile: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let mut profile_data = ProfileData::read_from_file()?;
+
+    profile_data.set_last_profile(profile.map(str::to_string));
+
+    profile_data.save_to_file()
+}
+
+/// Activates the profile of the Steam account (There is no profile selection in the steam build)
+#[cfg(feature = "steam")]
+pub fn activate_steam_account_profile(steam_account_id: u64) -> Result<(), Box<dyn Error>> {
+    let mut profile_data = ProfileData::read_from_file()?;
+
+    set_active_profile(profile_data.steam_account_profile(steam_account_id));
+
+    profile_data.save_to_file()
+}
@@ -0,0 +1,46 @@
+use crate::game::profile::*;
+
+#[test]
+fn profile_name_validation() {
+    assert!(is_valid_profile_name("Alice"));
+    assert!(is_valid_profile_name("player_2-b"));
+
+    assert!(!is_valid_profile_name("ab"));
+    assert!(!is_valid_profile_name(&"a".repeat(MAX_PROFILE_NAME_LEN + 1)));
+    assert!(!is_valid_profile_name("../save"));
+    assert!(!is_valid_profile_name("default"));
+}
+
+#[test]
+fn profile_data_round_trip() {
+    let mut profile_data = ProfileData::default();
+    profile_data.set_last_profile(Some("Alice".to_string()));
+    assert_eq!(profile_data.steam_account_profile(42), None);
+
+    let profile_data = ProfileData::from_data(&profile_data.to_data());
+    assert_eq!(profile_data.last_profile(), Some("Alice"));
+    assert_eq!(profile_data.steam_account_id, Some(42));
+
+    assert_eq!(ProfileData::from_data("last_profile = ../x\nsteam_account_id = abc\n"), ProfileData::default());
+}
+
+#[test]
+fn profile_data_settings_file() {
+    //Files of older versions are not quoted
+    let profile_data = ProfileData::from_data("last_profile = Alice\nsteam_account_id = 42\n");
+    assert_eq!(profile_data.last_profile(), Some("Alice"));
+    assert_eq!(profile_data.steam_account_id, Some(42));
+
+    let profile_data = ProfileData::from_data("version = 1\nlast_profile = \"Alice\"\nfuture_option = true\n");
+    assert_eq!(profile_data.last_profile(), Some("Alice"));
+    assert_eq!(profile_data.to_data(), "version = 1\nlast_profile = \"Alice\"\nfuture_option = true\n");
+}
+
+#[test]
+fn profile_data_steam_account_profile() {
+    let mut profile_data = ProfileData::default();
+
+    assert_eq!(profile_data.steam_account_profile(1), None);
+    assert_eq!(profile_data.steam_account_profile(2), Some("steam_2".to_string()));
+    assert_eq!(profile_data.steam_account_profile(1), None);
+}
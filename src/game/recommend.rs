@@ -0,0 +1,258 @@
+use std::error::Error;
+use std::fmt::Write as _;
+use crate::game::Game;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::GameState;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::events::{GameEventListener, LevelCompletedEvent, LevelStartedEvent};
+use crate::game::level::{LevelPack, LevelWithStats};
+use crate::game::logging::log_warn;
+use crate::game::save_writer::SaveWriter;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::game::screen::dialog::Dialog;
+
+#[cfg(test)]
+mod tests;
+
+const PLAYER_STATS_FILE_NAME: &str = "player_stats.data";
+
+/// Solving metrics of the player which are used to suggest the next level and level pack
+///
+/// The difficulty of a level is estimated with its par moves (Or the moves of the author's validation run)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlayerStats {
+    completions: u32,
+    restarts: u32,
+    //Sum of the estimated difficulty of all completed levels
+    difficulty_sum: u64,
+
+    //Completions with a time of levels with a par time
+    par_time_completions: u32,
+    //Sum of time/par time of these completions in percent
+    time_vs_par_percent_sum: u64,
+}
+
+impl PlayerStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read_from_file() -> Result<Self, Box<dyn Error>> {
+        let mut player_stats_file = Game::get_or_create_save_game_folder()?;
+        player_stats_file.push(PLAYER_STATS_FILE_NAME);
+
+        if !std::fs::exists(&player_stats_file)? {
+            return Ok(Self::new());
+        }
+
+        Ok(Self::parse(&std::fs::read_to_string(&player_stats_file)?))
+    }
+
+    /// Parses the data of the player stats file, invalid lines are ignored
+    pub fn parse(data: &str) -> Self {
+        let mut player_stats = Self::new();
+
+        for line in data.split("\n").
+                filter(|line| !line.trim().is_empty()) {
+            let Some((key, value)) = line.split_once(" = ") else {
+                log_warn!("\"{PLAYER_STATS_FILE_NAME}\" contains invalid data: \"{line}\": Ignoring");
+
+                continue;
+            };
+
+            let value = value.trim();
+            let is_valid = match key {
+                "completions" => value.parse().map(|value| player_stats.completions = value).is_ok(),
+                "restarts" => value.parse().map(|value| player_stats.restarts = value).is_ok(),
+                "difficulty_sum" => value.parse().map(|value| player_stats.difficulty_sum = value).is_ok(),
+                "par_time_completions" => value.parse().map(|value| player_stats.par_time_completions = value).is_ok(),
+                "time_vs_par_percent_sum" => value.parse().map(|value| player_stats.time_vs_par_percent_sum = value).is_ok(),
+
+                _ => false,
+            };
+
+            if !is_valid {
+                log_warn!("\"{PLAYER_STATS_FILE_NAME}\" contains invalid value for \"{key}\": \"{value}\": Ignoring");
+            }
+        }
+
+        player_stats
+    }
+
+    pub fn to_data(&self) -> String {
+        let mut data = String::new();
+
+        let _ = writeln!(data, "completions = {}", self.completions);
+        let _ = writeln!(data, "restarts = {}", self.restarts);
+        let _ = writeln!(data, "difficulty_sum = {}", self.difficulty_sum);
+        let _ = writeln!(data, "par_time_completions = {}", self.par_time_completions);
+        let _ = writeln!(data, "time_vs_par_percent_sum = {}", self.time_vs_par_percent_sum);
+
+        data
+    }
+
+    /// Queues the player stats to be written in the background (Write failures are reported by the save writer)
+    pub fn save_in_background(&self, save_writer: &SaveWriter) -> Result<(), Box<dyn Error>> {
+        let mut player_stats_file = Game::get_or_create_save_game_folder()?;
+        player_stats_file.push(PLAYER_STATS_FILE_NAME);
+
+        save_writer.write(player_stats_file, self.to_data());
+
+        Ok(())
+    }
+
+    /// Records a completed level, the time is None in relaxed mode
+    pub fn record_completion(&mut self, level: &LevelWithStats, moves: u32, time: Option<u64>) {
+        self.completions = self.completions.saturating_add(1);
+
+        //The moves of the player are used if the level pack author did not set any target moves
+        let difficulty = estimated_level_difficulty(level).unwrap_or(moves);
+        self.difficulty_sum = self.difficulty_sum.saturating_add(difficulty as u64);
+
+        if let Some(time) = time && let Some(par_time) = level.par_time() && par_time > 0 {
+            self.par_time_completions = self.par_time_completions.saturating_add(1);
+            self.time_vs_par_percent_sum = self.time_vs_par_percent_sum.saturating_add(time.saturating_mul(100) / par_time);
+        }
+    }
+
+    pub fn record_restart(&mut self) {
+        self.restarts = self.restarts.saturating_add(1);
+    }
+
+    pub fn completions(&self) -> u32 {
+        self.completions
+    }
+
+    /// Returns the average ratio of the completion time to the par time (1.0: Par time reached on average) or None if no level with a par time was completed
+    pub fn average_time_vs_par(&self) -> Option<f64> {
+        (self.par_time_completions > 0).
+                then(|| self.time_vs_par_percent_sum as f64 / self.par_time_completions as f64 / 100.0)
+    }
+
+    /// Returns the average count of restarts per completed level or None if no level was completed
+    pub fn restarts_per_completion(&self) -> Option<f64> {
+        (self.completions > 0).then(|| self.restarts as f64 / self.completions as f64)
+    }
+
+    /// Returns the difficulty of levels which match the demonstrated skill of the player or None if no level was completed
+    pub fn target_difficulty(&self) -> Option<u32> {
+        if self.completions == 0 {
+            return None;
+        }
+
+        let average_difficulty = self.difficulty_sum as f64 / self.completions as f64;
+
+        //Fast players are challenged with harder levels, slow players get easier levels
+        let mut factor = match self.average_time_vs_par() {
+            Some(time_vs_par) if time_vs_par <= 1.0 => 1.25,
+            Some(time_vs_par) if time_vs_par > 2.0 => 0.8,
+            _ => 1.0,
+        };
+
+        //Many restarts show that the levels are already hard enough
+        if self.restarts_per_completion().is_some_and(|restarts| restarts >= 2.0) {
+            factor *= 0.8;
+        }
+
+        Some((average_difficulty * factor).round() as u32)
+    }
+}
+
+/// Returns the estimated difficulty of the level or None if the level has no par moves and no author moves
+pub fn estimated_level_difficulty(level: &LevelWithStats) -> Option<u32> {
+    level.par_moves().or(level.author_moves())
+}
+
+/// Returns the index of the level which should be played next or None if all levels are completed
+///
+/// Only unlocked levels which were not completed (The next level and skipped levels) are suggested
+pub fn suggest_level(level_pack: &LevelPack, player_stats: &PlayerStats) -> Option<usize> {
+    let candidates = level_pack.levels().
+            iter().
+            enumerate().
+            take((level_pack.min_level_not_completed() + 1).min(level_pack.unlocked_level_count())).
+            filter(|(_, level)| level.best_moves().is_none()).
+            map(|(index, level)| (index, estimated_level_difficulty(level)));
+
+    closest_to_target(candidates, player_stats.target_difficulty())
+}
+
+/// Returns the index of the level pack which should be played next or None if all level packs are completed
+pub fn suggest_level_pack(level_packs: &[LevelPack], player_stats: &PlayerStats) -> Option<usize> {
+    let candidates = level_packs.iter().
+            enumerate().
+            filter_map(|(index, level_pack)| {
+                let level_index = suggest_level(level_pack, player_stats)?;
+
+                Some((index, estimated_level_difficulty(&level_pack.levels()[level_index])))
+            });
+
+    closest_to_target(candidates, player_stats.target_difficulty())
+}
+
+/// Returns the candidate with the difficulty closest to the target difficulty
+///
+/// The first candidate is returned if there is no target difficulty or if no candidate has a difficulty
+fn closest_to_target(candidates: impl IntoIterator<Item = (usize, Option<u32>)>, target_difficulty: Option<u32>) -> Option<usize> {
+    let candidates = candidates.into_iter().collect::<Vec<_>>();
+    let first_candidate = candidates.first().map(|(index, _)| *index);
+
+    let Some(target_difficulty) = target_difficulty else {
+        return first_candidate;
+    };
+
+    candidates.iter().
+            filter_map(|(index, difficulty)| difficulty.map(|difficulty| (*index, difficulty.abs_diff(target_difficulty)))).
+            min_by_key(|(_, distance)| *distance).
+            map(|(index, _)| index).
+            or(first_candidate)
+}
+
+/// Owns the player stats: Records the solving metrics of completed levels (Remixes are ignored, because they have no par values)
+#[cfg(any(feature = "cli", feature = "gui"))]
+#[derive(Debug, Default)]
+pub struct PlayerStatsPlugin {
+    player_stats: PlayerStats,
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl PlayerStatsPlugin {
+    pub fn new(player_stats: PlayerStats) -> Self {
+        Self { player_stats }
+    }
+
+    pub fn player_stats(&self) -> &PlayerStats {
+        &self.player_stats
+    }
+
+    fn save(&self, game_state: &mut GameState) {
+        if let Err(err) = self.player_stats.save_in_background(&game_state.save_writer) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save player stats: {}", err)));
+        }
+    }
+}
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+impl GameEventListener for PlayerStatsPlugin {
+    /// Restarts are recorded for the suggestions of the next level
+    fn on_level_started(&mut self, game_state: &mut GameState, event: &LevelStartedEvent) {
+        if event.is_restart {
+            self.player_stats.record_restart();
+            self.save(game_state);
+        }
+    }
+
+    fn on_level_completed(&mut self, game_state: &mut GameState, event: &LevelCompletedEvent) {
+        if event.remix_seed.is_some() {
+            return;
+        }
+
+        let Some(level) = game_state.level_packs.get(event.level_pack_index).
+                and_then(|level_pack| level_pack.levels().get(event.level_index)) else {
+            return;
+        };
+
+        self.player_stats.record_completion(level, event.moves, event.time);
+        self.save(game_state);
+    }
+}
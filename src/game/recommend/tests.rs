@@ -0,0 +1,81 @@
+use crate::game::level::{Level, LevelPack, LevelWithStats};
+use crate::game::recommend::*;
+
+fn level_with_par(par_moves: Option<u32>, par_time: Option<u64>) -> LevelWithStats {
+    let mut level = LevelWithStats::new(Level::new(3, 3), None, None);
+    level.set_par_moves(par_moves);
+    level.set_par_time(par_time);
+
+    level
+}
+
+#[test]
+fn target_difficulty_depends_on_time_vs_par_and_restarts() {
+    let mut player_stats = PlayerStats::new();
+    assert_eq!(player_stats.target_difficulty(), None);
+
+    //Par time reached: Harder levels are suggested
+    player_stats.record_completion(&level_with_par(Some(100), Some(10000)), 120, Some(8000));
+    assert_eq!(player_stats.average_time_vs_par(), Some(0.8));
+    assert_eq!(player_stats.target_difficulty(), Some(125));
+
+    //Without par values the moves of the player are used
+    player_stats.record_completion(&level_with_par(None, None), 300, Some(60000));
+    assert_eq!(player_stats.target_difficulty(), Some(250));
+
+    for _ in 0..4 {
+        player_stats.record_restart();
+    }
+    assert_eq!(player_stats.restarts_per_completion(), Some(2.0));
+    assert_eq!(player_stats.target_difficulty(), Some(200));
+
+    //Much slower than the par time: Easier levels are suggested
+    player_stats.record_completion(&level_with_par(Some(200), Some(10000)), 200, Some(50000));
+    assert_eq!(player_stats.average_time_vs_par(), Some(2.9));
+    assert_eq!(player_stats.target_difficulty(), Some(160));
+
+    assert_eq!(PlayerStats::parse(&player_stats.to_data()), player_stats);
+}
+
+#[test]
+fn parse_ignores_invalid_lines() {
+    let player_stats = PlayerStats::parse("completions = 3\nrestarts = -1\nunknown = 5\ninvalid\ndifficulty_sum = 90\n");
+
+    assert_eq!(player_stats.completions(), 3);
+    assert_eq!(player_stats.restarts_per_completion(), Some(0.0));
+    assert_eq!(player_stats.target_difficulty(), Some(30));
+}
+
+#[test]
+fn suggested_level_matches_target_difficulty() {
+    let mut level_pack = LevelPack::new("Test", "test", "test");
+    for par_moves in [10, 50, 20, 90, 30] {
+        level_pack.insert_level(level_pack.level_count(), level_with_par(Some(par_moves), None));
+    }
+
+    let mut player_stats = PlayerStats::new();
+    assert_eq!(suggest_level(&level_pack, &player_stats), Some(0));
+
+    level_pack.update_stats(0, None, 10);
+    level_pack.set_min_level_not_completed(1);
+    assert_eq!(suggest_level(&level_pack, &player_stats), Some(1));
+
+    //Level 2 was skipped and level 4 is locked
+    level_pack.set_min_level_not_completed(2);
+    player_stats.record_completion(&level_pack.levels()[0], 10, None);
+    player_stats.record_completion(&level_pack.levels()[2], 30, None);
+    assert_eq!(player_stats.target_difficulty(), Some(15));
+    assert_eq!(suggest_level(&level_pack, &player_stats), Some(2));
+
+    level_pack.update_stats(2, None, 20);
+    level_pack.set_min_level_not_completed(3);
+    assert_eq!(suggest_level(&level_pack, &player_stats), Some(1));
+
+    let mut completed_level_pack = LevelPack::new("Completed", "completed", "completed");
+    completed_level_pack.insert_level(0, level_with_par(Some(15), None));
+    completed_level_pack.update_stats(0, None, 15);
+    completed_level_pack.set_min_level_not_completed(1);
+    assert_eq!(suggest_level(&completed_level_pack, &player_stats), None);
+
+    assert_eq!(suggest_level_pack(&[completed_level_pack, level_pack], &player_stats), Some(1));
+}
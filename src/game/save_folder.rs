@@ -0,0 +1,184 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::RwLock;
+use crate::game::{Game, GameError};
+use crate::game::startup_flags;
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the save game folder next to the executable which is used in portable mode
+pub const PORTABLE_SAVE_GAME_FOLDER: &str = "SokoTermSaves";
+
+pub const MAX_SAVE_GAME_FOLDER_PATH_LEN: usize = 255;
+
+/// Name of the file in the default save game folder which contains the path of the relocated save game folder
+const LOCATION_FILE_NAME: &str = "save_folder_location.data";
+
+/// The resolved base save game folder (The location file is only read once)
+static BASE_SAVE_GAME_FOLDER: RwLock<Option<OsString>> = RwLock::new(None);
+
+/// Returns the base save game folder: The portable folder, the relocated folder, or the default folder
+///
+/// The folder is not created
+pub fn base_save_game_folder() -> Result<OsString, Box<dyn Error>> {
+    if let Some(directory) = BASE_SAVE_GAME_FOLDER.read().unwrap().clone() {
+        return Ok(directory);
+    }
+
+    let directory = if startup_flags::startup_flags().portable() {
+        portable_save_game_folder()?
+    }else if let Some(directory) = read_relocated_save_game_folder()? {
+        directory
+    }else {
+        default_save_game_folder()?
+    };
+
+    *BASE_SAVE_GAME_FOLDER.write().unwrap() = Some(directory.clone());
+
+    Ok(directory)
+}
+
+pub fn default_save_game_folder() -> Result<OsString, Box<dyn Error>> {
+    let mut directory = if cfg!(windows) {
+        std::env::var_os("USERPROFILE").
+                ok_or(GameError::new("%USERPROFILE% is not set!"))?
+    }else {
+        std::env::var_os("HOME").
+                ok_or(GameError::new("$HOME not set!"))?
+    };
+
+    directory.push("/.jddev0/");
+    directory.push(Game::SAVE_GAME_FOLDER);
+    directory.push("/");
+
+    Ok(directory)
+}
+
+pub fn portable_save_game_folder() -> Result<OsString, Box<dyn Error>> {
+    let executable = std::env::current_exe()?;
+    let mut directory = executable.parent().
+            ok_or_else(|| GameError::new("The folder of the executable could not be found!"))?.
+            as_os_str().
+            to_os_string();
+
+    directory.push("/");
+    directory.push(PORTABLE_SAVE_GAME_FOLDER);
+    directory.push("/");
+
+    Ok(directory)
+}
+
+fn read_relocated_save_game_folder() -> Result<Option<OsString>, Box<dyn Error>> {
+    let mut location_file = default_save_game_folder()?;
+    location_file.push(LOCATION_FILE_NAME);
+
+    if !std::fs::exists(&location_file)? {
+        return Ok(None);
+    }
+
+    let location = std::fs::read_to_string(&location_file)?;
+    let directory = parse_save_game_folder_path(&location).
+            ok_or_else(|| GameError::new(format!("The save game folder location \"{}\" is invalid!", location.trim())))?;
+
+    Ok(Some(directory))
+}
+
+/// Parses an absolute folder path, the returned path always ends with a path separator
+pub fn parse_save_game_folder_path(path: &str) -> Option<OsString> {
+    let path = path.trim();
+    if path.is_empty() || path.len() > MAX_SAVE_GAME_FOLDER_PATH_LEN || !Path::new(path).is_absolute() {
+        return None;
+    }
+
+    let mut directory = OsString::from(path);
+    if !path.ends_with(['/', '\\']) {
+        directory.push("/");
+    }
+
+    Some(directory)
+}
+
+/// Moves all files of the current save game folder into the new folder which is used afterward
+///
+/// None moves the save game files back to the default folder. The new folder must be empty.
+pub fn relocate_save_game_folder(path: Option<&str>) -> Result<(), Box<dyn Error>> {
+    if startup_flags::startup_flags().portable() {
+        return Err(Box::new(GameError::new("The save game folder cannot be moved in portable mode!")));
+    }
+
+    let default_directory = default_save_game_folder()?;
+    let old_directory = base_save_game_folder()?;
+    let new_directory = match path {
+        Some(path) => parse_save_game_folder_path(path).
+                ok_or_else(|| GameError::new(format!("\"{}\" is not an absolute folder path!", path.trim())))?,
+
+        None => default_directory.clone(),
+    };
+
+    if Path::new(&new_directory).starts_with(&old_directory) {
+        return Err(Box::new(GameError::new("The new save game folder must not be inside the current save game folder!")));
+    }
+
+    std::fs::create_dir_all(&new_directory)?;
+    let has_files = std::fs::read_dir(&new_directory)?.
+            filter_map(|entry| entry.ok()).
+            any(|entry| entry.file_name() != LOCATION_FILE_NAME);
+    if has_files {
+        return Err(Box::new(GameError::new("The new save game folder is not empty!")));
+    }
+
+    copy_folder_contents(Path::new(&old_directory), Path::new(&new_directory))?;
+
+    let mut location_file = default_directory.clone();
+    location_file.push(LOCATION_FILE_NAME);
+    if new_directory == default_directory {
+        std::fs::remove_file(&location_file)?;
+    }else {
+        std::fs::create_dir_all(&default_directory)?;
+        std::fs::write(&location_file, new_directory.to_string_lossy().as_ref())?;
+    }
+
+    *BASE_SAVE_GAME_FOLDER.write().unwrap() = Some(new_directory);
+
+    //The old files are only removed after the new folder is in use
+    for entry in std::fs::read_dir(&old_directory)? {
+        let entry = entry?;
+        if entry.file_name() == LOCATION_FILE_NAME {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            std::fs::remove_dir_all(entry.path())?;
+        }else {
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+
+    if old_directory != default_directory {
+        let _ = std::fs::remove_dir(&old_directory);
+    }
+
+    Ok(())
+}
+
+/// Copies all files and sub folders of the source folder into the target folder (The location file is skipped)
+fn copy_folder_contents(source: &Path, target: &Path) -> Result<(), Box<dyn Error>> {
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        if entry.file_name() == LOCATION_FILE_NAME {
+            continue;
+        }
+
+        let target_path = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            std::fs::create_dir_all(&target_path)?;
+            copy_folder_contents(&entry.path(), &target_path)?;
+        }else {
+            std::fs::copy(entry.path(), target_path)?;
+        }
+    }
+
+    Ok(())
+}
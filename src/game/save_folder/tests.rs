@@ -0,0 +1,34 @@
+use crate::game::save_folder::*;
+
+#[test]
+fn save_game_folder_path_parsing() {
+    assert_eq!(parse_save_game_folder_path("relative/path"), None);
+    assert_eq!(parse_save_game_folder_path("   "), None);
+
+    #[cfg(not(windows))]
+    {
+        assert_eq!(parse_save_game_folder_path(" /media/usb/saves "), Some(OsString::from("/media/usb/saves/")));
+        assert_eq!(parse_save_game_folder_path("/media/usb/saves/"), Some(OsString::from("/media/usb/saves/")));
+        assert_eq!(parse_save_game_folder_path(&format!("/{}", "a".repeat(MAX_SAVE_GAME_FOLDER_PATH_LEN))), None);
+    }
+}
+
+#[test]
+fn copy_folder_contents_skips_location_file() {
+    let folder = std::env::temp_dir().join(format!("sokoterm-save-folder-test-{}", std::process::id()));
+    let source = folder.join("source");
+    let target = folder.join("target");
+    std::fs::create_dir_all(source.join("profiles/Alice")).unwrap();
+    std::fs::create_dir_all(&target).unwrap();
+    std::fs::write(source.join("settings.data"), "settings").unwrap();
+    std::fs::write(source.join("profiles/Alice/pack.lvl.sav"), "save").unwrap();
+    std::fs::write(source.join(LOCATION_FILE_NAME), "/other/").unwrap();
+
+    copy_folder_contents(&source, &target).unwrap();
+
+    assert_eq!(std::fs::read_to_string(target.join("settings.data")).unwrap(), "settings");
+    assert_eq!(std::fs::read_to_string(target.join("profiles/Alice/pack.lvl.sav")).unwrap(), "save");
+    assert!(!target.join(LOCATION_FILE_NAME).exists());
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
@@ -0,0 +1,91 @@
+use std::ffi::OsString;
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::mpsc::{self, Sender};
+
+#[cfg(test)]
+mod tests;
+
+struct PendingWrite {
+    path: OsString,
+    data: String,
+}
+
+#[derive(Debug, Default)]
+struct WriterState {
+    pending_write_count: usize,
+    failures: Vec<String>,
+}
+
+/// Writes save game files in a background thread
+///
+/// Files are written in the order in which they were queued, failures are collected until they are taken by the game
+#[derive(Clone)]
+pub struct SaveWriter {
+    sender: Sender<PendingWrite>,
+    state: Arc<(Mutex<WriterState>, Condvar)>,
+}
+
+impl SaveWriter {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel::<PendingWrite>();
+        let state = Arc::new((Mutex::new(WriterState::default()), Condvar::new()));
+
+        let thread_state = Arc::clone(&state);
+        std::thread::spawn(move || {
+            for pending_write in receiver {
+                let result = std::fs::write(&pending_write.path, pending_write.data);
+
+                let (writer_state, all_written) = &*thread_state;
+                let mut writer_state = writer_state.lock().unwrap();
+                if let Err(err) = result {
+                    writer_state.failures.push(format!("\"{}\": {}", pending_write.path.to_string_lossy(), err));
+                }
+
+                writer_state.pending_write_count -= 1;
+                if writer_state.pending_write_count == 0 {
+                    all_written.notify_all();
+                }
+            }
+        });
+
+        Self { sender, state }
+    }
+
+    /// Queues the data to be written to the file (The file is replaced)
+    pub fn write(&self, path: OsString, data: String) {
+        let (writer_state, _) = &*self.state;
+        writer_state.lock().unwrap().pending_write_count += 1;
+
+        if let Err(err) = self.sender.send(PendingWrite { path, data }) {
+            //The writer thread is not running anymore: Write in the current thread instead
+            let PendingWrite { path, data } = err.0;
+            let result = std::fs::write(&path, data);
+
+            let mut writer_state = writer_state.lock().unwrap();
+            writer_state.pending_write_count -= 1;
+            if let Err(err) = result {
+                writer_state.failures.push(format!("\"{}\": {}", path.to_string_lossy(), err));
+            }
+        }
+    }
+
+    /// Blocks until all queued files are written
+    pub fn flush(&self) {
+        let (writer_state, all_written) = &*self.state;
+        let writer_state = writer_state.lock().unwrap();
+        let _writer_state = all_written.wait_while(writer_state, |writer_state| writer_state.pending_write_count > 0).unwrap();
+    }
+
+    /// Returns and removes all write failures since the last call
+    pub fn take_failures(&self) -> Vec<String> {
+        let (writer_state, _) = &*self.state;
+
+        std::mem::take(&mut writer_state.lock().unwrap().failures)
+    }
+}
+
+impl Default for SaveWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
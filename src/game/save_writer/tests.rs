@@ -0,0 +1,31 @@
+use crate::game::save_writer::*;
+
+#[test]
+fn save_writer_keeps_write_order() {
+    let folder = std::env::temp_dir().join(format!("sokoterm-save-writer-test-{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    let path = folder.join("test.lvl.sav");
+
+    let save_writer = SaveWriter::new();
+    for i in 0..20 {
+        save_writer.write(path.clone().into_os_string(), i.to_string());
+    }
+    save_writer.flush();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "19");
+    assert!(save_writer.take_failures().is_empty());
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn save_writer_collects_failures() {
+    let path = std::env::temp_dir().join("sokoterm-save-writer-test-missing-folder").join("test.lvl.sav");
+
+    let save_writer = SaveWriter::new();
+    save_writer.write(path.into_os_string(), "0".to_string());
+    save_writer.flush();
+
+    assert_eq!(save_writer.take_failures().len(), 1);
+    assert!(save_writer.take_failures().is_empty());
+}
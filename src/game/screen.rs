@@ -1,39 +1,152 @@
 use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::error::Error;
 use std::fmt::Write as _;
+use std::mem;
 use std::str::FromStr;
-use std::time::SystemTime;
-use crate::game::{audio, Game, GameState, TileMode};
-use crate::game::level::{Direction, Level, LevelPack, LevelWithStats, MoveResult, PlayingLevel, Tile};
-use crate::game::screen::dialog::{Dialog, DialogSelection};
+use std::time::{Duration, Instant, SystemTime};
+use crate::game::{audio, Game, GameError, GameState, Language, StreamerMode, TelemetryConsent, TileMode};
+use crate::game::achievements::{self, TotalStat};
+use crate::game::archive::{LevelPackArchive, ARCHIVE_FILE_EXTENSION};
+use crate::game::audio::{AudioDevices, PackSoundEffectId};
+use crate::game::level::{Direction, Level, LevelAttempt, LevelHint, LevelHintTrigger, LevelPack, LevelWithStats, MoveResult, PackStory, PlayingLevel, Tile, TileEvent};
+use crate::game::screen::dialog::{Dialog, DialogOption, DialogSelection, DialogType};
+use crate::game::screen::navigation::ListNavigator;
+use crate::game::screen::hit_regions::{HitRegionId, RegionClick};
 use crate::collections::UndoHistory;
 use crate::game::console_extension::ConsoleExtension;
-use crate::game::screen::components::{Rect, UIList, UIListElement};
+use crate::game::credits::{self, CreditCategory, CreditLine};
+use crate::game::diagnostics;
+#[cfg(feature = "cli")]
+use crate::game::editor_recovery::{self, EditorRecovery};
+use crate::game::events::LevelCompletedEvent;
+use crate::game::input::{self, InGameAction, MenuAction};
+#[cfg(not(feature = "steam"))]
+use crate::game::patch::LevelPackSource;
+use crate::game::profile;
+use crate::game::recommend;
+use crate::game::save_folder;
+use crate::game::solver::{self, LevelProblem, SolutionCache, TileCounts};
+use crate::game::trash::{self, TrashEntry, TrashEntryKind};
+use crate::game::workshop::{self, WorkshopFilter};
+use crate::game::screen::components::{draw_level_hint, draw_level_par, draw_level_stats_compare, draw_progress_bar, draw_tile_legend, PauseMenu, PauseMenuEntry, Rect, UIList, UIListElement};
 use crate::io::{Color, Console, Key};
 
+#[cfg(feature = "gui")]
+use crate::game::LevelPackThumbnailArea;
 #[cfg(feature = "steam")]
 use crate::game::steam::achievement::Achievement;
 #[cfg(feature = "steam")]
-use crate::game::steam;
+use crate::game::steam::{self, WorkshopSubscription};
+#[cfg(feature = "steam")]
+use bevy_steamworks::PublishedFileId;
+#[cfg(feature = "steam")]
+use crate::game::logging::log_warn;
 
 pub mod dialog;
 pub mod utils;
 pub mod components;
+pub mod navigation;
+pub mod hit_regions;
 
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum ScreenId {
     StartMenu,
     About,
     Settings,
+    Jukebox,
+    Achievements,
+    Diagnostics,
 
     SelectLevelPack,
     SelectLevel,
+    WorkshopBrowser,
+    #[cfg(feature = "steam")]
+    WorkshopSubscriptions,
 
     InGame,
+    PackStory,
 
     SelectLevelPackEditor,
     SelectLevelPackBackgroundMusic,
+    RecentlyDeleted,
     LevelPackEditor,
     LevelEditor,
+
+    #[cfg(not(feature = "steam"))]
+    SelectProfile,
+
+    #[cfg(feature = "gui")]
+    Credits,
+}
+
+impl ScreenId {
+    /// Returns a stable identifier of this screen (Used in game state snapshots)
+    pub fn id(&self) -> &'static str {
+        match self {
+            ScreenId::StartMenu => "start-menu",
+            ScreenId::About => "about",
+            ScreenId::Settings => "settings",
+            ScreenId::Jukebox => "jukebox",
+            ScreenId::Achievements => "achievements",
+            ScreenId::Diagnostics => "diagnostics",
+
+            ScreenId::SelectLevelPack => "select-level-pack",
+            ScreenId::SelectLevel => "select-level",
+            ScreenId::WorkshopBrowser => "workshop-browser",
+            #[cfg(feature = "steam")]
+            ScreenId::WorkshopSubscriptions => "workshop-subscriptions",
+
+            ScreenId::InGame => "in-game",
+            ScreenId::PackStory => "pack-story",
+
+            ScreenId::SelectLevelPackEditor => "select-level-pack-editor",
+            ScreenId::SelectLevelPackBackgroundMusic => "select-level-pack-background-music",
+            ScreenId::RecentlyDeleted => "recently-deleted",
+            ScreenId::LevelPackEditor => "level-pack-editor",
+            ScreenId::LevelEditor => "level-editor",
+
+            #[cfg(not(feature = "steam"))]
+            ScreenId::SelectProfile => "select-profile",
+
+            #[cfg(feature = "gui")]
+            ScreenId::Credits => "credits",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Some(match id {
+            "start-menu" => ScreenId::StartMenu,
+            "about" => ScreenId::About,
+            "settings" => ScreenId::Settings,
+            "jukebox" => ScreenId::Jukebox,
+            "achievements" => ScreenId::Achievements,
+            "diagnostics" => ScreenId::Diagnostics,
+
+            "select-level-pack" => ScreenId::SelectLevelPack,
+            "select-level" => ScreenId::SelectLevel,
+            "workshop-browser" => ScreenId::WorkshopBrowser,
+            #[cfg(feature = "steam")]
+            "workshop-subscriptions" => ScreenId::WorkshopSubscriptions,
+
+            "in-game" => ScreenId::InGame,
+            "pack-story" => ScreenId::PackStory,
+
+            "select-level-pack-editor" => ScreenId::SelectLevelPackEditor,
+            "select-level-pack-background-music" => ScreenId::SelectLevelPackBackgroundMusic,
+            "recently-deleted" => ScreenId::RecentlyDeleted,
+            "level-pack-editor" => ScreenId::LevelPackEditor,
+            "level-editor" => ScreenId::LevelEditor,
+
+            #[cfg(not(feature = "steam"))]
+            "select-profile" => ScreenId::SelectProfile,
+
+            #[cfg(feature = "gui")]
+            "credits" => ScreenId::Credits,
+
+            _ => return None,
+        })
+    }
 }
 
 #[allow(unused_variables)]
@@ -43,11 +156,42 @@ pub trait Screen {
     fn update(&mut self, game_state: &mut GameState) {}
     fn animate(&mut self, game_state: &mut GameState) {}
 
+    /// Returns true if the screen changes without any input (e.g. timers or animations)
+    ///
+    /// The CLI version only redraws other screens after input or if the player background was toggled
+    fn is_animated(&self, game_state: &GameState) -> bool {
+        false
+    }
+
     fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {}
     fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {}
 
+    /// Called instead of [Screen::on_mouse_pressed] if a region which was registered in [GameState::hit_regions] during the last draw was clicked
+    fn on_region_clicked(&mut self, game_state: &mut GameState, click: RegionClick) {}
+
+    /// Called if the mouse wheel was scrolled (Positive delta: Scrolled down)
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, column: usize, row: usize, delta: i32) {}
+
+    /// Called if the mouse button which is mapped to the "Preview" action was pressed
+    fn on_toggle_preview(&mut self, game_state: &mut GameState) {}
+
+    /// Called with sanitized text (Printable ASCII characters and line breaks only) if text was pasted from the clipboard
+    fn on_text_pasted(&mut self, game_state: &mut GameState, text: &str) {}
+
     fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {}
 
+    /// Returns the progress of the current level which should be stored in a game state snapshot
+    fn level_attempt_snapshot(&self) -> Option<LevelAttempt> {
+        None
+    }
+
+    /// Called if the game should exit (e.g. the window was closed), returns false if the exit must be delayed
+    ///
+    /// If false is returned, the screen must call [GameState::exit] itself (e.g. after unsaved changes were saved)
+    fn on_exit_requested(&mut self, game_state: &mut GameState) -> bool {
+        true
+    }
+
     fn on_pause(&mut self, game_state: &mut GameState) {}
     fn on_continue(&mut self, game_state: &mut GameState) {}
     fn on_set_screen(&mut self, game_state: &mut GameState) {}
@@ -56,13 +200,20 @@ pub trait Screen {
 pub struct ScreenStartMenu {}
 
 impl ScreenStartMenu {
+    const REGION_START: HitRegionId = HitRegionId(0);
+    const REGION_JUKEBOX: HitRegionId = HitRegionId(1);
+    const REGION_SETTINGS: HitRegionId = HitRegionId(2);
+    const REGION_ABOUT: HitRegionId = HitRegionId(3);
+    const REGION_HELP: HitRegionId = HitRegionId(4);
+    const REGION_ACHIEVEMENTS: HitRegionId = HitRegionId(5);
+
     pub fn new() -> Self {
         Self {}
     }
 }
 
 impl Screen for ScreenStartMenu {
-    fn draw(&self, _: &GameState, console: &Console) {
+    fn draw(&self, game_state: &GameState, console: &Console) {
         //Draw border (top)
         console.set_color(Color::White, Color::Blue);
         console.draw_text(
@@ -98,26 +249,42 @@ impl Screen for ScreenStartMenu {
         console.draw_key_input_text("ENTER");
         console.reset_color();
         console.draw_text(" to start the game!");
+        game_state.hit_regions().add(Self::REGION_START, Rect::new(21, 16, 30, 1));
 
         console.set_cursor_pos(1, 21);
         console.draw_text("By ");
         console.set_color(Color::Default, Color::Yellow);
         console.draw_text("JDDev0");
 
+        console.reset_color();
+        console.set_cursor_pos(58, 17);
+        console.draw_text("Achievements: ");
+        console.draw_key_input_text("c");
+        game_state.hit_regions().add(Self::REGION_ACHIEVEMENTS, Rect::new(58, 17, 15, 1));
+
+        console.reset_color();
+        console.set_cursor_pos(63, 18);
+        console.draw_text("Jukebox: ");
+        console.draw_key_input_text("j");
+        game_state.hit_regions().add(Self::REGION_JUKEBOX, Rect::new(63, 18, 10, 1));
+
         console.reset_color();
         console.set_cursor_pos(62, 19);
         console.draw_text("Settings: ");
         console.draw_key_input_text("s");
+        game_state.hit_regions().add(Self::REGION_SETTINGS, Rect::new(62, 19, 11, 1));
 
         console.reset_color();
         console.set_cursor_pos(65, 20);
         console.draw_text("About: ");
         console.draw_key_input_text("a");
+        game_state.hit_regions().add(Self::REGION_ABOUT, Rect::new(65, 20, 8, 1));
 
         console.reset_color();
         console.set_cursor_pos(65, 21);
         console.draw_text("Help: ");
         console.draw_key_input_text("F1");
+        game_state.hit_regions().add(Self::REGION_HELP, Rect::new(65, 21, 8, 1));
 
         //Draw border
         console.set_color(Color::White, Color::Blue);
@@ -133,7 +300,15 @@ impl Screen for ScreenStartMenu {
 
     fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
         if key == Key::ESC {
-            game_state.open_dialog(Dialog::new_yes_no("Exit game?"));
+            let session_summary = game_state.settings.show_session_stats_on_exit.
+                    then(|| game_state.session_stats.summary()).
+                    flatten();
+
+            if let Some(session_summary) = session_summary {
+                game_state.open_dialog(Dialog::new_yes_no(format!("{session_summary}\n\nExit game?")));
+            }else {
+                game_state.open_dialog(Dialog::new_yes_no("Exit game?"));
+            }
 
             return;
         }
@@ -154,28 +329,39 @@ impl Screen for ScreenStartMenu {
             return;
         }
 
-        if key == Key::ENTER || key == Key::SPACE {
+        if key == Key::J {
             game_state.play_sound_effect_ui_select();
 
-            game_state.set_screen(ScreenId::SelectLevelPack);
-        }
-    }
+            game_state.set_screen(ScreenId::Jukebox);
 
-    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
-        if row == 16 && (21..51).contains(&column) {
-            self.on_key_pressed(game_state, Key::ENTER);
+            return;
         }
 
-        if row == 21 && column > 64 && column < 73 {
-            game_state.open_help_page();
+        if key == Key::C {
+            game_state.play_sound_effect_ui_select();
+
+            game_state.set_screen(ScreenId::Achievements);
+
+            return;
         }
 
-        if row == 20 && column > 64 && column < 73 {
-            self.on_key_pressed(game_state, Key::A);
+        if key == Key::ENTER || key == Key::SPACE {
+            game_state.play_sound_effect_ui_select();
+
+            game_state.set_screen(ScreenId::SelectLevelPack);
         }
+    }
 
-        if row == 19 && column > 61 && column < 73 {
-            self.on_key_pressed(game_state, Key::S);
+    fn on_region_clicked(&mut self, game_state: &mut GameState, click: RegionClick) {
+        match click.id {
+            Self::REGION_START => self.on_key_pressed(game_state, Key::ENTER),
+            Self::REGION_JUKEBOX => self.on_key_pressed(game_state, Key::J),
+            Self::REGION_ACHIEVEMENTS => self.on_key_pressed(game_state, Key::C),
+            Self::REGION_SETTINGS => self.on_key_pressed(game_state, Key::S),
+            Self::REGION_ABOUT => self.on_key_pressed(game_state, Key::A),
+            Self::REGION_HELP => game_state.open_help_page(),
+
+            _ => {},
         }
     }
 
@@ -186,95 +372,29 @@ impl Screen for ScreenStartMenu {
     }
 
     fn on_set_screen(&mut self, game_state: &mut GameState) {
-        game_state.set_background_music_loop(&audio::BACKGROUND_MUSIC_FIELDS_OF_ICE);
+        game_state.set_menu_background_music_loop();
     }
 }
 
-mod attribution {
-    use std::sync::LazyLock;
-
-    const ATTRIBUTION_AUDIO_SOUND_EFFECTS_TEXT: &str = include_str!("../../resources/attribution/audio_sound_effects.txt");
-    const ATTRIBUTION_AUDIO_BACKGROUND_MUSIC_TEXT: &str = include_str!("../../resources/attribution/audio_background_music.txt");
-
-    #[cfg(all(feature = "gui", not(feature = "steam")))]
-    const ATTRIBUTION_FONTS_TEXT: &str = include_str!("../../resources/attribution/fonts_gui.txt");
-    #[cfg(feature = "steam")]
-    const ATTRIBUTION_FONTS_TEXT: &str = include_str!("../../resources/attribution/fonts_steam.txt");
-
-    #[cfg(feature = "cli")]
-    const ATTRIBUTION_LIBRARIES_TEXT: &str = include_str!("../../resources/attribution/libraries_cli.txt");
-    #[cfg(all(feature = "gui", not(feature = "steam")))]
-    const ATTRIBUTION_LIBRARIES_TEXT: &str = include_str!("../../resources/attribution/libraries_gui.txt");
-    #[cfg(feature = "steam")]
-    const ATTRIBUTION_LIBRARIES_TEXT: &str = include_str!("../../resources/attribution/libraries_steam.txt");
-
-    pub static ATTRIBUTION_AUDIO_SOUND_EFFECTS_TOKENS: LazyLock<Box<[[&str; 4]]>> = LazyLock::new(|| {
-        ATTRIBUTION_AUDIO_SOUND_EFFECTS_TEXT.split("\n").
-                filter(|line| !line.trim().is_empty()).
-                map(|line| {
-                    line.split(";").
-                            map(|token| token.trim()).
-                            collect::<Box<[&str]>>().
-                            as_ref().try_into().unwrap()
-                }).collect()
-    });
-
-    pub static ATTRIBUTION_AUDIO_BACKGROUND_MUSIC_TOKENS: LazyLock<Box<[[&str; 4]]>> = LazyLock::new(|| {
-        ATTRIBUTION_AUDIO_BACKGROUND_MUSIC_TEXT.split("\n").
-                filter(|line| !line.trim().is_empty()).
-                map(|line| {
-                    line.split(";").
-                            map(|token| token.trim()).
-                            collect::<Box<[&str]>>().
-                            as_ref().try_into().unwrap()
-                }).collect()
-    });
-
-    #[cfg(feature = "gui")]
-    pub static ATTRIBUTION_FONTS_TOKENS: LazyLock<Box<[[&str; 4]]>> = LazyLock::new(|| {
-        ATTRIBUTION_FONTS_TEXT.split("\n").
-                filter(|line| !line.trim().is_empty()).
-                map(|line| {
-                    line.split(";").
-                            map(|token| token.trim()).
-                            collect::<Box<[&str]>>().
-                            as_ref().try_into().unwrap()
-                }).collect()
-    });
-
-    pub static ATTRIBUTION_LIBRARIES_TOKENS: LazyLock<Box<[[&str; 3]]>> = LazyLock::new(|| {
-        ATTRIBUTION_LIBRARIES_TEXT.split("\n").
-                filter(|line| !line.trim().is_empty()).
-                map(|line| {
-                    line.split(";").
-                            map(|token| token.trim()).
-                            collect::<Box<[&str]>>().
-                            as_ref().try_into().unwrap()
-                }).collect()
-    });
-}
-
 pub struct ScreenAbout {
     scroll_position_row: usize,
     scroll_position_row_max: usize,
 }
 
 impl ScreenAbout {
-    pub fn new() -> Self {
-        let mut scroll_position_row_max = 11; //Open source game, version, link, build info, thank you text
-
-        scroll_position_row_max += 3 + 5 * attribution::ATTRIBUTION_AUDIO_SOUND_EFFECTS_TOKENS.len();
-        scroll_position_row_max += 3 + 5 * attribution::ATTRIBUTION_AUDIO_BACKGROUND_MUSIC_TOKENS.len();
+    //Open source game, version, link, build info
+    const CREDITS_START_ROW: usize = 9;
 
-        #[cfg(feature = "gui")]
-        {
-            scroll_position_row_max += 3 + 5 * attribution::ATTRIBUTION_FONTS_TOKENS.len();
-        }
+    #[cfg(feature = "gui")]
+    const REGION_ROLL_CREDITS: HitRegionId = HitRegionId(0);
+    const REGION_DIAGNOSTICS: HitRegionId = HitRegionId(1);
 
-        scroll_position_row_max += 3 + 4 * attribution::ATTRIBUTION_LIBRARIES_TOKENS.len();
+    pub fn new() -> Self {
+        //The thank you text is drawn after two empty lines
+        let thank_you_row = Self::CREDITS_START_ROW + credits::credit_lines().len() + 2;
 
         //Do not allow scrolling past the last line of text
-        scroll_position_row_max -= Game::CONSOLE_MIN_HEIGHT - 1;
+        let scroll_position_row_max = thank_you_row - (Game::CONSOLE_MIN_HEIGHT - 1);
 
         Self {
             scroll_position_row: 0,
@@ -325,12 +445,33 @@ impl ScreenAbout {
 }
 
 impl Screen for ScreenAbout {
-    fn draw(&self, _game_state: &GameState, console: &Console) {
+    fn draw(&self, game_state: &GameState, console: &Console) {
         console.reset_color();
         console.set_underline(true);
         console.draw_text("About SokoTerm:");
         console.set_underline(false);
 
+        console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 35, 0);
+        console.draw_text("Diagnostics (");
+
+        console.draw_key_input_text("d");
+
+        console.reset_color();
+        console.draw_text(")");
+        game_state.hit_regions().add(Self::REGION_DIAGNOSTICS, Rect::new(Game::CONSOLE_MIN_WIDTH - 35, 0, 15, 1));
+
+        #[cfg(feature = "gui")]
+        {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 17, 0);
+            console.draw_text("Roll credits (");
+
+            console.draw_key_input_text("r");
+
+            console.reset_color();
+            console.draw_text(")");
+            game_state.hit_regions().add(Self::REGION_ROLL_CREDITS, Rect::new(Game::CONSOLE_MIN_WIDTH - 17, 0, 16, 1));
+        }
+
         self.draw_scrollbar(console);
 
         let mut current_row = 2;
@@ -345,7 +486,7 @@ impl Screen for ScreenAbout {
         current_row += 1;
         if self.set_cursor_pos_if_visible(console, 0, current_row) {
             console.reset_color();
-            console.draw_text(format!("Version: {}", Game::VERSION));
+            console.draw_fmt(format_args!("Version: {}", Game::VERSION));
         }
 
         current_row += 2;
@@ -382,184 +523,10 @@ impl Screen for ScreenAbout {
             console.draw_text(" build of this game.");
         }
 
-        current_row += 3;
-        if self.set_cursor_pos_if_visible(console, 0, current_row) {
-            console.reset_color();
-            console.draw_text("This build of the game uses the following sound effects:");
-        }
-
-        current_row += 1;
-        if self.set_cursor_pos_if_visible(console, 0, current_row) {
-            console.reset_color();
-            console.draw_text("========================================================");
-        }
-
-        for [name, creator, license, project_link] in attribution::ATTRIBUTION_AUDIO_SOUND_EFFECTS_TOKENS.iter() {
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightCyan, Color::Default);
-                console.draw_text(*name);
-            }
-
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.reset_color();
-                console.draw_text("[by ");
-
-                console.set_color(Color::LightPink, Color::Default);
-                console.draw_text(*creator);
-
-                console.reset_color();
-                console.draw_text("]");
-            }
-
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightYellow, Color::Default);
-                console.draw_text(*license);
-            }
-
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightBlue, Color::Default);
-                console.set_underline(true);
-                console.draw_text(*project_link);
-                console.set_underline(false);
-            }
-
-            current_row += 1;
-        }
-
-        current_row += 2;
-        if self.set_cursor_pos_if_visible(console, 0, current_row) {
-            console.reset_color();
-            console.draw_text("This build of the game uses the following background music tracks:");
-        }
-
-        current_row += 1;
-        if self.set_cursor_pos_if_visible(console, 0, current_row) {
-            console.reset_color();
-            console.draw_text("==================================================================");
-        }
-
-        for [name, creator, license, project_link] in attribution::ATTRIBUTION_AUDIO_BACKGROUND_MUSIC_TOKENS.iter() {
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightCyan, Color::Default);
-                console.draw_text(*name);
-            }
-
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.reset_color();
-                console.draw_text("[by ");
-
-                console.set_color(Color::LightPink, Color::Default);
-                console.draw_text(*creator);
-
-                console.reset_color();
-                console.draw_text("]");
-            }
-
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightYellow, Color::Default);
-                console.draw_text(*license);
-            }
-
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightBlue, Color::Default);
-                console.set_underline(true);
-                console.draw_text(*project_link);
-                console.set_underline(false);
-            }
-
-            current_row += 1;
-        }
-
-        #[cfg(feature = "gui")]
-        {
-            current_row += 2;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.reset_color();
-                console.draw_text("This build of the game uses the following text fonts:");
-            }
-
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.reset_color();
-                console.draw_text("=====================================================");
-            }
-
-            for [name, creator, license, project_link] in attribution::ATTRIBUTION_FONTS_TOKENS.iter() {
-                current_row += 1;
-                if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                    console.set_color(Color::LightCyan, Color::Default);
-                    console.draw_text(*name);
-                }
-
-                current_row += 1;
-                if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                    console.reset_color();
-                    console.draw_text("[by ");
-
-                    console.set_color(Color::LightPink, Color::Default);
-                    console.draw_text(*creator);
-
-                    console.reset_color();
-                    console.draw_text("]");
-                }
-
-                current_row += 1;
-                if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                    console.set_color(Color::LightYellow, Color::Default);
-                    console.draw_text(*license);
-                }
-
-                current_row += 1;
-                if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                    console.set_color(Color::LightBlue, Color::Default);
-                    console.set_underline(true);
-                    console.draw_text(*project_link);
-                    console.set_underline(false);
-                }
-
-                current_row += 1;
-            }
-        }
-
-        current_row += 2;
-        if self.set_cursor_pos_if_visible(console, 0, current_row) {
-            console.reset_color();
-            console.draw_text("This build of the game uses the following open-source libraries:");
-        }
-
         current_row += 1;
-        if self.set_cursor_pos_if_visible(console, 0, current_row) {
-            console.reset_color();
-            console.draw_text("================================================================");
-        }
-
-        for [name, license, project_link] in attribution::ATTRIBUTION_LIBRARIES_TOKENS.iter() {
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightCyan, Color::Default);
-                console.draw_text(*name);
-            }
-
-            current_row += 1;
-            if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightYellow, Color::Default);
-                console.draw_text(*license);
-            }
-
-            current_row += 1;
+        for line in credits::credit_lines() {
             if self.set_cursor_pos_if_visible(console, 0, current_row) {
-                console.set_color(Color::LightBlue, Color::Default);
-                console.set_underline(true);
-                console.draw_text(*project_link);
-                console.set_underline(false);
+                draw_credit_line(console, *line);
             }
 
             current_row += 1;
@@ -587,6 +554,23 @@ impl Screen for ScreenAbout {
             return;
         }
 
+        #[cfg(feature = "gui")]
+        if key == Key::R {
+            game_state.play_sound_effect_ui_select();
+
+            game_state.set_screen(ScreenId::Credits);
+
+            return;
+        }
+
+        if key == Key::D {
+            game_state.play_sound_effect_ui_select();
+
+            game_state.set_screen(ScreenId::Diagnostics);
+
+            return;
+        }
+
         if key == Key::UP && self.scroll_position_row > 0 {
             self.scroll_position_row -= 1;
         }else if key == Key::DOWN && self.scroll_position_row < self.scroll_position_row_max {
@@ -594,6 +578,17 @@ impl Screen for ScreenAbout {
         }
     }
 
+    fn on_region_clicked(&mut self, game_state: &mut GameState, click: RegionClick) {
+        #[cfg(feature = "gui")]
+        if click.id == Self::REGION_ROLL_CREDITS {
+            self.on_key_pressed(game_state, Key::R);
+        }
+
+        if click.id == Self::REGION_DIAGNOSTICS {
+            self.on_key_pressed(game_state, Key::D);
+        }
+    }
+
     fn on_mouse_pressed(&mut self, _game_state: &mut GameState, column: usize, row: usize) {
         if column == Game::CONSOLE_MIN_WIDTH - 1 && (2..Game::CONSOLE_MIN_HEIGHT).contains(&row) {
             let scrollbar_y_coord = row - 2;
@@ -608,38 +603,482 @@ impl Screen for ScreenAbout {
                     + if scrollbar_y_coord == 0 { 0 } else { 1 };
         }
     }
-}
 
-pub struct ScreenSettings {}
-
-impl ScreenSettings {
-    pub fn new() -> Self {
-        Self {}
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
+        }
     }
 }
 
-impl Screen for ScreenSettings {
-    fn draw(&self, game_state: &GameState, console: &Console) {
-        console.set_color(Color::Yellow, Color::Default);
-        console.set_underline(true);
-        console.draw_text("Settings menu");
-        console.set_underline(false);
+/// Draws a line of the credits at the current cursor position
+fn draw_credit_line(console: &Console, line: CreditLine) {
+    match line {
+        CreditLine::Empty => {},
 
-        console.reset_color();
-        console.set_cursor_pos(0, 2);
-        if cfg!(feature = "gui") {
-            console.draw_text("Color scheme (Toggle with ");
+        CreditLine::Heading(heading) => {
+            console.reset_color();
+            console.draw_text(heading);
+        },
 
-            console.draw_key_input_text("F10");
+        CreditLine::Separator(len) => {
+            console.reset_color();
+            console.draw_text("=".repeat(len));
+        },
 
+        CreditLine::Name(name) => {
+            console.set_color(Color::LightCyan, Color::Default);
+            console.draw_text(name);
+        },
+
+        CreditLine::Creator(creator) => {
             console.reset_color();
-            console.draw_text("):");
-        }else {
-            console.draw_text("Color scheme:");
-        }
+            console.draw_text("[by ");
 
-        //Draw color scheme
-        console.set_cursor_pos(0, 3);
+            console.set_color(Color::LightPink, Color::Default);
+            console.draw_text(creator);
+
+            console.reset_color();
+            console.draw_text("]");
+        },
+
+        CreditLine::License(license) => {
+            console.set_color(Color::LightYellow, Color::Default);
+            console.draw_text(license);
+        },
+
+        CreditLine::Link(link) => {
+            console.set_color(Color::LightBlue, Color::Default);
+            console.set_underline(true);
+            console.draw_text(link);
+            console.set_underline(false);
+        },
+    }
+}
+
+/// Returns the count of columns of a line of the credits
+#[cfg(feature = "gui")]
+fn credit_line_width(line: CreditLine) -> usize {
+    match line {
+        CreditLine::Empty => 0,
+        CreditLine::Separator(len) => len,
+        CreditLine::Creator(creator) => creator.len() + 5,
+        CreditLine::Heading(text) | CreditLine::Name(text) | CreditLine::License(text) | CreditLine::Link(text) => text.len(),
+    }
+}
+
+/// Scrolling credits with clickable links (Opened from the About screen or rolled after all built-in level packs were completed)
+#[cfg(feature = "gui")]
+pub struct ScreenCredits {
+    scroll_position_row: usize,
+    scroll_position_row_max: usize,
+
+    is_auto_scrolling: bool,
+    auto_scroll_delay: u32,
+
+    //True if the credits were rolled after all built-in level packs were completed
+    is_rolling: bool,
+}
+
+#[cfg(feature = "gui")]
+impl ScreenCredits {
+    const CONTENT_START_ROW: usize = 2;
+    const VISIBLE_ROW_COUNT: usize = Game::CONSOLE_MIN_HEIGHT - 1 - Self::CONTENT_START_ROW;
+
+    //Title, empty line, and author
+    const HEADER_ROW_COUNT: usize = 3;
+    //Two empty lines and the thank you text
+    const FOOTER_ROW_COUNT: usize = 3;
+
+    //Updates per scrolled row (25 updates per second)
+    const AUTO_SCROLL_DELAY: u32 = 12;
+
+    pub fn new() -> Self {
+        let row_count = Self::HEADER_ROW_COUNT + credits::credit_lines().len() + Self::FOOTER_ROW_COUNT;
+
+        Self {
+            scroll_position_row: 0,
+            scroll_position_row_max: row_count.saturating_sub(Self::VISIBLE_ROW_COUNT),
+
+            is_auto_scrolling: true,
+            auto_scroll_delay: 0,
+
+            is_rolling: false,
+        }
+    }
+
+    fn draw_centered(&self, console: &Console, content_row: usize, width: usize) -> bool {
+        if content_row < self.scroll_position_row || content_row >= self.scroll_position_row + Self::VISIBLE_ROW_COUNT {
+            return false;
+        }
+
+        console.set_cursor_pos(
+            Game::CONSOLE_MIN_WIDTH.saturating_sub(width) / 2,
+            Self::CONTENT_START_ROW + content_row - self.scroll_position_row,
+        );
+
+        true
+    }
+}
+
+#[cfg(feature = "gui")]
+impl Screen for ScreenCredits {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        if self.is_rolling {
+            console.draw_text("Congratulations! You have completed all built-in level packs!");
+        }else {
+            console.draw_text("Credits:");
+        }
+        console.set_underline(false);
+
+        if self.draw_centered(console, 0, 8) {
+            console.set_color(Color::LightYellow, Color::Default);
+            console.draw_text("SokoTerm");
+        }
+
+        if self.draw_centered(console, 2, 9) {
+            console.reset_color();
+            console.draw_text("By ");
+            console.set_color(Color::Default, Color::Yellow);
+            console.draw_text("JDDev0");
+        }
+
+        for (i, line) in credits::credit_lines().iter().enumerate() {
+            let width = credit_line_width(*line);
+            let content_row = Self::HEADER_ROW_COUNT + i;
+            if !self.draw_centered(console, content_row, width) {
+                continue;
+            }
+
+            draw_credit_line(console, *line);
+
+            if matches!(line, CreditLine::Link(_)) {
+                game_state.hit_regions().add(HitRegionId(i as u32), Rect::new(
+                    Game::CONSOLE_MIN_WIDTH.saturating_sub(width) / 2,
+                    Self::CONTENT_START_ROW + content_row - self.scroll_position_row,
+                    width, 1,
+                ));
+            }
+        }
+
+        let thank_you_row = Self::HEADER_ROW_COUNT + credits::credit_lines().len() + 2;
+        if self.draw_centered(console, thank_you_row, 31) {
+            console.reset_color();
+            console.draw_text("Thank you for playing ");
+
+            console.set_color(Color::LightYellow, Color::Default);
+            console.draw_text("SokoTerm");
+
+            console.reset_color();
+            console.draw_text("!");
+        }
+
+        console.set_cursor_pos(0, Game::CONSOLE_MIN_HEIGHT - 1);
+        console.reset_color();
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back  ");
+        console.draw_key_input_text("SPACE");
+        console.reset_color();
+        console.draw_text(if self.is_auto_scrolling { ": Pause  " } else { ": Resume  " });
+        console.draw_key_input_text("Click link");
+        console.reset_color();
+        console.draw_text(": Copy link");
+    }
+
+    fn update(&mut self, _game_state: &mut GameState) {
+        if !self.is_auto_scrolling {
+            return;
+        }
+
+        self.auto_scroll_delay += 1;
+        if self.auto_scroll_delay < Self::AUTO_SCROLL_DELAY {
+            return;
+        }
+        self.auto_scroll_delay = 0;
+
+        if self.scroll_position_row < self.scroll_position_row_max {
+            self.scroll_position_row += 1;
+        }else {
+            self.is_auto_scrolling = false;
+        }
+    }
+
+    fn is_animated(&self, _game_state: &GameState) -> bool {
+        self.is_auto_scrolling
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if key == Key::ESC {
+            game_state.play_sound_effect_ui_select();
+
+            game_state.set_screen(if self.is_rolling {
+                ScreenId::SelectLevelPack
+            }else {
+                ScreenId::About
+            });
+
+            return;
+        }
+
+        if key == Key::SPACE {
+            self.is_auto_scrolling = !self.is_auto_scrolling;
+            self.auto_scroll_delay = 0;
+
+            return;
+        }
+
+        //Manual scrolling stops the auto scroll
+        if key == Key::UP && self.scroll_position_row > 0 {
+            self.is_auto_scrolling = false;
+            self.scroll_position_row -= 1;
+        }else if key == Key::DOWN && self.scroll_position_row < self.scroll_position_row_max {
+            self.is_auto_scrolling = false;
+            self.scroll_position_row += 1;
+        }
+    }
+
+    fn on_region_clicked(&mut self, game_state: &mut GameState, click: RegionClick) {
+        let Some(CreditLine::Link(link)) = credits::credit_lines().get(click.id.0 as usize) else {
+            return;
+        };
+
+        game_state.play_sound_effect_ui_select();
+
+        game_state.copy_to_clipboard(*link);
+        game_state.show_toast("Link copied to clipboard");
+    }
+
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
+        }
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.is_rolling = mem::take(&mut game_state.should_roll_credits);
+
+        self.scroll_position_row = 0;
+        self.is_auto_scrolling = true;
+        self.auto_scroll_delay = 0;
+
+        game_state.set_menu_background_music_loop();
+    }
+}
+
+/// Information about the game and the system for bug reports which can be written to a file
+pub struct ScreenDiagnostics {
+    lines: Vec<String>,
+
+    scroll_position_row: usize,
+}
+
+impl ScreenDiagnostics {
+    const VISIBLE_ROW_COUNT: usize = 16;
+
+    //Width of the inside of the box
+    const MAX_LINE_WIDTH: usize = Game::CONSOLE_MIN_WIDTH - 4;
+
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+
+            scroll_position_row: 0,
+        }
+    }
+
+    fn scroll_position_row_max(&self) -> usize {
+        self.lines.len().saturating_sub(Self::VISIBLE_ROW_COUNT)
+    }
+
+    fn save_diagnostics(&self, game_state: &mut GameState) {
+        //The file always contains the save folder path, even in streamer mode
+        let lines = diagnostics::diagnostics_lines(game_state, false);
+
+        match diagnostics::write_diagnostics_file(&lines) {
+            Ok(_) if game_state.settings.streamer_mode.is_enabled() => {
+                game_state.show_toast(format!("Saved to \"{}\" in the save folder", diagnostics::DIAGNOSTICS_FILE_NAME));
+            },
+
+            Ok(diagnostics_file) => {
+                game_state.open_dialog(Dialog::new_ok(format!(
+                    "The diagnostics were saved to:\n{}\n\nPlease attach this file to your bug report.",
+                    diagnostics_file.to_string_lossy(),
+                )));
+            },
+
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save diagnostics: {}", err)));
+            },
+        }
+    }
+}
+
+impl Screen for ScreenDiagnostics {
+    fn draw(&self, _game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_text("Diagnostics:");
+        console.set_underline(false);
+
+        console.set_color(Color::Cyan, Color::Default);
+        console.set_cursor_pos(0, 1);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 2..(5 + Self::VISIBLE_ROW_COUNT) {
+            console.set_cursor_pos(0, i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, 5 + Self::VISIBLE_ROW_COUNT);
+        console.draw_text("\'------------------------------------------------------------------------\'");
+
+        console.reset_color();
+        console.set_cursor_pos(2, 2);
+        console.draw_text("Please include this information in bug reports on GitHub:");
+
+        for (i, line) in self.lines.iter().
+                enumerate().
+                skip(self.scroll_position_row).
+                take(Self::VISIBLE_ROW_COUNT) {
+            console.set_cursor_pos(2, 4 + i - self.scroll_position_row);
+            if line.chars().count() > Self::MAX_LINE_WIDTH {
+                console.draw_fmt(format_args!("{}...", line.chars().take(Self::MAX_LINE_WIDTH - 3).collect::<String>()));
+            }else {
+                console.draw_text(line);
+            }
+        }
+
+        console.set_color(Color::Cyan, Color::Default);
+        if self.scroll_position_row > 0 {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 2, 4);
+            console.draw_text("^");
+        }
+        if self.scroll_position_row < self.scroll_position_row_max() {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 2, 3 + Self::VISIBLE_ROW_COUNT);
+            console.draw_text("v");
+        }
+
+        console.set_cursor_pos(1, 6 + Self::VISIBLE_ROW_COUNT);
+        console.draw_key_input_text("s");
+        console.reset_color();
+        console.draw_fmt(format_args!(": Save to \"{}\", ", diagnostics::DIAGNOSTICS_FILE_NAME));
+        console.draw_key_input_text("c");
+        console.reset_color();
+        console.draw_text(": Copy, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back");
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
+
+                game_state.set_screen(ScreenId::About);
+            },
+
+            Key::S => {
+                game_state.play_sound_effect_ui_select();
+
+                self.save_diagnostics(game_state);
+            },
+
+            Key::C => {
+                game_state.play_sound_effect_ui_select();
+
+                game_state.copy_to_clipboard(diagnostics::diagnostics_lines(game_state, false).join("\n"));
+                game_state.show_toast("Diagnostics copied to clipboard");
+            },
+
+            Key::UP => {
+                self.scroll_position_row = self.scroll_position_row.saturating_sub(1);
+            },
+            Key::DOWN => {
+                self.scroll_position_row = (self.scroll_position_row + 1).min(self.scroll_position_row_max());
+            },
+
+            _ => {},
+        }
+    }
+
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
+        }
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        //The save folder path usually contains the user name
+        self.lines = diagnostics::diagnostics_lines(game_state, game_state.settings.streamer_mode.is_enabled());
+        self.scroll_position_row = 0;
+    }
+}
+
+pub struct ScreenSettings {
+    is_editing_save_folder: bool,
+    save_folder_path: String,
+}
+
+impl ScreenSettings {
+    //The audio device row must not be wider than the console
+    const MAX_AUDIO_DEVICE_NAME_LEN: usize = 44;
+    const MAX_SAVE_FOLDER_PATH_DISPLAY_LEN: usize = 44;
+
+    pub fn new() -> Self {
+        Self {
+            is_editing_save_folder: Default::default(),
+            save_folder_path: String::new(),
+        }
+    }
+
+    fn stop_editing_save_folder(&mut self) {
+        self.is_editing_save_folder = false;
+        self.save_folder_path = String::new();
+    }
+
+    /// Moves the save game files into the entered folder, an empty path moves them back to the default folder
+    fn relocate_save_folder(&mut self, game_state: &mut GameState) {
+        //All queued save game files must be written before they are moved
+        game_state.save_writer.flush();
+
+        let path = self.save_folder_path.trim();
+        if let Err(err) = save_folder::relocate_save_game_folder((!path.is_empty()).then_some(path)) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot move save game folder: {}", err)));
+
+            return;
+        }
+
+        game_state.play_sound_effect_ui_select();
+        game_state.show_toast("Save game folder moved");
+
+        self.stop_editing_save_folder();
+    }
+}
+
+impl Screen for ScreenSettings {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        console.set_color(Color::Yellow, Color::Default);
+        console.set_underline(true);
+        console.draw_text("Settings menu");
+        console.set_underline(false);
+
+        console.reset_color();
+        console.set_cursor_pos(0, 2);
+        if cfg!(feature = "gui") {
+            console.draw_text("Color scheme (Toggle with ");
+
+            console.draw_key_input_text("F10");
+
+            console.reset_color();
+            console.draw_text("):");
+        }else {
+            console.draw_text("Color scheme:");
+        }
+
+        //Draw color scheme
+        console.set_cursor_pos(0, 3);
         console.set_color(Color::Default, Color::Black);
         console.draw_text("   ");
         console.set_color(Color::Default, Color::Red);
@@ -696,25 +1135,127 @@ impl Screen for ScreenSettings {
             console.draw_text("ASCII");
         }
 
-        console.reset_color();
-        console.set_cursor_pos(0, 8);
-        console.draw_text("Background Music: ");
+        if cfg!(feature = "cli") {
+            console.reset_color();
+            console.draw_text(", Double width: ");
 
-        if game_state.settings.background_music {
-            console.set_color(Color::Green, Color::Default);
-            console.draw_text("Enabled");
-        }else {
-            console.set_color(Color::Red, Color::Default);
-            console.draw_text("Disabled");
+            if game_state.settings.double_width_tiles {
+                console.set_color(Color::Green, Color::Default);
+                console.draw_text("Enabled");
+            }else {
+                console.set_color(Color::Red, Color::Default);
+                console.draw_text("Disabled");
+            }
+
+            console.reset_color();
+            console.draw_text(" (Toggle with ");
+
+            console.draw_key_input_text("t");
+
+            console.reset_color();
+            console.draw_text(")");
+
+            console.set_cursor_pos(0, 7);
+            console.draw_text("Large tiles (2x2 for low vision): ");
+
+            if game_state.settings.large_tiles {
+                console.set_color(Color::Green, Color::Default);
+                console.draw_text("Enabled");
+            }else {
+                console.set_color(Color::Red, Color::Default);
+                console.draw_text("Disabled");
+            }
+
+            console.reset_color();
+            console.draw_text(" (Toggle with ");
+
+            console.draw_key_input_text("l");
+
+            console.reset_color();
+            console.draw_text(")");
+        }
+
+        if cfg!(feature = "gui") {
+            console.reset_color();
+            console.set_cursor_pos(0, 7);
+            console.draw_text("Wall variation (Graphical tile mode): ");
+
+            if game_state.settings.wall_variation {
+                console.set_color(Color::Green, Color::Default);
+                console.draw_text("Enabled");
+            }else {
+                console.set_color(Color::Red, Color::Default);
+                console.draw_text("Disabled");
+            }
+
+            console.reset_color();
+            console.draw_text(" (Toggle with ");
+
+            console.draw_key_input_text("v");
+
+            console.reset_color();
+            console.draw_text(")");
         }
 
         console.reset_color();
-        console.draw_text(" (Toggle with ");
+        console.set_cursor_pos(0, 8);
+        console.draw_text("Background Music: ");
+
+        if game_state.startup_flags().mute_music() {
+            console.set_color(Color::Red, Color::Default);
+            console.draw_text("Muted by startup flag");
+        }else {
+            if game_state.settings.background_music {
+                console.set_color(Color::Green, Color::Default);
+                console.draw_text("Enabled");
+            }else {
+                console.set_color(Color::Red, Color::Default);
+                console.draw_text("Disabled");
+            }
 
-        console.draw_key_input_text("F8");
+            console.reset_color();
+            console.draw_text(" (Toggle with ");
+
+            console.draw_key_input_text("F8");
+
+            console.reset_color();
+            console.draw_text(")");
+        }
 
         console.reset_color();
-        console.draw_text(")");
+        console.set_cursor_pos(0, 9);
+        console.draw_text("Audio device: ");
+
+        if game_state.startup_flags().no_audio() {
+            console.set_color(Color::Red, Color::Default);
+            console.draw_text("Disabled by startup flag");
+        }else {
+            match game_state.settings.audio_device() {
+                Some(audio_device) if game_state.is_audio_device_fallback() => {
+                    console.set_color(Color::Red, Color::Default);
+                    console.draw_text(utils::truncate_text(audio_device, Self::MAX_AUDIO_DEVICE_NAME_LEN - 15));
+                    console.draw_text(" (Disconnected)");
+                },
+
+                Some(audio_device) => {
+                    console.set_color(Color::Blue, Color::Default);
+                    console.draw_text(utils::truncate_text(audio_device, Self::MAX_AUDIO_DEVICE_NAME_LEN));
+                },
+
+                None => {
+                    console.set_color(Color::Blue, Color::Default);
+                    console.draw_text("System default");
+                },
+            }
+
+            console.reset_color();
+            console.draw_text(" (Change with ");
+
+            console.draw_key_input_text("d");
+
+            console.reset_color();
+            console.draw_text(")");
+        }
 
         console.reset_color();
         console.set_cursor_pos(0, 10);
@@ -729,1510 +1270,2063 @@ impl Screen for ScreenSettings {
         console.draw_key_input_text("F7");
 
         console.reset_color();
-        console.draw_text(")");
-    }
+        console.draw_text("), Log (");
 
-    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
-        if key == Key::ESC {
-            game_state.play_sound_effect_ui_select();
+        console.draw_key_input_text("g");
 
-            game_state.set_screen(ScreenId::StartMenu);
-        }
-    }
+        console.reset_color();
+        console.draw_text("): ");
 
-    fn on_mouse_pressed(&mut self, _game_state: &mut GameState, _column: usize, _row: usize) {
-        //TODO
-    }
-}
+        console.set_color(Color::Blue, Color::Default);
+        console.draw_text(game_state.settings.log_verbosity.display_name());
 
-pub struct ScreenSelectLevelPack {
-    level_pack_list: UIList,
-    code_index: usize,
-}
+        console.reset_color();
+        console.set_cursor_pos(0, 11);
+        console.draw_text("Simultaneous arrow keys: ");
 
-impl ScreenSelectLevelPack {
-    pub fn new() -> Self {
-        Self {
-            level_pack_list: UIList::new(
-                Rect::new(0, 1, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT - 1),
-                vec![
-                    UIListElement::new("<<", Color::White, Color::LightBlue),
-                    //[Level Pack Entries]
-                    UIListElement::new(" +", Color::White, Color::LightBlue),
-                    #[cfg(feature = "steam")]
-                    UIListElement::new("[]", Color::White, Color::LightBlue),
-                ],
-                Box::new(|_, game_state: &mut GameState, cursor_index: usize| {
-                    game_state.play_sound_effect_ui_select();
+        console.set_color(Color::Blue, Color::Default);
+        console.draw_text(game_state.settings.arrow_key_priority.display_name());
 
-                    if cursor_index == 0 {
-                        game_state.set_screen(ScreenId::StartMenu);
-                    }else if cursor_index > game_state.get_level_pack_count() {
-                        if cursor_index == game_state.get_level_pack_count() + 2 {
-                            #[cfg(feature = "steam")]
-                            {
-                                //And Steam Workshop entry on steam build
-                                game_state.steam_client.friends().activate_game_overlay_to_web_page(&format!("steam://url/SteamWorkshopPage/{}", steam::APP_ID.0));
-                            }
+        console.reset_color();
+        console.draw_text(" (Toggle with ");
 
-                            #[cfg(not(feature = "steam"))]
-                            unreachable!();
-                        }else {
-                            //Level Pack Editor entry
-                            game_state.set_level_pack_index(game_state.get_level_pack_count());
+        console.draw_key_input_text("k");
 
-                            game_state.set_screen(ScreenId::SelectLevelPackEditor);
-                        }
-                    }else {
-                        game_state.set_level_pack_index(cursor_index - 1);
+        console.reset_color();
+        console.draw_text(")");
 
-                        //Set selected level
-                        let level_pack = game_state.get_current_level_pack().unwrap();
-                        let min_level_not_completed = level_pack.min_level_not_completed();
-                        if min_level_not_completed >= level_pack.level_count() {
-                            let first_skipped_level = level_pack.levels().
-                                    iter().
-                                    enumerate().
-                                    find(|(_, level)| level.best_moves().is_none()).
-                                    map(|(index, _)| index).
-                                    unwrap_or(0);
+        console.reset_color();
+        console.set_cursor_pos(0, 12);
+        console.draw_text("Language: ");
 
-                            game_state.set_level_index(first_skipped_level);
-                        }else {
-                            game_state.set_level_index(min_level_not_completed);
-                        }
+        console.set_color(Color::Blue, Color::Default);
+        console.draw_text(game_state.settings.language.display_name());
 
-                        game_state.set_screen(ScreenId::SelectLevel);
-                    }
-                }),
-            ),
-            code_index: 0,
-        }
-    }
+        console.reset_color();
+        console.draw_text(" (Toggle with ");
 
-    fn update_list_elements(&mut self, game_state: &GameState) {
-        let elements = self.level_pack_list.elements_mut();
+        console.draw_key_input_text("F6");
 
-        //Remove all level pack entries
-        let trailing_element_count = if cfg!(feature = "steam") { 2 } else { 1 };
-        let mut trailing_elements = elements.drain(1..).
-                rev().
-                take(trailing_element_count).
-                rev().
-                collect::<Vec<_>>();
+        console.reset_color();
+        console.draw_text(")");
 
-        for (i, level_pack) in game_state.level_packs().iter().enumerate() {
-            elements.push(UIListElement::new(
-                utils::number_to_string_leading_ascii(2, i as u32 + 1, false),
-                Color::Black,
-                if level_pack.level_pack_best_moves_sum().is_some() {
-                    Color::Green
-                }else {
-                    Color::Yellow
-                },
-            ));
+        console.set_cursor_pos(0, 13);
+        console.draw_text("(Used for level pack names and hints if a translation is available)");
+
+        console.set_cursor_pos(0, 14);
+        console.draw_text("Streamer mode: ");
+
+        if game_state.settings.streamer_mode.is_enabled() {
+            console.set_color(Color::Green, Color::Default);
+        }else {
+            console.set_color(Color::Red, Color::Default);
         }
+        console.draw_text(game_state.settings.streamer_mode.display_name());
 
-        elements.append(&mut trailing_elements);
-    }
-}
+        console.reset_color();
+        console.draw_text(" (Toggle with ");
+
+        console.draw_key_input_text("s");
 
-impl Screen for ScreenSelectLevelPack {
-    fn draw(&self, game_state: &GameState, console: &Console) {
         console.reset_color();
-        console.set_underline(true);
-        console.draw_text("Select a level pack:");
-        console.set_underline(false);
+        console.draw_text("), Margin: ");
 
-        self.level_pack_list.draw(console);
+        console.set_color(Color::Blue, Color::Default);
+        console.draw_fmt(format_args!("{}", game_state.settings.overlay_margin));
 
-        let entry_count = self.level_pack_list.elements().len();
+        console.reset_color();
+        console.draw_text(" (Toggle with ");
 
-        //Draw border for best time and best moves
-        let y = 4 + (entry_count/24)*2;
+        console.draw_key_input_text("o");
 
-        console.set_cursor_pos(0, y);
-        console.set_color(Color::Cyan, Color::Default);
-        console.draw_text(".------------------------------------------------------------------------.");
-        for i in 1..4 {
-            console.set_cursor_pos(0, y + i);
-            console.draw_text("|                                                                        |");
+        console.reset_color();
+        console.draw_text(")");
+
+        console.set_cursor_pos(0, 15);
+        console.draw_text("Session summary on exit: ");
+
+        if game_state.settings.show_session_stats_on_exit {
+            console.set_color(Color::Green, Color::Default);
+            console.draw_text("Enabled");
+        }else {
+            console.set_color(Color::Red, Color::Default);
+            console.draw_text("Disabled");
         }
-        console.set_cursor_pos(0, y + 4);
-        console.draw_text("\'------------------------------------------------------------------------\'");
+
         console.reset_color();
+        console.draw_text(" (Toggle with ");
 
-        let cursor_index = self.level_pack_list.cursor_index();
-        if cursor_index == 0 {
-            console.set_cursor_pos(35, y + 2);
-            console.draw_text("Back");
-        }else if cursor_index > game_state.get_level_pack_count() {
-            if cursor_index == game_state.get_level_pack_count() + 2 {
-                #[cfg(feature = "steam")]
-                {
-                    //And Steam Workshop entry on steam build
-                    console.set_cursor_pos(14, y + 1);
-                    console.draw_text("Download level packs from the Steam Workshop");
+        console.draw_key_input_text("x");
 
-                    console.set_cursor_pos(8, y + 3);
-                    console.set_color(Color::LightBlack, Color::Default);
-                    console.draw_text("You must relaunch the game after downloading level packs.");
-                }
+        console.reset_color();
+        console.draw_text(")");
 
-                #[cfg(not(feature = "steam"))]
-                unreachable!();
-            }else {
-                //Level Pack Editor entry
-                console.set_cursor_pos(23, y + 2);
-                console.draw_text("Create or edit level packs");
-            }
-        }else {
-            //Draw sum of best time and sum of best moves
-            console.set_cursor_pos(1, y + 1);
-            console.draw_text(format!("Selected level pack: {}", game_state.level_packs().get(cursor_index - 1).unwrap().name()));
+        console.set_cursor_pos(0, 16);
+        console.draw_text("Control preset: ");
 
-            let level_pack = game_state.level_packs.get(cursor_index - 1).unwrap();
+        console.set_color(Color::Blue, Color::Default);
+        console.draw_text(game_state.settings.control_preset.display_name());
 
-            #[cfg(feature = "steam")]
-            if level_pack.steam_level_pack_data().is_some() {
-                console.draw_text(" [");
+        console.reset_color();
+        console.draw_text(" (Toggle with ");
 
-                console.draw_key_input_text("o");
+        console.draw_key_input_text("c");
 
-                console.reset_color();
-                console.draw_text(": open Steam Workshop]");
-            }
+        console.reset_color();
+        console.draw_text(")");
 
-            console.set_cursor_pos(1, y + 2);
-            console.draw_text("Sum of best time   : ");
-            match level_pack.level_pack_best_time_sum() {
-                None => console.draw_text("X:XX:XX:XX.XXX"),
-                Some(best_time_sum) => {
-                    console.draw_text(format!(
-                        "{:01}:{:02}:{:02}:{:02}.{:03}",
-                        best_time_sum/86400000,
-                        (best_time_sum/3600000)%24,
-                        (best_time_sum/60000)%60,
-                        (best_time_sum/1000)%60,
-                        best_time_sum%1000
-                    ));
-                },
+        console.set_cursor_pos(0, 17);
+        console.draw_text("Relaxed mode: ");
+
+        if game_state.settings.relaxed_mode {
+            console.set_color(Color::Green, Color::Default);
+            console.draw_text("Enabled");
+        }else {
+            console.set_color(Color::Red, Color::Default);
+            console.draw_text("Disabled");
+        }
+
+        console.reset_color();
+        console.draw_text(" (Toggle with ");
+
+        console.draw_key_input_text("r");
+
+        console.reset_color();
+        console.draw_text(")");
+
+        console.set_cursor_pos(0, 18);
+        console.draw_text("(Hides the timer and the move counter, no best times are recorded)");
+
+        console.set_cursor_pos(0, 19);
+        console.draw_text("Save folder: ");
+
+        if self.is_editing_save_folder {
+            //Only the end of long paths is shown (Input is ASCII only)
+            let skipped_len = self.save_folder_path.len().saturating_sub(Self::MAX_SAVE_FOLDER_PATH_DISPLAY_LEN);
+
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_fmt(format_args!("> {}", &self.save_folder_path[skipped_len..]));
+
+            if self.save_folder_path.is_empty() {
+                console.set_color(Color::LightBlack, Color::Default);
+                console.draw_text("(Empty: Default folder)");
             }
-            console.set_cursor_pos(1, y + 3);
-            console.draw_text("Sum of best moves  : ");
-            match level_pack.level_pack_best_moves_sum() {
-                None => console.draw_text("XXXXXXX"),
-                Some(best_moves_sum) => console.draw_text(format!("{:07}", best_moves_sum)),
+        }else if game_state.startup_flags().portable() {
+            console.set_color(Color::Red, Color::Default);
+            console.draw_text("Portable mode (Set by startup flag)");
+        }else if game_state.settings.streamer_mode.is_enabled() {
+            //The path usually contains the user name
+            console.set_color(Color::LightBlack, Color::Default);
+            console.draw_text("Hidden (Streamer mode)");
+        }else {
+            console.set_color(Color::Blue, Color::Default);
+            console.draw_text(save_folder::base_save_game_folder().map_or_else(
+                |_| "Unknown".to_string(),
+                |directory| utils::truncate_text(&directory.to_string_lossy(), Self::MAX_SAVE_FOLDER_PATH_DISPLAY_LEN),
+            ));
+
+            console.reset_color();
+            console.draw_text(" (Move with ");
+
+            console.draw_key_input_text("f");
+
+            console.reset_color();
+            console.draw_text(")");
+        }
+
+        console.reset_color();
+
+        #[cfg(not(feature = "steam"))]
+        {
+            console.set_cursor_pos(0, 20);
+            console.draw_text("Profile: ");
+
+            if game_state.settings.streamer_mode.is_enabled() {
+                console.set_color(Color::LightBlack, Color::Default);
+                console.draw_text("Hidden (Streamer mode)");
+            }else {
+                console.set_color(Color::Blue, Color::Default);
+                console.draw_text(profile::display_name(profile::active_profile().as_deref()));
             }
 
-            console.set_cursor_pos(45, y + 3);
-            console.draw_key_input_text("r");
+            console.reset_color();
+            console.draw_text(" (Change with ");
+
+            console.draw_key_input_text("p");
 
             console.reset_color();
-            console.draw_text(": Reset level pack progress");
+            console.draw_text(")");
         }
-    }
 
-    fn update(&mut self, game_state: &mut GameState) {
-        let expected_entry_count = game_state.get_level_pack_count() + if cfg!(feature = "steam") { 3 } else { 2 };
-        if expected_entry_count != self.level_pack_list.elements().len() {
-            self.update_list_elements(game_state);
+        console.set_cursor_pos(0, 21);
+        console.draw_text("Anonymous level stats: ");
+
+        if game_state.settings.telemetry_consent.is_allowed() {
+            console.set_color(Color::Green, Color::Default);
+        }else {
+            console.set_color(Color::Red, Color::Default);
+        }
+        console.draw_text(game_state.settings.telemetry_consent.display_name());
+
+        console.reset_color();
+        console.draw_text(" (Toggle with ");
+
+        console.draw_key_input_text("a");
+
+        console.reset_color();
+        console.draw_text(")");
+
+        //The CLI version only supports the left mouse button
+        if cfg!(feature = "gui") {
+            console.set_cursor_pos(0, 22);
+            console.draw_text("Mouse: ");
+
+            for (i, (name, key, action)) in [
+                ("Right", "m", game_state.settings.mouse_right_button_action.display_name()),
+                ("Middle", "n", game_state.settings.mouse_middle_button_action.display_name()),
+                ("Wheel", "w", game_state.settings.mouse_wheel_action.display_name()),
+            ].into_iter().enumerate() {
+                if i > 0 {
+                    console.reset_color();
+                    console.draw_text(", ");
+                }
+
+                console.reset_color();
+                console.draw_fmt(format_args!("{name} ("));
+
+                console.draw_key_input_text(key);
+
+                console.reset_color();
+                console.draw_text("): ");
+
+                console.set_color(Color::Blue, Color::Default);
+                console.draw_text(action);
+            }
         }
     }
 
     fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
-        if key == Key::ESC {
-            game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+        if self.is_editing_save_folder {
+            match key {
+                key if key.is_ascii() && key.to_ascii().is_some_and(|c| c.is_ascii_graphic() || c == b' ') => {
+                    if self.save_folder_path.len() >= save_folder::MAX_SAVE_GAME_FOLDER_PATH_LEN {
+                        return;
+                    }
 
-            game_state.set_screen(ScreenId::StartMenu);
+                    let _ = write!(self.save_folder_path, "{}", key.to_ascii().unwrap() as char);
+                },
+                Key::DELETE => {
+                    self.save_folder_path.pop();
+                },
+
+                Key::ENTER => {
+                    self.relocate_save_folder(game_state);
+                },
+
+                Key::ESC => {
+                    game_state.play_sound_effect_ui_select();
+
+                    self.stop_editing_save_folder();
+                },
+
+                _ => {},
+            }
 
             return;
         }
 
-        #[cfg(feature = "steam")]
-        if key == Key::O && self.level_pack_list.cursor_index() >= 1 &&
-                let Some(steam_level_pack_data) = game_state.level_packs().get(self.level_pack_list.cursor_index() - 1).and_then(LevelPack::steam_level_pack_data) {
-            let id = steam_level_pack_data.workshop_id();
+        if key == Key::ESC {
+            game_state.play_sound_effect_ui_select();
 
-            game_state.play_sound_effect_ui_dialog_open();
+            let screen_id = game_state.settings_back_screen_id.take().unwrap_or(ScreenId::StartMenu);
+            game_state.set_screen(screen_id);
+        }else if key == Key::X {
+            game_state.play_sound_effect_ui_select();
 
-            game_state.steam_client.friends().activate_game_overlay_to_web_page(&format!("steam://url/CommunityFilePage/{}", id.0));
+            if let Err(err) = game_state.set_and_save_show_session_stats_on_exit(!game_state.settings.show_session_stats_on_exit) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
         }
 
-        if key == Key::R && self.level_pack_list.cursor_index() >= 1 && self.level_pack_list.cursor_index() <= game_state.get_level_pack_count() {
-            let level_pack = game_state.level_packs().get(self.level_pack_list.cursor_index() - 1).unwrap();
+        if cfg!(feature = "cli") && key == Key::T {
+            game_state.play_sound_effect_ui_select();
 
-            game_state.open_dialog(Dialog::new_yes_no(format!(
-                "Do you really want to reset the level pack progress of\n\"{}\"?\n\nThis action can not be undone!",
-                level_pack.name(),
-            )));
+            if let Err(err) = game_state.set_and_save_double_width_tiles(!game_state.settings.double_width_tiles) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
         }
 
-        self.level_pack_list.on_key_press(&mut (), game_state, key);
+        if cfg!(feature = "cli") && key == Key::L {
+            game_state.play_sound_effect_ui_select();
 
-        pub const CODE: [Key; 10] = [
-            Key::UP, Key::UP,
-            Key::DOWN, Key::DOWN,
-            Key::LEFT, Key::RIGHT,
-            Key::LEFT, Key::RIGHT,
-            Key::B, Key::A
-        ];
-        if CODE.get(self.code_index).is_some_and(|k| *k == key) {
-            self.code_index += 1;
+            if let Err(err) = game_state.set_and_save_large_tiles(!game_state.settings.large_tiles) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
+        }
 
-            if self.code_index == CODE.len() {
-                self.code_index = 0;
+        if key == Key::K {
+            game_state.play_sound_effect_ui_select();
 
-                game_state.set_level_pack_index(1);
+            if let Err(err) = game_state.set_and_save_arrow_key_priority(game_state.settings.arrow_key_priority.next_setting()) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
+        }
 
-                #[cfg(feature = "steam")]
-                Achievement::LEVEL_PACK_SECRET_DISCOVERED.unlock(game_state.steam_client.clone());
+        if key == Key::S {
+            game_state.play_sound_effect_ui_select();
 
-                game_state.open_dialog(Dialog::new_ok_secret_found("You have found a secret!"));
+            if let Err(err) = game_state.set_and_save_streamer_mode(game_state.settings.streamer_mode.next_setting()) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
+        }
 
-                if let Err(err) = game_state.on_found_secret() {
-                    game_state.open_dialog(Dialog::new_ok_error(format!("Error: {}", err)));
-                }
+        if key == Key::O {
+            game_state.play_sound_effect_ui_select();
 
-                self.level_pack_list.set_cursor_index(1);
-                game_state.set_level_pack_index(4);
-                game_state.set_screen(ScreenId::SelectLevelPack);
+            if let Err(err) = game_state.set_and_save_overlay_margin(game_state.settings.next_overlay_margin()) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
             }
-        }else {
-            self.code_index = 0;
         }
-    }
 
-    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
-        self.level_pack_list.on_mouse_pressed(&mut (), game_state, column, row);
+        if key == Key::C {
+            game_state.play_sound_effect_ui_select();
 
-        let entry_count = self.level_pack_list.elements().len();
-        let y = 4 + (entry_count/24)*2;
+            if let Err(err) = game_state.set_and_save_control_preset(game_state.settings.control_preset.next_setting()) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
+        }
 
-        #[cfg(feature = "steam")]
-        if row == y + 1 && game_state.level_packs().get(self.level_pack_list.cursor_index() - 1).and_then(LevelPack::steam_level_pack_data).is_some() {
-            let name_len = game_state.level_packs.get(self.level_pack_list.cursor_index() - 1).unwrap().name().len();
+        if cfg!(feature = "gui") && key == Key::V {
+            game_state.play_sound_effect_ui_select();
 
-            let start_x = 22 + name_len + 2;
-            if column >= start_x && column < start_x + 22 {
-                self.on_key_pressed(game_state, Key::O);
+            if let Err(err) = game_state.set_and_save_wall_variation(!game_state.settings.wall_variation) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
             }
         }
 
-        if row == y + 3 && (45..73).contains(&column) && self.level_pack_list.cursor_index() >= 1 &&
-                self.level_pack_list.cursor_index() <= game_state.get_level_pack_count() {
-            self.on_key_pressed(game_state, Key::R);
+        if key == Key::D && !game_state.startup_flags().no_audio() {
+            //The selection cycles through the default device and all available devices
+            let audio_devices = AudioDevices::enumerate();
+            let next_audio_device = match game_state.settings.audio_device() {
+                None => audio_devices.names().first(),
+                Some(audio_device) => audio_devices.names().iter().
+                        position(|name| name == audio_device).
+                        and_then(|index| audio_devices.names().get(index + 1)),
+            };
+
+            if let Err(err) = game_state.set_and_save_audio_device(next_audio_device.cloned()) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
+
+            //The sound effect is played with the new device
+            game_state.play_sound_effect_ui_select();
         }
-    }
 
-    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
-        if selection == DialogSelection::Yes {
-            game_state.set_level_pack_index(self.level_pack_list.cursor_index() - 1);
-            let level_pack = game_state.get_current_level_pack_mut().unwrap();
+        if key == Key::R {
+            game_state.play_sound_effect_ui_select();
 
-            level_pack.set_min_level_not_completed(0);
+            if let Err(err) = game_state.set_and_save_relaxed_mode(!game_state.settings.relaxed_mode) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
+        }
 
-            for level in level_pack.levels_mut() {
-                level.set_best_moves(None);
-                level.set_best_time(None);
+        if key == Key::G {
+            game_state.play_sound_effect_ui_select();
+
+            if let Err(err) = game_state.set_and_save_log_verbosity(game_state.settings.log_verbosity.next_setting()) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
             }
+        }
 
-            level_pack.calculate_stats_sum();
+        if key == Key::A {
+            game_state.play_sound_effect_ui_select();
 
-            if let Err(err) = level_pack.save_save_game(false) {
-                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+            if let Err(err) = game_state.set_and_save_telemetry_consent(game_state.settings.telemetry_consent.next_setting()) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
             }
+        }
 
-            self.update_list_elements(game_state);
+        #[cfg(not(feature = "steam"))]
+        if key == Key::P {
+            game_state.play_sound_effect_ui_select();
+
+            //A paused level is not continued after a profile switch
+            game_state.settings_back_screen_id = None;
+            game_state.pending_snapshot_level_attempt = None;
+
+            game_state.set_screen(ScreenId::SelectProfile);
         }
-    }
 
-    fn on_set_screen(&mut self, game_state: &mut GameState) {
-        self.code_index = 0;
+        if key == Key::F && !game_state.startup_flags().portable() {
+            game_state.play_sound_effect_ui_select();
 
-        self.update_list_elements(game_state);
+            self.is_editing_save_folder = true;
+        }
 
-        if self.level_pack_list.cursor_index() == 0 {
-            //Skip "back" entry and set to first level pack
-            self.level_pack_list.set_cursor_index(1);
-        }else {
-            self.level_pack_list.set_cursor_index(game_state.current_level_pack_index + 1);
+        if cfg!(feature = "gui") && matches!(key, Key::M | Key::N | Key::W) {
+            game_state.play_sound_effect_ui_select();
+
+            let result = match key {
+                Key::M => game_state.set_and_save_mouse_right_button_action(game_state.settings.mouse_right_button_action.next_setting()),
+                Key::N => game_state.set_and_save_mouse_middle_button_action(game_state.settings.mouse_middle_button_action.next_setting()),
+                _ => game_state.set_and_save_mouse_wheel_action(game_state.settings.mouse_wheel_action.next_setting()),
+            };
+
+            if let Err(err) = result {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
         }
+    }
+
+    fn on_text_pasted(&mut self, _: &mut GameState, text: &str) {
+        if self.is_editing_save_folder {
+            utils::append_pasted_text(
+                &mut self.save_folder_path, text, save_folder::MAX_SAVE_GAME_FOLDER_PATH_LEN,
+                |c| c.is_ascii_graphic() || c == ' ',
+            );
+        }
+    }
 
-        game_state.set_background_music_loop(&audio::BACKGROUND_MUSIC_FIELDS_OF_ICE);
+    fn on_mouse_pressed(&mut self, _game_state: &mut GameState, _column: usize, _row: usize) {
+        //TODO
+    }
+
+    fn on_set_screen(&mut self, _: &mut GameState) {
+        self.stop_editing_save_folder();
     }
 }
 
-pub struct ScreenSelectLevel {
-    level_list: UIList,
-    level_preview: bool,
+pub struct ScreenJukebox {
+    cursor_index: usize,
 }
 
-impl ScreenSelectLevel {
+impl ScreenJukebox {
+    pub const SEEK_OFFSET_SECS: i64 = 10;
+
+    const ROWS_PER_PAGE: usize = 5;
+    //The track info box is drawn below the visible tracks
+    const VISIBLE_TRACK_COUNT: usize = 10;
+
     pub fn new() -> Self {
         Self {
-            level_list: UIList::new(
-                Rect::new(0, 1, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT - 1),
-                vec![
-                    UIListElement::new("<<", Color::White, Color::LightBlue),
-                    //[Level Entries]
-                ],
-                Box::new(|_, game_state: &mut GameState, cursor_index: usize| {
-                    if cursor_index == 0 {
-                        game_state.play_sound_effect_ui_select();
-                        game_state.set_screen(ScreenId::SelectLevelPack);
-
-                        return;
-                    }
+            cursor_index: 0,
+        }
+    }
 
-                    let level_index = cursor_index - 1;
+    fn first_visible_index(&self) -> usize {
+        self.cursor_index.saturating_sub(Self::VISIBLE_TRACK_COUNT - 1)
+    }
 
-                    let level_pack = game_state.get_current_level_pack().unwrap();
-                    let min_level_not_completed = level_pack.min_level_not_completed();
+    fn play_or_pause_selected_track(&mut self, game_state: &mut GameState) {
+        if game_state.startup_flags().mute_music() {
+            game_state.open_dialog(Dialog::new_ok_error("Background music is muted by a startup flag!"));
 
-                    if level_index <= min_level_not_completed {
-                        game_state.play_sound_effect_ui_select();
+            return;
+        }
 
-                        game_state.set_level_index(level_index);
-                        game_state.set_screen(ScreenId::InGame);
+        if !game_state.settings.background_music {
+            game_state.open_dialog(Dialog::new_ok_error("Background music is disabled!\nPlease enable it by pressing \"F8\"."));
 
-                        if level_index == min_level_not_completed {
-                            game_state.allow_skip_level = true;
-                        }
-                    }else {
-                        game_state.play_sound_effect_ui_error();
-                    }
-                }),
-            ),
-            level_preview: false,
+            return;
         }
-    }
-
-    fn update_list_elements(&mut self, game_state: &GameState) {
-        let elements = self.level_list.elements_mut();
 
-        //Remove all level entries
-        elements.drain(1..);
+        game_state.play_sound_effect_ui_select();
 
-        let level_pack = game_state.get_current_level_pack().unwrap();
-        let min_level_not_completed = level_pack.min_level_not_completed();
-        for i in 0..level_pack.level_count() {
-            elements.push(UIListElement::new(
-                utils::number_to_string_leading_ascii(2, i as u32 + 1, false),
-                Color::Black,
-                match i.cmp(&min_level_not_completed) {
-                    Ordering::Less => {
-                        if level_pack.levels()[i].best_moves().is_some() {
-                            Color::Green
-                        }else {
-                            Color::Yellow
-                        }
-                    },
-                    Ordering::Equal => Color::Yellow,
-                    Ordering::Greater => Color::Red,
-                },
-            ));
+        let track = audio::BACKGROUND_MUSIC_TRACKS.tracks()[self.cursor_index];
+        if game_state.current_background_music_id() == Some(track.id()) {
+            if game_state.is_background_music_paused() {
+                game_state.resume_background_music();
+            }else {
+                game_state.pause_background_music();
+            }
+        }else {
+            game_state.set_background_music_loop(track);
         }
     }
+}
 
-    fn draw_overview(&self, game_state: &GameState, console: &Console) {
+impl Screen for ScreenJukebox {
+    fn draw(&self, game_state: &GameState, console: &Console) {
         console.reset_color();
         console.set_underline(true);
-        console.draw_text(format!("Select a level (Level pack \"{}\"):", game_state.get_current_level_pack().unwrap().name()));
+        console.draw_text("Jukebox:");
         console.set_underline(false);
 
-        self.level_list.draw(console);
-
-        let entry_count = self.level_list.elements().len();
-
-        //Draw border for best time and best moves
-        let y = 4 + ((entry_count - 1)/24)*2;
+        console.set_cursor_pos(0, 1);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(": Play/Pause, ");
+        console.draw_key_input_text("LEFT");
+        console.reset_color();
+        console.draw_text("/");
+        console.draw_key_input_text("RIGHT");
+        console.reset_color();
+        console.draw_fmt(format_args!(": Seek ({}s)", Self::SEEK_OFFSET_SECS));
 
-        console.set_cursor_pos(0, y);
-        console.set_color(Color::Cyan, Color::Default);
-        console.draw_text(".-------------------------.");
-        for i in 1..4 {
-            console.set_cursor_pos(0, y + i);
-            console.draw_text("|                         |");
-        }
-        console.set_cursor_pos(0, y + 4);
-        console.draw_text("\'-------------------------\'");
+        console.set_cursor_pos(0, 2);
+        console.draw_key_input_text("m");
+        console.reset_color();
+        console.draw_text(": Set as menu music, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back");
 
-        let cursor_index = self.level_list.cursor_index();
-        if cursor_index == 0 {
-            console.reset_color();
-            console.set_cursor_pos(11, y + 2);
-            console.draw_text("Back");
-        }else {
-            //Draw best time and best moves
-            console.reset_color();
-            console.set_cursor_pos(1, y + 1);
-            console.draw_text("Selected level:       ");
-            console.draw_text(format!("{:03}", cursor_index));
+        let current_background_music_id = game_state.current_background_music_id();
+        let is_paused = game_state.is_background_music_paused() || !game_state.is_background_music_enabled();
 
-            let level_pack = game_state.get_current_level_pack().unwrap();
-            let level = level_pack.levels().get(cursor_index - 1).unwrap();
+        let first_visible_index = self.first_visible_index();
+        for (i, track) in audio::BACKGROUND_MUSIC_TRACKS.tracks().iter().
+                enumerate().
+                skip(first_visible_index).
+                take(Self::VISIBLE_TRACK_COUNT) {
+            console.set_cursor_pos(0, i - first_visible_index + 4);
 
-            console.set_cursor_pos(1, y + 2);
-            console.draw_text("Best time     : ");
-            match level.best_time() {
-                None => console.draw_text("XX:XX.XXX"),
-                Some(best_time) => {
-                    console.draw_text(format!(
-                        "{:02}:{:02}.{:03}",
-                        best_time/60000,
-                        (best_time%60000)/1000,
-                        best_time%1000
-                    ));
-                },
-            }
-            console.set_cursor_pos(1, y + 3);
-            console.draw_text("Best moves    :      ");
-            match level.best_moves() {
-                None => console.draw_text("XXXX"),
-                Some(best_moves) => {
-                    console.draw_text(format!("{:04}", best_moves));
-                },
+            if i == self.cursor_index {
+                console.set_color(Color::Yellow, Color::Default);
+                console.draw_text(">");
+            }else {
+                console.draw_text(" ");
             }
 
             console.reset_color();
-            console.set_cursor_pos(29, y + 1);
-            console.draw_text("Press ");
+            if current_background_music_id == Some(track.id()) {
+                console.set_color(Color::LightGreen, Color::Default);
+                console.draw_text(if is_paused { " || " } else { " |> " });
+            }else {
+                console.draw_text("    ");
+            }
 
-            console.draw_key_input_text("p");
+            console.set_color(Color::LightCyan, Color::Default);
+            console.draw_fmt(format_args!("{:35}", track.display_name()));
 
             console.reset_color();
-            console.draw_text(" for level preview");
+            console.draw_text(" [by ");
 
-            if game_state.allow_skip_level && cursor_index - 1 == level_pack.min_level_not_completed() &&
-                    cursor_index < level_pack.level_count()  {
-                console.reset_color();
-                console.set_cursor_pos(29, y + 3);
-                console.draw_text("Press ");
+            console.set_color(Color::LightPink, Color::Default);
+            console.draw_text(track.creator());
 
-                console.draw_key_input_text("n");
+            console.reset_color();
+            console.draw_text("]");
 
-                console.reset_color();
-                console.draw_text(" to skip this level");
+            if game_state.settings.menu_background_music_id() == track.id() {
+                console.set_color(Color::Yellow, Color::Default);
+                console.draw_text(" (Menu)");
             }
         }
-    }
-
-    fn draw_level_preview(&self, game_state: &GameState, console: &Console) {
-        let cursor_index = self.level_list.cursor_index();
 
-        if cursor_index == 1 {
-            console.draw_key_input_text("<");
-
-            console.reset_color();
-            console.draw_text(" Back");
-        }else if cursor_index > 1 {
-            console.draw_key_input_text("<");
+        let track = audio::BACKGROUND_MUSIC_TRACKS.tracks()[self.cursor_index];
 
-            console.reset_color();
-            console.draw_text(format!(" Level {:03}", cursor_index - 1));
+        let y = audio::BACKGROUND_MUSIC_TRACKS.tracks().len().min(Self::VISIBLE_TRACK_COUNT) + 5;
+        console.set_cursor_pos(0, y);
+        console.set_color(Color::Cyan, Color::Default);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 1..6 {
+            console.set_cursor_pos(0, y + i);
+            console.draw_text("|                                                                        |");
         }
+        console.set_cursor_pos(0, y + 6);
+        console.draw_text("\'------------------------------------------------------------------------\'");
 
-        if cursor_index < game_state.get_current_level_pack().unwrap().level_count() {
-            console.reset_color();
-            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 11, 0);
-            console.draw_text(format!("Level {:03} ", cursor_index + 1));
-
-            console.draw_key_input_text(">");
-        }
+        console.set_cursor_pos(2, y + 1);
+        console.set_color(Color::LightCyan, Color::Default);
+        console.draw_text(track.display_name());
 
         console.reset_color();
-        console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 23) as f64 * 0.5) as usize, 0);
-        console.draw_text("Preview (");
+        console.draw_text(" [by ");
 
-        console.draw_key_input_text("p");
+        console.set_color(Color::LightPink, Color::Default);
+        console.draw_text(track.creator());
 
         console.reset_color();
-        console.draw_text(format!(") [Level {:03}]", cursor_index));
-
-        if cursor_index == 0 {
-            let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
-            let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
-
-            console.set_cursor_pos(x, y);
-            console.set_color(Color::Cyan, Color::Default);
-            console.draw_text(".--------------------------------------.");
-            for i in 1..4 {
-                console.set_cursor_pos(x, y + i);
-                console.draw_text("|                                      |");
-            }
-            console.set_cursor_pos(x, y + 4);
-            console.draw_text("\'--------------------------------------\'");
+        console.draw_text("]");
 
-            console.reset_color();
-            console.set_cursor_pos(35, y + 2);
-            console.draw_text("Back");
-        }else {
-            let min_level_not_completed = game_state.get_current_level_pack().as_ref().unwrap().min_level_not_completed();
-            let level = game_state.get_current_level_pack().unwrap().levels()[cursor_index - 1].level();
+        if track.id().is_custom() {
+            console.set_cursor_pos(2, y + 2);
+            console.set_color(Color::LightYellow, Color::Default);
+            console.draw_fmt(format_args!("Custom track from the \"{}\" folder", audio::CUSTOM_MUSIC_FOLDER));
+        }else if let Some(entry) = CreditCategory::BackgroundMusic.entries().iter().
+                find(|entry| entry.name() == track.display_name()) {
+            console.set_cursor_pos(2, y + 2);
+            console.set_color(Color::LightYellow, Color::Default);
+            console.draw_text(entry.license());
 
-            if cursor_index - 1 > min_level_not_completed {
-                let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
-                let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
+            console.set_cursor_pos(2, y + 3);
+            console.set_color(Color::LightBlue, Color::Default);
+            console.set_underline(true);
+            console.draw_text(entry.link());
+            console.set_underline(false);
+        }
 
-                console.set_cursor_pos(x, y);
-                console.set_color(Color::Cyan, Color::Default);
-                console.draw_text(".--------------------------------------.");
-                for i in 1..4 {
-                    console.set_cursor_pos(x, y + i);
-                    console.draw_text("|                                      |");
-                }
-                console.set_cursor_pos(x, y + 4);
-                console.draw_text("\'--------------------------------------\'");
+        console.set_cursor_pos(2, y + 5);
+        if game_state.startup_flags().mute_music() {
+            console.set_color(Color::LightRed, Color::Default);
+            console.draw_text("Background music is muted by a startup flag");
+        }else if !game_state.settings.background_music {
+            console.set_color(Color::LightRed, Color::Default);
+            console.draw_text("Background music is disabled (Toggle with ");
 
-                console.reset_color();
-                console.set_cursor_pos(x + 2, y + 2);
-                console.draw_text(format!("Beat level {:03} to unlock this level.", cursor_index - 1));
-            }else {
-                let x_offset = ((Game::CONSOLE_MIN_WIDTH - level.width()) as f64 * 0.5) as usize;
-                let y_offset = 1;
+            console.draw_key_input_text("F8");
 
-                level.draw(console, x_offset, y_offset, game_state.is_player_background(), None);
-            }
-        }
-    }
-}
+            console.set_color(Color::LightRed, Color::Default);
+            console.draw_text(")");
+        }else if current_background_music_id == Some(track.id()) &&
+                let Some(position) = game_state.background_music_position() {
+            let position = position.as_secs();
 
-impl Screen for ScreenSelectLevel {
-    fn draw(&self, game_state: &GameState, console: &Console) {
-        if self.level_preview {
-            self.draw_level_preview(game_state, console);
-        }else {
-            self.draw_overview(game_state, console);
+            console.reset_color();
+            console.draw_fmt(format_args!(
+                "{}: {:02}:{:02}",
+                if is_paused { "Paused" } else { "Playing" },
+                position/60,
+                position%60,
+            ));
         }
     }
 
     fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
-        if key == Key::ESC {
-            game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+        let navigator = ListNavigator::new(Self::ROWS_PER_PAGE);
+        if let Some(cursor_index) = navigator.navigate(self.cursor_index, audio::BACKGROUND_MUSIC_TRACKS.tracks().len(), key) {
+            game_state.play_sound_effect_ui_select();
 
-            if self.level_preview {
-                self.level_preview = false;
-            }else {
-                game_state.set_screen(ScreenId::SelectLevelPack);
-            }
+            self.cursor_index = cursor_index;
 
             return;
         }
 
-        if key == Key::P {
-            game_state.play_sound_effect_ui_select();
+        match key {
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
 
-            self.level_preview = !self.level_preview;
+                game_state.resume_background_music();
+                game_state.set_screen(ScreenId::StartMenu);
+            },
 
-            return;
-        }
+            Key::LEFT | Key::A => {
+                game_state.seek_background_music(-Self::SEEK_OFFSET_SECS);
+            },
 
-        if key == Key::N && game_state.allow_skip_level &&
-                self.level_list.cursor_index() - 1 == game_state.get_current_level_pack().as_ref().unwrap().min_level_not_completed() &&
-                self.level_list.cursor_index() < game_state.get_current_level_pack().as_ref().unwrap().level_count() {
-            game_state.open_dialog(Dialog::new_yes_no("Do you really want to skip this level?"));
+            Key::RIGHT | Key::D => {
+                game_state.seek_background_music(Self::SEEK_OFFSET_SECS);
+            },
 
-            return;
-        }
+            Key::ENTER | Key::SPACE => {
+                self.play_or_pause_selected_track(game_state);
+            },
 
-        self.level_list.on_key_press(&mut (), game_state, key);
+            Key::M => {
+                game_state.play_sound_effect_ui_select();
+
+                let track_id = audio::BACKGROUND_MUSIC_TRACKS.tracks()[self.cursor_index].id();
+                if let Err(err) = game_state.set_and_save_menu_background_music_id(track_id) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+                }
+            },
+
+            _ => {},
+        }
     }
 
     fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
-        if self.level_preview {
-            if row == 0 {
-                let center_text_start = ((Game::CONSOLE_MIN_WIDTH - 23) as f64 * 0.5) as usize;
-
-                if column < 11 {
-                    self.on_key_pressed(game_state, Key::LEFT);
-                }else if column >= center_text_start && column < center_text_start + 23 {
-                    self.on_key_pressed(game_state, Key::ENTER);
-                }else if column > Game::CONSOLE_MIN_WIDTH - 12 {
-                    self.on_key_pressed(game_state, Key::RIGHT);
-                }
-            }
+        if row == 1 && column < 5 {
+            self.on_key_pressed(game_state, Key::ENTER);
+        }else if row == 2 && column < 1 {
+            self.on_key_pressed(game_state, Key::M);
+        }else if row == 2 && (22..25).contains(&column) {
+            self.on_key_pressed(game_state, Key::ESC);
+        }
 
+        if row < 4 {
             return;
         }
 
-        let element_count = self.level_list.elements().len();
-        let y = 4 + ((element_count - 1)/24)*2;
-        if row == y + 1 && (29..54).contains(&column) {
-            self.on_key_pressed(game_state, Key::P);
-        }else if row == y + 3 && (29..55).contains(&column) {
-            self.on_key_pressed(game_state, Key::N);
+        let track_index = self.first_visible_index() + row - 4;
+        if row - 4 >= Self::VISIBLE_TRACK_COUNT || track_index >= audio::BACKGROUND_MUSIC_TRACKS.tracks().len() {
+            return;
         }
 
-        self.level_list.on_mouse_pressed(&mut (), game_state, column, row);
+        self.cursor_index = track_index;
+        self.play_or_pause_selected_track(game_state);
     }
 
-    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
-        if selection == DialogSelection::Yes {
-            let level_pack = game_state.get_current_level_pack_mut().unwrap();
-            level_pack.set_min_level_not_completed(level_pack.min_level_not_completed() + 1);
-
-            if let Err(err) = level_pack.save_save_game(false) {
-                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
-            }
-
-            game_state.allow_skip_level = false;
-
-            self.level_list.set_cursor_index(self.level_list.cursor_index() + 1);
-            self.update_list_elements(game_state);
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
         }
     }
 
     fn on_set_screen(&mut self, game_state: &mut GameState) {
-        self.update_list_elements(game_state);
+        let selected_track_id = game_state.current_background_music_id().
+                unwrap_or(game_state.settings.menu_background_music_id());
 
-        self.level_list.set_cursor_index(game_state.get_level_index() + 1);
-
-        self.level_preview = false;
+        self.cursor_index = audio::BACKGROUND_MUSIC_TRACKS.tracks().iter().
+                position(|track| track.id() == selected_track_id).
+                unwrap_or(0);
     }
 }
 
-pub struct ScreenInGame {
-    time_start_in_menu: Option<SystemTime>,
-    time_start: Option<SystemTime>,
-    time_millis: u32,
-    time_sec: u32,
-    time_min: u32,
+pub struct ScreenAchievements {}
 
-    animation_first_frame: bool,
-    level: Option<PlayingLevel>,
-
-    show_floor: bool,
+impl ScreenAchievements {
+    const LIST_Y_OFFSET: usize = 3;
+    const PROGRESS_BAR_WIDTH: usize = 42;
 
-    continue_flag: bool,
-    secret_found_flag: bool,
-    game_over_flag: bool,
+    pub fn new() -> Self {
+        Self {}
+    }
 }
 
-impl ScreenInGame {
-    pub const UNDO_HISTORY_SIZE_PLAYING: usize = 10000;
+impl Screen for ScreenAchievements {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        //All achievements are shown as locked if the local achievements are disabled
+        let total_stats = &game_state.total_stats().copied().unwrap_or_default();
+        let unlocked_count = achievements::LOCAL_ACHIEVEMENTS.iter().
+                filter(|achievement| achievement.is_unlocked(total_stats)).
+                count();
 
-    pub fn new() -> Self {
-        Self {
-            time_start_in_menu: Default::default(),
-            time_start: Default::default(),
-            time_millis: Default::default(),
-            time_sec: Default::default(),
-            time_min: Default::default(),
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_fmt(format_args!("Achievements ({}/{}):", unlocked_count, achievements::LOCAL_ACHIEVEMENTS.len()));
+        console.set_underline(false);
 
-            animation_first_frame: false,
-            level: Default::default(),
+        console.set_cursor_pos(0, 1);
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back");
 
-            show_floor: false,
+        for (i, achievement) in achievements::LOCAL_ACHIEVEMENTS.iter().enumerate() {
+            let is_unlocked = achievement.is_unlocked(total_stats);
 
-            continue_flag: Default::default(),
-            secret_found_flag: Default::default(),
-            game_over_flag: Default::default(),
-        }
-    }
+            console.set_cursor_pos(0, Self::LIST_Y_OFFSET + i * 2);
+            if is_unlocked {
+                console.set_color(Color::LightGreen, Color::Default);
+                console.draw_text("[X] ");
+            }else {
+                console.draw_text("[ ] ");
+            }
 
-    pub fn start_level(&mut self, level: &Level) {
-        //Reset stats
-        self.time_start = None;
-        self.time_millis = 0;
-        self.time_sec = 0;
-        self.time_min = 0;
+            console.set_color(Color::LightCyan, Color::Default);
+            console.draw_text(achievement.name());
+            console.reset_color();
+            console.draw_fmt(format_args!(": {}", achievement.description()));
 
-        self.continue_flag = false;
-        self.game_over_flag = false;
+            console.set_cursor_pos(4, Self::LIST_Y_OFFSET + i * 2 + 1);
+            draw_progress_bar(console, achievement.progress(total_stats), achievement.target(), Self::PROGRESS_BAR_WIDTH);
+            console.draw_fmt(format_args!(" {}/{}", achievement.progress(total_stats), achievement.target()));
+        }
+    }
 
-        self.animation_first_frame = false;
-        self.level = Some(PlayingLevel::new(level, Self::UNDO_HISTORY_SIZE_PLAYING).unwrap());
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if key == Key::ESC {
+            game_state.play_sound_effect_ui_select();
 
-        self.show_floor = false;
+            game_state.set_screen(ScreenId::StartMenu);
+        }
     }
 
-    fn draw_tutorial_level_text(&self, game_state: &GameState, console: &Console) {
-        //Draw special help text for tutorial levels (tutorial pack and tutorial levels in special pack)
-        if game_state.get_level_pack_index() == 0 { //Built-in Tutorial pack
-            console.reset_color();
-            match game_state.current_level_index {
-                0 => {
-                    if self.continue_flag {
-                        console.set_cursor_pos(13, 8);
-                        console.draw_text("Press ");
-
-                        console.draw_key_input_text("ENTER");
-                        console.reset_color();
-                        console.draw_text("/");
-                        console.draw_key_input_text("SPACEBAR");
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if row == 1 && column < 3 {
+            self.on_key_pressed(game_state, Key::ESC);
+        }
+    }
+}
 
-                        console.reset_color();
-                        console.draw_text(" to go to the next level...");
-                    }else {
-                        console.set_cursor_pos(13, 8);
-                        console.draw_text("Use ");
+#[cfg(not(feature = "steam"))]
+pub struct ScreenSelectProfile {
+    //None is the default profile
+    profiles: Vec<Option<String>>,
+    cursor_index: usize,
 
-                        console.draw_key_input_text("Arrow Keys");
+    is_creating_new_profile: bool,
+    new_profile_name: String,
+}
 
-                        console.reset_color();
-                        console.draw_text(" (< ^ > v) or ");
+#[cfg(not(feature = "steam"))]
+impl ScreenSelectProfile {
+    const LIST_Y_OFFSET: usize = 3;
+    const ROWS_PER_PAGE: usize = 5;
 
-                        console.draw_key_input_text("WASD");
+    pub fn new() -> Self {
+        Self {
+            profiles: vec![None],
+            cursor_index: 0,
 
-                        console.reset_color();
-                        console.draw_text(" keys to move...");
-                    }
-                },
-                1 => {
-                    console.set_cursor_pos(16, 8);
-                    console.draw_text("Boxes (");
+            is_creating_new_profile: false,
+            new_profile_name: String::new(),
+        }
+    }
 
-                    Tile::Box.draw(console, false, false);
+    fn update_profiles(&mut self, game_state: &mut GameState) {
+        self.profiles = vec![None];
 
-                    console.reset_color();
-                    console.draw_text(") must be placed on ");
+        match profile::profile_names() {
+            Ok(profile_names) => {
+                self.profiles.extend(profile_names.into_iter().
+                        take(profile::MAX_PROFILE_COUNT).
+                        map(Some));
+            },
 
-                    console.set_color(Color::LightRed, Color::Default);
-                    console.draw_text("all");
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot load profiles: {}", err)));
+            },
+        }
+    }
 
-                    console.reset_color();
-                    console.draw_text(" goals (");
+    fn create_new_profile(&mut self, game_state: &mut GameState) {
+        if let Err(err) = profile::create_profile(&self.new_profile_name) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot create profile: {}", err)));
 
-                    Tile::Goal.draw(console, false, false);
+            return;
+        }
 
-                    console.reset_color();
-                    console.draw_text(")");
-                },
-                2 => {
-                    console.set_cursor_pos(14, 8);
-                    console.draw_text("Some boxes (");
+        game_state.play_sound_effect_ui_select();
 
-                    Tile::BoxInGoal.draw(console, false, false);
+        self.update_profiles(game_state);
+        self.cursor_index = self.profiles.iter().
+                position(|profile| profile.as_deref() == Some(self.new_profile_name.as_str())).
+                unwrap_or(0);
 
-                    console.reset_color();
-                    console.draw_text(") might already be in a goal (");
+        self.is_creating_new_profile = false;
+        self.new_profile_name = String::new();
+    }
 
-                    Tile::Goal.draw(console, false, false);
+    fn select_profile(&mut self, game_state: &mut GameState) {
+        game_state.play_sound_effect_ui_select();
 
-                    console.reset_color();
-                    console.draw_text(")");
-                },
-                3 => {
-                    console.set_cursor_pos(14, 8);
-                    console.draw_text("Not all boxes (");
+        let profile = self.profiles[self.cursor_index].clone();
+        if profile == profile::active_profile() {
+            game_state.set_screen(ScreenId::StartMenu);
+        }else {
+            game_state.switch_profile(profile);
+        }
+    }
+}
 
-                    Tile::Box.draw(console, false, false);
+#[cfg(not(feature = "steam"))]
+impl Screen for ScreenSelectProfile {
+    fn draw(&self, _game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_text("Select a profile:");
+        console.set_underline(false);
 
-                    console.reset_color();
-                    console.draw_text(") must be in a goal (");
+        console.set_cursor_pos(0, 1);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(": Select, ");
+        console.draw_key_input_text("n");
+        console.reset_color();
+        console.draw_text(": New profile, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back");
 
-                    Tile::Goal.draw(console, false, false);
+        let active_profile = profile::active_profile();
 
-                    console.reset_color();
-                    console.draw_text(") to win");
-                },
-                4 => {
-                    console.set_cursor_pos(5, 8);
-                    console.draw_text("One-way doors (");
+        for (i, profile) in self.profiles.iter().
+                enumerate() {
+            console.set_cursor_pos(0, i + Self::LIST_Y_OFFSET);
 
-                    Tile::OneWayLeft.draw(console, false, false);
+            if i == self.cursor_index {
+                console.set_color(Color::Yellow, Color::Default);
+                console.draw_text(">");
+            }else {
+                console.draw_text(" ");
+            }
 
-                    console.reset_color();
-                    console.draw_text(" ");
+            console.set_color(Color::LightCyan, Color::Default);
+            console.draw_fmt(format_args!(" {}", profile::display_name(profile.as_deref())));
 
-                    Tile::OneWayUp.draw(console, false, false);
+            if *profile == active_profile {
+                console.set_color(Color::LightGreen, Color::Default);
+                console.draw_text(" (Active)");
+            }
+        }
 
-                    console.reset_color();
-                    console.draw_text(" ");
+        if self.is_creating_new_profile {
+            let y = Self::LIST_Y_OFFSET + profile::MAX_PROFILE_COUNT + 3;
 
-                    Tile::OneWayRight.draw(console, false, false);
+            console.reset_color();
+            console.set_cursor_pos(0, y);
+            console.draw_text("Enter a new profile name:");
 
-                    console.reset_color();
-                    console.draw_text(" ");
+            console.set_cursor_pos(0, y + 1);
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_fmt(format_args!("> {}", &self.new_profile_name));
+        }
+    }
 
-                    Tile::OneWayDown.draw(console, false, false);
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if self.is_creating_new_profile {
+            match key {
+                key if key.is_ascii() && (key.is_alphanumeric() || key == Key::UNDERSCORE || key == Key::MINUS) => {
+                    if self.new_profile_name.len() >= profile::MAX_PROFILE_NAME_LEN {
+                        return;
+                    }
 
-                    console.reset_color();
-                    console.draw_text(") can only be entered from the opened side");
+                    let _ = write!(self.new_profile_name, "{}", key.to_ascii().unwrap() as char);
+                },
+                Key::DELETE => {
+                    self.new_profile_name.pop();
                 },
-                5 => {
-                    if self.game_over_flag {
-                        console.set_cursor_pos(6, 8);
-                        console.draw_text("Press ");
-
-                        console.draw_key_input_text("ENTER");
-                        console.reset_color();
-                        console.draw_text("/");
-                        console.draw_key_input_text("SPACEBAR");
-
-                        console.reset_color();
-                        console.draw_text(" to go back to the level selection screen");
-                    }else {
-                        console.set_cursor_pos(8, 8);
-                        console.draw_text("Boxes (");
-
-                        Tile::Box.draw(console, false, false);
-
-                        console.reset_color();
-                        console.draw_text(") cannot be moved through one-way doors (");
-
-                        Tile::OneWayLeft.draw(console, false, false);
-
-                        console.reset_color();
-                        console.draw_text(" ");
-
-                        Tile::OneWayUp.draw(console, false, false);
 
-                        console.reset_color();
-                        console.draw_text(" ");
+                Key::ENTER => {
+                    if self.new_profile_name.len() < profile::MIN_PROFILE_NAME_LEN {
+                        game_state.open_dialog(Dialog::new_ok_error(format!(
+                            "Profile name must have at least {} characters!",
+                            profile::MIN_PROFILE_NAME_LEN,
+                        )));
 
-                        Tile::OneWayRight.draw(console, false, false);
+                        return;
+                    }
 
-                        console.reset_color();
-                        console.draw_text(" ");
+                    self.create_new_profile(game_state);
+                },
 
-                        Tile::OneWayDown.draw(console, false, false);
+                Key::ESC => {
+                    game_state.play_sound_effect_ui_select();
 
-                        console.reset_color();
-                        console.draw_text(")");
-                    }
+                    self.is_creating_new_profile = false;
+                    self.new_profile_name = String::new();
                 },
+
                 _ => {},
             }
-        }else if game_state.get_level_pack_index() == 1 { //Built-in Main pack
-            console.reset_color();
-            if game_state.current_level_index < 3 {
-                let start_y = if game_state.current_level_index < 2 { 8 } else { 11 };
-
-                console.set_cursor_pos(28, start_y);
-                console.draw_key_input_text("z");
-                console.reset_color();
-                console.draw_text("/");
-                console.draw_key_input_text("u");
-
-                console.reset_color();
-                console.draw_text(": Undo, ");
 
-                console.draw_key_input_text("y");
+            return;
+        }
 
-                console.reset_color();
-                console.draw_text(": Redo");
+        let navigator = ListNavigator::new(Self::ROWS_PER_PAGE);
+        if let Some(cursor_index) = navigator.navigate(self.cursor_index, self.profiles.len(), key) {
+            game_state.play_sound_effect_ui_select();
 
-                console.set_cursor_pos(29, start_y + 1);
-                console.draw_key_input_text("r");
+            self.cursor_index = cursor_index;
 
-                console.reset_color();
-                console.draw_text(": Restart Level");
-            }
-        }else if game_state.get_level_pack_index() == 2 { //Built-in Special pack
-            console.reset_color();
-            match game_state.current_level_index {
-                0 => {
-                    console.set_cursor_pos(18, 8);
-                    console.draw_text("Keys (");
+            return;
+        }
 
-                    Tile::Key.draw(console, false, false);
+        match key {
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
 
-                    console.reset_color();
-                    console.draw_text(") can be used to open doors (");
+                game_state.set_screen(ScreenId::StartMenu);
+            },
 
-                    Tile::LockedDoor.draw(console, false, false);
+            Key::ENTER | Key::SPACE => {
+                self.select_profile(game_state);
+            },
 
-                    console.reset_color();
-                    console.draw_text(")");
-                },
-                1 => {
-                    console.set_cursor_pos(19, 8);
-                    console.draw_text("Every key (");
+            Key::N => {
+                if self.profiles.len() > profile::MAX_PROFILE_COUNT {
+                    game_state.open_dialog(Dialog::new_ok_error(format!(
+                        "Cannot create new profiles (Max profile count ({}) reached)",
+                        profile::MAX_PROFILE_COUNT,
+                    )));
 
-                    Tile::Key.draw(console, false, false);
+                    return;
+                }
 
-                    console.reset_color();
-                    console.draw_text(") can open any door (");
+                game_state.play_sound_effect_ui_select();
 
-                    Tile::LockedDoor.draw(console, false, false);
+                self.is_creating_new_profile = true;
+            },
 
-                    console.reset_color();
-                    console.draw_text(")");
-                },
-                2 => {
-                    console.set_cursor_pos(21, 8);
-                    console.draw_text("Keys (");
+            _ => {},
+        }
+    }
 
-                    Tile::KeyInGoal.draw(console, false, false);
+    fn on_text_pasted(&mut self, _: &mut GameState, text: &str) {
+        if self.is_creating_new_profile {
+            utils::append_pasted_text(
+                &mut self.new_profile_name, text, profile::MAX_PROFILE_NAME_LEN,
+                profile::is_valid_profile_name_char,
+            );
+        }
+    }
 
-                    console.reset_color();
-                    console.draw_text(") might be in a goal (");
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if self.is_creating_new_profile {
+            return;
+        }
 
-                    Tile::Goal.draw(console, false, false);
+        if row == 1 && column < 5 {
+            self.on_key_pressed(game_state, Key::ENTER);
+        }else if row == 1 && column == 15 {
+            self.on_key_pressed(game_state, Key::N);
+        }else if row == 1 && (31..34).contains(&column) {
+            self.on_key_pressed(game_state, Key::ESC);
+        }
 
-                    console.reset_color();
-                    console.draw_text(")");
-                },
-                13 => {
-                    console.set_cursor_pos(23, 8);
-                    console.draw_text("Holes (");
+        if row < Self::LIST_Y_OFFSET {
+            return;
+        }
 
-                    Tile::Hole.draw(console, false, false);
+        let profile_index = row - Self::LIST_Y_OFFSET;
+        if profile_index >= self.profiles.len() {
+            return;
+        }
 
-                    console.reset_color();
-                    console.draw_text(") cannot be crossed");
-                },
-                14 => {
-                    console.set_cursor_pos(21, 8);
-                    console.draw_text("Filled holes (");
+        self.cursor_index = profile_index;
+        self.select_profile(game_state);
+    }
 
-                    Tile::BoxInHole.draw(console, false, false);
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.is_creating_new_profile = false;
+        self.new_profile_name = String::new();
 
-                    console.reset_color();
-                    console.draw_text(") can be crossed");
-                },
-                15 => {
-                    console.set_cursor_pos(23, 8);
-                    console.draw_text("Boxes (");
+        self.update_profiles(game_state);
 
-                    Tile::Box.draw(console, false, false);
+        let active_profile = profile::active_profile();
+        self.cursor_index = self.profiles.iter().
+                position(|profile| *profile == active_profile).
+                unwrap_or(0);
+    }
+}
 
-                    console.reset_color();
-                    console.draw_text(") can fill holes (");
+pub struct ScreenSelectLevelPack {
+    level_pack_list: UIList,
+    code_index: usize,
 
-                    Tile::Hole.draw(console, false, false);
+    suggested_level_pack_index: Option<usize>,
+}
 
-                    console.reset_color();
-                    console.draw_text(")");
-                },
-                16 => {
-                    console.set_cursor_pos(13, 8);
-                    console.draw_text("Keys (");
+impl ScreenSelectLevelPack {
+    pub fn new() -> Self {
+        Self {
+            level_pack_list: UIList::new(
+                Rect::new(0, 1, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT - 1),
+                vec![
+                    UIListElement::new("<<", Color::White, Color::LightBlue),
+                    //[Level Pack Entries]
+                    UIListElement::new(" +", Color::White, Color::LightBlue),
+                    UIListElement::new("[]", Color::White, Color::LightBlue),
+                ],
+                Box::new(|_, game_state: &mut GameState, cursor_index: usize| {
+                    game_state.play_sound_effect_ui_select();
 
-                    Tile::Key.draw(console, false, false);
+                    if cursor_index == 0 {
+                        game_state.set_screen(ScreenId::StartMenu);
+                    }else if cursor_index > game_state.get_level_pack_count() {
+                        if cursor_index == game_state.get_level_pack_count() + 2 {
+                            //Steam Workshop entry
+                            game_state.set_screen(ScreenId::WorkshopBrowser);
+                        }else {
+                            //Level Pack Editor entry
+                            game_state.set_level_pack_index(game_state.get_level_pack_count());
 
-                    console.reset_color();
-                    console.draw_text(") cannot fill holes (");
+                            game_state.set_screen(ScreenId::SelectLevelPackEditor);
+                        }
+                    }else {
+                        game_state.set_level_pack_index(cursor_index - 1);
 
-                    Tile::Hole.draw(console, false, false);
+                        //Set selected level
+                        let level_pack = game_state.get_current_level_pack().unwrap();
+                        let min_level_not_completed = level_pack.min_level_not_completed();
+                        if min_level_not_completed >= level_pack.unlocked_level_count() {
+                            let first_skipped_level = level_pack.levels().
+                                    iter().
+                                    enumerate().
+                                    find(|(_, level)| level.best_moves().is_none()).
+                                    map(|(index, _)| index).
+                                    unwrap_or(0);
 
-                    console.reset_color();
-                    console.draw_text(") and will be lost");
-                },
-                22 => {
-                    console.set_cursor_pos(2, 8);
-                    console.draw_text("Fragile Floor (");
+                            game_state.set_level_index(first_skipped_level);
+                        }else {
+                            game_state.set_level_index(min_level_not_completed);
+                        }
 
-                    Tile::FragileFloor.draw(console, false, false);
+                        game_state.set_screen(ScreenId::SelectLevel);
+                    }
+                }),
+            ),
+            code_index: 0,
 
-                    console.reset_color();
-                    console.draw_text(") turns into a hole (");
+            suggested_level_pack_index: None,
+        }
+    }
 
-                    Tile::Hole.draw(console, false, false);
+    fn update_list_elements(&mut self, game_state: &GameState) {
+        self.suggested_level_pack_index = game_state.player_stats().
+                and_then(|player_stats| recommend::suggest_level_pack(game_state.level_packs(), player_stats));
 
-                    console.reset_color();
-                    console.draw_text(") once crossed by the player (");
+        let elements = self.level_pack_list.elements_mut();
 
-                    Tile::Player.draw(console, false, false);
+        //Remove all level pack entries
+        let mut trailing_elements = elements.drain(1..).
+                rev().
+                take(2).
+                rev().
+                collect::<Vec<_>>();
 
-                    console.reset_color();
-                    console.draw_text(")");
+        for (i, level_pack) in game_state.level_packs().iter().enumerate() {
+            elements.push(UIListElement::new(
+                utils::number_to_string_leading_ascii(2, i as u32 + 1, false),
+                Color::Black,
+                if self.suggested_level_pack_index == Some(i) {
+                    Color::Cyan
+                }else if level_pack.level_pack_best_moves_sum().is_some() {
+                    Color::Green
+                }else {
+                    Color::Yellow
+                },
+            ));
+        }
 
-                    console.set_cursor_pos(23, 10);
-                    console.draw_text("Press ");
+        elements.append(&mut trailing_elements);
+    }
 
-                    console.draw_key_input_text("q");
+    fn open_level_pack_info_dialog(game_state: &mut GameState, level_pack_index: usize) {
+        let level_pack = game_state.level_packs().get(level_pack_index).unwrap();
 
-                    console.reset_color();
-                    console.draw_text(" to view floor tiles");
-                },
-                28 => {
-                    console.set_cursor_pos(17, 10);
-                    console.draw_text("Ice (");
+        let last_played = match level_pack.read_last_played() {
+            Ok(last_played) => last_played,
 
-                    Tile::Ice.draw(console, false, false);
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot load last played time: {}", err)));
 
-                    console.reset_color();
-                    console.draw_text(") causes the player (");
+                return;
+            },
+        };
 
-                    Tile::Player.draw(console, false, false);
+        let mut message = String::new();
+        let _ = writeln!(message, "{} ({})", level_pack.localized_name(game_state.settings.language), level_pack.id());
+        let _ = writeln!(message, "Author: {}", level_pack.author().unwrap_or("Unknown"));
 
-                    console.reset_color();
-                    console.draw_text("), keys (");
+        let description = level_pack.localized_description(game_state.settings.language);
+        if !description.is_empty() {
+            message += "\n";
+            for description_line in description {
+                let _ = writeln!(message, "{}", description_line);
+            }
+        }
 
-                    Tile::KeyOnIce.draw(console, false, false);
+        message += "\n";
+        if let Some(background_music_id) = level_pack.background_music_id() {
+            let background_music = audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id);
 
-                    console.reset_color();
-                    console.draw_text("),");
+            let _ = writeln!(message, "Background music: \"{}\" by {}", background_music.display_name(), background_music.creator());
+        }else {
+            message += "Background music: None\n";
+        }
 
-                    console.set_cursor_pos(26, 11);
-                    console.draw_text("and boxes (");
+        #[cfg(feature = "steam")]
+        if let Some(steam_level_pack_data) = level_pack.steam_level_pack_data() {
+            let _ = writeln!(message, "Steam Workshop: steam://url/CommunityFilePage/{}", steam_level_pack_data.workshop_id().0);
+        }
 
-                    Tile::BoxOnIce.draw(console, false, false);
+        for required_workshop_id in level_pack.required_workshop_ids() {
+            let _ = writeln!(message, "Requires: steam://url/CommunityFilePage/{}", required_workshop_id);
+        }
 
-                    console.reset_color();
-                    console.draw_text(") to slide");
-                },
-                29 => {
-                    console.set_cursor_pos(14, 8);
-                    console.draw_text("If a box (");
+        let _ = write!(message, "Levels: {}", level_pack.level_count());
+        if level_pack.bonus_level_index().is_some() {
+            message += " (Including a bonus level)";
+        }
+        message += "\n";
 
-                    Tile::BoxOnIce.draw(console, false, false);
+        let completed_level_count = level_pack.levels().iter().
+                filter(|level| level.best_moves().is_some()).
+                count();
 
-                    console.reset_color();
-                    console.draw_text(") or a key (");
+        message += "\n";
+        let _ = writeln!(message, "Completed levels: {}/{}", completed_level_count, level_pack.level_count());
 
-                    Tile::KeyOnIce.draw(console, false, false);
+        let secret_level_count = level_pack.secret_level_indices().count();
+        if secret_level_count > 0 && level_pack.bonus_level_index().is_some() {
+            let found_secret_count = level_pack.secret_level_indices().
+                    filter(|level_index| level_pack.is_secret_found(*level_index)).
+                    count();
 
-                    console.reset_color();
-                    console.draw_text(") is pushed on ice (");
+            let _ = writeln!(message, "Secrets found: {}/{}", found_secret_count, secret_level_count);
+        }
 
-                    Tile::Ice.draw(console, false, false);
+        let _ = writeln!(message, "Sum of best time: {}", level_pack.level_pack_best_time_sum().
+                map_or_else(|| "-".to_string(), utils::time_to_string));
+        let _ = writeln!(message, "Sum of best moves: {}", level_pack.level_pack_best_moves_sum().
+                map_or_else(|| "-".to_string(), |best_moves_sum| best_moves_sum.to_string()));
 
-                    console.reset_color();
-                    console.draw_text(")");
+        let _ = write!(message, "Last played: {}", last_played.
+                map_or_else(|| "Never".to_string(), |last_played| utils::days_ago_to_string(trash::current_time(), last_played)));
 
-                    console.set_cursor_pos(21, 9);
-                    console.draw_text("the player (");
+        game_state.open_dialog(Dialog::new_ok(message));
+    }
+}
 
-                    Tile::Player.draw(console, false, false);
+impl Screen for ScreenSelectLevelPack {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_text("Select a level pack:");
+        console.set_underline(false);
 
-                    console.reset_color();
-                    console.draw_text(") will stop sliding");
+        if self.level_pack_list.cursor_index() >= 1 && self.level_pack_list.cursor_index() <= game_state.get_level_pack_count() {
+            console.set_cursor_pos(24, 0);
+            console.draw_text("Info (");
 
-                    console.set_cursor_pos(23, 11);
-                    console.draw_text("Press ");
+            console.draw_key_input_text("i");
 
-                    console.draw_key_input_text("q");
+            console.reset_color();
+            console.draw_text(")");
+        }
 
-                    console.reset_color();
-                    console.draw_text(" to view floor tiles");
-                },
-                30 => {
-                    console.set_cursor_pos(23, 11);
-                    console.draw_text("Press ");
+        if let Some(suggested_level_pack_index) = self.suggested_level_pack_index {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 22, 0);
+            console.draw_text("Suggested next (");
 
-                    console.draw_key_input_text("q");
+            console.draw_key_input_text("g");
 
-                    console.reset_color();
-                    console.draw_text(" to view floor tiles");
-                },
-                _ => {},
-            }
-        }else if game_state.get_level_pack_index() == 4 && game_state.found_secret_main_level_pack { //Built-in Secret pack
             console.reset_color();
-            #[expect(clippy::single_match)]
-            match game_state.current_level_index {
-                0 => {
-                    console.set_cursor_pos(35, 7);
-                    console.draw_text("???");
-                },
+            console.draw_text("): ");
 
-                _ => {},
-            }
+            console.set_color(Color::Black, Color::Cyan);
+            console.draw_fmt(format_args!("{}", utils::LeadingAsciiNumber::new(2, suggested_level_pack_index as u32 + 1, false)));
+            console.reset_color();
         }
-    }
 
-    fn handle_move_result(&mut self, game_state: &mut GameState, move_result: MoveResult) {
-        #[cfg(feature = "steam")]
-        let steam_client = game_state.steam_client.clone();
+        self.level_pack_list.draw(console);
 
-        let current_level_index = game_state.current_level_index;
-        let Some(level_pack) = game_state.get_current_level_pack_mut() else {
-            return;
-        };
+        let entry_count = self.level_pack_list.elements().len();
 
-        match move_result {
-            MoveResult::Valid { has_won, secret_found, sound_effect } => {
-                self.time_start.get_or_insert_with(SystemTime::now);
+        //Draw border for best time and best moves
+        let y = 4 + (entry_count/24)*2;
 
-                if secret_found {
-                    self.game_over_flag = true;
-                    self.secret_found_flag = true;
-                }
+        console.set_cursor_pos(0, y);
+        console.set_color(Color::Cyan, Color::Default);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 1..4 {
+            console.set_cursor_pos(0, y + i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, y + 4);
+        console.draw_text("\'------------------------------------------------------------------------\'");
+        console.reset_color();
 
-                if has_won {
-                    self.continue_flag = true;
+        let cursor_index = self.level_pack_list.cursor_index();
+        if cursor_index == 0 {
+            console.set_cursor_pos(35, y + 2);
+            console.draw_text("Back");
+        }else if cursor_index > game_state.get_level_pack_count() {
+            if cursor_index == game_state.get_level_pack_count() + 2 {
+                //Steam Workshop entry
+                #[cfg(feature = "steam")]
+                {
+                    console.set_cursor_pos(14, y + 1);
+                    console.draw_text("Download level packs from the Steam Workshop");
+
+                    console.set_cursor_pos(8, y + 3);
+                    console.set_color(Color::LightBlack, Color::Default);
+                    console.draw_text("You must relaunch the game after downloading level packs.");
+                }
 
-                    //Update best scores
-                    let time = self.time_millis as u64 + 1000 * self.time_sec as u64 + 60000 * self.time_min as u64;
-                    let moves = self.level.as_ref().unwrap().current_move_index() as u32;
+                #[cfg(not(feature = "steam"))]
+                {
+                    console.set_cursor_pos(17, y + 1);
+                    console.draw_text("Browse level packs on the Steam Workshop");
 
-                    level_pack.update_stats(current_level_index, time, moves);
+                    console.set_cursor_pos(11, y + 3);
+                    console.set_color(Color::LightBlack, Color::Default);
+                    console.draw_text("Downloaded level packs can only be played on Steam.");
+                }
+            }else {
+                //Level Pack Editor entry
+                console.set_cursor_pos(23, y + 2);
+                console.draw_text("Create or edit level packs");
+            }
+        }else {
+            //Draw sum of best time and sum of best moves
+            console.set_cursor_pos(1, y + 1);
+            console.draw_fmt(format_args!("Selected level pack: {}", game_state.level_packs().get(cursor_index - 1).unwrap().localized_name(game_state.settings.language)));
 
-                    if current_level_index >= level_pack.min_level_not_completed() {
-                        level_pack.set_min_level_not_completed(current_level_index + 1);
-                    }
+            let level_pack = game_state.level_packs.get(cursor_index - 1).unwrap();
 
-                    #[cfg(feature = "steam")]
-                    if level_pack.id() == "main" && current_level_index == level_pack.level_count() - 1 && moves < 150 {
-                        Achievement::LEVEL_PACK_MAIN_FINAL_LEVEL_CHALLENGE.unlock(steam_client.clone());
-                    }
+            #[cfg(feature = "steam")]
+            if level_pack.steam_level_pack_data().is_some() {
+                console.draw_text(" [");
 
-                    #[cfg(feature = "steam")]
-                    if level_pack.level_pack_best_moves_sum().is_some() && level_pack.level_pack_best_time_sum().is_some() {
-                        match level_pack.id() {
-                            "tutorial" => {
-                                Achievement::LEVEL_PACK_TUTORIAL_COMPLETED.unlock(steam_client.clone());
+                console.draw_key_input_text("o");
 
-                                if level_pack.level_pack_best_time_sum().unwrap() < 6000 {
-                                    Achievement::LEVEL_PACK_TUTORIAL_FAST.unlock(steam_client.clone());
-                                }
-                            },
+                console.reset_color();
+                console.draw_text(": open Steam Workshop]");
+            }
 
-                            "main" => {
-                                Achievement::LEVEL_PACK_MAIN_COMPLETED.unlock(steam_client.clone());
-                            },
+            console.set_cursor_pos(1, y + 2);
+            console.draw_text("Sum of best time   : ");
+            match level_pack.level_pack_best_time_sum() {
+                None => console.draw_text("X:XX:XX:XX.XXX"),
+                Some(best_time_sum) => {
+                    console.draw_fmt(format_args!(
+                        "{:01}:{:02}:{:02}:{:02}.{:03}",
+                        best_time_sum/86400000,
+                        (best_time_sum/3600000)%24,
+                        (best_time_sum/60000)%60,
+                        (best_time_sum/1000)%60,
+                        best_time_sum%1000
+                    ));
+                },
+            }
+            console.set_cursor_pos(1, y + 3);
+            console.draw_text("Sum of best moves  : ");
+            match level_pack.level_pack_best_moves_sum() {
+                None => console.draw_text("XXXXXXX"),
+                Some(best_moves_sum) => console.draw_fmt(format_args!("{:07}", best_moves_sum)),
+            }
 
-                            "special" => {
-                                Achievement::LEVEL_PACK_SPECIAL_COMPLETED.unlock(steam_client.clone());
-                            },
+            //Shows if a built-in level pack was replaced by a patch
+            if let Some(source) = game_state.level_pack_source(level_pack.id()) {
+                console.set_cursor_pos(45, y + 2);
+                console.draw_text("Source: ");
 
-                            "demon" => {
-                                Achievement::LEVEL_PACK_DEMON_COMPLETED.unlock(steam_client.clone());
-                            },
+                if matches!(source, LevelPackSource::Patch { .. }) {
+                    console.set_color(Color::Yellow, Color::Default);
+                }
+                console.draw_fmt(format_args!("{}", source));
+                console.reset_color();
+            }
 
-                            "secret" => {
-                                Achievement::LEVEL_PACK_SECRET_COMPLETED.unlock(steam_client.clone());
-                            },
+            console.set_cursor_pos(45, y + 3);
+            console.draw_key_input_text("r");
 
-                            _ => {},
-                        }
+            console.reset_color();
+            console.draw_text(": Reset level pack progress");
+        }
+    }
 
-                        if level_pack.steam_level_pack_data().is_some() {
-                            Achievement::STEAM_WORKSHOP_LEVEL_PACK_COMPLETED.unlock(steam_client.clone());
-                        }
-                    }
+    fn update(&mut self, game_state: &mut GameState) {
+        let expected_entry_count = game_state.get_level_pack_count() + 3;
+        if expected_entry_count != self.level_pack_list.elements().len() {
+            self.update_list_elements(game_state);
+        }
 
-                    if let Err(err) = level_pack.save_save_game(false) {
-                        game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
-                    }
+        #[cfg(feature = "gui")]
+        {
+            //The thumbnail level of the selected level pack is shown below the stats box (The last row is kept free for toasts)
+            let cursor_index = self.level_pack_list.cursor_index();
+            let row = 4 + (self.level_pack_list.elements().len()/24)*2 + 5;
+            let height = (Game::CONSOLE_MIN_HEIGHT - 1).saturating_sub(row);
+
+            let level_pack_thumbnail_area = (cursor_index >= 1 && cursor_index <= game_state.get_level_pack_count() && height >= 3).
+                    then(|| LevelPackThumbnailArea {
+                        level_pack_index: cursor_index - 1,
+
+                        column: 1,
+                        row,
+                        width: Game::CONSOLE_MIN_WIDTH - 2,
+                        height,
+                    });
+            game_state.set_level_pack_thumbnail_area(level_pack_thumbnail_area);
+        }
+    }
 
-                    game_state.play_sound_effect(audio::LEVEL_COMPLETE_EFFECT);
-                }
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if key == Key::ESC {
+            game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
 
-                game_state.play_sound_effect(audio::STEP_EFFECT);
+            game_state.set_screen(ScreenId::StartMenu);
 
-                if let Some(sound_effect) = sound_effect {
-                    game_state.play_level_sound_effect(sound_effect);
-                }
-            },
+            return;
+        }
 
-            MoveResult::Invalid => {
-                game_state.play_sound_effect(audio::NO_PATH_EFFECT);
-            },
+        #[cfg(feature = "steam")]
+        if key == Key::O && self.level_pack_list.cursor_index() >= 1 &&
+                let Some(steam_level_pack_data) = game_state.level_packs().get(self.level_pack_list.cursor_index() - 1).and_then(LevelPack::steam_level_pack_data) {
+            let id = steam_level_pack_data.workshop_id();
 
-            MoveResult::Animation { sound_effect, .. } => {
-                if self.animation_first_frame {
-                    game_state.play_sound_effect(audio::STEP_EFFECT);
-                }
+            game_state.play_sound_effect_ui_dialog_open();
 
-                if let Some(sound_effect) = sound_effect {
-                    game_state.play_level_sound_effect(sound_effect);
-                }
-            },
+            game_state.steam_client.friends().activate_game_overlay_to_web_page(&format!("steam://url/CommunityFilePage/{}", id.0));
         }
 
-        if self.secret_found_flag {
-            #[cfg(feature = "steam")]
-            Achievement::LEVEL_PACK_SECRET_DISCOVERED.unlock(steam_client.clone());
+        if key == Key::I && self.level_pack_list.cursor_index() >= 1 && self.level_pack_list.cursor_index() <= game_state.get_level_pack_count() {
+            game_state.play_sound_effect_ui_dialog_open();
 
-            game_state.open_dialog(Dialog::new_ok_secret_found("You have found a secret!"));
+            Self::open_level_pack_info_dialog(game_state, self.level_pack_list.cursor_index() - 1);
 
-            if let Err(err) = game_state.on_found_secret() {
-                game_state.open_dialog(Dialog::new_ok_error(format!("Error: {}", err)));
-            }
+            return;
         }
-    }
-}
 
-impl Screen for ScreenInGame {
-    fn draw(&self, game_state: &GameState, console: &Console) {
-        console.reset_color();
-        console.draw_text(format!("Pack: {:02}", game_state.get_level_pack_index() + 1));
+        if key == Key::G && let Some(suggested_level_pack_index) = self.suggested_level_pack_index {
+            game_state.play_sound_effect_ui_select();
 
-        console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 9) as f64 * 0.25) as usize, 0);
-        console.draw_text("Level: ");
-        console.draw_text(utils::number_to_string_leading_ascii(2, game_state.current_level_index as u32 + 1, true));
-
-        console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 11) as f64 * 0.75) as usize, 0);
-        console.draw_text(format!("Moves: {:04}", self.level.as_ref().unwrap().current_move_index()));
-
-        console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 15, 0);
-        console.draw_text(format!(
-            "Time: {:02}:{:02}.{:03}",
-            self.time_min,
-            self.time_sec,
-            self.time_millis,
-        ));
+            self.level_pack_list.set_cursor_index(suggested_level_pack_index + 1);
 
-        if self.continue_flag {
-            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 16) as f64 * 0.5) as usize, 0);
-            console.draw_text("Level completed!");
-        }else if self.game_over_flag {
-            if self.secret_found_flag {
-                console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 13) as f64 * 0.5) as usize, 0);
-                console.draw_text("Secret found!");
-            }else {
-                console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 13) as f64 * 0.5) as usize, 0);
-                console.draw_text("You have won!");
-            }
-        }else if self.show_floor {
-            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 14) as f64 * 0.5) as usize, 0);
-            console.draw_text("Show tiles (");
-            console.draw_key_input_text("q");
-            console.reset_color();
-            console.draw_text(")");
+            return;
         }
 
-        if let Some(playing_level) = self.level.as_ref() {
-            let level = &playing_level.current_playing_level().0;
+        if key == Key::R && self.level_pack_list.cursor_index() >= 1 && self.level_pack_list.cursor_index() <= game_state.get_level_pack_count() {
+            let level_pack = game_state.level_packs().get(self.level_pack_list.cursor_index() - 1).unwrap();
 
-            let x_offset = ((Game::CONSOLE_MIN_WIDTH - level.width()) as f64 * 0.5) as usize;
-            let y_offset = 1;
+            game_state.open_dialog(Dialog::new_yes_no(format!(
+                "Do you really want to reset the level pack progress of\n\"{}\"?\n\nThis action can not be undone!",
+                level_pack.localized_name(game_state.settings.language),
+            )));
+        }
 
-            if self.show_floor {
-                level.draw_floor(console, x_offset, y_offset, game_state.is_player_background(), playing_level.original_level(), None);
-            }else {
-                level.draw(console, x_offset, y_offset, game_state.is_player_background(), None);
-            }
+        self.level_pack_list.on_key_press(&mut (), game_state, key);
 
-            self.draw_tutorial_level_text(game_state, console);
-        }
-    }
+        pub const CODE: [Key; 10] = [
+            Key::UP, Key::UP,
+            Key::DOWN, Key::DOWN,
+            Key::LEFT, Key::RIGHT,
+            Key::LEFT, Key::RIGHT,
+            Key::B, Key::A
+        ];
+        if CODE.get(self.code_index).is_some_and(|k| *k == key) {
+            self.code_index += 1;
 
-    fn update(&mut self, game_state: &mut GameState) {
-        if game_state.is_dialog_opened() || self.game_over_flag || self.continue_flag {
-            return;
-        }
+            if self.code_index == CODE.len() {
+                self.code_index = 0;
+
+                game_state.set_level_pack_index(1);
 
-        if let Some(ref time_start) = self.time_start {
-            let time_current = SystemTime::now();
+                #[cfg(feature = "steam")]
+                Achievement::LEVEL_PACK_SECRET_DISCOVERED.unlock(game_state.steam_client.clone());
 
-            let diff = time_current.duration_since(*time_start).
-                    expect("Time manipulation detected (Start time is in the future)!").
-                    as_millis();
+                game_state.open_dialog(Dialog::new_ok_secret_found("You have found a secret!"));
 
-            self.time_millis = (diff % 1000) as u32;
-            self.time_sec = (diff / 1000 % 60) as u32;
-            self.time_min = (diff / 1000 / 60 % 60) as u32;
+                if let Err(err) = game_state.on_found_secret() {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Error: {}", err)));
+                }
 
-            if self.time_min >= 60 {
-                self.time_millis = 999;
-                self.time_sec = 59;
-                self.time_min = 59;
+                self.level_pack_list.set_cursor_index(1);
+                game_state.set_level_pack_index(4);
+                game_state.set_screen(ScreenId::SelectLevelPack);
             }
+        }else {
+            self.code_index = 0;
         }
     }
 
-    fn animate(&mut self, game_state: &mut GameState) {
-        if game_state.is_dialog_opened() || self.game_over_flag || self.continue_flag {
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if row == 0 && column >= Game::CONSOLE_MIN_WIDTH - 22 {
+            self.on_key_pressed(game_state, Key::G);
+
             return;
         }
 
-        if let Some(playing_level) = &mut self.level &&
-                playing_level.is_playing_animation() && !self.animation_first_frame {
-            let move_result = playing_level.continue_animation();
-            self.handle_move_result(game_state, move_result);
+        if row == 0 && (24..32).contains(&column) {
+            self.on_key_pressed(game_state, Key::I);
+
+            return;
         }
-        self.animation_first_frame = false;
-    }
 
-    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
-        if key == Key::ESC {
-            if self.game_over_flag {
-                self.continue_flag = false;
-                self.game_over_flag = false;
+        self.level_pack_list.on_mouse_pressed(&mut (), game_state, column, row);
 
-                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+        let entry_count = self.level_pack_list.elements().len();
+        let y = 4 + (entry_count/24)*2;
 
-                game_state.set_screen(ScreenId::SelectLevel);
+        #[cfg(feature = "steam")]
+        if row == y + 1 && game_state.level_packs().get(self.level_pack_list.cursor_index() - 1).and_then(LevelPack::steam_level_pack_data).is_some() {
+            let name_len = game_state.level_packs.get(self.level_pack_list.cursor_index() - 1).unwrap().localized_name(game_state.settings.language).len();
 
-                return;
+            let start_x = 22 + name_len + 2;
+            if column >= start_x && column < start_x + 22 {
+                self.on_key_pressed(game_state, Key::O);
             }
+        }
 
-            self.time_start_in_menu = Some(SystemTime::now());
-
-            game_state.open_dialog(Dialog::new_yes_no("Back to level selection?"));
+        if row == y + 3 && (45..73).contains(&column) && self.level_pack_list.cursor_index() >= 1 &&
+                self.level_pack_list.cursor_index() <= game_state.get_level_pack_count() {
+            self.on_key_pressed(game_state, Key::R);
+        }
+    }
 
-            return;
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.level_pack_list.on_key_press(&mut (), game_state, key);
         }
+    }
 
-        if self.game_over_flag {
-            if key == Key::ENTER || key == Key::SPACE {
-                self.continue_flag = false;
-                self.game_over_flag = false;
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        if selection == DialogSelection::Yes {
+            game_state.set_level_pack_index(self.level_pack_list.cursor_index() - 1);
+            let save_writer = game_state.save_writer.clone();
+            let level_pack = game_state.get_current_level_pack_mut().unwrap();
 
-                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+            level_pack.set_min_level_not_completed(0);
+            level_pack.clear_found_secrets();
 
-                game_state.set_screen(ScreenId::SelectLevel);
+            for level in level_pack.levels_mut() {
+                level.set_best_moves(None);
+                level.set_best_time(None);
+                level.set_previous_best(None);
             }
 
-            return;
-        }
-
-        let current_level_index = game_state.current_level_index;
-        let Some(level_pack) = game_state.get_current_level_pack_mut() else {
-            return;
-        };
-
-        //Reset
-        if key == Key::R {
-            let should_play_sound_effect = self.level.as_ref().unwrap().current_move_index() > 0 &&
-                    ((self.time_min * 60) + self.time_sec) * 1000 + self.time_millis > 50;
-
-            self.start_level(level_pack.levels()[current_level_index].level());
+            level_pack.calculate_stats_sum();
 
-            if should_play_sound_effect {
-                game_state.play_sound_effect(audio::LEVEL_RESET);
+            if let Err(err) = level_pack.save_save_game_in_background(&save_writer) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
             }
 
-            return;
+            self.update_list_elements(game_state);
         }
+    }
 
-        if key == Key::Q {
-            game_state.play_sound_effect_ui_select();
-            self.show_floor = !self.show_floor;
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.code_index = 0;
 
-            return;
+        self.update_list_elements(game_state);
+
+        if self.level_pack_list.cursor_index() == 0 {
+            //Skip "back" entry and set to first level pack
+            self.level_pack_list.set_cursor_index(1);
+        }else {
+            self.level_pack_list.set_cursor_index(game_state.current_level_pack_index + 1);
         }
 
-        //Level end (Prevent movement)
-        if self.continue_flag {
-            if key == Key::ENTER || key == Key::SPACE {
-                self.continue_flag = false;
+        game_state.set_menu_background_music_loop();
+    }
+}
 
-                //All levels completed
-                if current_level_index + 1 == level_pack.level_count() {
-                    self.game_over_flag = true;
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum LevelSortMode {
+    #[default]
+    LevelNumber,
+    ParReached,
+    SlowestTime,
+    MoveEfficiency,
+    UnplayedFirst,
+    /// Unlocked levels are sorted in a random order of the shuffle seed of the level pack
+    Shuffled,
+}
 
-                    game_state.play_sound_effect(audio::LEVEL_PACK_COMPLETE_EFFECT);
+impl LevelSortMode {
+    fn next(self) -> Self {
+        match self {
+            LevelSortMode::LevelNumber => LevelSortMode::ParReached,
+            LevelSortMode::ParReached => LevelSortMode::SlowestTime,
+            LevelSortMode::SlowestTime => LevelSortMode::MoveEfficiency,
+            LevelSortMode::MoveEfficiency => LevelSortMode::UnplayedFirst,
+            LevelSortMode::UnplayedFirst => LevelSortMode::Shuffled,
+            LevelSortMode::Shuffled => LevelSortMode::LevelNumber,
+        }
+    }
 
-                    return;
-                }else {
-                    game_state.current_level_index += 1;
-                }
+    fn name(self) -> &'static str {
+        match self {
+            LevelSortMode::LevelNumber => "Level number",
+            LevelSortMode::ParReached => "Par reached",
+            LevelSortMode::SlowestTime => "Slowest time",
+            LevelSortMode::MoveEfficiency => "Move efficiency",
+            LevelSortMode::UnplayedFirst => "Unplayed first",
+            LevelSortMode::Shuffled => "Shuffled",
+        }
+    }
 
-                self.start_level(game_state.get_current_level_pack().unwrap().levels()[game_state.current_level_index].level());
-            }
+    /// Returns the level indices of the provided level pack in presentation order
+    ///
+    /// Levels which need the most work are sorted first, locked levels are always sorted last
+    fn sorted_level_indices(self, level_pack: &LevelPack, shuffle_seed: u64) -> Vec<usize> {
+        if self == LevelSortMode::Shuffled {
+            let shuffled_level_indices = level_pack.shuffled_level_indices(shuffle_seed);
+            let locked_level_indices = (0..level_pack.level_count()).
+                    filter(|level_index| !shuffled_level_indices.contains(level_index)).
+                    collect::<Vec<_>>();
 
-            return;
+            return [shuffled_level_indices, locked_level_indices].concat();
         }
 
-        //Prevent movement during animation
-        if self.level.as_mut().unwrap().is_playing_animation() {
-            //Allow undo while animation is playing
-            if key == Key::U || key == Key::Z {
-                let level = self.level.as_mut().unwrap().cancel_animation_and_undo_move();
-                if level.is_some() {
-                    game_state.play_sound_effect(audio::UNDO_REDO_EFFECT);
-                }
-            }
+        let mut level_indices = (0..level_pack.level_count()).collect::<Vec<_>>();
 
-            return;
+        if self == LevelSortMode::LevelNumber {
+            return level_indices;
         }
 
-        if key == Key::U || key == Key::Z {
-            let level = self.level.as_mut().unwrap().undo_move();
-            if level.is_some() {
-                game_state.play_sound_effect(audio::UNDO_REDO_EFFECT);
-            }
+        let min_level_not_completed = level_pack.min_level_not_completed();
+        level_indices.sort_by_key(|&level_index| {
+            let level = &level_pack.levels()[level_index];
 
-            return;
-        }else if key == Key::Y {
-            let level = self.level.as_mut().unwrap().redo_move();
-            if level.is_some() {
-                game_state.play_sound_effect(audio::UNDO_REDO_EFFECT);
-            }
+            let sort_key = match self {
+                LevelSortMode::LevelNumber | LevelSortMode::Shuffled => 0,
 
-            return;
-        }
+                LevelSortMode::ParReached => {
+                    if level.par_moves().is_none() && level.par_time().is_none() {
+                        //Levels without par values are sorted after all levels with par values
+                        3
+                    }else {
+                        level.par_moves().zip(level.best_moves()).
+                                is_some_and(|(par_moves, best_moves)| best_moves <= par_moves) as u64 +
+                                level.par_time().zip(level.best_time()).
+                                        is_some_and(|(par_time, best_time)| best_time <= par_time) as u64
+                    }
+                },
 
-        let direction = match key {
-            Key::W | Key::UP => Some(Direction::Up),
-            Key::A | Key::LEFT => Some(Direction::Left),
-            Key::S | Key::DOWN => Some(Direction::Down),
-            Key::D | Key::RIGHT => Some(Direction::Right),
+                LevelSortMode::SlowestTime => {
+                    level.best_time().map_or(u64::MAX, |best_time| u64::MAX - 1 - best_time)
+                },
 
-            _ => None,
-        };
+                LevelSortMode::MoveEfficiency => {
+                    //Ratio of best moves to par moves in per mill (Worst ratio first)
+                    level.par_moves().zip(level.best_moves()).
+                            map_or(u64::MAX, |(par_moves, best_moves)| {
+                                u64::MAX - 1 - best_moves as u64 * 1000 / (par_moves as u64).max(1)
+                            })
+                },
 
-        if let Some(direction) = direction {
-            let move_result = self.level.as_mut().unwrap().move_player(direction);
-            if move_result.is_animation() {
-                self.animation_first_frame = true;
-            }
-            self.handle_move_result(game_state, move_result);
-        }
-    }
+                LevelSortMode::UnplayedFirst => level.best_moves().is_some() as u64,
+            };
 
-    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
-        if self.secret_found_flag {
-            self.continue_flag = false;
-            self.game_over_flag = false;
-            self.secret_found_flag = false;
+            (level_index > min_level_not_completed, sort_key)
+        });
 
-            //Set level pack selection to secret level pack
-            game_state.set_level_pack_index(4);
-            game_state.set_screen(ScreenId::SelectLevelPack);
+        level_indices
+    }
+}
 
-            return;
-        }
+pub struct ScreenWorkshopBrowser {
+    filter: WorkshopFilter,
+    cursor_index: usize,
+}
 
-        if selection == DialogSelection::Yes {
-            self.continue_flag = false;
-            self.game_over_flag = false;
+impl ScreenWorkshopBrowser {
+    const LIST_Y_OFFSET: usize = 3;
+    //Sort order, difficulty tag, and gameplay tags
+    const ROW_COUNT: usize = 2 + workshop::GAMEPLAY_TAGS.len();
 
-            game_state.set_screen(ScreenId::SelectLevel);
-        }else if selection == DialogSelection::No {
-            self.on_continue(game_state);
+    pub fn new() -> Self {
+        Self {
+            filter: WorkshopFilter::new(),
+            cursor_index: 0,
         }
     }
 
-    fn on_pause(&mut self, _: &mut GameState) {
-        self.time_start_in_menu = Some(SystemTime::now());
-    }
+    fn change_selected_filter(&mut self, game_state: &mut GameState, is_reverse: bool) {
+        game_state.play_sound_effect_ui_select();
 
-    fn on_continue(&mut self, _: &mut GameState) {
-        if self.game_over_flag || self.continue_flag || self.time_start.is_none() || self.time_start_in_menu.is_none() {
-            return;
+        match self.cursor_index {
+            0 => self.filter.set_sort_order(self.filter.sort_order().next()),
+            1 if is_reverse => self.filter.select_previous_difficulty_tag(),
+            1 => self.filter.select_next_difficulty_tag(),
+            index => self.filter.toggle_gameplay_tag(workshop::GAMEPLAY_TAGS[index - 2]),
         }
-
-        let diff = SystemTime::now().duration_since(self.time_start_in_menu.take().unwrap()).
-                expect("Time manipulation detected (Start time is in the future)!");
-
-        self.time_start = self.time_start.map(|time_start| time_start + diff);
     }
+}
 
-    fn on_set_screen(&mut self, game_state: &mut GameState) {
-        self.start_level(game_state.get_current_level_pack().as_ref().unwrap().levels().get(
-            game_state.get_level_index()).unwrap().level());
+impl Screen for ScreenWorkshopBrowser {
+    fn draw(&self, _game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_text("Browse the Steam Workshop:");
+        console.set_underline(false);
 
+        console.set_cursor_pos(0, 1);
         #[cfg(feature = "steam")]
-        if game_state.get_current_level_pack().unwrap().steam_level_pack_data().is_some() {
-            Achievement::STEAM_WORKSHOP_LEVEL_PACK_PLAYED.unlock(game_state.steam_client.clone());
+        {
+            console.draw_key_input_text("ENTER");
+            console.reset_color();
+            console.draw_text(": Open, ");
         }
-
-        if let Some(background_music_id) = game_state.get_current_level_pack().as_ref().unwrap().background_music_id() {
-            game_state.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id));
-        }else {
-            game_state.stop_background_music();
+        console.draw_key_input_text("LEFT");
+        console.reset_color();
+        console.draw_text("/");
+        console.draw_key_input_text("RIGHT");
+        console.reset_color();
+        console.draw_text(": Change filter, ");
+        #[cfg(feature = "steam")]
+        {
+            console.draw_key_input_text("m");
+            console.reset_color();
+            console.draw_text(": Subscriptions, ");
         }
-    }
-}
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back");
 
-pub struct ScreenSelectLevelPackEditor {
-    level_pack_editor_list: UIList<bool>,
+        for i in 0..Self::ROW_COUNT {
+            console.set_cursor_pos(0, i + Self::LIST_Y_OFFSET);
 
-    is_exporting_level_pack: bool,
-    is_deleting_level_pack: bool,
+            if i == self.cursor_index {
+                console.set_color(Color::Yellow, Color::Default);
+                console.draw_text(">");
+            }else {
+                console.draw_text(" ");
+            }
 
-    is_creating_new_level_pack: bool,
-    new_level_pack_id: String,
-}
+            console.set_color(Color::LightCyan, Color::Default);
+            match i {
+                0 => {
+                    console.draw_text(" Sort by        : ");
+                    console.reset_color();
+                    console.draw_text(self.filter.sort_order().display_name());
+                },
 
-impl ScreenSelectLevelPackEditor {
-    pub fn new() -> Self {
-        Self {
-            level_pack_editor_list: UIList::new(
-                Rect::new(0, 1, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT - 1),
-                vec![
-                    UIListElement::new("<<", Color::White, Color::LightBlue),
-                    //[Level Pack Editor Entries]
+                1 => {
+                    console.draw_text(" Difficulty     : ");
+                    console.reset_color();
+                    console.draw_text(self.filter.difficulty_tag().unwrap_or("Any"));
+                },
+
+                index => {
+                    let tag = workshop::GAMEPLAY_TAGS[index - 2];
+
+                    console.draw_fmt(format_args!(" {:15}: ", format!("Tag \"{}\"", tag)));
+                    console.reset_color();
+                    if self.filter.has_gameplay_tag(tag) {
+                        console.set_color(Color::Green, Color::Default);
+                        console.draw_text("Required");
+                    }else {
+                        console.draw_text("-");
+                    }
+                },
+            }
+        }
+
+        //The Steam Workshop can only be opened in a web browser without the Steam overlay
+        #[cfg(not(feature = "steam"))]
+        {
+            let url = self.filter.browse_url();
+            let url_lines = url.as_bytes().chunks(Game::CONSOLE_MIN_WIDTH - 4).collect::<Vec<_>>();
+
+            let y = Self::LIST_Y_OFFSET + Self::ROW_COUNT + 1;
+            console.set_cursor_pos(0, y);
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_text(".------------------------------------------------------------------------.");
+            for i in 1..url_lines.len() + 3 {
+                console.set_cursor_pos(0, y + i);
+                console.draw_text("|                                                                        |");
+            }
+            console.set_cursor_pos(0, y + url_lines.len() + 3);
+            console.draw_text("\'------------------------------------------------------------------------\'");
+
+            console.reset_color();
+            console.set_cursor_pos(2, y + 1);
+            console.draw_text("Open this URL in a web browser:");
+
+            console.set_color(Color::LightBlue, Color::Default);
+            console.set_underline(true);
+            for (i, line) in url_lines.into_iter().enumerate() {
+                console.set_cursor_pos(2, y + i + 2);
+                console.draw_text(String::from_utf8_lossy(line));
+            }
+            console.set_underline(false);
+        }
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        let navigator = ListNavigator::new(Self::ROW_COUNT);
+        if let Some(cursor_index) = navigator.navigate(self.cursor_index, Self::ROW_COUNT, key) {
+            game_state.play_sound_effect_ui_select();
+
+            self.cursor_index = cursor_index;
+
+            return;
+        }
+
+        match key {
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
+
+                game_state.set_screen(ScreenId::SelectLevelPack);
+            },
+
+            Key::LEFT | Key::A => {
+                self.change_selected_filter(game_state, true);
+            },
+
+            Key::RIGHT | Key::D | Key::SPACE => {
+                self.change_selected_filter(game_state, false);
+            },
+
+            #[cfg(feature = "steam")]
+            Key::ENTER => {
+                game_state.play_sound_effect_ui_dialog_open();
+
+                game_state.steam_client.friends().activate_game_overlay_to_web_page(&self.filter.browse_url());
+            },
+
+            #[cfg(feature = "steam")]
+            Key::M => {
+                game_state.play_sound_effect_ui_select();
+
+                game_state.set_screen(ScreenId::WorkshopSubscriptions);
+            },
+
+            _ => {},
+        }
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, _column: usize, row: usize) {
+        if row < Self::LIST_Y_OFFSET {
+            return;
+        }
+
+        let index = row - Self::LIST_Y_OFFSET;
+        if index < Self::ROW_COUNT {
+            self.cursor_index = index;
+            self.change_selected_filter(game_state, false);
+        }
+    }
+
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
+        }
+    }
+
+    fn on_set_screen(&mut self, _game_state: &mut GameState) {
+        self.cursor_index = 0;
+    }
+}
+
+#[cfg(feature = "steam")]
+pub struct ScreenWorkshopSubscriptions {
+    subscriptions: Vec<WorkshopSubscription>,
+    cursor_index: usize,
+    last_refresh: Instant,
+    //The item which should be unsubscribed after the confirmation dialog
+    pending_unsubscribe_workshop_id: Option<PublishedFileId>,
+}
+
+#[cfg(feature = "steam")]
+impl ScreenWorkshopSubscriptions {
+    const LIST_Y_OFFSET: usize = 3;
+    //The last row is kept free for toasts
+    const VISIBLE_ROW_COUNT: usize = Game::CONSOLE_MIN_HEIGHT - Self::LIST_Y_OFFSET - 1;
+    const MAX_NAME_LEN: usize = 32;
+
+    //The download states are refreshed while the screen is open
+    const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn new() -> Self {
+        Self {
+            subscriptions: Vec::new(),
+            cursor_index: 0,
+            last_refresh: Instant::now(),
+            pending_unsubscribe_workshop_id: None,
+        }
+    }
+
+    fn first_visible_index(&self) -> usize {
+        self.cursor_index.saturating_sub(Self::VISIBLE_ROW_COUNT - 1)
+    }
+
+    fn refresh(&mut self, game_state: &GameState) {
+        self.subscriptions = steam::workshop_subscriptions(&game_state.steam_client);
+        self.cursor_index = self.cursor_index.min(self.subscriptions.len().saturating_sub(1));
+        self.last_refresh = Instant::now();
+    }
+
+    /// Returns the name of the loaded level pack of the item (Items which were subscribed after the start are not loaded yet)
+    fn subscription_name(game_state: &GameState, subscription: &WorkshopSubscription) -> String {
+        let level_pack_id = format!("workshop:{}", subscription.workshop_id().0);
+
+        game_state.level_packs().iter().
+                find(|level_pack| level_pack.id() == level_pack_id).
+                map_or_else(
+                    || format!("Workshop item {}", subscription.workshop_id().0),
+                    |level_pack| level_pack.name().to_string(),
+                )
+    }
+}
+
+#[cfg(feature = "steam")]
+impl Screen for ScreenWorkshopSubscriptions {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_text("Steam Workshop subscriptions:");
+        console.set_underline(false);
+
+        console.set_cursor_pos(0, 1);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(": Download again, ");
+        console.draw_key_input_text("DELETE");
+        console.reset_color();
+        console.draw_text(": Unsubscribe, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back");
+
+        if self.subscriptions.is_empty() {
+            console.set_cursor_pos(0, Self::LIST_Y_OFFSET);
+            console.set_color(Color::LightBlack, Color::Default);
+            console.draw_text("No Steam Workshop level packs are subscribed.");
+
+            return;
+        }
+
+        let first_visible_index = self.first_visible_index();
+        for (i, subscription) in self.subscriptions.iter().
+                enumerate().
+                skip(first_visible_index).
+                take(Self::VISIBLE_ROW_COUNT) {
+            console.set_cursor_pos(0, Self::LIST_Y_OFFSET + i - first_visible_index);
+
+            if i == self.cursor_index {
+                console.set_color(Color::Yellow, Color::Default);
+                console.draw_text(">");
+            }else {
+                console.draw_text(" ");
+            }
+
+            let name = Self::subscription_name(game_state, subscription);
+            console.set_color(Color::LightCyan, Color::Default);
+            console.draw_fmt(format_args!(" {:32}", utils::truncate_text(&name, Self::MAX_NAME_LEN)));
+
+            console.set_color(Color::LightBlack, Color::Default);
+            console.draw_fmt(format_args!(
+                " {:>10} {:10} ",
+                subscription.size_on_disk().map_or_else(|| "-".to_string(), crate::utils::byte_count_to_string_with_binary_prefix),
+                subscription.last_updated().map_or_else(|| "-".to_string(), |last_updated| crate::utils::unix_time_to_date_string(last_updated as u64)),
+            ));
+
+            if subscription.is_downloading() {
+                console.set_color(Color::LightBlue, Color::Default);
+                console.draw_text("Downloading");
+            }else if subscription.is_update_available() {
+                console.set_color(Color::Yellow, Color::Default);
+                console.draw_text("Update available");
+            }else if subscription.is_installed() {
+                console.set_color(Color::Green, Color::Default);
+                console.draw_text("Installed");
+            }else {
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("Not installed");
+            }
+        }
+    }
+
+    fn update(&mut self, game_state: &mut GameState) {
+        let unsubscribe_results = steam::take_unsubscribe_results();
+        for (workshop_id, result) in unsubscribe_results.iter() {
+            match result {
+                Ok(()) => game_state.show_toast("Unsubscribed (The level pack is removed after a restart)"),
+
+                Err(err) => game_state.open_dialog(Dialog::new_ok_error(format!(
+                    "Cannot unsubscribe from the workshop item {}: {}",
+                    workshop_id.0, err,
+                ))),
+            }
+        }
+
+        if !unsubscribe_results.is_empty() || self.last_refresh.elapsed() >= Self::REFRESH_INTERVAL {
+            self.refresh(game_state);
+        }
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        let navigator = ListNavigator::new(Self::VISIBLE_ROW_COUNT);
+        if let Some(cursor_index) = navigator.navigate(self.cursor_index, self.subscriptions.len(), key) {
+            game_state.play_sound_effect_ui_select();
+
+            self.cursor_index = cursor_index;
+
+            return;
+        }
+
+        match key {
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
+
+                game_state.set_screen(ScreenId::WorkshopBrowser);
+            },
+
+            Key::ENTER if !self.subscriptions.is_empty() => {
+                let workshop_id = self.subscriptions[self.cursor_index].workshop_id();
+                if let Err(err) = steam::redownload_workshop_item(&game_state.steam_client, workshop_id) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot download: {}", err)));
+
+                    return;
+                }
+
+                game_state.play_sound_effect_ui_select();
+                game_state.show_toast("Download started");
+
+                self.refresh(game_state);
+            },
+
+            Key::DELETE if !self.subscriptions.is_empty() => {
+                let subscription = &self.subscriptions[self.cursor_index];
+                let message = format!(
+                    "Do you really want to unsubscribe from \"{}\"?\n\nThe level pack is removed after the game was restarted.",
+                    Self::subscription_name(game_state, subscription),
+                );
+                self.pending_unsubscribe_workshop_id = Some(subscription.workshop_id());
+
+                game_state.open_dialog(Dialog::new_yes_no(message));
+            },
+
+            _ => {},
+        }
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, _column: usize, row: usize) {
+        if row < Self::LIST_Y_OFFSET {
+            return;
+        }
+
+        let index = self.first_visible_index() + row - Self::LIST_Y_OFFSET;
+        if index < self.subscriptions.len() {
+            game_state.play_sound_effect_ui_select();
+
+            self.cursor_index = index;
+        }
+    }
+
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
+        }
+    }
+
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        let Some(workshop_id) = self.pending_unsubscribe_workshop_id.take() else {
+            return;
+        };
+
+        if selection == DialogSelection::Yes {
+            steam::unsubscribe_workshop_item(&game_state.steam_client, workshop_id);
+        }
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.cursor_index = 0;
+        self.pending_unsubscribe_workshop_id = None;
+
+        self.refresh(game_state);
+    }
+}
+
+pub struct ScreenSelectLevel {
+    level_list: UIList<Vec<usize>>,
+    level_preview: bool,
+    show_tile_legend: bool,
+
+    sort_mode: LevelSortMode,
+    //List position (Without the back entry) -> Level index
+    level_order: Vec<usize>,
+    suggested_level_index: Option<usize>,
+
+    //Level pack ID -> Seed of the shuffled sort mode
+    shuffle_seeds: HashMap<String, u64>,
+    is_editing_shuffle_seed: bool,
+    shuffle_seed_str: String,
+
+    is_confirming_locked_level: bool,
+    //Level index of the level whose previous best should be restored
+    pending_restore_previous_best_level_index: Option<usize>,
+    is_asking_telemetry_consent: bool,
+    #[cfg(feature = "steam")]
+    is_confirming_missing_required_level_packs: bool,
+    //IDs of level packs for which the player declined to open the missing required level packs
+    #[cfg(feature = "steam")]
+    ignored_missing_required_level_pack_ids: BTreeSet<String>,
+}
+
+impl ScreenSelectLevel {
+    /// Remix seeds are small to allow the same remix to be played again
+    const MAX_REMIX_SEED: u64 = 999;
+    /// Shuffle seeds are small to allow friends to enter the seed of a shuffled level order
+    const MAX_SHUFFLE_SEED: u64 = 9999;
+
+    pub fn new() -> Self {
+        Self {
+            level_list: UIList::new(
+                Rect::new(0, 1, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT - 1),
+                vec![
+                    UIListElement::new("<<", Color::White, Color::LightBlue),
+                    //[Level Entries]
                 ],
-                Box::new(|is_creating_new_level_pack: &mut bool, game_state: &mut GameState, cursor_index: usize| {
+                Box::new(|level_order: &mut Vec<usize>, game_state: &mut GameState, cursor_index: usize| {
                     if cursor_index == 0 {
                         game_state.play_sound_effect_ui_select();
                         game_state.set_screen(ScreenId::SelectLevelPack);
@@ -2240,113 +3334,5076 @@ impl ScreenSelectLevelPackEditor {
                         return;
                     }
 
-                    let level_pack_index = cursor_index - 1;
-                    if level_pack_index == game_state.editor_state.get_level_pack_count() {
-                        //Level Pack Editor entry
-                        if game_state.editor_state.get_level_pack_count() == LevelPack::MAX_LEVEL_PACK_COUNT {
-                            game_state.open_dialog(Dialog::new_ok_error(format!(
-                                "Cannot create new level packs (Max level pack count ({}) reached)",
-                                LevelPack::MAX_LEVEL_PACK_COUNT,
-                            )));
+                    let level_index = level_order[cursor_index - 1];
+
+                    let level_pack = game_state.get_current_level_pack().unwrap();
+                    let min_level_not_completed = level_pack.min_level_not_completed();
+
+                    if level_pack.is_locked_bonus_level(level_index) {
+                        game_state.play_sound_effect_ui_error();
+                        game_state.open_dialog(Dialog::new_ok("Find all secrets of this level pack to unlock this bonus level!"));
+                    }else if level_index <= min_level_not_completed {
+                        //The intro is shown before the first level is played for the first time
+                        let has_intro = level_index == 0 && min_level_not_completed == 0 &&
+                                !level_pack.story(PackStory::Intro).is_empty();
+
+                        game_state.play_sound_effect_ui_select();
+
+                        game_state.set_level_index(level_index);
+                        if has_intro {
+                            game_state.show_pack_story(PackStory::Intro);
                         }else {
-                            game_state.play_sound_effect_ui_select();
-                            *is_creating_new_level_pack = true;
+                            game_state.set_screen(ScreenId::InGame);
                         }
-                    }else {
-                        game_state.play_sound_effect_ui_select();
-                        game_state.editor_state.set_level_pack_index(level_pack_index);
 
-                        //Set selected level pack
-                        game_state.editor_state.set_level_index(0);
-                        game_state.set_screen(ScreenId::LevelPackEditor);
+                        if level_index == min_level_not_completed {
+                            game_state.allow_skip_level = true;
+                        }
+                    }else {
+                        game_state.play_sound_effect_ui_error();
                     }
                 }),
             ),
+            level_preview: false,
+            show_tile_legend: false,
 
-            is_exporting_level_pack: Default::default(),
-            is_deleting_level_pack: Default::default(),
+            sort_mode: LevelSortMode::default(),
+            level_order: Vec::new(),
+            suggested_level_index: None,
 
-            is_creating_new_level_pack: Default::default(),
-            new_level_pack_id: String::new(),
+            shuffle_seeds: HashMap::new(),
+            is_editing_shuffle_seed: false,
+            shuffle_seed_str: String::new(),
+
+            is_confirming_locked_level: false,
+            pending_restore_previous_best_level_index: None,
+            is_asking_telemetry_consent: false,
+            #[cfg(feature = "steam")]
+            is_confirming_missing_required_level_packs: false,
+            #[cfg(feature = "steam")]
+            ignored_missing_required_level_pack_ids: BTreeSet::new(),
         }
     }
 
-    fn update_list_elements(&mut self, game_state: &GameState) {
-        let elements = self.level_pack_editor_list.elements_mut();
+    /// Asks to open the Steam Workshop page of a missing required level pack (e.g. a previous part of a campaign)
+    ///
+    /// Returns true if the dialog was opened
+    #[cfg(feature = "steam")]
+    fn check_missing_required_level_packs(&mut self, game_state: &mut GameState) -> bool {
+        let level_pack_id = game_state.get_current_level_pack().unwrap().id();
+        if self.ignored_missing_required_level_pack_ids.contains(level_pack_id) {
+            return false;
+        }
+
+        let missing_required_workshop_ids = game_state.missing_required_workshop_ids();
+        if missing_required_workshop_ids.is_empty() {
+            return false;
+        }
+
+        let mut message = String::from("This level pack requires level packs which are not installed:\n\n");
+        for missing_required_workshop_id in missing_required_workshop_ids.iter() {
+            let _ = writeln!(message, "steam://url/CommunityFilePage/{}", missing_required_workshop_id);
+        }
+        message += "\nDo you want to open the Steam Workshop page of the first missing level pack?";
+
+        self.is_confirming_missing_required_level_packs = true;
+        game_state.open_dialog(Dialog::new_yes_no(message));
+
+        true
+    }
+
+    /// Returns the level index of the selected list entry or None if the back entry is selected
+    fn selected_level_index(&self) -> Option<usize> {
+        self.level_list.cursor_index().checked_sub(1).map(|i| self.level_order[i])
+    }
+
+    fn set_cursor_to_level(&mut self, level_index: usize) {
+        let cursor_index = self.level_order.iter().
+                position(|i| *i == level_index).
+                map_or(0, |i| i + 1);
+
+        self.level_list.set_cursor_index(cursor_index);
+    }
+
+    fn update_list_elements(&mut self, game_state: &mut GameState) {
+        let level_pack = game_state.get_current_level_pack().unwrap();
+        let shuffle_seed = *self.shuffle_seeds.entry(level_pack.id().to_string()).
+                or_insert_with(|| rand::random_range(1..=Self::MAX_SHUFFLE_SEED));
+        self.level_order = self.sort_mode.sorted_level_indices(level_pack, shuffle_seed);
+        self.suggested_level_index = game_state.player_stats().
+                and_then(|player_stats| recommend::suggest_level(level_pack, player_stats));
+
+        let elements = self.level_list.elements_mut();
+
+        //Remove all level entries
+        elements.drain(1..);
+
+        let min_level_not_completed = level_pack.min_level_not_completed();
+
+        //Levels with undiscovered secrets are only revealed after the level pack was completed
+        let show_undiscovered_secrets = level_pack.are_unlocked_levels_completed();
+
+        for &i in self.level_order.iter() {
+            //The locked bonus level stays hidden
+            if level_pack.is_locked_bonus_level(i) {
+                elements.push(UIListElement::new(" ?", Color::Black, Color::Red));
+
+                continue;
+            }
+
+            elements.push(UIListElement::new(
+                utils::number_to_string_leading_ascii(2, i as u32 + 1, false),
+                if show_undiscovered_secrets && level_pack.has_undiscovered_secret(i) {
+                    Color::Blue
+                }else {
+                    Color::Black
+                },
+                match i.cmp(&min_level_not_completed) {
+                    _ if self.suggested_level_index == Some(i) => Color::Cyan,
+                    Ordering::Less => {
+                        if level_pack.levels()[i].best_moves().is_some() {
+                            Color::Green
+                        }else {
+                            Color::Yellow
+                        }
+                    },
+                    Ordering::Equal => Color::Yellow,
+                    Ordering::Greater => Color::Red,
+                },
+            ));
+        }
+
+        //Completed levels are continued with the next level of the shuffled order
+        let shuffled_level_order = (self.sort_mode == LevelSortMode::Shuffled).
+                then(|| (game_state.current_level_pack_index, level_pack.shuffled_level_indices(shuffle_seed)));
+        game_state.shuffled_level_order = shuffled_level_order;
+    }
+
+    fn on_key_pressed_editing_shuffle_seed(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.is_ascii() && key.is_numeric() => {
+                if self.shuffle_seed_str.len() >= 4 {
+                    return;
+                }
+
+                let _ = write!(self.shuffle_seed_str, "{}", key.to_ascii().unwrap() as char);
+            },
+            Key::DELETE => {
+                self.shuffle_seed_str.pop();
+            },
+
+            Key::ENTER => {
+                let Some(shuffle_seed) = u64::from_str(&self.shuffle_seed_str).ok().
+                        filter(|shuffle_seed| (1..=Self::MAX_SHUFFLE_SEED).contains(shuffle_seed)) else {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Shuffle seed must be >= 1 and <= {}!", Self::MAX_SHUFFLE_SEED)));
+
+                    return;
+                };
+
+                game_state.play_sound_effect_ui_select();
+
+                self.is_editing_shuffle_seed = false;
+                self.shuffle_seed_str = String::new();
+
+                let level_pack_id = game_state.get_current_level_pack().unwrap().id().to_string();
+                self.shuffle_seeds.insert(level_pack_id, shuffle_seed);
+
+                self.sort_mode = LevelSortMode::Shuffled;
+                self.update_list_elements(game_state);
+
+                //Start at the first level of the shuffled order
+                self.level_list.set_cursor_index(1);
+            },
+
+            Key::ESC => {
+                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                self.is_editing_shuffle_seed = false;
+                self.shuffle_seed_str = String::new();
+            },
+
+            _ => {},
+        }
+    }
+
+    /// Starts a random remix of a completed level
+    fn start_remix(&mut self, game_state: &mut GameState, level_index: usize) {
+        let level_pack = game_state.get_current_level_pack().unwrap();
+        if level_index >= level_pack.min_level_not_completed() {
+            game_state.play_sound_effect_ui_error();
+            game_state.open_dialog(Dialog::new_ok("Complete this level to unlock its remixes!"));
+
+            return;
+        }
+
+        let seed = rand::random_range(1..=Self::MAX_REMIX_SEED);
+        let Some(level) = solver::remix_level(level_pack.levels()[level_index].level(), seed) else {
+            game_state.play_sound_effect_ui_error();
+            game_state.open_dialog(Dialog::new_ok("No remix was found for this level, try again!"));
+
+            return;
+        };
+
+        game_state.play_sound_effect_ui_select();
+
+        game_state.pending_remix = Some((seed, level));
+        game_state.set_level_index(level_index);
+        game_state.set_screen(ScreenId::InGame);
+    }
+
+    fn draw_overview(&self, game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_fmt(format_args!("Select a level (Level pack \"{}\"):", game_state.get_current_level_pack().unwrap().localized_name(game_state.settings.language)));
+        console.set_underline(false);
+
+        self.level_list.draw(console);
+
+        //Draw results of the background validation below the level entries
+        for (i, level_index) in self.level_order.iter().
+                enumerate() {
+            if let Some(solvability) = game_state.level_solvability(*level_index) {
+                let (x, y) = self.level_list.element_pos(i + 1);
+                let (icon, color) = solvability.icon();
+
+                console.set_cursor_pos(x + 1, y + 1);
+                console.set_color(color, Color::Default);
+                console.draw_text(icon);
+            }
+        }
+
+        let entry_count = self.level_list.elements().len();
+
+        //Draw border for best time and best moves
+        let y = 4 + ((entry_count - 1)/24)*2;
+
+        console.set_cursor_pos(0, y);
+        console.set_color(Color::Cyan, Color::Default);
+        console.draw_text(".-------------------------.");
+        for i in 1..4 {
+            console.set_cursor_pos(0, y + i);
+            console.draw_text("|                         |");
+        }
+        console.set_cursor_pos(0, y + 4);
+        console.draw_text("\'-------------------------\'");
+
+        console.reset_color();
+        console.set_cursor_pos(29, y + 4);
+        console.draw_text("Sort (");
+
+        console.draw_key_input_text("o");
+
+        console.reset_color();
+        console.draw_fmt(format_args!("): {}", self.sort_mode.name()));
+
+        let level_pack = game_state.get_current_level_pack().unwrap();
+        if level_pack.min_level_not_completed() >= level_pack.unlocked_level_count() && !game_state.settings.relaxed_mode {
+            console.set_cursor_pos(57, y + 4);
+            console.draw_text("Speedrun (");
+
+            console.draw_key_input_text("r");
+
+            console.reset_color();
+            console.draw_text(")");
+        }else if let Some(suggested_level_index) = self.suggested_level_index {
+            console.set_cursor_pos(55, y + 4);
+            console.draw_text("Suggested (");
+
+            console.draw_key_input_text("g");
+
+            console.reset_color();
+            console.draw_text("): ");
+
+            console.set_color(Color::Black, Color::Cyan);
+            console.draw_fmt(format_args!("{:03}", suggested_level_index + 1));
+            console.reset_color();
+        }
+
+        if self.is_editing_shuffle_seed {
+            console.set_cursor_pos(29, y + 3);
+            console.draw_text("Enter shuffle seed: ");
+
+            console.set_color(Color::LightBlue, Color::Default);
+            console.draw_text(&self.shuffle_seed_str);
+            console.reset_color();
+        }else if self.sort_mode == LevelSortMode::Shuffled && let Some(shuffle_seed) = self.shuffle_seeds.get(level_pack.id()) {
+            console.set_cursor_pos(29, y + 3);
+            console.draw_text("Shuffle seed (");
+
+            console.draw_key_input_text("e");
+
+            console.reset_color();
+            console.draw_fmt(format_args!("): {}", shuffle_seed));
+        }
+
+        if let Some(level_index) = self.selected_level_index() {
+            //Draw best time and best moves
+            console.reset_color();
+            //The values are right aligned to the width of best times of at least one hour ("H:MM:SS.mmm")
+            console.set_cursor_pos(1, y + 1);
+            console.draw_text("Selected level:         ");
+            console.draw_fmt(format_args!("{:03}", level_index + 1));
+
+            let level_pack = game_state.get_current_level_pack().unwrap();
+            let level = level_pack.levels().get(level_index).unwrap();
+
+            console.set_cursor_pos(1, y + 2);
+            console.draw_text("Best time     : ");
+            match level.best_time() {
+                None => console.draw_text("  XX:XX.XXX"),
+                Some(best_time) => {
+                    console.draw_fmt(format_args!("{:>11}", utils::TimeDisplay(best_time)));
+                },
+            }
+            console.set_cursor_pos(1, y + 3);
+            console.draw_text("Best moves    :        ");
+            match level.best_moves() {
+                None => console.draw_text("XXXX"),
+                Some(best_moves) => {
+                    console.draw_fmt(format_args!("{:04}", best_moves));
+                },
+            }
+
+            if level.previous_best().is_some() {
+                console.reset_color();
+                console.set_cursor_pos(57, y + 1);
+                console.draw_text("Prev. best (");
+
+                console.draw_key_input_text("b");
+
+                console.reset_color();
+                console.draw_text(")");
+            }
+
+            if !level.is_stats_verified() {
+                console.set_color(Color::LightRed, Color::Default);
+                console.set_cursor_pos(57, y + 2);
+                console.draw_text("Unverified best");
+            }
+
+            //The minimum push count is only solved for levels which were completed
+            if level.best_moves().is_some() && let Some(min_push_count) = game_state.level_min_push_count(level_index) {
+                console.reset_color();
+                console.set_cursor_pos(57, y + 3);
+                console.draw_fmt(format_args!("Min pushes: {:04}", min_push_count));
+            }
+
+            if level_pack.is_locked_bonus_level(level_index) {
+                let secret_count = level_pack.secret_level_indices().count();
+                let found_secret_count = level_pack.secret_level_indices().
+                        filter(|level_index| level_pack.is_secret_found(*level_index)).
+                        count();
+
+                console.reset_color();
+                console.set_cursor_pos(29, y + 1);
+                console.draw_text("Bonus level: Find all secrets to unlock");
+                console.set_cursor_pos(29, y + 2);
+                console.draw_fmt(format_args!("Secrets found: {}/{}", found_secret_count, secret_count));
+
+                return;
+            }
+
+            console.reset_color();
+            console.set_cursor_pos(29, y + 1);
+            console.draw_text("Press ");
+
+            console.draw_key_input_text(MenuAction::LevelPreview.key_name());
+
+            console.reset_color();
+            console.draw_text(" for level preview");
+
+            if level.par_moves().is_some() || level.par_time().is_some() {
+                console.set_cursor_pos(29, y + 2);
+                draw_level_par(console, level);
+            }
+
+            if level_pack.are_unlocked_levels_completed() && level_pack.has_undiscovered_secret(level_index) {
+                console.set_color(Color::LightBlue, Color::Default);
+                console.set_cursor_pos(29, y + 3);
+                console.draw_text("A secret is hidden here");
+                console.reset_color();
+            }else if game_state.allow_skip_level && level_index == level_pack.min_level_not_completed() &&
+                    level_index + 1 < level_pack.unlocked_level_count()  {
+                console.reset_color();
+                console.set_cursor_pos(29, y + 3);
+                console.draw_text("Press ");
+
+                console.draw_key_input_text(MenuAction::SkipLevel.key_name());
+
+                console.reset_color();
+                console.draw_text(" to skip this level");
+            }
+        }else {
+            console.reset_color();
+            console.set_cursor_pos(11, y + 2);
+            console.draw_text("Back");
+        }
+    }
+
+    fn draw_level_preview(&self, game_state: &GameState, console: &Console) {
+        let cursor_index = self.level_list.cursor_index();
+
+        if cursor_index == 1 {
+            console.draw_key_input_text("<");
+
+            console.reset_color();
+            console.draw_text(" Back");
+        }else if cursor_index > 1 {
+            console.draw_key_input_text("<");
+
+            console.reset_color();
+            console.draw_fmt(format_args!(" Level {:03}", self.level_order[cursor_index - 2] + 1));
+        }
+
+        if cursor_index < self.level_order.len() {
+            console.reset_color();
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 11, 0);
+            console.draw_fmt(format_args!("Level {:03} ", self.level_order[cursor_index] + 1));
+
+            console.draw_key_input_text(">");
+        }
+
+        console.reset_color();
+        console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 23) as f64 * 0.5) as usize, 0);
+        console.draw_text("Preview (");
+
+        console.draw_key_input_text(MenuAction::LevelPreview.key_name());
+
+        console.reset_color();
+        console.draw_fmt(format_args!(") [Level {:03}]", self.selected_level_index().map_or(0, |level_index| level_index + 1)));
+
+        if let Some(level_index) = self.selected_level_index() {
+            let min_level_not_completed = game_state.get_current_level_pack().as_ref().unwrap().min_level_not_completed();
+            let level = game_state.get_current_level_pack().unwrap().levels()[level_index].level();
+
+            if game_state.get_current_level_pack().unwrap().is_locked_bonus_level(level_index) {
+                let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
+                let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
+
+                console.set_cursor_pos(x, y);
+                console.set_color(Color::Cyan, Color::Default);
+                console.draw_text(".--------------------------------------.");
+                for i in 1..4 {
+                    console.set_cursor_pos(x, y + i);
+                    console.draw_text("|                                      |");
+                }
+                console.set_cursor_pos(x, y + 4);
+                console.draw_text("\'--------------------------------------\'");
+
+                console.reset_color();
+                console.set_cursor_pos(x + 2, y + 1);
+                console.draw_text("Find all secrets of this level pack");
+                console.set_cursor_pos(x + 2, y + 3);
+                console.draw_text("to unlock this bonus level.");
+            }else if level_index > min_level_not_completed {
+                let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
+                let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
+
+                console.set_cursor_pos(x, y);
+                console.set_color(Color::Cyan, Color::Default);
+                console.draw_text(".--------------------------------------.");
+                for i in 1..4 {
+                    console.set_cursor_pos(x, y + i);
+                    console.draw_text("|                                      |");
+                }
+                console.set_cursor_pos(x, y + 4);
+                console.draw_text("\'--------------------------------------\'");
+
+                console.reset_color();
+                console.set_cursor_pos(x + 2, y + 1);
+                console.draw_fmt(format_args!("Beat level {:03} to unlock this level.", level_index));
+
+                console.set_cursor_pos(x + 7, y + 3);
+                console.draw_text("Press ");
+
+                console.draw_key_input_text("ENTER");
+
+                console.reset_color();
+                console.draw_text(" to play anyway");
+            }else {
+                let x_offset = ((Game::CONSOLE_MIN_WIDTH - level.draw_width()) as f64 * 0.5) as usize;
+                let y_offset = 1;
+
+                level.draw(console, x_offset, y_offset, game_state.is_player_background(), None);
+
+                let level_with_stats = &game_state.get_current_level_pack().unwrap().levels()[level_index];
+                if y_offset + level.draw_height() < Game::CONSOLE_MIN_HEIGHT {
+                    console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 68) as f64 * 0.5) as usize, Game::CONSOLE_MIN_HEIGHT - 1);
+                    draw_level_stats_compare(console, level_with_stats);
+                }
+
+                if level_index < min_level_not_completed && y_offset + level.draw_height() < Game::CONSOLE_MIN_HEIGHT - 1 {
+                    console.reset_color();
+                    console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 9) as f64 * 0.5) as usize, Game::CONSOLE_MIN_HEIGHT - 2);
+                    console.draw_text("Remix (");
+
+                    console.draw_key_input_text("m");
+
+                    console.reset_color();
+                    console.draw_text(")");
+                }
+
+                if self.show_tile_legend {
+                    draw_tile_legend(console, level, None);
+                }
+            }
+        }else {
+            let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
+            let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
+
+            console.set_cursor_pos(x, y);
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_text(".--------------------------------------.");
+            for i in 1..4 {
+                console.set_cursor_pos(x, y + i);
+                console.draw_text("|                                      |");
+            }
+            console.set_cursor_pos(x, y + 4);
+            console.draw_text("\'--------------------------------------\'");
+
+            console.reset_color();
+            console.set_cursor_pos(35, y + 2);
+            console.draw_text("Back");
+        }
+    }
+}
+
+impl Screen for ScreenSelectLevel {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        if self.level_preview {
+            self.draw_level_preview(game_state, console);
+        }else {
+            self.draw_overview(game_state, console);
+        }
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if self.is_editing_shuffle_seed {
+            self.on_key_pressed_editing_shuffle_seed(game_state, key);
+
+            return;
+        }
+
+        if key == Key::ESC {
+            game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+            if self.level_preview {
+                self.level_preview = false;
+                self.show_tile_legend = false;
+            }else {
+                game_state.set_screen(ScreenId::SelectLevelPack);
+            }
+
+            return;
+        }
+
+        if key == MenuAction::LevelPreview.key() {
+            game_state.play_sound_effect_ui_select();
+
+            self.level_preview = !self.level_preview;
+            self.show_tile_legend = false;
+
+            return;
+        }
+
+        if key == Key::L && self.level_preview {
+            game_state.play_sound_effect_ui_select();
+
+            self.show_tile_legend = !self.show_tile_legend;
+
+            return;
+        }
+
+        if key == Key::M && self.level_preview && let Some(level_index) = self.selected_level_index() {
+            self.start_remix(game_state, level_index);
+
+            return;
+        }
+
+        if key == Key::O {
+            game_state.play_sound_effect_ui_select();
+
+            let selected_level_index = self.selected_level_index();
+
+            self.sort_mode = self.sort_mode.next();
+            self.update_list_elements(game_state);
+
+            //Keep the selected level selected
+            if let Some(selected_level_index) = selected_level_index {
+                self.set_cursor_to_level(selected_level_index);
+            }
+
+            return;
+        }
+
+        if key == Key::E && !self.level_preview {
+            game_state.play_sound_effect_ui_select();
+
+            self.is_editing_shuffle_seed = true;
+            self.shuffle_seed_str = String::new();
+
+            return;
+        }
+
+        if key == Key::G && !self.level_preview && let Some(suggested_level_index) = self.suggested_level_index {
+            game_state.play_sound_effect_ui_select();
+
+            self.set_cursor_to_level(suggested_level_index);
+
+            return;
+        }
+
+        if key == Key::R {
+            if game_state.settings.relaxed_mode {
+                game_state.play_sound_effect_ui_error();
+                game_state.open_dialog(Dialog::new_ok("The speedrun mode is not available in relaxed mode!"));
+
+                return;
+            }
+
+            let level_pack = game_state.get_current_level_pack().unwrap();
+            if level_pack.min_level_not_completed() < level_pack.unlocked_level_count() {
+                game_state.play_sound_effect_ui_error();
+                game_state.open_dialog(Dialog::new_ok("Complete all levels of this level pack to unlock the speedrun mode!"));
+
+                return;
+            }
+
+            game_state.play_sound_effect_ui_select();
+
+            game_state.should_start_speedrun = true;
+            game_state.set_level_index(0);
+            game_state.set_screen(ScreenId::InGame);
+
+            return;
+        }
+
+        if (key == Key::ENTER || key == Key::SPACE) && self.level_preview && let Some(level_index) = self.selected_level_index() {
+            if game_state.get_current_level_pack().unwrap().is_locked_bonus_level(level_index) {
+                game_state.play_sound_effect_ui_error();
+
+                return;
+            }
+
+            if level_index > game_state.get_current_level_pack().as_ref().unwrap().min_level_not_completed() {
+                self.is_confirming_locked_level = true;
+
+                game_state.open_dialog(Dialog::new_yes_no(format!(
+                    "Level {:03} is locked!\nDo you want to play it anyway? (Progress will not be saved)",
+                    level_index + 1,
+                )));
+
+                return;
+            }
+        }
+
+        if key == Key::B && !self.level_preview && let Some(level_index) = self.selected_level_index() &&
+                let Some((previous_best_time, previous_best_moves)) = game_state.get_current_level_pack().unwrap().levels()[level_index].previous_best() {
+            let level = &game_state.get_current_level_pack().unwrap().levels()[level_index];
+            let time_to_string = |time: Option<u64>| time.map_or_else(|| "XX:XX.XXX".to_string(), utils::time_to_string);
+
+            self.pending_restore_previous_best_level_index = Some(level_index);
+            game_state.open_dialog(Dialog::new_yes_no(format!(
+                "Do you want to restore the previous best of level {:03}?\n\n\
+                Best time : {} -> {}\nBest moves: {:04} -> {:04}\n\n\
+                The current best can not be restored afterward!",
+                level_index + 1,
+                time_to_string(level.best_time()), time_to_string(previous_best_time),
+                level.best_moves().unwrap_or_default(), previous_best_moves,
+            )));
+
+            return;
+        }
+
+        if key == MenuAction::SkipLevel.key() && game_state.allow_skip_level && let Some(level_index) = self.selected_level_index() &&
+                level_index == game_state.get_current_level_pack().as_ref().unwrap().min_level_not_completed() &&
+                level_index + 1 < game_state.get_current_level_pack().as_ref().unwrap().unlocked_level_count() {
+            game_state.open_dialog(Dialog::new_yes_no("Do you really want to skip this level?"));
+
+            return;
+        }
+
+        self.level_list.on_key_press(&mut self.level_order, game_state, key);
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if self.level_preview {
+            if row == 0 {
+                let center_text_start = ((Game::CONSOLE_MIN_WIDTH - 23) as f64 * 0.5) as usize;
+
+                if column < 11 {
+                    self.on_key_pressed(game_state, Key::LEFT);
+                }else if column >= center_text_start && column < center_text_start + 23 {
+                    self.on_key_pressed(game_state, Key::ENTER);
+                }else if column > Game::CONSOLE_MIN_WIDTH - 12 {
+                    self.on_key_pressed(game_state, Key::RIGHT);
+                }
+            }else if row == Game::CONSOLE_MIN_HEIGHT - 2 {
+                let remix_text_start = ((Game::CONSOLE_MIN_WIDTH - 9) as f64 * 0.5) as usize;
+
+                if column >= remix_text_start && column < remix_text_start + 9 {
+                    self.on_key_pressed(game_state, Key::M);
+                }
+            }
+
+            return;
+        }
+
+        let element_count = self.level_list.elements().len();
+        let y = 4 + ((element_count - 1)/24)*2;
+        if row == y + 1 && (29..54).contains(&column) {
+            self.on_key_pressed(game_state, MenuAction::LevelPreview.key());
+        }else if row == y + 3 && (29..55).contains(&column) {
+            self.on_key_pressed(game_state, MenuAction::SkipLevel.key());
+        }else if row == y + 4 && (29..37).contains(&column) {
+            self.on_key_pressed(game_state, Key::O);
+        }else if row == y + 4 && (55..73).contains(&column) {
+            let level_pack = game_state.get_current_level_pack().unwrap();
+            if level_pack.min_level_not_completed() < level_pack.unlocked_level_count() {
+                self.on_key_pressed(game_state, Key::G);
+            }else if (57..69).contains(&column) {
+                self.on_key_pressed(game_state, Key::R);
+            }
+        }
+
+        self.level_list.on_mouse_pressed(&mut self.level_order, game_state, column, row);
+    }
+
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            //The previous or next level is shown in the level preview
+            let key = match key {
+                Key::UP if self.level_preview => Key::LEFT,
+                Key::DOWN if self.level_preview => Key::RIGHT,
+                key => key,
+            };
+
+            self.level_list.on_key_press(&mut self.level_order, game_state, key);
+        }
+    }
+
+    fn on_toggle_preview(&mut self, game_state: &mut GameState) {
+        self.on_key_pressed(game_state, MenuAction::LevelPreview.key());
+    }
+
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        #[cfg(feature = "steam")]
+        if self.is_confirming_missing_required_level_packs {
+            self.is_confirming_missing_required_level_packs = false;
+
+            if selection == DialogSelection::Yes {
+                if let Some(missing_required_workshop_id) = game_state.missing_required_workshop_ids().first() {
+                    game_state.steam_client.friends().activate_game_overlay_to_web_page(&format!(
+                        "steam://url/CommunityFilePage/{}", missing_required_workshop_id,
+                    ));
+                }
+            }else {
+                let level_pack_id = game_state.get_current_level_pack().unwrap().id().to_string();
+                self.ignored_missing_required_level_pack_ids.insert(level_pack_id);
+            }
+
+            return;
+        }
+
+        if self.is_asking_telemetry_consent {
+            self.is_asking_telemetry_consent = false;
+
+            let telemetry_consent = if selection == DialogSelection::Yes {
+                TelemetryConsent::Allowed
+            }else {
+                TelemetryConsent::Denied
+            };
+
+            if let Err(err) = game_state.set_and_save_telemetry_consent(telemetry_consent) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save settings: {}", err)));
+            }
+
+            return;
+        }
+
+        if self.is_confirming_locked_level {
+            self.is_confirming_locked_level = false;
+
+            if selection == DialogSelection::Yes && let Some(level_index) = self.selected_level_index() {
+                game_state.set_level_index(level_index);
+                game_state.set_screen(ScreenId::InGame);
+            }
+
+            return;
+        }
+
+        if let Some(level_index) = self.pending_restore_previous_best_level_index.take() {
+            if selection == DialogSelection::Yes {
+                let save_writer = game_state.save_writer.clone();
+                let level_pack = game_state.get_current_level_pack_mut().unwrap();
+
+                if level_pack.restore_previous_best(level_index).is_some() {
+                    if let Err(err) = level_pack.save_save_game_in_background(&save_writer) {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                    }else {
+                        game_state.show_toast("Previous best restored");
+                    }
+
+                    self.update_list_elements(game_state);
+                    self.set_cursor_to_level(level_index);
+                }
+            }
+
+            return;
+        }
+
+        if selection == DialogSelection::Yes {
+            let save_writer = game_state.save_writer.clone();
+            let level_pack = game_state.get_current_level_pack_mut().unwrap();
+            level_pack.set_min_level_not_completed(level_pack.min_level_not_completed() + 1);
+            let min_level_not_completed = level_pack.min_level_not_completed();
+
+            if let Err(err) = level_pack.save_save_game_in_background(&save_writer) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+            }
+
+            game_state.allow_skip_level = false;
+
+            self.update_list_elements(game_state);
+            self.set_cursor_to_level(min_level_not_completed);
+        }
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.update_list_elements(game_state);
+
+        self.set_cursor_to_level(game_state.get_level_index());
+
+        self.level_preview = false;
+        self.show_tile_legend = false;
+        self.is_confirming_locked_level = false;
+        self.is_editing_shuffle_seed = false;
+
+        //Level statistics can not be uploaded without the telemetry feature
+        self.is_asking_telemetry_consent = cfg!(feature = "telemetry") &&
+                game_state.settings.telemetry_consent == TelemetryConsent::NotAsked &&
+                game_state.get_current_level_pack().is_some_and(|level_pack| level_pack.telemetry_endpoint().is_some());
+
+        #[cfg(feature = "steam")]
+        {
+            self.is_confirming_missing_required_level_packs = false;
+            if self.check_missing_required_level_packs(game_state) {
+                //The telemetry consent is asked the next time the level selection is opened
+                self.is_asking_telemetry_consent = false;
+            }
+        }
+
+        if self.is_asking_telemetry_consent {
+            game_state.open_dialog(Dialog::new_yes_no(
+                "The author of this level pack collects anonymous level statistics.\n\n\
+                If allowed, the completion rate, the average move count, and the restart count of each level \
+                are sent to the level pack author. No personal data is sent.\n\n\
+                You can change this later in the settings.\n\n\
+                Allow anonymous level statistics?",
+            ));
+        }
+    }
+}
+
+/// Measures the elapsed time in milliseconds, time spent in menus is excluded with pause and resume
+#[derive(Debug, Default)]
+struct Stopwatch {
+    time_start: Option<SystemTime>,
+    time_start_in_menu: Option<SystemTime>,
+    time: u64,
+}
+
+impl Stopwatch {
+    //99:59:59.999
+    const MAX_TIME: u64 = 100 * 60 * 60000 - 1;
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Starts the stopwatch if it is not already running
+    fn start(&mut self) {
+        self.time_start.get_or_insert_with(SystemTime::now);
+    }
+
+    fn set_time(&mut self, time: u64) {
+        self.time_start = Some(SystemTime::now() - Duration::from_millis(time));
+        self.time = time.min(Self::MAX_TIME);
+    }
+
+    fn update(&mut self) {
+        if let Some(time_start) = self.time_start {
+            let diff = SystemTime::now().duration_since(time_start).
+                    expect("Time manipulation detected (Start time is in the future)!").
+                    as_millis();
+
+            self.time = diff.min(Self::MAX_TIME as u128) as u64;
+        }
+    }
+
+    /// Pauses the stopwatch if it is not already paused
+    fn pause(&mut self) {
+        self.time_start_in_menu.get_or_insert_with(SystemTime::now);
+    }
+
+    fn resume(&mut self) {
+        let Some(time_start_in_menu) = self.time_start_in_menu.take() else {
+            return;
+        };
+
+        let diff = SystemTime::now().duration_since(time_start_in_menu).
+                expect("Time manipulation detected (Start time is in the future)!");
+
+        self.time_start = self.time_start.map(|time_start| time_start + diff);
+    }
+
+    fn time(&self) -> u64 {
+        self.time
+    }
+}
+
+/// State of a speedrun through all levels of a level pack
+struct Speedrun {
+    timer: Stopwatch,
+
+    //Time and best time before the speedrun of each completed level
+    splits: Vec<(u64, Option<u64>)>,
+
+    //Best total time before the speedrun
+    best_time: Option<u64>,
+    is_finished: bool,
+
+    results_scroll_offset: usize,
+}
+
+impl Speedrun {
+    fn new(best_time: Option<u64>) -> Self {
+        Self {
+            timer: Stopwatch::default(),
+
+            splits: Vec::new(),
+
+            best_time,
+            is_finished: false,
+
+            results_scroll_offset: 0,
+        }
+    }
+}
+
+/// A box which is sinking into a hole: The box is shown on top of the hole, then the empty hole and then the filled hole
+#[derive(Debug, Clone, Copy)]
+struct BoxSinkAnimation {
+    x: usize,
+    y: usize,
+
+    frame: usize,
+}
+
+impl BoxSinkAnimation {
+    const FRAME_COUNT: usize = 8;
+
+    fn new(x: usize, y: usize) -> Self {
+        Self { x, y, frame: 0 }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.frame >= Self::FRAME_COUNT
+    }
+
+    fn draw(&self, console: &Console, x_offset: usize, y_offset: usize, level: &Level) {
+        let (tile_width, tile_height) = (level.tile_width(), level.tile_height());
+
+        for tile_row in 0..tile_height {
+            console.set_cursor_pos(x_offset + self.x * tile_width, y_offset + self.y * tile_height + tile_row);
+
+            for _ in 0..tile_width {
+                match self.frame {
+                    0..3 => Tile::Box.draw(console, false, false),
+                    3..5 => Tile::Hole.draw(console, false, false),
+
+                    //Inverted colors fade into the normal filled hole
+                    _ => Tile::BoxInHole.draw(console, false, true),
+                }
+            }
+        }
+    }
+}
+
+/// Shown instead of "Level completed!" if the completion beat the previous best moves or best time of the level
+#[derive(Debug, Clone, Copy)]
+struct NewRecord {
+    moves_improvement: Option<u32>,
+    time_improvement: Option<u64>,
+
+    frame: usize,
+}
+
+impl NewRecord {
+    const COLORS: [Color; 4] = [Color::LightYellow, Color::LightGreen, Color::LightCyan, Color::LightPink];
+    const FRAMES_PER_COLOR: usize = 3;
+
+    /// Returns None if neither the best moves nor the best time were beaten (The first completion is not a new record)
+    fn new(previous_best_moves: Option<u32>, previous_best_time: Option<u64>, moves: u32, time: Option<u64>) -> Option<Self> {
+        let moves_improvement = previous_best_moves.
+                and_then(|best_moves| best_moves.checked_sub(moves)).
+                filter(|improvement| *improvement > 0);
+        let time_improvement = previous_best_time.
+                zip(time).
+                and_then(|(best_time, time)| best_time.checked_sub(time)).
+                filter(|improvement| *improvement > 0);
+
+        (moves_improvement.is_some() || time_improvement.is_some()).then_some(Self {
+            moves_improvement,
+            time_improvement,
+
+            frame: 0,
+        })
+    }
+
+    fn text(&self) -> String {
+        if let Some(moves_improvement) = self.moves_improvement {
+            format!("New record! -{} moves", moves_improvement)
+        }else {
+            let time_improvement = self.time_improvement.unwrap_or_default();
+
+            format!("New record! -{}.{:03}s", time_improvement/1000, time_improvement%1000)
+        }
+    }
+
+    fn draw(&self, console: &Console, width: usize) {
+        let text = self.text();
+
+        console.set_cursor_pos(((width - text.len()) as f64 * 0.5) as usize, 0);
+        console.set_color(Self::COLORS[(self.frame / Self::FRAMES_PER_COLOR) % Self::COLORS.len()], Color::Default);
+        console.draw_text(text);
+        console.reset_color();
+    }
+}
+
+/// Tracks the undo/redo key which is pressed repeatedly: Holding the key undoes/redoes moves faster after some time
+struct UndoScrub {
+    key: Key,
+    held_since: Instant,
+    last_key_press: Instant,
+}
+
+impl UndoScrub {
+    //Must be longer than the initial delay of the key repeat of the OS
+    const MAX_KEY_REPEAT_DELAY: Duration = Duration::from_millis(600);
+
+    const FAST_SCRUB_DELAY: Duration = Duration::from_secs(1);
+    const FAST_SCRUB_MOVE_COUNT: usize = 5;
+
+    const INDICATOR_DURATION: Duration = Duration::from_millis(1500);
+
+    fn new(key: Key) -> Self {
+        let now = Instant::now();
+
+        Self {
+            key,
+            held_since: now,
+            last_key_press: now,
+        }
+    }
+
+    /// Returns the count of moves which should be undone/redone for this key press
+    fn on_key_pressed(&mut self, key: Key) -> usize {
+        let now = Instant::now();
+
+        if self.key != key || now.duration_since(self.last_key_press) > Self::MAX_KEY_REPEAT_DELAY {
+            self.key = key;
+            self.held_since = now;
+        }
+        self.last_key_press = now;
+
+        if now.duration_since(self.held_since) >= Self::FAST_SCRUB_DELAY {
+            Self::FAST_SCRUB_MOVE_COUNT
+        }else {
+            1
+        }
+    }
+
+    fn is_indicator_visible(&self) -> bool {
+        self.last_key_press.elapsed() < Self::INDICATOR_DURATION
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SolverHintTier {
+    /// Highlights the box which is pushed next
+    NextBox,
+    /// Shows the direction of the next push
+    PushDirection,
+    /// Plays the next moves of the solution
+    NextMoves,
+}
+
+impl SolverHintTier {
+    /// Count of moves which are added to the score of the level if the tier is revealed
+    fn penalty_moves(self) -> u32 {
+        match self {
+            SolverHintTier::NextBox => 5,
+            SolverHintTier::PushDirection => 10,
+            SolverHintTier::NextMoves => 20,
+        }
+    }
+
+    fn next(tier: Option<Self>) -> Self {
+        match tier {
+            None => SolverHintTier::NextBox,
+            Some(SolverHintTier::NextBox) => SolverHintTier::PushDirection,
+            Some(SolverHintTier::PushDirection | SolverHintTier::NextMoves) => SolverHintTier::NextMoves,
+        }
+    }
+}
+
+/// Hints from the solver which are revealed in tiers for the current state of the level
+#[derive(Debug, Default)]
+struct SolverHints {
+    solution_cache: SolutionCache,
+
+    //Tiles of the state for which the hint was revealed (The hint is hidden after the player moved)
+    revealed_state: Vec<Tile>,
+    revealed_tier: Option<SolverHintTier>,
+    //Position of the box which is pushed next and the push direction (Or the direction of the next move if no box is pushed)
+    revealed_box_pos: Option<(usize, usize)>,
+    revealed_direction: Option<Direction>,
+
+    //Moves penalty of all revealed tiers of the level
+    penalty_moves: u32,
+
+    pending_moves: VecDeque<Direction>,
+    pending_move_delay: u32,
+}
+
+impl SolverHints {
+    //Frames between two moves which are played by a hint
+    const MOVE_DELAY_FRAMES: u32 = 4;
+
+    /// Returns the revealed tier if the hint is still valid for the current state
+    fn revealed_tier(&self, playing_level: &PlayingLevel) -> Option<SolverHintTier> {
+        self.revealed_tier.filter(|_| self.revealed_state == playing_level.current_playing_level().0.tiles())
+    }
+
+    fn highlighted_pos(&self, playing_level: &PlayingLevel) -> Option<(usize, usize)> {
+        self.revealed_tier(playing_level).and(self.revealed_box_pos)
+    }
+
+    fn text(&self, playing_level: &PlayingLevel) -> Option<String> {
+        let direction = match self.revealed_direction? {
+            Direction::Left => "left",
+            Direction::Up => "up",
+            Direction::Right => "right",
+            Direction::Down => "down",
+        };
+
+        Some(match self.revealed_tier(playing_level)? {
+            SolverHintTier::NextBox if self.revealed_box_pos.is_some() => "Hint: Marked tile".to_string(),
+            SolverHintTier::NextBox | SolverHintTier::PushDirection if self.revealed_box_pos.is_none() => format!("Hint: Move {}", direction),
+            SolverHintTier::NextBox | SolverHintTier::PushDirection => format!("Hint: Push {}", direction),
+            SolverHintTier::NextMoves => "Hint: Next moves".to_string(),
+        })
+    }
+}
+
+/// A variation of a completed level with other box start positions, each remix seed has its own best scores
+struct Remix {
+    seed: u64,
+    level: Level,
+
+    best_time: Option<u64>,
+    best_moves: Option<u32>,
+}
+
+pub struct ScreenInGame {
+    level_timer: Stopwatch,
+    speedrun: Option<Speedrun>,
+    remix: Option<Remix>,
+
+    animation_first_frame: bool,
+    //The push sound effect is played instead of the step sound effect for the last move
+    is_push_move: bool,
+    level: Option<PlayingLevel>,
+    box_sink_animations: Vec<BoxSinkAnimation>,
+    new_record: Option<NewRecord>,
+    undo_scrub: Option<UndoScrub>,
+
+    show_floor: bool,
+    show_tile_legend: bool,
+
+    encountered_tiles: Vec<Tile>,
+
+    solver_hints: SolverHints,
+
+    pending_level_attempt: Option<LevelAttempt>,
+
+    pause_menu: Option<PauseMenu>,
+
+    #[cfg(feature = "steam")]
+    is_showing_workshop_rating_prompt: bool,
+
+    continue_flag: bool,
+    secret_found_flag: bool,
+    game_over_flag: bool,
+}
+
+impl ScreenInGame {
+    pub const UNDO_HISTORY_SIZE_PLAYING: usize = 10000;
+
+    const UNDO_JUMP_MOVE_COUNT: usize = 10;
+
+    const SPEEDRUN_RESULTS_ROW_COUNT: usize = 15;
+
+    const SOLVER_HINT_MOVE_COUNT: usize = 5;
+
+    const REGION_PAUSE_MENU: HitRegionId = HitRegionId(0);
+
+    #[cfg(feature = "steam")]
+    const WORKSHOP_RATING_OPEN_PAGE: &'static DialogOption = &DialogOption::new("[O]pen page", DialogSelection::Ok, &[Key::O]);
+
+    pub fn new() -> Self {
+        Self {
+            level_timer: Default::default(),
+            speedrun: None,
+            remix: None,
+
+            animation_first_frame: false,
+            is_push_move: false,
+            level: Default::default(),
+            box_sink_animations: Vec::new(),
+            new_record: None,
+            undo_scrub: None,
+
+            show_floor: false,
+            show_tile_legend: false,
+
+            encountered_tiles: Vec::new(),
+
+            solver_hints: SolverHints::default(),
+
+            pending_level_attempt: None,
+
+            pause_menu: None,
+
+            #[cfg(feature = "steam")]
+            is_showing_workshop_rating_prompt: false,
+
+            continue_flag: Default::default(),
+            secret_found_flag: Default::default(),
+            game_over_flag: Default::default(),
+        }
+    }
+
+    /// Asks the player once per Steam Workshop level pack to rate the level pack after completing it
+    #[cfg(feature = "steam")]
+    fn open_workshop_rating_prompt(&mut self, game_state: &mut GameState) {
+        let Some(level_pack) = game_state.get_current_level_pack() else {
+            return;
+        };
+
+        //The last level might have been played without completing the level pack
+        if level_pack.steam_level_pack_data().is_none() || level_pack.min_level_not_completed() < level_pack.unlocked_level_count() {
+            return;
+        }
+
+        match level_pack.was_workshop_rating_prompt_shown() {
+            Ok(false) => {},
+
+            Ok(true) => return,
+
+            Err(err) => {
+                log_warn!("Cannot read workshop rating prompt state: {}", err);
+
+                return;
+            },
+        }
+
+        if let Err(err) = level_pack.set_workshop_rating_prompt_shown() {
+            log_warn!("Cannot save workshop rating prompt state: {}", err);
+        }
+
+        self.is_showing_workshop_rating_prompt = true;
+
+        //Yes and No rate the level pack directly with a thumbs up or a thumbs down
+        game_state.open_dialog(Dialog::new(
+            DialogType::Information,
+            "You completed this Steam Workshop level pack!\nDid you like it? Your rating is shown on its Steam Workshop page.",
+            Box::from([DialogOption::YES, DialogOption::NO, Self::WORKSHOP_RATING_OPEN_PAGE, DialogOption::CANCEL]),
+        ));
+    }
+
+    pub fn start_level(&mut self, level: &Level) {
+        //Reset stats
+        self.level_timer.reset();
+
+        self.continue_flag = false;
+        self.game_over_flag = false;
+
+        self.animation_first_frame = false;
+        self.level = Some(PlayingLevel::new(level, Self::UNDO_HISTORY_SIZE_PLAYING).unwrap());
+        self.box_sink_animations.clear();
+        self.new_record = None;
+        self.undo_scrub = None;
+
+        self.show_floor = false;
+
+        self.encountered_tiles.clear();
+        self.update_encountered_tiles();
+    }
+
+    fn start_remix(&mut self, game_state: &mut GameState, seed: u64, level: Level) {
+        self.start_level(&level);
+
+        let level_pack = game_state.get_current_level_pack().unwrap();
+        let (best_time, best_moves) = match level_pack.read_remix_best_score(game_state.get_level_index(), seed) {
+            Ok(best_score) => best_score.map_or((None, None), |best_score| (best_score.best_time, Some(best_score.best_moves))),
+
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot load remix score: {}", err)));
+
+                (None, None)
+            },
+        };
+
+        self.remix = Some(Remix {
+            seed,
+            level,
+
+            best_time,
+            best_moves,
+        });
+    }
+
+    fn restore_level_attempt(&mut self, level_attempt: &LevelAttempt) {
+        let Some(playing_level) = self.level.as_mut() else {
+            return;
+        };
+
+        //Keep all valid moves if the level attempt is no longer valid (e.g. level was changed by an update)
+        playing_level.replay_moves(level_attempt.moves());
+        if playing_level.moves().is_empty() {
+            return;
+        }
+
+        self.level_timer.set_time(level_attempt.time());
+        self.update_encountered_tiles();
+    }
+
+    fn open_level_attempt_prompt(&mut self, game_state: &mut GameState) {
+        match game_state.get_current_level_pack().unwrap().read_level_attempt(game_state.get_level_index()) {
+            Ok(Some(level_attempt)) => {
+                self.pending_level_attempt = Some(level_attempt);
+
+                game_state.open_dialog(Dialog::new_yes_no("Continue your last attempt? (\"No\" restarts the level)"));
+            },
+
+            Ok(None) => {},
+
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot load level attempt: {}", err)));
+            },
+        }
+    }
+
+    fn save_level_attempt(&self, game_state: &mut GameState) {
+        let Some(playing_level) = self.level.as_ref() else {
+            return;
+        };
+
+        let level_attempt = (!playing_level.moves().is_empty()).then(|| {
+            LevelAttempt::new(self.level_timer.time(), playing_level.moves())
+        });
+
+        let current_level_index = game_state.current_level_index;
+        let Some(level_pack) = game_state.get_current_level_pack() else {
+            return;
+        };
+
+        if let Err(err) = level_pack.save_level_attempt(current_level_index, level_attempt) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save level attempt: {}", err)));
+        }
+    }
+
+    fn quit_to_level_selection(&mut self, game_state: &mut GameState) {
+        //Level attempts are not saved during speedruns and remixes
+        //Progress is not saved for locked levels which were started from the level preview
+        let is_level_locked = game_state.get_current_level_pack().
+                is_some_and(|level_pack| game_state.current_level_index > level_pack.min_level_not_completed());
+        if !self.continue_flag && !self.game_over_flag && self.speedrun.is_none() && self.remix.is_none() && !is_level_locked {
+            self.save_level_attempt(game_state);
+        }
+
+        self.continue_flag = false;
+        self.game_over_flag = false;
+
+        game_state.set_screen(ScreenId::SelectLevel);
+    }
+
+    fn restart_level(&mut self, game_state: &mut GameState) {
+        let has_moved = self.level.as_ref().unwrap().current_move_index() > 0;
+        let should_play_sound_effect = has_moved && self.level_timer.time() > 50;
+
+        if let Some(remix) = self.remix.take() {
+            self.start_level(&remix.level);
+            self.remix = Some(remix);
+        }else {
+            let Some(level_pack) = game_state.get_current_level_pack() else {
+                return;
+            };
+
+            self.start_level(level_pack.levels()[game_state.current_level_index].level());
+
+            if has_moved {
+                game_state.on_level_started(true);
+            }
+        }
+
+        if should_play_sound_effect {
+            game_state.play_sound_effect(audio::LEVEL_RESET);
+        }
+    }
+
+    /// Pauses the timers and shows the pause menu
+    fn open_pause_menu(&mut self, game_state: &mut GameState) {
+        game_state.play_sound_effect_ui_select();
+
+        self.on_pause(game_state);
+
+        let mut entries = vec![PauseMenuEntry::Resume, PauseMenuEntry::Restart];
+
+        //The level is restored after the settings were closed, this is not possible for speedruns, remixes, and completed levels
+        if self.speedrun.is_none() && self.remix.is_none() && !self.continue_flag {
+            entries.push(PauseMenuEntry::Settings);
+        }
+
+        entries.extend([PauseMenuEntry::Help, PauseMenuEntry::QuitToSelection]);
+
+        self.pause_menu = Some(PauseMenu::new(entries));
+    }
+
+    fn on_pause_menu_entry_selected(&mut self, game_state: &mut GameState, entry: PauseMenuEntry) {
+        match entry {
+            PauseMenuEntry::Resume => {
+                game_state.play_sound_effect_ui_select();
+
+                self.pause_menu = None;
+                self.on_continue(game_state);
+            },
+
+            PauseMenuEntry::Restart => {
+                self.pause_menu = None;
+                self.on_continue(game_state);
+
+                self.restart_level(game_state);
+            },
+
+            PauseMenuEntry::Settings => {
+                game_state.play_sound_effect_ui_select();
+
+                //The current attempt is restored if the settings are closed
+                game_state.pending_snapshot_level_attempt = self.level_attempt_snapshot();
+                game_state.settings_back_screen_id = Some(ScreenId::InGame);
+
+                game_state.set_screen(ScreenId::Settings);
+            },
+
+            PauseMenuEntry::Help => {
+                //The timers stay paused while the help page is open
+                game_state.open_help_page();
+            },
+
+            PauseMenuEntry::QuitToSelection => {
+                game_state.play_sound_effect_ui_select();
+
+                if self.speedrun.is_some() {
+                    game_state.open_dialog(Dialog::new_yes_no("Abort speedrun and go back to level selection?"));
+                }else {
+                    self.quit_to_level_selection(game_state);
+                }
+            },
+        }
+    }
+
+    /// Leaves the level pack completed screen, the outro of the level pack is shown before the level selection
+    fn leave_game_over(&mut self, game_state: &mut GameState) {
+        self.continue_flag = false;
+        self.game_over_flag = false;
+
+        game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+        //The outro is not shown after speedruns
+        let has_outro = self.speedrun.is_none() && game_state.get_current_level_pack().
+                is_some_and(|level_pack| !level_pack.story(PackStory::Outro).is_empty());
+        if has_outro {
+            game_state.show_pack_story(PackStory::Outro);
+        }else {
+            game_state.set_screen(ScreenId::SelectLevel);
+        }
+    }
+
+    /// Starts the next level of the speedrun or finishes the speedrun after the last level
+    fn continue_speedrun(&mut self, game_state: &mut GameState) {
+        let current_level_index = game_state.current_level_index;
+        let Some(level_pack) = game_state.get_current_level_pack() else {
+            return;
+        };
+
+        if current_level_index + 1 < level_pack.unlocked_level_count() {
+            game_state.set_level_index(current_level_index + 1);
+            self.start_level(game_state.get_current_level_pack().unwrap().levels()[current_level_index + 1].level());
+            game_state.on_level_started(false);
+
+            return;
+        }
+
+        let Some(speedrun) = self.speedrun.as_mut() else {
+            return;
+        };
+
+        speedrun.timer.update();
+        speedrun.is_finished = true;
+
+        self.continue_flag = false;
+        self.game_over_flag = true;
+
+        let time = speedrun.timer.time();
+        if speedrun.best_time.is_none_or(|best_time| time < best_time) &&
+                let Err(err) = level_pack.save_speedrun_best_time(time) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save speedrun time: {}", err)));
+        }
+
+        game_state.play_sound_effect(audio::LEVEL_PACK_COMPLETE_EFFECT);
+    }
+
+    /// Draws the level pack number, the level number, the move counter, and the timer
+    fn draw_hud_bar(&self, game_state: &GameState, console: &Console, width: usize) {
+        let streamer_mode = game_state.settings.streamer_mode;
+
+        console.reset_color();
+        if streamer_mode.is_enabled() {
+            console.draw_text("Level: ");
+        }else {
+            console.draw_fmt(format_args!("Pack: {:02}", game_state.get_level_pack_index() + 1));
+
+            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 9) as f64 * 0.25) as usize, 0);
+            console.draw_text("Level: ");
+        }
+        console.draw_fmt(format_args!("{}", utils::LeadingAsciiNumber::new(2, game_state.current_level_index as u32 + 1, true)));
+
+        //The move counter and the timer are hidden in relaxed mode
+        if game_state.settings.relaxed_mode {
+            return;
+        }
+
+        let moves = self.level.as_ref().unwrap().current_move_index();
+        if streamer_mode.is_enabled() {
+            //The move counter is drawn next to the level number in order to keep the center free for smaller widths
+            console.draw_fmt(format_args!("  Moves: {:04}", moves));
+
+            if streamer_mode != StreamerMode::OnHideTime {
+                let time = utils::TimeDisplay(self.level_timer.time());
+
+                console.set_cursor_pos(width - 6 - time.width(), 0);
+                console.draw_fmt(format_args!("Time: {}", time));
+            }
+
+            return;
+        }
+
+        let time = utils::TimeDisplay(self.level_timer.time());
+        let time_x = width - 6 - time.width();
+
+        //The move counter is moved to the left for times of at least one hour
+        console.set_cursor_pos((((width - 11) as f64 * 0.75) as usize).min(time_x - 12), 0);
+        console.draw_fmt(format_args!("Moves: {:04}", moves));
+
+        console.set_cursor_pos(time_x, 0);
+        console.draw_fmt(format_args!("Time: {}", time));
+    }
+
+    fn draw_level(&self, game_state: &GameState, console: &Console, width: usize) {
+        let Some(playing_level) = self.level.as_ref() else {
+            return;
+        };
+
+        let level = &playing_level.current_playing_level().0;
+
+        let x_offset = ((width.max(level.draw_width()) - level.draw_width()) as f64 * 0.5) as usize;
+        let y_offset = 1;
+
+        if self.show_floor {
+            level.draw_floor(console, x_offset, y_offset, game_state.is_player_background(), playing_level.original_level(), None);
+        }else {
+            level.draw(console, x_offset, y_offset, game_state.is_player_background(), self.solver_hints.highlighted_pos(playing_level));
+
+            for box_sink_animation in self.box_sink_animations.iter() {
+                box_sink_animation.draw(console, x_offset, y_offset, level);
+            }
+        }
+    }
+
+    fn draw_speedrun_status(&self, console: &Console, speedrun: &Speedrun) {
+        let speedrun_time = utils::TimeDisplay(speedrun.timer.time());
+        let mut text_width = 10 + speedrun_time.width();
+        if let Some(&(time, best_time)) = speedrun.splits.last() {
+            let difference = best_time.map_or(9, |best_time| utils::TimeDisplay(time.abs_diff(best_time)).width() + 1);
+            text_width += 15 + utils::TimeDisplay(time).width() + difference;
+        }
+
+        console.reset_color();
+        console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH.saturating_sub(text_width) / 2, Game::CONSOLE_MIN_HEIGHT - 1);
+        console.draw_fmt(format_args!("Speedrun: {}", speedrun_time));
+
+        if let Some(&(time, best_time)) = speedrun.splits.last() {
+            console.draw_fmt(format_args!(" | Split {:03}: {} ", speedrun.splits.len(), utils::TimeDisplay(time)));
+            Self::draw_split_difference(console, time, best_time);
+        }
+    }
+
+    fn draw_speedrun_results(&self, game_state: &GameState, console: &Console, speedrun: &Speedrun) {
+        let level_pack = game_state.get_current_level_pack().unwrap();
+
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_fmt(format_args!("Speedrun results (Level pack \"{}\"):", level_pack.localized_name(game_state.settings.language)));
+        console.set_underline(false);
+
+        let time = speedrun.timer.time();
+
+        console.set_cursor_pos(0, 2);
+        console.draw_fmt(format_args!("Total time    : {}", utils::TimeDisplay(time)));
+
+        console.set_cursor_pos(0, 3);
+        console.draw_text("Best run      : ");
+        match speedrun.best_time {
+            Some(best_time) if best_time <= time => {
+                console.draw_fmt(format_args!("{}", utils::TimeDisplay(best_time)));
+            },
+
+            _ => {
+                console.set_color(Color::LightGreen, Color::Default);
+                console.draw_text("New best run!");
+            },
+        }
+
+        console.reset_color();
+        console.set_cursor_pos(0, 4);
+        console.draw_text("Sum of bests  : ");
+        match level_pack.level_pack_best_time_sum() {
+            None => console.draw_text("XX:XX.XXX"),
+            Some(best_time_sum) => console.draw_fmt(format_args!("{}", utils::TimeDisplay(best_time_sum))),
+        }
+
+        console.set_cursor_pos(0, 6);
+        console.set_underline(true);
+        console.draw_text("Level  Time         Best before  Difference");
+        console.set_underline(false);
+
+        for (i, &(time, best_time)) in speedrun.splits.iter().
+                enumerate().
+                skip(speedrun.results_scroll_offset).
+                take(Self::SPEEDRUN_RESULTS_ROW_COUNT) {
+            let y = 7 + i - speedrun.results_scroll_offset;
+
+            console.reset_color();
+            console.set_cursor_pos(0, y);
+            console.draw_fmt(format_args!("{:03}", i + 1));
+
+            console.set_cursor_pos(7, y);
+            console.draw_fmt(format_args!("{}", utils::TimeDisplay(time)));
+
+            console.set_cursor_pos(20, y);
+            match best_time {
+                None => console.draw_text("XX:XX.XXX"),
+                Some(best_time) => console.draw_fmt(format_args!("{}", utils::TimeDisplay(best_time))),
+            }
+
+            console.set_cursor_pos(33, y);
+            Self::draw_split_difference(console, time, best_time);
+        }
+
+        console.reset_color();
+        console.set_cursor_pos(0, Game::CONSOLE_MIN_HEIGHT - 1);
+        console.draw_text("Press ");
+
+        console.draw_key_input_text("ENTER");
+
+        console.reset_color();
+        console.draw_text(" to go back to the level selection screen");
+
+        if speedrun.splits.len() > Self::SPEEDRUN_RESULTS_ROW_COUNT {
+            console.draw_text(" (");
+
+            console.draw_key_input_text("UP");
+
+            console.reset_color();
+            console.draw_text("/");
+
+            console.draw_key_input_text("DOWN");
+
+            console.reset_color();
+            console.draw_text(": Scroll)");
+        }
+    }
+
+    /// Draws the difference between the split time and the best time (10 characters, 12 or more characters for differences of at least one hour)
+    fn draw_split_difference(console: &Console, time: u64, best_time: Option<u64>) {
+        let Some(best_time) = best_time else {
+            console.reset_color();
+            console.draw_text("(No best) ");
+
+            return;
+        };
+
+        if time <= best_time {
+            console.set_color(Color::LightGreen, Color::Default);
+            console.draw_fmt(format_args!("-{}", utils::TimeDisplay(best_time - time)));
+        }else {
+            console.set_color(Color::LightRed, Color::Default);
+            console.draw_fmt(format_args!("+{}", utils::TimeDisplay(time - best_time)));
+        }
+
+        console.reset_color();
+    }
+
+    fn draw_level_hints(&self, game_state: &GameState, console: &Console) {
+        let Some(level_pack) = game_state.get_current_level_pack() else {
+            return;
+        };
+
+        for hint in level_pack.levels()[game_state.current_level_index].localized_hints(game_state.settings.language) {
+            let is_triggered = match hint.trigger() {
+                LevelHintTrigger::Always => true,
+                LevelHintTrigger::Playing => !self.continue_flag && !self.game_over_flag,
+                LevelHintTrigger::Completed => self.continue_flag,
+                LevelHintTrigger::PackCompleted => self.game_over_flag && !self.secret_found_flag,
+                LevelHintTrigger::TileEncountered(tile) => self.encountered_tiles.contains(&tile),
+            };
+
+            if is_triggered {
+                draw_level_hint(console, hint);
+            }
+        }
+    }
+
+    /// Remembers all tiles which are next to or below the player for level hints
+    fn update_encountered_tiles(&mut self) {
+        let Some(playing_level) = self.level.as_ref() else {
+            return;
+        };
+
+        let (level, (player_x, player_y), _) = playing_level.current_playing_level();
+
+        let mut tiles = Vec::with_capacity(5);
+        if let Some(tile) = level.get_tile(*player_x, *player_y) {
+            tiles.push(tile.floor_tile());
+        }
+
+        for direction in [Direction::Left, Direction::Up, Direction::Right, Direction::Down] {
+            if let Some((x, y)) = level.neighbor_pos(*player_x, *player_y, direction) &&
+                    let Some(tile) = level.get_tile(x, y) {
+                tiles.push(tile);
+            }
+        }
+
+        for tile in tiles {
+            if !self.encountered_tiles.contains(&tile) {
+                self.encountered_tiles.push(tile);
+            }
+        }
+    }
+
+    /// Returns the moves of the current level including the penalty of revealed hints
+    fn scored_moves(&self) -> u32 {
+        self.level.as_ref().unwrap().current_move_index() as u32 + self.solver_hints.penalty_moves
+    }
+
+    /// Reveals the next tier of the hint for the current state, each revealed tier adds a moves penalty to the score
+    fn reveal_next_solver_hint(&mut self, game_state: &mut GameState) {
+        if self.speedrun.is_some() {
+            game_state.open_dialog(Dialog::new_ok_error("Hints are not available during speedruns!"));
+
+            return;
+        }
+
+        let Some(playing_level) = self.level.as_ref() else {
+            return;
+        };
+
+        let tier = SolverHintTier::next(self.solver_hints.revealed_tier(playing_level));
+        let Some(solution) = self.solver_hints.solution_cache.solution(playing_level).
+                filter(|solution| !solution.is_empty()) else {
+            game_state.play_sound_effect_ui_error();
+            game_state.show_toast("No solution was found for this position");
+
+            return;
+        };
+
+        let first_move = solution[0];
+        let next_push = solver::find_next_push(playing_level, solution);
+        let next_moves = solution.iter().
+                take(Self::SOLVER_HINT_MOVE_COUNT).
+                copied().
+                collect::<VecDeque<_>>();
+
+        self.solver_hints.revealed_state = playing_level.current_playing_level().0.tiles().to_vec();
+        self.solver_hints.revealed_tier = Some(tier);
+        self.solver_hints.revealed_box_pos = next_push.map(|(pos, _)| pos);
+        self.solver_hints.revealed_direction = Some(next_push.map_or(first_move, |(_, direction)| direction));
+        self.solver_hints.penalty_moves += tier.penalty_moves();
+
+        if tier == SolverHintTier::NextMoves {
+            self.solver_hints.pending_moves = next_moves;
+            self.solver_hints.pending_move_delay = 0;
+        }
+
+        game_state.play_sound_effect_ui_select();
+        game_state.show_toast(format!("Hint revealed (+{} moves)", tier.penalty_moves()));
+    }
+
+    fn move_player(&mut self, game_state: &mut GameState, direction: Direction) {
+        self.undo_scrub = None;
+
+        let is_push = self.level.as_ref().unwrap().is_push(direction);
+        self.is_push_move = is_push;
+
+        let move_result = self.level.as_mut().unwrap().move_player(direction);
+        if move_result.is_animation() {
+            self.animation_first_frame = true;
+        }
+        if !move_result.is_invalid() {
+            game_state.session_stats.on_move();
+            game_state.record_total_stat(TotalStat::Moves);
+            if is_push {
+                game_state.record_total_stat(TotalStat::Pushes);
+            }
+        }
+        self.handle_move_result(game_state, move_result);
+    }
+
+    fn handle_move_result(&mut self, game_state: &mut GameState, move_result: MoveResult) {
+        #[cfg(feature = "steam")]
+        let steam_client = game_state.steam_client.clone();
+
+        self.update_encountered_tiles();
+
+        if move_result.has_won() {
+            game_state.session_stats.on_level_completed();
+        }
+
+        if let Some(playing_level) = &self.level {
+            for tile_event in playing_level.tile_events() {
+                match *tile_event {
+                    //Decorative animations are not shown with reduced motion
+                    TileEvent::BoxSunk { .. } if game_state.startup_flags().reduced_motion() => {},
+                    TileEvent::BoxSunk { x, y } => self.box_sink_animations.push(BoxSinkAnimation::new(x, y)),
+                }
+            }
+        }
+
+        let current_level_pack_index = game_state.current_level_pack_index;
+        let current_level_index = game_state.current_level_index;
+        let relaxed_mode = game_state.settings.relaxed_mode;
+        let save_writer = game_state.save_writer.clone();
+        let Some(level_pack) = game_state.get_current_level_pack_mut() else {
+            return;
+        };
+
+        let step_sound_effect_id = if self.is_push_move {
+            PackSoundEffectId::Push
+        }else {
+            PackSoundEffectId::Step
+        };
+
+        let mut level_completed_event = None;
+
+        match move_result {
+            MoveResult::Valid { has_won, secret_found, sound_effect } => {
+                self.level_timer.start();
+                if let Some(speedrun) = &mut self.speedrun {
+                    speedrun.timer.start();
+                }
+
+                if secret_found {
+                    self.game_over_flag = true;
+                    self.secret_found_flag = true;
+                }
+
+                let moves = self.scored_moves();
+                if has_won && let Some(remix) = &mut self.remix {
+                    self.continue_flag = true;
+
+                    //Remixes do not change the progress and the best scores of the level
+                    let time = (!relaxed_mode).then_some(self.level_timer.time());
+                    self.new_record = NewRecord::new(remix.best_moves, remix.best_time, moves, time);
+
+                    remix.best_moves = Some(remix.best_moves.map_or(moves, |best_moves| best_moves.min(moves)));
+                    remix.best_time = match (remix.best_time, time) {
+                        (Some(best_time), Some(time)) => Some(best_time.min(time)),
+                        (best_time, time) => best_time.or(time),
+                    };
+
+                    if let Err(err) = level_pack.save_remix_best_score(current_level_index, remix.seed, time, moves) {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save remix score: {}", err)));
+                    }
+
+                    level_completed_event = Some(LevelCompletedEvent {
+                        level_pack_index: current_level_pack_index,
+                        level_index: current_level_index,
+                        moves,
+                        time,
+                        is_progress_saved: false,
+                        remix_seed: Some(remix.seed),
+                    });
+
+                    if self.new_record.is_some() {
+                        game_state.play_sound_effect(audio::NEW_RECORD_EFFECT);
+                    }else {
+                        game_state.play_pack_sound_effect(PackSoundEffectId::LevelComplete);
+                    }
+                }else if has_won && current_level_index > level_pack.min_level_not_completed() {
+                    //Progress is not saved for locked levels which were started from the level preview
+                    self.continue_flag = true;
+
+                    level_completed_event = Some(LevelCompletedEvent {
+                        level_pack_index: current_level_pack_index,
+                        level_index: current_level_index,
+                        moves: self.scored_moves(),
+                        time: (!relaxed_mode).then_some(self.level_timer.time()),
+                        is_progress_saved: false,
+                        remix_seed: None,
+                    });
+
+                    game_state.play_pack_sound_effect(PackSoundEffectId::LevelComplete);
+                }else if has_won {
+                    self.continue_flag = true;
+
+                    //Update best scores (No time is recorded in relaxed mode)
+                    let time = self.level_timer.time();
+                    let moves = self.scored_moves();
+
+                    if let Some(speedrun) = &mut self.speedrun {
+                        speedrun.splits.push((time, level_pack.levels()[current_level_index].best_time()));
+                    }
+
+                    //The previous best values must be compared before they are overwritten
+                    let time = (!relaxed_mode).then_some(time);
+                    let level = &level_pack.levels()[current_level_index];
+                    self.new_record = NewRecord::new(level.best_moves(), level.best_time(), moves, time);
+
+                    level_pack.update_stats(current_level_index, time, moves);
+
+                    if current_level_index >= level_pack.min_level_not_completed() {
+                        level_pack.set_min_level_not_completed(current_level_index + 1);
+                    }
+
+                    level_completed_event = Some(LevelCompletedEvent {
+                        level_pack_index: current_level_pack_index,
+                        level_index: current_level_index,
+                        moves,
+                        time,
+                        is_progress_saved: true,
+                        remix_seed: None,
+                    });
+
+                    if let Err(err) = level_pack.save_save_game_in_background(&save_writer) {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                    }else if let Err(err) = level_pack.save_level_attempt(current_level_index, None) {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save level attempt: {}", err)));
+                    }else if self.new_record.is_none() {
+                        game_state.show_toast("Progress saved");
+                    }
+
+                    if self.new_record.is_some() {
+                        game_state.play_sound_effect(audio::NEW_RECORD_EFFECT);
+                    }else {
+                        game_state.play_pack_sound_effect(PackSoundEffectId::LevelComplete);
+                    }
+                }
+
+                game_state.play_pack_sound_effect(step_sound_effect_id);
+
+                if let Some(sound_effect) = sound_effect {
+                    game_state.play_level_sound_effect(sound_effect);
+                }
+            },
+
+            MoveResult::Invalid => {
+                game_state.play_sound_effect(audio::NO_PATH_EFFECT);
+            },
+
+            MoveResult::Animation { sound_effect, .. } => {
+                if self.animation_first_frame {
+                    game_state.play_pack_sound_effect(step_sound_effect_id);
+                }
+
+                if let Some(sound_effect) = sound_effect {
+                    game_state.play_level_sound_effect(sound_effect);
+                }
+            },
+        }
+
+        if let Some(level_completed_event) = level_completed_event {
+            game_state.on_level_completed(level_completed_event);
+        }
+
+        if self.secret_found_flag {
+            //Finding a secret ends the speedrun
+            self.speedrun = None;
+
+            #[cfg(feature = "steam")]
+            Achievement::LEVEL_PACK_SECRET_DISCOVERED.unlock(steam_client.clone());
+
+            game_state.open_dialog(Dialog::new_ok_secret_found("You have found a secret!"));
+
+            if let Err(err) = game_state.on_found_secret() {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Error: {}", err)));
+            }
+
+            //Secrets of remixes are not tracked
+            if self.remix.is_none() && let Some(level_pack) = game_state.get_current_level_pack_mut() &&
+                    !level_pack.is_secret_found(current_level_index) {
+                let is_bonus_level_unlocked = level_pack.set_secret_found(current_level_index);
+
+                if let Err(err) = level_pack.save_save_game_in_background(&save_writer) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                }else if is_bonus_level_unlocked {
+                    game_state.show_toast("Bonus level unlocked!");
+                }
+            }
+        }else if self.continue_flag && self.speedrun.is_some() {
+            self.continue_speedrun(game_state);
+        }
+    }
+}
+
+impl Screen for ScreenInGame {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        if let Some(speedrun) = &self.speedrun && speedrun.is_finished {
+            self.draw_speedrun_results(game_state, console, speedrun);
+
+            return;
+        }
+
+        //The columns of the overlay margin are kept empty
+        let width = Game::CONSOLE_MIN_WIDTH - game_state.settings.overlay_margin();
+
+        if game_state.is_hud_hidden() {
+            self.draw_level(game_state, console, width);
+
+            if let Some(pause_menu) = &self.pause_menu {
+                pause_menu.draw(game_state, console, Self::REGION_PAUSE_MENU);
+            }
+
+            return;
+        }
+
+        self.draw_hud_bar(game_state, console, width);
+
+        if self.continue_flag && let Some(new_record) = &self.new_record {
+            new_record.draw(console, width);
+        }else if self.continue_flag {
+            console.set_cursor_pos(((width - 16) as f64 * 0.5) as usize, 0);
+            console.draw_text("Level completed!");
+        }else if self.game_over_flag {
+            if self.secret_found_flag {
+                console.set_cursor_pos(((width - 13) as f64 * 0.5) as usize, 0);
+                console.draw_text("Secret found!");
+            }else {
+                console.set_cursor_pos(((width - 13) as f64 * 0.5) as usize, 0);
+                console.draw_text("You have won!");
+            }
+        }else if let Some(undo_scrub) = &self.undo_scrub && undo_scrub.is_indicator_visible() &&
+                let Some(playing_level) = self.level.as_ref() {
+            let text = format!("Move {}/{}", playing_level.current_move_index(), playing_level.max_move_index());
+
+            console.set_cursor_pos(((width - text.len()) as f64 * 0.5) as usize, 0);
+            console.draw_text(text);
+        }else if let Some(playing_level) = self.level.as_ref() &&
+                let Some(text) = self.solver_hints.text(playing_level) {
+            console.set_cursor_pos(((width - text.len()) as f64 * 0.5) as usize, 0);
+            console.set_color(Color::LightCyan, Color::Default);
+            console.draw_text(text);
+            console.reset_color();
+        }else if self.show_floor {
+            console.set_cursor_pos(((width - 14) as f64 * 0.5) as usize, 0);
+            console.draw_text("Show tiles (");
+            console.draw_action_key_input_text(InGameAction::ToggleFloor);
+            console.reset_color();
+            console.draw_text(")");
+        }else if let Some(playing_level) = self.level.as_ref() &&
+                !playing_level.original_key_door_count().is_empty() {
+            let key_door_count = playing_level.current_playing_level().2;
+
+            //Collected keys of all keys of the level
+            console.set_cursor_pos(((width - 15) as f64 * 0.5) as usize, 0);
+            Tile::Key.draw(console, false, false);
+            console.reset_color();
+            console.draw_fmt(format_args!(": {:02}/{:02}  ", key_door_count.collected_keys(), playing_level.original_key_door_count().keys()));
+
+            Tile::LockedDoor.draw(console, false, false);
+            console.reset_color();
+            console.draw_fmt(format_args!(": {:02}", key_door_count.locked_doors()));
+        }else if let Some(remix) = &self.remix {
+            let text = format!("Remix #{:03}", remix.seed);
+
+            console.set_cursor_pos(((width - text.len()) as f64 * 0.5) as usize, 0);
+            console.draw_text(text);
+        }
+
+        self.draw_level(game_state, console, width);
+
+        if let Some(playing_level) = self.level.as_ref() {
+            let level = &playing_level.current_playing_level().0;
+            let y_offset = 1;
+
+            self.draw_level_hints(game_state, console);
+
+            if let Some(speedrun) = &self.speedrun && y_offset + level.draw_height() < Game::CONSOLE_MIN_HEIGHT {
+                self.draw_speedrun_status(console, speedrun);
+            }
+
+            if self.show_tile_legend {
+                draw_tile_legend(console, level, Some(InGameAction::ToggleTileLegend));
+            }
+        }
+
+        if let Some(pause_menu) = &self.pause_menu {
+            pause_menu.draw(game_state, console, Self::REGION_PAUSE_MENU);
+        }
+    }
+
+    fn update(&mut self, game_state: &mut GameState) {
+        if game_state.is_dialog_opened() || self.game_over_flag || self.continue_flag || self.pause_menu.is_some() {
+            return;
+        }
+
+        self.level_timer.update();
+        if let Some(speedrun) = &mut self.speedrun {
+            speedrun.timer.update();
+        }
+    }
+
+    fn is_animated(&self, _: &GameState) -> bool {
+        //The level timer is updated continuously
+        self.level.is_some()
+    }
+
+    fn animate(&mut self, game_state: &mut GameState) {
+        //The color of the new record text does not cycle with reduced motion
+        if self.continue_flag && !game_state.startup_flags().reduced_motion() && let Some(new_record) = &mut self.new_record {
+            new_record.frame += 1;
+        }
+
+        if game_state.is_dialog_opened() || self.game_over_flag || self.continue_flag || self.pause_menu.is_some() {
+            return;
+        }
+
+        for box_sink_animation in self.box_sink_animations.iter_mut() {
+            box_sink_animation.frame += 1;
+        }
+        self.box_sink_animations.retain(|box_sink_animation| !box_sink_animation.is_finished());
+
+        if let Some(playing_level) = &mut self.level &&
+                playing_level.is_playing_animation() && !self.animation_first_frame {
+            let move_result = playing_level.continue_animation();
+            self.handle_move_result(game_state, move_result);
+        }
+        self.animation_first_frame = false;
+
+        //The moves of a hint are played one by one after the animation of the previous move has finished
+        if !self.solver_hints.pending_moves.is_empty() &&
+                self.level.as_ref().is_some_and(|playing_level| !playing_level.is_playing_animation()) {
+            if self.solver_hints.pending_move_delay > 0 {
+                self.solver_hints.pending_move_delay -= 1;
+            }else if let Some(direction) = self.solver_hints.pending_moves.pop_front() {
+                self.solver_hints.pending_move_delay = SolverHints::MOVE_DELAY_FRAMES;
+
+                self.move_player(game_state, direction);
+            }
+        }
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        //Any input stops the moves which are played by a hint
+        self.solver_hints.pending_moves.clear();
+
+        //The keys of the control preset are handled as the keys of the default preset
+        let Some(key) = input::to_default_preset_key(key, game_state.settings.control_preset) else {
+            return;
+        };
+
+        if let Some(pause_menu) = &mut self.pause_menu {
+            if let Some(entry) = pause_menu.on_key_pressed(game_state, key) {
+                self.on_pause_menu_entry_selected(game_state, entry);
+            }
+
+            return;
+        }
+
+        if key == Key::ESC {
+            if self.game_over_flag {
+                self.leave_game_over(game_state);
+
+                return;
+            }
+
+            self.open_pause_menu(game_state);
+
+            return;
+        }
+
+        if self.game_over_flag {
+            if let Some(speedrun) = &mut self.speedrun {
+                let max_scroll_offset = speedrun.splits.len().saturating_sub(Self::SPEEDRUN_RESULTS_ROW_COUNT);
+
+                match key {
+                    Key::UP | Key::W => {
+                        speedrun.results_scroll_offset = speedrun.results_scroll_offset.saturating_sub(1);
+                    },
+                    Key::DOWN | Key::S => {
+                        speedrun.results_scroll_offset = (speedrun.results_scroll_offset + 1).min(max_scroll_offset);
+                    },
+
+                    _ => {},
+                }
+            }
+
+            if key == Key::ENTER || key == Key::SPACE {
+                self.leave_game_over(game_state);
+            }
+
+            return;
+        }
+
+        let current_level_index = game_state.current_level_index;
+        if game_state.get_current_level_pack().is_none() {
+            return;
+        }
+
+        //Reset
+        if key == Key::R {
+            self.restart_level(game_state);
+
+            return;
+        }
+
+        if key == Key::Q {
+            game_state.play_sound_effect_ui_select();
+            self.show_floor = !self.show_floor;
+
+            return;
+        }
+
+        if key == Key::L {
+            game_state.play_sound_effect_ui_select();
+            self.show_tile_legend = !self.show_tile_legend;
+
+            return;
+        }
+
+        //Level end (Prevent movement)
+        if self.continue_flag {
+            if (key == Key::ENTER || key == Key::SPACE) && self.remix.is_some() {
+                self.continue_flag = false;
+
+                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                game_state.set_screen(ScreenId::SelectLevel);
+            }else if key == Key::ENTER || key == Key::SPACE {
+                self.continue_flag = false;
+
+                let shuffled_level_order = game_state.shuffled_level_order.as_ref().
+                        filter(|(level_pack_index, _)| *level_pack_index == game_state.current_level_pack_index).
+                        map(|(_, level_order)| level_order);
+
+                if let Some(shuffled_level_order) = shuffled_level_order {
+                    let next_level_index = shuffled_level_order.iter().
+                            position(|&level_index| level_index == current_level_index).
+                            and_then(|i| shuffled_level_order.get(i + 1).copied());
+
+                    //The last level of the shuffled order was completed
+                    let Some(next_level_index) = next_level_index else {
+                        game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+                        game_state.set_screen(ScreenId::SelectLevel);
+
+                        return;
+                    };
+
+                    game_state.set_level_index(next_level_index);
+                }else if current_level_index + 1 >= game_state.get_current_level_pack().unwrap().unlocked_level_count() {
+                    //All levels completed (A locked bonus level is not started)
+                    self.game_over_flag = true;
+
+                    game_state.play_sound_effect(audio::LEVEL_PACK_COMPLETE_EFFECT);
+
+                    #[cfg(feature = "steam")]
+                    self.open_workshop_rating_prompt(game_state);
+
+                    return;
+                }else {
+                    game_state.set_level_index(current_level_index + 1);
+                }
+
+                self.solver_hints = SolverHints::default();
+                self.start_level(game_state.get_current_level_pack().unwrap().levels()[game_state.current_level_index].level());
+                game_state.on_level_started(false);
+            }
+
+            return;
+        }
+
+        //Prevent movement during animation
+        if self.level.as_mut().unwrap().is_playing_animation() {
+            //Allow undo while animation is playing
+            if key == Key::U || key == Key::Z {
+                let level = self.level.as_mut().unwrap().cancel_animation_and_undo_move();
+                if level.is_some() {
+                    self.box_sink_animations.clear();
+                    game_state.session_stats.on_undo();
+                    game_state.record_total_stat(TotalStat::Undos);
+
+                    game_state.play_sound_effect(audio::UNDO_REDO_EFFECT);
+                }
+            }
+
+            return;
+        }
+
+        if key == Key::H {
+            self.reveal_next_solver_hint(game_state);
+
+            return;
+        }
+
+        if matches!(key, Key::U | Key::Z | Key::Y | Key::COMMA | Key::DOT) {
+            let is_redo = matches!(key, Key::Y | Key::DOT);
+
+            let scrub_move_count = self.undo_scrub.get_or_insert_with(|| UndoScrub::new(key)).on_key_pressed(key);
+            let move_count = if matches!(key, Key::COMMA | Key::DOT) {
+                Self::UNDO_JUMP_MOVE_COUNT
+            }else {
+                scrub_move_count
+            };
+
+            let playing_level = self.level.as_mut().unwrap();
+            let changed_move_count = if is_redo {
+                playing_level.redo_moves(move_count)
+            }else {
+                playing_level.undo_moves(move_count)
+            };
+
+            if changed_move_count > 0 {
+                self.box_sink_animations.clear();
+                if !is_redo {
+                    for _ in 0..changed_move_count {
+                        game_state.session_stats.on_undo();
+                        game_state.record_total_stat(TotalStat::Undos);
+                    }
+                }
+
+                game_state.play_sound_effect(audio::UNDO_REDO_EFFECT);
+            }
+
+            return;
+        }
+
+        let direction = match key {
+            Key::W | Key::UP => Some(Direction::Up),
+            Key::A | Key::LEFT => Some(Direction::Left),
+            Key::S | Key::DOWN => Some(Direction::Down),
+            Key::D | Key::RIGHT => Some(Direction::Right),
+
+            _ => None,
+        };
+
+        if let Some(direction) = direction {
+            self.move_player(game_state, direction);
+        }
+    }
+
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        if let Some(level_attempt) = self.pending_level_attempt.take() {
+            if selection == DialogSelection::Yes {
+                self.restore_level_attempt(&level_attempt);
+            }else {
+                let current_level_index = game_state.current_level_index;
+                if let Some(level_pack) = game_state.get_current_level_pack() &&
+                        let Err(err) = level_pack.save_level_attempt(current_level_index, None) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save level attempt: {}", err)));
+                }
+            }
+
+            return;
+        }
+
+        #[cfg(feature = "steam")]
+        if self.is_showing_workshop_rating_prompt {
+            self.is_showing_workshop_rating_prompt = false;
+
+            let Some(workshop_id) = game_state.get_current_level_pack().
+                    and_then(|level_pack| level_pack.steam_level_pack_data()).
+                    map(|steam_level_pack_data| steam_level_pack_data.workshop_id()) else {
+                return;
+            };
+
+            match selection {
+                DialogSelection::Yes | DialogSelection::No => {
+                    steam::vote_workshop_item(&game_state.steam_client, workshop_id, selection == DialogSelection::Yes);
+
+                    game_state.show_toast("Thank you for rating this level pack!");
+                },
+
+                DialogSelection::Ok => {
+                    game_state.steam_client.friends().activate_game_overlay_to_web_page(&format!("steam://url/CommunityFilePage/{}", workshop_id.0));
+                },
+
+                DialogSelection::Cancel => {},
+            }
+
+            return;
+        }
+
+        if self.secret_found_flag {
+            self.continue_flag = false;
+            self.game_over_flag = false;
+            self.secret_found_flag = false;
+
+            //Set level pack selection to secret level pack
+            game_state.set_level_pack_index(4);
+            game_state.set_screen(ScreenId::SelectLevelPack);
+
+            return;
+        }
+
+        //The speedrun abort dialog of the pause menu
+        if selection == DialogSelection::Yes {
+            self.quit_to_level_selection(game_state);
+        }
+    }
+
+    fn on_region_clicked(&mut self, game_state: &mut GameState, click: RegionClick) {
+        if click.id == Self::REGION_PAUSE_MENU && let Some(pause_menu) = &mut self.pause_menu &&
+                let Some(entry) = pause_menu.on_region_clicked(click) {
+            self.on_pause_menu_entry_selected(game_state, entry);
+        }
+    }
+
+    fn level_attempt_snapshot(&self) -> Option<LevelAttempt> {
+        //Snapshots can only restore attempts of the original level
+        if self.remix.is_some() {
+            return None;
+        }
+
+        let playing_level = self.level.as_ref()?;
+
+        (!playing_level.moves().is_empty()).then(|| {
+            LevelAttempt::new(self.level_timer.time(), playing_level.moves())
+        })
+    }
+
+    fn on_pause(&mut self, _: &mut GameState) {
+        self.level_timer.pause();
+        if let Some(speedrun) = &mut self.speedrun {
+            speedrun.timer.pause();
+        }
+    }
+
+    fn on_continue(&mut self, _: &mut GameState) {
+        //The timers are resumed if the pause menu is closed
+        if self.game_over_flag || self.continue_flag || self.pause_menu.is_some() {
+            return;
+        }
+
+        self.level_timer.resume();
+        if let Some(speedrun) = &mut self.speedrun {
+            speedrun.timer.resume();
+        }
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.solver_hints = SolverHints::default();
+        self.pause_menu = None;
+        self.start_level(game_state.get_current_level_pack().as_ref().unwrap().levels().get(
+            game_state.get_level_index()).unwrap().level());
+
+        self.pending_level_attempt = None;
+
+        self.speedrun = None;
+        self.remix = None;
+        if let Some((seed, level)) = game_state.pending_remix.take() {
+            //Remixes always start from the beginning
+            self.start_remix(game_state, seed, level);
+        }else if let Some(level_attempt) = game_state.pending_snapshot_level_attempt.take() {
+            self.restore_level_attempt(&level_attempt);
+        }else if mem::take(&mut game_state.should_start_speedrun) {
+            let best_time = match game_state.get_current_level_pack().unwrap().read_speedrun_best_time() {
+                Ok(best_time) => best_time,
+
+                Err(err) => {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot load speedrun time: {}", err)));
+
+                    None
+                },
+            };
+
+            self.speedrun = Some(Speedrun::new(best_time));
+        }else {
+            self.open_level_attempt_prompt(game_state);
+        }
+
+        if self.remix.is_none() {
+            game_state.on_level_started(false);
+        }
+
+        if let Err(err) = game_state.get_current_level_pack().unwrap().save_last_played() {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save last played time: {}", err)));
+        }
+
+        #[cfg(feature = "steam")]
+        if game_state.get_current_level_pack().unwrap().steam_level_pack_data().is_some() {
+            Achievement::STEAM_WORKSHOP_LEVEL_PACK_PLAYED.unlock(game_state.steam_client.clone());
+        }
+
+        if let Some(background_music_id) = game_state.get_current_level_pack().as_ref().unwrap().background_music_id() {
+            game_state.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id));
+        }else {
+            game_state.stop_background_music();
+        }
+    }
+}
+
+/// Scrollable intro or outro text of a level pack which is shown with the background music of the level pack
+pub struct ScreenPackStory {
+    story: PackStory,
+
+    scroll_position_row: usize,
+    scroll_position_row_max: usize,
+}
+
+impl ScreenPackStory {
+    const CONTENT_START_ROW: usize = 2;
+    const VISIBLE_ROW_COUNT: usize = Game::CONSOLE_MIN_HEIGHT - 1 - Self::CONTENT_START_ROW;
+
+    pub fn new() -> Self {
+        Self {
+            story: PackStory::Intro,
+
+            scroll_position_row: 0,
+            scroll_position_row_max: 0,
+        }
+    }
+
+    fn on_continue(&mut self, game_state: &mut GameState) {
+        game_state.play_sound_effect_ui_select();
+
+        match self.story {
+            PackStory::Intro => game_state.set_screen(ScreenId::InGame),
+            PackStory::Outro => game_state.set_screen(ScreenId::SelectLevel),
+        }
+    }
+}
+
+impl Screen for ScreenPackStory {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        let Some(level_pack) = game_state.get_current_level_pack() else {
+            return;
+        };
+        let lines = level_pack.story(self.story);
+
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_fmt(format_args!("{} (Level pack \"{}\"):", self.story.display_name(), level_pack.localized_name(game_state.settings.language)));
+        console.set_underline(false);
+
+        for (i, line) in lines.iter().
+                enumerate().
+                skip(self.scroll_position_row).
+                take(Self::VISIBLE_ROW_COUNT) {
+            console.set_cursor_pos(3, Self::CONTENT_START_ROW + i - self.scroll_position_row);
+            console.draw_text(line);
+        }
+
+        console.set_color(Color::Cyan, Color::Default);
+        if self.scroll_position_row > 0 {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 2, Self::CONTENT_START_ROW);
+            console.draw_text("^");
+        }
+        if self.scroll_position_row < self.scroll_position_row_max {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 2, Self::CONTENT_START_ROW + Self::VISIBLE_ROW_COUNT - 1);
+            console.draw_text("v");
+        }
+
+        console.set_cursor_pos(0, Game::CONSOLE_MIN_HEIGHT - 1);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(match self.story {
+            PackStory::Intro => ": Start level 1  ",
+            PackStory::Outro => ": Continue  ",
+        });
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back to level selection");
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if key == Key::ESC {
+            game_state.play_sound_effect_ui_select();
+
+            game_state.set_screen(ScreenId::SelectLevel);
+
+            return;
+        }
+
+        if key == Key::ENTER || key == Key::SPACE {
+            self.on_continue(game_state);
+
+            return;
+        }
+
+        if key == Key::UP && self.scroll_position_row > 0 {
+            self.scroll_position_row -= 1;
+        }else if key == Key::DOWN && self.scroll_position_row < self.scroll_position_row_max {
+            self.scroll_position_row += 1;
+        }
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if row == Game::CONSOLE_MIN_HEIGHT - 1 && column < 5 {
+            self.on_key_pressed(game_state, Key::ENTER);
+        }
+    }
+
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
+        }
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.story = game_state.pending_pack_story.take().unwrap_or(PackStory::Intro);
+
+        self.scroll_position_row = 0;
+        self.scroll_position_row_max = game_state.get_current_level_pack().
+                map_or(0, |level_pack| level_pack.story(self.story).len().saturating_sub(Self::VISIBLE_ROW_COUNT));
+
+        if let Some(background_music_id) = game_state.get_current_level_pack().and_then(|level_pack| level_pack.background_music_id()) {
+            game_state.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id));
+        }else {
+            game_state.stop_background_music();
+        }
+    }
+}
+
+pub struct ScreenSelectLevelPackEditor {
+    level_pack_editor_list: UIList<bool>,
+
+    is_exporting_level_pack: bool,
+    is_exporting_level_pack_archive: bool,
+    is_deleting_level_pack: bool,
+    #[cfg(feature = "steam")]
+    is_confirming_solver_validation: bool,
+
+    is_creating_new_level_pack: bool,
+    new_level_pack_id: String,
+
+    is_importing_level_pack_archive: bool,
+    is_resolving_import_conflict: bool,
+    import_file_name: String,
+}
+
+impl ScreenSelectLevelPackEditor {
+    pub fn new() -> Self {
+        Self {
+            level_pack_editor_list: UIList::new(
+                Rect::new(0, 1, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT - 1),
+                vec![
+                    UIListElement::new("<<", Color::White, Color::LightBlue),
+                    //[Level Pack Editor Entries]
+                ],
+                Box::new(|is_creating_new_level_pack: &mut bool, game_state: &mut GameState, cursor_index: usize| {
+                    if cursor_index == 0 {
+                        game_state.play_sound_effect_ui_select();
+                        game_state.set_screen(ScreenId::SelectLevelPack);
+
+                        return;
+                    }
+
+                    let level_pack_index = cursor_index - 1;
+                    if level_pack_index == game_state.editor_state.get_level_pack_count() {
+                        //Level Pack Editor entry
+                        if game_state.editor_state.get_level_pack_count() == LevelPack::MAX_LEVEL_PACK_COUNT {
+                            game_state.open_dialog(Dialog::new_ok_error(format!(
+                                "Cannot create new level packs (Max level pack count ({}) reached)",
+                                LevelPack::MAX_LEVEL_PACK_COUNT,
+                            )));
+                        }else {
+                            game_state.play_sound_effect_ui_select();
+                            *is_creating_new_level_pack = true;
+                        }
+                    }else {
+                        game_state.play_sound_effect_ui_select();
+                        game_state.editor_state.set_level_pack_index(level_pack_index);
+
+                        //Set selected level pack
+                        game_state.editor_state.set_level_index(0);
+                        game_state.set_screen(ScreenId::LevelPackEditor);
+                    }
+                }),
+            ),
+
+            is_exporting_level_pack: Default::default(),
+            is_exporting_level_pack_archive: Default::default(),
+            is_deleting_level_pack: Default::default(),
+            #[cfg(feature = "steam")]
+            is_confirming_solver_validation: Default::default(),
+
+            is_creating_new_level_pack: Default::default(),
+            new_level_pack_id: String::new(),
+
+            is_importing_level_pack_archive: Default::default(),
+            is_resolving_import_conflict: Default::default(),
+            import_file_name: String::new(),
+        }
+    }
+
+    /// Validates the levels which were not validated yet with the solver and opens the Steam Workshop upload popup afterward
+    #[cfg(feature = "steam")]
+    fn validate_with_solver_and_open_upload_popup(&mut self, game_state: &mut GameState) {
+        let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+        if let Err(unsolved_level_indices) = solver::validate_unvalidated_levels(level_pack) {
+            game_state.open_dialog(Dialog::new_ok_error(format!(
+                "The solver could not validate the following levels:\n{}\n\nThese levels must be validated in the level editor.",
+                unsolved_level_indices.iter().
+                        map(|level_index| (level_index + 1).to_string()).
+                        collect::<Vec<_>>().
+                        join(", "),
+            )));
+
+            return;
+        }
+
+        if let Err(err) = level_pack.save_editor_level_pack() {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+
+            return;
+        }
+
+        self.update_list_elements(game_state);
+
+        Self::open_workshop_upload_popup(game_state);
+    }
+
+    #[cfg(feature = "steam")]
+    fn open_workshop_upload_popup(game_state: &mut GameState) {
+        let ret = steam::prepare_workshop_upload_temp_data(
+            game_state.editor_state.get_current_level_pack().unwrap(),
+        );
+        if let Err(err) = ret {
+            game_state.open_dialog(Dialog::new_ok_error(format!(
+                "Could not prepare files for upload to steam workshop!\n{err}",
+            )));
+
+            return;
+        }
+
+        game_state.play_sound_effect_ui_dialog_open();
+        game_state.show_workshop_upload_popup = true;
+    }
+
+    const RENAME_OPTION: &'static DialogOption = &DialogOption::new("[R]ename", DialogSelection::Yes, &[Key::R]);
+    const OVERWRITE_OPTION: &'static DialogOption = &DialogOption::new("[O]verwrite", DialogSelection::No, &[Key::O]);
+
+    /// Imports the level pack archive with the entered file name or asks how an existing level pack with the same ID
+    /// should be handled
+    fn start_import_level_pack_archive(&mut self, game_state: &mut GameState) {
+        let level_pack_id = self.import_file_name.clone();
+        if level_pack_id.len() >= 3 && game_state.editor_state.level_packs.iter().any(|level_pack| level_pack.id() == level_pack_id) {
+            self.is_resolving_import_conflict = true;
+
+            game_state.open_dialog(Dialog::new(
+                DialogType::Information,
+                format!(
+                    "The level pack with the ID \"{}\" already exists!\n\n\
+                    Rename: The archive is imported with a new ID\n\
+                    Overwrite: The existing level pack is moved to \"Recently deleted\"",
+                    level_pack_id,
+                ),
+                Box::from([Self::RENAME_OPTION, Self::OVERWRITE_OPTION, DialogOption::CANCEL]),
+            ));
+
+            return;
+        }
+
+        self.finish_import_level_pack_archive(game_state, &level_pack_id, false);
+    }
+
+    fn finish_import_level_pack_archive(&mut self, game_state: &mut GameState, level_pack_id: &str, overwrite: bool) {
+        if let Err(err) = self.import_level_pack_archive(game_state, level_pack_id, overwrite) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot import: {}", err)));
+
+            return;
+        }
+
+        game_state.play_sound_effect_ui_select();
+        if level_pack_id == self.import_file_name {
+            game_state.show_toast("Level pack imported");
+        }else {
+            game_state.show_toast(format!("Level pack imported as \"{}\"", level_pack_id));
+        }
+
+        self.is_importing_level_pack_archive = false;
+        self.import_file_name = String::new();
+    }
+
+    /// Imports the level pack archive with the entered file name from the current directory as new editor level pack
+    /// with the provided ID
+    ///
+    /// An existing level pack with the same ID is only replaced if overwrite is true, it is moved to the trash as backup
+    fn import_level_pack_archive(&mut self, game_state: &mut GameState, level_pack_id: &str, overwrite: bool) -> Result<(), Box<dyn Error>> {
+        if self.import_file_name.len() < 3 {
+            return Err(Box::new(GameError::new("Level pack ID must have at least 3 characters!")));
+        }
+
+        let existing_index = game_state.editor_state.level_packs.iter().
+                position(|level_pack| level_pack.id() == level_pack_id);
+        if existing_index.is_some() && !overwrite {
+            return Err(Box::new(GameError::new(format!("The level pack with the ID \"{}\" already exists!", level_pack_id))));
+        }
+
+        if existing_index.is_none() && game_state.editor_state.get_level_pack_count() == LevelPack::MAX_LEVEL_PACK_COUNT {
+            return Err(Box::new(GameError::new(format!(
+                "Max level pack count ({}) reached",
+                LevelPack::MAX_LEVEL_PACK_COUNT,
+            ))));
+        }
+
+        let archive_data = std::fs::read(format!("{}{}", self.import_file_name, ARCHIVE_FILE_EXTENSION))?;
+        let archive = LevelPackArchive::decode(&archive_data)?;
+
+        let mut save_game_file = Game::get_or_create_save_game_folder()?;
+        save_game_file.push(level_pack_id);
+        save_game_file.push(".lvl.edit");
+        let Some(save_game_file) = save_game_file.to_str() else {
+            return Err(Box::new(GameError::new("Invalid save game path")));
+        };
+
+        let level_pack = LevelPack::read_from_save_game(
+            level_pack_id, save_game_file, archive.level_pack_data(), true,
+
+            #[cfg(feature = "steam")]
+            None,
+        )?;
+
+        if let Some(existing_index) = existing_index {
+            trash::move_level_pack_to_trash(&game_state.editor_state.level_packs[existing_index])?;
+            game_state.editor_state.level_packs.remove(existing_index);
+        }
+
+        level_pack.save_editor_level_pack()?;
+
+        //Only sound effect files are imported (Assets are written next to the level pack)
+        let sound_effects_folder = format!("{}{}", level_pack.path(), audio::PACK_SOUND_EFFECTS_FOLDER_POSTFIX);
+        for (name, data) in archive.assets().iter().
+                filter(|(name, _)| audio::is_pack_sound_effect_file_name(name)) {
+            std::fs::create_dir_all(&sound_effects_folder)?;
+            std::fs::write(std::path::Path::new(&sound_effects_folder).join(name), data)?;
+        }
+
+        let index = game_state.editor_state.level_packs.binary_search_by_key(
+            &level_pack.id().to_string(),
+            |level_pack| level_pack.id().to_string(),
+        ).err().unwrap();
+
+        game_state.editor_state.level_packs.insert(index, level_pack);
+
+        self.update_list_elements(game_state);
+        self.level_pack_editor_list.set_cursor_index(index + 1);
+
+        Ok(())
+    }
+
+    fn update_list_elements(&mut self, game_state: &GameState) {
+        let elements = self.level_pack_editor_list.elements_mut();
+
+        //Remove all level pack editor entries and create new level pack entry
+        elements.drain(1..);
+
+        for (i, level_pack) in game_state.editor_state.level_packs.iter().enumerate() {
+            elements.push(UIListElement::new(
+                utils::number_to_string_leading_ascii(2, i as u32 + 1, false),
+                Color::Black,
+                if level_pack.level_pack_best_moves_sum().is_some() {
+                    Color::Green
+                }else {
+                    Color::Yellow
+                },
+            ));
+        }
+
+        let has_max_level_pack_count = game_state.editor_state.get_level_pack_count() == LevelPack::MAX_LEVEL_PACK_COUNT;
+        elements.push(UIListElement::new(
+            " +",
+            Color::White,
+            if has_max_level_pack_count {
+                Color::LightRed
+            }else {
+                Color::LightBlue
+            },
+        ));
+    }
+}
+
+impl Screen for ScreenSelectLevelPackEditor {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_text("Edit a level pack:");
+        console.set_underline(false);
+
+        console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 20, 0);
+        console.draw_text("Recently deleted (");
+
+        console.draw_key_input_text("t");
+
+        console.reset_color();
+        console.draw_text(")");
+
+        self.level_pack_editor_list.draw(console);
+
+        let has_max_level_pack_count = game_state.editor_state.get_level_pack_count() == LevelPack::MAX_LEVEL_PACK_COUNT;
+
+        let entry_count = self.level_pack_editor_list.elements().len();
+
+        //Draw border for best time and best moves
+        let y = 4 + ((entry_count - 1)/24)*2;
+
+        console.set_cursor_pos(0, y);
+        console.set_color(Color::Cyan, Color::Default);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 1..4 {
+            console.set_cursor_pos(0, y + i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, y + 4);
+        console.draw_text("\'------------------------------------------------------------------------\'");
+        console.reset_color();
+
+        let cursor_index = self.level_pack_editor_list.cursor_index();
+        if self.is_creating_new_level_pack {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_text("Enter a new level pack ID:");
+
+            console.set_cursor_pos(1, y + 2);
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_fmt(format_args!("> {}", &self.new_level_pack_id));
+        }else if self.is_importing_level_pack_archive {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_fmt(format_args!("Enter the name of the archive in the current directory (\"<name>{}\"):", ARCHIVE_FILE_EXTENSION));
+
+            console.set_cursor_pos(1, y + 2);
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_fmt(format_args!("> {}", &self.import_file_name));
+        }else if cursor_index == 0 {
+            console.reset_color();
+            console.set_cursor_pos(35, y + 2);
+            console.draw_text("Back");
+        }else if cursor_index - 1 == game_state.editor_state.get_level_pack_count() {
+            //Level Pack Editor entry
+            if has_max_level_pack_count {
+                let error_msg = format!(
+                    "Max level pack count ({}) reached",
+                    LevelPack::MAX_LEVEL_PACK_COUNT,
+                );
+
+                let x_offset = ((Game::CONSOLE_MIN_WIDTH - error_msg.len()) as f64 * 0.5) as usize;
+                console.set_cursor_pos(x_offset, y + 2);
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text(error_msg);
+            }else {
+                console.set_cursor_pos(28, y + 2);
+                console.draw_text("Create a level pack");
+
+                console.set_cursor_pos(24, y + 3);
+                console.draw_key_input_text("i");
+
+                console.reset_color();
+                console.draw_text(": Import an archive");
+            }
+        }else {
+            let level_pack = game_state.editor_state.level_packs.get(cursor_index - 1).unwrap();
+
+            console.set_cursor_pos(1, y + 1);
+            console.draw_fmt(format_args!("Level Pack ID: {}", level_pack.id()));
+
+            console.set_cursor_pos(1, y + 2);
+            console.draw_fmt(format_args!("Levels: {}", level_pack.level_count()));
+
+            console.set_cursor_pos(1, y + 3);
+            console.draw_text("Background music: ");
+
+            match level_pack.background_music_id().
+                    map(|background_music_id| audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id)) {
+                Some(background_music) => {
+                    console.set_color(Color::LightCyan, Color::Default);
+                    console.draw_text(background_music.display_name());
+
+                    console.reset_color();
+                    console.draw_text(" [by ");
+
+                    console.set_color(Color::LightPink, Color::Default);
+                    console.draw_text(background_music.creator());
+
+                    console.reset_color();
+                    console.draw_text("]");
+                },
+
+                None => {
+                    console.draw_text("None");
+                },
+            }
+
+            console.set_cursor_pos(46, y + 1);
+            console.draw_key_input_text("m");
+
+            console.reset_color();
+            console.draw_text(":  Select background music");
+
+            #[cfg(feature = "steam")]
+            {
+                console.set_cursor_pos(46, y + 2);
+                console.draw_key_input_text("u");
+
+                console.reset_color();
+                console.draw_text(": Upload to Steam Workshop");
+            }
+
+            console.set_cursor_pos(46, y + 3);
+            console.draw_key_input_text("z");
+
+            console.reset_color();
+            console.draw_text(": Export as archive");
+        }
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if self.is_creating_new_level_pack {
+            match key {
+                key if key.is_ascii() && (key.is_alphanumeric() || key == Key::UNDERSCORE || key == Key::MINUS) => {
+                    if self.new_level_pack_id.len() >= LevelPack::MAX_LEVEL_PACK_NAME_LEN {
+                        return;
+                    }
+                    
+                    let _ = write!(self.new_level_pack_id, "{}", key.to_ascii().unwrap() as char);
+                },
+                Key::DELETE => {
+                    self.new_level_pack_id.pop();
+                },
+
+                Key::ENTER => {
+                    if self.new_level_pack_id.len() < 3 {
+                        game_state.open_dialog(Dialog::new_ok_error("Level pack ID must have at least 3 characters!"));
+
+                        return;
+                    }
+
+                    for id in game_state.editor_state.level_packs.iter().
+                            map(|level_pack| level_pack.id()) {
+                        if id == self.new_level_pack_id {
+                            game_state.open_dialog(Dialog::new_ok_error(format!("The level pack with the ID \"{}\" already exists!", id)));
+
+                            return;
+                        }
+                    }
+
+                    let Ok(mut save_game_file) = Game::get_or_create_save_game_folder() else {
+                        game_state.open_dialog(Dialog::new_ok_error("Cannot save!"));
+
+                        return;
+                    };
+                    save_game_file.push(&self.new_level_pack_id);
+                    save_game_file.push(".lvl.edit");
+
+                    let Some(save_game_file) = save_game_file.to_str() else {
+                        game_state.open_dialog(Dialog::new_ok_error("Cannot save!"));
+
+                        return;
+                    };
+
+                    let level_pack = LevelPack::new(&self.new_level_pack_id, &self.new_level_pack_id, save_game_file);
+                    if let Err(err) = level_pack.save_editor_level_pack() {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                    }
+
+                    game_state.play_sound_effect_ui_select();
+
+                    let index = game_state.editor_state.level_packs.binary_search_by_key(
+                        &level_pack.id().to_string(),
+                        |level_pack| level_pack.id().to_string(),
+                    ).err().unwrap();
+
+                    game_state.editor_state.level_packs.insert(index, level_pack);
+
+                    //self.is_creating_new_level_pack with be set to false in on_set_screen after background music selection
+                    self.new_level_pack_id = String::new();
+
+                    self.level_pack_editor_list.set_cursor_index(index + 1);
+                    game_state.editor_state.set_level_pack_index(index);
+                    game_state.editor_state.set_level_index(0);
+                    game_state.set_screen(ScreenId::SelectLevelPackBackgroundMusic);
+                },
+
+                Key::ESC => {
+                    game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                    self.is_creating_new_level_pack = false;
+                    self.new_level_pack_id = String::new();
+                },
+
+                _ => {},
+            }
+
+            return;
+        }
+
+        if self.is_importing_level_pack_archive {
+            match key {
+                key if key.is_ascii() && (key.is_alphanumeric() || key == Key::UNDERSCORE || key == Key::MINUS) => {
+                    if self.import_file_name.len() >= LevelPack::MAX_LEVEL_PACK_NAME_LEN {
+                        return;
+                    }
+
+                    let _ = write!(self.import_file_name, "{}", key.to_ascii().unwrap() as char);
+                },
+                Key::DELETE => {
+                    self.import_file_name.pop();
+                },
+
+                Key::ENTER => {
+                    self.start_import_level_pack_archive(game_state);
+                },
+
+                Key::ESC => {
+                    game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                    self.is_importing_level_pack_archive = false;
+                    self.import_file_name = String::new();
+                },
+
+                _ => {},
+            }
+
+            return;
+        }
+
+        if key == Key::ESC {
+            game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+            game_state.set_screen(ScreenId::SelectLevelPack);
+
+            return;
+        }
+
+        if key == Key::I {
+            game_state.play_sound_effect_ui_select();
+
+            self.is_importing_level_pack_archive = true;
+
+            return;
+        }
+
+        if key == Key::T {
+            game_state.play_sound_effect_ui_select();
+
+            game_state.set_screen(ScreenId::RecentlyDeleted);
+
+            return;
+        }
+
+        let cursor_index = self.level_pack_editor_list.cursor_index();
+        if cursor_index >= 1 && cursor_index - 1 != game_state.editor_state.get_level_pack_count() {
+            if key == Key::M {
+                game_state.play_sound_effect_ui_dialog_open();
+
+                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+
+                match game_state.editor_state.get_current_level_pack().unwrap().
+                        background_music_id().
+                        map(|background_music_id| audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id)) {
+                    Some(background_music) => game_state.set_background_music_loop(background_music),
+                    None => game_state.stop_background_music(),
+                }
+
+                game_state.set_screen(ScreenId::SelectLevelPackBackgroundMusic);
+            }
+
+            if key == Key::E {
+                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+
+                self.is_exporting_level_pack = true;
+
+                game_state.open_dialog(Dialog::new_yes_no("Do you want to export the level pack to the current directory?"));
+            }
+
+            if key == Key::Z {
+                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+
+                self.is_exporting_level_pack_archive = true;
+
+                game_state.open_dialog(Dialog::new_yes_no("Do you want to export the level pack as compressed archive to the current directory?"));
+            }
+
+            #[cfg(feature = "steam")]
+            if key == Key::U {
+                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+
+                let level_stats = &game_state.editor_state.get_current_level_pack().unwrap();
+                if level_stats.level_pack_best_moves_sum().is_none() {
+                    let unvalidated_level_count = level_stats.levels().iter().
+                            filter(|level| level.best_moves().is_none()).
+                            count();
+
+                    if unvalidated_level_count == 0 {
+                        game_state.open_dialog(Dialog::new_ok_error(
+                            "Level pack was not validated yet! All levels must be validated.",
+                        ));
+                    }else {
+                        //Large level packs can be uploaded without a validation run of each level
+                        self.is_confirming_solver_validation = true;
+
+                        game_state.open_dialog(Dialog::new_yes_no(format!(
+                            "Level pack was not validated yet! {} of {} levels are not validated.\n\n\
+                            Do you want to validate these levels with the solver?\n\
+                            Solver validated levels are marked in the level editor and in the upload.",
+                            unvalidated_level_count, level_stats.level_count(),
+                        )));
+                    }
+
+                    return;
+                }
+
+                Self::open_workshop_upload_popup(game_state);
+            }
+
+            if key == Key::DELETE {
+                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+
+                self.is_deleting_level_pack = true;
+
+                game_state.open_dialog(Dialog::new_yes_no(format!(
+                    "Do you really want to delete level pack \"{}\"?\n\nDeleted level packs can be restored for {} days.",
+                    game_state.editor_state.get_current_level_pack().unwrap().id(),
+                    trash::RETENTION_DAYS,
+                )));
+            }
+        }
+
+        self.level_pack_editor_list.on_key_press(&mut self.is_creating_new_level_pack, game_state, key);
+    }
+
+    fn on_text_pasted(&mut self, _: &mut GameState, text: &str) {
+        if self.is_creating_new_level_pack {
+            utils::append_pasted_text(
+                &mut self.new_level_pack_id, text, LevelPack::MAX_LEVEL_PACK_NAME_LEN,
+                |c| c.is_ascii_alphanumeric() || c == '_' || c == '-',
+            );
+        }else if self.is_importing_level_pack_archive {
+            //The file extension can be included in pasted file names
+            let text = text.trim().strip_suffix(ARCHIVE_FILE_EXTENSION).unwrap_or(text);
+
+            utils::append_pasted_text(
+                &mut self.import_file_name, text, LevelPack::MAX_LEVEL_PACK_NAME_LEN,
+                |c| c.is_ascii_alphanumeric() || c == '_' || c == '-',
+            );
+        }
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if self.is_importing_level_pack_archive {
+            return;
+        }
+
+        if row == 0 {
+            if column >= Game::CONSOLE_MIN_WIDTH - 20 && !self.is_creating_new_level_pack {
+                self.on_key_pressed(game_state, Key::T);
+            }
+
+            return;
+        }
+
+        let element_count = self.level_pack_editor_list.elements().len();
+        let y = 4 + ((element_count - 1)/24)*2;
+        if row == y + 1 && (46..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
+            self.on_key_pressed(game_state, Key::M);
+        }
+
+        #[cfg(feature = "steam")]
+        {
+            if row == y + 2 && (46..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
+                self.on_key_pressed(game_state, Key::U);
+            }
+        }
+
+        if row == y + 3 && (46..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
+            self.on_key_pressed(game_state, Key::Z);
+        }
+
+        self.level_pack_editor_list.on_mouse_pressed(&mut self.is_creating_new_level_pack, game_state, column, row);
+    }
+
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        if self.is_exporting_level_pack {
+            self.is_exporting_level_pack = false;
+
+            if selection == DialogSelection::Yes {
+                let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+                let path = level_pack.id().to_string() + ".lvl";
+
+                if std::fs::exists(&path).ok().is_none_or(|exists| exists) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!(
+                        "File \"{}\" already exists!",
+                        path,
+                    )));
+
+                    return;
+                }
+
+                if let Err(err) = level_pack.export_editor_level_pack_to_path(path) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot export: {}", err)));
+                }else {
+                    game_state.show_toast("Level pack exported");
+                }
+            }
+        }else if self.is_exporting_level_pack_archive {
+            self.is_exporting_level_pack_archive = false;
+
+            if selection == DialogSelection::Yes {
+                let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+                let path = level_pack.id().to_string() + ARCHIVE_FILE_EXTENSION;
+
+                if std::fs::exists(&path).ok().is_none_or(|exists| exists) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!(
+                        "File \"{}\" already exists!",
+                        path,
+                    )));
+
+                    return;
+                }
+
+                if let Err(err) = level_pack.export_editor_level_pack_to_archive(path) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot export: {}", err)));
+                }else {
+                    game_state.show_toast("Level pack archive exported");
+                }
+            }
+        }else if self.is_resolving_import_conflict {
+            self.is_resolving_import_conflict = false;
+
+            match selection {
+                DialogSelection::Yes => {
+                    let level_pack_id = LevelPack::unused_level_pack_id(
+                        &self.import_file_name,
+                        |id| game_state.editor_state.level_packs.iter().any(|level_pack| level_pack.id() == id),
+                    );
+
+                    if let Some(level_pack_id) = level_pack_id {
+                        self.finish_import_level_pack_archive(game_state, &level_pack_id, false);
+                    }else {
+                        game_state.open_dialog(Dialog::new_ok_error("Cannot import: No unused level pack ID was found!"));
+                    }
+                },
+
+                DialogSelection::No => {
+                    let level_pack_id = self.import_file_name.clone();
+                    self.finish_import_level_pack_archive(game_state, &level_pack_id, true);
+                },
+
+                _ => {},
+            }
+        }else if self.is_deleting_level_pack {
+            self.is_deleting_level_pack = false;
+
+            if selection == DialogSelection::Yes {
+                if let Err(err) = trash::move_level_pack_to_trash(game_state.editor_state.get_current_level_pack().unwrap()) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot delete: {}", err)));
+                }else {
+                    game_state.editor_state.level_packs.remove(self.level_pack_editor_list.cursor_index() - 1);
+                }
+            }
+
+            self.update_list_elements(game_state);
+            //Cursor index will always be inbound after level pack deletion because of the Create Level Pack Entry
+        }
+
+        #[cfg(feature = "steam")]
+        if self.is_confirming_solver_validation {
+            self.is_confirming_solver_validation = false;
+
+            if selection == DialogSelection::Yes {
+                self.validate_with_solver_and_open_upload_popup(game_state);
+            }
+        }
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        if self.is_creating_new_level_pack {
+            //Background music was selected for newly created level pack -> Do not change music and enter level pack editor
+
+            self.is_creating_new_level_pack = false;
+            game_state.set_screen(ScreenId::LevelPackEditor);
+        }else {
+            self.update_list_elements(game_state);
+
+            if self.level_pack_editor_list.cursor_index() == 0 {
+                //Skip "back" entry and set to first level pack
+                self.level_pack_editor_list.set_cursor_index(1);
+            }
+
+            game_state.set_menu_background_music_loop();
+        }
+    }
+}
+
+pub struct ScreenSelectLevelPackBackgroundMusic {}
+
+impl ScreenSelectLevelPackBackgroundMusic {
+    const ROWS_PER_PAGE: usize = 5;
+    const LIST_Y_OFFSET: usize = 4;
+    //The last row is kept free for toasts
+    const VISIBLE_ROW_COUNT: usize = Game::CONSOLE_MIN_HEIGHT - Self::LIST_Y_OFFSET - 1;
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Returns the index of the selected entry (The first entry is "None", followed by all tracks)
+    fn selection_index(game_state: &GameState) -> usize {
+        game_state.current_background_music_id().
+                and_then(|id| audio::BACKGROUND_MUSIC_TRACKS.track_index(id)).
+                map_or(0, |track_index| track_index + 1)
+    }
+
+    fn first_visible_index(selection_index: usize) -> usize {
+        selection_index.saturating_sub(Self::VISIBLE_ROW_COUNT - 1)
+    }
+
+    fn select(game_state: &mut GameState, selection_index: usize) {
+        if selection_index == 0 {
+            game_state.stop_background_music();
+        }else {
+            game_state.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.tracks()[selection_index - 1]);
+        }
+    }
+}
+
+impl Screen for ScreenSelectLevelPackBackgroundMusic {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_text("Select the background music for the level pack:");
+        console.set_underline(false);
+
+        console.set_cursor_pos(0, 1);
+        console.draw_key_input_text("ENTER");
+
+        console.reset_color();
+        console.draw_text(": Save selection");
+
+        console.set_cursor_pos(0, 2);
+        console.draw_key_input_text("ESC");
+
+        console.reset_color();
+        console.draw_text(": Cancel");
+
+        let selection_index = Self::selection_index(game_state);
+        let first_visible_index = Self::first_visible_index(selection_index);
+
+        if first_visible_index == 0 {
+            console.reset_color();
+            console.set_cursor_pos(0, Self::LIST_Y_OFFSET);
+            console.draw_text("( ) None");
+        }
+
+        for (i, track) in audio::BACKGROUND_MUSIC_TRACKS.tracks().iter().
+                enumerate().
+                map(|(i, track)| (i + 1, track)).
+                filter(|(i, _)| (first_visible_index..first_visible_index + Self::VISIBLE_ROW_COUNT).contains(i)) {
+            console.reset_color();
+            console.set_cursor_pos(0, Self::LIST_Y_OFFSET + i - first_visible_index);
+            console.reset_color();
+            console.draw_text("( ) ");
+
+            console.set_color(Color::LightCyan, Color::Default);
+            console.draw_fmt(format_args!("{:35}", track.display_name()));
+
+            console.reset_color();
+            console.draw_text(" [by ");
+
+            console.set_color(Color::LightPink, Color::Default);
+            console.draw_text(track.creator());
+
+            console.reset_color();
+            console.draw_text("]");
+        }
+
+        console.set_color(Color::Yellow, Color::Default);
+        console.set_cursor_pos(1, Self::LIST_Y_OFFSET + selection_index - first_visible_index);
+        console.draw_text("X");
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        let current_background_music_id = game_state.current_background_music_id();
+
+        //The first entry is "None"
+        let music_count = audio::BACKGROUND_MUSIC_TRACKS.tracks().len() + 1;
+        let navigator = ListNavigator::new(Self::ROWS_PER_PAGE);
+        if let Some(cursor_index) = navigator.navigate(Self::selection_index(game_state), music_count, key) {
+            game_state.play_sound_effect_ui_select();
+
+            Self::select(game_state, cursor_index);
+        }
+
+        if key == Key::ENTER || key == Key::SPACE {
+            let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+            level_pack.set_background_music_id(current_background_music_id);
+            level_pack.add_changelog_entry("Background music changed");
+
+            if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+            }
+        }
+
+        if key == Key::ENTER || key == Key::SPACE || key == Key::ESC {
+            game_state.play_sound_effect_ui_select();
+
+            game_state.set_screen(ScreenId::SelectLevelPackEditor);
+        }
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if row == 1 && column < 5 {
+            self.on_key_pressed(game_state, Key::ENTER);
+        }else if row == 2 && column < 3 {
+            self.on_key_pressed(game_state, Key::ESC);
+        }
+
+        if row < Self::LIST_Y_OFFSET || row - Self::LIST_Y_OFFSET >= Self::VISIBLE_ROW_COUNT {
+            return;
+        }
+
+        let background_music_selection_index = Self::first_visible_index(Self::selection_index(game_state)) + row - Self::LIST_Y_OFFSET;
+        if background_music_selection_index > audio::BACKGROUND_MUSIC_TRACKS.tracks().len() {
+            return;
+        }
+
+        game_state.play_sound_effect_ui_select();
+
+        Self::select(game_state, background_music_selection_index);
+    }
+
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
+        }
+    }
+}
+
+pub struct ScreenRecentlyDeleted {
+    entries: Vec<TrashEntry>,
+    cursor_index: usize,
+}
+
+impl ScreenRecentlyDeleted {
+    const LIST_Y_OFFSET: usize = 3;
+    //The last row is kept free for toasts
+    const VISIBLE_ROW_COUNT: usize = Game::CONSOLE_MIN_HEIGHT - Self::LIST_Y_OFFSET - 1;
+
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            cursor_index: 0,
+        }
+    }
+
+    fn first_visible_index(&self) -> usize {
+        self.cursor_index.saturating_sub(Self::VISIBLE_ROW_COUNT - 1)
+    }
+
+    fn restore_selected_entry(&mut self, game_state: &mut GameState) -> Result<(), Box<dyn Error>> {
+        let Some(entry) = self.entries.get(self.cursor_index) else {
+            return Ok(());
+        };
+
+        match entry.kind() {
+            TrashEntryKind::LevelPack => {
+                if game_state.editor_state.get_level_pack_count() == LevelPack::MAX_LEVEL_PACK_COUNT {
+                    return Err(Box::new(GameError::new(format!(
+                        "Max level pack count ({}) reached",
+                        LevelPack::MAX_LEVEL_PACK_COUNT,
+                    ))));
+                }
+
+                if game_state.editor_state.level_packs.iter().any(|level_pack| level_pack.id() == entry.level_pack_id()) {
+                    return Err(Box::new(GameError::new(format!("The level pack with the ID \"{}\" already exists!", entry.level_pack_id()))));
+                }
+
+                let level_pack = entry.restore_level_pack()?;
+
+                let index = game_state.editor_state.level_packs.binary_search_by_key(
+                    &level_pack.id().to_string(),
+                    |level_pack| level_pack.id().to_string(),
+                ).err().unwrap();
+
+                game_state.editor_state.level_packs.insert(index, level_pack);
+            },
+
+            TrashEntryKind::Level { level_index, .. } => {
+                let Some(level_pack) = game_state.editor_state.level_packs.iter_mut().
+                        find(|level_pack| level_pack.id() == entry.level_pack_id()) else {
+                    return Err(Box::new(GameError::new(format!("The level pack \"{}\" does not exist anymore", entry.level_pack_id()))));
+                };
+
+                if level_pack.level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK {
+                    return Err(Box::new(GameError::new(format!(
+                        "Max level count ({}) reached",
+                        LevelPack::MAX_LEVEL_COUNT_PER_PACK,
+                    ))));
+                }
+
+                let level = entry.restore_level()?;
+
+                //The level is restored at its previous position if possible
+                let level_index = level_index.min(level_pack.level_count());
+                level_pack.insert_level(level_index, level);
+                level_pack.add_changelog_entry(format!("Level {} restored", level_index + 1));
+
+                level_pack.save_editor_level_pack()?;
+            },
+        }
+
+        entry.delete()?;
+
+        self.entries.remove(self.cursor_index);
+        self.cursor_index = self.cursor_index.min(self.entries.len().saturating_sub(1));
+
+        Ok(())
+    }
+}
+
+impl Screen for ScreenRecentlyDeleted {
+    fn draw(&self, _game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_fmt(format_args!("Recently deleted (Level packs and levels are kept for {} days):", trash::RETENTION_DAYS));
+        console.set_underline(false);
+
+        console.set_cursor_pos(0, 1);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(": Restore, ");
+        console.draw_key_input_text("DELETE");
+        console.reset_color();
+        console.draw_text(": Delete permanently, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back");
+
+        if self.entries.is_empty() {
+            console.set_cursor_pos(0, Self::LIST_Y_OFFSET);
+            console.set_color(Color::LightBlack, Color::Default);
+            console.draw_text("No level packs or levels were deleted recently.");
+
+            return;
+        }
+
+        let now = trash::current_time();
+        let first_visible_index = self.first_visible_index();
+        for (i, entry) in self.entries.iter().
+                enumerate().
+                skip(first_visible_index).
+                take(Self::VISIBLE_ROW_COUNT) {
+            console.set_cursor_pos(0, Self::LIST_Y_OFFSET + i - first_visible_index);
+
+            if i == self.cursor_index {
+                console.set_color(Color::Yellow, Color::Default);
+                console.draw_text(">");
+            }else {
+                console.draw_text(" ");
+            }
+
+            let name = match entry.kind() {
+                TrashEntryKind::LevelPack => format!("Level pack \"{}\"", entry.level_pack_id()),
+                TrashEntryKind::Level { level_index, .. } => format!("Level {:03} of \"{}\"", level_index + 1, entry.level_pack_id()),
+            };
+
+            console.set_color(Color::LightCyan, Color::Default);
+            console.draw_fmt(format_args!(" {:42}", name));
+
+            let remaining_days = entry.remaining_days(now);
+            console.set_color(if remaining_days <= 3 { Color::LightRed } else { Color::LightBlack }, Color::Default);
+            console.draw_fmt(format_args!(" {:2} day{} left", remaining_days, if remaining_days == 1 { "" } else { "s" }));
+        }
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        let navigator = ListNavigator::new(Self::VISIBLE_ROW_COUNT);
+        if let Some(cursor_index) = navigator.navigate(self.cursor_index, self.entries.len(), key) {
+            game_state.play_sound_effect_ui_select();
+
+            self.cursor_index = cursor_index;
+
+            return;
+        }
+
+        match key {
+            Key::ESC => {
+                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                game_state.set_screen(ScreenId::SelectLevelPackEditor);
+            },
+
+            Key::ENTER if !self.entries.is_empty() => {
+                let toast = match self.entries[self.cursor_index].kind() {
+                    TrashEntryKind::LevelPack => "Level pack restored",
+                    TrashEntryKind::Level { .. } => "Level restored",
+                };
+
+                if let Err(err) = self.restore_selected_entry(game_state) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot restore: {}", err)));
+
+                    return;
+                }
+
+                game_state.play_sound_effect_ui_select();
+                game_state.show_toast(toast);
+            },
+
+            Key::DELETE if !self.entries.is_empty() => {
+                game_state.open_dialog(Dialog::new_yes_no("Do you really want to delete this entry permanently?\n\nThis action can not be undone!"));
+            },
+
+            _ => {},
+        }
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, _column: usize, row: usize) {
+        if row < Self::LIST_Y_OFFSET {
+            return;
+        }
+
+        //The first click selects the entry, a click on the selected entry restores it
+        let index = self.first_visible_index() + row - Self::LIST_Y_OFFSET;
+        if index == self.cursor_index {
+            self.on_key_pressed(game_state, Key::ENTER);
+        }else if index < self.entries.len() {
+            game_state.play_sound_effect_ui_select();
+
+            self.cursor_index = index;
+        }
+    }
+
+    fn on_mouse_scrolled(&mut self, game_state: &mut GameState, _column: usize, _row: usize, delta: i32) {
+        for key in utils::scroll_keys(delta) {
+            self.on_key_pressed(game_state, key);
+        }
+    }
+
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        if selection != DialogSelection::Yes {
+            return;
+        }
+
+        let Some(entry) = self.entries.get(self.cursor_index) else {
+            return;
+        };
+
+        if let Err(err) = entry.delete() {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot delete: {}", err)));
+
+            return;
+        }
+
+        self.entries.remove(self.cursor_index);
+        self.cursor_index = self.cursor_index.min(self.entries.len().saturating_sub(1));
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.cursor_index = 0;
+
+        self.entries = match trash::remove_expired_entries().and_then(|_| trash::read_entries()) {
+            Ok(entries) => entries,
+
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot read the trash: {}", err)));
+
+                Vec::new()
+            },
+        };
+    }
+}
+
+pub struct ScreenLevelPackEditor {
+    level_editor_list: UIList<bool>,
+
+    level_preview: bool,
+    is_creating_new_level: bool,
+    is_editing_height: bool,
+    is_deleting_level: bool,
+    new_level_width_str: String,
+    new_level_height_str: String,
+
+    is_editing_par: bool,
+    is_editing_par_time: bool,
+    par_moves_str: String,
+    par_time_str: String,
+
+    is_editing_localized_name: bool,
+    localized_name_language: Language,
+    localized_name_str: String,
+
+    is_editing_change_summary: bool,
+    change_summary_str: String,
+
+    is_editing_required_level_pack: bool,
+    required_workshop_id_str: String,
+
+    //Intro or outro which is edited and its edited lines
+    editing_story: Option<PackStory>,
+    story_lines: Vec<String>,
+
+    is_multi_select_mode: bool,
+    is_batch_deleting_levels: bool,
+    is_batch_moving_levels: bool,
+    marked_level_indices: BTreeSet<usize>,
+    batch_operation_undo_data: Option<(Vec<LevelWithStats>, Option<usize>)>,
+
+    level_clipboard: Option<LevelWithStats>,
+
+    //Level index and problem
+    problem_report: Option<Vec<(usize, LevelProblem)>>,
+    problem_report_cursor_index: usize,
+    problem_report_scroll_offset: usize,
+
+    is_resolving_external_change: bool,
+    external_change_check_ticks: usize,
+}
+
+impl ScreenLevelPackEditor {
+    const PROBLEM_REPORT_ROW_COUNT: usize = 16;
+
+    //The level pack file is checked for changes of other programs every 2 seconds
+    const EXTERNAL_CHANGE_CHECK_INTERVAL: usize = 50;
+
+    const RELOAD_OPTION: &'static DialogOption = &DialogOption::new("[R]eload", DialogSelection::Yes, &[Key::R]);
+    const KEEP_EDITOR_CHANGES_OPTION: &'static DialogOption = &DialogOption::new("[K]eep editor changes", DialogSelection::No, &[Key::K]);
+
+    pub fn new() -> Self {
+        Self {
+            level_editor_list: UIList::new(
+                Rect::new(0, 1, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT - 1),
+                vec![
+                    UIListElement::new("<<", Color::White, Color::LightBlue),
+                    //[Level Editor Entries]
+                ],
+                Box::new(|is_creating_new_level: &mut bool, game_state: &mut GameState, cursor_index: usize| {
+                    if cursor_index == 0 {
+                        game_state.play_sound_effect_ui_select();
+                        game_state.set_screen(ScreenId::SelectLevelPackEditor);
+
+                        return;
+                    }
+
+                    let level_index = cursor_index - 1;
+
+                    if level_index == game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+                        //Level Editor entry
+                        if game_state.editor_state.get_current_level_pack().unwrap().level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK {
+                            game_state.open_dialog(Dialog::new_ok_error(format!(
+                                "Cannot create level (Max level count ({}) reached)",
+                                LevelPack::MAX_LEVEL_COUNT_PER_PACK,
+                            )));
+                        }else {
+                            game_state.play_sound_effect_ui_select();
+
+                            *is_creating_new_level = true;
+                        }
+                    }else {
+                        game_state.play_sound_effect_ui_select();
+
+                        game_state.editor_state.set_level_index(level_index);
+
+                        //Set selected level
+                        game_state.set_screen(ScreenId::LevelEditor);
+                    }
+                }),
+            ),
+
+            level_preview: false,
+            is_creating_new_level: Default::default(),
+            is_editing_height: Default::default(),
+            is_deleting_level: Default::default(),
+            new_level_width_str: String::new(),
+            new_level_height_str: String::new(),
+
+            is_editing_par: Default::default(),
+            is_editing_par_time: Default::default(),
+            par_moves_str: String::new(),
+            par_time_str: String::new(),
+
+            is_editing_localized_name: Default::default(),
+            localized_name_language: Default::default(),
+            localized_name_str: String::new(),
+
+            is_editing_change_summary: Default::default(),
+            change_summary_str: String::new(),
+
+            is_editing_required_level_pack: Default::default(),
+            required_workshop_id_str: String::new(),
+
+            editing_story: None,
+            story_lines: Vec::new(),
+
+            is_multi_select_mode: Default::default(),
+            is_batch_deleting_levels: Default::default(),
+            is_batch_moving_levels: Default::default(),
+            marked_level_indices: BTreeSet::new(),
+            batch_operation_undo_data: None,
+
+            level_clipboard: None,
+
+            problem_report: None,
+            problem_report_cursor_index: 0,
+            problem_report_scroll_offset: 0,
+
+            is_resolving_external_change: Default::default(),
+            external_change_check_ticks: 0,
+        }
+    }
+
+    /// Asks if the level pack should be reloaded if its file was changed by another program (e.g. a text editor)
+    fn check_external_changes(&mut self, game_state: &mut GameState) {
+        if self.is_resolving_external_change || game_state.is_dialog_opened() {
+            return;
+        }
+
+        let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+        if !level_pack.was_editor_level_pack_changed_on_disk() {
+            return;
+        }
+
+        self.is_resolving_external_change = true;
+
+        game_state.open_dialog(Dialog::new(
+            DialogType::Information,
+            format!(
+                "The level pack file \"{}.lvl.edit\" was changed by another program.\n\n\
+                Reload: Changes of the editor which are not in the file are discarded\n\
+                Keep editor changes: The file is overwritten with the level pack of the editor",
+                level_pack.id(),
+            ),
+            Box::from([Self::RELOAD_OPTION, Self::KEEP_EDITOR_CHANGES_OPTION]),
+        ));
+    }
+
+    /// Replaces the level pack of the editor with the level pack which is stored in its file
+    fn reload_level_pack(&mut self, game_state: &mut GameState) {
+        match game_state.editor_state.get_current_level_pack().unwrap().reload_editor_level_pack() {
+            Ok(level_pack) => {
+                *game_state.editor_state.get_current_level_pack_mut().unwrap() = level_pack;
+
+                game_state.show_toast("Level pack reloaded from disk");
+            },
+
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot reload: {}", err)));
+
+                return;
+            },
+        }
+
+        self.stop_editing_par();
+        self.stop_editing_localized_name();
+        self.stop_editing_change_summary();
+        self.stop_editing_required_level_pack();
+
+        self.level_preview = false;
+        self.marked_level_indices.clear();
+        self.batch_operation_undo_data = None;
+        self.problem_report = None;
+
+        self.update_list_elements(game_state);
+
+        //The level count might have changed
+        let level_count = game_state.editor_state.get_current_level_pack().unwrap().level_count();
+        self.level_editor_list.set_cursor_index(self.level_editor_list.cursor_index().min(level_count + 1));
+    }
+
+    /// Moves all marked levels in front of the level at target_index or deletes them if target_index is None
+    fn apply_batch_operation(&mut self, game_state: &mut GameState, target_index: Option<usize>) {
+        let marked_level_indices = mem::take(&mut self.marked_level_indices);
+
+        let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+
+        self.batch_operation_undo_data = Some((level_pack.levels().to_vec(), level_pack.thumbnail_level_index()));
+
+        let (marked_level_indices, unmarked_level_indices): (Vec<_>, Vec<_>) = (0..level_pack.level_count()).
+                partition(|index| marked_level_indices.contains(index));
+
+        let new_level_order = match target_index {
+            Some(target_index) => {
+                let insert_index = unmarked_level_indices.iter().
+                        filter(|index| **index < target_index).
+                        count();
+
+                let mut new_level_order = unmarked_level_indices;
+                new_level_order.splice(insert_index..insert_index, marked_level_indices);
+
+                new_level_order
+            },
+
+            None => unmarked_level_indices,
+        };
+
+        let thumbnail_level_index = level_pack.thumbnail_level_index().
+                and_then(|thumbnail_level_index| new_level_order.iter().position(|index| *index == thumbnail_level_index));
+
+        let mut levels = mem::take(level_pack.levels_mut()).into_iter().
+                map(Some).
+                collect::<Vec<_>>();
+        *level_pack.levels_mut() = new_level_order.into_iter().
+                map(|index| levels[index].take().unwrap()).
+                collect();
+
+        level_pack.set_thumbnail_level_index(thumbnail_level_index);
+        level_pack.calculate_stats_sum();
+        level_pack.add_changelog_entry(if target_index.is_some() { "Levels moved" } else { "Levels removed" });
+
+        if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+        }
+
+        self.update_list_elements(game_state);
+
+        let element_count = self.level_editor_list.elements().len();
+        if self.level_editor_list.cursor_index() >= element_count {
+            self.level_editor_list.set_cursor_index(element_count - 1);
+        }
+    }
+
+    fn undo_batch_operation(&mut self, game_state: &mut GameState) {
+        let Some((levels, thumbnail_level_index)) = self.batch_operation_undo_data.take() else {
+            game_state.play_sound_effect_ui_error();
+
+            return;
+        };
+
+        game_state.play_sound_effect(audio::UNDO_REDO_EFFECT);
+
+        let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+        *level_pack.levels_mut() = levels;
+        level_pack.set_thumbnail_level_index(thumbnail_level_index);
+        level_pack.calculate_stats_sum();
+        level_pack.add_changelog_entry("Levels restored");
+
+        if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+        }
+
+        self.marked_level_indices.clear();
+        self.update_list_elements(game_state);
+    }
+
+    fn on_key_pressed_multi_select_mode(&mut self, game_state: &mut GameState, key: Key) {
+        let level_count = game_state.editor_state.get_current_level_pack().unwrap().level_count();
+        let cursor_index = self.level_editor_list.cursor_index();
+
+        match key {
+            Key::ESC | Key::M => {
+                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                self.is_multi_select_mode = false;
+                self.marked_level_indices.clear();
+                self.update_list_elements(game_state);
+            },
+
+            Key::SPACE => {
+                if cursor_index == 0 || cursor_index - 1 == level_count {
+                    game_state.play_sound_effect_ui_error();
+
+                    return;
+                }
+
+                game_state.play_sound_effect_ui_select();
+
+                let selected_level_index = cursor_index - 1;
+                if !self.marked_level_indices.remove(&selected_level_index) {
+                    self.marked_level_indices.insert(selected_level_index);
+                }
+
+                self.update_list_elements(game_state);
+            },
+
+            Key::ENTER => {
+                if cursor_index == 0 || self.marked_level_indices.is_empty() {
+                    game_state.play_sound_effect_ui_error();
+
+                    return;
+                }
+
+                self.is_batch_moving_levels = true;
+
+                game_state.open_dialog(Dialog::new_yes_no(if cursor_index - 1 == level_count {
+                    format!("Do you really want to move {} levels to the end?", self.marked_level_indices.len())
+                }else {
+                    format!("Do you really want to move {} levels in front of level {}?", self.marked_level_indices.len(), cursor_index)
+                }));
+            },
+
+            Key::DELETE => {
+                if self.marked_level_indices.is_empty() {
+                    game_state.play_sound_effect_ui_error();
+
+                    return;
+                }
+
+                self.is_batch_deleting_levels = true;
+
+                game_state.open_dialog(Dialog::new_yes_no(format!("Do you really want to delete {} levels?", self.marked_level_indices.len())));
+            },
+
+            Key::U | Key::Z => {
+                self.undo_batch_operation(game_state);
+            },
+
+            _ => {
+                self.level_editor_list.on_key_press(&mut self.is_creating_new_level, game_state, key);
+            },
+        }
+    }
+
+    fn start_editing_par(&mut self, level: &LevelWithStats) {
+        self.is_editing_par = true;
+        self.is_editing_par_time = false;
+
+        //Default to validation results
+        self.par_moves_str = level.par_moves().or(level.best_moves()).
+                map(|par_moves| par_moves.to_string()).
+                unwrap_or_default();
+        self.par_time_str = level.par_time().or(level.best_time()).
+                map(|par_time| format!("{}.{:03}", par_time / 1000, par_time % 1000)).
+                unwrap_or_default();
+    }
+
+    fn stop_editing_par(&mut self) {
+        self.is_editing_par = false;
+        self.is_editing_par_time = false;
+        self.par_moves_str = String::new();
+        self.par_time_str = String::new();
+    }
+
+    fn parse_par_time(par_time_str: &str) -> Option<u64> {
+        let (sec, millis) = par_time_str.split_once(".").unwrap_or((par_time_str, ""));
+        if sec.is_empty() || millis.len() > 3 {
+            return None;
+        }
+
+        let sec = u64::from_str(sec).ok()?;
+        let millis = if millis.is_empty() {
+            0
+        }else {
+            u64::from_str(&format!("{:0<3}", millis)).ok()?
+        };
+
+        Some(sec * 1000 + millis)
+    }
+
+    fn on_key_pressed_editing_par(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.is_ascii() && (key.is_numeric() || (self.is_editing_par_time && key == Key::DOT)) => {
+                let input_str = if self.is_editing_par_time {
+                    &mut self.par_time_str
+                }else {
+                    &mut self.par_moves_str
+                };
+
+                if input_str.len() >= if self.is_editing_par_time { 8 } else { 4 } {
+                    return;
+                }
+
+                if key == Key::DOT && input_str.contains(".") {
+                    return;
+                }
+
+                let _ = write!(input_str, "{}", key.to_ascii().unwrap() as char);
+            },
+            Key::DELETE => {
+                if self.is_editing_par_time {
+                    self.par_time_str.pop();
+                }else {
+                    self.par_moves_str.pop();
+                }
+            },
+
+            Key::TAB => {
+                self.is_editing_par_time = !self.is_editing_par_time;
+            },
+
+            Key::ENTER => {
+                let par_moves = if self.par_moves_str.is_empty() {
+                    None
+                }else {
+                    let Ok(par_moves) = u32::from_str(&self.par_moves_str) else {
+                        game_state.open_dialog(Dialog::new_ok_error("Par moves must be a number"));
+
+                        return;
+                    };
+
+                    Some(par_moves)
+                };
+
+                if !self.is_editing_par_time && self.par_time_str.is_empty() {
+                    self.is_editing_par_time = true;
+
+                    return;
+                }
+
+                let par_time = if self.par_time_str.is_empty() {
+                    None
+                }else {
+                    let Some(par_time) = Self::parse_par_time(&self.par_time_str) else {
+                        game_state.open_dialog(Dialog::new_ok_error("Par time must be a number of seconds (e.g. 12.5)"));
+
+                        return;
+                    };
+
+                    Some(par_time)
+                };
+
+                game_state.play_sound_effect_ui_select();
+
+                let index = self.level_editor_list.cursor_index() - 1;
+                let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+                let level = &mut level_pack.levels_mut()[index];
+                level.set_par_moves(par_moves);
+                level.set_par_time(par_time);
+                level_pack.add_changelog_entry(format!("Level {} par values changed", index + 1));
+
+                if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                }
+
+                self.stop_editing_par();
+            },
+
+            Key::ESC => {
+                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                self.stop_editing_par();
+            },
+
+            _ => {},
+        }
+    }
+
+    fn start_editing_localized_name(&mut self, level_pack: &LevelPack, language: Language) {
+        self.is_editing_localized_name = true;
+        self.localized_name_language = language;
+
+        //Default to the untranslated name
+        self.localized_name_str = level_pack.localized_name(language).to_string();
+    }
+
+    fn stop_editing_localized_name(&mut self) {
+        self.is_editing_localized_name = false;
+        self.localized_name_str = String::new();
+    }
+
+    /// Stores the edited name for the current language (An empty name removes the translation) and saves the level pack if it was changed
+    fn save_localized_name(&self, game_state: &mut GameState) {
+        let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+        if !level_pack.update_localized_name(self.localized_name_language, &self.localized_name_str) {
+            return;
+        }
+        level_pack.add_changelog_entry("Translated level pack names changed");
+
+        if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+        }
+    }
+
+    fn on_key_pressed_editing_localized_name(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.is_ascii() && key.to_ascii().is_some_and(|c| c.is_ascii_graphic() || c == b' ') => {
+                if self.localized_name_str.len() >= LevelPack::MAX_LEVEL_PACK_NAME_LEN {
+                    return;
+                }
+
+                let _ = write!(self.localized_name_str, "{}", key.to_ascii().unwrap() as char);
+            },
+            Key::DELETE => {
+                self.localized_name_str.pop();
+            },
+
+            Key::TAB => {
+                //The pending name of the current language is saved before switching to the next language
+                self.save_localized_name(game_state);
+
+                self.start_editing_localized_name(
+                    game_state.editor_state.get_current_level_pack().unwrap(),
+                    self.localized_name_language.next_setting(),
+                );
+            },
+
+            Key::ENTER => {
+                game_state.play_sound_effect_ui_select();
+
+                self.save_localized_name(game_state);
+                self.stop_editing_localized_name();
+            },
+
+            Key::ESC => {
+                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                self.stop_editing_localized_name();
+            },
+
+            _ => {},
+        }
+    }
+
+    fn start_editing_change_summary(&mut self, level_pack: &LevelPack) {
+        self.is_editing_change_summary = true;
+        self.change_summary_str = level_pack.change_summary().to_string();
+    }
+
+    fn stop_editing_change_summary(&mut self) {
+        self.is_editing_change_summary = false;
+        self.change_summary_str = String::new();
+    }
+
+    fn on_key_pressed_editing_change_summary(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.is_ascii() && key.to_ascii().is_some_and(|c| c.is_ascii_graphic() || c == b' ') => {
+                if self.change_summary_str.len() >= LevelPack::MAX_CHANGE_SUMMARY_LEN {
+                    return;
+                }
+
+                let _ = write!(self.change_summary_str, "{}", key.to_ascii().unwrap() as char);
+            },
+            Key::DELETE => {
+                self.change_summary_str.pop();
+            },
+
+            Key::ENTER => {
+                game_state.play_sound_effect_ui_select();
+
+                let change_summary = self.change_summary_str.trim().to_string();
+                game_state.editor_state.get_current_level_pack_mut().unwrap().set_change_summary(change_summary);
+
+                if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                }
+
+                self.stop_editing_change_summary();
+            },
+
+            Key::ESC => {
+                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                self.stop_editing_change_summary();
+            },
+
+            _ => {},
+        }
+    }
+
+    fn start_editing_story(&mut self, level_pack: &LevelPack, story: PackStory) {
+        self.editing_story = Some(story);
+        self.story_lines = level_pack.story(story).to_vec();
+        if self.story_lines.is_empty() {
+            self.story_lines.push(String::new());
+        }
+    }
+
+    /// Stores the edited lines in the level pack and saves the level pack if the text was changed
+    fn apply_story_lines(&mut self, game_state: &mut GameState) {
+        let Some(story) = self.editing_story else {
+            return;
+        };
+
+        //Trailing empty lines are removed
+        let mut story_lines = mem::take(&mut self.story_lines);
+        while story_lines.last().is_some_and(String::is_empty) {
+            story_lines.pop();
+        }
+
+        let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+        if level_pack.story(story) == story_lines {
+            return;
+        }
+
+        level_pack.set_story(story, story_lines);
+        level_pack.add_changelog_entry(format!("{} changed", story.display_name()));
+
+        if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+        }
+    }
+
+    fn on_key_pressed_editing_story(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.to_ascii().is_some_and(|c| c.is_ascii_graphic() || c == b' ') => {
+                let Some(line) = self.story_lines.last_mut() else {
+                    return;
+                };
+
+                if line.len() >= LevelPack::MAX_STORY_LINE_LEN {
+                    return;
+                }
+
+                line.push(key.to_ascii().unwrap() as char);
+            },
+
+            Key::DELETE => {
+                let Some(line) = self.story_lines.last_mut() else {
+                    return;
+                };
+
+                if line.pop().is_none() && self.story_lines.len() > 1 {
+                    self.story_lines.pop();
+                }
+            },
+
+            Key::ENTER => {
+                if self.story_lines.len() >= LevelPack::MAX_STORY_LINE_COUNT {
+                    return;
+                }
+
+                self.story_lines.push(String::new());
+            },
+
+            Key::TAB => {
+                game_state.play_sound_effect_ui_select();
+
+                self.apply_story_lines(game_state);
+
+                let story = match self.editing_story {
+                    Some(PackStory::Intro) => PackStory::Outro,
+                    _ => PackStory::Intro,
+                };
+                self.start_editing_story(game_state.editor_state.get_current_level_pack().unwrap(), story);
+            },
+
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
+
+                self.apply_story_lines(game_state);
+
+                self.editing_story = None;
+            },
+
+            _ => {},
+        }
+    }
+
+    fn draw_story_editor(&self, console: &Console, story: PackStory) {
+        console.set_color(Color::Cyan, Color::Default);
+        console.set_cursor_pos(0, 1);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 2..(5 + Self::PROBLEM_REPORT_ROW_COUNT) {
+            console.set_cursor_pos(0, i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, 5 + Self::PROBLEM_REPORT_ROW_COUNT);
+        console.draw_text("\'------------------------------------------------------------------------\'");
+
+        console.reset_color();
+        console.set_underline(true);
+        console.set_cursor_pos(2, 2);
+        console.draw_fmt(format_args!(
+            "{} ({}, {}/{} lines):",
+            story.display_name(),
+            match story {
+                PackStory::Intro => "Shown before level 1",
+                PackStory::Outro => "Shown after the last level",
+            },
+            self.story_lines.len(),
+            LevelPack::MAX_STORY_LINE_COUNT,
+        ));
+        console.set_underline(false);
+
+        //The last lines are shown, because text is only added at the end
+        let scroll_offset = self.story_lines.len().saturating_sub(Self::PROBLEM_REPORT_ROW_COUNT);
+        for (i, line) in self.story_lines.iter().
+                enumerate().
+                skip(scroll_offset) {
+            console.set_cursor_pos(2, 4 + i - scroll_offset);
+            console.draw_text(line);
+        }
+
+        console.set_color(Color::Cyan, Color::Default);
+        console.draw_text("_");
+
+        if scroll_offset > 0 {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 3, 4);
+            console.draw_text("^");
+        }
+
+        console.reset_color();
+        console.set_cursor_pos(1, 6 + Self::PROBLEM_REPORT_ROW_COUNT);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(": New line, ");
+        console.draw_key_input_text("TAB");
+        console.reset_color();
+        console.draw_text(": Intro/Outro, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Save and close");
+    }
+
+    fn stop_editing_required_level_pack(&mut self) {
+        self.is_editing_required_level_pack = false;
+        self.required_workshop_id_str = String::new();
+    }
+
+    fn on_key_pressed_editing_required_level_pack(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.is_ascii() && key.is_numeric() => {
+                //Max length of u64
+                if self.required_workshop_id_str.len() >= 20 {
+                    return;
+                }
+
+                let _ = write!(self.required_workshop_id_str, "{}", key.to_ascii().unwrap() as char);
+            },
+            Key::DELETE => {
+                self.required_workshop_id_str.pop();
+            },
+
+            Key::ENTER => {
+                if self.required_workshop_id_str.is_empty() {
+                    game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                    self.stop_editing_required_level_pack();
+
+                    return;
+                }
+
+                let Some(required_workshop_id) = u64::from_str(&self.required_workshop_id_str).ok().filter(|id| *id > 0) else {
+                    game_state.open_dialog(Dialog::new_ok_error("The Steam Workshop ID is invalid!"));
+
+                    return;
+                };
+
+                let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+                match level_pack.toggle_required_workshop_id(required_workshop_id) {
+                    Some(true) => level_pack.add_changelog_entry(format!("Required level pack {} added", required_workshop_id)),
+                    Some(false) => level_pack.add_changelog_entry(format!("Required level pack {} removed", required_workshop_id)),
+
+                    None => {
+                        game_state.open_dialog(Dialog::new_ok_error(format!(
+                            "Cannot add required level pack (Max count ({}) reached)",
+                            LevelPack::MAX_REQUIRED_LEVEL_PACK_COUNT,
+                        )));
+
+                        return;
+                    },
+                }
+
+                game_state.play_sound_effect_ui_select();
+
+                if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                }
+
+                self.stop_editing_required_level_pack();
+            },
+
+            Key::ESC => {
+                game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                self.stop_editing_required_level_pack();
+            },
+
+            _ => {},
+        }
+    }
+
+    fn update_list_elements(&mut self, game_state: &GameState) {
+        let elements = self.level_editor_list.elements_mut();
+
+        //Remove all level editor entries and create new level entry
+        elements.drain(1..);
+
+        let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+        for (i, level) in level_pack.levels().iter().enumerate() {
+            elements.push(UIListElement::new(
+                utils::number_to_string_leading_ascii(2, i as u32 + 1, false),
+                Color::Black,
+                if self.marked_level_indices.contains(&i) {
+                    Color::LightPink
+                }else if level.best_moves().is_some() {
+                    Color::Green
+                }else {
+                    Color::Yellow
+                },
+            ));
+        }
+
+        let has_max_level_count = level_pack.level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK;
+        elements.push(UIListElement::new(
+            " +",
+            Color::White,
+            if has_max_level_count {
+                Color::LightRed
+            }else {
+                Color::LightBlue
+            },
+        ));
+    }
+
+    fn open_problem_report(&mut self, game_state: &mut GameState) {
+        let problems = solver::find_level_pack_problems(game_state.editor_state.get_current_level_pack().unwrap());
+        if problems.is_empty() {
+            game_state.open_dialog(Dialog::new_ok("No problems were found in this level pack."));
+
+            return;
+        }
+
+        game_state.play_sound_effect_ui_select();
+
+        self.problem_report = Some(problems);
+        self.problem_report_cursor_index = 0;
+        self.problem_report_scroll_offset = 0;
+    }
+
+    fn on_key_pressed_problem_report(&mut self, game_state: &mut GameState, key: Key) {
+        let Some(problems) = &self.problem_report else {
+            return;
+        };
+
+        let navigator = ListNavigator::new(Self::PROBLEM_REPORT_ROW_COUNT);
+        if let Some(cursor_index) = navigator.navigate(self.problem_report_cursor_index, problems.len(), key) {
+            self.problem_report_cursor_index = cursor_index;
+        }
+
+        match key {
+            Key::ENTER => {
+                game_state.play_sound_effect_ui_select();
+
+                let level_index = problems[self.problem_report_cursor_index].0;
+                self.level_editor_list.set_cursor_index(level_index + 1);
+                self.level_preview = true;
+
+                self.problem_report = None;
+
+                return;
+            },
+
+            Key::ESC | Key::I => {
+                game_state.play_sound_effect_ui_select();
+
+                self.problem_report = None;
+
+                return;
+            },
+
+            _ => {},
+        }
+
+        if self.problem_report_cursor_index < self.problem_report_scroll_offset {
+            self.problem_report_scroll_offset = self.problem_report_cursor_index;
+        }else if self.problem_report_cursor_index >= self.problem_report_scroll_offset + Self::PROBLEM_REPORT_ROW_COUNT {
+            self.problem_report_scroll_offset = self.problem_report_cursor_index + 1 - Self::PROBLEM_REPORT_ROW_COUNT;
+        }
+    }
+
+    fn draw_problem_report(&self, console: &Console, problems: &[(usize, LevelProblem)]) {
+        console.set_color(Color::Cyan, Color::Default);
+        console.set_cursor_pos(0, 1);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 2..(5 + Self::PROBLEM_REPORT_ROW_COUNT) {
+            console.set_cursor_pos(0, i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, 5 + Self::PROBLEM_REPORT_ROW_COUNT);
+        console.draw_text("\'------------------------------------------------------------------------\'");
+
+        console.reset_color();
+        console.set_underline(true);
+        console.set_cursor_pos(2, 2);
+        console.draw_fmt(format_args!("Problem report ({} problems found):", problems.len()));
+        console.set_underline(false);
+
+        for (i, (level_index, problem)) in problems.iter().
+                enumerate().
+                skip(self.problem_report_scroll_offset).
+                take(Self::PROBLEM_REPORT_ROW_COUNT) {
+            console.set_cursor_pos(2, 4 + i - self.problem_report_scroll_offset);
+            if i == self.problem_report_cursor_index {
+                console.set_color(Color::Black, Color::Cyan);
+            }else {
+                console.reset_color();
+            }
+
+            console.draw_fmt(format_args!("Level {:03}: {}", level_index + 1, problem));
+        }
+
+        console.reset_color();
+        if self.problem_report_scroll_offset > 0 {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 3, 4);
+            console.draw_text("^");
+        }
+        if self.problem_report_scroll_offset + Self::PROBLEM_REPORT_ROW_COUNT < problems.len() {
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 3, 3 + Self::PROBLEM_REPORT_ROW_COUNT);
+            console.draw_text("v");
+        }
+
+        console.set_cursor_pos(1, 6 + Self::PROBLEM_REPORT_ROW_COUNT);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(": Go to level, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Close");
+    }
+
+    fn draw_overview(&self, game_state: &GameState, console: &Console) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_fmt(format_args!("Edit a level (Level pack \"{}\"):", game_state.editor_state.get_current_level_pack().unwrap().id()));
+        console.set_underline(false);
+
+        self.level_editor_list.draw(console);
+
+        //Draw estimated difficulties below the level entries
+        for i in 0..game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+            if let Some(difficulty) = game_state.editor_state.level_difficulty(i) {
+                let (x, y) = self.level_editor_list.element_pos(i + 1);
+                let (icon, color) = difficulty.icon();
+
+                console.set_cursor_pos(x + 1, y + 1);
+                console.set_color(color, Color::Default);
+                console.draw_text(icon);
+            }
+        }
+
+        let has_max_level_count = game_state.editor_state.get_current_level_pack().unwrap().level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK;
+
+        let entry_count = self.level_editor_list.elements().len();
+
+        //Draw border for best time and best moves
+        let y = 4 + ((entry_count - 1)/24)*2;
+
+        console.set_cursor_pos(0, y);
+        console.set_color(Color::Cyan, Color::Default);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 1..4 {
+            console.set_cursor_pos(0, y + i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, y + 4);
+        console.draw_text("\'------------------------------------------------------------------------\'");
+        console.reset_color();
+
+        let cursor_index = self.level_editor_list.cursor_index();
+        if self.is_creating_new_level {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_text("Enter width and height for new level:");
+
+            console.set_color(if self.is_editing_height {
+                Color::LightBlue
+            }else {
+                Color::Cyan
+            }, Color::Default);
+            console.set_cursor_pos(1, y + 2);
+            console.draw_fmt(format_args!("Width: {}", &self.new_level_width_str));
+
+            console.set_color(if self.is_editing_height {
+                Color::Cyan
+            }else {
+                Color::LightBlue
+            }, Color::Default);
+            console.set_cursor_pos(14, y + 2);
+            console.draw_fmt(format_args!("Height: {}", &self.new_level_height_str));
+        }else if self.is_editing_par {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_fmt(format_args!("Enter par values for level {:03} (Leave empty to remove):", cursor_index));
+
+            console.set_color(if self.is_editing_par_time {
+                Color::LightBlue
+            }else {
+                Color::Cyan
+            }, Color::Default);
+            console.set_cursor_pos(1, y + 2);
+            console.draw_fmt(format_args!("Moves: {}", &self.par_moves_str));
+
+            console.set_color(if self.is_editing_par_time {
+                Color::Cyan
+            }else {
+                Color::LightBlue
+            }, Color::Default);
+            console.set_cursor_pos(14, y + 2);
+            console.draw_fmt(format_args!("Time (s): {}", &self.par_time_str));
+        }else if self.is_editing_localized_name {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_text("Enter the translated level pack name (Leave empty to remove):");
+
+            console.set_cursor_pos(1, y + 2);
+            console.draw_text("Language (");
+            console.draw_key_input_text("TAB");
+            console.reset_color();
+            console.draw_text("): ");
+
+            console.set_color(Color::LightBlue, Color::Default);
+            console.draw_text(self.localized_name_language.display_name());
+
+            console.set_color(Color::Cyan, Color::Default);
+            console.set_cursor_pos(31, y + 2);
+            console.draw_fmt(format_args!("Name: {}", &self.localized_name_str));
+        }else if self.is_editing_change_summary {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_fmt(format_args!(
+                "Enter the change summary for the next upload ({} changelog entries):",
+                game_state.editor_state.get_current_level_pack().unwrap().changelog().len(),
+            ));
+
+            console.set_color(Color::Cyan, Color::Default);
+            console.set_cursor_pos(1, y + 2);
+            console.draw_text(&self.change_summary_str);
+        }else if self.is_editing_required_level_pack {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_text("Enter the Steam Workshop ID of a required level pack (Toggle):");
+
+            console.set_color(Color::Cyan, Color::Default);
+            console.set_cursor_pos(1, y + 2);
+            console.draw_fmt(format_args!("ID: {}", &self.required_workshop_id_str));
+
+            let required_workshop_ids = game_state.editor_state.get_current_level_pack().unwrap().required_workshop_ids().iter().
+                    map(|required_workshop_id| required_workshop_id.to_string()).
+                    collect::<Vec<_>>().
+                    join(", ");
+
+            let mut required_text = format!("Required: {}", if required_workshop_ids.is_empty() {
+                "None"
+            }else {
+                required_workshop_ids.as_str()
+            });
+            required_text.truncate(Game::CONSOLE_MIN_WIDTH - 2);
+
+            console.reset_color();
+            console.set_cursor_pos(1, y + 3);
+            console.draw_text(required_text);
+        }else if self.is_multi_select_mode {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_fmt(format_args!("Multi-select: {:03} levels marked", self.marked_level_indices.len()));
+
+            console.set_cursor_pos(1, y + 2);
+            console.draw_text("Mark: ");
+            console.draw_key_input_text("SPACEBAR");
+            console.reset_color();
+            console.draw_text(", Move: ");
+            console.draw_key_input_text("ENTER");
+            console.reset_color();
+            console.draw_text(", Delete: ");
+            console.draw_key_input_text("DELETE");
+            console.reset_color();
+            console.draw_text(", Undo: ");
+            console.draw_key_input_text("z");
+            console.reset_color();
+            console.draw_text(", Exit: ");
+            console.draw_key_input_text("m");
+            console.reset_color();
+        }else if cursor_index == 0 {
+            console.reset_color();
+            console.set_cursor_pos(35, y + 2);
+            console.draw_text("Back");
+        }else {
+            let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+
+            if cursor_index - 1 == level_pack.level_count() {
+                //Level Editor entry
+                if has_max_level_count {
+                    let error_msg = format!(
+                        "Max level count ({}) reached",
+                        LevelPack::MAX_LEVEL_COUNT_PER_PACK,
+                    );
+
+                    let x_offset = ((Game::CONSOLE_MIN_WIDTH - error_msg.len()) as f64 * 0.5) as usize;
+                    console.set_cursor_pos(x_offset, y + 2);
+                    console.set_color(Color::LightRed, Color::Default);
+                    console.draw_text(error_msg);
+                }else {
+                    console.set_cursor_pos(30, y + 2);
+                    console.draw_text("Create a level");
+                }
+            }else {
+                let level = level_pack.levels().get(cursor_index - 1).unwrap();
+
+                //Draw best time and best moves
+                console.set_cursor_pos(1, y + 1);
+                console.draw_text("Selected level: ");
+                console.draw_fmt(format_args!("{:03}", cursor_index));
+
+                if level_pack.thumbnail_level_index().is_some_and(|index| index == cursor_index - 1) {
+                    console.draw_text(" [Thumbnail]");
+
+                    console.reset_color();
+                    console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 38, y + 2);
+                    console.draw_text("Press ");
+
+                    console.draw_key_input_text(MenuAction::LevelPackThumbnail.key_name());
+
+                    console.reset_color();
+                    console.draw_text(" to unset level pack thumbnail");
+                }else {
+                    console.reset_color();
+                    console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 36, y + 2);
+                    console.draw_text("Press ");
+
+                    console.draw_key_input_text(MenuAction::LevelPackThumbnail.key_name());
+
+                    console.reset_color();
+                    console.draw_text(" to set level pack thumbnail");
+                }
+
+                console.set_cursor_pos(1, y + 2);
+                console.draw_fmt(format_args!(
+                    "Size: {} x {}",
+                    level.level().width(),
+                    level.level().height(),
+                ));
+
+                console.set_cursor_pos(15, y + 2);
+                draw_level_par(console, level);
+
+                console.set_cursor_pos(1, y + 3);
+                console.draw_text("Validation: ");
+                {
+                    if let Some(best_moves) = level.best_moves() {
+                        console.set_color(Color::Green, Color::Default);
+                        console.draw_fmt(format_args!("Best moves: {best_moves}"));
+
+                        console.reset_color();
+                        console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 22, y + 3);
+                        console.draw_text("Stats locked (");
+                        console.draw_key_input_text("l");
+                        console.reset_color();
+                        console.draw_text("): ");
+                        if level_pack.stats_locked() {
+                            console.set_color(Color::Green, Color::Default);
+                            console.draw_text("Yes");
+                        }else {
+                            console.set_color(Color::Red, Color::Default);
+                            console.draw_text(" No");
+                        }
+                    }else {
+                        console.set_color(Color::Red, Color::Default);
+                        console.draw_text("You need to complete this level to validate it");
+                    }
+                }
+
+                if let Some(difficulty) = game_state.editor_state.level_difficulty(cursor_index - 1) {
+                    console.reset_color();
+                    console.set_cursor_pos(33, y + 1);
+                    console.draw_text("Estimate: ");
+                    console.set_color(difficulty.icon().1, Color::Default);
+                    console.draw_text(difficulty.display_name());
+                }
+
+                console.reset_color();
+                console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 26, y + 1);
+                console.draw_text("Press ");
 
-        //Remove all level pack editor entries and create new level pack entry
-        elements.drain(1..);
+                console.draw_key_input_text(MenuAction::LevelPreview.key_name());
 
-        for (i, level_pack) in game_state.editor_state.level_packs.iter().enumerate() {
-            elements.push(UIListElement::new(
-                utils::number_to_string_leading_ascii(2, i as u32 + 1, false),
-                Color::Black,
-                if level_pack.level_pack_best_moves_sum().is_some() {
-                    Color::Green
-                }else {
-                    Color::Yellow
-                },
-            ));
+                console.reset_color();
+                console.draw_text(" for level preview");
+            }
         }
-
-        let has_max_level_pack_count = game_state.editor_state.get_level_pack_count() == LevelPack::MAX_LEVEL_PACK_COUNT;
-        elements.push(UIListElement::new(
-            " +",
-            Color::White,
-            if has_max_level_pack_count {
-                Color::LightRed
-            }else {
-                Color::LightBlue
-            },
-        ));
     }
-}
 
-impl Screen for ScreenSelectLevelPackEditor {
-    fn draw(&self, game_state: &GameState, console: &Console) {
-        console.reset_color();
-        console.set_underline(true);
-        console.draw_text("Edit a level pack:");
-        console.set_underline(false);
+    fn draw_level_preview(&self, game_state: &GameState, console: &Console) {
+        let cursor_index = self.level_editor_list.cursor_index();
 
-        self.level_pack_editor_list.draw(console);
+        if cursor_index == 1 {
+            console.draw_key_input_text("<");
 
-        let has_max_level_pack_count = game_state.editor_state.get_level_pack_count() == LevelPack::MAX_LEVEL_PACK_COUNT;
+            console.reset_color();
+            console.draw_text(" Back");
+        }else if cursor_index > 1 {
+            console.draw_key_input_text("<");
 
-        let entry_count = self.level_pack_editor_list.elements().len();
+            console.reset_color();
+            console.draw_fmt(format_args!(" Level {:03}", cursor_index - 1));
+        }
 
-        //Draw border for best time and best moves
-        let y = 4 + ((entry_count - 1)/24)*2;
+        if game_state.editor_state.get_current_level_pack().unwrap().level_count() > 0 &&
+                cursor_index < game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+            console.reset_color();
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 11, 0);
+            console.draw_fmt(format_args!("Level {:03} ", cursor_index + 1));
 
-        console.set_cursor_pos(0, y);
-        console.set_color(Color::Cyan, Color::Default);
-        console.draw_text(".------------------------------------------------------------------------.");
-        for i in 1..4 {
-            console.set_cursor_pos(0, y + i);
-            console.draw_text("|                                                                        |");
+            console.draw_key_input_text(">");
         }
-        console.set_cursor_pos(0, y + 4);
-        console.draw_text("\'------------------------------------------------------------------------\'");
-        console.reset_color();
 
-        let cursor_index = self.level_pack_editor_list.cursor_index();
-        if self.is_creating_new_level_pack {
-            console.set_cursor_pos(1, y + 1);
-            console.draw_text("Enter a new level pack ID:");
+        if game_state.editor_state.get_current_level_pack().unwrap().level_count() > 0 &&
+                cursor_index == game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+            console.reset_color();
+            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 16, 0);
+            console.draw_text("Create a level ");
 
-            console.set_cursor_pos(1, y + 2);
+            console.draw_key_input_text(">");
+        }
+
+        if cursor_index == 0 {
+            let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
+            let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
+
+            console.set_cursor_pos(x, y);
             console.set_color(Color::Cyan, Color::Default);
-            console.draw_text(format!("> {}", &self.new_level_pack_id));
-        }else if cursor_index == 0 {
+            console.draw_text(".--------------------------------------.");
+            for i in 1..4 {
+                console.set_cursor_pos(x, y + i);
+                console.draw_text("|                                      |");
+            }
+            console.set_cursor_pos(x, y + 4);
+            console.draw_text("\'--------------------------------------\'");
+
             console.reset_color();
             console.set_cursor_pos(35, y + 2);
             console.draw_text("Back");
-        }else if cursor_index - 1 == game_state.editor_state.get_level_pack_count() {
-            //Level Pack Editor entry
-            if has_max_level_pack_count {
+        }else if cursor_index - 1 == game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+            let has_max_level_count = game_state.editor_state.get_current_level_pack().unwrap().level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK;
+
+            console.reset_color();
+            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 11) as f64 * 0.5) as usize, 0);
+            console.draw_text("Preview (");
+
+            console.draw_key_input_text(MenuAction::LevelPreview.key_name());
+
+            console.reset_color();
+            console.draw_text(")");
+
+            let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
+            let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
+
+            console.set_cursor_pos(x, y);
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_text(".--------------------------------------.");
+            for i in 1..4 {
+                console.set_cursor_pos(x, y + i);
+                console.draw_text("|                                      |");
+            }
+            console.set_cursor_pos(x, y + 4);
+            console.draw_text("\'--------------------------------------\'");
+
+            if has_max_level_count {
                 let error_msg = format!(
-                    "Max level pack count ({}) reached",
-                    LevelPack::MAX_LEVEL_PACK_COUNT,
+                    "Max level count ({}) reached",
+                    LevelPack::MAX_LEVEL_COUNT_PER_PACK,
                 );
 
                 let x_offset = ((Game::CONSOLE_MIN_WIDTH - error_msg.len()) as f64 * 0.5) as usize;
@@ -2354,131 +8411,178 @@ impl Screen for ScreenSelectLevelPackEditor {
                 console.set_color(Color::LightRed, Color::Default);
                 console.draw_text(error_msg);
             }else {
-                console.set_cursor_pos(28, y + 2);
-                console.draw_text("Create a level pack");
+                console.set_cursor_pos(30, y + 2);
+                console.reset_color();
+                console.draw_text("Create a level");
             }
         }else {
-            let level_pack = game_state.editor_state.level_packs.get(cursor_index - 1).unwrap();
+            console.reset_color();
+            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 23) as f64 * 0.5) as usize, 0);
+            console.draw_text("Preview (");
 
-            console.set_cursor_pos(1, y + 1);
-            console.draw_text(format!("Level Pack ID: {}", level_pack.id()));
+            console.draw_key_input_text(MenuAction::LevelPreview.key_name());
 
-            console.set_cursor_pos(1, y + 2);
-            console.draw_text(format!("Levels: {}", level_pack.level_count()));
+            console.reset_color();
+            console.draw_fmt(format_args!(") [Level {:03}]", cursor_index));
 
-            console.set_cursor_pos(1, y + 3);
-            console.draw_text("Background music: ");
+            let level = game_state.editor_state.get_current_level_pack().unwrap().levels()[cursor_index - 1].level();
 
-            match level_pack.background_music_id().
-                    map(|background_music_id| audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id)) {
-                Some(background_music) => {
-                    console.set_color(Color::LightCyan, Color::Default);
-                    console.draw_text(background_music.display_name());
+            let x_offset = ((Game::CONSOLE_MIN_WIDTH - level.draw_width()) as f64 * 0.5) as usize;
+            let y_offset = 1;
 
-                    console.reset_color();
-                    console.draw_text(" [by ");
+            level.draw(console, x_offset, y_offset, game_state.is_player_background(), None);
+        }
+    }
+}
 
-                    console.set_color(Color::LightPink, Color::Default);
-                    console.draw_text(background_music.creator());
+impl Screen for ScreenLevelPackEditor {
+    fn draw(&self, game_state: &GameState, console: &Console) {
+        if self.level_preview {
+            self.draw_level_preview(game_state, console);
+        }else {
+            self.draw_overview(game_state, console);
+        }
 
-                    console.reset_color();
-                    console.draw_text("]");
-                },
+        if let Some(problems) = &self.problem_report {
+            self.draw_problem_report(console, problems);
+        }
 
-                None => {
-                    console.draw_text("None");
-                },
-            }
+        if let Some(story) = self.editing_story {
+            self.draw_story_editor(console, story);
+        }
+    }
 
-            console.set_cursor_pos(46, y + 1);
-            console.draw_key_input_text("m");
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if self.problem_report.is_some() {
+            self.on_key_pressed_problem_report(game_state, key);
 
-            console.reset_color();
-            console.draw_text(":  Select background music");
+            return;
+        }
 
-            #[cfg(feature = "steam")]
-            {
-                console.set_cursor_pos(46, y + 2);
-                console.draw_key_input_text("u");
+        if self.is_editing_par {
+            self.on_key_pressed_editing_par(game_state, key);
 
-                console.reset_color();
-                console.draw_text(": Upload to Steam Workshop");
-            }
+            return;
         }
-    }
 
-    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
-        if self.is_creating_new_level_pack {
+        if self.is_editing_localized_name {
+            self.on_key_pressed_editing_localized_name(game_state, key);
+
+            return;
+        }
+
+        if self.is_editing_change_summary {
+            self.on_key_pressed_editing_change_summary(game_state, key);
+
+            return;
+        }
+
+        if self.is_editing_required_level_pack {
+            self.on_key_pressed_editing_required_level_pack(game_state, key);
+
+            return;
+        }
+
+        if self.editing_story.is_some() {
+            self.on_key_pressed_editing_story(game_state, key);
+
+            return;
+        }
+
+        if self.is_creating_new_level {
             match key {
-                key if key.is_ascii() && (key.is_alphanumeric() || key == Key::UNDERSCORE || key == Key::MINUS) => {
-                    if self.new_level_pack_id.len() >= LevelPack::MAX_LEVEL_PACK_NAME_LEN {
-                        return;
+                key if key.is_ascii() && key.is_numeric() => {
+                    if self.is_editing_height {
+                        if self.new_level_height_str.len() >= 2 {
+                            return;
+                        }
+
+                        let _ = write!(self.new_level_height_str, "{}", key.to_ascii().unwrap() as char);
+                    }else {
+                        if self.new_level_width_str.len() >= 2 {
+                            return;
+                        }
+
+                        let _ = write!(self.new_level_width_str, "{}", key.to_ascii().unwrap() as char);
                     }
-                    
-                    let _ = write!(self.new_level_pack_id, "{}", key.to_ascii().unwrap() as char);
                 },
                 Key::DELETE => {
-                    self.new_level_pack_id.pop();
+                    if self.is_editing_height {
+                        self.new_level_height_str.pop();
+                    }else {
+                        self.new_level_width_str.pop();
+                    }
+                },
+
+                Key::TAB => {
+                    self.is_editing_height = !self.is_editing_height;
                 },
 
                 Key::ENTER => {
-                    if self.new_level_pack_id.len() < 3 {
-                        game_state.open_dialog(Dialog::new_ok_error("Level pack ID must have at least 3 characters!"));
+                    if !(1..=2).contains(&self.new_level_width_str.len()) {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Width must be >= 3 and <= {}!", Game::LEVEL_MAX_WIDTH)));
 
                         return;
                     }
 
-                    for id in game_state.editor_state.level_packs.iter().
-                            map(|level_pack| level_pack.id()) {
-                        if id == self.new_level_pack_id {
-                            game_state.open_dialog(Dialog::new_ok_error(format!("The level pack with the ID \"{}\" already exists!", id)));
+                    let Ok(width) = usize::from_str(&self.new_level_width_str) else {
+                        game_state.open_dialog(Dialog::new_ok_error("Width must be a number"));
 
-                            return;
-                        }
+                        return;
+                    };
+
+                    if !(3..=Game::LEVEL_MAX_WIDTH).contains(&width) {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Width must be >= 3 and <= {}!", Game::LEVEL_MAX_WIDTH)));
+
+                        return;
+                    }
+
+                    if self.new_level_height_str.is_empty() && !self.is_editing_height {
+                        self.is_editing_height = true;
+
+                        return;
                     }
 
-                    let Ok(mut save_game_file) = Game::get_or_create_save_game_folder() else {
-                        game_state.open_dialog(Dialog::new_ok_error("Cannot save!"));
+                    if !(1..=2).contains(&self.new_level_height_str.len()) {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Height must be >= 3 and <= {}!", Game::LEVEL_MAX_HEIGHT)));
 
                         return;
-                    };
-                    save_game_file.push(&self.new_level_pack_id);
-                    save_game_file.push(".lvl.edit");
+                    }
 
-                    let Some(save_game_file) = save_game_file.to_str() else {
-                        game_state.open_dialog(Dialog::new_ok_error("Cannot save!"));
+                    let Ok(height) = usize::from_str(&self.new_level_height_str) else {
+                        game_state.open_dialog(Dialog::new_ok_error("Height must be a number"));
 
                         return;
                     };
 
-                    let level_pack = LevelPack::new(&self.new_level_pack_id, &self.new_level_pack_id, save_game_file);
-                    if let Err(err) = level_pack.save_editor_level_pack() {
-                        game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                    if !(3..=Game::LEVEL_MAX_HEIGHT).contains(&height) {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Height must be >= 3 and <= {}!", Game::LEVEL_MAX_HEIGHT)));
+
+                        return;
                     }
 
                     game_state.play_sound_effect_ui_select();
 
-                    let index = game_state.editor_state.level_packs.binary_search_by_key(
-                        &level_pack.id().to_string(),
-                        |level_pack| level_pack.id().to_string(),
-                    ).err().unwrap();
-
-                    game_state.editor_state.level_packs.insert(index, level_pack);
+                    let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+                    level_pack.add_level(Level::new(width, height));
+                    level_pack.add_changelog_entry(format!("Level {} added", level_pack.level_count()));
 
-                    //self.is_creating_new_level_pack with be set to false in on_set_screen after background music selection
-                    self.new_level_pack_id = String::new();
+                    self.is_creating_new_level = false;
+                    self.is_editing_height = false;
+                    self.new_level_width_str = String::new();
+                    self.new_level_height_str = String::new();
 
-                    self.level_pack_editor_list.set_cursor_index(index + 1);
-                    game_state.editor_state.set_level_pack_index(index);
-                    game_state.editor_state.set_level_index(0);
-                    game_state.set_screen(ScreenId::SelectLevelPackBackgroundMusic);
+                    game_state.editor_state.set_level_index(self.level_editor_list.cursor_index() - 1);
+                    game_state.set_screen(ScreenId::LevelEditor);
                 },
 
                 Key::ESC => {
                     game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
 
-                    self.is_creating_new_level_pack = false;
-                    self.new_level_pack_id = String::new();
+                    self.is_creating_new_level = false;
+                    self.is_editing_height = false;
+                    self.new_level_width_str = String::new();
+                    self.new_level_height_str = String::new();
                 },
 
                 _ => {},
@@ -2487,971 +8591,1089 @@ impl Screen for ScreenSelectLevelPackEditor {
             return;
         }
 
+        if self.is_multi_select_mode {
+            self.on_key_pressed_multi_select_mode(game_state, key);
+
+            return;
+        }
+
         if key == Key::ESC {
             game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
 
-            game_state.set_screen(ScreenId::SelectLevelPack);
+            if self.level_preview {
+                self.level_preview = false;
+            }else {
+                game_state.set_screen(ScreenId::SelectLevelPackEditor);
+            }
 
             return;
         }
 
-        let cursor_index = self.level_pack_editor_list.cursor_index();
-        if cursor_index >= 1 && cursor_index - 1 != game_state.editor_state.get_level_pack_count() {
-            if key == Key::M {
-                game_state.play_sound_effect_ui_dialog_open();
+        if key == Key::M {
+            game_state.play_sound_effect_ui_select();
 
-                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+            self.level_preview = false;
+            self.is_multi_select_mode = true;
+            self.marked_level_indices.clear();
+            self.update_list_elements(game_state);
 
-                match game_state.editor_state.get_current_level_pack().unwrap().
-                        background_music_id().
-                        map(|background_music_id| audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id)) {
-                    Some(background_music) => game_state.set_background_music_loop(background_music),
-                    None => game_state.stop_background_music(),
-                }
+            return;
+        }
 
-                game_state.set_screen(ScreenId::SelectLevelPackBackgroundMusic);
-            }
+        if key == MenuAction::LevelPreview.key() {
+            game_state.play_sound_effect_ui_select();
 
-            if key == Key::E {
-                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+            self.level_preview = !self.level_preview;
 
-                self.is_exporting_level_pack = true;
+            return;
+        }
 
-                game_state.open_dialog(Dialog::new_yes_no("Do you want to export the level pack to the current directory?"));
-            }
+        if key == Key::N {
+            game_state.play_sound_effect_ui_select();
 
-            #[cfg(feature = "steam")]
-            if key == Key::U {
-                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+            self.level_preview = false;
+            self.start_editing_localized_name(game_state.editor_state.get_current_level_pack().unwrap(), game_state.settings.language);
 
-                let level_stats = &game_state.editor_state.get_current_level_pack().unwrap();
-                if level_stats.level_pack_best_moves_sum().is_none() {
-                    game_state.open_dialog(Dialog::new_ok_error(
-                        "Level pack was not validated yet! All levels must be validated.",
-                    ));
+            return;
+        }
 
-                    return;
-                }
+        if key == Key::H {
+            game_state.play_sound_effect_ui_select();
 
-                let ret = steam::prepare_workshop_upload_temp_data(
-                    game_state.editor_state.get_current_level_pack().unwrap(),
-                );
-                if let Err(err) = ret {
-                    game_state.open_dialog(Dialog::new_ok_error(format!(
-                        "Could not prepare files for upload to steam workshop!\n{err}",
-                    )));
+            self.level_preview = false;
+            self.start_editing_change_summary(game_state.editor_state.get_current_level_pack().unwrap());
 
-                    return;
-                }
+            return;
+        }
 
-                game_state.play_sound_effect_ui_dialog_open();
-                game_state.show_workshop_upload_popup = true;
-            }
+        if key == Key::I {
+            self.level_preview = false;
+            self.open_problem_report(game_state);
 
-            if key == Key::DELETE {
-                game_state.editor_state.set_level_pack_index(cursor_index - 1);
+            return;
+        }
 
-                self.is_deleting_level_pack = true;
+        if key == Key::W {
+            game_state.play_sound_effect_ui_select();
 
-                game_state.open_dialog(Dialog::new_yes_no(format!(
-                    "Do you really want to delete level pack \"{}\"?",
-                    game_state.editor_state.get_current_level_pack().unwrap().id(),
-                )));
-            }
+            self.level_preview = false;
+            self.is_editing_required_level_pack = true;
+
+            return;
         }
 
-        self.level_pack_editor_list.on_key_press(&mut self.is_creating_new_level_pack, game_state, key);
-    }
+        if key == Key::O {
+            game_state.play_sound_effect_ui_select();
+
+            self.level_preview = false;
+            self.start_editing_story(game_state.editor_state.get_current_level_pack().unwrap(), PackStory::Intro);
 
-    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
-        if row == 0 {
             return;
         }
 
-        let element_count = self.level_pack_editor_list.elements().len();
-        let y = 4 + ((element_count - 1)/24)*2;
-        if row == y + 1 && (46..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
-            self.on_key_pressed(game_state, Key::M);
+        if key == Key::R {
+            game_state.play_sound_effect_ui_select();
+
+            self.reload_level_pack(game_state);
+
+            return;
         }
 
-        #[cfg(feature = "steam")]
-        {
-            if row == y + 2 && (46..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
-                self.on_key_pressed(game_state, Key::U);
+        if key == Key::L {
+            game_state.play_sound_effect_ui_select();
+
+            let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+            let stats_locked = level_pack.stats_locked();
+            if let Err(err) = level_pack.set_stats_locked(!stats_locked) {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
             }
+
+            return;
         }
 
-        self.level_pack_editor_list.on_mouse_pressed(&mut self.is_creating_new_level_pack, game_state, column, row);
-    }
+        let cursor_index = self.level_editor_list.cursor_index();
+        if cursor_index > 0 {
+            let selected_level_index = cursor_index - 1;
 
-    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
-        if self.is_exporting_level_pack {
-            self.is_exporting_level_pack = false;
+            match key {
+                key if key == MenuAction::LevelPackThumbnail.key() => {
+                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+                        game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
 
-            if selection == DialogSelection::Yes {
-                let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
-                let path = level_pack.id().to_string() + ".lvl";
+                        if game_state.editor_state.get_current_level_pack().unwrap().
+                                thumbnail_level_index().is_some_and(|index| index == selected_level_index) {
+                            game_state.editor_state.get_current_level_pack_mut().unwrap().set_thumbnail_level_index(None);
+                        }else {
+                            game_state.editor_state.get_current_level_pack_mut().unwrap().set_thumbnail_level_index(Some(selected_level_index));
+                        }
+                        game_state.editor_state.get_current_level_pack_mut().unwrap().add_changelog_entry("Thumbnail level changed");
 
-                if std::fs::exists(&path).ok().is_none_or(|exists| exists) {
-                    game_state.open_dialog(Dialog::new_ok_error(format!(
-                        "File \"{}\" already exists!",
-                        path,
-                    )));
+                        if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+                            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                        }
 
-                    return;
-                }
+                        self.update_list_elements(game_state);
+                    }
+                },
 
-                if let Err(err) = level_pack.export_editor_level_pack_to_path(path) {
-                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot export: {}", err)));
-                }else {
-                    game_state.open_dialog(Dialog::new_ok("The level pack was exported successfully"));
-                }
-            }
-        }else if self.is_deleting_level_pack {
-            self.is_deleting_level_pack = false;
+                Key::C => {
+                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+                        game_state.play_sound_effect_ui_select();
 
-            if selection == DialogSelection::Yes {
-                let path = game_state.editor_state.get_current_level_pack().unwrap().path().to_string();
-                let save_game_path = path.clone() + ".sav";
+                        self.level_clipboard = Some(game_state.editor_state.get_current_level_pack().unwrap().levels()[selected_level_index].clone());
+                    }
+                },
 
-                if let Err(err) = std::fs::remove_file(save_game_path) {
-                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot delete: {}", err)));
-                }else if let Err(err) = std::fs::remove_file(path) {
-                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot delete: {}", err)));
-                }else {
-                    game_state.editor_state.level_packs.remove(self.level_pack_editor_list.cursor_index() - 1);
-                }
-            }
+                Key::X => {
+                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+                        game_state.play_sound_effect_ui_select();
 
-            self.update_list_elements(game_state);
-            //Cursor index will always be inbound after level pack deletion because of the Create Level Pack Entry
-        }
-    }
+                        let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+                        self.level_clipboard = Some(level_pack.remove_level(selected_level_index));
+                        level_pack.add_changelog_entry(format!("Level {} removed", selected_level_index + 1));
 
-    fn on_set_screen(&mut self, game_state: &mut GameState) {
-        if self.is_creating_new_level_pack {
-            //Background music was selected for newly created level pack -> Do not change music and enter level pack editor
+                        if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+                            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                        }
+                    }
 
-            self.is_creating_new_level_pack = false;
-            game_state.set_screen(ScreenId::LevelPackEditor);
-        }else {
-            self.update_list_elements(game_state);
+                    self.update_list_elements(game_state);
+                },
 
-            if self.level_pack_editor_list.cursor_index() == 0 {
-                //Skip "back" entry and set to first level pack
-                self.level_pack_editor_list.set_cursor_index(1);
+                Key::V => {
+                    if let Some(ref level) = self.level_clipboard {
+                        if game_state.editor_state.get_current_level_pack().unwrap().level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK {
+                            game_state.open_dialog(Dialog::new_ok_error(format!(
+                                "Cannot paste level (Max level count ({}) reached)",
+                                LevelPack::MAX_LEVEL_COUNT_PER_PACK,
+                            )));
+                        }else {
+                            game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+
+                            let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+                            level_pack.insert_level(selected_level_index, level.clone());
+                            level_pack.add_changelog_entry(format!("Level {} added", selected_level_index + 1));
+
+                            if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+                                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                            }
+                        }
+                    }else {
+                        game_state.open_dialog(Dialog::new_ok_error("No level in clipboard!\nPlease copy a level by pressing \"C\" or cut a level by pressing \"X\"."));
+                    }
+
+                    self.update_list_elements(game_state);
+                },
+
+                Key::E => {
+                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+                        game_state.play_sound_effect_ui_select();
+
+                        self.level_preview = false;
+                        self.start_editing_par(&game_state.editor_state.get_current_level_pack().unwrap().levels()[selected_level_index]);
+                    }
+                },
+
+                Key::DELETE => {
+                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+                        self.is_deleting_level = true;
+
+                        game_state.open_dialog(Dialog::new_yes_no(format!(
+                            "Do you really want to delete level {}?\n\nDeleted levels can be restored for {} days.",
+                            selected_level_index + 1,
+                            trash::RETENTION_DAYS,
+                        )));
+                    }
+                },
+
+                _ => {},
             }
+        }
 
-            game_state.set_background_music_loop(&audio::BACKGROUND_MUSIC_FIELDS_OF_ICE);
+        let is_creating_new_level_orig = self.is_creating_new_level;
+        self.level_editor_list.on_key_press(&mut self.is_creating_new_level, game_state, key);
+        if is_creating_new_level_orig != self.is_creating_new_level && self.is_creating_new_level {
+            self.level_preview = false;
         }
     }
-}
 
-pub struct ScreenSelectLevelPackBackgroundMusic {}
+    fn on_text_pasted(&mut self, _: &mut GameState, text: &str) {
+        if self.is_editing_par {
+            if self.is_editing_par_time {
+                let mut has_dot = self.par_time_str.contains(".");
+                utils::append_pasted_text(&mut self.par_time_str, text, 8, |c| {
+                    c.is_ascii_digit() || (c == '.' && !mem::replace(&mut has_dot, true))
+                });
+            }else {
+                utils::append_pasted_text(&mut self.par_moves_str, text, 4, |c| c.is_ascii_digit());
+            }
+        }else if self.is_editing_localized_name {
+            utils::append_pasted_text(&mut self.localized_name_str, text, LevelPack::MAX_LEVEL_PACK_NAME_LEN, |c| c.is_ascii_graphic() || c == ' ');
+        }else if self.is_editing_change_summary {
+            utils::append_pasted_text(&mut self.change_summary_str, text, LevelPack::MAX_CHANGE_SUMMARY_LEN, |c| c.is_ascii_graphic() || c == ' ');
+        }else if self.is_editing_required_level_pack {
+            utils::append_pasted_text(&mut self.required_workshop_id_str, text, 20, |c| c.is_ascii_digit());
+        }else if self.editing_story.is_some() && let Some(line) = self.story_lines.last_mut() {
+            utils::append_pasted_text(line, text, LevelPack::MAX_STORY_LINE_LEN, |c| c.is_ascii_graphic() || c == ' ');
+        }else if self.is_creating_new_level {
+            let input_str = if self.is_editing_height {
+                &mut self.new_level_height_str
+            }else {
+                &mut self.new_level_width_str
+            };
 
-impl ScreenSelectLevelPackBackgroundMusic {
-    pub fn new() -> Self {
-        Self {}
+            utils::append_pasted_text(input_str, text, 2, |c| c.is_ascii_digit());
+        }
     }
-}
-
-impl Screen for ScreenSelectLevelPackBackgroundMusic {
-    fn draw(&self, game_state: &GameState, console: &Console) {
-        console.reset_color();
-        console.set_underline(true);
-        console.draw_text("Select the background music for the level pack:");
-        console.set_underline(false);
 
-        console.set_cursor_pos(0, 1);
-        console.draw_key_input_text("ENTER");
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if self.is_editing_par || self.is_editing_localized_name || self.is_editing_change_summary || self.is_editing_required_level_pack ||
+                self.editing_story.is_some() || self.problem_report.is_some() {
+            return;
+        }
 
-        console.reset_color();
-        console.draw_text(": Save selection");
+        if self.level_preview {
+            if row == 0 {
+                let center_text_start = ((Game::CONSOLE_MIN_WIDTH - 23) as f64 * 0.5) as usize;
 
-        console.set_cursor_pos(0, 2);
-        console.draw_key_input_text("ESC");
+                if column < 11 {
+                    self.on_key_pressed(game_state, Key::LEFT);
+                }else if column >= center_text_start && column < center_text_start + 23 {
+                    self.on_key_pressed(game_state, Key::ENTER);
+                }else if column > Game::CONSOLE_MIN_WIDTH - 12 {
+                    self.on_key_pressed(game_state, Key::RIGHT);
+                }
 
-        console.reset_color();
-        console.draw_text(": Cancel");
+                let selected_level = game_state.editor_state.get_level_index();
+                if game_state.editor_state.get_current_level_pack().unwrap().level_count() > 0 &&
+                        selected_level == game_state.editor_state.get_current_level_pack().unwrap().level_count() - 1 &&
+                        column > Game::CONSOLE_MIN_WIDTH - 17 {
+                    self.on_key_pressed(game_state, Key::RIGHT);
+                }
+            }
 
-        console.reset_color();
-        console.set_cursor_pos(0, 4);
-        console.draw_text("( ) None");
+            return;
+        }
 
-        let current_selected_music_index = game_state.current_background_music_id().
-                map(|id| id.id()).
-                unwrap_or(0);
+        if row == 0 {
+            return;
+        }
 
-        for track in audio::BACKGROUND_MUSIC_TRACKS.tracks() {
-            console.reset_color();
-            console.set_cursor_pos(0, track.id().id() + 4);
-            console.reset_color();
-            console.draw_text("( ) ");
+        let element_count = self.level_editor_list.elements().len();
+        let y = 4 + ((element_count - 1)/24)*2;
+        if row == y + 1 && (Game::CONSOLE_MIN_WIDTH - 26..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
+            self.on_key_pressed(game_state, MenuAction::LevelPreview.key());
+        }
 
-            console.set_color(Color::LightCyan, Color::Default);
-            console.draw_text(format!("{:35}", track.display_name()));
+        if row == y + 2 && (Game::CONSOLE_MIN_WIDTH - 38..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
+            self.on_key_pressed(game_state, MenuAction::LevelPackThumbnail.key());
+        }
 
-            console.reset_color();
-            console.draw_text(" [by ");
+        if self.is_multi_select_mode {
+            if let Some(element_index) = self.level_editor_list.element_index_at(column, row) {
+                self.level_editor_list.set_cursor_index(element_index);
+                self.on_key_pressed(game_state, Key::SPACE);
+            }
 
-            console.set_color(Color::LightPink, Color::Default);
-            console.draw_text(track.creator());
+            return;
+        }
 
-            console.reset_color();
-            console.draw_text("]");
+        if row == y + 2 && (15..35).contains(&column) && !self.is_creating_new_level && !self.is_editing_par {
+            self.on_key_pressed(game_state, Key::E);
         }
 
-        console.set_color(Color::Yellow, Color::Default);
-        console.set_cursor_pos(1, current_selected_music_index + 4);
-        console.draw_text("X");
+        let is_creating_new_level_orig = self.is_creating_new_level;
+        self.level_editor_list.on_mouse_pressed(&mut self.is_creating_new_level, game_state, column, row);
+        if is_creating_new_level_orig != self.is_creating_new_level && self.is_creating_new_level {
+            self.level_preview = false;
+        }
     }
 
-    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
-        let current_background_music_id = game_state.current_background_music_id();
-        let mut current_selected_music_index = current_background_music_id.
-                map(|id| id.id()).
-                unwrap_or(0);
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        if self.is_deleting_level {
+            self.is_deleting_level = false;
 
-        if key == Key::UP && current_selected_music_index > 0 {
-            game_state.play_sound_effect_ui_select();
+            if selection == DialogSelection::Yes {
+                let index = self.level_editor_list.cursor_index() - 1;
+                if let Err(err) = trash::move_level_to_trash(game_state.editor_state.get_current_level_pack().unwrap(), index) {
+                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot delete: {}", err)));
+                }else {
+                    let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+                    level_pack.remove_level(index);
+                    level_pack.add_changelog_entry(format!("Level {} removed", index + 1));
 
-            current_selected_music_index -= 1;
-        }else if key == Key::DOWN && current_selected_music_index < audio::BACKGROUND_MUSIC_TRACKS.tracks().len() {
-            game_state.play_sound_effect_ui_select();
+                    if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+                        game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                    }
+                }
+            }
 
-            current_selected_music_index += 1;
-        }
+            self.update_list_elements(game_state);
+            //Cursor index will always be inbound after level pack deletion because of the Create Level Entry
+        }else if self.is_batch_deleting_levels {
+            self.is_batch_deleting_levels = false;
 
-        if current_selected_music_index == 0 {
-            game_state.stop_background_music();
-        }else {
-            game_state.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(
-                audio::BACKGROUND_MUSIC_TRACKS.check_id(current_selected_music_index).unwrap(),
-            ));
-        }
+            if selection == DialogSelection::Yes {
+                self.apply_batch_operation(game_state, None);
+            }
+        }else if self.is_batch_moving_levels {
+            self.is_batch_moving_levels = false;
 
-        if key == Key::ENTER || key == Key::SPACE {
-            game_state.editor_state.get_current_level_pack_mut().unwrap().set_background_music_id(current_background_music_id);
+            if selection == DialogSelection::Yes {
+                let target_index = self.level_editor_list.cursor_index() - 1;
+                self.apply_batch_operation(game_state, Some(target_index));
+            }
+        }else if self.is_resolving_external_change {
+            self.is_resolving_external_change = false;
 
-            if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
+            if selection == DialogSelection::Yes {
+                self.reload_level_pack(game_state);
+            }else if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().force_save_editor_level_pack() {
                 game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
             }
         }
-
-        if key == Key::ENTER || key == Key::SPACE || key == Key::ESC {
-            game_state.play_sound_effect_ui_select();
-
-            game_state.set_screen(ScreenId::SelectLevelPackEditor);
-        }
     }
 
-    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
-        if row == 1 && column < 5 {
-            self.on_key_pressed(game_state, Key::ENTER);
-        }else if row == 2 && column < 3 {
-            self.on_key_pressed(game_state, Key::ESC);
-        }
-
-        if row < 4 {
-            return;
-        }
-
-        let background_music_selection_index = row - 4;
-        if background_music_selection_index > audio::BACKGROUND_MUSIC_TRACKS.tracks().len() {
-            return;
-        }
-
-        if background_music_selection_index == 0 {
-            game_state.play_sound_effect_ui_select();
-
-            game_state.stop_background_music();
-        }else {
-            game_state.play_sound_effect_ui_select();
+    fn update(&mut self, game_state: &mut GameState) {
+        self.external_change_check_ticks += 1;
+        if self.external_change_check_ticks >= Self::EXTERNAL_CHANGE_CHECK_INTERVAL {
+            self.external_change_check_ticks = 0;
 
-            game_state.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(
-                audio::BACKGROUND_MUSIC_TRACKS.check_id(background_music_selection_index).unwrap(),
-            ));
+            self.check_external_changes(game_state);
         }
     }
-}
 
-pub struct ScreenLevelPackEditor {
-    level_editor_list: UIList<bool>,
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.update_list_elements(game_state);
 
-    level_preview: bool,
-    is_creating_new_level: bool,
-    is_editing_height: bool,
-    is_deleting_level: bool,
-    new_level_width_str: String,
-    new_level_height_str: String,
+        self.level_editor_list.set_cursor_index(game_state.editor_state.get_level_index() + 1);
 
-    level_clipboard: Option<LevelWithStats>,
-}
+        self.stop_editing_par();
+        self.stop_editing_localized_name();
+        self.stop_editing_change_summary();
+        self.stop_editing_required_level_pack();
 
-impl ScreenLevelPackEditor {
-    pub fn new() -> Self {
-        Self {
-            level_editor_list: UIList::new(
-                Rect::new(0, 1, Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT - 1),
-                vec![
-                    UIListElement::new("<<", Color::White, Color::LightBlue),
-                    //[Level Editor Entries]
-                ],
-                Box::new(|is_creating_new_level: &mut bool, game_state: &mut GameState, cursor_index: usize| {
-                    if cursor_index == 0 {
-                        game_state.play_sound_effect_ui_select();
-                        game_state.set_screen(ScreenId::SelectLevelPackEditor);
+        self.editing_story = None;
+        self.story_lines = Vec::new();
 
-                        return;
-                    }
+        self.is_multi_select_mode = false;
+        self.marked_level_indices.clear();
+        self.batch_operation_undo_data = None;
 
-                    let level_index = cursor_index - 1;
+        self.problem_report = None;
 
-                    if level_index == game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-                        //Level Editor entry
-                        if game_state.editor_state.get_current_level_pack().unwrap().level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK {
-                            game_state.open_dialog(Dialog::new_ok_error(format!(
-                                "Cannot create level (Max level count ({}) reached)",
-                                LevelPack::MAX_LEVEL_COUNT_PER_PACK,
-                            )));
-                        }else {
-                            game_state.play_sound_effect_ui_select();
+        if let Some(background_music_id) = game_state.editor_state.get_current_level_pack().as_ref().unwrap().background_music_id() {
+            game_state.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id));
+        }else {
+            game_state.stop_background_music();
+        }
 
-                            *is_creating_new_level = true;
-                        }
-                    }else {
-                        game_state.play_sound_effect_ui_select();
+        self.level_preview = false;
 
-                        game_state.editor_state.set_level_index(level_index);
+        self.is_resolving_external_change = false;
+        self.external_change_check_ticks = 0;
+        self.check_external_changes(game_state);
+    }
+}
 
-                        //Set selected level
-                        game_state.set_screen(ScreenId::LevelEditor);
-                    }
-                }),
-            ),
+/// The state of the level which is simulated by the hint preview of the level editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HintPreviewState {
+    Playing,
+    Completed,
+}
 
-            level_preview: false,
-            is_creating_new_level: Default::default(),
-            is_editing_height: Default::default(),
-            is_deleting_level: Default::default(),
-            new_level_width_str: String::new(),
-            new_level_height_str: String::new(),
+/// The player positions of the last validation run which is replayed as a ghost during playtests in the level editor
+#[derive(Debug, Clone)]
+struct ValidationGhost {
+    //The first position is the start position of the player
+    positions: Vec<(usize, usize)>,
+    //The index of the first move which is invalid in the edited level
+    broken_at_move: Option<usize>,
+}
 
-            level_clipboard: None,
-        }
-    }
+impl ValidationGhost {
+    fn new(level: &Level, moves: &[Direction]) -> Option<Self> {
+        let mut playing_level = PlayingLevel::new(level, 4).ok()?;
+
+        //Ice might move the player in a loop if wrap around is enabled
+        let max_animation_step_count = level.tiles().len() * 2;
+
+        let mut positions = vec![playing_level.current_playing_level().1];
+        let mut broken_at_move = None;
+        for (i, direction) in moves.iter().copied().enumerate() {
+            let mut move_result = playing_level.move_player(direction);
+            let mut animation_step_count = 0;
+            while playing_level.is_playing_animation() && animation_step_count < max_animation_step_count {
+                move_result = playing_level.continue_animation();
+                animation_step_count += 1;
+            }
 
-    fn update_list_elements(&mut self, game_state: &GameState) {
-        let elements = self.level_editor_list.elements_mut();
+            if playing_level.is_playing_animation() || move_result.is_invalid() {
+                broken_at_move = Some(i);
 
-        //Remove all level editor entries and create new level entry
-        elements.drain(1..);
+                break;
+            }
 
-        let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
-        for (i, level) in level_pack.levels().iter().enumerate() {
-            elements.push(UIListElement::new(
-                utils::number_to_string_leading_ascii(2, i as u32 + 1, false),
-                Color::Black,
-                if level.best_moves().is_some() {
-                    Color::Green
-                }else {
-                    Color::Yellow
-                },
-            ));
+            positions.push(playing_level.current_playing_level().1);
         }
 
-        let has_max_level_count = level_pack.level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK;
-        elements.push(UIListElement::new(
-            " +",
-            Color::White,
-            if has_max_level_count {
-                Color::LightRed
-            }else {
-                Color::LightBlue
-            },
-        ));
+        Some(Self {
+            positions,
+            broken_at_move,
+        })
     }
 
-    fn draw_overview(&self, game_state: &GameState, console: &Console) {
-        console.reset_color();
-        console.set_underline(true);
-        console.draw_text(format!("Edit a level (Level pack \"{}\"):", game_state.editor_state.get_current_level_pack().unwrap().id()));
-        console.set_underline(false);
+    /// Returns the position of the ghost after the provided count of moves (The ghost stays at its last position)
+    fn position(&self, move_index: usize) -> (usize, usize) {
+        self.positions[move_index.min(self.positions.len() - 1)]
+    }
+}
 
-        self.level_editor_list.draw(console);
+pub struct ScreenLevelEditor {
+    level: UndoHistory<Level>,
+    is_vertical_input: bool,
+    is_reverse_input: bool,
+    should_exit_after_save: bool,
+    last_saved_history_index: usize,
+    continue_flag: bool,
+    validation_result_history_index: usize,
+    //TODO best time
+    validation_best_moves: Option<u32>,
+    //Moves of the last validation run, they are kept if the validation is invalidated by an edit
+    validation_moves: Option<Vec<Direction>>,
+    //True if the validation was found by the solver before a Steam Workshop upload
+    is_solver_validated: bool,
+    show_validation_ghost: bool,
+    validation_ghost: Option<ValidationGhost>,
+    animation_first_frame: bool,
+    is_push_move: bool,
+    playing_level: Option<PlayingLevel>,
+    //Only shown while playing
+    pause_menu: Option<PauseMenu>,
+    cursor_pos: (usize, usize),
+    last_tile_input: Option<Tile>,
+    selection_start: Option<(usize, usize)>,
 
-        let has_max_level_count = game_state.editor_state.get_current_level_pack().unwrap().level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK;
+    show_floor: bool,
+    show_changes: bool,
+    //The cursor position is shown in the header instead of the tile counts
+    show_cursor_pos: bool,
 
-        let entry_count = self.level_editor_list.elements().len();
+    editor_notes: Vec<String>,
+    todo_markers: Vec<(usize, usize)>,
+    is_editing_notes: bool,
 
-        //Draw border for best time and best moves
-        let y = 4 + ((entry_count - 1)/24)*2;
+    is_selecting_undo_branch: bool,
+    undo_branch_cursor_index: usize,
 
-        console.set_cursor_pos(0, y);
-        console.set_color(Color::Cyan, Color::Default);
-        console.draw_text(".------------------------------------------------------------------------.");
-        for i in 1..4 {
-            console.set_cursor_pos(0, y + i);
-            console.draw_text("|                                                                        |");
-        }
-        console.set_cursor_pos(0, y + 4);
-        console.draw_text("\'------------------------------------------------------------------------\'");
-        console.reset_color();
+    hint_preview: Option<HintPreviewState>,
 
-        let cursor_index = self.level_editor_list.cursor_index();
-        if self.is_creating_new_level {
-            console.set_cursor_pos(1, y + 1);
-            console.draw_text("Enter width and height for new level:");
+    is_editing_goto_level: bool,
+    goto_level_str: String,
+    //The level which is edited next after the save dialog of the current level was closed
+    pending_goto_level_index: Option<usize>,
 
-            console.set_color(if self.is_editing_height {
-                Color::LightBlue
-            }else {
-                Color::Cyan
-            }, Color::Default);
-            console.set_cursor_pos(1, y + 2);
-            console.draw_text(format!("Width: {}", &self.new_level_width_str));
+    //The game exits after the save dialog was closed (e.g. the window was closed)
+    should_exit_game_after_save: bool,
 
-            console.set_color(if self.is_editing_height {
-                Color::Cyan
-            }else {
-                Color::LightBlue
-            }, Color::Default);
-            console.set_cursor_pos(14, y + 2);
-            console.draw_text(format!("Height: {}", &self.new_level_height_str));
-        }else if cursor_index == 0 {
-            console.reset_color();
-            console.set_cursor_pos(35, y + 2);
-            console.draw_text("Back");
-        }else {
-            let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+    //The level which was written to the recovery file last
+    #[cfg(feature = "cli")]
+    recovery_level: Option<Level>,
+    #[cfg(feature = "cli")]
+    pending_editor_recovery: Option<EditorRecovery>,
 
-            if cursor_index - 1 == level_pack.level_count() {
-                //Level Editor entry
-                if has_max_level_count {
-                    let error_msg = format!(
-                        "Max level count ({}) reached",
-                        LevelPack::MAX_LEVEL_COUNT_PER_PACK,
-                    );
+    is_confirming_locked_stats_edit: bool,
+}
 
-                    let x_offset = ((Game::CONSOLE_MIN_WIDTH - error_msg.len()) as f64 * 0.5) as usize;
-                    console.set_cursor_pos(x_offset, y + 2);
-                    console.set_color(Color::LightRed, Color::Default);
-                    console.draw_text(error_msg);
-                }else {
-                    console.set_cursor_pos(30, y + 2);
-                    console.draw_text("Create a level");
-                }
-            }else {
-                let level = level_pack.levels().get(cursor_index - 1).unwrap();
+impl ScreenLevelEditor {
+    pub const UNDO_HISTORY_SIZE: usize = 1000;
+    pub const UNDO_HISTORY_SIZE_PLAYING: usize = 10000;
+    pub const UNDO_BRANCH_COUNT: usize = 12;
 
-                //Draw best time and best moves
-                console.set_cursor_pos(1, y + 1);
-                console.draw_text("Selected level: ");
-                console.draw_text(format!("{:03}", cursor_index));
+    const MAX_GOTO_LEVEL_LEN: usize = 3;
 
-                if level_pack.thumbnail_level_index().is_some_and(|index| index == cursor_index - 1) {
-                    console.draw_text(" [Thumbnail]");
+    const EDIT_AND_INVALIDATE_OPTION: &'static DialogOption = &DialogOption::new("[E]dit and invalidate", DialogSelection::Yes, &[Key::E]);
 
-                    console.reset_color();
-                    console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 38, y + 2);
-                    console.draw_text("Press ");
+    const REGION_LEVEL: HitRegionId = HitRegionId(0);
+    const REGION_PAUSE_MENU: HitRegionId = HitRegionId(1);
 
-                    console.draw_key_input_text("t");
+    pub fn new() -> Self {
+        Self {
+            level: UndoHistory::new_branching(Self::UNDO_HISTORY_SIZE, Self::UNDO_BRANCH_COUNT, Level::new(1, 1)),
+            is_vertical_input: Default::default(),
+            is_reverse_input: Default::default(),
+            should_exit_after_save: false,
+            last_saved_history_index: 0,
+            continue_flag: false,
+            validation_result_history_index: 0,
+            //TODO best time
+            validation_best_moves: None,
+            validation_moves: None,
+            is_solver_validated: false,
+            show_validation_ghost: false,
+            validation_ghost: None,
+            animation_first_frame: false,
+            is_push_move: false,
+            playing_level: Default::default(),
+            pause_menu: None,
+            cursor_pos: Default::default(),
+            last_tile_input: None,
+            selection_start: None,
 
-                    console.reset_color();
-                    console.draw_text(" to unset level pack thumbnail");
-                }else {
-                    console.reset_color();
-                    console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 36, y + 2);
-                    console.draw_text("Press ");
+            show_floor: false,
+            show_changes: false,
+            show_cursor_pos: false,
 
-                    console.draw_key_input_text("t");
+            editor_notes: Vec::new(),
+            todo_markers: Vec::new(),
+            is_editing_notes: false,
 
-                    console.reset_color();
-                    console.draw_text(" to set level pack thumbnail");
-                }
+            is_selecting_undo_branch: false,
+            undo_branch_cursor_index: 0,
 
-                console.set_cursor_pos(1, y + 2);
-                console.draw_text(format!(
-                    "Size: {} x {}",
-                    level.level().width(),
-                    level.level().height(),
-                ));
+            hint_preview: None,
 
-                console.set_cursor_pos(1, y + 3);
-                console.draw_text("Validation: ");
-                {
-                    if let Some(best_moves) = level.best_moves() {
-                        console.set_color(Color::Green, Color::Default);
-                        console.draw_text(format!("Best moves: {best_moves}"));
-                    }else {
-                        console.set_color(Color::Red, Color::Default);
-                        console.draw_text("You need to complete this level to validate it");
-                    }
-                }
+            is_editing_goto_level: false,
+            goto_level_str: String::new(),
+            pending_goto_level_index: None,
 
-                console.reset_color();
-                console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 26, y + 1);
-                console.draw_text("Press ");
+            should_exit_game_after_save: false,
 
-                console.draw_key_input_text("p");
+            #[cfg(feature = "cli")]
+            recovery_level: None,
+            #[cfg(feature = "cli")]
+            pending_editor_recovery: None,
 
-                console.reset_color();
-                console.draw_text(" for level preview");
-            }
+            is_confirming_locked_stats_edit: false,
         }
     }
 
-    fn draw_level_preview(&self, game_state: &GameState, console: &Console) {
-        let cursor_index = self.level_editor_list.cursor_index();
-
-        if cursor_index == 1 {
-            console.draw_key_input_text("<");
-
-            console.reset_color();
-            console.draw_text(" Back");
-        }else if cursor_index > 1 {
-            console.draw_key_input_text("<");
-
-            console.reset_color();
-            console.draw_text(format!(" Level {:03}", cursor_index - 1));
-        }
+    fn mark_as_unsaved(&mut self) {
+        self.last_saved_history_index = Self::UNDO_HISTORY_SIZE + 1;
+    }
 
-        if game_state.editor_state.get_current_level_pack().unwrap().level_count() > 0 &&
-                cursor_index < game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-            console.reset_color();
-            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 11, 0);
-            console.draw_text(format!("Level {:03} ", cursor_index + 1));
+    /// Starts playing the current level from the start position
+    fn start_playing(&mut self, game_state: &mut GameState) {
+        self.animation_first_frame = false;
+        self.continue_flag = false;
 
-            console.draw_key_input_text(">");
-        }
+        match PlayingLevel::new(self.level.current(), Self::UNDO_HISTORY_SIZE_PLAYING) {
+            Ok(playing_level) => {
+                self.update_validation_ghost();
 
-        if game_state.editor_state.get_current_level_pack().unwrap().level_count() > 0 &&
-                cursor_index == game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-            console.reset_color();
-            console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 16, 0);
-            console.draw_text("Create a level ");
+                self.playing_level = Some(playing_level);
+            },
 
-            console.draw_key_input_text(">");
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(err.to_string()));
+            },
         }
+    }
 
-        if cursor_index == 0 {
-            let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
-            let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
+    fn open_exit_dialog(&mut self, game_state: &mut GameState) {
+        game_state.open_dialog(Dialog::new_yes_cancel_no("Exiting (Save changes and level validation state?)"));
+        self.should_exit_after_save = true;
+    }
 
-            console.set_cursor_pos(x, y);
-            console.set_color(Color::Cyan, Color::Default);
-            console.draw_text(".--------------------------------------.");
-            for i in 1..4 {
-                console.set_cursor_pos(x, y + i);
-                console.draw_text("|                                      |");
-            }
-            console.set_cursor_pos(x, y + 4);
-            console.draw_text("\'--------------------------------------\'");
+    fn on_pause_menu_entry_selected(&mut self, game_state: &mut GameState, entry: PauseMenuEntry) {
+        match entry {
+            PauseMenuEntry::Resume => {
+                game_state.play_sound_effect_ui_select();
 
-            console.reset_color();
-            console.set_cursor_pos(35, y + 2);
-            console.draw_text("Back");
-        }else if cursor_index - 1 == game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-            let has_max_level_count = game_state.editor_state.get_current_level_pack().unwrap().level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK;
+                self.pause_menu = None;
+            },
 
-            console.reset_color();
-            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 11) as f64 * 0.5) as usize, 0);
-            console.draw_text("Preview (");
+            PauseMenuEntry::Restart => {
+                self.pause_menu = None;
 
-            console.draw_key_input_text("p");
+                game_state.play_sound_effect(audio::LEVEL_RESET);
+                self.start_playing(game_state);
+            },
 
-            console.reset_color();
-            console.draw_text(")");
+            //The settings are not available in the level editor, because the unsaved changes would be lost
+            PauseMenuEntry::Settings => {},
 
-            let x = ((Game::CONSOLE_MIN_WIDTH - 40) as f64 * 0.5) as usize;
-            let y = ((Game::CONSOLE_MIN_HEIGHT - 5) as f64 * 0.5) as usize;
+            PauseMenuEntry::Help => {
+                game_state.open_help_page();
+            },
 
-            console.set_cursor_pos(x, y);
-            console.set_color(Color::Cyan, Color::Default);
-            console.draw_text(".--------------------------------------.");
-            for i in 1..4 {
-                console.set_cursor_pos(x, y + i);
-                console.draw_text("|                                      |");
-            }
-            console.set_cursor_pos(x, y + 4);
-            console.draw_text("\'--------------------------------------\'");
+            PauseMenuEntry::QuitToSelection => {
+                game_state.play_sound_effect_ui_select();
 
-            if has_max_level_count {
-                let error_msg = format!(
-                    "Max level count ({}) reached",
-                    LevelPack::MAX_LEVEL_COUNT_PER_PACK,
-                );
+                self.pause_menu = None;
+                self.open_exit_dialog(game_state);
+            },
+        }
+    }
 
-                let x_offset = ((Game::CONSOLE_MIN_WIDTH - error_msg.len()) as f64 * 0.5) as usize;
-                console.set_cursor_pos(x_offset, y + 2);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text(error_msg);
-            }else {
-                console.set_cursor_pos(30, y + 2);
-                console.reset_color();
-                console.draw_text("Create a level");
-            }
-        }else {
-            console.reset_color();
-            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 23) as f64 * 0.5) as usize, 0);
-            console.draw_text("Preview (");
+    fn update_validation_after_edit(&mut self, old_history_len: usize, old_history_index: usize, is_redo: bool) {
+        let new_history_len = self.level.len();
+        let new_history_index = self.level.current_index();
 
-            console.draw_key_input_text("p");
+        //Undo: New history len is same and new index is smaller
+        //Redo: New history len is same and new index is larger
+        //Commit change without previous undo: New history len is larger and new index is larger
+        //Commit change with previous undo(s): New history len is same or smaller and new index is larger
 
-            console.reset_color();
-            console.draw_text(format!(") [Level {:03}]", cursor_index));
+        //Level is no longer valid if changed (one or more changes), then validated, then one or more undo(s), and then change again
+        if self.validation_result_history_index >= new_history_index && !is_redo &&
+                new_history_len <= old_history_len && old_history_index + 1 == new_history_index {
+            self.validation_best_moves = None;
+        }
+    }
 
-            let level = game_state.editor_state.get_current_level_pack().unwrap().levels()[cursor_index - 1].level();
+    /// Returns the selected region (x, y, width, height) or None if no selection was started
+    fn selection(&self) -> Option<(usize, usize, usize, usize)> {
+        let level = self.level.current();
+        let (x, y) = self.selection_start.
+                filter(|&(x, y)| x < level.width() && y < level.height())?;
 
-            let x_offset = ((Game::CONSOLE_MIN_WIDTH - level.width()) as f64 * 0.5) as usize;
-            let y_offset = 1;
+        let (min_x, max_x) = (x.min(self.cursor_pos.0), x.max(self.cursor_pos.0));
+        let (min_y, max_y) = (y.min(self.cursor_pos.1), y.max(self.cursor_pos.1));
 
-            level.draw(console, x_offset, y_offset, game_state.is_player_background(), None);
-        }
+        Some((min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
     }
-}
 
-impl Screen for ScreenLevelPackEditor {
-    fn draw(&self, game_state: &GameState, console: &Console) {
-        if self.level_preview {
-            self.draw_level_preview(game_state, console);
+    /// Pastes a level in the XSB format or in the level pack format
+    ///
+    /// Levels which fit into the current level are pasted at the cursor, larger levels replace the current level
+    fn paste_level_text(&mut self, game_state: &mut GameState, text: &str) {
+        let pasted_level = if text.trim_start().starts_with("w: ") {
+            Level::from_str(text.trim())
         }else {
-            self.draw_overview(game_state, console);
-        }
-    }
+            Level::from_xsb(text)
+        };
+        let mut pasted_level = match pasted_level {
+            Ok(pasted_level) => pasted_level,
 
-    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
-        if self.is_creating_new_level {
-            match key {
-                key if key.is_ascii() && key.is_numeric() => {
-                    if self.is_editing_height {
-                        if self.new_level_height_str.len() >= 2 {
-                            return;
-                        }
+            Err(err) => {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot paste level: {}", err)));
 
-                        let _ = write!(self.new_level_height_str, "{}", key.to_ascii().unwrap() as char);
-                    }else {
-                        if self.new_level_width_str.len() >= 2 {
-                            return;
-                        }
+                return;
+            },
+        };
 
-                        let _ = write!(self.new_level_width_str, "{}", key.to_ascii().unwrap() as char);
-                    }
-                },
-                Key::DELETE => {
-                    if self.is_editing_height {
-                        self.new_level_height_str.pop();
-                    }else {
-                        self.new_level_width_str.pop();
-                    }
-                },
+        let level = self.level.current();
+        let new_level = if pasted_level.width() <= level.width() && pasted_level.height() <= level.height() {
+            let (x_offset, y_offset) = self.cursor_pos;
+            if x_offset + pasted_level.width() > level.width() || y_offset + pasted_level.height() > level.height() {
+                game_state.open_dialog(Dialog::new_ok_error(format!(
+                    "The pasted tiles ({}x{}) do not fit at the cursor position",
+                    pasted_level.width(), pasted_level.height(),
+                )));
+
+                return;
+            }
+
+            let mut new_level = level.clone();
+            for y in 0..pasted_level.height() {
+                for x in 0..pasted_level.width() {
+                    new_level.set_tile(x_offset + x, y_offset + y, pasted_level.get_tile(x, y).unwrap());
+                }
+            }
 
-                Key::TAB => {
-                    self.is_editing_height = !self.is_editing_height;
-                },
+            new_level
+        }else {
+            if pasted_level.width() < 3 || pasted_level.width() > Game::LEVEL_MAX_WIDTH {
+                game_state.open_dialog(Dialog::new_ok_error(format!(
+                    "Level width limit reached (min: {}, max: {})",
+                    3, Game::LEVEL_MAX_WIDTH,
+                )));
 
-                Key::ENTER => {
-                    if !(1..=2).contains(&self.new_level_width_str.len()) {
-                        game_state.open_dialog(Dialog::new_ok_error(format!("Width must be >= 3 and <= {}!", Game::LEVEL_MAX_WIDTH)));
+                return;
+            }
 
-                        return;
-                    }
+            if pasted_level.height() < 3 || pasted_level.height() > Game::LEVEL_MAX_HEIGHT {
+                game_state.open_dialog(Dialog::new_ok_error(format!(
+                    "Level height limit reached (min: {}, max: {})",
+                    3, Game::LEVEL_MAX_HEIGHT,
+                )));
 
-                    let Ok(width) = usize::from_str(&self.new_level_width_str) else {
-                        game_state.open_dialog(Dialog::new_ok_error("Width must be a number"));
+                return;
+            }
 
-                        return;
-                    };
+            pasted_level.set_wrap_around(level.wrap_around());
+            pasted_level.set_theme(level.theme());
 
-                    if !(3..=Game::LEVEL_MAX_WIDTH).contains(&width) {
-                        game_state.open_dialog(Dialog::new_ok_error(format!("Width must be >= 3 and <= {}!", Game::LEVEL_MAX_WIDTH)));
+            pasted_level
+        };
 
-                        return;
-                    }
+        if new_level == *self.level.current() {
+            return;
+        }
 
-                    if self.new_level_height_str.is_empty() && !self.is_editing_height {
-                        self.is_editing_height = true;
+        let old_history_len = self.level.len();
+        let old_history_index = self.level.current_index();
 
-                        return;
-                    }
+        self.cursor_pos.0 = self.cursor_pos.0.min(new_level.width() - 1);
+        self.cursor_pos.1 = self.cursor_pos.1.min(new_level.height() - 1);
+        self.level.commit_change(new_level);
 
-                    if !(1..=2).contains(&self.new_level_height_str.len()) {
-                        game_state.open_dialog(Dialog::new_ok_error(format!("Height must be >= 3 and <= {}!", Game::LEVEL_MAX_HEIGHT)));
+        self.update_validation_after_edit(old_history_len, old_history_index, false);
 
-                        return;
-                    }
+        game_state.show_toast("Level pasted");
+    }
 
-                    let Ok(height) = usize::from_str(&self.new_level_height_str) else {
-                        game_state.open_dialog(Dialog::new_ok_error("Height must be a number"));
+    fn on_key_pressed_notes(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.to_ascii().is_some_and(|c| c.is_ascii_graphic() || c == b' ') => {
+                let Some(line) = self.editor_notes.last_mut() else {
+                    return;
+                };
 
-                        return;
-                    };
+                if line.len() >= LevelWithStats::MAX_EDITOR_NOTE_LINE_LEN {
+                    return;
+                }
 
-                    if !(3..=Game::LEVEL_MAX_HEIGHT).contains(&height) {
-                        game_state.open_dialog(Dialog::new_ok_error(format!("Height must be >= 3 and <= {}!", Game::LEVEL_MAX_HEIGHT)));
+                line.push(key.to_ascii().unwrap() as char);
+                self.mark_as_unsaved();
+            },
 
+            Key::DELETE => {
+                let Some(line) = self.editor_notes.last_mut() else {
+                    return;
+                };
+
+                if line.pop().is_none() {
+                    if self.editor_notes.len() == 1 {
                         return;
                     }
 
-                    game_state.play_sound_effect_ui_select();
+                    self.editor_notes.pop();
+                }
+                self.mark_as_unsaved();
+            },
 
-                    game_state.editor_state.get_current_level_pack_mut().unwrap().add_level(Level::new(width, height));
+            Key::ENTER => {
+                if self.editor_notes.len() >= LevelWithStats::MAX_EDITOR_NOTE_LINE_COUNT {
+                    return;
+                }
 
-                    self.is_creating_new_level = false;
-                    self.is_editing_height = false;
-                    self.new_level_width_str = String::new();
-                    self.new_level_height_str = String::new();
+                self.editor_notes.push(String::new());
+                self.mark_as_unsaved();
+            },
 
-                    game_state.editor_state.set_level_index(self.level_editor_list.cursor_index() - 1);
-                    game_state.set_screen(ScreenId::LevelEditor);
-                },
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
 
-                Key::ESC => {
-                    game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+                //Trailing empty lines are removed
+                while self.editor_notes.last().is_some_and(String::is_empty) {
+                    self.editor_notes.pop();
+                }
 
-                    self.is_creating_new_level = false;
-                    self.is_editing_height = false;
-                    self.new_level_width_str = String::new();
-                    self.new_level_height_str = String::new();
-                },
+                self.is_editing_notes = false;
+            },
 
-                _ => {},
-            }
+            _ => {},
+        }
+    }
 
-            return;
+    /// Fills all console cells of the tile with the text in the current color
+    fn draw_tile_overlay(console: &Console, level: &Level, x_offset: usize, y_offset: usize, (x, y): (usize, usize), text: &str) {
+        let (tile_width, tile_height) = (level.tile_width(), level.tile_height());
+
+        for tile_row in 0..tile_height {
+            console.set_cursor_pos(x_offset + x * tile_width, y_offset + y * tile_height + tile_row);
+            console.draw_text(text.repeat(tile_width));
         }
+    }
 
-        if key == Key::ESC {
-            game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+    /// Draws the counts of players, boxes, goals, keys, and doors in the header, counts which make the level unwinnable are red
+    fn draw_tile_counts(console: &Console, tile_counts: &TileCounts) {
+        let counts = [
+            (format!("P{}", tile_counts.player_count), tile_counts.is_player_count_valid()),
+            (format!("B{:02}", tile_counts.box_count), tile_counts.is_box_count_valid()),
+            (format!("G{:02}", tile_counts.goal_count), tile_counts.is_box_count_valid()),
+            (format!("K{}", tile_counts.key_count), tile_counts.is_key_count_valid()),
+            (format!("D{}", tile_counts.door_count), tile_counts.is_key_count_valid()),
+        ];
+        let len = counts.iter().map(|(text, _)| text.len()).sum::<usize>() + counts.len() - 1;
 
-            if self.level_preview {
-                self.level_preview = false;
-            }else {
-                game_state.set_screen(ScreenId::SelectLevelPackEditor);
+        console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - len) as f64 * 0.5) as usize, 0);
+        for (i, (text, is_valid)) in counts.iter().enumerate() {
+            console.reset_color();
+            if i > 0 {
+                console.draw_text(" ");
             }
 
-            return;
+            if !is_valid {
+                console.set_color(Color::Red, Color::Default);
+            }
+            console.draw_text(text);
         }
+        console.reset_color();
+    }
 
-        if key == Key::P {
-            game_state.play_sound_effect_ui_select();
+    fn draw_notes(&self, console: &Console) {
+        console.set_color(Color::Cyan, Color::Default);
+        console.set_cursor_pos(0, 2);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 3..(4 + LevelWithStats::MAX_EDITOR_NOTE_LINE_COUNT) {
+            console.set_cursor_pos(0, i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, 4 + LevelWithStats::MAX_EDITOR_NOTE_LINE_COUNT);
+        console.draw_text("\'------------------------------------------------------------------------\'");
 
-            self.level_preview = !self.level_preview;
+        console.reset_color();
+        console.set_underline(true);
+        console.set_cursor_pos(2, 3);
+        console.draw_text("Level notes (Not included in exports):");
+        console.set_underline(false);
 
-            return;
+        for (i, line) in self.editor_notes.iter().
+                enumerate() {
+            console.set_cursor_pos(2, 4 + i);
+            console.draw_text(line);
         }
 
-        let cursor_index = self.level_editor_list.cursor_index();
-        if cursor_index > 0 {
-            let selected_level_index = cursor_index - 1;
+        console.set_color(Color::Cyan, Color::Default);
+        console.draw_text("_");
 
-            match key {
-                Key::T => {
-                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-                        game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+        console.reset_color();
+        console.set_cursor_pos(1, 5 + LevelWithStats::MAX_EDITOR_NOTE_LINE_COUNT);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(": New line, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Close");
+    }
 
-                        if game_state.editor_state.get_current_level_pack().unwrap().
-                                thumbnail_level_index().is_some_and(|index| index == selected_level_index) {
-                            game_state.editor_state.get_current_level_pack_mut().unwrap().set_thumbnail_level_index(None);
-                        }else {
-                            game_state.editor_state.get_current_level_pack_mut().unwrap().set_thumbnail_level_index(Some(selected_level_index));
-                        }
+    fn on_key_pressed_goto_level(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.to_ascii().is_some_and(|c| c.is_ascii_digit()) && self.goto_level_str.len() < Self::MAX_GOTO_LEVEL_LEN => {
+                self.goto_level_str.push(key.to_ascii().unwrap() as char);
+            },
 
-                        if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
-                            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
-                        }
+            Key::DELETE => {
+                self.goto_level_str.pop();
+            },
 
-                        self.update_list_elements(game_state);
-                    }
-                },
+            Key::ENTER => {
+                self.is_editing_goto_level = false;
 
-                Key::C => {
-                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-                        game_state.play_sound_effect_ui_select();
+                let level_count = game_state.editor_state.get_current_level_pack().unwrap().level_count();
+                let level_index = match usize::from_str(&self.goto_level_str) {
+                    Ok(level_number) if (1..=level_count).contains(&level_number) => level_number - 1,
 
-                        self.level_clipboard = Some(game_state.editor_state.get_current_level_pack().unwrap().levels()[selected_level_index].clone());
-                    }
-                },
+                    _ => {
+                        game_state.open_dialog(Dialog::new_ok_error(format!(
+                            "Level number must be between 1 and {}",
+                            level_count,
+                        )));
 
-                Key::X => {
-                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-                        game_state.play_sound_effect_ui_select();
+                        return;
+                    },
+                };
 
-                        let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
-                        self.level_clipboard = Some(level_pack.levels_mut().remove(selected_level_index));
-                        level_pack.calculate_stats_sum();
+                if level_index == game_state.editor_state.selected_level_index {
+                    return;
+                }
 
-                        if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
-                            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
-                        }
-                    }
+                if self.last_saved_history_index == self.level.current_index() {
+                    self.goto_level(game_state, level_index);
+                }else {
+                    game_state.open_dialog(Dialog::new_yes_cancel_no(format!(
+                        "Switching to level {} (Save changes and level validation state?)",
+                        level_index + 1,
+                    )));
+                    self.should_exit_after_save = false;
+                    self.pending_goto_level_index = Some(level_index);
+                }
+            },
 
-                    self.update_list_elements(game_state);
-                },
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
 
-                Key::V => {
-                    if let Some(ref level) = self.level_clipboard {
-                        if game_state.editor_state.get_current_level_pack().unwrap().level_count() == LevelPack::MAX_LEVEL_COUNT_PER_PACK {
-                            game_state.open_dialog(Dialog::new_ok_error(format!(
-                                "Cannot paste level (Max level count ({}) reached)",
-                                LevelPack::MAX_LEVEL_COUNT_PER_PACK,
-                            )));
-                        }else {
-                            game_state.play_sound_effect(audio::UI_SELECT_EFFECT);
+                self.is_editing_goto_level = false;
+            },
 
-                            let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
-                            level_pack.levels_mut().insert(selected_level_index, level.clone());
-                            level_pack.calculate_stats_sum();
+            _ => {},
+        }
+    }
 
-                            if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
-                                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
-                            }
-                        }
-                    }else {
-                        game_state.open_dialog(Dialog::new_ok_error("No level in clipboard!\nPlease copy a level by pressing \"C\" or cut a level by pressing \"X\"."));
-                    }
+    /// Starts editing another level of the current level pack, unsaved changes of the current level are discarded
+    fn goto_level(&mut self, game_state: &mut GameState, level_index: usize) {
+        game_state.play_sound_effect_ui_select();
 
-                    self.update_list_elements(game_state);
-                },
+        #[cfg(feature = "cli")]
+        self.clear_editor_recovery(game_state);
 
-                Key::DELETE => {
-                    if selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-                        self.is_deleting_level = true;
+        self.level.clear();
+        game_state.editor_state.selected_level_index = level_index;
 
-                        game_state.open_dialog(Dialog::new_yes_no(format!("Do you really want to delete level {}?", selected_level_index + 1)));
-                    }
-                },
+        self.on_set_screen(game_state);
+    }
 
-                _ => {},
-            }
+    /// Writes unsaved changes to the recovery file, because the terminal can be closed without a save dialog
+    #[cfg(feature = "cli")]
+    fn update_editor_recovery(&mut self, game_state: &mut GameState) {
+        if self.last_saved_history_index == self.level.current_index() || self.recovery_level.as_ref() == Some(self.level.current()) {
+            return;
         }
 
-        let is_creating_new_level_orig = self.is_creating_new_level;
-        self.level_editor_list.on_key_press(&mut self.is_creating_new_level, game_state, key);
-        if is_creating_new_level_orig != self.is_creating_new_level && self.is_creating_new_level {
-            self.level_preview = false;
+        let Some(level_pack) = game_state.editor_state.get_current_level_pack() else {
+            return;
+        };
+
+        //The level is not written again if writing failed to prevent an error dialog in every update
+        self.recovery_level = Some(self.level.current().clone());
+
+        let recovery = EditorRecovery::new(level_pack.id(), game_state.editor_state.selected_level_index, self.level.current().clone());
+        if let Err(err) = editor_recovery::write_editor_recovery(&recovery) {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot write editor recovery file: {}", err)));
         }
     }
 
-    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
-        if self.level_preview {
-            if row == 0 {
-                let center_text_start = ((Game::CONSOLE_MIN_WIDTH - 23) as f64 * 0.5) as usize;
+    /// Removes the recovery file after the changes were saved or discarded
+    #[cfg(feature = "cli")]
+    fn clear_editor_recovery(&mut self, game_state: &mut GameState) {
+        if self.recovery_level.take().is_none() {
+            return;
+        }
 
-                if column < 11 {
-                    self.on_key_pressed(game_state, Key::LEFT);
-                }else if column >= center_text_start && column < center_text_start + 23 {
-                    self.on_key_pressed(game_state, Key::ENTER);
-                }else if column > Game::CONSOLE_MIN_WIDTH - 12 {
-                    self.on_key_pressed(game_state, Key::RIGHT);
-                }
+        if let Err(err) = editor_recovery::remove_editor_recovery() {
+            game_state.open_dialog(Dialog::new_ok_error(format!("Cannot remove editor recovery file: {}", err)));
+        }
+    }
 
-                let selected_level = game_state.editor_state.get_level_index();
-                if game_state.editor_state.get_current_level_pack().unwrap().level_count() > 0 &&
-                        selected_level == game_state.editor_state.get_current_level_pack().unwrap().level_count() - 1 &&
-                        column > Game::CONSOLE_MIN_WIDTH - 17 {
-                    self.on_key_pressed(game_state, Key::RIGHT);
-                }
-            }
+    /// Asks to restore unsaved changes of the current level from a previous session
+    #[cfg(feature = "cli")]
+    fn check_editor_recovery(&mut self, game_state: &mut GameState) {
+        let recovery = match editor_recovery::read_editor_recovery() {
+            Ok(Some(recovery)) => recovery,
+            Ok(None) => return,
 
+            Err(err) => {
+                let _ = editor_recovery::remove_editor_recovery();
+
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot read editor recovery file: {}", err)));
+
+                return;
+            },
+        };
+
+        let level_pack_id = game_state.editor_state.get_current_level_pack().unwrap().id();
+        if recovery.level_pack_id() != level_pack_id || recovery.level_index() != game_state.editor_state.selected_level_index {
             return;
         }
 
-        if row == 0 {
+        if recovery.level() == self.level.current() {
+            let _ = editor_recovery::remove_editor_recovery();
+
             return;
         }
 
-        let element_count = self.level_editor_list.elements().len();
-        let y = 4 + ((element_count - 1)/24)*2;
-        if row == y + 1 && (Game::CONSOLE_MIN_WIDTH - 26..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
-            self.on_key_pressed(game_state, Key::P);
-        }
+        self.pending_editor_recovery = Some(recovery);
 
-        if row == y + 2 && (Game::CONSOLE_MIN_WIDTH - 38..Game::CONSOLE_MIN_WIDTH - 1).contains(&column) {
-            self.on_key_pressed(game_state, Key::T);
-        }
+        game_state.open_dialog(Dialog::new_yes_no(format!(
+            "Unsaved changes of level {} from a previous session were found.\nRestore the changes?",
+            game_state.editor_state.selected_level_index + 1,
+        )));
+    }
 
-        let is_creating_new_level_orig = self.is_creating_new_level;
-        self.level_editor_list.on_mouse_pressed(&mut self.is_creating_new_level, game_state, column, row);
-        if is_creating_new_level_orig != self.is_creating_new_level && self.is_creating_new_level {
-            self.level_preview = false;
+    fn on_key_pressed_undo_branches(&mut self, game_state: &mut GameState, key: Key) {
+        let branch_count = self.level.branches().len();
+
+        let navigator = ListNavigator::new(Self::UNDO_BRANCH_COUNT);
+        if let Some(cursor_index) = navigator.navigate(self.undo_branch_cursor_index, branch_count, key) {
+            self.undo_branch_cursor_index = cursor_index;
+
+            return;
         }
-    }
 
-    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
-        if self.is_deleting_level {
-            self.is_deleting_level = false;
+        match key {
+            Key::ENTER => {
+                game_state.play_sound_effect_ui_select();
+
+                self.is_selecting_undo_branch = false;
 
-            if selection == DialogSelection::Yes {
-                let index = self.level_editor_list.cursor_index() - 1;
-                let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
-                level_pack.levels_mut().remove(index);
-                level_pack.calculate_stats_sum();
+                //Branches are listed newest first
+                let Some(branch_index) = branch_count.checked_sub(self.undo_branch_cursor_index + 1) else {
+                    return;
+                };
+                let fork_index = self.level.branches()[branch_index].fork_index();
 
-                if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
-                    game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
+                let Some(level) = self.level.jump_to_branch(branch_index) else {
+                    return;
+                };
+
+                if self.cursor_pos.0 >= level.width() {
+                    self.cursor_pos.0 = level.width() - 1;
                 }
-            }
 
-            self.update_list_elements(game_state);
-            //Cursor index will always be inbound after level pack deletion because of the Create Level Entry
-        }
-    }
+                if self.cursor_pos.1 >= level.height() {
+                    self.cursor_pos.1 = level.height() - 1;
+                }
 
-    fn on_set_screen(&mut self, game_state: &mut GameState) {
-        self.update_list_elements(game_state);
+                //History entries after the fork index were replaced
+                if self.last_saved_history_index > fork_index {
+                    self.mark_as_unsaved();
+                }
+                if self.validation_result_history_index > fork_index {
+                    self.validation_best_moves = None;
+                }
+            },
 
-        self.level_editor_list.set_cursor_index(game_state.editor_state.get_level_index() + 1);
+            Key::ESC | Key::H => {
+                game_state.play_sound_effect_ui_select();
 
-        if let Some(background_music_id) = game_state.editor_state.get_current_level_pack().as_ref().unwrap().background_music_id() {
-            game_state.set_background_music_loop(audio::BACKGROUND_MUSIC_TRACKS.get_track_by_id(background_music_id));
-        }else {
-            game_state.stop_background_music();
-        }
+                self.is_selecting_undo_branch = false;
+            },
 
-        self.level_preview = false;
+            _ => {},
+        }
     }
-}
 
-pub struct ScreenLevelEditor {
-    level: UndoHistory<Level>,
-    is_vertical_input: bool,
-    is_reverse_input: bool,
-    should_exit_after_save: bool,
-    last_saved_history_index: usize,
-    continue_flag: bool,
-    validation_result_history_index: usize,
-    //TODO best time
-    validation_best_moves: Option<u32>,
-    animation_first_frame: bool,
-    playing_level: Option<PlayingLevel>,
-    cursor_pos: (usize, usize),
+    fn draw_undo_branches(&self, console: &Console) {
+        console.set_color(Color::Cyan, Color::Default);
+        console.set_cursor_pos(0, 2);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 3..(5 + Self::UNDO_BRANCH_COUNT) {
+            console.set_cursor_pos(0, i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, 5 + Self::UNDO_BRANCH_COUNT);
+        console.draw_text("\'------------------------------------------------------------------------\'");
 
-    show_floor: bool,
-}
+        console.reset_color();
+        console.set_underline(true);
+        console.set_cursor_pos(2, 3);
+        console.draw_fmt(format_args!("Undo branches (Current edit: {:03}):", self.level.current_index()));
+        console.set_underline(false);
 
-impl ScreenLevelEditor {
-    pub const UNDO_HISTORY_SIZE: usize = 1000;
-    pub const UNDO_HISTORY_SIZE_PLAYING: usize = 10000;
+        if self.level.branches().is_empty() {
+            console.set_cursor_pos(2, 5);
+            console.draw_text("No undo branches (Edits after an undo create a new branch)");
+        }
 
-    pub fn new() -> Self {
-        Self {
-            level: UndoHistory::new(Self::UNDO_HISTORY_SIZE, Level::new(1, 1)),
-            is_vertical_input: Default::default(),
-            is_reverse_input: Default::default(),
-            should_exit_after_save: false,
-            last_saved_history_index: 0,
-            continue_flag: false,
-            validation_result_history_index: 0,
-            //TODO best time
-            validation_best_moves: None,
-            animation_first_frame: false,
-            playing_level: Default::default(),
-            cursor_pos: Default::default(),
+        for (i, branch) in self.level.branches().iter().
+                rev().
+                enumerate() {
+            console.set_cursor_pos(2, 5 + i);
+            if i == self.undo_branch_cursor_index {
+                console.set_color(Color::Black, Color::Cyan);
+            }else {
+                console.reset_color();
+            }
 
-            show_floor: false,
+            let level = branch.last();
+            console.draw_fmt(format_args!(
+                "Branch {:02}: {:03} edit(s) after edit {:03} (Size: {:02}x{:02})",
+                i + 1, branch.len(), branch.fork_index(), level.width(), level.height(),
+            ));
         }
+
+        console.reset_color();
+        console.set_cursor_pos(1, 6 + Self::UNDO_BRANCH_COUNT);
+        console.draw_key_input_text("ENTER");
+        console.reset_color();
+        console.draw_text(": Restore branch, ");
+        console.draw_key_input_text("ESC");
+        console.reset_color();
+        console.draw_text(": Close");
     }
 
     fn on_key_pressed_playing(&mut self, game_state: &mut GameState, key: Key) {
@@ -3480,6 +9702,20 @@ impl ScreenLevelEditor {
                 return;
             }
 
+            if key == Key::G {
+                if self.validation_moves.is_none() {
+                    game_state.open_dialog(Dialog::new_ok_error("The level has no validation run which could be shown as ghost"));
+
+                    return;
+                }
+
+                game_state.play_sound_effect_ui_select();
+                self.show_validation_ghost = !self.show_validation_ghost;
+                self.update_validation_ghost();
+
+                return;
+            }
+
             if matches!(key, Key::U | Key::Z | Key::Y) {
                 let is_redo = key == Key::Y;
 
@@ -3504,6 +9740,8 @@ impl ScreenLevelEditor {
             };
 
             if let Some(direction) = direction {
+                self.is_push_move = playing_level.is_push(direction);
+
                 let move_result = playing_level.move_player(direction);
                 if move_result.is_animation() {
                     self.animation_first_frame = true;
@@ -3513,6 +9751,63 @@ impl ScreenLevelEditor {
         }
     }
 
+    /// Replays the moves of the last validation run in the current level (The ghost is removed if it is hidden)
+    fn update_validation_ghost(&mut self) {
+        self.validation_ghost = if self.show_validation_ghost && let Some(validation_moves) = &self.validation_moves {
+            ValidationGhost::new(self.level.current(), validation_moves)
+        }else {
+            None
+        };
+    }
+
+    /// Returns the hints of the level in the language in which they are shown to players
+    fn level_hints<'a>(&self, game_state: &'a GameState) -> &'a [LevelHint] {
+        game_state.editor_state.get_current_level_pack().
+                and_then(|level_pack| level_pack.levels().get(game_state.editor_state.selected_level_index)).
+                map(|level| level.localized_hints(game_state.settings.language)).
+                unwrap_or_default()
+    }
+
+    fn draw_hint_preview(&self, game_state: &GameState, console: &Console, hint_preview: HintPreviewState) {
+        let is_last_level = game_state.editor_state.get_current_level_pack().
+                is_some_and(|level_pack| game_state.editor_state.selected_level_index + 1 == level_pack.level_count());
+
+        for hint in self.level_hints(game_state) {
+            //Tiles are always encountered while playing, therefore all tile hints are shown
+            let is_triggered = match hint.trigger() {
+                LevelHintTrigger::Always | LevelHintTrigger::TileEncountered(_) => true,
+                LevelHintTrigger::Playing => hint_preview == HintPreviewState::Playing,
+                LevelHintTrigger::Completed => hint_preview == HintPreviewState::Completed && !is_last_level,
+                LevelHintTrigger::PackCompleted => hint_preview == HintPreviewState::Completed && is_last_level,
+            };
+
+            if is_triggered {
+                draw_level_hint(console, hint);
+            }
+        }
+    }
+
+    fn on_key_pressed_hint_preview(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            Key::QUESTION_MARK => {
+                game_state.play_sound_effect_ui_select();
+
+                self.hint_preview = match self.hint_preview {
+                    Some(HintPreviewState::Playing) => Some(HintPreviewState::Completed),
+                    _ => None,
+                };
+            },
+
+            Key::ESC => {
+                game_state.play_sound_effect_ui_select();
+
+                self.hint_preview = None;
+            },
+
+            _ => {},
+        }
+    }
+
     fn on_key_pressed_editing(&mut self, game_state: &mut GameState, key: Key) {
         match key {
             Key::LEFT => {
@@ -3559,6 +9854,8 @@ impl ScreenLevelEditor {
 
                     let level_orig = self.level.current().clone();
                     let mut new_level = Level::new(level_orig.width() - 1, level_orig.height());
+                    new_level.set_wrap_around(level_orig.wrap_around());
+                    new_level.set_theme(level_orig.theme());
 
                     if index == new_level.width() {
                         self.cursor_pos.0 -= 1;
@@ -3579,6 +9876,9 @@ impl ScreenLevelEditor {
                             new_level.set_tile(j, i, tile);
                         }
                     }
+                    new_level.set_start_pos(level_orig.start_pos().
+                            filter(|&(x, _)| x != index).
+                            map(|(x, y)| (if x > index { x - 1 } else { x }, y)));
 
                     self.level.commit_change(new_level);
                 }else {
@@ -3595,6 +9895,8 @@ impl ScreenLevelEditor {
 
                     let level_orig = self.level.current().clone();
                     let mut new_level = Level::new(level_orig.width(), level_orig.height() - 1);
+                    new_level.set_wrap_around(level_orig.wrap_around());
+                    new_level.set_theme(level_orig.theme());
 
                     if index == new_level.height() {
                         self.cursor_pos.1 -= 1;
@@ -3615,6 +9917,9 @@ impl ScreenLevelEditor {
                             new_level.set_tile(i, j, tile);
                         }
                     }
+                    new_level.set_start_pos(level_orig.start_pos().
+                            filter(|&(_, y)| y != index).
+                            map(|(x, y)| (x, if y > index { y - 1 } else { y })));
 
                     self.level.commit_change(new_level);
                 }
@@ -3646,92 +9951,267 @@ impl ScreenLevelEditor {
 
                     let level_orig = self.level.current().clone();
                     let mut new_level = Level::new(level_orig.width(), level_orig.height() + 1);
+                    new_level.set_wrap_around(level_orig.wrap_around());
+                    new_level.set_theme(level_orig.theme());
+
+                    for i in 0..level_orig.width() {
+                        for mut j in 0..level_orig.height() {
+                            let tile = level_orig.get_tile(i, j).unwrap();
+
+                            if j >= index {
+                                j += 1;
+                            }
+
+                            new_level.set_tile(i, j, tile);
+                        }
+
+                        let tile = if is_copy {
+                            level_orig.get_tile(i, index_orig).unwrap()
+                        }else {
+                            Tile::Empty
+                        };
+                        new_level.set_tile(i, index, tile);
+                    }
+                    new_level.set_start_pos(level_orig.start_pos().
+                            map(|(x, y)| (x, if y >= index { y + 1 } else { y })));
+
+                    self.level.commit_change(new_level);
+                }else {
+                    if self.level.current().width() == Game::LEVEL_MAX_WIDTH {
+                        game_state.open_dialog(Dialog::new_ok_error(format!(
+                            "Level width limit reached (max: {})",
+                            Game::LEVEL_MAX_WIDTH,
+                        )));
+
+                        return;
+                    }
+
+                    let index_orig = self.cursor_pos.0;
+                    if !self.is_reverse_input {
+                        self.cursor_pos.0 += 1;
+                    }
+                    let index = self.cursor_pos.0;
+
+                    let level_orig = self.level.current().clone();
+                    let mut new_level = Level::new(level_orig.width() + 1, level_orig.height());
+                    new_level.set_wrap_around(level_orig.wrap_around());
+                    new_level.set_theme(level_orig.theme());
+
+                    for i in 0..level_orig.height() {
+                        for mut j in 0..level_orig.width() {
+                            let tile = level_orig.get_tile(j, i).unwrap();
+
+                            if j >= index {
+                                j += 1;
+                            }
+
+                            new_level.set_tile(j, i, tile);
+                        }
+
+                        let tile = if is_copy {
+                            level_orig.get_tile(index_orig, i).unwrap()
+                        }else {
+                            Tile::Empty
+                        };
+                        new_level.set_tile(index, i, tile);
+                    }
+                    new_level.set_start_pos(level_orig.start_pos().
+                            map(|(x, y)| (if x >= index { x + 1 } else { x }, y)));
+
+                    self.level.commit_change(new_level);
+                }
+            },
+
+            Key::U | Key::Z | Key::Y => {
+                let is_redo = key == Key::Y;
+
+                let level = if is_redo {
+                    self.level.redo()
+                }else {
+                    self.level.undo()
+                };
+
+                if let Some(level) = level {
+                    if self.cursor_pos.0 >= level.width() {
+                        self.cursor_pos.0 = level.width() - 1;
+                    }
+
+                    if self.cursor_pos.1 >= level.height() {
+                        self.cursor_pos.1 = level.height() - 1;
+                    }
+                }
+            },
+
+            Key::ENTER => {
+                game_state.open_dialog(Dialog::new_yes_no("Save changes and level validation state?"));
+                self.should_exit_after_save = false;
+            },
+
+            Key::F => {
+                let Some(tile_input) = self.last_tile_input else {
+                    game_state.open_dialog(Dialog::new_ok_error("No tile selected (Place a tile first)"));
+
+                    return;
+                };
+
+                //The whole fill is committed as a single change
+                let mut level = self.level.current().clone();
+                if level.flood_fill(self.cursor_pos.0, self.cursor_pos.1, tile_input) {
+                    self.level.commit_change(level);
+                }
+            },
+
+            Key::G => {
+                let Some(new_level) = self.level.current().with_wall_outline() else {
+                    game_state.open_dialog(Dialog::new_ok_error("No play area found (Place a player, box, goal, ... first)"));
+
+                    return;
+                };
+
+                if new_level.width() > Game::LEVEL_MAX_WIDTH {
+                    game_state.open_dialog(Dialog::new_ok_error(format!(
+                        "Level width limit reached (max: {})",
+                        Game::LEVEL_MAX_WIDTH,
+                    )));
+
+                    return;
+                }
+
+                if new_level.height() > Game::LEVEL_MAX_HEIGHT {
+                    game_state.open_dialog(Dialog::new_ok_error(format!(
+                        "Level height limit reached (max: {})",
+                        Game::LEVEL_MAX_HEIGHT,
+                    )));
+
+                    return;
+                }
+
+                if new_level != *self.level.current() {
+                    self.level.commit_change(new_level);
+                }
+            },
+
+            Key::E => {
+                let mut level = self.level.current().clone();
+                level.set_wrap_around(!level.wrap_around());
+                self.level.commit_change(level);
+            },
+
+            Key::L => {
+                let mut level = self.level.current().clone();
+                level.set_theme(level.theme().next());
+                self.level.commit_change(level);
+            },
+
+            Key::J => {
+                game_state.play_sound_effect_ui_select();
+
+                self.show_changes = !self.show_changes;
+            },
+
+            Key::TAB => {
+                game_state.play_sound_effect_ui_select();
+
+                self.show_cursor_pos = !self.show_cursor_pos;
+            },
+
+            Key::T => {
+                if let Some(index) = self.todo_markers.iter().position(|todo_marker| *todo_marker == self.cursor_pos) {
+                    self.todo_markers.remove(index);
+                }else {
+                    self.todo_markers.push(self.cursor_pos);
+                }
+                self.mark_as_unsaved();
+            },
+
+            Key::M => {
+                self.selection_start = if self.selection_start.is_some() {
+                    None
+                }else {
+                    Some(self.cursor_pos)
+                };
+            },
 
-                    for i in 0..level_orig.width() {
-                        for mut j in 0..level_orig.height() {
-                            let tile = level_orig.get_tile(i, j).unwrap();
+            Key::K => {
+                let level = self.level.current();
+                let (copied_level, message) = if let Some((x, y, width, height)) = self.selection() {
+                    (level.region(x, y, width, height), "Selection copied")
+                }else {
+                    (level.clone(), "Level copied")
+                };
 
-                            if j >= index {
-                                j += 1;
-                            }
+                //Levels with tiles which do not exist in the XSB format are copied in the level pack format
+                let text = copied_level.to_xsb().unwrap_or_else(|| copied_level.to_str());
+                game_state.copy_to_clipboard(text);
+                game_state.show_toast(message);
 
-                            new_level.set_tile(i, j, tile);
-                        }
+                self.selection_start = None;
+            },
 
-                        let tile = if is_copy {
-                            level_orig.get_tile(i, index_orig).unwrap()
-                        }else {
-                            Tile::Empty
-                        };
-                        new_level.set_tile(i, index, tile);
-                    }
+            Key::N => {
+                game_state.play_sound_effect_ui_select();
 
-                    self.level.commit_change(new_level);
-                }else {
-                    if self.level.current().width() == Game::LEVEL_MAX_WIDTH {
-                        game_state.open_dialog(Dialog::new_ok_error(format!(
-                            "Level width limit reached (max: {})",
-                            Game::LEVEL_MAX_WIDTH,
-                        )));
+                if self.editor_notes.is_empty() {
+                    self.editor_notes.push(String::new());
+                }
 
-                        return;
-                    }
+                self.is_editing_notes = true;
+            },
 
-                    let index_orig = self.cursor_pos.0;
-                    if !self.is_reverse_input {
-                        self.cursor_pos.0 += 1;
-                    }
-                    let index = self.cursor_pos.0;
+            Key::H => {
+                game_state.play_sound_effect_ui_select();
 
-                    let level_orig = self.level.current().clone();
-                    let mut new_level = Level::new(level_orig.width() + 1, level_orig.height());
+                self.undo_branch_cursor_index = 0;
+                self.is_selecting_undo_branch = true;
+            },
 
-                    for i in 0..level_orig.height() {
-                        for mut j in 0..level_orig.width() {
-                            let tile = level_orig.get_tile(j, i).unwrap();
+            Key::QUESTION_MARK => {
+                let hints = self.level_hints(game_state);
+                if hints.is_empty() {
+                    game_state.open_dialog(Dialog::new_ok_error("The level has no hints (Hints are added in the level pack file)"));
 
-                            if j >= index {
-                                j += 1;
-                            }
+                    return;
+                }
 
-                            new_level.set_tile(j, i, tile);
-                        }
+                let warnings = LevelHint::placement_warnings(hints, self.level.current());
 
-                        let tile = if is_copy {
-                            level_orig.get_tile(index_orig, i).unwrap()
-                        }else {
-                            Tile::Empty
-                        };
-                        new_level.set_tile(index, i, tile);
-                    }
+                game_state.play_sound_effect_ui_select();
+                self.hint_preview = Some(HintPreviewState::Playing);
 
-                    self.level.commit_change(new_level);
+                if !warnings.is_empty() {
+                    game_state.open_dialog(Dialog::new_ok(format!("Hint placement warnings:\n{}", warnings.join("\n"))));
                 }
             },
 
-            Key::U | Key::Z | Key::Y => {
-                let is_redo = key == Key::Y;
-
-                let level = if is_redo {
-                    self.level.redo()
+            Key::DOLLAR => {
+                let mut level = self.level.current().clone();
+                if level.start_pos() == Some(self.cursor_pos) {
+                    level.set_start_pos(None);
                 }else {
-                    self.level.undo()
-                };
+                    let tile = level.get_tile(self.cursor_pos.0, self.cursor_pos.1).unwrap();
+                    if tile.player_start_tile().is_none() {
+                        game_state.open_dialog(Dialog::new_ok_error("The player cannot start on this tile"));
 
-                if let Some(level) = level {
-                    if self.cursor_pos.0 >= level.width() {
-                        self.cursor_pos.0 = level.width() - 1;
+                        return;
                     }
 
-                    if self.cursor_pos.1 >= level.height() {
-                        self.cursor_pos.1 = level.height() - 1;
+                    //The start marker replaces the player tiles
+                    for y in 0..level.height() {
+                        for x in 0..level.width() {
+                            let tile = level.get_tile_mut(x, y).unwrap();
+                            *tile = match *tile {
+                                Tile::Player => Tile::Empty,
+                                Tile::PlayerOnFragileFloor | Tile::PlayerOnIce => tile.floor_tile(),
+
+                                tile => tile,
+                            };
+                        }
                     }
+
+                    level.set_start_pos(Some(self.cursor_pos));
                 }
-            },
 
-            Key::ENTER => {
-                game_state.open_dialog(Dialog::new_yes_no("Save changes and level validation state?"));
-                self.should_exit_after_save = false;
+                self.level.commit_change(level);
             },
 
             key if key.is_ascii() => {
@@ -3743,12 +10223,19 @@ impl ScreenLevelEditor {
                         tile => tile,
                     };
 
+                    self.last_tile_input = Some(tile_input);
+
                     let mut level = self.level.current().clone();
                     let tile = level.get_tile_mut(self.cursor_pos.0, self.cursor_pos.1).unwrap();
 
                     if *tile != tile_input {
                         *tile = tile_input;
 
+                        //Player tiles replace the start marker
+                        if matches!(tile_input, Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce) {
+                            level.set_start_pos(None);
+                        }
+
                         self.level.commit_change(level);
                     }
                 }
@@ -3777,6 +10264,12 @@ impl ScreenLevelEditor {
             return;
         };
 
+        let step_sound_effect_id = if self.is_push_move {
+            PackSoundEffectId::Push
+        }else {
+            PackSoundEffectId::Step
+        };
+
         match move_result {
             MoveResult::Valid { has_won, sound_effect, .. } => {
                 if has_won {
@@ -3787,21 +10280,23 @@ impl ScreenLevelEditor {
                     //Use current index of playing level history
                     let moves = playing_level.current_move_index() as u32;
                     if self.validation_best_moves.is_none_or(|best_moves| moves < best_moves) ||
-                            self.validation_result_history_index != self.level.current_index() {
-                        //Always update best moves of validation if level was changed
+                            self.validation_result_history_index != self.level.current_index() || self.is_solver_validated {
+                        //Always update best moves of validation if level was changed or if the level was only validated by the solver
                         self.validation_best_moves = Some(moves);
+                        self.validation_moves = Some(playing_level.moves().to_vec());
+                        self.is_solver_validated = false;
 
                         //Mark level as unsaved
-                        self.last_saved_history_index = Self::UNDO_HISTORY_SIZE + 1;
+                        self.mark_as_unsaved();
                     }
 
                     //Update validation
                     self.validation_result_history_index = self.level.current_index(); //Use current index of editor level history
 
-                    game_state.play_sound_effect(audio::LEVEL_COMPLETE_EFFECT);
+                    game_state.play_editor_pack_sound_effect(PackSoundEffectId::LevelComplete);
                 }
 
-                game_state.play_sound_effect(audio::STEP_EFFECT);
+                game_state.play_editor_pack_sound_effect(step_sound_effect_id);
 
                 if let Some(sound_effect) = sound_effect {
                     game_state.play_level_sound_effect(sound_effect);
@@ -3814,7 +10309,7 @@ impl ScreenLevelEditor {
 
             MoveResult::Animation { sound_effect, .. } => {
                 if self.animation_first_frame {
-                    game_state.play_sound_effect(audio::STEP_EFFECT);
+                    game_state.play_editor_pack_sound_effect(step_sound_effect_id);
                 }
 
                 if let Some(sound_effect) = sound_effect {
@@ -3827,6 +10322,14 @@ impl ScreenLevelEditor {
 
 impl Screen for ScreenLevelEditor {
     fn draw(&self, game_state: &GameState, console: &Console) {
+        //Changes are compared with the level in the level pack (The level is only written to the level pack when it is saved)
+        let tile_changes = (self.show_changes && !self.show_floor && self.playing_level.is_none()).then(|| {
+            game_state.editor_state.get_current_level_pack().
+                    and_then(|level_pack| level_pack.levels().get(game_state.editor_state.selected_level_index)).
+                    map(|saved_level| self.level.current().tile_changes(saved_level.level())).
+                    unwrap_or_default()
+        });
+
         console.reset_color();
         if let Some(level_history) = &self.playing_level {
             console.draw_text("Playing");
@@ -3843,9 +10346,9 @@ impl Screen for ScreenLevelEditor {
             }
 
             console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 11) as f64 * 0.75) as usize, 0);
-            console.draw_text(format!("Moves: {:04}", level_history.current_move_index()));
+            console.draw_fmt(format_args!("Moves: {:04}", level_history.current_move_index()));
         }else {
-            console.draw_text(format!(
+            console.draw_fmt(format_args!(
                 "Editing ({})",
                 match self.is_vertical_input {
                     true if self.is_reverse_input => "^",
@@ -3855,15 +10358,34 @@ impl Screen for ScreenLevelEditor {
                 }
             ));
 
-            if self.show_floor {
+            if let Some(hint_preview) = self.hint_preview {
+                let state = match hint_preview {
+                    HintPreviewState::Playing => "Playing",
+                    HintPreviewState::Completed => "Completed",
+                };
+
+                console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 11 - state.len()) as f64 * 0.5) as usize, 0);
+                console.draw_text("Hints (");
+                console.draw_key_input_text("?");
+                console.reset_color();
+                console.draw_fmt(format_args!("): {}", state));
+            }else if self.show_floor {
                 console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 14) as f64 * 0.5) as usize, 0);
                 console.draw_text("Show tiles (");
                 console.draw_key_input_text("q");
                 console.reset_color();
                 console.draw_text(")");
-            }else {
+            }else if let Some(tile_changes) = &tile_changes {
+                console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 16) as f64 * 0.5) as usize, 0);
+                console.draw_text("Changes (");
+                console.draw_key_input_text("j");
+                console.reset_color();
+                console.draw_fmt(format_args!("): {:03}", tile_changes.len()));
+            }else if self.show_cursor_pos {
                 console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 14) as f64 * 0.5) as usize, 0);
-                console.draw_text(format!("Cursor ({:02}:{:02})", self.cursor_pos.0 + 1, self.cursor_pos.1 + 1));
+                console.draw_fmt(format_args!("Cursor ({:02}:{:02})", self.cursor_pos.0 + 1, self.cursor_pos.1 + 1));
+            }else {
+                Self::draw_tile_counts(console, &TileCounts::of_level(self.level.current()));
             }
 
             console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 10) as f64 * 0.75) as usize, 0);
@@ -3883,7 +10405,11 @@ impl Screen for ScreenLevelEditor {
                 let validated = self.validation_result_history_index == self.level.current_index() &&
                         self.validation_best_moves.is_some();
 
-                if validated {
+                if validated && self.is_solver_validated {
+                    //Validated by the solver before a Steam Workshop upload
+                    console.set_color(Color::Yellow, Color::Default);
+                    console.draw_text("Bot");
+                }else if validated {
                     console.set_color(Color::Green, Color::Default);
                     console.draw_text("Yes");
                 }else {
@@ -3895,10 +10421,18 @@ impl Screen for ScreenLevelEditor {
 
         console.reset_color();
         console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 9) as f64 * 0.25) as usize, 0);
-        console.draw_text("Level: ");
-        console.draw_text(utils::number_to_string_leading_ascii(2, game_state.editor_state.selected_level_index as u32 + 1, true));
+        if self.is_editing_goto_level {
+            console.draw_text("Go to: ");
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_text(&self.goto_level_str);
+            console.draw_text("_");
+            console.reset_color();
+        }else {
+            console.draw_text("Level: ");
+            console.draw_fmt(format_args!("{}", utils::LeadingAsciiNumber::new(2, game_state.editor_state.selected_level_index as u32 + 1, true)));
+        }
 
-        let x_offset = ((Game::CONSOLE_MIN_WIDTH - self.level.current().width()) as f64 * 0.5) as usize;
+        let x_offset = ((Game::CONSOLE_MIN_WIDTH - self.level.current().draw_width()) as f64 * 0.5) as usize;
         let y_offset = 1;
 
 
@@ -3910,6 +10444,56 @@ impl Screen for ScreenLevelEditor {
             }else {
                 level.draw(console, x_offset, y_offset, game_state.is_player_background(), None);
             }
+
+            //The ghost is drawn with the tile below it, it is hidden if the player is at the same position
+            if let Some(validation_ghost) = &self.validation_ghost {
+                let (x, y) = validation_ghost.position(playing_level.current_move_index());
+                if (x, y) != playing_level.current_playing_level().1 && x < level.width() && y < level.height() {
+                    let (text, _, _) = level.get_tile(x, y).unwrap().text_appearance(game_state.is_player_background());
+
+                    console.set_color(Color::Black, Color::LightPink);
+                    Self::draw_tile_overlay(console, level, x_offset, y_offset, (x, y), text);
+                }
+            }
+
+            //The last row is only free if the level is not at the max height
+            if y_offset + level.draw_height() < Game::CONSOLE_MIN_HEIGHT {
+                console.reset_color();
+                console.set_cursor_pos(0, Game::CONSOLE_MIN_HEIGHT - 1);
+                console.draw_text("Ghost (");
+                console.draw_key_input_text("g");
+                console.reset_color();
+                console.draw_text("): ");
+                match &self.validation_ghost {
+                    Some(ValidationGhost { broken_at_move: Some(broken_at_move), .. }) => {
+                        console.set_color(Color::Red, Color::Default);
+                        console.draw_fmt(format_args!("Broken at move {:04}", broken_at_move + 1));
+                    },
+
+                    Some(validation_ghost) => {
+                        console.set_color(Color::Green, Color::Default);
+                        console.draw_fmt(format_args!(
+                            "Move {:04}/{:04}",
+                            playing_level.current_move_index().min(validation_ghost.positions.len() - 1),
+                            validation_ghost.positions.len() - 1,
+                        ));
+                    },
+
+                    None => {
+                        console.set_color(Color::Red, Color::Default);
+                        console.draw_text("Off");
+                    },
+                }
+            }
+
+            if let Some(pause_menu) = &self.pause_menu {
+                pause_menu.draw(game_state, console, Self::REGION_PAUSE_MENU);
+            }
+        }else if let Some(hint_preview) = self.hint_preview {
+            //The level is drawn without any editor overlays like it is drawn during gameplay
+            self.level.current().draw(console, x_offset, y_offset, game_state.is_player_background(), None);
+
+            self.draw_hint_preview(game_state, console, hint_preview);
         }else {
             let level = self.level.current();
 
@@ -3918,11 +10502,99 @@ impl Screen for ScreenLevelEditor {
             }else {
                 level.draw(console, x_offset, y_offset, game_state.is_player_background(), Some(self.cursor_pos));
             }
+
+            //Changed tiles are highlighted, the cursor is drawn above the highlight
+            for &(x, y, change) in tile_changes.iter().flatten() {
+                if (x, y) == self.cursor_pos {
+                    continue;
+                }
+
+                let (text, _, _) = level.get_tile(x, y).unwrap().text_appearance(game_state.is_player_background());
+
+                console.set_color(Color::Black, change.background_color());
+                Self::draw_tile_overlay(console, level, x_offset, y_offset, (x, y), text);
+            }
+
+            //The start marker shows the tile on which the player starts with the background color of the player
+            if !self.show_floor && let Some((x, y)) = level.start_pos() &&
+                    x < level.width() && y < level.height() && (x, y) != self.cursor_pos {
+                let (text, _, _) = level.get_tile(x, y).unwrap().text_appearance(game_state.is_player_background());
+
+                console.set_color(Color::Black, Color::Yellow);
+                Self::draw_tile_overlay(console, level, x_offset, y_offset, (x, y), text);
+            }
+
+            //TODO markers hide the tile, the tile is visible if the cursor is on the marker
+            for &(x, y) in self.todo_markers.iter() {
+                if x >= level.width() || y >= level.height() || (x, y) == self.cursor_pos {
+                    continue;
+                }
+
+                console.set_color(Color::Black, Color::LightYellow);
+                Self::draw_tile_overlay(console, level, x_offset, y_offset, (x, y), "!");
+            }
+
+            //The selection start is marked like TODO markers
+            if let Some((x, y)) = self.selection_start && x < level.width() && y < level.height() && (x, y) != self.cursor_pos {
+                console.set_color(Color::Black, Color::LightCyan);
+                Self::draw_tile_overlay(console, level, x_offset, y_offset, (x, y), "+");
+            }
+
+            //The last row is only free if the level is not at the max height
+            if y_offset + level.draw_height() < Game::CONSOLE_MIN_HEIGHT {
+                console.reset_color();
+                console.set_cursor_pos(0, Game::CONSOLE_MIN_HEIGHT - 1);
+                console.draw_text("Wrap around (");
+                console.draw_key_input_text("e");
+                console.reset_color();
+                console.draw_text("): ");
+                if level.wrap_around() {
+                    console.set_color(Color::Green, Color::Default);
+                    console.draw_text("Yes");
+                }else {
+                    console.set_color(Color::Red, Color::Default);
+                    console.draw_text("No");
+                }
+
+                console.reset_color();
+                console.set_cursor_pos(24, Game::CONSOLE_MIN_HEIGHT - 1);
+                console.draw_text("TODO (");
+                console.draw_key_input_text("t");
+                console.reset_color();
+                console.draw_fmt(format_args!("): {:02}", self.todo_markers.len()));
+
+                console.set_cursor_pos(39, Game::CONSOLE_MIN_HEIGHT - 1);
+                console.draw_text("Theme (");
+                console.draw_key_input_text("l");
+                console.reset_color();
+                console.draw_text("): ");
+                let theme = level.theme();
+                console.set_color(Color::White, theme.background_color());
+                console.draw_text(theme.display_name());
+
+                console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 12, Game::CONSOLE_MIN_HEIGHT - 1);
+                console.draw_text("Notes (");
+                console.draw_key_input_text("n");
+                console.reset_color();
+                console.draw_fmt(format_args!("): {}", if self.editor_notes.is_empty() { "-" } else { "+" }));
+            }
+
+            if self.is_editing_notes {
+                self.draw_notes(console);
+            }else if self.is_selecting_undo_branch {
+                self.draw_undo_branches(console);
+            }else {
+                //Each tile is a cell of the region, therefore clicks are independent of the tile size
+                game_state.hit_regions().add_grid(
+                    Self::REGION_LEVEL, Rect::new(x_offset, y_offset, level.draw_width(), level.draw_height()),
+                    level.tile_width(), level.tile_height(),
+                );
+            }
         }
     }
 
     fn animate(&mut self, game_state: &mut GameState) {
-        if game_state.is_dialog_opened() || self.continue_flag {
+        if game_state.is_dialog_opened() || self.continue_flag || self.pause_menu.is_some() {
             return;
         }
 
@@ -3933,11 +10605,54 @@ impl Screen for ScreenLevelEditor {
         }
         self.animation_first_frame = false;
     }
+
+    fn is_animated(&self, _: &GameState) -> bool {
+        self.playing_level.as_ref().is_some_and(|playing_level| playing_level.is_playing_animation())
+    }
     
     fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if self.is_editing_notes {
+            self.on_key_pressed_notes(game_state, key);
+
+            return;
+        }
+
+        if self.is_selecting_undo_branch {
+            self.on_key_pressed_undo_branches(game_state, key);
+
+            return;
+        }
+
+        if self.hint_preview.is_some() {
+            self.on_key_pressed_hint_preview(game_state, key);
+
+            return;
+        }
+
+        if self.is_editing_goto_level {
+            self.on_key_pressed_goto_level(game_state, key);
+
+            return;
+        }
+
+        if let Some(pause_menu) = &mut self.pause_menu {
+            if let Some(entry) = pause_menu.on_key_pressed(game_state, key) {
+                self.on_pause_menu_entry_selected(game_state, entry);
+            }
+
+            return;
+        }
+
         if key == Key::ESC {
-            game_state.open_dialog(Dialog::new_yes_cancel_no("Exiting (Save changes and level validation state?)"));
-            self.should_exit_after_save = true;
+            if self.playing_level.is_some() {
+                game_state.play_sound_effect_ui_select();
+
+                self.pause_menu = Some(PauseMenu::new(vec![
+                    PauseMenuEntry::Resume, PauseMenuEntry::Restart, PauseMenuEntry::Help, PauseMenuEntry::QuitToSelection,
+                ]));
+            }else {
+                self.open_exit_dialog(game_state);
+            }
 
             return;
         }
@@ -3945,27 +10660,13 @@ impl Screen for ScreenLevelEditor {
         if key == Key::R {
             self.show_floor = false;
 
-            self.playing_level = if self.playing_level.is_some() {
+            if self.playing_level.is_some() {
                 game_state.play_sound_effect(audio::LEVEL_RESET);
 
-                None
+                self.playing_level = None;
             }else {
-                self.animation_first_frame = false;
-                self.continue_flag = false;
-
-                let playing_level = PlayingLevel::new(self.level.current(), Self::UNDO_HISTORY_SIZE_PLAYING);
-                match playing_level {
-                    Ok(playing_level) => {
-                        Some(playing_level)
-                    },
-
-                    Err(err) => {
-                        game_state.open_dialog(Dialog::new_ok_error(err.to_string()));
-
-                        return;
-                    },
-                }
-            };
+                self.start_playing(game_state);
+            }
 
             return;
         }
@@ -3977,6 +10678,14 @@ impl Screen for ScreenLevelEditor {
             return;
         }
 
+        //Digits are not used as tiles, they start entering the number of the level which is edited next
+        if self.playing_level.is_none() && let Some(c) = key.to_ascii() && c.is_ascii_digit() {
+            self.is_editing_goto_level = true;
+            self.goto_level_str = String::from(c as char);
+
+            return;
+        }
+
         if self.playing_level.is_none() {
             let old_history_len = self.level.len();
             let old_history_index = self.level.current_index();
@@ -3984,75 +10693,141 @@ impl Screen for ScreenLevelEditor {
 
             self.on_key_pressed_editing(game_state, key);
 
-            let new_history_len = self.level.len();
-            let new_history_index = self.level.current_index();
+            self.update_validation_after_edit(old_history_len, old_history_index, is_redo);
+        }else {
+            self.on_key_pressed_playing(game_state, key);
+        }
+    }
 
-            //Undo: New history len is same and new index is smaller
-            //Redo: New history len is same and new index is larger
-            //Commit change without previous undo: New history len is larger and new index is larger
-            //Commit change with previous undo(s): New history len is same or smaller and new index is larger
+    #[cfg(feature = "cli")]
+    fn update(&mut self, game_state: &mut GameState) {
+        self.update_editor_recovery(game_state);
+    }
 
-            //Level is no longer valid if changed (one or more changes), then validated, then one or more undo(s), and then change again
-            if self.validation_result_history_index >= new_history_index && !is_redo &&
-                    new_history_len <= old_history_len && old_history_index + 1 == new_history_index {
-                self.validation_best_moves = None;
+    fn on_text_pasted(&mut self, game_state: &mut GameState, text: &str) {
+        if self.is_editing_notes {
+            if let Some(line) = self.editor_notes.last_mut() {
+                utils::append_pasted_text(
+                    line, text, LevelWithStats::MAX_EDITOR_NOTE_LINE_LEN,
+                    |c| c.is_ascii_graphic() || c == ' ',
+                );
+                self.mark_as_unsaved();
             }
-        }else {
-            self.on_key_pressed_playing(game_state, key);
+        }else if self.is_editing_goto_level {
+            utils::append_pasted_text(&mut self.goto_level_str, text, Self::MAX_GOTO_LEVEL_LEN, |c| c.is_ascii_digit());
+        }else if self.playing_level.is_none() && !self.is_selecting_undo_branch && self.hint_preview.is_none() {
+            self.paste_level_text(game_state, text);
         }
     }
 
-    fn on_mouse_pressed(&mut self, _: &mut GameState, column: usize, row: usize) {
-        if row == 0 || self.playing_level.is_some() {
-            return;
+    fn on_region_clicked(&mut self, game_state: &mut GameState, click: RegionClick) {
+        if click.id == Self::REGION_PAUSE_MENU && let Some(pause_menu) = &mut self.pause_menu &&
+                let Some(entry) = pause_menu.on_region_clicked(click) {
+            self.on_pause_menu_entry_selected(game_state, entry);
+        }else if click.id == Self::REGION_LEVEL {
+            self.cursor_pos = (click.x, click.y);
         }
+    }
 
-        let x_offset = ((Game::CONSOLE_MIN_WIDTH - self.level.current().width()) as f64 * 0.5) as usize;
-        let y_offset = 1;
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        if self.is_confirming_locked_stats_edit {
+            self.is_confirming_locked_stats_edit = false;
 
-        if column < x_offset {
-            return;
-        }
+            #[cfg(feature = "cli")]
+            if selection == DialogSelection::Yes {
+                self.check_editor_recovery(game_state);
+            }
+
+            if selection != DialogSelection::Yes {
+                self.level.clear();
+                game_state.set_screen(ScreenId::LevelPackEditor);
+            }
 
-        let x = column - x_offset;
-        if x >= self.level.current().width() {
             return;
         }
 
-        let y = row - y_offset;
-        if y >= self.level.current().height() {
+        #[cfg(feature = "cli")]
+        if let Some(recovery) = self.pending_editor_recovery.take() {
+            if selection == DialogSelection::Yes {
+                //The restored level is unsaved and is written to the recovery file again
+                self.level.commit_change(recovery.level().clone());
+            }else if let Err(err) = editor_recovery::remove_editor_recovery() {
+                game_state.open_dialog(Dialog::new_ok_error(format!("Cannot remove editor recovery file: {}", err)));
+            }
+
             return;
         }
 
-        self.cursor_pos = (x, y);
-    }
-
-    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
         if selection == DialogSelection::Yes {
             let index = game_state.editor_state.selected_level_index;
             let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
             let level = level_pack.levels_mut().get_mut(index).unwrap();
 
+            let is_level_changed = level.level() != self.level.current();
             *level.level_mut() = self.level.current().clone();
 
+            //Author values of a previous export are outdated
+            level.set_author_moves(None);
+            level.set_author_time(None);
+
+            level.set_editor_notes(self.editor_notes.clone());
+            let (width, height) = (self.level.current().width(), self.level.current().height());
+            level.set_todo_markers(self.todo_markers.iter().
+                    copied().
+                    filter(|&(x, y)| x < width && y < height).
+                    collect::<Vec<_>>());
+            level.set_validation_moves(self.validation_moves.clone());
+            level.set_solver_validated(self.is_solver_validated && self.validation_result_history_index == self.level.current_index());
+
             if self.validation_result_history_index == self.level.current_index() {
                 //TODO best time
-                level.set_best_moves(self.validation_best_moves);
+                level_pack.set_level_best_moves(index, self.validation_best_moves);
             }else {
                 //Reset validation if editor level current history index does not match validation history index
                 //TODO best time
-                level.set_best_moves(None);
+                level_pack.set_level_best_moves(index, None);
+            }
+
+            if is_level_changed {
+                level_pack.add_changelog_entry(format!("Level {} modified", index + 1));
             }
-            level_pack.calculate_stats_sum();
 
             if let Err(err) = game_state.editor_state.get_current_level_pack().unwrap().save_editor_level_pack() {
                 game_state.open_dialog(Dialog::new_ok_error(format!("Cannot save: {}", err)));
             }else {
                 self.last_saved_history_index = self.level.current_index();
+
+                #[cfg(feature = "cli")]
+                self.clear_editor_recovery(game_state);
             }
         }
 
+        if self.should_exit_game_after_save {
+            self.should_exit_game_after_save = false;
+
+            //The game does not exit if the changes could not be saved
+            let is_saved = self.last_saved_history_index == self.level.current_index();
+            if selection == DialogSelection::No || (selection == DialogSelection::Yes && is_saved) {
+                game_state.exit();
+            }
+
+            return;
+        }
+
+        if let Some(level_index) = self.pending_goto_level_index.take() {
+            //The current level is not left if it could not be saved
+            let is_saved = self.last_saved_history_index == self.level.current_index();
+            if selection == DialogSelection::No || (selection == DialogSelection::Yes && is_saved) {
+                self.goto_level(game_state, level_index);
+            }
+
+            return;
+        }
+
         if self.should_exit_after_save && (selection == DialogSelection::Yes || selection == DialogSelection::No) {
+            #[cfg(feature = "cli")]
+            self.clear_editor_recovery(game_state);
+
             self.level.clear();
             game_state.set_screen(ScreenId::LevelPackEditor);
         }
@@ -4065,7 +10840,10 @@ impl Screen for ScreenLevelEditor {
         self.is_vertical_input = false;
         self.is_reverse_input = false;
         self.playing_level = None;
+        self.pause_menu = None;
         self.cursor_pos = (0, 0);
+        self.last_tile_input = None;
+        self.selection_start = None;
 
         let level = game_state.editor_state.get_current_level_pack().
                 unwrap().levels().get(game_state.editor_state.selected_level_index).unwrap();
@@ -4077,7 +10855,67 @@ impl Screen for ScreenLevelEditor {
         self.validation_result_history_index = 0;
         //TODO best time
         self.validation_best_moves = level.best_moves();
+        self.validation_moves = level.validation_moves().map(|moves| moves.to_vec());
+        self.is_solver_validated = level.is_solver_validated();
+        self.validation_ghost = None;
 
         self.show_floor = false;
+        self.show_changes = false;
+
+        self.editor_notes = level.editor_notes().to_vec();
+        self.todo_markers = level.todo_markers().to_vec();
+        self.is_editing_notes = false;
+        self.is_selecting_undo_branch = false;
+        self.hint_preview = None;
+
+        self.is_editing_goto_level = false;
+        self.goto_level_str.clear();
+        self.pending_goto_level_index = None;
+
+        self.should_exit_game_after_save = false;
+
+        #[cfg(feature = "cli")]
+        {
+            self.recovery_level = None;
+            self.pending_editor_recovery = None;
+        }
+
+        let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+        if level_pack.stats_locked() && level.best_moves().is_some() {
+            self.is_confirming_locked_stats_edit = true;
+
+            game_state.open_dialog(Dialog::new(
+                DialogType::Information,
+                format!(
+                    "The validation stats of this level pack are locked.\nChanges to level {} will invalidate its validation.",
+                    game_state.editor_state.selected_level_index + 1,
+                ),
+                Box::from([Self::EDIT_AND_INVALIDATE_OPTION, DialogOption::CANCEL]),
+            ));
+        }
+
+        //Changes of a previous session are only restored after the locked stats were confirmed
+        #[cfg(feature = "cli")]
+        if !self.is_confirming_locked_stats_edit {
+            self.check_editor_recovery(game_state);
+        }
+    }
+
+    fn on_exit_requested(&mut self, game_state: &mut GameState) -> bool {
+        if self.last_saved_history_index == self.level.current_index() {
+            return true;
+        }
+
+        //The save dialog is already opened
+        if self.should_exit_game_after_save {
+            return false;
+        }
+
+        self.should_exit_game_after_save = true;
+        self.pending_goto_level_index = None;
+
+        game_state.open_dialog(Dialog::new_yes_cancel_no("Exiting the game (Save changes and level validation state?)"));
+
+        false
     }
 }
@@ -1,8 +1,15 @@
 #![allow(unused)]
 
-use crate::game::GameState;
+use crate::game::{Game, GameState};
+use crate::game::console_extension::ConsoleExtension;
+use crate::game::input::InGameAction;
+use crate::game::level::{Level, LevelHint, LevelHintSegment, LevelWithStats, Tile};
+use crate::game::screen::hit_regions::{HitRegionId, RegionClick};
+use crate::game::screen::navigation::{GridNavigator, ListNavigator};
+use crate::game::screen::utils;
 use crate::io::{Color, Console, Key};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Rect {
     pub x: usize,
     pub y: usize,
@@ -14,6 +21,10 @@ impl Rect {
     pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
         Self { x, y, width, height }
     }
+
+    pub fn contains(&self, column: usize, row: usize) -> bool {
+        (self.x..self.x + self.width).contains(&column) && (self.y..self.y + self.height).contains(&row)
+    }
 }
 
 pub struct UIListElement {
@@ -49,6 +60,9 @@ pub struct UIList<T = ()> {
 }
 
 impl <T> UIList<T> {
+    /// Count of rows which are skipped with PAGE UP and PAGE DOWN
+    const ROWS_PER_PAGE: usize = 4;
+
     #[expect(clippy::type_complexity)]
     pub fn new(rect: Rect, elements: Vec<UIListElement>, on_select: Box<dyn FnMut(&mut T, &mut GameState, usize)>) -> Self {
         Self { rect, cursor_index: 0, elements, on_select }
@@ -129,49 +143,32 @@ impl <T> UIList<T> {
     pub fn on_key_press(&mut self, custom_state: &mut T, game_state: &mut GameState, key: Key) {
         let elements_per_row = (self.rect.width - 1) / 3;
 
-        match key {
-            Key::LEFT|Key::A => {
-                if self.cursor_index == 0 {
-                    return;
-                }
+        let navigator = GridNavigator::new(elements_per_row, Self::ROWS_PER_PAGE);
+        if let Some(cursor_index) = navigator.navigate(self.cursor_index, self.elements.len(), key) {
+            self.cursor_index = cursor_index;
 
-                self.cursor_index -= 1;
-            },
-            Key::UP|Key::W => {
-                if self.cursor_index < elements_per_row {
-                    return;
-                }
-
-                self.cursor_index -= elements_per_row;
-            },
-            Key::RIGHT|Key::D => {
-                if self.cursor_index + 1 >= self.elements.len() {
-                    return;
-                }
-
-                self.cursor_index += 1;
-            },
-            Key::DOWN|Key::S => {
-                if self.cursor_index + elements_per_row >= self.elements.len() {
-                    return;
-                }
+            return;
+        }
 
-                self.cursor_index += elements_per_row;
-            },
+        if (key == Key::ENTER || key == Key::SPACE) && self.cursor_index < self.elements.len() {
+            (self.on_select)(custom_state, game_state, self.cursor_index);
+        }
+    }
 
-            Key::ENTER|Key::SPACE => {
-                if self.cursor_index < self.elements.len() {
-                    (self.on_select)(custom_state, game_state, self.cursor_index);
-                }
-            },
+    /// Returns the position of the first character of the element
+    pub fn element_pos(&self, element_index: usize) -> (usize, usize) {
+        let elements_per_row = (self.rect.width - 1) / 3;
 
-            _ => {},
-        }
+        (
+            self.rect.x + 1 + (element_index%elements_per_row)*3,
+            self.rect.y + 1 + (element_index/elements_per_row)*2,
+        )
     }
 
-    pub fn on_mouse_pressed(&mut self, custom_state: &mut T, game_state: &mut GameState, column: usize, row: usize) {
+    /// Returns the index of the element at the provided position
+    pub fn element_index_at(&self, column: usize, row: usize) -> Option<usize> {
         if column < self.rect.x || row < self.rect.y {
-            return;
+            return None;
         }
 
         let column = column - self.rect.x;
@@ -180,9 +177,292 @@ impl <T> UIList<T> {
         let elements_per_row = (self.rect.width - 1) / 3;
 
         let element_index = column/3 + row/2 * elements_per_row;
-        if element_index < self.elements().len() {
+        (element_index < self.elements().len()).then_some(element_index)
+    }
+
+    pub fn on_mouse_pressed(&mut self, custom_state: &mut T, game_state: &mut GameState, column: usize, row: usize) {
+        if let Some(element_index) = self.element_index_at(column, row) {
             self.cursor_index = element_index;
             (self.on_select)(custom_state, game_state, element_index);
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseMenuEntry {
+    Resume,
+    Restart,
+    Settings,
+    Help,
+    QuitToSelection,
+}
+
+impl PauseMenuEntry {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            PauseMenuEntry::Resume => "Resume",
+            PauseMenuEntry::Restart => "Restart level",
+            PauseMenuEntry::Settings => "Settings",
+            PauseMenuEntry::Help => "Help",
+            PauseMenuEntry::QuitToSelection => "Quit to selection",
+        }
+    }
+}
+
+/// Menu overlay which is shown while a level is paused
+///
+/// The entries can be selected with the arrow keys and ENTER or with the mouse, ESC selects [PauseMenuEntry::Resume]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PauseMenu {
+    entries: Vec<PauseMenuEntry>,
+    cursor_index: usize,
+}
+
+impl PauseMenu {
+    const WIDTH: usize = 25;
+
+    pub fn new(entries: Vec<PauseMenuEntry>) -> Self {
+        Self { entries, cursor_index: 0 }
+    }
+
+    fn rect(&self) -> Rect {
+        let height = self.entries.len() + 4;
+
+        Rect::new(
+            (Game::CONSOLE_MIN_WIDTH - Self::WIDTH) / 2,
+            ((Game::CONSOLE_MIN_HEIGHT - height) / 2).max(1),
+            Self::WIDTH,
+            height,
+        )
+    }
+
+    /// Draws the menu and registers the entries as a clickable region with the provided ID
+    pub fn draw(&self, game_state: &GameState, console: &Console, region_id: HitRegionId) {
+        let rect = self.rect();
+
+        console.set_color(Color::Cyan, Color::Default);
+        console.set_cursor_pos(rect.x, rect.y);
+        console.draw_fmt(format_args!(".{}.", "-".repeat(rect.width - 2)));
+        for i in 1..rect.height - 1 {
+            console.set_cursor_pos(rect.x, rect.y + i);
+            console.draw_fmt(format_args!("|{}|", " ".repeat(rect.width - 2)));
+        }
+        console.set_cursor_pos(rect.x, rect.y + rect.height - 1);
+        console.draw_fmt(format_args!("\'{}\'", "-".repeat(rect.width - 2)));
+
+        console.reset_color();
+        console.set_cursor_pos(rect.x + (rect.width - 6) / 2, rect.y + 1);
+        console.draw_text("Paused");
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            console.set_cursor_pos(rect.x + 2, rect.y + 3 + i);
+            if i == self.cursor_index {
+                console.set_color(Color::Cyan, Color::Default);
+                console.draw_fmt(format_args!("> {}", entry.display_name()));
+                console.reset_color();
+            }else {
+                console.draw_fmt(format_args!("  {}", entry.display_name()));
+            }
+        }
+
+        game_state.hit_regions().add_grid(region_id, Rect::new(rect.x + 1, rect.y + 3, rect.width - 2, self.entries.len()), rect.width - 2, 1);
+    }
+
+    /// Returns the selected entry or None if no entry was selected
+    pub fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) -> Option<PauseMenuEntry> {
+        let navigator = ListNavigator::new(self.entries.len());
+        if let Some(cursor_index) = navigator.navigate(self.cursor_index, self.entries.len(), key) {
+            game_state.play_sound_effect_ui_select();
+
+            self.cursor_index = cursor_index;
+
+            return None;
+        }
+
+        match key {
+            Key::ENTER | Key::SPACE => self.entries.get(self.cursor_index).copied(),
+            Key::ESC => Some(PauseMenuEntry::Resume),
+
+            _ => None,
+        }
+    }
+
+    /// Returns the clicked entry (The click must be on the region which was registered by [PauseMenu::draw])
+    pub fn on_region_clicked(&mut self, click: RegionClick) -> Option<PauseMenuEntry> {
+        let entry = self.entries.get(click.y).copied()?;
+        self.cursor_index = click.y;
+
+        Some(entry)
+    }
+}
+
+/// Draws an overlay which lists all tiles which are present in the provided level
+///
+/// The key of the in-game action is shown as the toggle key, "l" is shown otherwise
+pub fn draw_tile_legend(console: &Console, level: &Level, toggle_action: Option<InGameAction>) {
+    let tiles = Tile::VALUES.into_iter().
+            filter(|tile| tile.legend_name().is_some() && level.tiles().contains(tile)).
+            collect::<Vec<_>>();
+
+    let row_count = tiles.len().div_ceil(2);
+
+    let width = 58;
+    let height = row_count + 4;
+
+    let x = (Game::CONSOLE_MIN_WIDTH - width) / 2;
+    let y = ((Game::CONSOLE_MIN_HEIGHT - height) / 2).max(1);
+
+    console.set_color(Color::Cyan, Color::Default);
+    console.set_cursor_pos(x, y);
+    console.draw_fmt(format_args!(".{}.", "-".repeat(width - 2)));
+    for i in 1..height - 1 {
+        console.set_cursor_pos(x, y + i);
+        console.draw_fmt(format_args!("|{}|", " ".repeat(width - 2)));
+    }
+    console.set_cursor_pos(x, y + height - 1);
+    console.draw_fmt(format_args!("\'{}\'", "-".repeat(width - 2)));
+
+    console.reset_color();
+    console.set_cursor_pos(x + (width - 15) / 2, y + 1);
+    console.draw_text("Tile legend (");
+    match toggle_action {
+        Some(toggle_action) => console.draw_action_key_input_text(toggle_action),
+        None => console.draw_key_input_text("l"),
+    }
+    console.reset_color();
+    console.draw_text(")");
+
+    for (i, tile) in tiles.into_iter().
+            enumerate() {
+        console.set_cursor_pos(x + 2 + (i % 2) * 28, y + 3 + i / 2);
+        tile.draw(console, false, false);
+
+        console.reset_color();
+        console.draw_fmt(format_args!(": {}", tile.legend_name().unwrap()));
+    }
+}
+
+/// Draws a progress bar with the provided width (Including the brackets) at the current cursor position
+pub fn draw_progress_bar(console: &Console, progress: u64, target: u64, width: usize) {
+    let bar_width = width.saturating_sub(2);
+    let filled_width = if target == 0 {
+        bar_width
+    }else {
+        (progress.min(target) as u128 * bar_width as u128 / target as u128) as usize
+    };
+
+    console.reset_color();
+    console.draw_text("[");
+    console.set_color(Color::LightGreen, Color::Default);
+    console.draw_text("#".repeat(filled_width));
+    console.set_color(Color::LightBlack, Color::Default);
+    console.draw_text("-".repeat(bar_width - filled_width));
+    console.reset_color();
+    console.draw_text("]");
+}
+
+/// Draws the provided level hint horizontally centered in its row
+pub fn draw_level_hint(console: &Console, hint: &LevelHint) {
+    console.reset_color();
+    console.set_cursor_pos(hint.column(), hint.row());
+    for segment in hint.segments() {
+        match segment {
+            LevelHintSegment::Text(text) => console.draw_text(text),
+            LevelHintSegment::Tile(tile) => tile.draw(console, false, false),
+            LevelHintSegment::KeyInput(key) => console.draw_key_input_text(key),
+            LevelHintSegment::Action(action) => console.draw_action_key_input_text(action),
+            LevelHintSegment::Emphasis(text) => {
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text(text);
+            },
+        }
+
+        console.reset_color();
+    }
+}
+
+/// Draws the par values of the provided level at the current cursor position (20 characters)
+///
+/// Par values which were reached by the player are highlighted
+pub fn draw_level_par(console: &Console, level: &LevelWithStats) {
+    console.reset_color();
+    console.draw_text("Par: ");
+
+    match level.par_moves() {
+        None => console.draw_text("XXXX"),
+        Some(par_moves) => {
+            if level.best_moves().is_some_and(|best_moves| best_moves <= par_moves) {
+                console.set_color(Color::LightGreen, Color::Default);
+            }
+
+            console.draw_fmt(format_args!("{:04}", par_moves));
+        },
+    }
+
+    console.reset_color();
+    console.draw_text(", ");
+
+    match level.par_time() {
+        None => console.draw_text("XX:XX.XXX"),
+        Some(par_time) => {
+            if level.best_time().is_some_and(|best_time| best_time <= par_time) {
+                console.set_color(Color::LightGreen, Color::Default);
+            }
+
+            console.draw_fmt(format_args!("{}", utils::TimeDisplay(par_time)));
+        },
+    }
+
+    console.reset_color();
+}
+
+/// Draws the best values of the player compared to the author and par values of the provided level at the current cursor position (68 characters)
+///
+/// Best values which reached the author values are highlighted
+pub fn draw_level_stats_compare(console: &Console, level: &LevelWithStats) {
+    console.reset_color();
+    console.draw_text("Best: ");
+
+    match level.best_moves() {
+        None => console.draw_text("XXXX"),
+        Some(best_moves) => {
+            if level.author_moves().is_some_and(|author_moves| best_moves <= author_moves) {
+                console.set_color(Color::LightGreen, Color::Default);
+            }
+
+            console.draw_fmt(format_args!("{:04}", best_moves));
+        },
+    }
+
+    console.reset_color();
+    console.draw_text(", ");
+
+    match level.best_time() {
+        None => console.draw_text("XX:XX.XXX"),
+        Some(best_time) => {
+            if level.author_time().is_some_and(|author_time| best_time <= author_time) {
+                console.set_color(Color::LightGreen, Color::Default);
+            }
+
+            console.draw_fmt(format_args!("{}", utils::TimeDisplay(best_time)));
+        },
+    }
+
+    console.reset_color();
+    console.draw_text("  Author: ");
+
+    match level.author_moves() {
+        None => console.draw_text("XXXX"),
+        Some(author_moves) => console.draw_fmt(format_args!("{:04}", author_moves)),
+    }
+
+    console.draw_text(", ");
+
+    match level.author_time() {
+        None => console.draw_text("XX:XX.XXX"),
+        Some(author_time) => console.draw_fmt(format_args!("{}", utils::TimeDisplay(author_time))),
+    }
+
+    console.draw_text("  ");
+    draw_level_par(console, level);
+}
@@ -1,5 +1,10 @@
+use std::collections::VecDeque;
+use crate::game::console_extension::ConsoleExtension;
 use crate::io::{Color, Console, Key};
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum DialogType {
     Information,
@@ -133,6 +138,8 @@ pub struct RenderedDialog {
     lines: Box<[Box<str>]>,
     width: usize,
     height: usize,
+
+    scroll_offset: usize,
 }
 
 impl RenderedDialog {
@@ -199,31 +206,72 @@ impl RenderedDialog {
             lines,
             width,
             height,
+
+            scroll_offset: 0,
         }
     }
 
-    pub fn draw(&self, console: &Console) {
-        let option_len = self.dialog.options.iter().
+    /// Returns the number of message lines which are drawn at once, longer messages can be scrolled
+    fn visible_line_count(&self) -> usize {
+        self.lines.len().min(self.height.saturating_sub(7).max(1))
+    }
+
+    fn max_scroll_offset(&self) -> usize {
+        self.lines.len() - self.visible_line_count()
+    }
+
+    /// The line numbers are padded to prevent width changes of the dialog during scrolling
+    fn scroll_text(&self) -> Option<String> {
+        let line_count = self.lines.len();
+        let digits = line_count.to_string().len();
+
+        (self.max_scroll_offset() > 0).then(|| format!(
+            "Lines {:>digits$}-{:>digits$} of {} (Scroll with UP/DOWN)",
+            self.scroll_offset + 1,
+            self.scroll_offset + self.visible_line_count(),
+            line_count,
+        ))
+    }
+
+    fn option_len(&self) -> usize {
+        self.dialog.options.iter().
                 map(|option| option.text.len() + 3).
-                sum::<usize>();
+                sum::<usize>()
+    }
+
+    fn max_char_count(&self) -> usize {
+        self.lines.iter().map(|line| line.len()).max().unwrap_or(0)
+    }
+
+    fn content_width(&self) -> usize {
+        let scroll_text_len = self.scroll_text().map(|scroll_text| scroll_text.len()).unwrap_or(0);
+
+        self.max_char_count().max(self.option_len() + 2).max(scroll_text_len)
+    }
 
-        let max_char_count = self.lines.iter().map(|line| line.len()).max().unwrap_or(0);
-        let width = max_char_count.max(option_len + 2);
+    pub fn draw(&self, console: &Console) {
+        let option_len = self.option_len();
+
+        let max_char_count = self.max_char_count();
+        let width = self.content_width();
         let width_with_border = width + 2;
 
-        let line_count = self.lines.len();
+        let line_count = self.visible_line_count();
 
         let x_start = ((self.width - width - 2) as f64 * 0.5) as usize;
         let y_start = ((self.height - line_count - 5) as f64 * 0.5) as usize;
 
-        for (i, line) in self.lines.iter().enumerate() {
+        for (i, line) in self.lines.iter().
+                skip(self.scroll_offset).
+                take(line_count).
+                enumerate() {
             let char_count = line.len();
 
             let whitespace_count_half = ((width - char_count) as f64 * 0.5) as usize;
 
             console.set_color(self.dialog.dialog_type.text_color(), Color::Yellow);
             console.set_cursor_pos(x_start + 1, y_start + i + 1);
-            console.draw_text(format!(
+            console.draw_fmt(format_args!(
                 "{}{}{}",
                 " ".repeat(whitespace_count_half),
                 line,
@@ -234,7 +282,7 @@ impl RenderedDialog {
         let whitespace_count_half = ((width - max_char_count) as f64 * 0.5) as usize;
 
         console.set_cursor_pos(x_start + 1, y_start + line_count + 1);
-        console.draw_text(format!(
+        console.draw_fmt(format_args!(
             "{}{}{}",
             " ".repeat(whitespace_count_half),
             "-".repeat(max_char_count),
@@ -242,7 +290,18 @@ impl RenderedDialog {
         ));
 
         console.set_cursor_pos(x_start + 1, y_start + line_count + 2);
-        console.draw_text(" ".repeat(width));
+        if let Some(scroll_text) = self.scroll_text() {
+            let whitespace_count_half = ((width - scroll_text.len()) as f64 * 0.5) as usize;
+
+            console.draw_fmt(format_args!(
+                "{}{}{}",
+                " ".repeat(whitespace_count_half),
+                scroll_text,
+                " ".repeat(width - scroll_text.len() - whitespace_count_half),
+            ));
+        }else {
+            console.draw_text(" ".repeat(width));
+        }
 
         let whitespace_count_half = ((width - option_len + 3) as f64 * 0.5) as usize;
 
@@ -250,7 +309,7 @@ impl RenderedDialog {
         console.draw_text(" ".repeat(whitespace_count_half));
 
         for option in self.dialog.options.iter() {
-            console.draw_text(format!("{}   ", option.text));
+            console.draw_fmt(format_args!("{}   ", option.text));
         }
 
         console.draw_text(" ".repeat(width - option_len - whitespace_count_half));
@@ -274,7 +333,22 @@ impl RenderedDialog {
         }
     }
 
-    pub fn on_key_pressed(&self, key: Key) -> Option<DialogSelection> {
+    pub fn on_key_pressed(&mut self, key: Key) -> Option<DialogSelection> {
+        match key {
+            Key::UP => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+
+                return None;
+            },
+            Key::DOWN => {
+                self.scroll_offset = (self.scroll_offset + 1).min(self.max_scroll_offset());
+
+                return None;
+            },
+
+            _ => {},
+        }
+
         for option in self.dialog.options.iter() {
             if option.keys.contains(&key) {
                 return Some(option.action);
@@ -285,7 +359,7 @@ impl RenderedDialog {
     }
 
     pub fn on_mouse_pressed(&self, column: usize, row: usize) -> Option<DialogSelection> {
-        let line_count = self.lines.len();
+        let line_count = self.visible_line_count();
 
         let y_start = ((self.height - line_count - 5) as f64 * 0.5) as usize;
 
@@ -294,12 +368,8 @@ impl RenderedDialog {
             return None;
         }
 
-        let option_len = self.dialog.options.iter().
-                map(|option| option.text.len() + 3).
-                sum::<usize>();
-
-        let max_char_count = self.lines.iter().map(|line| line.len()).max().unwrap_or(0);
-        let width = max_char_count.max(option_len + 2);
+        let option_len = self.option_len();
+        let width = self.content_width();
 
         let x_start = ((self.width - width - 2) as f64 * 0.5) as usize;
 
@@ -328,3 +398,73 @@ impl RenderedDialog {
         None
     }
 }
+
+/// The open dialog and the dialogs which are opened one after another after it was closed
+#[derive(Debug, Default)]
+pub struct DialogQueue {
+    current: Option<RenderedDialog>,
+    queued: VecDeque<Dialog>,
+}
+
+impl DialogQueue {
+    /// Further dialogs are dropped if too many are queued (e.g. the same error in every update)
+    pub const MAX_QUEUED_COUNT: usize = 8;
+
+    pub fn current(&self) -> Option<&RenderedDialog> {
+        self.current.as_ref()
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut RenderedDialog> {
+        self.current.as_mut()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.current.is_some()
+    }
+
+    #[cfg(test)]
+    pub fn queued_count(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Opens the dialog if no dialog is open, otherwise the dialog is queued
+    ///
+    /// Returns true if the dialog was opened
+    pub fn open(&mut self, dialog: Dialog, width: usize, height: usize) -> bool {
+        if self.current.is_some() {
+            if self.queued.len() < Self::MAX_QUEUED_COUNT {
+                self.queued.push_back(dialog);
+            }
+
+            return false;
+        }
+
+        self.current = Some(dialog.render(width, height));
+
+        true
+    }
+
+    /// Closes the open dialog, the next queued dialog is only opened by [DialogQueue::open_next]
+    pub fn close(&mut self) {
+        self.current = None;
+    }
+
+    /// Opens the next queued dialog if no dialog is open and returns its type
+    pub fn open_next(&mut self, width: usize, height: usize) -> Option<DialogType> {
+        if self.current.is_some() {
+            return None;
+        }
+
+        let dialog = self.queued.pop_front()?;
+        let dialog_type = dialog.dialog_type();
+        self.current = Some(dialog.render(width, height));
+
+        Some(dialog_type)
+    }
+
+    /// Closes the open dialog and discards all queued dialogs
+    pub fn clear(&mut self) {
+        self.current = None;
+        self.queued.clear();
+    }
+}
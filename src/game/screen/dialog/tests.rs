@@ -0,0 +1,56 @@
+use crate::game::screen::dialog::*;
+
+const WIDTH: usize = 74;
+const HEIGHT: usize = 23;
+
+fn current_message(dialogs: &DialogQueue) -> Option<&str> {
+    dialogs.current().map(|dialog| dialog.dialog.message())
+}
+
+#[test]
+fn dialogs_are_opened_sequentially() {
+    let mut dialogs = DialogQueue::default();
+    assert!(dialogs.open(Dialog::new_yes_no("First"), WIDTH, HEIGHT));
+    assert!(!dialogs.open(Dialog::new_ok_error("Second"), WIDTH, HEIGHT));
+    assert_eq!(current_message(&dialogs), Some("First"));
+    assert_eq!(dialogs.queued_count(), 1);
+
+    dialogs.close();
+    assert!(!dialogs.is_open());
+    assert_eq!(dialogs.open_next(WIDTH, HEIGHT), Some(DialogType::Error));
+    assert_eq!(current_message(&dialogs), Some("Second"));
+
+    dialogs.close();
+    assert_eq!(dialogs.open_next(WIDTH, HEIGHT), None);
+    assert!(!dialogs.is_open());
+}
+
+#[test]
+fn dialogs_opened_in_selection_handler_are_opened_before_queued_dialogs() {
+    let mut dialogs = DialogQueue::default();
+    dialogs.open(Dialog::new_yes_no("Delete level?"), WIDTH, HEIGHT);
+    dialogs.open(Dialog::new_ok_error("Queued error"), WIDTH, HEIGHT);
+
+    //The dialog is closed before the screen processes the selection
+    dialogs.close();
+    assert!(dialogs.open(Dialog::new_ok_error("Cannot delete level"), WIDTH, HEIGHT));
+    assert_eq!(dialogs.open_next(WIDTH, HEIGHT), None);
+    assert_eq!(current_message(&dialogs), Some("Cannot delete level"));
+
+    dialogs.close();
+    assert_eq!(dialogs.open_next(WIDTH, HEIGHT), Some(DialogType::Error));
+    assert_eq!(current_message(&dialogs), Some("Queued error"));
+}
+
+#[test]
+fn dialog_queue_is_limited() {
+    let mut dialogs = DialogQueue::default();
+    for i in 0..DialogQueue::MAX_QUEUED_COUNT + 3 {
+        dialogs.open(Dialog::new_ok(format!("Dialog {i}")), WIDTH, HEIGHT);
+    }
+    assert_eq!(dialogs.queued_count(), DialogQueue::MAX_QUEUED_COUNT);
+
+    dialogs.clear();
+    assert!(!dialogs.is_open());
+    assert_eq!(dialogs.queued_count(), 0);
+}
@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+use crate::game::screen::components::Rect;
+
+#[cfg(test)]
+mod tests;
+
+/// ID of a clickable region, the meaning of the ID is defined by the screen which registered the region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HitRegionId(pub u32);
+
+/// A click on a registered region
+///
+/// The position is in cells of the region (e.g. tiles of a level which are drawn with multiple columns per tile)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionClick {
+    pub id: HitRegionId,
+
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HitRegion {
+    id: HitRegionId,
+    rect: Rect,
+    cell_width: usize,
+    cell_height: usize,
+}
+
+/// Clickable regions which are registered by the current screen while it is drawn
+///
+/// Regions use console coordinates (Mouse positions are converted to console coordinates by the CLI and GUI input handling),
+/// therefore screens do not need to know how their content is rendered. Regions which are registered later are above earlier regions.
+#[derive(Debug, Default)]
+pub struct HitRegions {
+    regions: RefCell<Vec<HitRegion>>,
+}
+
+impl HitRegions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes all regions (Called before each frame is drawn)
+    pub fn clear(&self) {
+        self.regions.borrow_mut().clear();
+    }
+
+    /// Registers a region which is clicked as a whole (The click position is the console offset in the region)
+    pub fn add(&self, id: HitRegionId, rect: Rect) {
+        self.add_grid(id, rect, 1, 1);
+    }
+
+    /// Registers a region which consists of cells with the provided size in console columns and rows
+    pub fn add_grid(&self, id: HitRegionId, rect: Rect, cell_width: usize, cell_height: usize) {
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+
+        self.regions.borrow_mut().push(HitRegion {
+            id,
+            rect,
+            cell_width: cell_width.max(1),
+            cell_height: cell_height.max(1),
+        });
+    }
+
+    /// Returns the click on the topmost region at the provided console position or None if no region is at the position
+    pub fn hit_test(&self, column: usize, row: usize) -> Option<RegionClick> {
+        self.regions.borrow().iter().
+                rev().
+                find(|region| region.rect.contains(column, row)).
+                map(|region| RegionClick {
+                    id: region.id,
+
+                    x: (column - region.rect.x) / region.cell_width,
+                    y: (row - region.rect.y) / region.cell_height,
+                })
+    }
+}
@@ -0,0 +1,19 @@
+use super::*;
+
+#[test]
+fn hit_test_returns_topmost_region_cell() {
+    let hit_regions = HitRegions::new();
+    hit_regions.add_grid(HitRegionId(0), Rect::new(10, 1, 20, 5), 2, 1);
+    hit_regions.add(HitRegionId(1), Rect::new(14, 2, 4, 1));
+    hit_regions.add(HitRegionId(2), Rect::new(0, 0, 0, 1));
+
+    assert_eq!(hit_regions.hit_test(10, 1), Some(RegionClick { id: HitRegionId(0), x: 0, y: 0 }));
+    assert_eq!(hit_regions.hit_test(13, 4), Some(RegionClick { id: HitRegionId(0), x: 1, y: 3 }));
+    assert_eq!(hit_regions.hit_test(15, 2), Some(RegionClick { id: HitRegionId(1), x: 1, y: 0 }));
+
+    assert_eq!(hit_regions.hit_test(30, 1), None);
+    assert_eq!(hit_regions.hit_test(0, 0), None);
+
+    hit_regions.clear();
+    assert_eq!(hit_regions.hit_test(10, 1), None);
+}
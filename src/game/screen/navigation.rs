@@ -0,0 +1,92 @@
+use crate::io::Key;
+
+#[cfg(test)]
+mod tests;
+
+/// Cursor movement in a grid of elements which is filled row by row (e.g. the level pack and level lists)
+///
+/// Arrow keys and WASD move the cursor by one element, HOME and END jump to the first and last element,
+/// PAGE UP and PAGE DOWN move the cursor by multiple rows while keeping the column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridNavigator {
+    columns: usize,
+    rows_per_page: usize,
+}
+
+impl GridNavigator {
+    pub fn new(columns: usize, rows_per_page: usize) -> Self {
+        Self {
+            columns: columns.max(1),
+            rows_per_page: rows_per_page.max(1),
+        }
+    }
+
+    /// Returns the new cursor index or None if the key is not a navigation key or the cursor would not be moved
+    pub fn navigate(&self, cursor_index: usize, element_count: usize, key: Key) -> Option<usize> {
+        if element_count == 0 {
+            return None;
+        }
+
+        let cursor_index = cursor_index.min(element_count - 1);
+
+        let new_cursor_index = match key {
+            Key::LEFT | Key::A => cursor_index.checked_sub(1)?,
+            Key::RIGHT | Key::D => cursor_index + 1,
+
+            Key::UP | Key::W => self.move_rows_up(cursor_index, 1),
+            Key::DOWN | Key::S => self.move_rows_down(cursor_index, element_count, 1),
+
+            #[cfg(feature = "gui")]
+            Key::PAGE_UP => self.move_rows_up(cursor_index, self.rows_per_page),
+            #[cfg(feature = "gui")]
+            Key::PAGE_DOWN => self.move_rows_down(cursor_index, element_count, self.rows_per_page),
+
+            #[cfg(feature = "gui")]
+            Key::HOME => 0,
+            #[cfg(feature = "gui")]
+            Key::END => element_count - 1,
+
+            _ => return None,
+        };
+
+        (new_cursor_index != cursor_index && new_cursor_index < element_count).then_some(new_cursor_index)
+    }
+
+    fn move_rows_up(&self, cursor_index: usize, max_rows: usize) -> usize {
+        let rows = (cursor_index / self.columns).min(max_rows);
+
+        cursor_index - rows * self.columns
+    }
+
+    fn move_rows_down(&self, cursor_index: usize, element_count: usize, max_rows: usize) -> usize {
+        let rows = ((element_count - 1 - cursor_index) / self.columns).min(max_rows);
+
+        cursor_index + rows * self.columns
+    }
+}
+
+/// Cursor movement in a vertical list of elements
+///
+/// Only UP/W and DOWN/S move the cursor by one element (LEFT and RIGHT can be used by the screen for other actions),
+/// HOME, END, PAGE UP, and PAGE DOWN are handled like in [GridNavigator]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListNavigator {
+    grid: GridNavigator,
+}
+
+impl ListNavigator {
+    pub fn new(rows_per_page: usize) -> Self {
+        Self {
+            grid: GridNavigator::new(1, rows_per_page),
+        }
+    }
+
+    /// Returns the new cursor index or None if the key is not a navigation key or the cursor would not be moved
+    pub fn navigate(&self, cursor_index: usize, element_count: usize, key: Key) -> Option<usize> {
+        if matches!(key, Key::LEFT | Key::A | Key::RIGHT | Key::D) {
+            return None;
+        }
+
+        self.grid.navigate(cursor_index, element_count, key)
+    }
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn grid_navigation_stops_at_boundaries() {
+    let navigator = GridNavigator::new(24, 4);
+
+    assert_eq!(navigator.navigate(0, 30, Key::LEFT), None);
+    assert_eq!(navigator.navigate(0, 30, Key::UP), None);
+    assert_eq!(navigator.navigate(29, 30, Key::RIGHT), None);
+    assert_eq!(navigator.navigate(10, 30, Key::DOWN), None);
+
+    assert_eq!(navigator.navigate(0, 30, Key::D), Some(1));
+    assert_eq!(navigator.navigate(5, 30, Key::S), Some(29));
+    assert_eq!(navigator.navigate(29, 30, Key::W), Some(5));
+
+    assert_eq!(navigator.navigate(0, 0, Key::RIGHT), None);
+}
+
+#[test]
+fn list_navigation_ignores_horizontal_keys() {
+    let navigator = ListNavigator::new(5);
+
+    assert_eq!(navigator.navigate(2, 10, Key::LEFT), None);
+    assert_eq!(navigator.navigate(2, 10, Key::D), None);
+    assert_eq!(navigator.navigate(2, 10, Key::UP), Some(1));
+    assert_eq!(navigator.navigate(9, 10, Key::DOWN), None);
+}
+
+#[test]
+#[cfg(feature = "gui")]
+fn page_and_jump_navigation() {
+    let grid_navigator = GridNavigator::new(24, 4);
+
+    assert_eq!(grid_navigator.navigate(30, 200, Key::HOME), Some(0));
+    assert_eq!(grid_navigator.navigate(30, 200, Key::END), Some(199));
+    assert_eq!(grid_navigator.navigate(199, 200, Key::END), None);
+
+    assert_eq!(grid_navigator.navigate(3, 200, Key::PAGE_DOWN), Some(3 + 4*24));
+    assert_eq!(grid_navigator.navigate(150, 200, Key::PAGE_DOWN), Some(150 + 2*24));
+    assert_eq!(grid_navigator.navigate(60, 200, Key::PAGE_UP), Some(12));
+
+    let list_navigator = ListNavigator::new(5);
+
+    assert_eq!(list_navigator.navigate(7, 10, Key::PAGE_DOWN), Some(9));
+    assert_eq!(list_navigator.navigate(7, 10, Key::PAGE_UP), Some(2));
+    assert_eq!(list_navigator.navigate(0, 10, Key::PAGE_UP), None);
+}
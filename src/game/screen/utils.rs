@@ -1,34 +1,157 @@
-use std::fmt::Write as _;
+use std::fmt;
+use std::fmt::{Display, Formatter, Write as _};
+use crate::io::Key;
 
 #[cfg(test)]
 mod tests;
 
 pub fn number_to_string_leading_ascii(digits: u32, num: u32, leading_zeros: bool) -> String {
-    if digits == 0 {
-        panic!("Not enough digits");
-    }else if digits > 9 {
-        panic!("Too many digits");
+    LeadingAsciiNumber::new(digits, num, leading_zeros).to_string()
+}
+
+/// Formats a number with a fixed count of digits without allocating, the leading digit of larger numbers is a letter ("A0" = 100)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeadingAsciiNumber {
+    digits: u32,
+    num: u32,
+    leading_zeros: bool,
+}
+
+impl LeadingAsciiNumber {
+    /// Panics if digits is not between 1 and 9 or if the number can not be displayed with the count of digits
+    pub fn new(digits: u32, num: u32, leading_zeros: bool) -> Self {
+        if digits == 0 {
+            panic!("Not enough digits");
+        }else if digits > 9 {
+            panic!("Too many digits");
+        }
+
+        let digits_10s = 10_u32.pow(digits);
+        if num >= digits_10s && num / (digits_10s / 10) - 10 > 25 {
+            panic!("Number too large");
+        }
+
+        Self { digits, num, leading_zeros }
     }
+}
 
-    let digits_10s = 10_u32.pow(digits);
-    if num < digits_10s {
-        return if leading_zeros {
-            format!("{:01$}", num, digits as usize)
-        } else {
-            format!("{:1$}", num, digits as usize)
+impl Display for LeadingAsciiNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let digits_10s = 10_u32.pow(self.digits);
+        if self.num < digits_10s {
+            return if self.leading_zeros {
+                write!(f, "{:01$}", self.num, self.digits as usize)
+            } else {
+                write!(f, "{:1$}", self.num, self.digits as usize)
+            }
         }
+
+        let leading_digit = self.num / (digits_10s / 10) - 10;
+        f.write_char((b'A' + leading_digit as u8) as char)?;
+
+        if self.digits > 1 {
+            write!(f, "{:01$}", self.num % (digits_10s / 10), self.digits as usize - 1)?;
+        }
+
+        Ok(())
     }
+}
+
+/// Formats the provided time in milliseconds as "MM:SS.mmm" or as "H:MM:SS.mmm" for times of at least one hour
+pub fn time_to_string(time: u64) -> String {
+    TimeDisplay(time).to_string()
+}
 
-    let leading_digit = num / (digits_10s / 10) - 10;
-    if leading_digit > 25 {
-        panic!("Number too large");
+/// Formats a time in milliseconds as "MM:SS.mmm" or "H:MM:SS.mmm" without allocating (e.g. for [ConsoleExtension::draw_fmt](crate::game::console_extension::ConsoleExtension::draw_fmt))
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeDisplay(pub u64);
+
+impl TimeDisplay {
+    const HOUR: u64 = 60 * 60000;
+
+    /// Returns the count of characters of the formatted time
+    pub fn width(&self) -> usize {
+        if self.0 < Self::HOUR {
+            9
+        }else {
+            //Hours + ":MM:SS.mmm"
+            (self.0 / Self::HOUR).ilog10() as usize + 11
+        }
     }
+}
 
-    let mut out = ((b'A' + leading_digit as u8) as char).to_string();
+/// The width and the alignment of the format spec are supported (e.g. "{:>11}")
+impl Display for TimeDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let padding = f.width().unwrap_or(0).saturating_sub(self.width());
+        let (padding_left, padding_right) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
 
-    if digits > 1 {
-        let _ = write!(out, "{:01$}", num % (digits_10s / 10), digits as usize - 1);
+        for _ in 0..padding_left {
+            f.write_char(f.fill())?;
+        }
+
+        if self.0 >= Self::HOUR {
+            write!(f, "{}:", self.0/Self::HOUR)?;
+        }
+
+        write!(
+            f,
+            "{:02}:{:02}.{:03}",
+            (self.0%Self::HOUR)/60000,
+            (self.0%60000)/1000,
+            self.0%1000
+        )?;
+
+        for _ in 0..padding_right {
+            f.write_char(f.fill())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the time between the provided times (Seconds since the unix epoch) as "Today", "Yesterday", or "N days ago"
+pub fn days_ago_to_string(now: u64, time: u64) -> String {
+    const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+    match now.saturating_sub(time) / SECONDS_PER_DAY {
+        0 => "Today".to_string(),
+        1 => "Yesterday".to_string(),
+        days => format!("{} days ago", days),
     }
+}
+
+/// Appends all allowed characters of the first line of the pasted text to the input until the input contains max_len characters
+pub fn append_pasted_text(input: &mut String, text: &str, max_len: usize, mut is_allowed_char: impl FnMut(char) -> bool) {
+    let remaining_len = max_len.saturating_sub(input.len());
+
+    input.extend(text.lines().
+            next().
+            unwrap_or_default().
+            chars().
+            filter(|c| is_allowed_char(*c)).
+            take(remaining_len));
+}
+
+/// Shortens the text to at most max_len characters, "..." is appended to shortened texts
+pub fn truncate_text(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+
+    let mut truncated_text = text.chars().take(max_len.saturating_sub(3)).collect::<String>();
+    truncated_text += "...";
+
+    truncated_text
+}
+
+/// Converts the count of scrolled lines of the mouse wheel to UP or DOWN key presses
+pub fn scroll_keys(delta: i32) -> impl Iterator<Item = Key> {
+    let key = if delta < 0 { Key::UP } else { Key::DOWN };
 
-    out
+    std::iter::repeat_n(key, delta.unsigned_abs() as usize)
 }
@@ -152,3 +152,59 @@ fn number_with_leading_ascii() {
         assert_eq!(number_to_string_leading_ascii(4, i, false), ((b'A' + (i / 1000 - 10) as u8) as char).to_string() + "999");
     }
 }
+
+#[test]
+fn append_pasted_text_filter_and_limit() {
+    let mut input = "ab".to_string();
+    append_pasted_text(&mut input, "c d-e!f", 5, |c| c.is_ascii_alphanumeric());
+    assert_eq!(input, "abcde");
+
+    let mut input = "abcde".to_string();
+    append_pasted_text(&mut input, "fgh", 5, |_| true);
+    assert_eq!(input, "abcde");
+
+    //Only the first line is used
+    let mut input = String::new();
+    append_pasted_text(&mut input, "ab\ncd", 5, |_| true);
+    assert_eq!(input, "ab");
+}
+
+#[test]
+fn time_to_string_format() {
+    assert_eq!(time_to_string(0), "00:00.000");
+    assert_eq!(time_to_string(61042), "01:01.042");
+    assert_eq!(time_to_string(3599999), "59:59.999");
+    assert_eq!(time_to_string(3600000), "1:00:00.000");
+    assert_eq!(time_to_string(36061042), "10:01:01.042");
+
+    assert_eq!(TimeDisplay(3599999).width(), 9);
+    assert_eq!(TimeDisplay(3600000).width(), 11);
+    assert_eq!(TimeDisplay(36061042).width(), 12);
+    assert_eq!(TimeDisplay(360000000).width(), 13);
+
+    assert_eq!(format!("{:>11}", TimeDisplay(61042)), "  01:01.042");
+    assert_eq!(format!("{:11}|", TimeDisplay(61042)), "01:01.042  |");
+    assert_eq!(format!("{:>5}", TimeDisplay(3600000)), "1:00:00.000");
+}
+
+#[test]
+fn days_ago() {
+    assert_eq!(days_ago_to_string(1000, 1000), "Today");
+    assert_eq!(days_ago_to_string(1000, 2000), "Today");
+    assert_eq!(days_ago_to_string(86400 + 1000, 1000), "Yesterday");
+    assert_eq!(days_ago_to_string(10 * 86400 + 1000, 1000), "10 days ago");
+}
+
+#[test]
+fn scroll_keys_direction_and_count() {
+    assert_eq!(scroll_keys(-2).collect::<Vec<_>>(), [Key::UP, Key::UP]);
+    assert_eq!(scroll_keys(3).collect::<Vec<_>>(), [Key::DOWN, Key::DOWN, Key::DOWN]);
+    assert_eq!(scroll_keys(0).count(), 0);
+}
+
+#[test]
+fn truncate_text_appends_ellipsis() {
+    assert_eq!(truncate_text("Speakers", 8), "Speakers");
+    assert_eq!(truncate_text("USB Headset", 8), "USB H...");
+    assert_eq!(truncate_text("Ä Headset", 5), "Ä ...");
+}
@@ -0,0 +1,265 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write as _};
+use std::path::Path;
+use std::str::FromStr;
+use crate::game::logging::{log_debug, log_warn};
+
+#[cfg(test)]
+mod tests;
+
+/// Current version of the settings file format
+///
+/// Version 1: String values are quoted (The file is TOML compatible), files without version contain unquoted values.
+pub const SETTINGS_FILE_VERSION: u32 = 1;
+
+const VERSION_KEY: &str = "version";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsValue {
+    Bool(bool),
+    Int(usize),
+    String(String),
+}
+
+impl SettingsValue {
+    /// Parses a value of a file without version (Values are not quoted)
+    fn from_legacy(value: &str) -> Self {
+        if let Ok(value) = bool::from_str(value) {
+            SettingsValue::Bool(value)
+        }else if let Ok(value) = usize::from_str(value) {
+            SettingsValue::Int(value)
+        }else {
+            SettingsValue::String(value.to_string())
+        }
+    }
+
+    /// Parses a quoted string, a boolean, or an integer
+    fn from_toml(value: &str) -> Option<Self> {
+        let Some(value) = value.strip_prefix('"') else {
+            return if let Ok(value) = bool::from_str(value) {
+                Some(SettingsValue::Bool(value))
+            }else {
+                usize::from_str(value).ok().map(SettingsValue::Int)
+            };
+        };
+        let value = value.strip_suffix('"')?;
+
+        let mut out = String::with_capacity(value.len());
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            if c == '"' {
+                return None;
+            }
+
+            if c != '\\' {
+                out.push(c);
+
+                continue;
+            }
+
+            out.push(match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                _ => return None,
+            });
+        }
+
+        Some(SettingsValue::String(out))
+    }
+
+    fn to_toml(&self) -> String {
+        match self {
+            SettingsValue::Bool(value) => value.to_string(),
+            SettingsValue::Int(value) => value.to_string(),
+            SettingsValue::String(value) => {
+                let mut out = String::with_capacity(value.len() + 2);
+                out.push('"');
+                for c in value.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        '\t' => out.push_str("\\t"),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+
+                out
+            },
+        }
+    }
+}
+
+/// Unquoted value
+impl Display for SettingsValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsValue::Bool(value) => write!(f, "{value}"),
+            SettingsValue::Int(value) => write!(f, "{value}"),
+            SettingsValue::String(value) => f.write_str(value),
+        }
+    }
+}
+
+/// Schema entry of one option of a settings file
+pub struct SettingsOption<T> {
+    pub key: &'static str,
+    /// Returns false if the value is invalid (The default value is kept)
+    pub read: fn(&mut T, &str) -> bool,
+    /// Returns None if the option should not be written (e.g. if no audio device is selected)
+    pub write: fn(&T) -> Option<SettingsValue>,
+}
+
+/// A settings file with "key = value" lines
+///
+/// Options which are not part of the schema are kept, because the file might be shared with other versions of the game
+/// (e.g. CLI and GUI builds or synchronized by the Steam Cloud).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SettingsFile {
+    version: u32,
+    entries: Vec<(String, SettingsValue)>,
+}
+
+impl SettingsFile {
+    /// Parses the settings file, invalid lines are ignored
+    ///
+    /// `file_name` is only used for log messages.
+    pub fn parse(file_name: &str, data: &str) -> Self {
+        let lines = data.lines().
+                map(str::trim).
+                filter(|line| !line.is_empty() && !line.starts_with('#')).
+                collect::<Vec<_>>();
+
+        let mut version = 0;
+        for line in &lines {
+            if let Some((key, value)) = line.split_once('=') && key.trim() == VERSION_KEY {
+                match u32::from_str(value.trim()) {
+                    Ok(value) => version = value,
+                    Err(_) => log_warn!("\"{file_name}\" contains invalid version: \"{}\": Ignoring", value.trim()),
+                }
+            }
+        }
+
+        if version > SETTINGS_FILE_VERSION {
+            log_warn!("\"{file_name}\" was written by a newer version of the game (Version {version}): Unknown options are kept");
+        }
+
+        let mut entries = Vec::<(String, SettingsValue)>::new();
+        for line in lines {
+            let Some((key, value)) = line.split_once('=') else {
+                log_warn!("\"{file_name}\" contains invalid data: \"{line}\": Ignoring");
+
+                continue;
+            };
+
+            let key = key.trim();
+            let value = value.trim();
+            if key == VERSION_KEY {
+                continue;
+            }
+
+            let parsed_value = if version == 0 {
+                Some(SettingsValue::from_legacy(value))
+            }else {
+                SettingsValue::from_toml(value)
+            };
+            let Some(parsed_value) = parsed_value else {
+                log_warn!("\"{file_name}\" contains invalid value for option \"{key}\": \"{value}\": Ignoring");
+
+                continue;
+            };
+
+            //Later entries overwrite earlier entries
+            if let Some(entry) = entries.iter_mut().find(|(entry_key, _)| entry_key == key) {
+                entry.1 = parsed_value;
+            }else {
+                entries.push((key.to_string(), parsed_value));
+            }
+        }
+
+        Self { version, entries }
+    }
+
+    /// Creates a settings file with the current version from the values of `source`
+    ///
+    /// `unknown_entries` are appended after the options of the schema.
+    pub fn from_schema<T>(source: &T, schema: &[SettingsOption<T>], unknown_entries: &[(String, SettingsValue)]) -> Self {
+        let mut entries = schema.iter().
+                filter_map(|option| Some((option.key.to_string(), (option.write)(source)?))).
+                collect::<Vec<_>>();
+
+        for (key, value) in unknown_entries {
+            if !entries.iter().any(|(entry_key, _)| entry_key == key) {
+                entries.push((key.clone(), value.clone()));
+            }
+        }
+
+        Self {
+            version: SETTINGS_FILE_VERSION,
+            entries,
+        }
+    }
+
+    /// Applies all valid values to `target` and returns the entries which are not part of the schema
+    ///
+    /// Options which are missing or invalid keep the value of `target` (The default value).
+    pub fn apply<T>(&self, file_name: &str, target: &mut T, schema: &[SettingsOption<T>]) -> Vec<(String, SettingsValue)> {
+        for option in schema {
+            match self.get(option.key) {
+                Some(value) => if !(option.read)(target, &value.to_string()) {
+                    log_warn!("\"{file_name}\" contains invalid value for option \"{}\": \"{value}\": Using default", option.key);
+                },
+
+                None => log_debug!("\"{file_name}\" does not contain option \"{}\": Using default", option.key),
+            }
+        }
+
+        self.entries.iter().
+                filter(|(key, _)| !schema.iter().any(|option| option.key == key)).
+                inspect(|(key, value)| {
+                    log_warn!("\"{file_name}\" contains unknown settings option: \"{key}\" with value \"{value}\": Keeping it for other versions of the game");
+                }).
+                cloned().
+                collect()
+    }
+
+    /// Returns the version of the file (0 for files without a version)
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn get(&self, key: &str) -> Option<&SettingsValue> {
+        self.entries.iter().
+                find(|(entry_key, _)| entry_key == key).
+                map(|(_, value)| value)
+    }
+
+    pub fn to_str(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "{VERSION_KEY} = {}", self.version);
+        for (key, value) in &self.entries {
+            let _ = writeln!(out, "{key} = {}", value.to_toml());
+        }
+
+        out
+    }
+}
+
+/// Writes the data to a temporary file which replaces the file afterward
+///
+/// The file is never left partially written if the game is closed while saving (e.g. before a cloud synchronization).
+pub fn write_file_atomically(path: &Path, data: &str) -> Result<(), Box<dyn Error>> {
+    let mut tmp_path = path.as_os_str().to_os_string();
+    tmp_path.push(".tmp");
+
+    std::fs::write(&tmp_path, data)?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
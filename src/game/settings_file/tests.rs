@@ -0,0 +1,85 @@
+use std::str::FromStr;
+use crate::game::settings_file::*;
+
+#[derive(Debug, Default)]
+struct TestSettings {
+    flag: bool,
+    count: usize,
+    name: Option<String>,
+}
+
+const TEST_SCHEMA: &[SettingsOption<TestSettings>] = &[
+    SettingsOption {
+        key: "flag",
+        read: |settings, value| bool::from_str(value).map(|value| settings.flag = value).is_ok(),
+        write: |settings| Some(SettingsValue::Bool(settings.flag)),
+    },
+    SettingsOption {
+        key: "count",
+        read: |settings, value| usize::from_str(value).map(|value| settings.count = value).is_ok(),
+        write: |settings| Some(SettingsValue::Int(settings.count)),
+    },
+    SettingsOption {
+        key: "name",
+        read: |settings, value| {
+            settings.name = Some(value.to_string());
+
+            true
+        },
+        write: |settings| settings.name.clone().map(SettingsValue::String),
+    },
+];
+
+#[test]
+fn legacy_file_values_are_not_quoted() {
+    let settings_file = SettingsFile::parse("test.data", "flag = true\ncount = 4\nname = Device \"A\"\n");
+    assert_eq!(settings_file.version(), 0);
+
+    let mut settings = TestSettings::default();
+    let unknown_entries = settings_file.apply("test.data", &mut settings, TEST_SCHEMA);
+    assert!(unknown_entries.is_empty());
+    assert!(settings.flag);
+    assert_eq!(settings.count, 4);
+    assert_eq!(settings.name.as_deref(), Some("Device \"A\""));
+}
+
+#[test]
+fn invalid_and_missing_values_use_default() {
+    let settings_file = SettingsFile::parse("test.data", "version = 1\ncount = \"abc\"\nflag = maybe\n");
+    assert_eq!(settings_file.version(), 1);
+
+    let mut settings = TestSettings::default();
+    settings_file.apply("test.data", &mut settings, TEST_SCHEMA);
+    assert!(!settings.flag);
+    assert_eq!(settings.count, 0);
+    assert_eq!(settings.name, None);
+}
+
+#[test]
+fn unknown_options_are_kept() {
+    let settings_file = SettingsFile::parse("test.data", "version = 2\nflag = true\nnew_option = \"value\"\n");
+
+    let mut settings = TestSettings::default();
+    let unknown_entries = settings_file.apply("test.data", &mut settings, TEST_SCHEMA);
+    assert_eq!(unknown_entries, [("new_option".to_string(), SettingsValue::String("value".to_string()))]);
+
+    let settings_file = SettingsFile::from_schema(&settings, TEST_SCHEMA, &unknown_entries);
+    assert_eq!(settings_file.to_str(), "version = 1\nflag = true\ncount = 0\nnew_option = \"value\"\n");
+}
+
+#[test]
+fn string_values_round_trip() {
+    let settings = TestSettings {
+        flag: false,
+        count: 8,
+        name: Some("Speakers \"Front\" \\ 2".to_string()),
+    };
+
+    let data = SettingsFile::from_schema(&settings, TEST_SCHEMA, &[]).to_str();
+    assert_eq!(data, "version = 1\nflag = false\ncount = 8\nname = \"Speakers \\\"Front\\\" \\\\ 2\"\n");
+
+    let mut parsed_settings = TestSettings::default();
+    SettingsFile::parse("test.data", &data).apply("test.data", &mut parsed_settings, TEST_SCHEMA);
+    assert_eq!(parsed_settings.name, settings.name);
+    assert_eq!(parsed_settings.count, 8);
+}
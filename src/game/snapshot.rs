@@ -0,0 +1,249 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use crate::game::{Game, GameError};
+use crate::game::level::LevelAttempt;
+use crate::game::screen::ScreenId;
+
+#[cfg(test)]
+mod tests;
+
+/// Command line flag which restores the snapshot at the following path on startup
+pub const SNAPSHOT_ARG: &str = "--snapshot";
+
+/// Name of the snapshot file in the save game folder which is written by the snapshot key
+pub const SNAPSHOT_FILE_NAME: &str = "snapshot.txt";
+
+const SNAPSHOT_HEADER: &str = "SokoTerm Snapshot";
+
+/// Splits the command line arguments into the snapshot path and all other arguments (Level pack paths)
+pub fn split_snapshot_arg(args: impl IntoIterator<Item = String>) -> Result<(Option<String>, Vec<String>), GameError> {
+    let mut snapshot_path = None;
+    let mut other_args = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg != SNAPSHOT_ARG {
+            other_args.push(arg);
+
+            continue;
+        }
+
+        let Some(path) = args.next() else {
+            return Err(GameError::new(format!("The snapshot path is missing after \"{}\"", SNAPSHOT_ARG)));
+        };
+
+        if snapshot_path.replace(path).is_some() {
+            return Err(GameError::new(format!("\"{}\" can only be used once", SNAPSHOT_ARG)));
+        }
+    }
+
+    Ok((snapshot_path, other_args))
+}
+
+/// Returns the path of the snapshot file which is written by the snapshot key
+pub fn snapshot_file_path() -> Result<OsString, Box<dyn Error>> {
+    let mut path = Game::get_or_create_save_game_folder()?;
+    path.push(SNAPSHOT_FILE_NAME);
+
+    Ok(path)
+}
+
+/// The state of the game (Current screen, selected level pack and level, and level progress) which can be
+/// saved to a file and restored later (e.g. for bug reports)
+///
+/// Level packs are referenced by their ID, therefore a snapshot can only be restored if all referenced level packs are loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameStateSnapshot {
+    screen_id: ScreenId,
+
+    level_pack_id: Option<String>,
+    level_index: usize,
+
+    editor_level_pack_id: Option<String>,
+    editor_level_index: usize,
+
+    level_attempt: Option<LevelAttempt>,
+}
+
+impl GameStateSnapshot {
+    pub fn new(screen_id: ScreenId) -> Self {
+        Self {
+            screen_id,
+
+            level_pack_id: None,
+            level_index: 0,
+
+            editor_level_pack_id: None,
+            editor_level_index: 0,
+
+            level_attempt: None,
+        }
+    }
+
+    pub fn with_level(mut self, level_pack_id: impl Into<String>, level_index: usize) -> Self {
+        self.level_pack_id = Some(level_pack_id.into());
+        self.level_index = level_index;
+
+        self
+    }
+
+    pub fn with_editor_level(mut self, editor_level_pack_id: impl Into<String>, editor_level_index: usize) -> Self {
+        self.editor_level_pack_id = Some(editor_level_pack_id.into());
+        self.editor_level_index = editor_level_index;
+
+        self
+    }
+
+    pub fn with_level_attempt(mut self, level_attempt: Option<LevelAttempt>) -> Self {
+        self.level_attempt = level_attempt;
+
+        self
+    }
+
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+
+        let snapshot_data = std::fs::read_to_string(path).map_err(|err| GameError::new(format!(
+            "Cannot read snapshot \"{}\": {}",
+            path.display(), err,
+        )))?;
+
+        Ok(Self::from_str(&snapshot_data)?)
+    }
+
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let mut file = File::create(path)?;
+        write!(file, "{}", self.to_str())?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    pub fn screen_id(&self) -> &ScreenId {
+        &self.screen_id
+    }
+
+    pub fn level_pack_id(&self) -> Option<&str> {
+        self.level_pack_id.as_deref()
+    }
+
+    pub fn level_index(&self) -> usize {
+        self.level_index
+    }
+
+    pub fn editor_level_pack_id(&self) -> Option<&str> {
+        self.editor_level_pack_id.as_deref()
+    }
+
+    pub fn editor_level_index(&self) -> usize {
+        self.editor_level_index
+    }
+
+    pub fn level_attempt(&self) -> Option<&LevelAttempt> {
+        self.level_attempt.as_ref()
+    }
+
+    /// Level numbers are stored 1-based to match the level numbers which are shown in game
+    pub fn to_str(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "{}", SNAPSHOT_HEADER);
+        let _ = writeln!(out, "Game Version: {}", Game::VERSION);
+        let _ = writeln!(out, "Screen: {}", self.screen_id.id());
+
+        if let Some(level_pack_id) = &self.level_pack_id {
+            let _ = writeln!(out, "Level Pack: {}", level_pack_id);
+            let _ = writeln!(out, "Level: {}", self.level_index + 1);
+        }
+
+        if let Some(editor_level_pack_id) = &self.editor_level_pack_id {
+            let _ = writeln!(out, "Editor Level Pack: {}", editor_level_pack_id);
+            let _ = writeln!(out, "Editor Level: {}", self.editor_level_index + 1);
+        }
+
+        if let Some(level_attempt) = &self.level_attempt {
+            let _ = writeln!(out, "Level Attempt: {}", level_attempt.to_str());
+        }
+
+        out
+    }
+}
+
+impl FromStr for GameStateSnapshot {
+    type Err = GameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().
+                map(|line| line.trim()).
+                filter(|line| !line.is_empty());
+
+        if lines.next().is_none_or(|line| line != SNAPSHOT_HEADER) {
+            return Err(GameError::new("The file is not a snapshot!"));
+        }
+
+        let mut screen_id = None;
+        let mut level_pack_id = None;
+        let mut level_index = 0;
+        let mut editor_level_pack_id = None;
+        let mut editor_level_index = 0;
+        let mut level_attempt = None;
+
+        for line in lines {
+            let Some((key, value)) = line.split_once(": ") else {
+                return Err(GameError::new(format!("Invalid snapshot entry \"{}\"", line)));
+            };
+
+            let parse_level_index = || usize::from_str(value).ok().
+                    and_then(|level_number| level_number.checked_sub(1)).
+                    ok_or_else(|| GameError::new(format!("Invalid level number \"{}\"", value)));
+
+            match key {
+                //Informational only
+                "Game Version" => {},
+
+                "Screen" => {
+                    screen_id = Some(ScreenId::from_id(value).ok_or_else(|| GameError::new(format!(
+                        "Invalid screen \"{}\"", value,
+                    )))?);
+                },
+
+                "Level Pack" => level_pack_id = Some(value.to_string()),
+                "Level" => level_index = parse_level_index()?,
+
+                "Editor Level Pack" => editor_level_pack_id = Some(value.to_string()),
+                "Editor Level" => editor_level_index = parse_level_index()?,
+
+                "Level Attempt" => {
+                    level_attempt = Some(LevelAttempt::from_str(value).map_err(|err| GameError::new(format!(
+                        "Invalid level attempt: {}", err,
+                    )))?);
+                },
+
+                _ => {
+                    return Err(GameError::new(format!("Invalid snapshot entry \"{}\"", key)));
+                },
+            }
+        }
+
+        let Some(screen_id) = screen_id else {
+            return Err(GameError::new("The screen is missing in the snapshot!"));
+        };
+
+        Ok(Self {
+            screen_id,
+
+            level_pack_id,
+            level_index,
+
+            editor_level_pack_id,
+            editor_level_index,
+
+            level_attempt,
+        })
+    }
+}
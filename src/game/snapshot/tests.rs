@@ -0,0 +1,37 @@
+use std::str::FromStr;
+use crate::game::level::{Direction, LevelAttempt};
+use crate::game::screen::ScreenId;
+use crate::game::snapshot::*;
+
+#[test]
+fn snapshot_round_trip() {
+    let snapshot = GameStateSnapshot::new(ScreenId::InGame).
+            with_level("main", 4).
+            with_editor_level("my_pack", 0).
+            with_level_attempt(Some(LevelAttempt::new(1234, [Direction::Right, Direction::Right, Direction::Up])));
+
+    let snapshot_str = snapshot.to_str();
+    assert!(snapshot_str.contains("Level: 5\n"));
+    assert!(snapshot_str.contains("Level Attempt: 1234,2ru\n"));
+
+    assert_eq!(GameStateSnapshot::from_str(&snapshot_str).unwrap(), snapshot);
+
+    let snapshot = GameStateSnapshot::new(ScreenId::StartMenu);
+    assert_eq!(GameStateSnapshot::from_str(&snapshot.to_str()).unwrap(), snapshot);
+
+    assert!(GameStateSnapshot::from_str("Screen: in-game\n").is_err());
+    assert!(GameStateSnapshot::from_str("SokoTerm Snapshot\nLevel Pack: main\n").is_err());
+    assert!(GameStateSnapshot::from_str("SokoTerm Snapshot\nScreen: unknown\n").is_err());
+    assert!(GameStateSnapshot::from_str("SokoTerm Snapshot\nScreen: in-game\nLevel: 0\n").is_err());
+}
+
+#[test]
+fn snapshot_arg_splitting() {
+    let args = ["a.lvl", "--snapshot", "snapshot.txt", "b.lvl"].map(String::from);
+    let (snapshot_path, other_args) = split_snapshot_arg(args).unwrap();
+    assert_eq!(snapshot_path.as_deref(), Some("snapshot.txt"));
+    assert_eq!(other_args, ["a.lvl", "b.lvl"]);
+
+    assert!(split_snapshot_arg(["--snapshot".to_string()]).is_err());
+    assert!(split_snapshot_arg(["--snapshot", "a.txt", "--snapshot", "b.txt"].map(String::from)).is_err());
+}
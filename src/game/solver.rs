@@ -0,0 +1,964 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write as _};
+use std::sync::{Arc, Mutex};
+use rand::prelude::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use crate::game::Game;
+use crate::game::level::{Direction, Level, LevelPack, PlayingLevel, Tile};
+use crate::game::logging::log_warn;
+#[cfg(any(feature = "cli", feature = "gui"))]
+use crate::io::Color;
+use crate::utils;
+
+#[cfg(test)]
+mod tests;
+
+/// Result of the analysis of a level by the solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solvability {
+    Solvable,
+    /// The search was aborted before a solution was found
+    Unknown,
+    /// The level is deadlocked at the start or no solution exists
+    LikelyUnsolvable,
+}
+
+impl Solvability {
+    /// Returns the icon which is shown in the level selection
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn icon(self) -> (&'static str, Color) {
+        match self {
+            Solvability::Solvable => ("+", Color::LightGreen),
+            Solvability::Unknown => ("?", Color::LightYellow),
+            Solvability::LikelyUnsolvable => ("!", Color::LightRed),
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Solvability::Solvable => "Solvable",
+            Solvability::Unknown => "Unknown",
+            Solvability::LikelyUnsolvable => "Likely unsolvable",
+        }
+    }
+}
+
+/// Max count of states which are visited per level before the search is aborted
+pub const MAX_STATE_COUNT: usize = 20000;
+
+/// The undo history must be large enough for animations
+const HISTORY_SIZE: usize = 4;
+
+/// Searches a solution of the level with a breadth-first search over all reachable states
+pub fn analyze_level(level: &Level) -> Solvability {
+    analyze_level_with_limit(level, MAX_STATE_COUNT)
+}
+
+fn analyze_level_with_limit(level: &Level, max_state_count: usize) -> Solvability {
+    search_level(level, max_state_count).0
+}
+
+/// Returns the result of the breadth-first search and the count of visited states
+fn search_level(level: &Level, max_state_count: usize) -> (Solvability, usize) {
+    if is_deadlocked(level) {
+        return (Solvability::LikelyUnsolvable, 0);
+    }
+
+    let Ok(playing_level) = PlayingLevel::new(level, HISTORY_SIZE) else {
+        return (Solvability::LikelyUnsolvable, 0);
+    };
+    //The start state contains the player tile at the start marker
+    let (start_state, player_pos, _) = playing_level.current_playing_level();
+
+    //Ice might move the player in a loop if wrap around is enabled
+    let max_animation_step_count = level.tiles().len() * 2;
+
+    let mut visited_states = HashSet::from([start_state.tiles().to_vec()]);
+    let mut pending_states = VecDeque::from([(start_state.clone(), *player_pos)]);
+    while let Some((state, player_pos)) = pending_states.pop_front() {
+        for direction in [Direction::Left, Direction::Up, Direction::Right, Direction::Down] {
+            let mut playing_level = PlayingLevel::from_state(level, state.clone(), player_pos, HISTORY_SIZE);
+
+            let mut move_result = playing_level.move_player(direction);
+            let mut animation_step_count = 0;
+            while playing_level.is_playing_animation() && animation_step_count < max_animation_step_count {
+                move_result = playing_level.continue_animation();
+                animation_step_count += 1;
+            }
+
+            if playing_level.is_playing_animation() || move_result.is_invalid() {
+                continue;
+            }
+
+            if move_result.has_won() {
+                return (Solvability::Solvable, visited_states.len());
+            }
+
+            let (next_state, next_player_pos, _) = playing_level.current_playing_level();
+            if is_deadlocked(next_state) || !visited_states.insert(next_state.tiles().to_vec()) {
+                continue;
+            }
+
+            if visited_states.len() >= max_state_count {
+                return (Solvability::Unknown, visited_states.len());
+            }
+
+            pending_states.push_back((next_state.clone(), *next_player_pos));
+        }
+    }
+
+    //All reachable states were visited
+    (Solvability::LikelyUnsolvable, visited_states.len())
+}
+
+/// Node of the solution search
+struct SearchNode {
+    state: Level,
+    player_pos: (usize, usize),
+
+    /// The index of the previous node with the move which leads to this state
+    previous: Option<(usize, Direction)>,
+}
+
+/// Searches the solution with the fewest moves from the provided state of the original level
+///
+/// None is returned if no solution exists or if the search was aborted after [MAX_STATE_COUNT] states
+pub fn find_solution(original_level: &Level, state: &Level, player_pos: (usize, usize)) -> Option<Vec<Direction>> {
+    if is_deadlocked(state) {
+        return None;
+    }
+
+    //Ice might move the player in a loop if wrap around is enabled
+    let max_animation_step_count = state.tiles().len() * 2;
+
+    let mut nodes = vec![SearchNode {
+        state: state.clone(),
+        player_pos,
+        previous: None,
+    }];
+    let mut visited_states = HashSet::from([state.tiles().to_vec()]);
+    for node_index in 0.. {
+        let Some(node) = nodes.get(node_index) else {
+            break;
+        };
+        let (state, player_pos) = (node.state.clone(), node.player_pos);
+
+        for direction in [Direction::Left, Direction::Up, Direction::Right, Direction::Down] {
+            let mut playing_level = PlayingLevel::from_state(original_level, state.clone(), player_pos, HISTORY_SIZE);
+
+            let mut move_result = playing_level.move_player(direction);
+            let mut animation_step_count = 0;
+            while playing_level.is_playing_animation() && animation_step_count < max_animation_step_count {
+                move_result = playing_level.continue_animation();
+                animation_step_count += 1;
+            }
+
+            if playing_level.is_playing_animation() || move_result.is_invalid() {
+                continue;
+            }
+
+            if move_result.has_won() {
+                let mut moves = vec![direction];
+                let mut index = node_index;
+                while let Some((previous_index, direction)) = nodes[index].previous {
+                    moves.push(direction);
+                    index = previous_index;
+                }
+                moves.reverse();
+
+                return Some(moves);
+            }
+
+            let (next_state, next_player_pos, _) = playing_level.current_playing_level();
+            if is_deadlocked(next_state) || !visited_states.insert(next_state.tiles().to_vec()) {
+                continue;
+            }
+
+            if visited_states.len() >= MAX_STATE_COUNT {
+                return None;
+            }
+
+            nodes.push(SearchNode {
+                state: next_state.clone(),
+                player_pos: *next_player_pos,
+                previous: Some((node_index, direction)),
+            });
+        }
+    }
+
+    None
+}
+
+/// Searches the minimum count of pushes of boxes and keys which is required to solve the level
+///
+/// Moves without a push are not counted. None is returned if no solution exists or if the search was aborted after
+/// [MAX_STATE_COUNT] states.
+pub fn find_min_push_count(level: &Level) -> Option<u32> {
+    if is_deadlocked(level) {
+        return None;
+    }
+
+    let Ok(playing_level) = PlayingLevel::new(level, HISTORY_SIZE) else {
+        return None;
+    };
+    let (start_state, player_pos, _) = playing_level.current_playing_level();
+
+    //Ice might move the player in a loop if wrap around is enabled
+    let max_animation_step_count = level.tiles().len() * 2;
+
+    //0-1 breadth-first search: States which are reached without a push are visited before states with an additional push
+    let mut push_counts = HashMap::from([(start_state.tiles().to_vec(), 0)]);
+    let mut pending_states = VecDeque::from([(start_state.clone(), *player_pos, 0)]);
+    let mut min_push_count: Option<u32> = None;
+    while let Some((state, player_pos, push_count)) = pending_states.pop_front() {
+        if min_push_count.is_some_and(|min_push_count| push_count >= min_push_count) {
+            break;
+        }
+
+        //The state was already reached with fewer pushes
+        if push_counts.get(state.tiles()).is_some_and(|&count| count < push_count) {
+            continue;
+        }
+
+        for direction in [Direction::Left, Direction::Up, Direction::Right, Direction::Down] {
+            let is_push = state.neighbor_pos(player_pos.0, player_pos.1, direction).
+                    and_then(|(x, y)| state.get_tile(x, y)).
+                    is_some_and(is_pushable_tile);
+            let next_push_count = push_count + is_push as u32;
+
+            let mut playing_level = PlayingLevel::from_state(level, state.clone(), player_pos, HISTORY_SIZE);
+
+            let mut move_result = playing_level.move_player(direction);
+            let mut animation_step_count = 0;
+            while playing_level.is_playing_animation() && animation_step_count < max_animation_step_count {
+                move_result = playing_level.continue_animation();
+                animation_step_count += 1;
+            }
+
+            if playing_level.is_playing_animation() || move_result.is_invalid() {
+                continue;
+            }
+
+            if move_result.has_won() {
+                min_push_count = Some(min_push_count.map_or(next_push_count, |count| count.min(next_push_count)));
+
+                continue;
+            }
+
+            let (next_state, next_player_pos, _) = playing_level.current_playing_level();
+            if is_deadlocked(next_state) || push_counts.get(next_state.tiles()).is_some_and(|&count| count <= next_push_count) {
+                continue;
+            }
+
+            push_counts.insert(next_state.tiles().to_vec(), next_push_count);
+            if push_counts.len() >= MAX_STATE_COUNT {
+                return None;
+            }
+
+            if is_push {
+                pending_states.push_back((next_state.clone(), *next_player_pos, next_push_count));
+            }else {
+                pending_states.push_front((next_state.clone(), *next_player_pos, next_push_count));
+            }
+        }
+    }
+
+    min_push_count
+}
+
+/// Validates all levels without best moves with the solver (Used instead of validation runs before Steam Workshop uploads)
+///
+/// The solutions are stored as validation moves and the levels are marked as solver validated.
+/// If a level can not be solved, no level is changed and the indices of all unsolved levels are returned.
+pub fn validate_unvalidated_levels(level_pack: &mut LevelPack) -> Result<usize, Vec<usize>> {
+    let mut solutions = Vec::new();
+    let mut unsolved_level_indices = Vec::new();
+    for (i, level) in level_pack.levels().iter().enumerate() {
+        if level.best_moves().is_some() {
+            continue;
+        }
+
+        let Ok(playing_level) = PlayingLevel::new(level.level(), HISTORY_SIZE) else {
+            unsolved_level_indices.push(i);
+
+            continue;
+        };
+        let (start_state, player_pos, _) = playing_level.current_playing_level();
+
+        match find_solution(level.level(), start_state, *player_pos) {
+            Some(solution) => solutions.push((i, solution)),
+            None => unsolved_level_indices.push(i),
+        }
+    }
+
+    if !unsolved_level_indices.is_empty() {
+        return Err(unsolved_level_indices);
+    }
+
+    let validated_level_count = solutions.len();
+    for (i, solution) in solutions {
+        level_pack.set_level_best_moves(i, Some(solution.len() as u32));
+
+        let level = &mut level_pack.levels_mut()[i];
+        level.set_validation_moves(Some(solution));
+        level.set_solver_validated(true);
+    }
+
+    Ok(validated_level_count)
+}
+
+/// Returns the position of the box or key which is pushed by the first push of the moves and the push direction
+pub fn find_next_push(playing_level: &PlayingLevel, moves: &[Direction]) -> Option<((usize, usize), Direction)> {
+    let (state, player_pos, _) = playing_level.current_playing_level();
+    let mut simulation = PlayingLevel::from_state(playing_level.original_level(), state.clone(), *player_pos, HISTORY_SIZE);
+
+    for &direction in moves {
+        let (level, (x, y), _) = simulation.current_playing_level();
+        if let Some((x, y)) = level.neighbor_pos(*x, *y, direction) && level.get_tile(x, y).is_some_and(is_pushable_tile) {
+            return Some(((x, y), direction));
+        }
+
+        if !simulation.replay_moves(&[direction]) {
+            return None;
+        }
+    }
+
+    None
+}
+
+fn is_pushable_tile(tile: Tile) -> bool {
+    matches!(
+        tile,
+        Tile::Box | Tile::BoxInGoal | Tile::BoxOnFragileFloor | Tile::BoxOnIce |
+        Tile::Key | Tile::KeyInGoal | Tile::KeyOnFragileFloor | Tile::KeyOnIce
+    )
+}
+
+/// Caches the solutions of the states of one level
+///
+/// The states along a found solution are cached as well, because players will likely follow the solution
+#[derive(Debug, Default)]
+pub struct SolutionCache {
+    //Tiles of the state -> Remaining moves (None if no solution was found)
+    solutions: HashMap<Vec<Tile>, Option<Vec<Direction>>>,
+}
+
+impl SolutionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Must be called if another level is played
+    pub fn clear(&mut self) {
+        self.solutions.clear();
+    }
+
+    /// Returns the remaining moves from the current state of the playing level or None if no solution was found
+    pub fn solution(&mut self, playing_level: &PlayingLevel) -> Option<&[Direction]> {
+        let (state, player_pos, _) = playing_level.current_playing_level();
+
+        if !self.solutions.contains_key(state.tiles()) {
+            let solution = find_solution(playing_level.original_level(), state, *player_pos);
+
+            if let Some(solution) = &solution {
+                let mut simulation = PlayingLevel::from_state(playing_level.original_level(), state.clone(), *player_pos, HISTORY_SIZE);
+                for (i, &direction) in solution.iter().enumerate() {
+                    if !simulation.replay_moves(&[direction]) {
+                        break;
+                    }
+
+                    self.solutions.entry(simulation.current_playing_level().0.tiles().to_vec()).
+                            or_insert_with(|| Some(solution[i + 1..].to_vec()));
+                }
+            }
+
+            self.solutions.insert(state.tiles().to_vec(), solution);
+        }
+
+        self.solutions.get(state.tiles()).and_then(Option::as_deref)
+    }
+}
+
+/// Count of variations which are generated for a remix before no remix is returned
+const REMIX_ATTEMPT_COUNT: usize = 12;
+
+/// Creates a variation of the level by moving some boxes which are not in a goal to a free neighbor position
+///
+/// The same seed always results in the same variation, None is returned if no variation which is solvable was found
+pub fn remix_level(level: &Level, seed: u64) -> Option<Level> {
+    let mut rand = ChaCha8Rng::seed_from_u64(seed);
+
+    let box_positions = (0..level.height()).
+            flat_map(|y| (0..level.width()).map(move |x| (x, y))).
+            filter(|&(x, y)| level.get_tile(x, y) == Some(Tile::Box)).
+            collect::<Vec<_>>();
+    if box_positions.is_empty() {
+        return None;
+    }
+
+    for _ in 0..REMIX_ATTEMPT_COUNT {
+        let mut remixed_level = level.clone();
+        let mut moved_box_count = 0;
+
+        for &(x, y) in box_positions.iter() {
+            if !rand.random_bool(0.5) {
+                continue;
+            }
+
+            let direction = *[Direction::Left, Direction::Up, Direction::Right, Direction::Down].choose(&mut rand).unwrap();
+            let Some((new_x, new_y)) = remixed_level.neighbor_pos(x, y, direction) else {
+                continue;
+            };
+
+            //Boxes are only moved to empty floor to keep all other tiles (e.g. goals, holes, or ice) unchanged
+            if remixed_level.get_tile(x, y) == Some(Tile::Box) && remixed_level.get_tile(new_x, new_y) == Some(Tile::Empty) &&
+                    remixed_level.start_pos() != Some((new_x, new_y)) {
+                remixed_level.set_tile(x, y, Tile::Empty);
+                remixed_level.set_tile(new_x, new_y, Tile::Box);
+
+                moved_box_count += 1;
+            }
+        }
+
+        if moved_box_count > 0 && remixed_level.tiles() != level.tiles() &&
+                analyze_level(&remixed_level) == Solvability::Solvable {
+            return Some(remixed_level);
+        }
+    }
+
+    None
+}
+
+/// Checks if there are not enough boxes left or if a box is stuck in a corner outside a goal
+fn is_deadlocked(level: &Level) -> bool {
+    let goal_count = level.tiles().iter().
+            filter(|tile| matches!(tile, Tile::Goal | Tile::BoxInGoal | Tile::KeyInGoal)).
+            count();
+    let box_count = level.tiles().iter().
+            filter(|tile| matches!(tile, Tile::Box | Tile::BoxInGoal | Tile::BoxOnFragileFloor | Tile::BoxOnIce)).
+            count();
+    if goal_count == 0 || box_count < goal_count {
+        return true;
+    }
+
+    let is_wall = |x: usize, y: usize, direction: Direction| {
+        level.neighbor_pos(x, y, direction).
+                is_none_or(|(x, y)| level.get_tile(x, y) == Some(Tile::Wall))
+    };
+
+    (0..level.height()).any(|y| (0..level.width()).any(|x| {
+        level.get_tile(x, y) == Some(Tile::Box) &&
+                (is_wall(x, y, Direction::Up) || is_wall(x, y, Direction::Down)) &&
+                (is_wall(x, y, Direction::Left) || is_wall(x, y, Direction::Right))
+    }))
+}
+
+/// Estimated difficulty of a level, the values match the difficulty tags of the Steam Workshop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LevelDifficulty {
+    Easy,
+    Medium,
+    Hard,
+    Demon,
+}
+
+impl LevelDifficulty {
+    /// Returns the icon which is shown in the level list of the level pack editor
+    #[cfg(any(feature = "cli", feature = "gui"))]
+    pub fn icon(self) -> (&'static str, Color) {
+        match self {
+            LevelDifficulty::Easy => ("E", Color::LightGreen),
+            LevelDifficulty::Medium => ("M", Color::LightYellow),
+            LevelDifficulty::Hard => ("H", Color::LightRed),
+            LevelDifficulty::Demon => ("D", Color::LightPink),
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            LevelDifficulty::Easy => "Easy",
+            LevelDifficulty::Medium => "Medium",
+            LevelDifficulty::Hard => "Hard",
+            LevelDifficulty::Demon => "Demon",
+        }
+    }
+
+    /// Maps the heuristic score of [difficulty_score] to a difficulty
+    fn from_score(score: f64) -> Self {
+        if score < 10.0 {
+            LevelDifficulty::Easy
+        }else if score < 14.0 {
+            LevelDifficulty::Medium
+        }else if score < 18.0 {
+            LevelDifficulty::Hard
+        }else {
+            LevelDifficulty::Demon
+        }
+    }
+}
+
+/// Estimates the difficulty of the level based on the search effort of the solver, the box count, and the corridor complexity
+///
+/// None is returned if the level is likely unsolvable, levels for which the search was aborted are estimated as [LevelDifficulty::Demon]
+pub fn estimate_level_difficulty(level: &Level) -> Option<LevelDifficulty> {
+    let (solvability, visited_state_count) = search_level(level, MAX_STATE_COUNT);
+
+    match solvability {
+        Solvability::Solvable => {
+            let box_count = level.tiles().iter().
+                    filter(|tile| matches!(tile, Tile::Box | Tile::BoxInGoal | Tile::BoxOnFragileFloor | Tile::BoxOnIce)).
+                    count();
+
+            Some(LevelDifficulty::from_score(difficulty_score(visited_state_count, box_count, corridor_tile_ratio(level))))
+        },
+        Solvability::Unknown => Some(LevelDifficulty::Demon),
+        Solvability::LikelyUnsolvable => None,
+    }
+}
+
+/// Suggests the difficulty of a level pack: The difficulty of the median level (Unsolvable levels should not be included)
+pub fn estimate_level_pack_difficulty(level_difficulties: impl IntoIterator<Item = LevelDifficulty>) -> Option<LevelDifficulty> {
+    let mut level_difficulties = level_difficulties.into_iter().collect::<Vec<_>>();
+    level_difficulties.sort();
+
+    level_difficulties.get(level_difficulties.len()/2).copied()
+}
+
+/// Heuristic score of the level: The search effort is weighted logarithmically, long corridors make pushing boxes harder
+fn difficulty_score(visited_state_count: usize, box_count: usize, corridor_tile_ratio: f64) -> f64 {
+    (visited_state_count.max(1) as f64).log2() + box_count as f64 * 0.5 + corridor_tile_ratio * 4.0
+}
+
+/// Returns the ratio of floor tiles with exactly two floor neighbors to all floor tiles
+fn corridor_tile_ratio(level: &Level) -> f64 {
+    let is_floor = |x: usize, y: usize| !matches!(level.get_tile(x, y), None | Some(Tile::Wall | Tile::DecorationBlank));
+
+    let floor_positions = (0..level.height()).
+            flat_map(|y| (0..level.width()).map(move |x| (x, y))).
+            filter(|&(x, y)| is_floor(x, y)).
+            collect::<Vec<_>>();
+    if floor_positions.is_empty() {
+        return 0.0;
+    }
+
+    let corridor_tile_count = floor_positions.iter().
+            filter(|&&(x, y)| [Direction::Left, Direction::Up, Direction::Right, Direction::Down].into_iter().
+                    filter(|&direction| level.neighbor_pos(x, y, direction).
+                            is_some_and(|(x, y)| is_floor(x, y))).
+                    count() == 2).
+            count();
+
+    corridor_tile_count as f64 / floor_positions.len() as f64
+}
+
+/// Problem of a level which is reported by [find_level_problems]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelProblem {
+    NoPlayer,
+    TooManyPlayers(usize),
+    /// The start marker is outside of the level or on a tile on which the player cannot start
+    InvalidStartMarker,
+    TooFewBoxes {
+        box_count: usize,
+        goal_count: usize,
+    },
+    /// There are more boxes than goals and holes
+    TooManyBoxes {
+        box_count: usize,
+        goal_count: usize,
+    },
+    /// Count of goals which cannot be reached from the player position (Walls are never passable)
+    UnreachableGoals(usize),
+    /// Count of keys which are not needed for locked doors
+    UnusedKeys(usize),
+    /// Count of locked doors which cannot be opened with the available keys
+    MissingKeys(usize),
+    TooLarge {
+        width: usize,
+        height: usize,
+    },
+}
+
+impl Display for LevelProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelProblem::NoPlayer => write!(f, "No player"),
+            LevelProblem::TooManyPlayers(player_count) => write!(f, "Too many players ({})", player_count),
+            LevelProblem::InvalidStartMarker => write!(f, "Invalid start marker"),
+            LevelProblem::TooFewBoxes { box_count, goal_count } => {
+                write!(f, "Fewer boxes ({}) than goals ({})", box_count, goal_count)
+            },
+            LevelProblem::TooManyBoxes { box_count, goal_count } => {
+                write!(f, "More boxes ({}) than goals ({}) and holes", box_count, goal_count)
+            },
+            LevelProblem::UnreachableGoals(goal_count) => write!(f, "Unreachable goals ({})", goal_count),
+            LevelProblem::UnusedKeys(key_count) => write!(f, "Unused keys ({})", key_count),
+            LevelProblem::MissingKeys(door_count) => write!(f, "Locked doors without keys ({})", door_count),
+            LevelProblem::TooLarge { width, height } => write!(
+                f, "Too large ({}x{}, max: {}x{})",
+                width, height, Game::LEVEL_MAX_WIDTH, Game::LEVEL_MAX_HEIGHT,
+            ),
+        }
+    }
+}
+
+/// Counts of the tiles which must match for a level to be winnable (e.g. shown in the level editor)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TileCounts {
+    pub player_count: usize,
+    pub box_count: usize,
+    pub goal_count: usize,
+    pub hole_count: usize,
+    pub key_count: usize,
+    pub door_count: usize,
+}
+
+impl TileCounts {
+    pub fn of_level(level: &Level) -> Self {
+        let count_tiles = |tiles: &[Tile]| level.tiles().iter().
+                filter(|tile| tiles.contains(tile)).
+                count();
+
+        Self {
+            player_count: level.player_count(),
+            box_count: count_tiles(&[Tile::Box, Tile::BoxInGoal, Tile::BoxOnFragileFloor, Tile::BoxOnIce]),
+            goal_count: count_tiles(&[Tile::Goal, Tile::BoxInGoal, Tile::KeyInGoal]),
+            hole_count: count_tiles(&[Tile::Hole]),
+            key_count: count_tiles(&[Tile::Key, Tile::KeyInGoal, Tile::KeyOnFragileFloor, Tile::KeyOnIce]),
+            door_count: count_tiles(&[Tile::LockedDoor]),
+        }
+    }
+
+    pub fn is_player_count_valid(&self) -> bool {
+        self.player_count == 1
+    }
+
+    /// Every goal needs a box and every other box must be sunk in a hole
+    pub fn is_box_count_valid(&self) -> bool {
+        self.box_count >= self.goal_count && self.box_count <= self.goal_count + self.hole_count
+    }
+
+    pub fn is_key_count_valid(&self) -> bool {
+        self.key_count == self.door_count
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.is_player_count_valid() && self.is_box_count_valid() && self.is_key_count_valid()
+    }
+}
+
+/// Checks the level for problems which can be found without searching a solution
+pub fn find_level_problems(level: &Level) -> Vec<LevelProblem> {
+    let mut problems = Vec::new();
+
+    let TileCounts { player_count, box_count, goal_count, hole_count, key_count, door_count } = TileCounts::of_level(level);
+    if player_count == 0 {
+        problems.push(LevelProblem::NoPlayer);
+    }else if player_count > 1 {
+        problems.push(LevelProblem::TooManyPlayers(player_count));
+    }
+
+    if level.with_player_at_start().is_err() {
+        problems.push(LevelProblem::InvalidStartMarker);
+    }
+
+    if box_count < goal_count {
+        problems.push(LevelProblem::TooFewBoxes { box_count, goal_count });
+    }else if box_count > goal_count + hole_count {
+        problems.push(LevelProblem::TooManyBoxes { box_count, goal_count });
+    }
+
+    if player_count == 1 {
+        let unreachable_goal_count = count_unreachable_goals(level);
+        if unreachable_goal_count > 0 {
+            problems.push(LevelProblem::UnreachableGoals(unreachable_goal_count));
+        }
+    }
+
+    if key_count > door_count {
+        problems.push(LevelProblem::UnusedKeys(key_count - door_count));
+    }else if door_count > key_count {
+        problems.push(LevelProblem::MissingKeys(door_count - key_count));
+    }
+
+    if level.width() > Game::LEVEL_MAX_WIDTH || level.height() > Game::LEVEL_MAX_HEIGHT {
+        problems.push(LevelProblem::TooLarge { width: level.width(), height: level.height() });
+    }
+
+    problems
+}
+
+/// Checks all levels of the level pack for problems, the level index is returned for each problem
+pub fn find_level_pack_problems(level_pack: &LevelPack) -> Vec<(usize, LevelProblem)> {
+    level_pack.levels().iter().
+            enumerate().
+            flat_map(|(i, level)| find_level_problems(level.level()).
+                    into_iter().
+                    map(move |problem| (i, problem))).
+            collect()
+}
+
+/// Counts goals which are not connected to the player by tiles other than walls
+fn count_unreachable_goals(level: &Level) -> usize {
+    let Some(player_pos) = level.start_pos().or_else(|| (0..level.height()).
+            flat_map(|y| (0..level.width()).map(move |x| (x, y))).
+            find(|&(x, y)| matches!(level.get_tile(x, y), Some(Tile::Player | Tile::PlayerOnFragileFloor | Tile::PlayerOnIce)))) else {
+        return 0;
+    };
+
+    let mut reachable = HashSet::from([player_pos]);
+    let mut pending_positions = vec![player_pos];
+    while let Some((x, y)) = pending_positions.pop() {
+        for direction in [Direction::Left, Direction::Up, Direction::Right, Direction::Down] {
+            let Some(pos) = level.neighbor_pos(x, y, direction) else {
+                continue;
+            };
+
+            if matches!(level.get_tile(pos.0, pos.1), Some(Tile::Wall | Tile::DecorationBlank)) {
+                continue;
+            }
+
+            if reachable.insert(pos) {
+                pending_positions.push(pos);
+            }
+        }
+    }
+
+    (0..level.height()).
+            flat_map(|y| (0..level.width()).map(move |x| (x, y))).
+            filter(|&(x, y)| matches!(level.get_tile(x, y), Some(Tile::Goal | Tile::BoxInGoal | Tile::KeyInGoal))).
+            filter(|pos| !reachable.contains(pos)).
+            count()
+}
+
+/// Analyzes the levels of imported level packs in background threads
+#[derive(Debug, Default)]
+pub struct BackgroundValidator {
+    //Level pack ID -> Result for each level (None if the level was not analyzed yet)
+    results: Arc<Mutex<HashMap<String, Vec<Option<Solvability>>>>>,
+}
+
+impl BackgroundValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the analysis of all levels of the level pack in a new background thread
+    pub fn validate_level_pack(&self, level_pack: &LevelPack) {
+        let level_pack_id = level_pack.id().to_string();
+        let levels = level_pack.levels().iter().
+                map(|level| level.level().clone()).
+                collect::<Vec<_>>();
+
+        self.results.lock().unwrap().insert(level_pack_id.clone(), vec![None; levels.len()]);
+
+        let results = Arc::clone(&self.results);
+        std::thread::spawn(move || {
+            for (i, level) in levels.iter().enumerate() {
+                let solvability = analyze_level(level);
+
+                if let Some(level_results) = results.lock().unwrap().get_mut(&level_pack_id) {
+                    level_results[i] = Some(solvability);
+                }
+            }
+        });
+    }
+
+    /// Returns the result of the level or None if the level pack is not analyzed or the analysis of the level is not finished
+    pub fn solvability(&self, level_pack_id: &str, level_index: usize) -> Option<Solvability> {
+        self.results.lock().unwrap().
+                get(level_pack_id).
+                and_then(|level_results| level_results.get(level_index).copied().flatten())
+    }
+}
+
+/// Computes the minimum push counts of levels in a background thread (One level at a time)
+///
+/// The results are cached in a file of the base save game folder, the cache is discarded after game updates
+#[derive(Debug, Default)]
+pub struct MinPushCountCache {
+    state: Arc<Mutex<MinPushCountCacheState>>,
+}
+
+#[derive(Debug, Default)]
+struct MinPushCountCacheState {
+    is_loaded: bool,
+    is_computing: bool,
+    //Level hash -> Minimum push count (None if no solution was found)
+    results: HashMap<u64, Option<u32>>,
+}
+
+impl MinPushCountCache {
+    const FILE_NAME: &'static str = "min_push_counts.data";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the minimum push count of the level or None if it is not known yet
+    ///
+    /// The computation is started in a background thread if the level was not solved yet and no other level is being solved
+    pub fn min_push_count(&self, level: &Level) -> Option<u32> {
+        let level_hash = level_hash(level);
+
+        let mut state = self.state.lock().unwrap();
+        if !state.is_loaded {
+            state.is_loaded = true;
+
+            let results = Self::read_from_file();
+
+            if let Err(err) = &results {
+                log_warn!("Cannot load \"{}\": {err}", Self::FILE_NAME);
+            }
+
+            state.results = results.unwrap_or_default();
+        }
+
+        if let Some(&min_push_count) = state.results.get(&level_hash) {
+            return min_push_count;
+        }
+
+        if state.is_computing {
+            return None;
+        }
+        state.is_computing = true;
+
+        let level = level.clone();
+        let state = Arc::clone(&self.state);
+        std::thread::spawn(move || {
+            let min_push_count = find_min_push_count(&level);
+
+            let mut state = state.lock().unwrap();
+            state.is_computing = false;
+            state.results.insert(level_hash, min_push_count);
+
+            let result = Self::save_to_file(&state.results);
+
+            if let Err(err) = result {
+                log_warn!("Cannot save \"{}\": {err}", Self::FILE_NAME);
+            }
+        });
+
+        None
+    }
+
+    fn read_from_file() -> Result<HashMap<u64, Option<u32>>, Box<dyn Error>> {
+        let mut min_push_counts_file = Game::get_or_create_base_save_game_folder()?;
+        min_push_counts_file.push(Self::FILE_NAME);
+
+        if !std::fs::exists(&min_push_counts_file)? {
+            return Ok(HashMap::new());
+        }
+
+        Ok(parse_min_push_counts(&std::fs::read_to_string(&min_push_counts_file)?, Game::VERSION))
+    }
+
+    fn save_to_file(results: &HashMap<u64, Option<u32>>) -> Result<(), Box<dyn Error>> {
+        let mut min_push_counts_file = Game::get_or_create_base_save_game_folder()?;
+        min_push_counts_file.push(Self::FILE_NAME);
+
+        std::fs::write(min_push_counts_file, min_push_counts_to_str(results, Game::VERSION))?;
+
+        Ok(())
+    }
+}
+
+/// Estimates the difficulties of levels in a background thread (One level at a time)
+#[derive(Debug, Default)]
+pub struct DifficultyEstimateCache {
+    state: Arc<Mutex<DifficultyEstimateCacheState>>,
+}
+
+#[derive(Debug, Default)]
+struct DifficultyEstimateCacheState {
+    is_computing: bool,
+    //Level hash -> Difficulty (None if the level is likely unsolvable)
+    results: HashMap<u64, Option<LevelDifficulty>>,
+}
+
+impl DifficultyEstimateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the estimated difficulty of the level or None if it is not known yet or if the level is likely unsolvable
+    ///
+    /// The estimation is started in a background thread if the level was not estimated yet and no other level is being estimated
+    pub fn difficulty(&self, level: &Level) -> Option<LevelDifficulty> {
+        let level_hash = level_hash(level);
+
+        let mut state = self.state.lock().unwrap();
+        if let Some(&difficulty) = state.results.get(&level_hash) {
+            return difficulty;
+        }
+
+        if state.is_computing {
+            return None;
+        }
+        state.is_computing = true;
+
+        let level = level.clone();
+        let state = Arc::clone(&self.state);
+        std::thread::spawn(move || {
+            let difficulty = estimate_level_difficulty(&level);
+
+            let mut state = state.lock().unwrap();
+            state.is_computing = false;
+            state.results.insert(level_hash, difficulty);
+        });
+
+        None
+    }
+}
+
+/// Returns a hash of the level which identifies the level in the min push count cache and the difficulty estimate cache
+///
+/// Only properties which change the solutions are included (e.g. the theme is not included)
+fn level_hash(level: &Level) -> u64 {
+    let data = format!("{}{:?},{}", level.to_str(), level.start_pos(), level.wrap_around());
+
+    utils::fnv1a_64(data.bytes())
+}
+
+/// Converts the min push counts to the format of the min push count file ("version = 1.0.0" followed by "<Level hash> = <Count or ->")
+fn min_push_counts_to_str(results: &HashMap<u64, Option<u32>>, version: &str) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "version = {version}");
+    for (level_hash, min_push_count) in results {
+        match min_push_count {
+            Some(min_push_count) => {
+                let _ = writeln!(out, "{level_hash:016x} = {min_push_count}");
+            },
+            None => {
+                let _ = writeln!(out, "{level_hash:016x} = -");
+            },
+        }
+    }
+
+    out
+}
+
+/// Parses the data of the min push count file, all entries are ignored if they were computed by another game version
+fn parse_min_push_counts(data: &str, version: &str) -> HashMap<u64, Option<u32>> {
+    let mut lines = data.split("\n").
+            map(|line| line.trim()).
+            filter(|line| !line.is_empty());
+
+    if lines.next().and_then(|line| line.strip_prefix("version = ")) != Some(version) {
+        return HashMap::new();
+    }
+
+    lines.filter_map(|line| {
+        let (level_hash, min_push_count) = line.split_once(" = ")?;
+        let level_hash = u64::from_str_radix(level_hash, 16).ok()?;
+        let min_push_count = match min_push_count {
+            "-" => None,
+            min_push_count => Some(min_push_count.parse().ok()?),
+        };
+
+        Some((level_hash, min_push_count))
+    }).collect()
+}
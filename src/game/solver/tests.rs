@@ -0,0 +1,179 @@
+use std::str::FromStr;
+use crate::game::solver::*;
+
+#[test]
+fn analyze_level_solvable() {
+    let level = Level::from_str("w: 4, h: 1\np@-x").unwrap();
+    assert_eq!(analyze_level(&level), Solvability::Solvable);
+}
+
+#[test]
+fn analyze_level_deadlocked_start() {
+    let level = Level::from_str("w: 4, h: 4\n####\n#@-#\n#px#\n####").unwrap();
+    assert_eq!(analyze_level(&level), Solvability::LikelyUnsolvable);
+
+    let level = Level::from_str("w: 4, h: 1\np--x").unwrap();
+    assert_eq!(analyze_level(&level), Solvability::LikelyUnsolvable);
+}
+
+#[test]
+fn analyze_level_no_solution() {
+    let level = Level::from_str("w: 6, h: 4\n######\n#-@--#\n#p--x#\n######").unwrap();
+    assert_eq!(analyze_level(&level), Solvability::LikelyUnsolvable);
+}
+
+#[test]
+fn analyze_level_state_limit() {
+    let level = Level::from_str("w: 6, h: 4\n######\n#----#\n#p@-x#\n######").unwrap();
+    assert_eq!(analyze_level_with_limit(&level, 2), Solvability::Unknown);
+}
+
+#[test]
+fn find_solution_with_cache() {
+    let level = Level::from_str("w: 6, h: 3\n######\n#p@-x#\n######").unwrap();
+    assert_eq!(find_solution(&level, &level, (1, 1)), Some(vec![Direction::Right, Direction::Right]));
+
+    let mut playing_level = PlayingLevel::new(&level, 4).unwrap();
+    assert_eq!(find_next_push(&playing_level, &[Direction::Right]), Some(((2, 1), Direction::Right)));
+
+    let mut solution_cache = SolutionCache::new();
+    assert_eq!(solution_cache.solution(&playing_level), Some(&[Direction::Right, Direction::Right][..]));
+
+    //The state after the first move was cached with the first solution
+    assert!(playing_level.replay_moves(&[Direction::Right]));
+    assert_eq!(solution_cache.solutions.len(), 3);
+    assert_eq!(solution_cache.solution(&playing_level), Some(&[Direction::Right][..]));
+
+    let level = Level::from_str("w: 6, h: 4\n######\n#-@--#\n#p--x#\n######").unwrap();
+    assert_eq!(find_solution(&level, &level, (1, 2)), None);
+}
+
+#[test]
+fn validate_unvalidated_levels_with_solver() {
+    let lvl_data = "Name: Test\nLevels: 2\n\nw: 6, h: 3\n######\n#p@-x#\n######\n\nw: 4, h: 1\np@-x";
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test", lvl_data, true).unwrap();
+    level_pack.set_level_best_moves(1, Some(5));
+
+    assert_eq!(validate_unvalidated_levels(&mut level_pack), Ok(1));
+    assert_eq!(level_pack.levels()[0].best_moves(), Some(2));
+    assert_eq!(level_pack.levels()[0].validation_moves(), Some(&[Direction::Right, Direction::Right][..]));
+    assert_eq!(level_pack.solver_validated_level_count(), 1);
+    assert!(!level_pack.levels()[1].is_solver_validated());
+
+    //Invalidated levels are no longer marked as solver validated
+    level_pack.set_level_best_moves(0, None);
+    assert_eq!(level_pack.solver_validated_level_count(), 0);
+
+    let lvl_data = "Name: Test\nLevels: 2\n\nw: 4, h: 1\np@-x\n\nw: 6, h: 4\n######\n#-@--#\n#p--x#\n######";
+    let mut level_pack = LevelPack::from_level_pack_data("test", "test", lvl_data, true).unwrap();
+    assert_eq!(validate_unvalidated_levels(&mut level_pack), Err(vec![1]));
+    assert_eq!(level_pack.levels()[0].best_moves(), None);
+}
+
+#[test]
+fn find_level_problems_valid_level() {
+    let level = Level::from_str("w: 6, h: 3\n######\n#p@*x#\n###=##").unwrap();
+    assert!(find_level_problems(&level).is_empty());
+
+    let level = Level::from_str("w: 4, h: 1\np@-x").unwrap();
+    assert!(find_level_problems(&level).is_empty());
+}
+
+#[test]
+fn find_level_problems_invalid_level() {
+    let level = Level::from_str("w: 7, h: 3\n#######\n#-@x#x#\n###=###").unwrap();
+    assert_eq!(find_level_problems(&level), [
+        LevelProblem::NoPlayer,
+        LevelProblem::TooFewBoxes { box_count: 1, goal_count: 2 },
+        LevelProblem::MissingKeys(1),
+    ]);
+
+    let level = Level::from_str("w: 7, h: 3\n#######\n#p@@#x#\n#######").unwrap();
+    assert_eq!(find_level_problems(&level), [
+        LevelProblem::TooManyBoxes { box_count: 2, goal_count: 1 },
+        LevelProblem::UnreachableGoals(1),
+    ]);
+}
+
+#[test]
+fn tile_counts_of_levels() {
+    let level = Level::from_str("w: 6, h: 3\n######\n#p@*x#\n###=##").unwrap();
+    let tile_counts = TileCounts::of_level(&level);
+    assert_eq!(tile_counts, TileCounts {
+        player_count: 1,
+        box_count: 1,
+        goal_count: 1,
+        hole_count: 0,
+        key_count: 1,
+        door_count: 1,
+    });
+    assert!(tile_counts.is_valid());
+
+    let level = Level::from_str("w: 7, h: 3\n#######\n#-@x#x#\n###=###").unwrap();
+    let tile_counts = TileCounts::of_level(&level);
+    assert!(!tile_counts.is_player_count_valid());
+    assert!(!tile_counts.is_box_count_valid());
+    assert!(!tile_counts.is_key_count_valid());
+}
+
+#[test]
+fn remix_level_is_solvable_and_deterministic() {
+    let level = Level::from_str("w: 7, h: 5\n#######\n#-----#\n#p-@-x#\n#-----#\n#######").unwrap();
+
+    let remixed_levels = (0..8).
+            filter_map(|seed| remix_level(&level, seed).map(|remixed_level| (seed, remixed_level))).
+            collect::<Vec<_>>();
+    assert!(!remixed_levels.is_empty());
+
+    for (seed, remixed_level) in remixed_levels {
+        assert_ne!(remixed_level.tiles(), level.tiles());
+        assert_eq!(analyze_level(&remixed_level), Solvability::Solvable);
+        assert_eq!(remix_level(&level, seed).unwrap().tiles(), remixed_level.tiles());
+    }
+
+    //Levels without boxes outside goals can not be remixed
+    let level = Level::from_str("w: 4, h: 1\np--x").unwrap();
+    assert!(remix_level(&level, 42).is_none());
+}
+
+#[test]
+fn find_min_push_count_of_levels() {
+    let level = Level::from_str("w: 6, h: 1\np-@--x").unwrap();
+    assert_eq!(find_min_push_count(&level), Some(3));
+
+    let level = Level::from_str("w: 7, h: 5\n#######\n#p----#\n#-@---#\n#----x#\n#######").unwrap();
+    assert_eq!(find_min_push_count(&level), Some(4));
+
+    let level = Level::from_str("w: 6, h: 4\n######\n#-@--#\n#p--x#\n######").unwrap();
+    assert_eq!(find_min_push_count(&level), None);
+}
+
+#[test]
+fn min_push_counts_round_trip() {
+    let results = HashMap::from([(0x0123456789abcdef, Some(12)), (42, None)]);
+
+    let data = min_push_counts_to_str(&results, "1.0.0");
+    assert_eq!(parse_min_push_counts(&data, "1.0.0"), results);
+
+    //Results of other game versions are discarded
+    assert!(parse_min_push_counts(&data, "1.1.0").is_empty());
+}
+
+#[test]
+fn estimate_level_difficulty_of_levels() {
+    let level = Level::from_str("w: 4, h: 1\np@-x").unwrap();
+    assert_eq!(estimate_level_difficulty(&level), Some(LevelDifficulty::Easy));
+
+    let level = Level::from_str("w: 6, h: 4\n######\n#-@--#\n#p--x#\n######").unwrap();
+    assert_eq!(estimate_level_difficulty(&level), None);
+
+    assert_eq!(LevelDifficulty::from_score(difficulty_score(300, 2, 0.2)), LevelDifficulty::Medium);
+    assert_eq!(LevelDifficulty::from_score(difficulty_score(MAX_STATE_COUNT, 6, 0.5)), LevelDifficulty::Demon);
+}
+
+#[test]
+fn estimate_level_pack_difficulty_from_median_level() {
+    assert_eq!(estimate_level_pack_difficulty([]), None);
+    assert_eq!(estimate_level_pack_difficulty([LevelDifficulty::Hard, LevelDifficulty::Easy, LevelDifficulty::Medium]), Some(LevelDifficulty::Medium));
+    assert_eq!(estimate_level_pack_difficulty([LevelDifficulty::Easy, LevelDifficulty::Demon]), Some(LevelDifficulty::Demon));
+}
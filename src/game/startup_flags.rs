@@ -0,0 +1,104 @@
+use std::sync::OnceLock;
+
+#[cfg(test)]
+mod tests;
+
+/// Command line flag which disables all audio (The audio device is not opened)
+pub const NO_AUDIO_ARG: &str = "--no-audio";
+/// Command line flag which mutes the background music regardless of the settings
+pub const MUTE_MUSIC_ARG: &str = "--mute-music";
+/// Command line flag which disables flashing and decorative animations
+pub const REDUCED_MOTION_ARG: &str = "--reduced-motion";
+/// Command line flag which stores the save game files in a folder next to the executable
+pub const PORTABLE_ARG: &str = "--portable";
+
+const NO_AUDIO_ENV_VAR: &str = "SOKOTERM_NO_AUDIO";
+const MUTE_MUSIC_ENV_VAR: &str = "SOKOTERM_MUTE_MUSIC";
+const REDUCED_MOTION_ENV_VAR: &str = "SOKOTERM_REDUCED_MOTION";
+const PORTABLE_ENV_VAR: &str = "SOKOTERM_PORTABLE";
+
+static STARTUP_FLAGS: OnceLock<StartupFlags> = OnceLock::new();
+
+/// Flags of the command line or of environment variables which override the settings for the current session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StartupFlags {
+    no_audio: bool,
+    mute_music: bool,
+    reduced_motion: bool,
+    portable: bool,
+}
+
+impl StartupFlags {
+    /// Reads the flags of the environment variables, variables which are set to "", "0", or "false" are ignored
+    pub fn from_env_vars(get_env_var: impl Fn(&str) -> Option<String>) -> Self {
+        let is_set = |name| get_env_var(name).is_some_and(|value| !matches!(value.trim(), "" | "0" | "false"));
+
+        Self {
+            no_audio: is_set(NO_AUDIO_ENV_VAR),
+            mute_music: is_set(MUTE_MUSIC_ENV_VAR),
+            reduced_motion: is_set(REDUCED_MOTION_ENV_VAR),
+            portable: is_set(PORTABLE_ENV_VAR),
+        }
+    }
+
+    /// Returns the flags which are set in any of both flags
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            no_audio: self.no_audio || other.no_audio,
+            mute_music: self.mute_music || other.mute_music,
+            reduced_motion: self.reduced_motion || other.reduced_motion,
+            portable: self.portable || other.portable,
+        }
+    }
+
+    pub fn no_audio(self) -> bool {
+        self.no_audio
+    }
+
+    /// The background music is also muted if audio is disabled
+    pub fn mute_music(self) -> bool {
+        self.mute_music || self.no_audio
+    }
+
+    pub fn reduced_motion(self) -> bool {
+        self.reduced_motion
+    }
+
+    pub fn portable(self) -> bool {
+        self.portable
+    }
+}
+
+/// Splits the command line arguments into the startup flags and all other arguments
+pub fn split_startup_flag_args(args: impl IntoIterator<Item = String>) -> (StartupFlags, Vec<String>) {
+    let mut startup_flags = StartupFlags::default();
+    let mut other_args = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            NO_AUDIO_ARG => startup_flags.no_audio = true,
+            MUTE_MUSIC_ARG => startup_flags.mute_music = true,
+            REDUCED_MOTION_ARG => startup_flags.reduced_motion = true,
+            PORTABLE_ARG => startup_flags.portable = true,
+
+            _ => other_args.push(arg),
+        }
+    }
+
+    (startup_flags, other_args)
+}
+
+/// Parses the startup flags of the process, this should be called before the UI is initialized
+pub fn init() {
+    let _ = startup_flags();
+}
+
+/// Returns the startup flags of the command line arguments and environment variables of the process
+pub fn startup_flags() -> StartupFlags {
+    *STARTUP_FLAGS.get_or_init(|| {
+        let (startup_flags, _) = split_startup_flag_args(std::env::args().skip(1));
+
+        startup_flags.union(StartupFlags::from_env_vars(|name| std::env::var(name).ok()))
+    })
+}
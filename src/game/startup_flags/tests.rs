@@ -0,0 +1,35 @@
+use crate::game::startup_flags::*;
+
+#[test]
+fn startup_flag_arg_splitting() {
+    let args = ["a.lvl", "--no-audio", "--reduced-motion", "b.lvl"].map(String::from);
+    let (startup_flags, other_args) = split_startup_flag_args(args);
+    assert!(startup_flags.no_audio());
+    assert!(startup_flags.mute_music());
+    assert!(startup_flags.reduced_motion());
+    assert!(!startup_flags.portable());
+    assert_eq!(other_args, ["a.lvl", "b.lvl"]);
+
+    let (startup_flags, other_args) = split_startup_flag_args(["--portable".to_string(), "c.lvl".to_string()]);
+    assert!(startup_flags.portable());
+    assert_eq!(other_args, ["c.lvl"]);
+
+    let (startup_flags, other_args) = split_startup_flag_args(["--mute-music".to_string()]);
+    assert_eq!((startup_flags.no_audio(), startup_flags.mute_music(), startup_flags.reduced_motion()), (false, true, false));
+    assert!(other_args.is_empty());
+}
+
+#[test]
+fn startup_flags_from_env_vars() {
+    let startup_flags = StartupFlags::from_env_vars(|name| match name {
+        "SOKOTERM_NO_AUDIO" => Some("0".to_string()),
+        "SOKOTERM_MUTE_MUSIC" => Some("1".to_string()),
+        "SOKOTERM_REDUCED_MOTION" => Some(String::new()),
+        _ => None,
+    });
+    assert_eq!((startup_flags.no_audio(), startup_flags.mute_music(), startup_flags.reduced_motion()), (false, true, false));
+
+    let (arg_startup_flags, _) = split_startup_flag_args(["--reduced-motion".to_string()]);
+    let startup_flags = startup_flags.union(arg_startup_flags);
+    assert_eq!((startup_flags.no_audio(), startup_flags.mute_music(), startup_flags.reduced_motion()), (false, true, true));
+}
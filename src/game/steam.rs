@@ -1,16 +1,27 @@
+use std::collections::VecDeque;
 use std::error::Error;
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 use bevy::prelude::*;
-use bevy_steamworks::{AppId, CallbackResult, Client, FileType, PublishedFileId, SteamError, SteamworksEvent};
-use crate::game::Game;
+use bevy_steamworks::{AppId, CallbackResult, Client, FileType, ItemState, PublishedFileId, SteamError, SteamworksEvent};
+use crate::game::{Game, GameError};
+use crate::game::audio;
 use crate::game::level::LevelPack;
+use crate::game::logging::{log_error, log_info, log_warn};
+use crate::game::workshop;
 
 pub mod achievement;
+pub mod rich_presence;
 
-pub const APP_ID: AppId = AppId(4160140);
+pub const APP_ID: AppId = AppId(workshop::STEAM_APP_ID);
 
 static USER_STATS_RECEIVED: AtomicBool = AtomicBool::new(false);
 
+/// Results of unsubscribe requests, they are received asynchronously from Steam
+static UNSUBSCRIBE_RESULTS: Mutex<VecDeque<(PublishedFileId, Result<(), SteamError>)>> = Mutex::new(VecDeque::new());
+
 pub fn steam_init(
     steam_client: Res<Client>,
 ) {
@@ -23,7 +34,7 @@ pub fn steam_callback(
     for event in steamworks_event.read() {
         let SteamworksEvent::CallbackResult(event) = event;
 
-        info!("Received steam event: {event:?}");
+        log_info!("Received steam event: {event:?}");
 
         #[expect(clippy::single_match)]
         match event {
@@ -33,7 +44,7 @@ pub fn steam_callback(
                         USER_STATS_RECEIVED.store(true, Ordering::Relaxed);
                     },
                     Err(err) => {
-                        error!("{err}");
+                        log_error!("{err}");
                     },
                 }
             }
@@ -58,7 +69,13 @@ pub fn prepare_workshop_upload_temp_data(level_pack: &LevelPack) -> Result<(), B
 
     tmp_upload_path.push("pack.lvl");
 
-    level_pack.export_editor_level_pack_to_path(tmp_upload_path)?;
+    level_pack.export_editor_level_pack_to_path(&tmp_upload_path)?;
+
+    //Sound effects are stored next to the level pack file in the same way as for editor level packs
+    let mut sound_effects_folder = OsString::from(level_pack.path());
+    sound_effects_folder.push(audio::PACK_SOUND_EFFECTS_FOLDER_POSTFIX);
+    tmp_upload_path.push(audio::PACK_SOUND_EFFECTS_FOLDER_POSTFIX);
+    audio::copy_pack_sound_effects(Path::new(&sound_effects_folder), Path::new(&tmp_upload_path))?;
 
     Ok(())
 }
@@ -69,3 +86,87 @@ pub fn crate_workshop_item<F>(
 ) where F: FnOnce(std::result::Result<(PublishedFileId, bool), SteamError>) + 'static + Send {
     steam_client.ugc().create_item(APP_ID, FileType::Community, callback);
 }
+
+/// Rates the Steam Workshop item with a thumbs up or a thumbs down (A previous vote of the player is replaced)
+pub fn vote_workshop_item(steam_client: &Client, workshop_id: PublishedFileId, vote_up: bool) {
+    steam_client.ugc().set_user_item_vote(workshop_id, vote_up, move |result| {
+        match result {
+            Ok(()) => log_info!("Voted for workshop item {} (Up: {})", workshop_id.0, vote_up),
+            Err(err) => log_warn!("Cannot vote for workshop item {}: {}", workshop_id.0, err),
+        }
+    });
+}
+
+/// A subscribed Steam Workshop item with its local install state
+#[derive(Debug, Clone)]
+pub struct WorkshopSubscription {
+    workshop_id: PublishedFileId,
+    state: ItemState,
+    size_on_disk: Option<u64>,
+    //Unix time of the last update of the installed version
+    last_updated: Option<u32>,
+}
+
+impl WorkshopSubscription {
+    pub fn workshop_id(&self) -> PublishedFileId {
+        self.workshop_id
+    }
+
+    pub fn size_on_disk(&self) -> Option<u64> {
+        self.size_on_disk
+    }
+
+    pub fn last_updated(&self) -> Option<u32> {
+        self.last_updated
+    }
+
+    pub fn is_update_available(&self) -> bool {
+        self.state.contains(ItemState::NEEDS_UPDATE)
+    }
+
+    pub fn is_downloading(&self) -> bool {
+        self.state.intersects(ItemState::DOWNLOADING | ItemState::DOWNLOAD_PENDING)
+    }
+
+    pub fn is_installed(&self) -> bool {
+        self.state.contains(ItemState::INSTALLED)
+    }
+}
+
+/// Returns all subscribed Steam Workshop items with their state from the UGC item state API
+pub fn workshop_subscriptions(steam_client: &Client) -> Vec<WorkshopSubscription> {
+    let ugc = steam_client.ugc();
+
+    ugc.subscribed_items(false).into_iter().
+            map(|workshop_id| {
+                let install_info = ugc.item_install_info(workshop_id);
+
+                WorkshopSubscription {
+                    workshop_id,
+                    state: ugc.item_state(workshop_id),
+                    size_on_disk: install_info.as_ref().map(|install_info| install_info.size_on_disk),
+                    last_updated: install_info.map(|install_info| install_info.timestamp),
+                }
+            }).
+            collect()
+}
+
+/// Requests a download of the item with high priority (Missing or outdated files are downloaded again)
+pub fn redownload_workshop_item(steam_client: &Client, workshop_id: PublishedFileId) -> Result<(), Box<dyn Error>> {
+    if !steam_client.ugc().download_item(workshop_id, true) {
+        return Err(Box::new(GameError::new(format!("The download of the workshop item {} could not be started!", workshop_id.0))));
+    }
+
+    Ok(())
+}
+
+/// Unsubscribes from the item, the result can be received with [take_unsubscribe_results]
+pub fn unsubscribe_workshop_item(steam_client: &Client, workshop_id: PublishedFileId) {
+    steam_client.ugc().unsubscribe_item(workshop_id, move |result| {
+        UNSUBSCRIBE_RESULTS.lock().unwrap().push_back((workshop_id, result));
+    });
+}
+
+pub fn take_unsubscribe_results() -> Vec<(PublishedFileId, Result<(), SteamError>)> {
+    UNSUBSCRIBE_RESULTS.lock().unwrap().drain(..).collect()
+}
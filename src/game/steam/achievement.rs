@@ -1,6 +1,9 @@
 use std::sync::atomic::Ordering;
-use bevy::prelude::*;
 use bevy_steamworks::Client;
+use crate::game::GameState;
+use crate::game::achievements::{TotalStat, TotalStats};
+use crate::game::events::{GameEventListener, LevelCompletedEvent};
+use crate::game::logging::{log_error, log_info};
 use crate::game::steam::USER_STATS_RECEIVED;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,24 +33,114 @@ impl Achievement {
     achievement! { STEAM_WORKSHOP_LEVEL_PACK_CREATED }
 
     pub fn unlock(&self, steam_client: Client) {
-        info!("Steam achievement unlocked: {}", self.id);
+        log_info!("Steam achievement unlocked: {}", self.id);
 
         if !USER_STATS_RECEIVED.load(Ordering::Relaxed) {
-            error!("Steam stats were not received yet!");
+            log_error!("Steam stats were not received yet!");
 
             return;
         }
 
         let ret = steam_client.user_stats().achievement(self.id).set();
         if ret.is_err() {
-            error!("Could not set achievement \"{}\"!", self.id);
+            log_error!("Could not set achievement \"{}\"!", self.id);
 
             return;
         }
 
         let ret = steam_client.user_stats().store_stats();
         if ret.is_err() {
-            error!("Could not save achievement progress!");
+            log_error!("Could not save achievement progress!");
+        }
+    }
+}
+
+/// Total stats which are mirrored to Steam stats (The progress achievements of these stats are unlocked by Steam)
+const STEAM_TOTAL_STATS: [(TotalStat, &str); 2] = [
+    (TotalStat::LevelsCompleted, "TOTAL_LEVELS_COMPLETED"),
+    (TotalStat::Pushes, "TOTAL_PUSHES"),
+];
+
+pub fn store_total_stats(steam_client: &Client, total_stats: &TotalStats) {
+    if !USER_STATS_RECEIVED.load(Ordering::Relaxed) {
+        log_error!("Steam stats were not received yet!");
+
+        return;
+    }
+
+    let user_stats = steam_client.user_stats();
+    for (stat, steam_stat_id) in STEAM_TOTAL_STATS {
+        let value = total_stats.value(stat).min(i32::MAX as u64) as i32;
+        if user_stats.set_stat_i32(steam_stat_id, value).is_err() {
+            log_error!("Could not set stat \"{}\"!", steam_stat_id);
+        }
+    }
+
+    let ret = user_stats.store_stats();
+    if ret.is_err() {
+        log_error!("Could not save stats!");
+    }
+}
+
+/// Unlocks the level pack achievements if levels are completed
+pub struct AchievementEventListener;
+
+impl GameEventListener for AchievementEventListener {
+    fn on_level_completed(&mut self, game_state: &mut GameState, event: &LevelCompletedEvent) {
+        if !event.is_progress_saved {
+            return;
+        }
+
+        let Some(level_pack) = game_state.level_packs.get(event.level_pack_index) else {
+            return;
+        };
+
+        if level_pack.id() == "main" && event.level_index == level_pack.level_count() - 1 && event.moves < 150 {
+            Achievement::LEVEL_PACK_MAIN_FINAL_LEVEL_CHALLENGE.unlock(game_state.steam_client.clone());
+        }
+    }
+
+    fn on_pack_completed(&mut self, game_state: &mut GameState, level_pack_index: usize) {
+        let Some(level_pack) = game_state.level_packs.get(level_pack_index) else {
+            return;
+        };
+
+        //Level packs which were completed in relaxed mode have no best time and do not count
+        let (Some(_), Some(best_time_sum)) = (level_pack.level_pack_best_moves_sum(), level_pack.level_pack_best_time_sum()) else {
+            return;
+        };
+
+        let steam_client = game_state.steam_client.clone();
+        match level_pack.id() {
+            "tutorial" => {
+                Achievement::LEVEL_PACK_TUTORIAL_COMPLETED.unlock(steam_client.clone());
+
+                if best_time_sum < 6000 {
+                    Achievement::LEVEL_PACK_TUTORIAL_FAST.unlock(steam_client.clone());
+                }
+            },
+
+            "main" => {
+                Achievement::LEVEL_PACK_MAIN_COMPLETED.unlock(steam_client.clone());
+            },
+
+            "special" => {
+                Achievement::LEVEL_PACK_SPECIAL_COMPLETED.unlock(steam_client.clone());
+            },
+
+            "demon" => {
+                Achievement::LEVEL_PACK_DEMON_COMPLETED.unlock(steam_client.clone());
+            },
+
+            "secret" => {
+                Achievement::LEVEL_PACK_SECRET_COMPLETED.unlock(steam_client.clone());
+            },
+
+            _ => {},
+        }
+
+        if level_pack.steam_level_pack_data().is_some() {
+            Achievement::STEAM_WORKSHOP_LEVEL_PACK_COMPLETED.unlock(steam_client);
         }
     }
 }
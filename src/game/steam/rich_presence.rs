@@ -0,0 +1,61 @@
+use bevy_steamworks::Client;
+use crate::game::GameState;
+use crate::game::events::GameEventListener;
+use crate::game::logging::log_warn;
+use crate::game::screen::ScreenId;
+
+/// Rich presence states which are shown to Steam friends.
+///
+/// The `steam_display` values are localization tokens which are defined in `build/steam/rich_presence_localization.vdf`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RichPresence<'a> {
+    InMenu,
+    Playing {
+        level_pack_name: &'a str,
+        level: usize,
+    },
+    BrowsingLevelPackEditor,
+    EditingLevelPack {
+        level_pack_name: &'a str,
+    },
+}
+
+impl RichPresence<'_> {
+    pub fn update(&self, steam_client: &Client) {
+        let friends = steam_client.friends();
+
+        friends.clear_rich_presence();
+
+        let display_token = match self {
+            RichPresence::InMenu => "#Status_InMenu",
+
+            RichPresence::Playing { level_pack_name, level } => {
+                friends.set_rich_presence("level_pack", Some(level_pack_name));
+                friends.set_rich_presence("level", Some(&level.to_string()));
+
+                "#Status_Playing"
+            },
+
+            RichPresence::BrowsingLevelPackEditor => "#Status_BrowsingLevelPackEditor",
+
+            RichPresence::EditingLevelPack { level_pack_name } => {
+                friends.set_rich_presence("level_pack", Some(level_pack_name));
+
+                "#Status_EditingLevelPack"
+            },
+        };
+
+        if !friends.set_rich_presence("steam_display", Some(display_token)) {
+            log_warn!("Could not set steam rich presence to \"{display_token}\"!");
+        }
+    }
+}
+
+/// Updates the rich presence if the screen is changed
+pub struct RichPresenceEventListener;
+
+impl GameEventListener for RichPresenceEventListener {
+    fn on_screen_changed(&mut self, game_state: &mut GameState, _: &ScreenId) {
+        game_state.update_rich_presence();
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use crate::game::Game;
+use crate::utils;
+
+#[cfg(feature = "telemetry")]
+use crate::game::GameState;
+#[cfg(feature = "telemetry")]
+use crate::game::events::{GameEventListener, LevelCompletedEvent, LevelStartedEvent};
+#[cfg(feature = "telemetry")]
+use crate::game::logging::log_warn;
+#[cfg(feature = "telemetry")]
+use crate::game::screen::ScreenId;
+
+#[cfg(test)]
+mod tests;
+
+/// Max length of the telemetry endpoint URL of a level pack
+pub const MAX_ENDPOINT_LEN: usize = 200;
+
+/// Checks if the endpoint is a valid HTTP or HTTPS URL
+pub fn is_valid_endpoint(endpoint: &str) -> bool {
+    endpoint.len() <= MAX_ENDPOINT_LEN &&
+            (endpoint.starts_with("http://") || endpoint.starts_with("https://")) &&
+            endpoint.bytes().all(|c| c.is_ascii_graphic())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryEvent {
+    LevelStarted,
+    LevelRestarted,
+    LevelCompleted {
+        moves: usize,
+    },
+}
+
+/// Anonymous statistics of a single level (No player names, IDs, or times are recorded)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LevelTelemetry {
+    attempts: u32,
+    restarts: u32,
+    completions: u32,
+    completion_moves_sum: u64,
+}
+
+impl LevelTelemetry {
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    pub fn completions(&self) -> u32 {
+        self.completions
+    }
+
+    /// Returns the average count of moves of all completions or None if the level was not completed
+    pub fn average_moves(&self) -> Option<u64> {
+        (self.completions > 0).then(|| self.completion_moves_sum / self.completions as u64)
+    }
+
+    fn record(&mut self, event: TelemetryEvent) {
+        match event {
+            TelemetryEvent::LevelStarted => self.attempts = self.attempts.saturating_add(1),
+            TelemetryEvent::LevelRestarted => self.restarts = self.restarts.saturating_add(1),
+            TelemetryEvent::LevelCompleted { moves } => {
+                self.completions = self.completions.saturating_add(1);
+                self.completion_moves_sum = self.completion_moves_sum.saturating_add(moves as u64);
+            },
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Collects level statistics of level packs with a telemetry endpoint until they are uploaded
+///
+/// Statistics are only kept in memory, statistics which were not uploaded are discarded on exit
+#[derive(Debug, Default)]
+pub struct Telemetry {
+    //Level pack ID -> Statistics for each level
+    pending: HashMap<String, Vec<LevelTelemetry>>,
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, level_pack_id: &str, level_count: usize, level_index: usize, event: TelemetryEvent) {
+        if level_index >= level_count {
+            return;
+        }
+
+        let levels = self.pending.entry(level_pack_id.to_string()).
+                or_insert_with(|| vec![LevelTelemetry::default(); level_count]);
+        if levels.len() != level_count {
+            //The level pack was changed (e.g. by an update): Previous statistics would be assigned to the wrong levels
+            *levels = vec![LevelTelemetry::default(); level_count];
+        }
+
+        levels[level_index].record(event);
+    }
+
+    pub fn pending_level_telemetry(&self, level_pack_id: &str) -> Option<&[LevelTelemetry]> {
+        self.pending.get(level_pack_id).map(Vec::as_slice)
+    }
+
+    /// Removes the pending statistics of the level pack and returns them as a JSON document or None if nothing was recorded
+    pub fn take_report(&mut self, level_pack_id: &str) -> Option<String> {
+        let levels = self.pending.remove(level_pack_id)?;
+        if levels.iter().all(LevelTelemetry::is_empty) {
+            return None;
+        }
+
+        Some(Self::create_report(level_pack_id, &levels))
+    }
+
+    /// Uploads the pending statistics of the level pack in a background thread
+    #[cfg(feature = "telemetry")]
+    pub fn upload(&mut self, level_pack_id: &str, endpoint: &str) {
+        let Some(report) = self.take_report(level_pack_id) else {
+            return;
+        };
+
+        let endpoint = endpoint.to_string();
+        std::thread::spawn(move || {
+            let result = ureq::post(&endpoint).
+                    set("Content-Type", "application/json").
+                    send_string(&report);
+
+            if let Err(err) = result {
+                log_warn!("Cannot upload level statistics to \"{}\": {}", endpoint, err);
+            }
+        });
+    }
+
+    fn create_report(level_pack_id: &str, levels: &[LevelTelemetry]) -> String {
+        let mut report = String::new();
+
+        let _ = write!(
+            report, "{{\"game_version\":{},\"level_pack\":{},\"levels\":[",
+            utils::json_string(Game::VERSION), utils::json_string(level_pack_id),
+        );
+
+        let recorded_levels = levels.iter().
+                enumerate().
+                filter(|(_, level)| !level.is_empty());
+        for (i, (level_index, level)) in recorded_levels.enumerate() {
+            if i > 0 {
+                report.push(',');
+            }
+
+            let _ = write!(
+                report,
+                "{{\"level\":{},\"attempts\":{},\"restarts\":{},\"completions\":{},\"average_moves\":",
+                level_index + 1, level.attempts, level.restarts, level.completions,
+            );
+            match level.average_moves() {
+                Some(average_moves) => {
+                    let _ = write!(report, "{}}}", average_moves);
+                },
+                None => report.push_str("null}"),
+            }
+        }
+
+        report.push_str("]}");
+
+        report
+    }
+}
+
+/// Records started and completed levels and uploads the statistics of the level pack afterward
+///
+/// The plugin is only enabled if the player allowed telemetry
+#[cfg(feature = "telemetry")]
+#[derive(Debug, Default)]
+pub struct TelemetryEventListener {
+    telemetry: Telemetry,
+}
+
+#[cfg(feature = "telemetry")]
+impl TelemetryEventListener {
+    /// Records the event for the level if the level pack has a telemetry endpoint
+    fn record(&mut self, game_state: &GameState, level_pack_index: usize, level_index: usize, event: TelemetryEvent) {
+        let Some(level_pack) = game_state.level_packs.get(level_pack_index) else {
+            return;
+        };
+        if level_pack.telemetry_endpoint().is_none() {
+            return;
+        }
+
+        self.telemetry.record(level_pack.id(), level_pack.level_count(), level_index, event);
+    }
+
+    /// Uploads the recorded statistics of the current level pack in the background
+    fn upload(&mut self, game_state: &GameState) {
+        let Some(level_pack) = game_state.get_current_level_pack() else {
+            return;
+        };
+        let Some(telemetry_endpoint) = level_pack.telemetry_endpoint() else {
+            return;
+        };
+
+        self.telemetry.upload(level_pack.id(), telemetry_endpoint);
+    }
+}
+
+#[cfg(feature = "telemetry")]
+impl GameEventListener for TelemetryEventListener {
+    fn on_screen_changed(&mut self, game_state: &mut GameState, screen_id: &ScreenId) {
+        //Statistics of levels which were not completed are uploaded after leaving the level
+        if *screen_id == ScreenId::SelectLevel {
+            self.upload(game_state);
+        }
+    }
+
+    fn on_level_started(&mut self, game_state: &mut GameState, event: &LevelStartedEvent) {
+        let telemetry_event = if event.is_restart {
+            TelemetryEvent::LevelRestarted
+        }else {
+            TelemetryEvent::LevelStarted
+        };
+
+        self.record(game_state, event.level_pack_index, event.level_index, telemetry_event);
+    }
+
+    fn on_level_completed(&mut self, game_state: &mut GameState, event: &LevelCompletedEvent) {
+        //Remixes are not recorded, because the statistics are collected for the levels of the author
+        if event.remix_seed.is_none() {
+            self.record(game_state, event.level_pack_index, event.level_index, TelemetryEvent::LevelCompleted {
+                moves: event.moves as usize,
+            });
+        }
+
+        self.upload(game_state);
+    }
+
+    fn on_disabled(&mut self) {
+        self.telemetry = Telemetry::new();
+    }
+}
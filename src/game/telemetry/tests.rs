@@ -0,0 +1,37 @@
+use crate::game::Game;
+use crate::game::telemetry::*;
+
+#[test]
+fn report_contains_only_recorded_levels() {
+    let mut telemetry = Telemetry::new();
+    assert_eq!(telemetry.take_report("pack"), None);
+
+    telemetry.record("pack", 3, 1, TelemetryEvent::LevelStarted);
+    telemetry.record("pack", 3, 1, TelemetryEvent::LevelRestarted);
+    telemetry.record("pack", 3, 1, TelemetryEvent::LevelCompleted { moves: 10 });
+    telemetry.record("pack", 3, 1, TelemetryEvent::LevelCompleted { moves: 15 });
+    telemetry.record("pack", 3, 2, TelemetryEvent::LevelStarted);
+
+    //Invalid level index
+    telemetry.record("pack", 3, 3, TelemetryEvent::LevelStarted);
+
+    assert_eq!(telemetry.pending_level_telemetry("pack").unwrap()[1].average_moves(), Some(12));
+
+    assert_eq!(telemetry.take_report("pack"), Some(format!(
+        "{{\"game_version\":\"{}\",\"level_pack\":\"pack\",\"levels\":[\
+        {{\"level\":2,\"attempts\":1,\"restarts\":1,\"completions\":2,\"average_moves\":12}},\
+        {{\"level\":3,\"attempts\":1,\"restarts\":0,\"completions\":0,\"average_moves\":null}}]}}",
+        Game::VERSION,
+    )));
+    assert_eq!(telemetry.take_report("pack"), None);
+}
+
+#[test]
+fn endpoint_validation() {
+    assert!(is_valid_endpoint("https://example.com/sokoterm/stats"));
+    assert!(is_valid_endpoint("http://localhost:8080"));
+
+    assert!(!is_valid_endpoint("ftp://example.com"));
+    assert!(!is_valid_endpoint("https://example.com/a b"));
+    assert!(!is_valid_endpoint(&format!("https://{}", "a".repeat(MAX_ENDPOINT_LEN))));
+}
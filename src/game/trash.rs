@@ -0,0 +1,359 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt::Write as _;
+use std::io::ErrorKind;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::game::{Game, GameError};
+use crate::game::level::{LevelPack, LevelWithStats};
+
+#[cfg(test)]
+mod tests;
+
+/// Deleted editor level packs and levels can be restored for this many days before they are removed permanently
+pub const RETENTION_DAYS: u64 = 30;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+const TRASH_FOLDER: &str = "Trash";
+const ENTRY_FILE_NAME: &str = "entry.data";
+const LEVEL_FILE_NAME: &str = "level.lvl.edit";
+
+//Files of an editor level pack, the level pack data file is moved first
+const LEVEL_PACK_FILE_POSTFIXES: [&str; 3] = [".lvl.edit", ".lvl.edit.sav", ".lvl.edit.lock.sav"];
+
+/// Returns the current time in seconds since the unix epoch
+pub fn current_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrashEntryKind {
+    LevelPack,
+    Level {
+        level_index: usize,
+        best_time: Option<u64>,
+        best_moves: Option<u32>,
+    },
+}
+
+/// A deleted editor level pack or level which can be restored until it expires
+///
+/// Each entry is stored in its own folder in the trash folder of the save game folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashEntry {
+    folder: OsString,
+
+    kind: TrashEntryKind,
+    level_pack_id: String,
+    //Seconds since the unix epoch
+    deleted_at: u64,
+}
+
+impl TrashEntry {
+    pub fn kind(&self) -> TrashEntryKind {
+        self.kind
+    }
+
+    pub fn level_pack_id(&self) -> &str {
+        &self.level_pack_id
+    }
+
+    pub fn deleted_at(&self) -> u64 {
+        self.deleted_at
+    }
+
+    /// Returns the count of days (Rounded up) until the entry is removed permanently
+    pub fn remaining_days(&self, now: u64) -> u64 {
+        let expires_at = self.deleted_at.saturating_add(RETENTION_DAYS * SECONDS_PER_DAY);
+
+        expires_at.saturating_sub(now).div_ceil(SECONDS_PER_DAY)
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.remaining_days(now) == 0
+    }
+
+    /// Moves the files of the deleted level pack back to the save game folder and loads the level pack
+    ///
+    /// The entry must be deleted afterward
+    pub fn restore_level_pack(&self) -> Result<LevelPack, Box<dyn Error>> {
+        if self.kind != TrashEntryKind::LevelPack {
+            return Err(Box::new(GameError::new("The entry is not a level pack")));
+        }
+
+        let save_game_folder = Game::get_or_create_save_game_folder()?;
+
+        let mut level_pack_file = save_game_folder.clone();
+        level_pack_file.push(&self.level_pack_id);
+        level_pack_file.push(LEVEL_PACK_FILE_POSTFIXES[0]);
+        if std::fs::exists(&level_pack_file)? {
+            return Err(Box::new(GameError::new(format!("The level pack with the ID \"{}\" already exists!", self.level_pack_id))));
+        }
+
+        for postfix in LEVEL_PACK_FILE_POSTFIXES {
+            let trash_file = self.file_path(&format!("level_pack{}", postfix));
+            if !std::fs::exists(&trash_file)? {
+                continue;
+            }
+
+            let mut save_game_file = save_game_folder.clone();
+            save_game_file.push(&self.level_pack_id);
+            save_game_file.push(postfix);
+
+            std::fs::rename(trash_file, save_game_file)?;
+        }
+
+        let Some(level_pack_file) = level_pack_file.to_str() else {
+            return Err(Box::new(GameError::new("Invalid save game path")));
+        };
+
+        LevelPack::read_from_save_game(
+            &self.level_pack_id, level_pack_file, std::fs::read_to_string(level_pack_file)?, true,
+
+            #[cfg(feature = "steam")]
+            None,
+        )
+    }
+
+    /// Loads the deleted level with its validation stats
+    ///
+    /// The entry must be deleted afterward
+    pub fn restore_level(&self) -> Result<LevelWithStats, Box<dyn Error>> {
+        let TrashEntryKind::Level { best_time, best_moves, .. } = self.kind else {
+            return Err(Box::new(GameError::new("The entry is not a level")));
+        };
+
+        let level_file = self.file_path(LEVEL_FILE_NAME);
+        let Some(level_file) = level_file.to_str() else {
+            return Err(Box::new(GameError::new("Invalid save game path")));
+        };
+
+        let level_pack = LevelPack::from_level_pack_data(
+            &self.level_pack_id, level_file, &std::fs::read_to_string(level_file)?, true,
+        )?;
+
+        let Some(mut level) = level_pack.levels().first().cloned() else {
+            return Err(Box::new(GameError::new("The deleted level is missing")));
+        };
+        level.set_best_time(best_time);
+        level.set_best_moves(best_moves);
+
+        Ok(level)
+    }
+
+    /// Removes the entry permanently
+    pub fn delete(&self) -> Result<(), Box<dyn Error>> {
+        std::fs::remove_dir_all(&self.folder)?;
+
+        Ok(())
+    }
+
+    /// Parses the data of the entry file or returns None if the data is invalid
+    fn parse(folder: OsString, data: &str) -> Option<Self> {
+        let mut kind = None;
+        let mut level_pack_id = None;
+        let mut deleted_at = None;
+        let mut level_index = None;
+        let mut best_time = None;
+        let mut best_moves = None;
+
+        for line in data.lines().
+                filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line.split_once(" = ")?;
+
+            match key {
+                "kind" => kind = Some(value),
+                "level_pack_id" => level_pack_id = Some(value.to_string()),
+                "deleted_at" => deleted_at = Some(value.parse().ok()?),
+                "level_index" => level_index = Some(value.parse().ok()?),
+                "best_time" => best_time = Some(value.parse().ok()?),
+                "best_moves" => best_moves = Some(value.parse().ok()?),
+
+                //Unknown keys of newer game versions are ignored
+                _ => {},
+            }
+        }
+
+        let kind = match kind? {
+            "level_pack" => TrashEntryKind::LevelPack,
+            "level" => TrashEntryKind::Level {
+                level_index: level_index?,
+                best_time,
+                best_moves,
+            },
+
+            _ => return None,
+        };
+
+        Some(Self {
+            folder,
+
+            kind,
+            level_pack_id: level_pack_id?,
+            deleted_at: deleted_at?,
+        })
+    }
+
+    fn to_data(&self) -> String {
+        let mut data = String::new();
+
+        match self.kind {
+            TrashEntryKind::LevelPack => {
+                let _ = writeln!(data, "kind = level_pack");
+            },
+
+            TrashEntryKind::Level { level_index, best_time, best_moves } => {
+                let _ = writeln!(data, "kind = level");
+                let _ = writeln!(data, "level_index = {}", level_index);
+                if let Some(best_time) = best_time {
+                    let _ = writeln!(data, "best_time = {}", best_time);
+                }
+                if let Some(best_moves) = best_moves {
+                    let _ = writeln!(data, "best_moves = {}", best_moves);
+                }
+            },
+        }
+
+        let _ = writeln!(data, "level_pack_id = {}", self.level_pack_id);
+        let _ = writeln!(data, "deleted_at = {}", self.deleted_at);
+
+        data
+    }
+
+    fn file_path(&self, file_name: &str) -> OsString {
+        let mut file_path = self.folder.clone();
+        file_path.push(file_name);
+
+        file_path
+    }
+}
+
+fn get_or_create_trash_folder() -> Result<OsString, Box<dyn Error>> {
+    let mut trash_folder = Game::get_or_create_save_game_folder()?;
+    trash_folder.push(TRASH_FOLDER);
+    trash_folder.push("/");
+    std::fs::create_dir_all(&trash_folder)?;
+
+    Ok(trash_folder)
+}
+
+/// Creates the folder and the entry file of a new entry (Entries which are deleted at the same time get different folders)
+fn create_entry(kind: TrashEntryKind, level_pack_id: &str) -> Result<TrashEntry, Box<dyn Error>> {
+    let trash_folder = get_or_create_trash_folder()?;
+    let deleted_at = current_time();
+
+    let mut folder_index = 0;
+    let folder = loop {
+        let mut folder = trash_folder.clone();
+        folder.push(format!("{}-{}/", deleted_at, folder_index));
+
+        match std::fs::create_dir(&folder) {
+            Ok(()) => break folder,
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => folder_index += 1,
+            Err(err) => return Err(Box::new(err)),
+        }
+    };
+
+    let entry = TrashEntry {
+        folder,
+
+        kind,
+        level_pack_id: level_pack_id.to_string(),
+        deleted_at,
+    };
+    std::fs::write(entry.file_path(ENTRY_FILE_NAME), entry.to_data())?;
+
+    Ok(entry)
+}
+
+/// Moves the files of the editor level pack to the trash instead of deleting them
+pub fn move_level_pack_to_trash(level_pack: &LevelPack) -> Result<(), Box<dyn Error>> {
+    let entry = create_entry(TrashEntryKind::LevelPack, level_pack.id())?;
+    let save_game_folder = Game::get_or_create_save_game_folder()?;
+
+    for (i, postfix) in LEVEL_PACK_FILE_POSTFIXES.into_iter().enumerate() {
+        let mut save_game_file = save_game_folder.clone();
+        save_game_file.push(level_pack.id());
+        save_game_file.push(postfix);
+        if !std::fs::exists(&save_game_file)? {
+            continue;
+        }
+
+        if let Err(err) = std::fs::rename(save_game_file, entry.file_path(&format!("level_pack{}", postfix))) {
+            //Nothing was moved yet if the level pack data file could not be moved
+            if i == 0 {
+                let _ = entry.delete();
+            }
+
+            return Err(Box::new(err));
+        }
+    }
+
+    Ok(())
+}
+
+/// Stores a copy of the level in the trash before it is removed from the editor level pack
+pub fn move_level_to_trash(level_pack: &LevelPack, level_index: usize) -> Result<(), Box<dyn Error>> {
+    let Some(level) = level_pack.levels().get(level_index) else {
+        return Err(Box::new(GameError::new(format!("Level {} does not exist", level_index + 1))));
+    };
+
+    let entry = create_entry(TrashEntryKind::Level {
+        level_index,
+        best_time: level.best_time(),
+        best_moves: level.best_moves(),
+    }, level_pack.id())?;
+
+    let level_file = entry.file_path(LEVEL_FILE_NAME);
+    let mut trash_level_pack = LevelPack::new(level_pack.name(), level_pack.id(), level_file.to_string_lossy());
+    trash_level_pack.insert_level(0, level.clone());
+
+    if let Err(err) = trash_level_pack.write_editor_level_pack_data_to_path(level_file) {
+        let _ = entry.delete();
+
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+/// Returns all entries of the trash (The most recently deleted entry is first), invalid entries are ignored
+pub fn read_entries() -> Result<Vec<TrashEntry>, Box<dyn Error>> {
+    let trash_folder = get_or_create_trash_folder()?;
+
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(trash_folder)?.
+            filter(|entry| entry.as_ref().
+                    is_ok_and(|entry| entry.path().is_dir())).
+            map(|entry| entry.unwrap()) {
+        let mut folder = entry.path().into_os_string();
+        folder.push("/");
+
+        let mut entry_file = folder.clone();
+        entry_file.push(ENTRY_FILE_NAME);
+
+        let Ok(data) = std::fs::read_to_string(entry_file) else {
+            continue;
+        };
+
+        if let Some(entry) = TrashEntry::parse(folder, &data) {
+            entries.push(entry);
+        }
+    }
+
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then_with(|| b.folder.cmp(&a.folder)));
+
+    Ok(entries)
+}
+
+/// Permanently removes all entries which were deleted more than [RETENTION_DAYS] days ago
+pub fn remove_expired_entries() -> Result<(), Box<dyn Error>> {
+    let now = current_time();
+
+    for entry in read_entries()?.into_iter().
+            filter(|entry| entry.is_expired(now)) {
+        entry.delete()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::game::trash::*;
+
+fn entry(kind: TrashEntryKind, deleted_at: u64) -> TrashEntry {
+    TrashEntry {
+        folder: "Trash/0-0/".into(),
+
+        kind,
+        level_pack_id: "my_pack".to_string(),
+        deleted_at,
+    }
+}
+
+#[test]
+fn entry_data_round_trip() {
+    for kind in [
+        TrashEntryKind::LevelPack,
+        TrashEntryKind::Level { level_index: 4, best_time: Some(12345), best_moves: Some(67) },
+        TrashEntryKind::Level { level_index: 0, best_time: None, best_moves: None },
+    ] {
+        let entry = entry(kind, 1700000000);
+
+        assert_eq!(TrashEntry::parse("Trash/0-0/".into(), &entry.to_data()), Some(entry));
+    }
+
+    assert_eq!(TrashEntry::parse("Trash/0-0/".into(), "kind = level\nlevel_pack_id = my_pack\ndeleted_at = 1\n"), None);
+    assert_eq!(TrashEntry::parse("Trash/0-0/".into(), "kind = level_pack\nlevel_pack_id = my_pack\ndeleted_at = x\n"), None);
+    assert_eq!(TrashEntry::parse("Trash/0-0/".into(), "kind = level_pack\nlevel_pack_id = my_pack\n"), None);
+}
+
+#[test]
+fn entries_expire_after_retention_period() {
+    let entry = entry(TrashEntryKind::LevelPack, 1000);
+
+    assert_eq!(entry.remaining_days(1000), RETENTION_DAYS);
+    assert_eq!(entry.remaining_days(1001), RETENTION_DAYS);
+    assert_eq!(entry.remaining_days(1000 + 24 * 60 * 60), RETENTION_DAYS - 1);
+    assert!(!entry.is_expired(1000 + RETENTION_DAYS * 24 * 60 * 60 - 1));
+    assert!(entry.is_expired(1000 + RETENTION_DAYS * 24 * 60 * 60));
+}
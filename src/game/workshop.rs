@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests;
+
+/// App ID of the Steam version of the game (Workshop pages can be opened without Steam in a web browser)
+pub const STEAM_APP_ID: u32 = 4160140;
+
+/// Difficulty tags of which exactly one is set by the level pack upload
+pub const DIFFICULTY_TAGS: [&str; 4] = ["Easy", "Medium", "Hard", "Demon"];
+
+/// Gameplay tags which can optionally be set by the level pack upload
+pub const GAMEPLAY_TAGS: [&str; 3] = ["Fun", "Tricky", "Weird"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WorkshopSortOrder {
+    #[default]
+    TopRated,
+    MostRecent,
+}
+
+impl WorkshopSortOrder {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            WorkshopSortOrder::TopRated => "Top rated",
+            WorkshopSortOrder::MostRecent => "Most recent",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            WorkshopSortOrder::TopRated => WorkshopSortOrder::MostRecent,
+            WorkshopSortOrder::MostRecent => WorkshopSortOrder::TopRated,
+        }
+    }
+
+    fn browse_sort(self) -> &'static str {
+        match self {
+            WorkshopSortOrder::TopRated => "toprated",
+            WorkshopSortOrder::MostRecent => "mostrecent",
+        }
+    }
+}
+
+/// Filters for browsing the level packs of the Steam Workshop
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkshopFilter {
+    //None: Any difficulty
+    difficulty_tag: Option<&'static str>,
+    gameplay_tags: Vec<&'static str>,
+    sort_order: WorkshopSortOrder,
+}
+
+impl WorkshopFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn difficulty_tag(&self) -> Option<&'static str> {
+        self.difficulty_tag
+    }
+
+    /// Selects the next difficulty tag, "Any" follows after the last difficulty tag
+    pub fn select_next_difficulty_tag(&mut self) {
+        self.difficulty_tag = match self.difficulty_tag {
+            None => Some(DIFFICULTY_TAGS[0]),
+            Some(tag) => DIFFICULTY_TAGS.iter().
+                    skip_while(|difficulty_tag| **difficulty_tag != tag).
+                    nth(1).
+                    copied(),
+        };
+    }
+
+    /// Selects the previous difficulty tag, "Any" follows after the first difficulty tag
+    pub fn select_previous_difficulty_tag(&mut self) {
+        self.difficulty_tag = match self.difficulty_tag {
+            None => DIFFICULTY_TAGS.last().copied(),
+            Some(tag) => DIFFICULTY_TAGS.iter().
+                    rev().
+                    skip_while(|difficulty_tag| **difficulty_tag != tag).
+                    nth(1).
+                    copied(),
+        };
+    }
+
+    pub fn has_gameplay_tag(&self, tag: &str) -> bool {
+        self.gameplay_tags.contains(&tag)
+    }
+
+    /// Only level packs with all selected gameplay tags are shown
+    pub fn toggle_gameplay_tag(&mut self, tag: &'static str) {
+        if let Some(index) = self.gameplay_tags.iter().position(|gameplay_tag| *gameplay_tag == tag) {
+            self.gameplay_tags.remove(index);
+        }else {
+            self.gameplay_tags.push(tag);
+        }
+    }
+
+    pub fn sort_order(&self) -> WorkshopSortOrder {
+        self.sort_order
+    }
+
+    pub fn set_sort_order(&mut self, sort_order: WorkshopSortOrder) {
+        self.sort_order = sort_order;
+    }
+
+    /// Returns the URL of the Steam Workshop page which lists all level packs matching this filter
+    pub fn browse_url(&self) -> String {
+        let mut url = format!(
+            "https://steamcommunity.com/workshop/browse/?appid={}&browsesort={}&section=readytouseitems",
+            STEAM_APP_ID, self.sort_order.browse_sort(),
+        );
+
+        for tag in self.difficulty_tag.iter().chain(self.gameplay_tags.iter()) {
+            url += "&requiredtags%5B%5D=";
+            url += tag;
+        }
+
+        url
+    }
+}
@@ -0,0 +1,38 @@
+use crate::game::workshop::*;
+
+#[test]
+fn workshop_filter_difficulty_tag_cycle() {
+    let mut filter = WorkshopFilter::new();
+    assert_eq!(filter.difficulty_tag(), None);
+
+    for tag in DIFFICULTY_TAGS {
+        filter.select_next_difficulty_tag();
+        assert_eq!(filter.difficulty_tag(), Some(tag));
+    }
+
+    filter.select_next_difficulty_tag();
+    assert_eq!(filter.difficulty_tag(), None);
+
+    filter.select_previous_difficulty_tag();
+    assert_eq!(filter.difficulty_tag(), Some("Demon"));
+}
+
+#[test]
+fn workshop_filter_browse_url() {
+    let mut filter = WorkshopFilter::new();
+    assert_eq!(
+        filter.browse_url(),
+        "https://steamcommunity.com/workshop/browse/?appid=4160140&browsesort=toprated&section=readytouseitems",
+    );
+
+    filter.select_next_difficulty_tag();
+    filter.toggle_gameplay_tag("Tricky");
+    filter.toggle_gameplay_tag("Fun");
+    filter.toggle_gameplay_tag("Tricky");
+    filter.set_sort_order(WorkshopSortOrder::MostRecent);
+    assert_eq!(
+        filter.browse_url(),
+        "https://steamcommunity.com/workshop/browse/?appid=4160140&browsesort=mostrecent&section=readytouseitems\
+        &requiredtags%5B%5D=Easy&requiredtags%5B%5D=Fun",
+    );
+}
@@ -5,3 +5,6 @@ pub use console_lib::{Console, Key, Color};
 pub mod bevy_abstraction;
 #[cfg(feature = "gui")]
 pub use bevy_abstraction::{Console, Key, Color};
+
+#[cfg(all(feature = "headless", not(feature = "steam")))]
+pub mod headless;
@@ -5,6 +5,7 @@ use std::sync::{Arc, Mutex};
 use bevy::asset::{AssetServer, Handle};
 use bevy::image::Image;
 use smol_str::SmolStr;
+use crate::game::console_extension::MouseInput;
 use crate::game::level::Tile;
 use crate::game::TileMode;
 
@@ -187,10 +188,13 @@ pub enum GraphicalCharacter {
     BoxInHole = 17,
 
     Secret = 18,
+
+    WallVariant1 = 19,
+    WallVariant2 = 20,
 }
 
 impl GraphicalCharacter {
-    pub const VALUES: [Self; 19] = [
+    pub const VALUES: [Self; 21] = [
         GraphicalCharacter::Empty,
         GraphicalCharacter::FragileFloor,
         GraphicalCharacter::Ice,
@@ -210,6 +214,8 @@ impl GraphicalCharacter {
         GraphicalCharacter::Hole,
         GraphicalCharacter::BoxInHole,
         GraphicalCharacter::Secret,
+        GraphicalCharacter::WallVariant1,
+        GraphicalCharacter::WallVariant2,
     ];
 
     pub fn id(self) -> u8 {
@@ -244,6 +250,9 @@ impl GraphicalCharacter {
 
             18 => Some(GraphicalCharacter::Secret),
 
+            19 => Some(GraphicalCharacter::WallVariant1),
+            20 => Some(GraphicalCharacter::WallVariant2),
+
             _ => None,
         }
     }
@@ -283,6 +292,16 @@ impl GraphicalCharacter {
         }
     }
 
+    /// Returns the graphical variant of the tile, tiles without variants use their default graphic
+    pub fn from_tile_variant(val: Tile, variant: usize) -> Option<Self> {
+        match (val, variant) {
+            (Tile::Wall, 1) => Some(GraphicalCharacter::WallVariant1),
+            (Tile::Wall, 2) => Some(GraphicalCharacter::WallVariant2),
+
+            _ => Self::from_tile(val),
+        }
+    }
+
     pub fn into_image(self, asset_server: &AssetServer) -> Handle<Image> {
         match self {
             GraphicalCharacter::Empty => asset_server.load("embedded://textures/tiles/empty.png"),
@@ -294,7 +313,9 @@ impl GraphicalCharacter {
             GraphicalCharacter::OneWayRight => asset_server.load("embedded://textures/tiles/one_way_right.png"),
             GraphicalCharacter::OneWayDown => asset_server.load("embedded://textures/tiles/one_way_down.png"),
 
-            GraphicalCharacter::Wall => asset_server.load("embedded://textures/tiles/wall.png"),
+            //Wall variants are tinted versions of the default wall graphic
+            GraphicalCharacter::Wall | GraphicalCharacter::WallVariant1 | GraphicalCharacter::WallVariant2 =>
+                asset_server.load("embedded://textures/tiles/wall.png"),
 
             GraphicalCharacter::Key => asset_server.load("embedded://textures/tiles/key.png"),
             GraphicalCharacter::KeyInGoal => asset_server.load("embedded://textures/tiles/key_in_goal.png"),
@@ -313,6 +334,16 @@ impl GraphicalCharacter {
             GraphicalCharacter::Secret => asset_server.load("embedded://textures/tiles/secret.png"),
         }
     }
+
+    /// Returns the color which is multiplied with the tile graphic
+    pub fn tint(self) -> bevy::color::Color {
+        match self {
+            GraphicalCharacter::WallVariant1 => bevy::color::Color::srgb(0.9, 0.9, 0.9),
+            GraphicalCharacter::WallVariant2 => bevy::color::Color::srgb(1.0, 0.95, 0.88),
+
+            _ => bevy::color::Color::WHITE,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
@@ -394,7 +425,7 @@ pub struct ConsoleState {
     secondary_buffer: ConsoleDrawBuffer,
 
     input_queue_keyboard: VecDeque<Key>,
-    input_queue_mouse: VecDeque<(usize, usize)>,
+    input_queue_mouse: VecDeque<MouseInput>,
 }
 
 impl ConsoleState {
@@ -464,7 +495,7 @@ impl ConsoleState {
         &mut self.input_queue_keyboard
     }
 
-    pub fn input_queue_mouse_mut(&mut self) -> &mut VecDeque<(usize, usize)> {
+    pub fn input_queue_mouse_mut(&mut self) -> &mut VecDeque<MouseInput> {
         &mut self.input_queue_mouse
     }
 }
@@ -503,12 +534,12 @@ impl <'a> Console<'a> {
         self.state.lock().unwrap().input_queue_keyboard.pop_front()
     }
 
-    /// Returns the coordinates of the pos where a left click occurred as (x, y).
+    /// Returns the mouse button which was pressed or the mouse wheel movement.
     ///
-    /// x and y represent character positions.
+    /// The column and the row represent character positions.
     ///
-    /// If None, no left click occurred.
-    pub fn get_mouse_pos_clicked(&self) -> Option<(usize, usize)> {
+    /// If None, no mouse input occurred.
+    pub fn get_mouse_input_internal(&self) -> Option<MouseInput> {
         self.state.lock().unwrap().input_queue_mouse.pop_front()
     }
 
@@ -517,8 +548,8 @@ impl <'a> Console<'a> {
     /// Behavior for Non-ASCII strings is terminal dependent.
     ///
     /// Characters which are out of bounds will be ignored and not drawn.
-    pub fn draw_text(&self, text: impl Into<String>) {
-        let text = text.into();
+    pub fn draw_text(&self, text: impl AsRef<str>) {
+        let text = text.as_ref();
         if text.is_empty() {
             //Ignore empty text
 
@@ -563,17 +594,18 @@ impl <'a> Console<'a> {
         }
     }
 
-    pub fn draw_tile_internal(&self, tile: Tile, is_player_background: bool, inverted: bool) {
+    pub fn draw_tile_internal(&self, tile: Tile, variant: usize, is_player_background: bool, inverted: bool, background: Color) {
         let tile_mode = self.state.lock().unwrap().tile_mode;
         if tile_mode == TileMode::Graphical &&
-                let Some(graphical_character) = GraphicalCharacter::from_tile(tile) {
+                let Some(graphical_character) = GraphicalCharacter::from_tile_variant(tile, variant) {
+            //The background color is drawn as backdrop behind the tile graphic
             self.draw_graphical_character(
                 graphical_character,
                 if is_player_background { Color::Yellow } else { Color::Default },
-                if inverted { Color::Black } else { Color::Default },
+                if inverted { Color::Black } else { background },
             );
         }else {
-            tile.draw_raw(self, is_player_background, inverted);
+            tile.draw_raw(self, is_player_background, inverted, background);
         }
     }
 
@@ -731,6 +763,12 @@ impl Key {
     pub const DELETE: Key = Key(5017);
     pub const ENTER: Key = Key(5018);
     pub const TAB: Key = Key(5019);
+
+    //Navigation keys (Not available in the cli version)
+    pub const HOME: Key = Key(5020);
+    pub const END: Key = Key(5021);
+    pub const PAGE_UP: Key = Key(5022);
+    pub const PAGE_DOWN: Key = Key(5023);
 }
 
 impl Key {
@@ -847,11 +885,22 @@ impl Key {
             bevy::input::keyboard::Key::Enter => Key::ENTER,
             bevy::input::keyboard::Key::Tab => Key::TAB,
 
+            //Navigation keys
+            bevy::input::keyboard::Key::Home => Key::HOME,
+            bevy::input::keyboard::Key::End => Key::END,
+            bevy::input::keyboard::Key::PageUp => Key::PAGE_UP,
+            bevy::input::keyboard::Key::PageDown => Key::PAGE_DOWN,
+
             _ => return None,
         };
         Some(key)
     }
 
+    /// Converts a printable ASCII character to a key, letters are converted to their lowercase keys (Like [Key::A])
+    pub fn from_ascii(c: u8) -> Option<Self> {
+        (c.is_ascii_graphic() || c == b' ').then(|| Key(c.to_ascii_lowercase() as u16))
+    }
+
     pub fn is_arrow_key(&self) -> bool {
         (Key::LEFT..=Key::DOWN).contains(self)
     }
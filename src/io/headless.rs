@@ -0,0 +1,300 @@
+//! A headless console backend for end-to-end tests of screens
+//!
+//! The [TestHarness] runs the game with the draw buffer of the GUI console abstraction without opening a window,
+//! key presses are replayed from scripts and the rendered screen can be read as text.
+
+use std::error::Error;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::game::{profile, Game, GameError, TileMode};
+use crate::game::snapshot::GameStateSnapshot;
+use crate::io::bevy_abstraction::{Console, ConsoleState, Key};
+
+static BASE_SAVE_GAME_FOLDER_OVERRIDE: RwLock<Option<OsString>> = RwLock::new(None);
+
+//The save game folder and the active profile are global, therefore only one harness can exist at the same time
+static TEST_HARNESS_LOCK: Mutex<()> = Mutex::new(());
+static TEST_HARNESS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the save game folder of the active test harness (Used instead of the save game folder in the home directory)
+pub(crate) fn base_save_game_folder_override() -> Option<OsString> {
+    BASE_SAVE_GAME_FOLDER_OVERRIDE.read().unwrap().clone()
+}
+
+/// A console without a terminal or window, the rendered characters are stored in a buffer
+pub struct HeadlessConsole {
+    state: Arc<Mutex<ConsoleState>>,
+    console: &'static Console<'static>,
+}
+
+impl HeadlessConsole {
+    pub fn new() -> Self {
+        let mut state = ConsoleState::new::<74, 23>();
+        state.set_tile_mode(TileMode::Ascii);
+
+        let state = Arc::new(Mutex::new(state));
+
+        //The console must outlive the game (Same as in the GUI version)
+        let console = Box::leak(Box::new(Console::new(state.clone())));
+
+        Self { state, console }
+    }
+
+    pub fn console(&self) -> &'static Console<'static> {
+        self.console
+    }
+
+    pub fn push_key(&self, key: Key) {
+        self.state.lock().unwrap().input_queue_keyboard_mut().push_back(key);
+    }
+
+    /// Returns the text of the given row, graphical characters are returned as "?"
+    pub fn row_text(&self, row: usize) -> String {
+        let state = self.state.lock().unwrap();
+
+        state.primary_buffer().text_buffer().
+                chunks(Game::CONSOLE_MIN_WIDTH).
+                nth(row).
+                map(|row| row.iter().
+                        map(|character| character.get().unwrap_or(b'?') as char).
+                        collect::<String>()).
+                unwrap_or_default()
+    }
+
+    /// Returns the text of all rows separated by line breaks
+    pub fn screen_text(&self) -> String {
+        (0..Game::CONSOLE_MIN_HEIGHT).
+                map(|row| self.row_text(row)).
+                collect::<Vec<_>>().
+                join("\n")
+    }
+}
+
+impl Default for HeadlessConsole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs the game on a [HeadlessConsole] with a temporary save game folder
+///
+/// Scripts contain whitespace separated tokens:
+/// - Key names: `ENTER`, `ESC`, `TAB`, `DELETE`, `SPACE`, `LEFT`, `UP`, `RIGHT`, `DOWN`, and `F1` - `F12`
+/// - Single printable ASCII characters (e.g. `a` or `+`)
+/// - Quoted text which is typed character by character (e.g. `"my_pack"`)
+/// - `WAIT:<n>`: Runs n updates without input (e.g. for animations)
+///
+/// Key names and characters can be repeated with `*<n>` (e.g. `RIGHT*3`)
+pub struct TestHarness {
+    console: HeadlessConsole,
+    game: Game<'static>,
+
+    save_game_folder: PathBuf,
+
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl TestHarness {
+    /// The level packs (ID and level pack data) are loaded in the same way as level packs of the command line arguments
+    pub fn new(level_packs: &[(&str, &str)]) -> Result<Self, Box<dyn Error>> {
+        let lock = TEST_HARNESS_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let save_game_folder = std::env::temp_dir().join(format!(
+            "SokoTermTestHarness-{}-{}",
+            std::process::id(),
+            TEST_HARNESS_COUNT.fetch_add(1, Ordering::Relaxed),
+        ));
+        if std::fs::exists(&save_game_folder)? {
+            std::fs::remove_dir_all(&save_game_folder)?;
+        }
+
+        let level_pack_folder = save_game_folder.join("LevelPacks");
+        std::fs::create_dir_all(&level_pack_folder)?;
+
+        let mut base_save_game_folder = save_game_folder.clone().into_os_string();
+        base_save_game_folder.push("/");
+        *BASE_SAVE_GAME_FOLDER_OVERRIDE.write().unwrap() = Some(base_save_game_folder);
+        profile::set_active_profile(None);
+
+        let mut args = Vec::with_capacity(level_packs.len());
+        for (id, level_pack_data) in level_packs {
+            let level_pack_path = level_pack_folder.join(format!("{id}.lvl"));
+            std::fs::write(&level_pack_path, level_pack_data)?;
+
+            let Some(level_pack_path) = level_pack_path.to_str() else {
+                return Err(Box::new(GameError::new("Invalid level pack path")));
+            };
+
+            args.push(level_pack_path.to_string());
+        }
+
+        let console = HeadlessConsole::new();
+        let game = Game::new_with_args(console.console(), args)?;
+
+        let mut test_harness = Self {
+            console,
+            game,
+
+            save_game_folder,
+
+            _lock: lock,
+        };
+        test_harness.update();
+
+        Ok(test_harness)
+    }
+
+    pub fn game(&self) -> &Game<'static> {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut Game<'static> {
+        &mut self.game
+    }
+
+    pub fn save_game_folder(&self) -> &Path {
+        &self.save_game_folder
+    }
+
+    /// Restores a snapshot in the format of the snapshot file
+    pub fn restore_snapshot(&mut self, snapshot: &str) -> Result<(), Box<dyn Error>> {
+        self.game.restore_snapshot(&GameStateSnapshot::from_str(snapshot)?)?;
+        self.update();
+
+        Ok(())
+    }
+
+    /// Runs a single update and draws the game, true is returned if the game should exit
+    pub fn update(&mut self) -> bool {
+        let should_stop = self.game.update();
+        self.game.draw();
+
+        should_stop
+    }
+
+    pub fn update_times(&mut self, count: usize) {
+        for _ in 0..count {
+            self.update();
+        }
+    }
+
+    /// Only one key is processed per update
+    pub fn press_key(&mut self, key: Key) {
+        self.console.push_key(key);
+        self.update();
+    }
+
+    pub fn type_text(&mut self, text: &str) -> Result<(), GameError> {
+        for c in text.bytes() {
+            let Some(key) = Key::from_ascii(c) else {
+                return Err(GameError::new(format!("Cannot type character \"{}\"", c.escape_ascii())));
+            };
+
+            self.press_key(key);
+        }
+
+        Ok(())
+    }
+
+    pub fn run_script(&mut self, script: &str) -> Result<(), GameError> {
+        for token in script.split_whitespace() {
+            if let Some(text) = token.strip_prefix('"').and_then(|token| token.strip_suffix('"')) {
+                self.type_text(text)?;
+
+                continue;
+            }
+
+            if let Some(count) = token.strip_prefix("WAIT:") {
+                let Ok(count) = usize::from_str(count) else {
+                    return Err(GameError::new(format!("Invalid wait token \"{}\"", token)));
+                };
+
+                self.update_times(count);
+
+                continue;
+            }
+
+            let (key_name, count) = match token.rsplit_once('*') {
+                Some((key_name, count)) if !key_name.is_empty() => {
+                    let Ok(count) = usize::from_str(count) else {
+                        return Err(GameError::new(format!("Invalid repeat count in token \"{}\"", token)));
+                    };
+
+                    (key_name, count)
+                },
+
+                _ => (token, 1),
+            };
+
+            let Some(key) = Self::parse_key(key_name) else {
+                return Err(GameError::new(format!("Invalid key \"{}\"", key_name)));
+            };
+
+            for _ in 0..count {
+                self.press_key(key);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_key(key_name: &str) -> Option<Key> {
+        Some(match key_name {
+            "ENTER" => Key::ENTER,
+            "ESC" => Key::ESC,
+            "TAB" => Key::TAB,
+            "DELETE" => Key::DELETE,
+            "SPACE" => Key::SPACE,
+
+            "LEFT" => Key::LEFT,
+            "UP" => Key::UP,
+            "RIGHT" => Key::RIGHT,
+            "DOWN" => Key::DOWN,
+
+            "HOME" => Key::HOME,
+            "END" => Key::END,
+            "PAGE_UP" => Key::PAGE_UP,
+            "PAGE_DOWN" => Key::PAGE_DOWN,
+
+            "F1" => Key::F1,
+            "F2" => Key::F2,
+            "F3" => Key::F3,
+            "F4" => Key::F4,
+            "F5" => Key::F5,
+            "F6" => Key::F6,
+            "F7" => Key::F7,
+            "F8" => Key::F8,
+            "F9" => Key::F9,
+            "F10" => Key::F10,
+            "F11" => Key::F11,
+            "F12" => Key::F12,
+
+            key_name if key_name.len() == 1 => return Key::from_ascii(key_name.as_bytes()[0]),
+
+            _ => return None,
+        })
+    }
+
+    pub fn row_text(&self, row: usize) -> String {
+        self.console.row_text(row)
+    }
+
+    pub fn screen_text(&self) -> String {
+        self.console.screen_text()
+    }
+
+    pub fn screen_contains(&self, text: &str) -> bool {
+        self.screen_text().contains(text)
+    }
+}
+
+impl Drop for TestHarness {
+    fn drop(&mut self) {
+        *BASE_SAVE_GAME_FOLDER_OVERRIDE.write().unwrap() = None;
+
+        let _ = std::fs::remove_dir_all(&self.save_game_folder);
+    }
+}
@@ -0,0 +1,22 @@
+//! The game engine of SokoTerm
+//!
+//! The level model ([Level](game::level::Level), [Tile](game::level::Tile)), the movement simulation
+//! ([PlayingLevel](game::level::PlayingLevel)) and level pack IO ([LevelPack](game::level::LevelPack)) can be used by
+//! external tools (e.g. solvers, level pack converters, or bots).
+//!
+//! The game itself (Screens and the console of the selected build) is only available with feature `cli` or `gui`.
+
+#![allow(clippy::uninlined_format_args)]
+
+#[cfg(all(feature = "cli", feature = "gui"))]
+compile_error!("Features `cli` and `gui` are mutually exclusively");
+
+pub mod game;
+pub mod collections;
+pub mod io;
+pub mod utils;
+#[cfg(any(feature = "cli", feature = "gui"))]
+mod ui;
+
+#[cfg(any(feature = "cli", feature = "gui"))]
+pub use ui::run_game;
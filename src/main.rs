@@ -1,23 +1,20 @@
-#![allow(clippy::uninlined_format_args)]
-
 //Disable windows console window for GUI build
 #![cfg_attr(feature = "gui", windows_subsystem = "windows")]
 
-#[cfg(not(any(feature = "cli", feature = "gui")))]
-compile_error!("Either feature `cli` or `gui` must be enabled");
-
-#[cfg(all(feature = "cli", feature = "gui"))]
-compile_error!("Features `cli` and `gui` are mutually exclusively");
-
 use std::process::ExitCode;
-use ui::run_game;
 
-pub mod game;
-pub mod collections;
-pub mod io;
-pub mod utils;
-mod ui;
+#[cfg(any(feature = "cli", feature = "gui"))]
+fn main() -> ExitCode {
+    //Parsed before the UI is initialized in order to not open the audio device if audio is disabled
+    sokoterm::game::startup_flags::init();
 
+    sokoterm::run_game()
+}
+
+//The library can be built without UI (e.g. for external tools), but the game itself requires feature `cli` or `gui`
+#[cfg(not(any(feature = "cli", feature = "gui")))]
 fn main() -> ExitCode {
-    run_game()
+    eprintln!("SokoTerm was built without UI: Either feature `cli` or `gui` must be enabled");
+
+    ExitCode::FAILURE
 }
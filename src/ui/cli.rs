@@ -1,8 +1,8 @@
 use std::error::Error;
 use std::process::ExitCode;
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 use bevy_app::prelude::*;
-use bevy_app::ScheduleRunnerPlugin;
 use bevy_ecs::prelude::*;
 use bevy_time::prelude::*;
 use bevy_time::TimePlugin;
@@ -12,16 +12,51 @@ use crate::io::Console;
 #[cfg(unix)]
 mod linux_terminal_helper;
 
+mod clipboard;
+mod thumbnail;
+mod validate;
+
+#[cfg(feature = "bot")]
+mod bot;
+
+const UPDATE_INTERVAL: Duration = Duration::from_millis(40);
+/// Interval between updates if nothing was redrawn for a while (e.g. in static menus) to reduce the idle CPU usage
+///
+/// Keys which are pressed while the game is idle are processed with a delay of up to this interval
+const IDLE_UPDATE_INTERVAL: Duration = Duration::from_millis(120);
+/// Count of consecutive updates without redraws after which the game is idle
+const IDLE_UPDATE_COUNT: u32 = 3;
+
+/// Count of consecutive updates without redraws
+#[derive(Resource, Default)]
+struct UpdatesWithoutRedraw(u32);
+
 pub fn run_game() -> ExitCode {
+    if let Some(exit_code) = thumbnail::render_thumbnail_if_requested() {
+        return exit_code;
+    }
+
+    if let Some(exit_code) = validate::validate_pack_if_requested() {
+        return exit_code;
+    }
+
+    #[cfg(feature = "bot")]
+    if let Some(exit_code) = bot::run_bot_if_requested() {
+        return exit_code;
+    }
+
     #[cfg(unix)]
     if let Some(exit_code) = linux_terminal_helper::reopen_in_terminal_if_required() {
         return exit_code;
     }
 
     let console = Box::leak(Box::new(Console::new().unwrap()));
+    clipboard::enable_bracketed_paste();
 
     let ret = run_game_internal(console);
 
+    clipboard::disable_bracketed_paste();
+
     // Drop of Console must be called to restore the terminal mode
     //
     // SAFETY: The mutable reference &mut console is valid before calling "Box::from_raw()"
@@ -46,10 +81,11 @@ fn run_game_internal(console: &'static Console) -> Result<ExitCode, Box<dyn Erro
     app.
             add_plugins(TaskPoolPlugin::default()).
             add_plugins(TimePlugin).
-            add_plugins(ScheduleRunnerPlugin::run_loop(Duration::from_millis(40))).
+            set_runner(run_loop).
 
-            insert_resource(Time::<Fixed>::from_seconds(0.040)). //Run FixedUpdate every 40ms
+            insert_resource(Time::<Fixed>::from_duration(UPDATE_INTERVAL)). //Run FixedUpdate every 40ms
 
+            init_resource::<UpdatesWithoutRedraw>().
             insert_non_send_resource(game).
 
             add_systems(FixedUpdate, update_game);
@@ -63,13 +99,50 @@ fn run_game_internal(console: &'static Console) -> Result<ExitCode, Box<dyn Erro
     Ok(exit_code)
 }
 
+/// Runs the app like the loop of "ScheduleRunnerPlugin", but with a longer interval while the game is idle
+///
+/// FixedUpdate is run multiple times after longer intervals, therefore timers and animations are not slowed down
+fn run_loop(mut app: App) -> AppExit {
+    //All plugins of the CLI version are ready directly after they were added
+    app.finish();
+    app.cleanup();
+
+    loop {
+        let start_time = Instant::now();
+
+        app.update();
+        if let Some(exit) = app.should_exit() {
+            return exit;
+        }
+
+        let is_idle = app.world().resource::<UpdatesWithoutRedraw>().0 >= IDLE_UPDATE_COUNT;
+        let interval = if is_idle { IDLE_UPDATE_INTERVAL } else { UPDATE_INTERVAL };
+        if let Some(wait) = interval.checked_sub(start_time.elapsed()) {
+            thread::sleep(wait);
+        }
+    }
+}
+
 fn update_game(
     mut game: NonSendMut<Game>,
+    mut updates_without_redraw: ResMut<UpdatesWithoutRedraw>,
 
     mut app_exit_event_writer: MessageWriter<AppExit>,
 ) {
     let should_stop = game.update();
-    game.draw();
+
+    //Static screens are not redrawn every update to reduce the CPU usage (e.g. if the game is played over SSH)
+    if game.take_redraw_required() {
+        game.draw();
+
+        updates_without_redraw.0 = 0;
+    }else {
+        updates_without_redraw.0 = updates_without_redraw.0.saturating_add(1);
+    }
+
+    if let Some(text) = game.take_clipboard_text() {
+        clipboard::write_clipboard_text(&text);
+    }
 
     if should_stop {
         app_exit_event_writer.write(AppExit::Success);
@@ -0,0 +1,378 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+use std::iter::Peekable;
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::Chars;
+use crate::game::GameError;
+use crate::game::level::{Direction, LevelPack, MoveResult, PlayingLevel};
+use crate::game::screen::ScreenInGame;
+use crate::utils::json_string;
+
+#[cfg(test)]
+mod tests;
+
+/// Runs the bot mode if the first command-line argument is "--bot"
+///
+/// Returns Some(ExitCode) if the bot mode was run and the program should exit
+pub fn run_bot_if_requested() -> Option<ExitCode> {
+    let mut args = std::env::args().skip(1);
+    if args.next().is_none_or(|arg| arg != "--bot") {
+        return None;
+    }
+
+    let Some(level_pack_path) = args.next() else {
+        eprintln!("Usage: --bot \"Path to level pack\"");
+
+        return Some(ExitCode::FAILURE);
+    };
+
+    if let Err(err) = run_bot(&level_pack_path) {
+        eprintln!("{err}");
+
+        return Some(ExitCode::FAILURE);
+    }
+
+    Some(ExitCode::SUCCESS)
+}
+
+/// Reads one JSON command per line from stdin and writes one JSON response per line to stdout
+///
+/// Commands:
+/// - {"command": "state"}: Returns the current state
+/// - {"command": "move", "directions": "lurd"}: Moves the player until a move is invalid or the level is completed
+/// - {"command": "undo"}: Undoes the last move
+/// - {"command": "restart"}: Restarts the current level
+/// - {"command": "select_level", "level_index": 0}: Starts the level with the provided index
+/// - {"command": "quit"}: Exits the bot mode
+fn run_bot(level_pack_path: &str) -> Result<(), Box<dyn Error>> {
+    if !level_pack_path.ends_with(".lvl") {
+        return Err(Box::new(GameError::new(format!(
+            "Invalid level pack \"{}\": The file extension of level pack must be \".lvl\"",
+            level_pack_path
+        ))));
+    }
+
+    let level_pack_id = Path::new(level_pack_path).file_stem().
+            and_then(|file_stem| file_stem.to_str()).
+            ok_or_else(|| GameError::new(format!("Error while loading level pack \"{}\": Invalid file name", level_pack_path)))?;
+
+    let level_pack_data = std::fs::read_to_string(level_pack_path)?;
+    let level_pack = LevelPack::read_from_save_game(level_pack_id, level_pack_path, level_pack_data, false)?;
+
+    let mut bot = Bot::new(level_pack)?;
+
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{}", bot.state_json(None))?;
+    stdout.flush()?;
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match parse_json_object(&line).and_then(|command| bot.handle_command(&command)) {
+            Ok(Some(response)) => response,
+            Ok(None) => break,
+            Err(err) => format!("{{\"error\":{}}}", json_string(&err.to_string())),
+        };
+
+        writeln!(stdout, "{}", response)?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
+
+struct Bot {
+    level_pack: LevelPack,
+    level_index: usize,
+    level: PlayingLevel,
+    has_won: bool,
+}
+
+impl Bot {
+    fn new(level_pack: LevelPack) -> Result<Self, GameError> {
+        let level = Self::create_playing_level(&level_pack, 0)?;
+
+        Ok(Self {
+            level_pack,
+            level_index: 0,
+            level,
+            has_won: false,
+        })
+    }
+
+    fn create_playing_level(level_pack: &LevelPack, level_index: usize) -> Result<PlayingLevel, GameError> {
+        let Some(level) = level_pack.levels().get(level_index) else {
+            return Err(GameError::new(format!(
+                "Level index {} is out of bounds (Level count: {})",
+                level_index, level_pack.level_count(),
+            )));
+        };
+
+        PlayingLevel::new(level.level(), ScreenInGame::UNDO_HISTORY_SIZE_PLAYING).
+                map_err(|err| GameError::new(format!("Level {} is invalid: {}", level_index + 1, err)))
+    }
+
+    fn start_level(&mut self, level_index: usize) -> Result<(), GameError> {
+        self.level = Self::create_playing_level(&self.level_pack, level_index)?;
+        self.level_index = level_index;
+        self.has_won = false;
+
+        Ok(())
+    }
+
+    /// Moves the player including all animations
+    fn move_player(&mut self, direction: Direction) -> MoveResult {
+        let mut move_result = self.level.move_player(direction);
+        while self.level.is_playing_animation() {
+            move_result = self.level.continue_animation();
+        }
+
+        move_result
+    }
+
+    /// Returns None if the bot mode should be exited
+    fn handle_command(&mut self, command: &BTreeMap<String, JsonValue>) -> Result<Option<String>, GameError> {
+        let Some(JsonValue::String(command_name)) = command.get("command") else {
+            return Err(GameError::new("The \"command\" value is missing"));
+        };
+
+        let result = match &**command_name {
+            "state" => None,
+
+            "move" => {
+                let Some(JsonValue::String(directions)) = command.get("directions") else {
+                    return Err(GameError::new("The \"directions\" value is missing"));
+                };
+
+                let directions = directions.bytes().
+                        map(|c| Direction::from_ascii(c).ok_or_else(|| GameError::new(format!("Invalid direction \"{}\"", c as char)))).
+                        collect::<Result<Vec<_>, _>>()?;
+
+                if self.has_won {
+                    return Err(GameError::new("The level was already completed"));
+                }
+
+                let mut result = "valid";
+                for direction in directions {
+                    let move_result = self.move_player(direction);
+                    if move_result.is_invalid() {
+                        result = "invalid";
+
+                        break;
+                    }
+
+                    if move_result.has_won() {
+                        self.has_won = true;
+                        result = "won";
+
+                        break;
+                    }
+
+                    if move_result.secret_found() {
+                        result = "secret_found";
+
+                        break;
+                    }
+                }
+
+                Some(result)
+            },
+
+            "undo" => {
+                if self.level.undo_move().is_some() {
+                    self.has_won = false;
+
+                    Some("valid")
+                }else {
+                    Some("invalid")
+                }
+            },
+
+            "restart" => {
+                self.start_level(self.level_index)?;
+
+                None
+            },
+
+            "select_level" => {
+                let Some(JsonValue::Number(level_index)) = command.get("level_index") else {
+                    return Err(GameError::new("The \"level_index\" value is missing"));
+                };
+
+                self.start_level(*level_index as usize)?;
+
+                None
+            },
+
+            "quit" => return Ok(None),
+
+            _ => {
+                return Err(GameError::new(format!("Invalid command \"{}\"", command_name)));
+            },
+        };
+
+        Ok(Some(self.state_json(result)))
+    }
+
+    fn state_json(&self, result: Option<&str>) -> String {
+        let (level, (player_x, player_y), key_door_count) = self.level.current_playing_level();
+
+        let tiles = (0..level.height()).
+                map(|y| json_string(&(0..level.width()).
+                        map(|x| level.get_tile(x, y).unwrap().to_ascii() as char).
+                        collect::<String>())).
+                collect::<Vec<_>>().
+                join(",");
+
+        let mut out = format!(
+            "{{\"level_pack\":{},\"level_index\":{},\"level_count\":{},\"width\":{},\"height\":{},\"tiles\":[{}],\
+            \"player\":{{\"x\":{},\"y\":{}}},\"keys\":{},\"locked_doors\":{},\"moves\":{},\"has_won\":{}",
+            json_string(self.level_pack.id()), self.level_index, self.level_pack.level_count(),
+            level.width(), level.height(), tiles,
+            player_x, player_y, key_door_count.keys(), key_door_count.locked_doors(),
+            self.level.current_move_index(), self.has_won,
+        );
+
+        if let Some(result) = result {
+            let _ = write!(out, ",\"result\":{}", json_string(result));
+        }
+
+        out.push('}');
+
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JsonValue {
+    String(String),
+    Number(u64),
+    Bool(bool),
+}
+
+/// Parses a flat JSON object which only contains string, unsigned integer, and boolean values
+fn parse_json_object(input: &str) -> Result<BTreeMap<String, JsonValue>, GameError> {
+    JsonParser { chars: input.chars().peekable() }.parse_object()
+}
+
+struct JsonParser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl JsonParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), GameError> {
+        if self.chars.next_if_eq(&expected).is_none() {
+            return Err(GameError::new(format!("Invalid JSON: Expected '{}'", expected)));
+        }
+
+        Ok(())
+    }
+
+    fn parse_object(mut self) -> Result<BTreeMap<String, JsonValue>, GameError> {
+        let mut object = BTreeMap::new();
+
+        self.skip_whitespace();
+        self.expect('{')?;
+        self.skip_whitespace();
+
+        if self.chars.next_if_eq(&'}').is_none() {
+            loop {
+                self.skip_whitespace();
+                let key = self.parse_string()?;
+
+                self.skip_whitespace();
+                self.expect(':')?;
+
+                self.skip_whitespace();
+                let value = self.parse_value()?;
+
+                object.insert(key, value);
+
+                self.skip_whitespace();
+                match self.chars.next() {
+                    Some(',') => {},
+                    Some('}') => break,
+
+                    _ => return Err(GameError::new("Invalid JSON: Expected ',' or '}'")),
+                }
+            }
+        }
+
+        self.skip_whitespace();
+        if self.chars.next().is_some() {
+            return Err(GameError::new("Invalid JSON: Unexpected data after object"));
+        }
+
+        Ok(object)
+    }
+
+    fn parse_string(&mut self) -> Result<String, GameError> {
+        self.expect('"')?;
+
+        let mut out = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(out),
+
+                Some('\\') => {
+                    out.push(match self.chars.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+
+                        _ => return Err(GameError::new("Invalid JSON: Unsupported escape sequence")),
+                    });
+                },
+
+                Some(c) => out.push(c),
+
+                None => return Err(GameError::new("Invalid JSON: Unterminated string")),
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, GameError> {
+        match self.chars.peek() {
+            Some('"') => Ok(JsonValue::String(self.parse_string()?)),
+
+            Some(c) if c.is_ascii_digit() => {
+                let mut number = String::new();
+                while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+                    number.push(c);
+                }
+
+                number.parse().
+                        map(JsonValue::Number).
+                        map_err(|_| GameError::new("Invalid JSON: Number is too large"))
+            },
+
+            Some(c) if c.is_ascii_alphabetic() => {
+                let mut word = String::new();
+                while let Some(c) = self.chars.next_if(char::is_ascii_alphabetic) {
+                    word.push(c);
+                }
+
+                match &*word {
+                    "true" => Ok(JsonValue::Bool(true)),
+                    "false" => Ok(JsonValue::Bool(false)),
+
+                    _ => Err(GameError::new(format!("Invalid JSON: Unsupported value \"{}\"", word))),
+                }
+            },
+
+            _ => Err(GameError::new("Invalid JSON: Unsupported value")),
+        }
+    }
+}
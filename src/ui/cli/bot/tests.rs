@@ -0,0 +1,29 @@
+use crate::ui::cli::bot::*;
+
+#[test]
+fn parse_json_object_values() {
+    let object = parse_json_object(r#" {"command": "move", "directions":"lu\"r", "level_index": 12, "flag": true} "#).unwrap();
+
+    assert_eq!(object.len(), 4);
+    assert_eq!(object["command"], JsonValue::String("move".to_string()));
+    assert_eq!(object["directions"], JsonValue::String("lu\"r".to_string()));
+    assert_eq!(object["level_index"], JsonValue::Number(12));
+    assert_eq!(object["flag"], JsonValue::Bool(true));
+
+    assert!(parse_json_object("{}").unwrap().is_empty());
+}
+
+#[test]
+fn parse_json_object_invalid() {
+    assert!(parse_json_object("").is_err());
+    assert!(parse_json_object("{\"command\"}").is_err());
+    assert!(parse_json_object("{\"command\": \"state\"").is_err());
+    assert!(parse_json_object("{\"command\": \"state\"} {}").is_err());
+    assert!(parse_json_object("{\"level_index\": -1}").is_err());
+    assert!(parse_json_object("{\"command\": null}").is_err());
+}
+
+#[test]
+fn json_string_escape() {
+    assert_eq!(json_string("#\\\"-"), r##""#\\\"-""##);
+}
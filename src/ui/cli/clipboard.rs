@@ -0,0 +1,51 @@
+use std::io::Write;
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Writes the text to the system clipboard with the OSC 52 escape sequence
+///
+/// The escape sequence is ignored by terminals which do not support it
+pub fn write_clipboard_text(text: &str) {
+    let mut stdout = std::io::stdout().lock();
+
+    //Errors are ignored, because the terminal output is also used for drawing the game
+    let _ = write!(stdout, "\x1b]52;c;{}\x07", encode_base64(text.as_bytes()));
+    let _ = stdout.flush();
+}
+
+/// Enables the bracketed paste mode of the terminal: Pasted text is sent between "ESC[200~" and "ESC[201~"
+///
+/// Without this mode, pasted text is received as separate key presses which would be processed as game inputs
+pub fn enable_bracketed_paste() {
+    write_escape_sequence("\x1b[?2004h");
+}
+
+pub fn disable_bracketed_paste() {
+    write_escape_sequence("\x1b[?2004l");
+}
+
+fn write_escape_sequence(sequence: &str) {
+    let mut stdout = std::io::stdout().lock();
+
+    let _ = stdout.write_all(sequence.as_bytes());
+    let _ = stdout.flush();
+}
+
+fn encode_base64(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], chunk.get(1).copied().unwrap_or_default(), chunk.get(2).copied().unwrap_or_default()];
+        let bits = ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | bytes[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_CHARS[((bits >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            }else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,215 @@
+use std::error::Error;
+use std::io::Write;
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+use flate2::{Compression, Crc};
+use flate2::write::ZlibEncoder;
+use crate::game::GameError;
+use crate::game::level::{Level, LevelPack};
+use crate::io::Color;
+
+#[cfg(test)]
+mod tests;
+
+/// The size of a tile in the bitmap font in pixels
+const GLYPH_SIZE: usize = 8;
+
+/// Every pixel of the bitmap font is drawn as a square of SCALE x SCALE pixels
+const SCALE: usize = 2;
+
+/// Same colors as the muted color scheme of the GUI version, the first entry is the default color
+const PALETTE: [[u8; 3]; 17] = [
+    [40, 42, 46],
+
+    [40, 42, 46],
+    [95, 129, 157],
+    [140, 148, 64],
+    [94, 141, 135],
+    [165, 66, 66],
+    [133, 103, 143],
+    [222, 147, 95],
+    [208, 207, 204],
+    [120, 120, 120],
+    [129, 162, 190],
+    [181, 189, 104],
+    [138, 190, 183],
+    [204, 102, 102],
+    [178, 148, 187],
+    [240, 198, 116],
+    [255, 255, 255],
+];
+
+/// Renders a level thumbnail if the first command-line argument is "--render-thumbnail"
+///
+/// Returns Some(ExitCode) if a thumbnail was requested and the program should exit
+pub fn render_thumbnail_if_requested() -> Option<ExitCode> {
+    let mut args = std::env::args().skip(1);
+    if args.next().is_none_or(|arg| arg != "--render-thumbnail") {
+        return None;
+    }
+
+    let (Some(level_pack_path), Some(level), Some(output_path)) = (args.next(), args.next(), args.next()) else {
+        eprintln!("Usage: --render-thumbnail \"Path to level pack\" LEVEL \"Path to PNG file\"");
+        eprintln!("LEVEL is the level number (Starting at 1) or \"pack\" for the thumbnail level of the level pack");
+
+        return Some(ExitCode::FAILURE);
+    };
+
+    if let Err(err) = render_thumbnail(&level_pack_path, &level, &output_path) {
+        eprintln!("{err}");
+
+        return Some(ExitCode::FAILURE);
+    }
+
+    Some(ExitCode::SUCCESS)
+}
+
+fn render_thumbnail(level_pack_path: &str, level: &str, output_path: &str) -> Result<(), Box<dyn Error>> {
+    if !level_pack_path.ends_with(".lvl") {
+        return Err(Box::new(GameError::new(format!(
+            "Invalid level pack \"{}\": The file extension of level pack must be \".lvl\"",
+            level_pack_path
+        ))));
+    }
+
+    let level_pack_id = Path::new(level_pack_path).file_stem().
+            and_then(|file_stem| file_stem.to_str()).
+            ok_or_else(|| GameError::new(format!("Error while loading level pack \"{}\": Invalid file name", level_pack_path)))?;
+
+    let level_pack_data = std::fs::read_to_string(level_pack_path)?;
+    let level_pack = LevelPack::from_level_pack_data(level_pack_id, level_pack_path, &level_pack_data, false)?;
+
+    let level_index = if level == "pack" {
+        level_pack.thumbnail_level_index().unwrap_or(0)
+    }else {
+        usize::from_str(level).ok().
+                and_then(|level_number| level_number.checked_sub(1)).
+                ok_or_else(|| GameError::new(format!("The level \"{}\" is invalid", level)))?
+    };
+
+    let Some(level) = level_pack.levels().get(level_index) else {
+        return Err(Box::new(GameError::new(format!(
+            "The level {} does not exist (Level count: {})",
+            level_index + 1, level_pack.level_count()
+        ))));
+    };
+
+    std::fs::write(output_path, render_level_png(level.level())?)?;
+
+    Ok(())
+}
+
+/// Renders the level with the embedded bitmap font and returns the encoded PNG file
+fn render_level_png(level: &Level) -> Result<Vec<u8>, Box<dyn Error>> {
+    let tile_size = GLYPH_SIZE * SCALE;
+    let width = level.width() * tile_size;
+    let height = level.height() * tile_size;
+
+    let mut pixels = vec![0; width * height];
+    for (i, tile) in level.tiles().iter().enumerate() {
+        let (text, fg, bg) = tile.text_appearance(false);
+        let glyph = glyph(text.bytes().next().unwrap_or(b' '));
+        let (fg, bg) = (palette_index(fg), palette_index(bg));
+
+        let x_start = (i % level.width()) * tile_size;
+        let y_start = (i / level.width()) * tile_size;
+        for y in 0..tile_size {
+            let glyph_row = glyph[y / SCALE];
+
+            for x in 0..tile_size {
+                let is_set = glyph_row & (0x80 >> (x / SCALE)) != 0;
+
+                pixels[(y_start + y) * width + x_start + x] = if is_set { fg } else { bg };
+            }
+        }
+    }
+
+    encode_png(width, height, &pixels)
+}
+
+fn palette_index(color: Color) -> u8 {
+    match color {
+        Color::Default => 0,
+
+        Color::Black => 1,
+        Color::Blue => 2,
+        Color::Green => 3,
+        Color::Cyan => 4,
+        Color::Red => 5,
+        Color::Pink => 6,
+        Color::Yellow => 7,
+        Color::White => 8,
+        Color::LightBlack => 9,
+        Color::LightBlue => 10,
+        Color::LightGreen => 11,
+        Color::LightCyan => 12,
+        Color::LightRed => 13,
+        Color::LightPink => 14,
+        Color::LightYellow => 15,
+        Color::LightWhite => 16,
+    }
+}
+
+/// Returns the 8x8 bitmap of the provided character (The most significant bit is the leftmost pixel)
+fn glyph(c: u8) -> [u8; GLYPH_SIZE] {
+    match c {
+        b'-' => [0x00, 0x00, 0x00, 0x7E, 0x00, 0x00, 0x00, 0x00],
+        b'~' => [0x00, 0x00, 0x32, 0x4C, 0x00, 0x00, 0x00, 0x00],
+        b'%' => [0x00, 0x62, 0x64, 0x08, 0x10, 0x26, 0x46, 0x00],
+        b'<' => [0x0C, 0x18, 0x30, 0x60, 0x30, 0x18, 0x0C, 0x00],
+        b'^' => [0x10, 0x38, 0x6C, 0xC6, 0x00, 0x00, 0x00, 0x00],
+        b'>' => [0x30, 0x18, 0x0C, 0x06, 0x0C, 0x18, 0x30, 0x00],
+        b'v' => [0x00, 0x00, 0xC6, 0xC6, 0xC6, 0x6C, 0x38, 0x00],
+        b'#' => [0x6C, 0x6C, 0xFE, 0x6C, 0xFE, 0x6C, 0x6C, 0x00],
+        b'P' => [0xFC, 0x66, 0x66, 0x7C, 0x60, 0x60, 0xF0, 0x00],
+        b'*' => [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00],
+        b'=' => [0x00, 0x00, 0x7E, 0x00, 0x00, 0x7E, 0x00, 0x00],
+        b'@' => [0x7C, 0xC6, 0xDE, 0xDE, 0xDE, 0xC0, 0x78, 0x00],
+        b'x' => [0x00, 0x00, 0xC6, 0x6C, 0x38, 0x6C, 0xC6, 0x00],
+        b'O' => [0x38, 0x6C, 0xC6, 0xC6, 0xC6, 0x6C, 0x38, 0x00],
+        b'+' => [0x00, 0x30, 0x30, 0xFC, 0x30, 0x30, 0x00, 0x00],
+
+        _ => [0x00; GLYPH_SIZE],
+    }
+}
+
+/// Encodes the pixels (One palette index per pixel) as indexed-color PNG file
+fn encode_png(width: usize, height: usize, pixels: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    for row in pixels.chunks(width) {
+        //Filter type: None
+        encoder.write_all(&[0])?;
+        encoder.write_all(row)?;
+    }
+    let image_data = encoder.finish()?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    //Bit depth: 8, Color type: Indexed-color, Compression method, Filter method, Interlace method
+    header.extend_from_slice(&[8, 3, 0, 0, 0]);
+
+    let palette = PALETTE.concat();
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_png_chunk(&mut png, b"IHDR", &header);
+    write_png_chunk(&mut png, b"PLTE", &palette);
+    write_png_chunk(&mut png, b"IDAT", &image_data);
+    write_png_chunk(&mut png, b"IEND", &[]);
+
+    Ok(png)
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let chunk_start_index = png.len();
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+
+    //The CRC of the chunk type and the chunk data
+    let mut crc = Crc::new();
+    crc.update(&png[chunk_start_index..]);
+    png.extend_from_slice(&crc.sum().to_be_bytes());
+}
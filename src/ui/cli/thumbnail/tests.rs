@@ -0,0 +1,32 @@
+use std::io::Read;
+use std::str::FromStr;
+use flate2::read::ZlibDecoder;
+use crate::game::level::Level;
+use crate::ui::cli::thumbnail::*;
+
+#[test]
+fn encode_png_image_data() {
+    let png = encode_png(2, 2, &[1, 2, 3, 4]).unwrap();
+
+    //IDAT chunk after signature (8 bytes), IHDR (25 bytes), and PLTE (12 bytes + palette)
+    let idat_start = 8 + 25 + 12 + PALETTE.len() * 3;
+    let idat_len = u32::from_be_bytes(png[idat_start..idat_start + 4].try_into().unwrap()) as usize;
+    assert_eq!(png[idat_start + 4..idat_start + 8], *b"IDAT");
+
+    let mut image_data = Vec::new();
+    ZlibDecoder::new(&png[idat_start + 8..idat_start + 8 + idat_len]).read_to_end(&mut image_data).unwrap();
+    assert_eq!(image_data, [0, 1, 2, 0, 3, 4]);
+}
+
+#[test]
+fn render_level_png_header() {
+    let level = Level::from_str("w: 3, h: 2\n#p#\n#@x").unwrap();
+    let png = render_level_png(&level).unwrap();
+
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert!(png.ends_with(b"IEND\xAE\x42\x60\x82"));
+
+    //IHDR data starts after signature, length, and chunk type
+    assert_eq!(png[16..20], ((3 * GLYPH_SIZE * SCALE) as u32).to_be_bytes());
+    assert_eq!(png[20..24], ((2 * GLYPH_SIZE * SCALE) as u32).to_be_bytes());
+}
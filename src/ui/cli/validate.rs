@@ -0,0 +1,230 @@
+use std::error::Error;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::ExitCode;
+use crate::game::GameError;
+use crate::game::level::{LevelAttempt, LevelHintTrigger, LevelPack};
+use crate::game::solver::{self, LevelProblem, Solvability};
+use crate::utils::json_string;
+
+#[cfg(test)]
+mod tests;
+
+/// Exit code if the arguments are invalid or if the level pack could not be loaded (Exit code 1 is used for failed validations)
+const EXIT_CODE_LOADING_ERROR: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IssueKind {
+    Unsolvable,
+    LevelProblem,
+    LimitExceeded,
+    /// Metadata which is never used by the game
+    UnreferencedMetadata,
+}
+
+impl IssueKind {
+    fn id(self) -> &'static str {
+        match self {
+            IssueKind::Unsolvable => "unsolvable",
+            IssueKind::LevelProblem => "level_problem",
+            IssueKind::LimitExceeded => "limit_exceeded",
+            IssueKind::UnreferencedMetadata => "unreferenced_metadata",
+        }
+    }
+
+    /// Unsolvable levels always fail the validation, all other issues only fail the validation in strict mode
+    fn fails_validation(self, strict: bool) -> bool {
+        strict || self == IssueKind::Unsolvable
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Issue {
+    kind: IssueKind,
+    //None for issues of the level pack itself
+    level_index: Option<usize>,
+    message: String,
+}
+
+impl Issue {
+    fn new(kind: IssueKind, level_index: Option<usize>, message: impl Into<String>) -> Self {
+        Self { kind, level_index, message: message.into() }
+    }
+}
+
+#[derive(Debug)]
+struct ValidationReport {
+    level_pack_id: String,
+    solvabilities: Vec<Solvability>,
+    issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    fn is_valid(&self, strict: bool) -> bool {
+        !self.issues.iter().any(|issue| issue.kind.fails_validation(strict))
+    }
+
+    fn to_json(&self, strict: bool) -> String {
+        let levels = self.solvabilities.iter().
+                enumerate().
+                map(|(i, solvability)| format!("{{\"level\":{},\"solvability\":{}}}", i + 1, json_string(solvability_id(*solvability)))).
+                collect::<Vec<_>>().
+                join(",");
+
+        let issues = self.issues.iter().
+                map(|issue| format!(
+                    "{{\"kind\":{},\"level\":{},\"message\":{},\"fails_validation\":{}}}",
+                    json_string(issue.kind.id()),
+                    issue.level_index.map_or("null".to_string(), |level_index| (level_index + 1).to_string()),
+                    json_string(&issue.message),
+                    issue.kind.fails_validation(strict),
+                )).
+                collect::<Vec<_>>().
+                join(",");
+
+        let mut out = String::new();
+        let _ = write!(
+            out, "{{\"level_pack\":{},\"strict\":{},\"valid\":{},\"levels\":[{}],\"issues\":[{}]}}",
+            json_string(&self.level_pack_id), strict, self.is_valid(strict), levels, issues,
+        );
+
+        out
+    }
+}
+
+/// Validates a level pack if the first command-line argument is "--validate-pack"
+///
+/// A JSON report is printed to stdout. Exit codes: 0 (Valid), 1 (Validation failed), 2 (Invalid arguments or the level pack could not be loaded)
+///
+/// Returns Some(ExitCode) if a validation was requested and the program should exit
+pub fn validate_pack_if_requested() -> Option<ExitCode> {
+    let mut args = std::env::args().skip(1);
+    if args.next().is_none_or(|arg| arg != "--validate-pack") {
+        return None;
+    }
+
+    let level_pack_path = args.next();
+    let strict = args.next();
+    let (Some(level_pack_path), None | Some("--strict"), None) = (level_pack_path, strict.as_deref(), args.next()) else {
+        eprintln!("Usage: --validate-pack \"Path to level pack\" [--strict]");
+        eprintln!("Unsolvable levels fail the validation, with \"--strict\" any found issue fails the validation");
+
+        return Some(ExitCode::from(EXIT_CODE_LOADING_ERROR));
+    };
+    let strict = strict.is_some();
+
+    let report = match validate_level_pack(&level_pack_path) {
+        Ok(report) => report,
+
+        Err(err) => {
+            println!("{{\"level_pack\":{},\"error\":{}}}", json_string(&level_pack_path), json_string(&err.to_string()));
+
+            return Some(ExitCode::from(EXIT_CODE_LOADING_ERROR));
+        },
+    };
+
+    println!("{}", report.to_json(strict));
+
+    Some(if report.is_valid(strict) {
+        ExitCode::SUCCESS
+    }else {
+        ExitCode::FAILURE
+    })
+}
+
+fn validate_level_pack(level_pack_path: &str) -> Result<ValidationReport, Box<dyn Error>> {
+    if !level_pack_path.ends_with(".lvl") {
+        return Err(Box::new(GameError::new(format!(
+            "Invalid level pack \"{}\": The file extension of level pack must be \".lvl\"",
+            level_pack_path
+        ))));
+    }
+
+    let level_pack_id = Path::new(level_pack_path).file_stem().
+            and_then(|file_stem| file_stem.to_str()).
+            ok_or_else(|| GameError::new(format!("Error while loading level pack \"{}\": Invalid file name", level_pack_path)))?;
+
+    let level_pack_data = std::fs::read_to_string(level_pack_path)?;
+    let level_pack = LevelPack::from_level_pack_data(level_pack_id, level_pack_path, &level_pack_data, false)?;
+
+    Ok(create_report(&level_pack, &level_pack_data))
+}
+
+/// Checks the loaded level pack, the level pack data is required for metadata which is dropped during loading
+fn create_report(level_pack: &LevelPack, level_pack_data: &str) -> ValidationReport {
+    let mut issues = Vec::new();
+
+    //Unknown background music IDs of custom tracks are ignored by the loader
+    if level_pack.background_music_id().is_none() && let Some(background_music_id) = level_pack_data.lines().
+            find_map(|line| line.trim().strip_prefix("Background Music: ")) {
+        issues.push(Issue::new(IssueKind::UnreferencedMetadata, None, format!(
+            "The background music \"{}\" does not exist (Custom tracks are not available on other computers)",
+            background_music_id.trim(),
+        )));
+    }
+
+    let solvabilities = level_pack.levels().iter().
+            map(|level| solver::analyze_level(level.level())).
+            collect::<Vec<_>>();
+
+    let last_level_index = level_pack.level_count() - 1;
+    for (i, level) in level_pack.levels().iter().enumerate() {
+        if solvabilities[i] == Solvability::LikelyUnsolvable {
+            issues.push(Issue::new(IssueKind::Unsolvable, Some(i), "The level is likely unsolvable"));
+        }
+
+        for problem in solver::find_level_problems(level.level()) {
+            let kind = if matches!(problem, LevelProblem::TooLarge { .. }) {
+                IssueKind::LimitExceeded
+            }else {
+                IssueKind::LevelProblem
+            };
+
+            issues.push(Issue::new(kind, Some(i), problem.to_string()));
+        }
+
+        for (name, moves) in [("par moves", level.par_moves()), ("author moves", level.author_moves())] {
+            if let Some(moves) = moves && moves as usize > LevelAttempt::MAX_MOVE_COUNT {
+                issues.push(Issue::new(IssueKind::LimitExceeded, Some(i), format!(
+                    "The {} ({}) exceed the max move count of recorded attempts ({})",
+                    name, moves, LevelAttempt::MAX_MOVE_COUNT,
+                )));
+            }
+        }
+
+        if i != last_level_index && level.hints().iter().any(|hint| hint.trigger() == LevelHintTrigger::PackCompleted) {
+            issues.push(Issue::new(
+                IssueKind::UnreferencedMetadata, Some(i),
+                "Hints with the \"pack-completed\" trigger are only shown in the last level",
+            ));
+        }
+    }
+
+    if level_pack.bonus_level_condition().is_some() {
+        if level_pack.bonus_level_index().is_none() {
+            issues.push(Issue::new(
+                IssueKind::UnreferencedMetadata, None,
+                "The bonus level condition is ignored because the level pack has less than 2 levels",
+            ));
+        }else if level_pack.secret_level_indices().next().is_none() {
+            issues.push(Issue::new(
+                IssueKind::UnreferencedMetadata, None,
+                "The bonus level is never locked because no other level contains a secret tile",
+            ));
+        }
+    }
+
+    ValidationReport {
+        level_pack_id: level_pack.id().to_string(),
+        solvabilities,
+        issues,
+    }
+}
+
+fn solvability_id(solvability: Solvability) -> &'static str {
+    match solvability {
+        Solvability::Solvable => "solvable",
+        Solvability::Unknown => "unknown",
+        Solvability::LikelyUnsolvable => "likely_unsolvable",
+    }
+}
@@ -0,0 +1,53 @@
+use crate::game::level::LevelPack;
+use crate::ui::cli::validate::*;
+
+const LEVEL_PACK_DATA: &str = "Name: Test
+Background Music: 1234
+Levels: 2
+Hint: 1, pack-completed, Done
+w: 5, h: 3
+#####
+#p@x#
+#####
+
+w: 5, h: 4
+#####
+#@--#
+#p-x#
+#####
+";
+
+#[test]
+fn report_contains_unsolvable_levels_and_unreferenced_metadata() {
+    let level_pack = LevelPack::from_level_pack_data("test", "test.lvl", LEVEL_PACK_DATA, false).unwrap();
+    let report = create_report(&level_pack, LEVEL_PACK_DATA);
+
+    assert_eq!(report.solvabilities, [Solvability::Solvable, Solvability::LikelyUnsolvable]);
+    assert_eq!(
+        report.issues.iter().map(|issue| (issue.kind, issue.level_index)).collect::<Vec<_>>(),
+        [
+            (IssueKind::UnreferencedMetadata, None),
+            (IssueKind::UnreferencedMetadata, Some(0)),
+            (IssueKind::Unsolvable, Some(1)),
+        ],
+    );
+
+    assert!(!report.is_valid(false));
+    assert!(!report.is_valid(true));
+}
+
+#[test]
+fn strict_mode_fails_for_all_issues() {
+    let report = ValidationReport {
+        level_pack_id: "test".to_string(),
+        solvabilities: vec![Solvability::Unknown],
+        issues: vec![Issue::new(IssueKind::LimitExceeded, Some(0), "Too \"large\"")],
+    };
+
+    assert!(report.is_valid(false));
+    assert!(!report.is_valid(true));
+    assert_eq!(
+        report.to_json(true),
+        r#"{"level_pack":"test","strict":true,"valid":false,"levels":[{"level":1,"solvability":"unknown"}],"issues":[{"kind":"limit_exceeded","level":1,"message":"Too \"large\"","fails_validation":true}]}"#,
+    );
+}
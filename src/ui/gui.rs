@@ -1,19 +1,26 @@
 use std::cmp;
+use std::collections::HashMap;
 use std::error::Error;
 use std::mem::ManuallyDrop;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use std::sync::{Arc, LazyLock, Mutex};
 use bevy::prelude::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::color::ColorToPacked;
+use bevy::image::ImageSampler;
 use bevy::input::ButtonState;
 use bevy::input::keyboard::KeyboardInput;
-use bevy::input::mouse::MouseButtonInput;
-use bevy::window::{PrimaryWindow, WindowMode, WindowResized};
+use bevy::input::mouse::{MouseButtonInput, MouseScrollUnit, MouseWheel};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::window::{PrimaryWindow, WindowCloseRequested, WindowMode, WindowResized};
 use bevy::asset::io::embedded::EmbeddedAssetRegistry;
 use bevy::log::LogPlugin;
 use crate::game::Game;
+use crate::game::console_extension::{self, MouseInput};
+use crate::game::level::{Level, Tile};
 use crate::game::screen::dialog::Dialog;
-use crate::io::bevy_abstraction::{ConsoleState, GraphicalCharacter, Key, COLOR_SCHEMES};
+use crate::io::bevy_abstraction::{ColorScheme, ConsoleState, GraphicalCharacter, Key, COLOR_SCHEMES};
 use crate::io::Console;
 
 #[cfg(feature = "steam")]
@@ -21,9 +28,12 @@ use bevy_steamworks::*;
 #[cfg(feature = "steam")]
 use crate::game::level::LevelPack;
 #[cfg(feature = "steam")]
+use crate::game::profile;
+#[cfg(feature = "steam")]
 use crate::ui::gui::steam_plugin::SteamPlugin;
 
 mod assets;
+mod clipboard;
 mod startup_error;
 
 #[cfg(feature = "steam")]
@@ -58,6 +68,16 @@ struct ConsoleTileCharacter {
     y: usize,
 }
 
+#[derive(Debug, Component)]
+struct LevelPackThumbnailSprite;
+
+/// Thumbnail images are created when a level pack is highlighted for the first time and are kept until the game is closed
+#[derive(Debug, Default, Resource)]
+struct LevelPackThumbnailCache {
+    //(Level pack ID, Color scheme index) -> Thumbnail image
+    images: HashMap<(String, usize), Handle<Image>>,
+}
+
 #[derive(Debug, Default, Resource)]
 struct CharacterScaling {
     font_size: f32,
@@ -97,6 +117,13 @@ pub fn run_game() -> ExitCode {
         app.world().get_resource::<Client>().unwrap().clone()
     };
 
+    #[cfg(feature = "steam")]
+    if let Err(err) = profile::activate_steam_account_profile(steam_client.user().steam_id().raw()) {
+        startup_error::show_startup_error_dialog(&mut app, &format!("Could not load profile of Steam account: {err}"));
+
+        return ExitCode::FAILURE;
+    }
+
     let console = Box::leak(Box::new(Console::new(CONSOLE_STATE.clone())));
     let game = Game::new(
         console,
@@ -155,6 +182,8 @@ pub fn run_game() -> ExitCode {
                     mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
                     ..default()
                 }),
+                //The game decides if it can exit (e.g. unsaved changes of the level editor must be saved first)
+                close_when_requested: false,
                 ..default()
             }).disable::<LogPlugin>()).
 
@@ -164,8 +193,10 @@ pub fn run_game() -> ExitCode {
             insert_resource(ClearColor(crate::io::bevy_abstraction::Color::Default.into_bevy_color(&COLOR_SCHEMES[settings.color_scheme_index()]))).
             insert_resource(CharacterScaling::default()).
             insert_resource(CurrentColorSchemeIndex(settings.color_scheme_index())).
+            insert_resource(LevelPackThumbnailCache::default()).
 
             add_systems(Startup, spawn_camera).
+            add_systems(Startup, spawn_level_pack_thumbnail).
             add_systems(Startup, preload_tiles).
             add_systems(Startup, update_text_entities).
             insert_non_send_resource(game).
@@ -173,6 +204,9 @@ pub fn run_game() -> ExitCode {
             add_systems(FixedUpdate, update_game.run_if(in_state(AppState::InGame))).
 
             add_systems(Update, draw_console_text.run_if(in_state(AppState::InGame))).
+            add_systems(Update, update_level_pack_thumbnail.
+                    run_if(in_state(AppState::InGame)).
+                    after(draw_console_text)).
             add_systems(Update, cycle_through_color_schemes.
                     pipe(handle_recoverable_error).
                     run_if(in_state(AppState::InGame)).
@@ -181,7 +215,12 @@ pub fn run_game() -> ExitCode {
                     pipe(handle_recoverable_error).
                     run_if(in_state(AppState::InGame)).
                     before(draw_console_text)).
-            add_systems(Update, (on_resize, toggle_fullscreen));
+            add_systems(Update, (on_resize, toggle_fullscreen, on_window_close_requested));
+
+    #[cfg(not(feature = "steam"))]
+    app.add_systems(FixedUpdate, apply_reloaded_settings.
+            after(update_game).
+            run_if(in_state(AppState::InGame)));
 
     let embedded = app.world_mut().resource_mut::<EmbeddedAssetRegistry>();
 
@@ -250,6 +289,18 @@ fn spawn_camera(
     commands.spawn(Camera2d);
 }
 
+fn spawn_level_pack_thumbnail(
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Sprite::default(),
+        //Drawn above the console characters
+        Transform::from_translation(Vec3::new(0.0, 0.0, 2.0)),
+        LevelPackThumbnailSprite,
+        Visibility::Hidden,
+    ));
+}
+
 fn preload_tiles(
     asset_server: Res<AssetServer>,
 ) {
@@ -324,6 +375,7 @@ fn update_text_entities(
 
             if let Err(tile) = char {
                 sprite.image = tile.into_image(&asset_server);
+                sprite.color = tile.tint();
             }
 
             commands.spawn((
@@ -336,6 +388,7 @@ fn update_text_entities(
     }
 }
 
+#[cfg_attr(feature = "steam", expect(clippy::too_many_arguments))]
 fn update_game(
     window_query: Query<&Window, With<PrimaryWindow>>,
 
@@ -343,14 +396,20 @@ fn update_game(
 
     character_scaling: Res<CharacterScaling>,
 
+    keyboard_input: Res<ButtonInput<KeyCode>>,
     mut keyboard_event: MessageReader<KeyboardInput>,
     mut mouse_event: MessageReader<MouseButtonInput>,
+    mut mouse_wheel_event: MessageReader<MouseWheel>,
+
+    //Fractions of lines of pixel based scrolling (e.g. touchpads) are accumulated
+    mut pending_scroll_lines: Local<f32>,
 
     mut app_exit_event_writer: MessageWriter<AppExit>,
 
     #[cfg(feature = "steam")]
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
+    let mut pasted_text = None;
     {
         let window = window_query.single().unwrap();
 
@@ -361,6 +420,14 @@ fn update_game(
                 continue;
             }
 
+            if event.key_code == KeyCode::KeyV && keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+                if !event.repeat {
+                    pasted_text = clipboard::read_clipboard_text();
+                }
+
+                continue;
+            }
+
             //Limit repeated key to once per update
             if last_key_code == Some(event.key_code) && event.repeat {
                 continue;
@@ -380,32 +447,71 @@ fn update_game(
             }
         }
 
+        let cursor_console_pos = || {
+            let pos = window.cursor_position()?;
+
+            let x = pos.x - character_scaling.x_offset + character_scaling.char_width * 0.5;
+            let y = pos.y - character_scaling.y_offset + character_scaling.char_height * 0.5;
+
+            let column = x / character_scaling.char_width;
+            let row = y / character_scaling.char_height;
+
+            let column = column as i32;
+            let row = row as i32;
+            if column < 0 || row < 0 || column >= 74 || row >= 23 {
+                return None;
+            }
+
+            Some((column as usize, row as usize))
+        };
+
         for event in mouse_event.read() {
             if event.state == ButtonState::Released {
                 continue;
             }
 
-            if event.button == MouseButton::Left && let Some(pos) = window.cursor_position() {
-                let x = pos.x - character_scaling.x_offset + character_scaling.char_width * 0.5;
-                let y = pos.y - character_scaling.y_offset + character_scaling.char_height * 0.5;
+            let button = match event.button {
+                MouseButton::Left => console_extension::MouseButton::Left,
+                MouseButton::Right => console_extension::MouseButton::Right,
+                MouseButton::Middle => console_extension::MouseButton::Middle,
 
-                let column = x / character_scaling.char_width;
-                let row = y / character_scaling.char_height;
+                _ => continue,
+            };
 
-                let column = column as i32;
-                let row = row as i32;
-                if column < 0 || row < 0 || column >= 74 || row >= 23 {
-                    continue;
-                }
+            if let Some((column, row)) = cursor_console_pos() {
+                state.input_queue_mouse_mut().push_back(MouseInput::Pressed { button, column, row });
+            }
+        }
 
-                state.input_queue_mouse_mut().push_back((column as usize, row as usize));
+        for event in mouse_wheel_event.read() {
+            //Bevy reports positive values if the wheel is scrolled up
+            *pending_scroll_lines -= match event.unit {
+                MouseScrollUnit::Line => event.y,
+                MouseScrollUnit::Pixel => event.y / character_scaling.char_height,
+            };
+        }
+
+        let delta = pending_scroll_lines.trunc();
+        if delta != 0.0 {
+            *pending_scroll_lines -= delta;
+
+            if let Some((column, row)) = cursor_console_pos() {
+                state.input_queue_mouse_mut().push_back(MouseInput::Scrolled { delta: delta as i32, column, row });
             }
         }
     }
 
+    if let Some(pasted_text) = pasted_text {
+        game.on_text_pasted(&pasted_text);
+    }
+
     let should_stop = game.update();
     game.draw();
 
+    if let Some(text) = game.take_clipboard_text() {
+        clipboard::write_clipboard_text(&text);
+    }
+
     if should_stop {
         app_exit_event_writer.write(AppExit::Success);
     }
@@ -444,6 +550,24 @@ fn on_resize(
     }
 }
 
+fn on_window_close_requested(
+    mut game: NonSendMut<Game>,
+    app_state: Res<State<AppState>>,
+
+    mut window_close_requested_reader: MessageReader<WindowCloseRequested>,
+    mut app_exit_event_writer: MessageWriter<AppExit>,
+) {
+    if window_close_requested_reader.read().count() == 0 {
+        return;
+    }
+
+    if *app_state.get() == AppState::InGame {
+        game.on_exit_requested();
+    }else {
+        app_exit_event_writer.write(AppExit::Success);
+    }
+}
+
 fn toggle_fullscreen(
     mut window_query: Query<&mut Window, With<PrimaryWindow>>,
 
@@ -480,6 +604,26 @@ fn cycle_through_color_schemes(
     Ok(())
 }
 
+/// Applies the color scheme and tile mode after the settings were reloaded (e.g. after switching the profile)
+#[cfg(not(feature = "steam"))]
+fn apply_reloaded_settings(
+    mut commands: Commands,
+
+    mut current_color_scheme_index: ResMut<CurrentColorSchemeIndex>,
+
+    mut game: NonSendMut<Game>,
+) {
+    if !game.take_reloaded_settings() {
+        return;
+    }
+
+    let settings = game.game_state().settings();
+    CONSOLE_STATE.lock().unwrap().set_tile_mode(settings.tile_mode());
+
+    current_color_scheme_index.0 = settings.color_scheme_index();
+    commands.insert_resource(ClearColor(crate::io::bevy_abstraction::Color::Default.into_bevy_color(&COLOR_SCHEMES[current_color_scheme_index.0])));
+}
+
 fn toggle_tile_mode(
     keyboard_input: Res<ButtonInput<KeyCode>>,
 
@@ -540,6 +684,12 @@ fn draw_console_text(
                     text.0 = " ".to_string();
                     fg_color.0 = fg.into_bevy_color(color_scheme);
                     bg_color.0 = bg.into_bevy_color(color_scheme).with_alpha(0.9);
+                }else if bg != crate::io::bevy_abstraction::Color::Default {
+                    //Level themes are drawn as translucent backdrop over the tile graphic
+                    *visibility = Visibility::Visible;
+                    text.0 = " ".to_string();
+                    fg_color.0 = fg.into_bevy_color(color_scheme);
+                    bg_color.0 = bg.into_bevy_color(color_scheme).with_alpha(0.35);
                 }else {
                     *visibility = Visibility::Hidden;
                 }
@@ -562,11 +712,97 @@ fn draw_console_text(
             Err(tile) => {
                 *visibility = Visibility::Visible;
                 sprite.image = tile.into_image(&asset_server);
+                sprite.color = tile.tint();
             },
         }
     }
 }
 
+fn update_level_pack_thumbnail(
+    mut thumbnail_query: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<LevelPackThumbnailSprite>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+
+    game: NonSend<Game>,
+
+    character_scaling: Res<CharacterScaling>,
+    current_color_scheme_index: Res<CurrentColorSchemeIndex>,
+    mut thumbnail_cache: ResMut<LevelPackThumbnailCache>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Ok((mut sprite, mut transform, mut visibility)) = thumbnail_query.single_mut() else {
+        return;
+    };
+
+    let thumbnail = game.level_pack_thumbnail_area().and_then(|area| {
+        let level_pack = game.game_state().level_packs().get(area.level_pack_index)?;
+        let level = level_pack.levels().get(level_pack.thumbnail_level_index().unwrap_or(0))?.level();
+
+        Some((area, level_pack.id(), level))
+    });
+    let Some((area, level_pack_id, level)) = thumbnail else {
+        *visibility = Visibility::Hidden;
+
+        return;
+    };
+
+    let image = thumbnail_cache.images.
+            entry((level_pack_id.to_string(), current_color_scheme_index.0)).
+            or_insert_with(|| images.add(create_level_thumbnail_image(level, &COLOR_SCHEMES[current_color_scheme_index.0])));
+
+    let window = window_query.single().unwrap();
+
+    let area_width = area.width as f32 * character_scaling.char_width;
+    let area_height = area.height as f32 * character_scaling.char_height;
+
+    //Tiles are square and the thumbnail is centered in the area
+    let tile_size = (area_width / level.width() as f32).min(area_height / level.height() as f32);
+
+    let area_x = character_scaling.x_offset + (area.column as f32 - 0.5) * character_scaling.char_width;
+    let area_y = character_scaling.y_offset + (area.row as f32 - 0.5) * character_scaling.char_height;
+
+    sprite.image = image.clone();
+    sprite.custom_size = Some(Vec2::new(level.width() as f32 * tile_size, level.height() as f32 * tile_size));
+
+    transform.translation.x = area_x + area_width * 0.5 - window.width() * 0.5;
+    transform.translation.y = window.height() * 0.5 - (area_y + area_height * 0.5);
+
+    *visibility = Visibility::Visible;
+}
+
+/// Creates an image with a single pixel for each tile of the level
+fn create_level_thumbnail_image(level: &Level, color_scheme: &ColorScheme) -> Image {
+    let mut data = Vec::with_capacity(level.width() * level.height() * 4);
+    for tile in level.tiles().iter().copied() {
+        let (_, fg, bg) = tile.text_appearance(false);
+
+        let color = if tile == Tile::Empty {
+            level.theme().background_color()
+        }else if bg == crate::io::bevy_abstraction::Color::Default {
+            fg
+        }else {
+            bg
+        };
+
+        data.extend_from_slice(&color.into_bevy_color(color_scheme).to_srgba().to_u8_array());
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: level.width() as u32,
+            height: level.height() as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    //Pixels must not be blurred if the image is scaled up
+    image.sampler = ImageSampler::nearest();
+
+    image
+}
+
 fn calculate_character_scaling(
     window_width: f32,
     window_height: f32,
@@ -0,0 +1,29 @@
+use arboard::Clipboard;
+use crate::game::logging::log_warn;
+
+/// Returns the text content of the system clipboard or None if the clipboard does not contain text
+pub fn read_clipboard_text() -> Option<String> {
+    let text = Clipboard::new().
+            and_then(|mut clipboard| clipboard.get_text());
+
+    match text {
+        Ok(text) => Some(text),
+
+        Err(arboard::Error::ContentNotAvailable) => None,
+
+        Err(err) => {
+            log_warn!("Could not read text from clipboard: {err}");
+
+            None
+        },
+    }
+}
+
+pub fn write_clipboard_text(text: &str) {
+    let result = Clipboard::new().
+            and_then(|mut clipboard| clipboard.set_text(text));
+
+    if let Err(err) = result {
+        log_warn!("Could not write text to clipboard: {err}");
+    }
+}
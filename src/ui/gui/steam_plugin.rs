@@ -5,6 +5,7 @@ use bevy::prelude::*;
 use bevy_steamworks::*;
 use crate::game::{steam, Game, GameError};
 use crate::game::audio::SoundEffect;
+use crate::game::logging::log_warn;
 use crate::ui::gui::{handle_recoverable_error, on_resize, CharacterScaling};
 use crate::ui::gui::steam_plugin::steam_workshop_upload_popup::SteamWorkshopUploadPopupPlugin;
 
@@ -142,7 +143,7 @@ fn load_steam_workshop_items(
                         if let Some(item) = item {
                             STEAM_WORKSHOP_ITEM_LOADING_QUEUE.lock().unwrap().push_back(Ok(item));
                         }else {
-                            warn!("Invalid workshop item after query.");
+                            log_warn!("Invalid workshop item after query.");
                         }
                     }
                 },
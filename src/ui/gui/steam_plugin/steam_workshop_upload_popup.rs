@@ -2,6 +2,8 @@ use std::error::Error;
 use std::ops::Deref;
 use std::path::Path;
 use std::sync::{Arc, LazyLock, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use bevy::camera::RenderTarget;
 use bevy::camera::visibility::RenderLayers;
 use bevy::input::ButtonState;
@@ -18,18 +20,36 @@ use bevy::ui::Checked;
 use bevy::window::{CursorIcon, PrimaryWindow, SystemCursorIcon};
 use bevy_steamworks::*;
 use crate::game::{audio, steam, Game, GameError};
+use crate::game::logging::{log_error, log_info, log_warn};
+use crate::game::solver::LevelDifficulty;
 use crate::game::steam::achievement::Achievement;
 use crate::ui::gui;
 use crate::ui::gui::{handle_recoverable_error, AppState, ConsoleTextCharacter, ConsoleTileCharacter, CONSOLE_STATE};
 use crate::ui::gui::steam_plugin::{on_resize_popup_text, PlaySoundEffect, ResizableNodeDimension, ResizableText};
 use crate::utils;
 
+#[cfg(test)]
+mod tests;
+
 const RADIO_BUTTON_COLOR: Color = Color::srgb_u8(140, 148, 64);
 const LINK_COLOR: Color = Color::srgb_u8(42, 123, 222);
 const LINK_COLOR_HOVERED: Color = Color::srgb_u8(18, 72, 139);
+const SOLVER_VALIDATION_NOTE_COLOR: Color = Color::srgb_u8(150, 60, 0);
 
 const TEXT_CURSOR_CHARACTER: &str = "\u{258F}";
 
+//Limits of the Steam Workshop (In bytes)
+const MAX_LEVEL_PACK_NAME_LEN: usize = 128;
+const MAX_LEVEL_PACK_DESCRIPTION_LEN: usize = 8000;
+
+//Steam calls are treated as failed with a timeout error if there was no progress for this duration
+const CREATE_ITEM_TIMEOUT: Duration = Duration::from_secs(30);
+const SUBMIT_ITEM_TIMEOUT: Duration = Duration::from_secs(90);
+
+//Transient Steam errors are retried with an exponential backoff (1s, 2s, 4s)
+const MAX_RETRY_COUNT: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
 pub struct SteamWorkshopUploadPopupPlugin;
 
 impl Plugin for SteamWorkshopUploadPopupPlugin {
@@ -50,6 +70,8 @@ impl Plugin for SteamWorkshopUploadPopupPlugin {
                 add_systems(Update, (
                     process_and_update_upload_progress.pipe(handle_recoverable_error),
                     process_update_progress_status.pipe(handle_recoverable_error),
+                    process_upload_operation,
+                    update_upload_cancel_button,
                     update_text_input_fields,
                     update_radio_button_checked_state,
                     update_ui_styles,
@@ -81,6 +103,13 @@ enum SteamWorkshopUploadWorkingData {
     ValidationFailed(String),
     CreateItemResult(Result<(PublishedFileId, bool), SteamError>),
     SubmitItemResult((PublishedFileId, Result<bool, SteamError>)),
+    Cancelled,
+    /// Result of the deletion of a workshop item which was created by a failed or cancelled upload
+    ItemDeleteResult {
+        message: String,
+        error: bool,
+        result: Result<(), SteamError>,
+    },
 }
 
 #[expect(clippy::type_complexity)]
@@ -89,6 +118,97 @@ static STEAM_WORKSHOP_UPLOAD_WORKING_DATA: LazyLock<
     fn() -> Arc<Mutex<SteamWorkshopUploadWorkingData>>,
 > = LazyLock::new(Default::default);
 
+//Incremented if an upload is cancelled or a Steam call timed out: Results of older Steam calls are ignored
+static STEAM_WORKSHOP_UPLOAD_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Stores the result of a Steam callback if no newer Steam call was started in the meantime
+///
+/// Returns false if the result is outdated
+fn set_working_data_if_current(generation: u64, working_data: SteamWorkshopUploadWorkingData) -> bool {
+    let mut current_data = STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap();
+    if STEAM_WORKSHOP_UPLOAD_GENERATION.load(Ordering::SeqCst) != generation {
+        return false;
+    }
+
+    *current_data = working_data;
+
+    true
+}
+
+/// Sets the working data and ignores the results of all running Steam calls
+fn set_working_data_and_ignore_running_calls(working_data: SteamWorkshopUploadWorkingData) {
+    let mut current_data = STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap();
+    STEAM_WORKSHOP_UPLOAD_GENERATION.fetch_add(1, Ordering::SeqCst);
+
+    *current_data = working_data;
+}
+
+fn is_transient_steam_error(err: &SteamError) -> bool {
+    matches!(
+        err,
+        SteamError::Timeout | SteamError::ServiceUnavailable | SteamError::Busy |
+        SteamError::NoConnection | SteamError::IOFailure
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UploadStep {
+    CreateItem,
+    SubmitItem(PublishedFileId),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UploadOperationState {
+    Running {
+        last_progress: Instant,
+    },
+    RetryScheduled {
+        retry_at: Instant,
+    },
+}
+
+/// The currently running Steam call of an upload (Used for timeouts, retries, and cancellation)
+#[derive(Debug, Resource)]
+struct UploadOperation {
+    step: UploadStep,
+    failed_attempts: u32,
+    state: UploadOperationState,
+}
+
+impl UploadOperation {
+    fn new(step: UploadStep) -> Self {
+        Self {
+            step,
+            failed_attempts: 0,
+            state: UploadOperationState::Running {
+                last_progress: Instant::now(),
+            },
+        }
+    }
+
+    /// Schedules a retry of the current step if the error is transient and there are retries left
+    ///
+    /// Returns the delay until the retry is started
+    fn schedule_retry(&mut self, err: &SteamError) -> Option<Duration> {
+        if !is_transient_steam_error(err) || self.failed_attempts >= MAX_RETRY_COUNT {
+            return None;
+        }
+
+        //A timed out submission might still be running in Steam, submitting it again could publish the item twice
+        if matches!(self.step, UploadStep::SubmitItem(_)) && matches!(err, SteamError::Timeout) {
+            return None;
+        }
+
+        let delay = RETRY_BASE_DELAY * 2_u32.pow(self.failed_attempts);
+        self.failed_attempts += 1;
+        self.state = UploadOperationState::RetryScheduled {
+            retry_at: Instant::now() + delay,
+        };
+
+        Some(delay)
+    }
+}
+
 #[derive(Resource)]
 struct UpdateWatchHandleWrapper(UpdateWatchHandle);
 
@@ -113,6 +233,9 @@ struct UploadProgressPopupContent;
 #[derive(Debug, Component)]
 struct UploadProgressPopupButtonContainer;
 
+#[derive(Debug, Component)]
+struct UploadCancelButton;
+
 #[derive(Debug, Clone, Message)]
 struct SetUploadProgressPopupTitle {
     title: String,
@@ -148,6 +271,17 @@ enum DifficultyTag {
     Demon,
 }
 
+impl From<LevelDifficulty> for DifficultyTag {
+    fn from(difficulty: LevelDifficulty) -> Self {
+        match difficulty {
+            LevelDifficulty::Easy => DifficultyTag::Easy,
+            LevelDifficulty::Medium => DifficultyTag::Medium,
+            LevelDifficulty::Hard => DifficultyTag::Hard,
+            LevelDifficulty::Demon => DifficultyTag::Demon,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash, Resource, Component)]
 enum GameplayTag {
     Fun,
@@ -196,6 +330,10 @@ fn process_and_update_upload_progress(
     mut set_upload_progress_popup_content: MessageWriter<SetUploadProgressPopupContent>,
 
     mut play_sound_effect: MessageWriter<PlaySoundEffect>,
+
+    mut upload_operation: Option<ResMut<UploadOperation>>,
+
+    mut game: NonSendMut<Game>,
 ) -> Result<(), Box<dyn Error>> {
     let current_data = STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap().clone();
     match current_data {
@@ -231,45 +369,7 @@ fn process_and_update_upload_progress(
                 ..default()
             };
 
-            commands.entity(popup_button_container_id).with_child((
-                Node {
-                    width: percent(100),
-                    border: UiRect::all(px(2)),
-                    border_radius: BorderRadius::all(px(10)),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                Button,
-                Hovered::default(),
-                TabIndex::default(),
-                BorderColor::all(crate::io::bevy_abstraction::Color::White),
-                BackgroundColor(crate::io::bevy_abstraction::Color::Black.into()),
-                children![(
-                    Text::new("Close"),
-                    text_font.clone(),
-                    LineHeight::RelativeToFont(1.1),
-                    TextColor(crate::io::bevy_abstraction::Color::White.into()),
-                    ResizableText::Paragraph,
-                )],
-                observe(
-                    |_: On<Activate>,
-
-                     commands: Commands,
-
-                     upload_progress_popup_elements: Query<Entity, With<UploadProgressPopup>>,
-
-                     mut play_sound_effect: MessageWriter<PlaySoundEffect>| {
-                        *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Idle;
-
-                        play_sound_effect.write(PlaySoundEffect {
-                            sound_effect: audio::UI_SELECT_EFFECT,
-                        });
-
-                        close_upload_progress_popup(commands, upload_progress_popup_elements);
-                    },
-                ),
-            ));
+            commands.entity(popup_button_container_id).with_child(upload_progress_popup_close_button(text_font));
 
             return Ok(());
         },
@@ -335,6 +435,9 @@ fn process_and_update_upload_progress(
 
             tmp_upload_path.push("Data/");
 
+            let change_note = game.game_state().editor_state().get_current_level_pack().unwrap().workshop_change_note();
+
+            let generation = STEAM_WORKSHOP_UPLOAD_GENERATION.load(Ordering::SeqCst);
             let handle = steam_client.ugc().start_item_update(steam::APP_ID, id).
                     visibility(PublishedFileVisibility::Private).
                     title(level_pack_name).
@@ -342,8 +445,8 @@ fn process_and_update_upload_progress(
                     content_path(Path::new(&tmp_upload_path)).
                     preview_path(Path::new(&tmp_thumbnail_path)).
                     tags(tags, false).
-                    submit(Some("<Initial Release>"), move |ret| {
-                        *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::SubmitItemResult(match ret {
+                    submit(Some(&change_note), move |ret| {
+                        set_working_data_if_current(generation, SteamWorkshopUploadWorkingData::SubmitItemResult(match ret {
                             Ok((id, needs_to_accept_workshop_terms)) => {
                                 (id, Ok(needs_to_accept_workshop_terms))
                             },
@@ -351,17 +454,46 @@ fn process_and_update_upload_progress(
                             Err(err) => {
                                 (id, Err(err))
                             },
-                        });
+                        }));
                     });
 
             commands.insert_resource(UpdateWatchHandleWrapper(handle));
             commands.insert_resource(PreviousUpdateStatus((UpdateStatus::Invalid, 0, 0)));
 
+            //The failed attempts are kept if the submission is retried
+            match upload_operation.as_deref_mut() {
+                Some(upload_operation) if upload_operation.step == UploadStep::SubmitItem(id) => {
+                    upload_operation.state = UploadOperationState::Running {
+                        last_progress: Instant::now(),
+                    };
+                },
+
+                _ => {
+                    commands.insert_resource(UploadOperation::new(UploadStep::SubmitItem(id)));
+                },
+            }
+
+            set_upload_progress_popup_content.write(SetUploadProgressPopupContent {
+                text: "Submitting...".to_string(),
+                error: false,
+            });
+
             *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Waiting;
         },
         SteamWorkshopUploadWorkingData::CreateItemResult(Err(err)) => {
             *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Waiting;
 
+            if let Some(mut upload_operation) = upload_operation &&
+                    let Some(delay) = upload_operation.schedule_retry(&err) {
+                set_upload_progress_popup_content.write(SetUploadProgressPopupContent {
+                    text: retry_message("Level pack creation", &err, delay, upload_operation.failed_attempts),
+                    error: false,
+                });
+
+                return Ok(());
+            }
+            commands.remove_resource::<UploadOperation>();
+
             play_sound_effect.write(PlaySoundEffect {
                 sound_effect: audio::UI_ERROR_EFFECT,
             });
@@ -391,45 +523,7 @@ fn process_and_update_upload_progress(
                 ..default()
             };
 
-            commands.entity(popup_button_container_id).with_child((
-                Node {
-                    width: percent(100),
-                    border: UiRect::all(px(2)),
-                    border_radius: BorderRadius::all(px(10)),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                Button,
-                Hovered::default(),
-                TabIndex::default(),
-                BorderColor::all(crate::io::bevy_abstraction::Color::White),
-                BackgroundColor(crate::io::bevy_abstraction::Color::Black.into()),
-                children![(
-                    Text::new("Close"),
-                    text_font.clone(),
-                    LineHeight::RelativeToFont(1.1),
-                    TextColor(crate::io::bevy_abstraction::Color::White.into()),
-                    ResizableText::Paragraph,
-                )],
-                observe(
-                    |_: On<Activate>,
-
-                     commands: Commands,
-
-                     upload_progress_popup_elements: Query<Entity, With<UploadProgressPopup>>,
-
-                     mut play_sound_effect: MessageWriter<PlaySoundEffect>| {
-                        *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Idle;
-
-                        play_sound_effect.write(PlaySoundEffect {
-                            sound_effect: audio::UI_SELECT_EFFECT,
-                        });
-
-                        close_upload_progress_popup(commands, upload_progress_popup_elements);
-                    },
-                ),
-            ));
+            commands.entity(popup_button_container_id).with_child(upload_progress_popup_close_button(text_font));
 
             return Ok(());
         },
@@ -439,8 +533,18 @@ fn process_and_update_upload_progress(
 
             Achievement::STEAM_WORKSHOP_LEVEL_PACK_CREATED.unlock(steam_client.clone());
 
+            //The changelog was published as the change note of this upload
+            if let Some(level_pack) = game.game_state_mut().editor_state_mut().get_current_level_pack_mut() {
+                level_pack.clear_changelog();
+
+                if let Err(err) = level_pack.save_editor_level_pack() {
+                    log_warn!("Cannot save level pack after upload: {err}");
+                }
+            }
+
             commands.remove_resource::<UpdateWatchHandleWrapper>();
             commands.remove_resource::<PreviousUpdateStatus>();
+            commands.remove_resource::<UploadOperation>();
             *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Waiting;
 
             play_sound_effect.write(PlaySoundEffect {
@@ -564,9 +668,20 @@ fn process_and_update_upload_progress(
             commands.remove_resource::<PreviousUpdateStatus>();
             *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Waiting;
 
-            steam_client.ugc().delete_item(id, |ret| {
-                info!("Workshop item upload failed: Cleanup status: {ret:?}");
-            });
+            //The created workshop item is reused for the retry
+            if let Some(mut upload_operation) = upload_operation &&
+                    let Some(delay) = upload_operation.schedule_retry(&err) {
+                set_upload_progress_popup_content.write(SetUploadProgressPopupContent {
+                    text: retry_message("Level pack submission", &err, delay, upload_operation.failed_attempts),
+                    error: false,
+                });
+
+                return Ok(());
+            }
+            commands.remove_resource::<UploadOperation>();
+
+            let message = format!("An error occurred during level pack submission: {err}");
+            delete_workshop_item(&steam_client, id, message.clone(), true);
 
             play_sound_effect.write(PlaySoundEffect {
                 sound_effect: audio::UI_ERROR_EFFECT,
@@ -582,7 +697,7 @@ fn process_and_update_upload_progress(
             });
 
             set_upload_progress_popup_content.write(SetUploadProgressPopupContent {
-                text: format!("An error occurred during level pack submission: {err}"),
+                text: format!("{message}\n\nDeleting the created workshop item..."),
                 error: true,
             });
 
@@ -597,47 +712,72 @@ fn process_and_update_upload_progress(
                 ..default()
             };
 
-            commands.entity(popup_button_container_id).with_child((
-                Node {
-                    width: percent(100),
-                    border: UiRect::all(px(2)),
-                    border_radius: BorderRadius::all(px(10)),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                Button,
-                Hovered::default(),
-                TabIndex::default(),
-                BorderColor::all(crate::io::bevy_abstraction::Color::White),
-                BackgroundColor(crate::io::bevy_abstraction::Color::Black.into()),
-                children![(
-                    Text::new("Close"),
-                    text_font.clone(),
-                    LineHeight::RelativeToFont(1.1),
-                    TextColor(crate::io::bevy_abstraction::Color::White.into()),
-                    ResizableText::Paragraph,
-                )],
-                observe(
-                    |_: On<Activate>,
+            commands.entity(popup_button_container_id).with_child(upload_progress_popup_close_button(text_font));
 
-                     commands: Commands,
+            return Ok(());
+        },
 
-                     upload_progress_popup_elements: Query<Entity, With<UploadProgressPopup>>,
+        SteamWorkshopUploadWorkingData::Cancelled => {
+            commands.remove_resource::<UpdateWatchHandleWrapper>();
+            commands.remove_resource::<PreviousUpdateStatus>();
+            commands.remove_resource::<UploadOperation>();
+            *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Waiting;
 
-                     mut play_sound_effect: MessageWriter<PlaySoundEffect>| {
-                        *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Idle;
+            //Items which are created after the cancellation are deleted by the create item callback
+            let message = "The upload was cancelled.".to_string();
+            let text = if let Some(upload_operation) = upload_operation &&
+                    let UploadStep::SubmitItem(id) = upload_operation.step {
+                delete_workshop_item(&steam_client, id, message.clone(), false);
 
-                        play_sound_effect.write(PlaySoundEffect {
-                            sound_effect: audio::UI_SELECT_EFFECT,
-                        });
+                format!("{message}\n\nDeleting the created workshop item...")
+            }else {
+                message
+            };
 
-                        close_upload_progress_popup(commands, upload_progress_popup_elements);
-                    },
-                ),
-            ));
+            if let Ok(window_id) = window_query.single() {
+                commands.entity(window_id).insert(CursorIcon::System(SystemCursorIcon::Default));
+            }
 
-            return Ok(());
+            set_upload_progress_popup_title.write(SetUploadProgressPopupTitle {
+                title: "Upload cancelled".to_string(),
+                error: false,
+            });
+
+            set_upload_progress_popup_content.write(SetUploadProgressPopupContent {
+                text,
+                error: false,
+            });
+
+            let Ok(popup_button_container_id) = upload_progress_popup_button_container_query.single() else {
+                return Err(Box::new(GameError::new("Invalid popup status")));
+            };
+
+            let font = asset_server.load("embedded://font/JetBrainsMonoNL-ExtraLight.ttf");
+            let text_font = TextFont {
+                font: font.clone(),
+                font_size: 1.0, //Dummy value
+                ..default()
+            };
+
+            commands.entity(popup_button_container_id).with_child(upload_progress_popup_close_button(text_font));
+        },
+
+        SteamWorkshopUploadWorkingData::ItemDeleteResult { message, error, result } => {
+            *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Waiting;
+
+            let (cleanup_text, cleanup_failed) = match result {
+                Ok(()) => ("The created workshop item was deleted.".to_string(), false),
+
+                Err(err) => (format!(
+                    "The created workshop item could not be deleted: {err}\n\
+                    Please delete it manually in the Steam Workshop."
+                ), true),
+            };
+
+            set_upload_progress_popup_content.write(SetUploadProgressPopupContent {
+                text: format!("{message}\n\n{cleanup_text}"),
+                error: error || cleanup_failed,
+            });
         },
 
         SteamWorkshopUploadWorkingData::Waiting |
@@ -647,11 +787,227 @@ fn process_and_update_upload_progress(
     Ok(())
 }
 
+/// Checks the running Steam call for timeouts and starts scheduled retries
+fn process_upload_operation(
+    upload_operation: Option<ResMut<UploadOperation>>,
+
+    steam_client: Res<Client>,
+
+    mut set_upload_progress_popup_content: MessageWriter<SetUploadProgressPopupContent>,
+) {
+    let Some(mut upload_operation) = upload_operation else {
+        return;
+    };
+
+    match upload_operation.state {
+        UploadOperationState::Running { last_progress } => {
+            let timeout = match upload_operation.step {
+                UploadStep::CreateItem => CREATE_ITEM_TIMEOUT,
+                UploadStep::SubmitItem(_) => SUBMIT_ITEM_TIMEOUT,
+            };
+
+            if last_progress.elapsed() < timeout {
+                return;
+            }
+
+            log_warn!("Workshop upload step {:?} timed out", upload_operation.step);
+
+            //The timeout is processed like a failed Steam call (Results of the timed out call are ignored)
+            //Timed out submissions are not retried: The created workshop item is deleted instead
+            upload_operation.state = UploadOperationState::Running {
+                last_progress: Instant::now(),
+            };
+            set_working_data_and_ignore_running_calls(match upload_operation.step {
+                UploadStep::CreateItem => SteamWorkshopUploadWorkingData::CreateItemResult(Err(SteamError::Timeout)),
+                UploadStep::SubmitItem(id) => SteamWorkshopUploadWorkingData::SubmitItemResult((id, Err(SteamError::Timeout))),
+            });
+        },
+
+        UploadOperationState::RetryScheduled { retry_at } => {
+            if Instant::now() < retry_at {
+                return;
+            }
+
+            upload_operation.state = UploadOperationState::Running {
+                last_progress: Instant::now(),
+            };
+
+            match upload_operation.step {
+                UploadStep::CreateItem => {
+                    set_upload_progress_popup_content.write(SetUploadProgressPopupContent {
+                        text: "Creating level pack...".to_string(),
+                        error: false,
+                    });
+
+                    start_create_workshop_item(&steam_client);
+                },
+
+                UploadStep::SubmitItem(id) => {
+                    //The submission is started again with the already created workshop item
+                    *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::CreateItemResult(Ok((id, false)));
+                },
+            }
+        },
+    }
+}
+
+fn retry_message(failed_step: &str, err: &SteamError, delay: Duration, failed_attempts: u32) -> String {
+    format!(
+        "{failed_step} failed: {err}\n\
+        Retrying in {} s (Attempt {} of {})...",
+        delay.as_secs(), failed_attempts + 1, MAX_RETRY_COUNT + 1,
+    )
+}
+
+/// Creates the workshop item of the upload, items which are created after the call was cancelled or timed out are deleted
+fn start_create_workshop_item(steam_client: &Client) {
+    let generation = STEAM_WORKSHOP_UPLOAD_GENERATION.load(Ordering::SeqCst);
+    let cleanup_steam_client = steam_client.clone();
+
+    steam::crate_workshop_item(steam_client.clone(), move |ret| {
+        if !set_working_data_if_current(generation, SteamWorkshopUploadWorkingData::CreateItemResult(ret.clone())) &&
+                let Ok((id, _)) = ret {
+            cleanup_steam_client.ugc().delete_item(id, |ret| {
+                log_info!("Outdated workshop item was created: Cleanup status: {ret:?}");
+            });
+        }
+    });
+}
+
+/// Deletes the workshop item of a failed or cancelled upload, the result is shown after the message in the upload progress popup
+fn delete_workshop_item(steam_client: &Client, id: PublishedFileId, message: String, error: bool) {
+    let generation = STEAM_WORKSHOP_UPLOAD_GENERATION.load(Ordering::SeqCst);
+
+    steam_client.ugc().delete_item(id, move |result| {
+        log_info!("Workshop item upload was aborted: Cleanup status: {result:?}");
+
+        set_working_data_if_current(generation, SteamWorkshopUploadWorkingData::ItemDeleteResult {
+            message,
+            error,
+            result,
+        });
+    });
+}
+
+/// Shows the cancel button in the upload progress popup while a Steam call is running or a retry is scheduled
+fn update_upload_cancel_button(
+    mut commands: Commands,
+
+    upload_progress_popup_button_container_query: Query<Entity, With<UploadProgressPopupButtonContainer>>,
+    upload_cancel_button_query: Query<Entity, With<UploadCancelButton>>,
+
+    upload_operation: Option<Res<UploadOperation>>,
+    asset_server: Res<AssetServer>,
+) {
+    let is_cancel_button_shown = !upload_cancel_button_query.is_empty();
+    if upload_operation.is_some() == is_cancel_button_shown {
+        return;
+    }
+
+    if is_cancel_button_shown {
+        for entity in upload_cancel_button_query.iter() {
+            commands.entity(entity).despawn();
+        }
+
+        return;
+    }
+
+    let Ok(popup_button_container_id) = upload_progress_popup_button_container_query.single() else {
+        return;
+    };
+
+    let font = asset_server.load("embedded://font/JetBrainsMonoNL-ExtraLight.ttf");
+    let text_font = TextFont {
+        font: font.clone(),
+        font_size: 1.0, //Dummy value
+        ..default()
+    };
+
+    commands.entity(popup_button_container_id).with_child((
+        Node {
+            width: percent(100),
+            border: UiRect::all(px(2)),
+            border_radius: BorderRadius::all(px(10)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Button,
+        UploadCancelButton,
+        Hovered::default(),
+        TabIndex::default(),
+        BorderColor::all(crate::io::bevy_abstraction::Color::White),
+        BackgroundColor(crate::io::bevy_abstraction::Color::Black.into()),
+        children![(
+            Text::new("Cancel"),
+            text_font.clone(),
+            LineHeight::RelativeToFont(1.1),
+            TextColor(crate::io::bevy_abstraction::Color::White.into()),
+            ResizableText::Paragraph,
+        )],
+        observe(
+            |_: On<Activate>,
+
+             mut play_sound_effect: MessageWriter<PlaySoundEffect>| {
+                set_working_data_and_ignore_running_calls(SteamWorkshopUploadWorkingData::Cancelled);
+
+                play_sound_effect.write(PlaySoundEffect {
+                    sound_effect: audio::UI_SELECT_EFFECT,
+                });
+            },
+        ),
+    ));
+}
+
+/// Returns a button which closes the upload progress popup and resets the upload state
+fn upload_progress_popup_close_button(text_font: TextFont) -> impl Bundle {
+    (
+        Node {
+            width: percent(100),
+            border: UiRect::all(px(2)),
+            border_radius: BorderRadius::all(px(10)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Button,
+        Hovered::default(),
+        TabIndex::default(),
+        BorderColor::all(crate::io::bevy_abstraction::Color::White),
+        BackgroundColor(crate::io::bevy_abstraction::Color::Black.into()),
+        children![(
+            Text::new("Close"),
+            text_font,
+            LineHeight::RelativeToFont(1.1),
+            TextColor(crate::io::bevy_abstraction::Color::White.into()),
+            ResizableText::Paragraph,
+        )],
+        observe(
+            |_: On<Activate>,
+
+             commands: Commands,
+
+             upload_progress_popup_elements: Query<Entity, With<UploadProgressPopup>>,
+
+             mut play_sound_effect: MessageWriter<PlaySoundEffect>| {
+                set_working_data_and_ignore_running_calls(SteamWorkshopUploadWorkingData::Idle);
+
+                play_sound_effect.write(PlaySoundEffect {
+                    sound_effect: audio::UI_SELECT_EFFECT,
+                });
+
+                close_upload_progress_popup(commands, upload_progress_popup_elements);
+            },
+        ),
+    )
+}
+
 fn process_update_progress_status(
     mut upload_progress_popup_content_text_query: Query<(&mut Text, &mut TextColor), With<UploadProgressPopupContent>>,
 
     update_watch_handle: Option<Res<UpdateWatchHandleWrapper>>,
     previous_update_status: Option<ResMut<PreviousUpdateStatus>>,
+    upload_operation: Option<ResMut<UploadOperation>>,
 ) -> Result<(), Box<dyn Error>> {
     'update_check: {
         if let Some(update_watch_handle) = update_watch_handle &&
@@ -662,6 +1018,14 @@ fn process_update_progress_status(
             };
             previous_update_status.0 = current_update_status;
 
+            //The timeout of the submission is restarted if the upload makes progress
+            if let Some(mut upload_operation) = upload_operation &&
+                    matches!(upload_operation.state, UploadOperationState::Running { .. }) {
+                upload_operation.state = UploadOperationState::Running {
+                    last_progress: Instant::now(),
+                };
+            }
+
             let (status, progress, max_progress) = current_update_status;
 
             let update_status_text = match status {
@@ -692,7 +1056,7 @@ fn process_update_progress_status(
                     "Committing changes...".to_string()
                 }
             };
-            info!("Workshop Update Item status: {update_status_text}");
+            log_info!("Workshop Update Item status: {update_status_text}");
 
             let Ok((mut popup_text, _)) = upload_progress_popup_content_text_query.single_mut() else {
                 return Err(Box::new(GameError::new("Invalid popup status")));
@@ -707,6 +1071,7 @@ fn process_update_progress_status(
     Ok(())
 }
 
+#[expect(clippy::too_many_arguments)]
 fn update_text_input_fields(
     focus: Res<InputFocus>,
     time: Res<Time>,
@@ -720,6 +1085,7 @@ fn update_text_input_fields(
     mut text_query: Query<&mut Text>,
     mut text_color_query: Query<&mut TextColor>,
 
+    keyboard_input: Res<ButtonInput<KeyCode>>,
     mut keyboard_event: MessageReader<KeyboardInput>,
 ) {
     let Some(entity_id) = focus.0 else {
@@ -731,7 +1097,7 @@ fn update_text_input_fields(
     };
 
     let Some(text_entity_id) = children.first() else {
-        warn!("Invalid text input field");
+        log_warn!("Invalid text input field");
         return;
     };
 
@@ -742,7 +1108,7 @@ fn update_text_input_fields(
     let show_cursor = (time.elapsed_secs_wrapped() * 2.0) as u32 & 1 == 1;
     if let Ok(children) = children_query.get(*text_entity_id) {
         let Some(text_span_entity_id) = children.first() else {
-            warn!("Invalid text input field");
+            log_warn!("Invalid text input field");
             return;
         };
 
@@ -781,14 +1147,46 @@ fn update_text_input_fields(
             continue;
         }
 
-        //TODO check for control key
+        let max_len = if is_level_pack_name {
+            MAX_LEVEL_PACK_NAME_LEN
+        }else {
+            MAX_LEVEL_PACK_DESCRIPTION_LEN
+        };
+
+        if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+            if event.key_code == KeyCode::KeyV && !event.repeat &&
+                    let Some(pasted_text) = gui::clipboard::read_clipboard_text() {
+                let pasted_text = if is_level_pack_name {
+                    pasted_text.lines().next().unwrap_or_default().to_string()
+                }else {
+                    pasted_text.replace("\r\n", "\n")
+                };
+
+                for c in pasted_text.chars().
+                        filter(|c| !c.is_control() || *c == '\n') {
+                    if text.len() + c.len_utf8() > max_len {
+                        break;
+                    }
+
+                    text.push(c);
+                }
+            }
+
+            continue;
+        }
 
         if let Some(key) = &event.text {
-            if key == "\r" {
-                text.push('\n');
+            let key = if key == "\r" {
+                "\n"
             }else {
-                text.push_str(key);
+                key.as_str()
+            };
+
+            if text.len() + key.len() > max_len {
+                continue;
             }
+
+            text.push_str(key);
         }
     }
 }
@@ -836,27 +1234,27 @@ fn update_ui_styles(
 ) {
     for (checked, Hovered(hovered), children) in radio_and_checkbox_query.into_iter() {
         let Some(radio_or_checkbox_node_id) = children.get(1) else {
-            warn!("Invalid radio button or checkbox");
+            log_warn!("Invalid radio button or checkbox");
             continue;
         };
 
         let Some(radio_or_checkbox_inner_node_id) = children_query.get(*radio_or_checkbox_node_id).ok().and_then(|children| children.first()) else {
-            warn!("Invalid radio button or checkbox");
+            log_warn!("Invalid radio button or checkbox");
             continue;
         };
 
         let Ok(mut border_color) = border_color_query.get_mut(*radio_or_checkbox_inner_node_id) else {
-            warn!("Invalid radio button or checkbox");
+            log_warn!("Invalid radio button or checkbox");
             continue;
         };
 
         let Some(radio_or_checkbox_inner_inner_node_id) = children_query.get(*radio_or_checkbox_inner_node_id).ok().and_then(|children| children.first()) else {
-            warn!("Invalid radio button or checkbox");
+            log_warn!("Invalid radio button or checkbox");
             continue;
         };
 
         let Ok(mut background_color) = background_color_query.get_mut(*radio_or_checkbox_inner_inner_node_id) else {
-            warn!("Invalid radio button or checkbox");
+            log_warn!("Invalid radio button or checkbox");
             continue;
         };
 
@@ -876,27 +1274,27 @@ fn update_ui_styles(
     for radio_id in radio_or_checkbox_unticked.read() {
         if let Ok((Hovered(hovered), children)) = radio_or_checkbox_unticked_query.get(radio_id) {
             let Some(radio_or_checkbox_node_id) = children.get(1) else {
-                warn!("Invalid radio button or checkbox");
+                log_warn!("Invalid radio button or checkbox");
                 continue;
             };
 
             let Some(radio_or_checkbox_inner_node_id) = children_query.get(*radio_or_checkbox_node_id).ok().and_then(|children| children.first()) else {
-                warn!("Invalid radio button or checkbox");
+                log_warn!("Invalid radio button or checkbox");
                 continue;
             };
 
             let Ok(mut border_color) = border_color_query.get_mut(*radio_or_checkbox_inner_node_id) else {
-                warn!("Invalid radio button or checkbox");
+                log_warn!("Invalid radio button or checkbox");
                 continue;
             };
 
             let Some(radio_or_checkbox_inner_inner_node_id) = children_query.get(*radio_or_checkbox_inner_node_id).ok().and_then(|children| children.first()) else {
-                warn!("Invalid radio button or checkbox");
+                log_warn!("Invalid radio button or checkbox");
                 continue;
             };
 
             let Ok(mut background_color) = background_color_query.get_mut(*radio_or_checkbox_inner_inner_node_id) else {
-                warn!("Invalid radio button or checkbox");
+                log_warn!("Invalid radio button or checkbox");
                 continue;
             };
 
@@ -1153,9 +1551,8 @@ fn on_validate_and_start_upload(
 
         *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Waiting;
 
-        steam::crate_workshop_item(steam_client.clone(), |ret| {
-            *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::CreateItemResult(ret);
-        });
+        commands.insert_resource(UploadOperation::new(UploadStep::CreateItem));
+        start_create_workshop_item(&steam_client);
     }
 }
 
@@ -1187,6 +1584,30 @@ fn on_open_steam_workshop_upload_popup(
         ..default()
     };
 
+    let level_pack = game.game_state().editor_state().get_current_level_pack().unwrap();
+    let solver_validated_level_count = level_pack.solver_validated_level_count();
+    let solver_validation_note = if solver_validated_level_count > 0 {
+        format!(
+            "{} of {} levels were validated by the solver instead of a playthrough.",
+            solver_validated_level_count, level_pack.level_count(),
+        )
+    }else {
+        String::new()
+    };
+
+    //The difficulty tag is preselected with the estimate of the solver
+    let suggested_difficulty = game.game_state().editor_state().level_pack_difficulty();
+    if let Some(suggested_difficulty) = suggested_difficulty {
+        commands.insert_resource(DifficultyTag::from(suggested_difficulty));
+    }
+    let difficulty_tag_label = match suggested_difficulty {
+        Some(suggested_difficulty) => format!(
+            "Difficulty tag (Cannot be changed after upload, estimate: {}):",
+            suggested_difficulty.display_name(),
+        ),
+        None => "Difficulty tag (Cannot be changed after upload):".to_string(),
+    };
+
     commands.spawn((
         Node {
             width: percent(100),
@@ -1227,12 +1648,19 @@ fn on_open_steam_workshop_upload_popup(
                     TextLayout::new(Justify::Center, LineBreak::WordBoundary),
                     ResizableText::Heading,
                 ), (
-                    Text(format!("You are about to upload level pack \"{}\".", game.game_state().editor_state().get_current_level_pack().unwrap().id())),
+                    Text(format!("You are about to upload level pack \"{}\".", level_pack.id())),
                     text_font.clone(),
                     LineHeight::RelativeToFont(1.1),
                     TextColor(Color::BLACK),
                     TextLayout::new(Justify::Center, LineBreak::WordBoundary),
                     ResizableText::Paragraph,
+                ), (
+                    Text(solver_validation_note),
+                    bold_text_font.clone(),
+                    LineHeight::RelativeToFont(1.1),
+                    TextColor(SOLVER_VALIDATION_NOTE_COLOR),
+                    TextLayout::new(Justify::Center, LineBreak::WordBoundary),
+                    ResizableText::Paragraph,
                 ), (
                     Node {
                         min_height: percent(2),
@@ -1272,7 +1700,8 @@ fn on_open_steam_workshop_upload_popup(
                     BackgroundColor(Color::srgb_u8(120, 120, 120)),
                     ResizableNodeDimension::Height(1.2),
                     children![(
-                        Text("".to_string()),
+                        //Translated names are not uploaded, the untranslated name is used as default title
+                        Text(game.game_state().editor_state().get_current_level_pack().unwrap().name().to_string()),
                         text_font.clone(),
                         LineHeight::RelativeToFont(1.1),
                         TextColor(Color::BLACK),
@@ -1329,7 +1758,7 @@ fn on_open_steam_workshop_upload_popup(
                 ), (
                     two_column_layout(
                          children![(
-                            Text("Difficulty tag (Cannot be changed after upload):".to_string()),
+                            Text(difficulty_tag_label),
                             bold_text_font.clone(),
                             TextColor(Color::BLACK),
                             TextLayout::new(Justify::Left, LineBreak::WordBoundary),
@@ -1506,7 +1935,7 @@ fn create_level_pack_thumbnail(
     CONSOLE_STATE.lock().unwrap().swap_buffer_selection();
 
     let Some((level_width, level_height)) = dimensions else {
-        //TODO error -> Could not create screenshot
+        log_error!("Could not create the level pack thumbnail screenshot");
 
         return;
     };
@@ -1665,9 +2094,10 @@ fn on_close_steam_workshop_upload_popup(
 
     mut game: NonSendMut<Game>,
 ) {
-    *STEAM_WORKSHOP_UPLOAD_WORKING_DATA.lock().unwrap() = SteamWorkshopUploadWorkingData::Idle;
+    set_working_data_and_ignore_running_calls(SteamWorkshopUploadWorkingData::Idle);
     commands.remove_resource::<UpdateWatchHandleWrapper>();
     commands.remove_resource::<PreviousUpdateStatus>();
+    commands.remove_resource::<UploadOperation>();
 
     for entity in steam_workshop_upload_popup_elements.iter() {
         commands.entity(entity).despawn();
@@ -0,0 +1,35 @@
+use crate::ui::gui::steam_plugin::steam_workshop_upload_popup::*;
+
+#[test]
+fn schedule_retry_backoff() {
+    let mut upload_operation = UploadOperation::new(UploadStep::CreateItem);
+
+    assert_eq!(upload_operation.schedule_retry(&SteamError::Timeout), Some(Duration::from_secs(1)));
+    assert!(matches!(upload_operation.state, UploadOperationState::RetryScheduled { .. }));
+    assert_eq!(upload_operation.schedule_retry(&SteamError::Busy), Some(Duration::from_secs(2)));
+    assert_eq!(upload_operation.schedule_retry(&SteamError::NoConnection), Some(Duration::from_secs(4)));
+
+    //Retry cap
+    assert_eq!(upload_operation.schedule_retry(&SteamError::Timeout), None);
+    assert_eq!(upload_operation.failed_attempts, MAX_RETRY_COUNT);
+}
+
+#[test]
+fn schedule_retry_non_transient_error() {
+    let mut upload_operation = UploadOperation::new(UploadStep::CreateItem);
+
+    assert_eq!(upload_operation.schedule_retry(&SteamError::AccessDenied), None);
+    assert_eq!(upload_operation.failed_attempts, 0);
+    assert!(matches!(upload_operation.state, UploadOperationState::Running { .. }));
+}
+
+#[test]
+fn schedule_retry_submit_item_timeout() {
+    let mut upload_operation = UploadOperation::new(UploadStep::SubmitItem(PublishedFileId(42)));
+
+    //The submission might still be running
+    assert_eq!(upload_operation.schedule_retry(&SteamError::Timeout), None);
+    assert_eq!(upload_operation.failed_attempts, 0);
+
+    assert_eq!(upload_operation.schedule_retry(&SteamError::ServiceUnavailable), Some(Duration::from_secs(1)));
+}
@@ -1,3 +1,8 @@
+use std::fmt::Write as _;
+
+#[cfg(test)]
+mod tests;
+
 pub fn byte_count_to_string_with_binary_prefix(byte_count: u64) -> String {
     const BINARY_PREFIXES: [&str; 4] = ["", "Ki", "Mi", "Gi"];
 
@@ -15,3 +20,75 @@ pub fn byte_count_to_string_with_binary_prefix(byte_count: u64) -> String {
 
     format!("{:.2} {}B", byte_count_with_prefix, BINARY_PREFIXES[prefix_index])
 }
+
+const FNV1A_64_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV1A_64_PRIME: u64 = 0x100000001b3;
+
+/// 64-bit FNV-1a hash of the bytes
+///
+/// The hash must not change in other game versions, because it is stored in files (e.g. custom track ids and solver caches).
+pub fn fnv1a_64(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    fnv1a_64_extend(FNV1A_64_OFFSET_BASIS, bytes)
+}
+
+/// Continues the 64-bit FNV-1a hash with additional bytes
+pub fn fnv1a_64_extend(hash: u64, bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().
+            fold(hash, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV1A_64_PRIME))
+}
+
+/// Returns the value as JSON string literal (Including the quotes)
+pub fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            },
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+
+    out
+}
+
+/// Converts seconds since the Unix epoch to the (year, month, day) of the date (UTC)
+fn unix_time_to_date(secs: u64) -> (i64, i64, i64) {
+    //Converts days since 1970-01-01 to a civil date (Years start at March 1st to put the leap day at the end)
+    let days = (secs / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Formats seconds since the Unix epoch as "YYYY-MM-DD" (UTC)
+pub fn unix_time_to_date_string(secs: u64) -> String {
+    let (year, month, day) = unix_time_to_date(secs);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Formats seconds since the Unix epoch as "YYYY-MM-DD HH:MM:SS" (UTC)
+pub fn unix_time_to_date_time_string(secs: u64) -> String {
+    let secs_of_day = secs % 86400;
+
+    format!(
+        "{} {:02}:{:02}:{:02}",
+        unix_time_to_date_string(secs), secs_of_day / 3600, secs_of_day / 60 % 60, secs_of_day % 60,
+    )
+}
@@ -0,0 +1,30 @@
+use crate::utils::*;
+
+#[test]
+fn byte_count_formatting() {
+    assert_eq!(byte_count_to_string_with_binary_prefix(1023), "1023 B");
+    assert_eq!(byte_count_to_string_with_binary_prefix(1536), "1.50 KiB");
+}
+
+#[test]
+fn json_string_control_characters() {
+    assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    assert_eq!(json_string("\n\r\t"), "\"\\n\\r\\t\"");
+    assert_eq!(json_string("\0\u{1b}[0m\u{7f}"), "\"\\u0000\\u001b[0m\\u007f\"");
+}
+
+#[test]
+fn fnv1a_64_hash() {
+    assert_eq!(fnv1a_64([]), 0xcbf29ce484222325);
+    assert_eq!(fnv1a_64(*b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(fnv1a_64(*b"foobar"), 0x85944171f73967e8);
+    assert_eq!(fnv1a_64_extend(fnv1a_64(*b"foo"), *b"bar"), fnv1a_64(*b"foobar"));
+}
+
+#[test]
+fn unix_time_formatting() {
+    assert_eq!(unix_time_to_date_time_string(0), "1970-01-01 00:00:00");
+    assert_eq!(unix_time_to_date_time_string(951782400), "2000-02-29 00:00:00");
+    assert_eq!(unix_time_to_date_time_string(1700000000), "2023-11-14 22:13:20");
+    assert_eq!(unix_time_to_date_string(1700000000), "2023-11-14");
+}
@@ -0,0 +1,20 @@
+//! Usage of the game engine by external tools, these tests also run without feature `cli` or `gui`
+//! (`cargo test --test library`)
+
+use sokoterm::game::level::{Direction, LevelPack, MoveResult, PlayingLevel, Tile};
+
+const LEVEL_PACK: &str = "Name: Library\nLevels: 1\n\nw: 6, h: 3\n######\n#P@-x#\n######\n";
+
+#[test]
+fn play_level_of_level_pack() {
+    let level_pack = LevelPack::from_level_pack_data("library", "library.lvl", LEVEL_PACK, false).unwrap();
+    assert_eq!(level_pack.name(), "Library");
+    assert_eq!(level_pack.level_count(), 1);
+
+    let level = level_pack.levels()[0].level();
+    assert_eq!(level.get_tile(2, 1), Some(Tile::Box));
+
+    let mut playing_level = PlayingLevel::new(level, 100).unwrap();
+    assert!(matches!(playing_level.move_player(Direction::Right), MoveResult::Valid { has_won: false, .. }));
+    assert!(matches!(playing_level.move_player(Direction::Right), MoveResult::Valid { has_won: true, .. }));
+}
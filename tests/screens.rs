@@ -0,0 +1,125 @@
+//! End-to-end tests of screens with the headless console backend (Run with `cargo test --features headless`)
+
+use sokoterm::io::headless::TestHarness;
+
+const LEVEL_PACK_HARNESS: &str = "Name: Harness\nLevels: 1\n\nw: 5, h: 3\n#####\n#P@x#\n#####\n";
+const LEVEL_PACK_HARNESS_TWO_LEVELS: &str = "Name: Harness\nLevels: 2\n\nw: 5, h: 3\n#####\n#P@x#\n#####\n\nw: 5, h: 4\n#####\n#P@x#\n#   #\n#####\n";
+
+#[test]
+fn start_menu_navigation() {
+    let mut test_harness = TestHarness::new(&[]).unwrap();
+    assert!(test_harness.screen_contains("to start the game!"));
+
+    test_harness.run_script("a").unwrap();
+    assert!(test_harness.screen_contains("About SokoTerm:"));
+
+    test_harness.run_script("ESC F1").unwrap();
+    assert!(test_harness.screen_contains("Help menu"));
+
+    test_harness.run_script("ESC").unwrap();
+    assert!(test_harness.screen_contains("to start the game!"));
+}
+
+#[test]
+fn level_completion() {
+    let mut test_harness = TestHarness::new(&[("harness", LEVEL_PACK_HARNESS)]).unwrap();
+    test_harness.restore_snapshot("SokoTerm Snapshot\nScreen: in-game\nLevel Pack: harness\nLevel: 1\n").unwrap();
+    assert!(test_harness.screen_contains("Moves: 0000"));
+    assert!(!test_harness.screen_contains("Level completed!"));
+
+    test_harness.run_script("RIGHT WAIT:10").unwrap();
+    assert!(test_harness.screen_contains("Level completed!"));
+    assert!(test_harness.screen_contains("Moves: 0001"));
+}
+
+#[test]
+fn in_game_pause_menu() {
+    let mut test_harness = TestHarness::new(&[("harness", LEVEL_PACK_HARNESS)]).unwrap();
+    test_harness.restore_snapshot("SokoTerm Snapshot\nScreen: in-game\nLevel Pack: harness\nLevel: 1\n").unwrap();
+
+    test_harness.run_script("ESC").unwrap();
+    assert!(test_harness.screen_contains("Paused"));
+    assert!(test_harness.screen_contains("Quit to selection"));
+
+    test_harness.run_script("ESC").unwrap();
+    assert!(!test_harness.screen_contains("Paused"));
+
+    test_harness.run_script("ESC DOWN DOWN DOWN DOWN ENTER").unwrap();
+    assert!(test_harness.screen_contains("Selected level:"));
+}
+
+#[test]
+fn level_pack_editor_creation() {
+    let mut test_harness = TestHarness::new(&[]).unwrap();
+    test_harness.restore_snapshot("SokoTerm Snapshot\nScreen: select-level-pack-editor\n").unwrap();
+    assert!(test_harness.screen_contains("Create a level pack"));
+
+    test_harness.run_script("ENTER \"harness_pack\" ENTER WAIT:2").unwrap();
+    assert!(test_harness.save_game_folder().join("harness_pack.lvl.edit").exists());
+}
+
+#[test]
+fn level_pack_editor_translated_name_is_saved_on_tab() {
+    let mut test_harness = TestHarness::new(&[]).unwrap();
+    test_harness.restore_snapshot("SokoTerm Snapshot\nScreen: select-level-pack-editor\n").unwrap();
+    test_harness.run_script("ENTER \"harness_pack\" ENTER WAIT:2").unwrap();
+    test_harness.restore_snapshot("SokoTerm Snapshot\nScreen: level-pack-editor\nEditor Level Pack: harness_pack\n").unwrap();
+
+    //The pending name is saved before the name of the next language is edited
+    test_harness.run_script("n DELETE*12 \"translated\" TAB ESC").unwrap();
+
+    let level_pack_data = std::fs::read_to_string(test_harness.save_game_folder().join("harness_pack.lvl.edit")).unwrap();
+    assert!(level_pack_data.contains("Name[en]: translated\n"));
+}
+
+#[test]
+fn start_menu_exit_dialog_selection() {
+    let mut test_harness = TestHarness::new(&[]).unwrap();
+
+    test_harness.run_script("ESC").unwrap();
+    assert!(test_harness.screen_contains("Exit game?"));
+
+    test_harness.run_script("n").unwrap();
+    assert!(!test_harness.screen_contains("Exit game?"));
+    assert!(test_harness.screen_contains("to start the game!"));
+    assert!(!test_harness.update());
+
+    test_harness.run_script("ESC y").unwrap();
+    assert!(test_harness.update());
+}
+
+#[test]
+fn level_pack_editor_delete_dialog_selection() {
+    let mut test_harness = TestHarness::new(&[]).unwrap();
+    test_harness.restore_snapshot("SokoTerm Snapshot\nScreen: select-level-pack-editor\n").unwrap();
+    test_harness.run_script("ENTER \"harness_pack\" ENTER WAIT:2").unwrap();
+    test_harness.restore_snapshot("SokoTerm Snapshot\nScreen: select-level-pack-editor\n").unwrap();
+    let level_pack_file = test_harness.save_game_folder().join("harness_pack.lvl.edit");
+
+    test_harness.run_script("DELETE").unwrap();
+    assert!(test_harness.screen_contains("Do you really want to delete"));
+
+    test_harness.run_script("n").unwrap();
+    assert!(!test_harness.screen_contains("Do you really want to delete"));
+    assert!(level_pack_file.exists());
+
+    test_harness.run_script("DELETE y").unwrap();
+    assert!(!test_harness.screen_contains("Do you really want to delete"));
+    assert!(!level_pack_file.exists());
+}
+
+#[test]
+fn locked_level_from_preview_does_not_save_attempt() {
+    let mut test_harness = TestHarness::new(&[("harness", LEVEL_PACK_HARNESS_TWO_LEVELS)]).unwrap();
+    test_harness.restore_snapshot("SokoTerm Snapshot\nScreen: select-level\nLevel Pack: harness\nLevel: 2\n").unwrap();
+
+    test_harness.run_script("p ENTER").unwrap();
+    assert!(test_harness.screen_contains("Level 002 is locked!"));
+
+    test_harness.run_script("y DOWN").unwrap();
+    assert!(test_harness.screen_contains("Moves: 0001"));
+
+    test_harness.run_script("ESC DOWN DOWN DOWN DOWN ENTER").unwrap();
+    assert!(test_harness.screen_contains("Selected level:"));
+    assert!(!test_harness.save_game_folder().join("harness.lvl.attempt.sav").exists());
+}